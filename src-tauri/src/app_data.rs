@@ -0,0 +1,382 @@
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::jobs::Job;
+use crate::metadata_store::MetadataStore;
+
+/// 백업 형식 버전 (이전 버전에서 읽을 수 없게 바뀌면 올림)
+const FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_NAME: &str = "manifest.json";
+
+/// 백업에 넣지 않는 설정 폴더의 JSON 파일 (실행 중 상태)
+const SETTINGS_EXCLUDED: &[&str] = &["queue_state.json"];
+
+/// 라이브러리에 포함되는 폴더 (app_data 기준)
+const LIBRARY_DIRS: &[&str] = &["manual_order"];
+
+const DB_ENTRY: &str = "metadata.db";
+
+/// 가져온 DB는 열려 있는 DB를 바꿀 수 없으므로 다음 실행 때 교체
+const RESTORE_DB_NAME: &str = "metadata.db.restore";
+
+/// 백업 항목
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataSection {
+    /// 설정, 즐겨찾기, 창/레이아웃 상태, 원격 소스/업로드 프로필 (비밀 키는 OS 키체인에 있어 제외)
+    Settings,
+    /// 메타데이터 DB (별점/픽, 스마트 앨범, 검토 메모, 버전 연결)와 사용자 지정 순서
+    Library,
+    Thumbnails,
+}
+
+impl DataSection {
+    fn prefix(self) -> &'static str {
+        match self {
+            DataSection::Settings => "settings",
+            DataSection::Library => "library",
+            DataSection::Thumbnails => "thumbnails",
+        }
+    }
+
+    fn from_prefix(prefix: &str) -> Option<Self> {
+        [DataSection::Settings, DataSection::Library, DataSection::Thumbnails]
+            .into_iter()
+            .find(|section| section.prefix() == prefix)
+    }
+}
+
+/// 백업 파일 정보 (zip 안의 manifest.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format_version: u32,
+    pub app_version: String,
+    pub created_at: i64,
+    pub sections: Vec<DataSection>,
+}
+
+/// 내보내기 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportSummary {
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// 가져오기 결과 (restart_required면 앱을 다시 시작해야 적용됨)
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportSummary {
+    pub restored: Vec<DataSection>,
+    pub files: usize,
+    pub restart_required: bool,
+}
+
+fn app_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))
+}
+
+// 폴더 아래 모든 파일 (zip 항목 이름, 원본 경로)
+fn collect_dir(dir: &Path, prefix: &str, entries: &mut Vec<(String, PathBuf)>) {
+    for entry in WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(dir) else {
+            continue;
+        };
+        // zip 항목 이름은 항상 '/' 구분자
+        let name = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        entries.push((format!("{}/{}", prefix, name), entry.path().to_path_buf()));
+    }
+}
+
+fn collect_settings(data_dir: &Path, entries: &mut Vec<(String, PathBuf)>) {
+    let Ok(dir) = fs::read_dir(data_dir) else {
+        return;
+    };
+    for path in dir.filter_map(|e| e.ok()).map(|e| e.path()) {
+        let Some(name) = path.file_name().map(|n| n.to_string_lossy().to_string()) else {
+            continue;
+        };
+        if path.is_file() && name.ends_with(".json") && !SETTINGS_EXCLUDED.contains(&name.as_str()) {
+            entries.push((format!("{}/{}", DataSection::Settings.prefix(), name), path));
+        }
+    }
+}
+
+// 열려 있는 DB의 일관된 복사본 (WAL 내용 포함)
+fn snapshot_db(store: &MetadataStore, dest: &Path) -> Result<(), String> {
+    let _ = fs::remove_file(dest);
+    store.with_conn(|conn| conn.execute("VACUUM INTO ?1", params![dest.to_string_lossy()]))?;
+    Ok(())
+}
+
+/// 선택한 항목을 zip 파일 하나로 내보내기 (다른 컴퓨터로 옮길 때)
+pub fn export_app_data(
+    app: &AppHandle,
+    store: &MetadataStore,
+    target: &Path,
+    sections: &[DataSection],
+    job: &Job,
+) -> Result<ExportSummary, String> {
+    if sections.is_empty() {
+        return Err("No sections selected".to_string());
+    }
+    let data_dir = app_data_dir(app)?;
+
+    let mut entries = Vec::new();
+    let mut db_snapshot = None;
+    for section in sections {
+        match section {
+            DataSection::Settings => collect_settings(&data_dir, &mut entries),
+            DataSection::Library => {
+                let snapshot = data_dir.join("metadata.db.export");
+                snapshot_db(store, &snapshot)?;
+                entries.push((format!("{}/{}", section.prefix(), DB_ENTRY), snapshot.clone()));
+                db_snapshot = Some(snapshot);
+                for dir in LIBRARY_DIRS {
+                    collect_dir(&data_dir.join(dir), &format!("{}/{}", section.prefix(), dir), &mut entries);
+                }
+            }
+            DataSection::Thumbnails => {
+                collect_dir(&crate::thumbnail::get_cache_dir(app)?, section.prefix(), &mut entries);
+            }
+        }
+    }
+
+    let partial = target.with_extension("zip.part");
+    let result = write_archive(&partial, sections, &entries, job);
+    if let Some(snapshot) = db_snapshot {
+        let _ = fs::remove_file(snapshot);
+    }
+
+    match result {
+        Ok(summary) if !job.is_cancelled() => {
+            fs::rename(&partial, target).map_err(|e| format!("Failed to save backup: {}", e))?;
+            Ok(summary)
+        }
+        Ok(_) => {
+            let _ = fs::remove_file(&partial);
+            Err(format!("{}: export", crate::error::CANCELLED))
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&partial);
+            Err(e)
+        }
+    }
+}
+
+fn write_archive(
+    path: &Path,
+    sections: &[DataSection],
+    entries: &[(String, PathBuf)],
+    job: &Job,
+) -> Result<ExportSummary, String> {
+    let file = File::create(path).map_err(|e| format!("Failed to create backup: {}", e))?;
+    let mut zip = ZipWriter::new(BufWriter::new(file));
+    let zip_err = |e: zip::result::ZipError| format!("Failed to write backup: {}", e);
+
+    let manifest = BackupManifest {
+        format_version: FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: chrono::Utc::now().timestamp(),
+        sections: sections.to_vec(),
+    };
+    let manifest_json = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+    zip.start_file(MANIFEST_NAME, SimpleFileOptions::default()).map_err(zip_err)?;
+    zip.write_all(&manifest_json).map_err(|e| format!("Failed to write backup: {}", e))?;
+
+    let mut summary = ExportSummary { files: 0, bytes: 0 };
+    for (i, (name, source)) in entries.iter().enumerate() {
+        if job.is_cancelled() {
+            break;
+        }
+        // 썸네일(WebP)은 이미 압축되어 있으므로 그대로 저장
+        let method = if name.starts_with(DataSection::Thumbnails.prefix()) {
+            CompressionMethod::Stored
+        } else {
+            CompressionMethod::Deflated
+        };
+        let Ok(file) = File::open(source) else {
+            // 내보내는 중에 지워진 캐시 파일 등
+            continue;
+        };
+        zip.start_file(name.as_str(), SimpleFileOptions::default().compression_method(method).large_file(true))
+            .map_err(zip_err)?;
+        summary.bytes += io::copy(&mut BufReader::new(file), &mut zip)
+            .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+        summary.files += 1;
+        job.set_progress(i + 1, entries.len());
+    }
+
+    zip.finish().map_err(zip_err)?;
+    Ok(summary)
+}
+
+/// 백업 파일 정보 읽기 (가져오기 전에 포함 항목 확인)
+pub fn read_manifest(source: &Path) -> Result<BackupManifest, String> {
+    let file = File::open(source).map_err(|e| format!("Failed to open backup: {}", e))?;
+    let mut archive = ZipArchive::new(BufReader::new(file)).map_err(|e| format!("Invalid backup file: {}", e))?;
+    manifest_of(&mut archive)
+}
+
+fn manifest_of<R: io::Read + io::Seek>(archive: &mut ZipArchive<R>) -> Result<BackupManifest, String> {
+    let mut content = String::new();
+    archive
+        .by_name(MANIFEST_NAME)
+        .map_err(|_| "Not a PixEngine backup (manifest missing)".to_string())?
+        .read_to_string(&mut content)
+        .map_err(|e| format!("Failed to read backup manifest: {}", e))?;
+    let manifest: BackupManifest =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid backup manifest: {}", e))?;
+
+    if manifest.format_version > FORMAT_VERSION {
+        return Err(format!(
+            "Backup was created by a newer version of PixEngine ({}), please update first",
+            manifest.app_version
+        ));
+    }
+    Ok(manifest)
+}
+
+// zip 항목 이름 → (항목, app_data 아래 상대 경로), 폴더 밖을 가리키는 이름은 거부
+fn restore_target(name: &str) -> Option<(DataSection, PathBuf)> {
+    let (prefix, rest) = name.split_once('/')?;
+    let section = DataSection::from_prefix(prefix)?;
+    let relative = Path::new(rest);
+    if rest.is_empty() || !relative.components().all(|c| matches!(c, Component::Normal(_))) {
+        return None;
+    }
+    Some((section, relative.to_path_buf()))
+}
+
+// 임시 파일에 풀고 교체 (중간에 실패해도 기존 파일 유지)
+fn extract_to(reader: &mut impl Read, dest: &Path) -> Result<(), String> {
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let mut partial = dest.as_os_str().to_owned();
+    partial.push(".part");
+    let partial = PathBuf::from(partial);
+
+    let result = File::create(&partial)
+        .and_then(|file| io::copy(reader, &mut BufWriter::new(file)))
+        .and_then(|_| fs::rename(&partial, dest));
+    result.map_err(|e| {
+        let _ = fs::remove_file(&partial);
+        format!("Failed to restore {}: {}", dest.display(), e)
+    })
+}
+
+/// 백업에서 선택한 항목 복원 (sections가 없으면 백업에 있는 모든 항목)
+/// 메타데이터 DB는 다음 실행 때 교체
+pub fn import_app_data(
+    app: &AppHandle,
+    source: &Path,
+    sections: Option<&[DataSection]>,
+    job: &Job,
+) -> Result<ImportSummary, String> {
+    let file = File::open(source).map_err(|e| format!("Failed to open backup: {}", e))?;
+    let mut archive = ZipArchive::new(BufReader::new(file)).map_err(|e| format!("Invalid backup file: {}", e))?;
+    let manifest = manifest_of(&mut archive)?;
+
+    let selected: Vec<DataSection> = manifest
+        .sections
+        .iter()
+        .copied()
+        .filter(|section| sections.is_none_or(|wanted| wanted.contains(section)))
+        .collect();
+    if selected.is_empty() {
+        return Err("Backup does not contain the selected sections".to_string());
+    }
+
+    let data_dir = app_data_dir(app)?;
+    let cache_dir = crate::thumbnail::get_cache_dir(app)?;
+
+    let total = archive.len();
+    let mut files = 0;
+    for i in 0..total {
+        if job.is_cancelled() {
+            break;
+        }
+        let mut entry = archive.by_index(i).map_err(|e| format!("Failed to read backup: {}", e))?;
+        if entry.is_dir() {
+            continue;
+        }
+        let Some((section, relative)) = restore_target(entry.name()) else {
+            continue;
+        };
+        if !selected.contains(&section) {
+            continue;
+        }
+
+        let dest = match section {
+            DataSection::Library if relative == Path::new(DB_ENTRY) => data_dir.join(RESTORE_DB_NAME),
+            DataSection::Settings | DataSection::Library => data_dir.join(&relative),
+            DataSection::Thumbnails => cache_dir.join(&relative),
+        };
+        extract_to(&mut entry, &dest)?;
+        files += 1;
+        job.set_progress(i + 1, total);
+    }
+
+    Ok(ImportSummary {
+        restored: selected,
+        files,
+        restart_required: files > 0,
+    })
+}
+
+/// 가져온 메타데이터 DB로 교체 (DB를 열기 전 시작 시 호출)
+pub fn apply_pending_restore(db_path: &Path) {
+    let Some(restore) = db_path.parent().map(|dir| dir.join(RESTORE_DB_NAME)) else {
+        return;
+    };
+    if !restore.exists() {
+        return;
+    }
+
+    // 이전 DB의 WAL이 남아 있으면 새 DB에 잘못 적용되므로 함께 삭제
+    for suffix in ["-wal", "-shm"] {
+        let mut sidecar = db_path.as_os_str().to_owned();
+        sidecar.push(suffix);
+        let _ = fs::remove_file(PathBuf::from(sidecar));
+    }
+    match fs::rename(&restore, db_path) {
+        Ok(()) => tracing::info!("Restored metadata database from backup"),
+        Err(e) => tracing::warn!("Failed to restore metadata database: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restore_target() {
+        assert_eq!(
+            restore_target("settings/settings.json"),
+            Some((DataSection::Settings, PathBuf::from("settings.json")))
+        );
+        assert_eq!(
+            restore_target("library/manual_order/abc.json"),
+            Some((DataSection::Library, PathBuf::from("manual_order/abc.json")))
+        );
+        assert_eq!(restore_target("settings/../../evil.json"), None);
+        assert_eq!(restore_target("unknown/file.json"), None);
+        assert_eq!(restore_target("manifest.json"), None);
+    }
+}
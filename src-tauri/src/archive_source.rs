@@ -0,0 +1,341 @@
+use lazy_static::lazy_static;
+use lru::LruCache;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{BufReader, Read};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+use zip::ZipArchive;
+
+use crate::directory::{DirectoryEntry, DirectoryListing};
+use crate::spool_index::SpoolIndex;
+
+/// 압축 파일 안 경로 접두사 (archive://<압축 파일 경로>!/<안쪽 경로>)
+pub const ARCHIVE_SCHEME: &str = "archive://";
+
+/// 압축 파일 경로와 안쪽 경로 구분자
+const ENTRY_SEPARATOR: &str = "!/";
+
+/// 꺼낸 파일 스풀 최대 크기 (넘으면 오래 쓰지 않은 파일부터 삭제)
+const SPOOL_BUDGET_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+/// 꺼낼 수 있는 항목 최대 크기 (압축 폭탄 방지, 선언 크기와 실제로 읽는 양 모두 제한)
+const MAX_ENTRY_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// 항목 번호 목록을 기억해 둘 압축 파일 수
+const ENTRY_INDEX_CACHE_SIZE: usize = 8;
+
+/// 폴더처럼 열 수 있는 압축 파일 확장자
+const ARCHIVE_EXTENSIONS: &[&str] = &["zip"];
+
+/// 파싱된 압축 파일 안 경로
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivePath {
+    pub archive: PathBuf,
+    /// 압축 파일 안 경로 ('/' 구분, 앞뒤 '/' 없음, 최상위는 빈 문자열)
+    pub inner: String,
+}
+
+impl ArchivePath {
+    pub fn parse(url: &str) -> Result<Self, String> {
+        let rest = url
+            .strip_prefix(ARCHIVE_SCHEME)
+            .ok_or_else(|| format!("Invalid archive path: {}", url))?;
+        let (archive, inner) = rest.split_once(ENTRY_SEPARATOR).unwrap_or((rest, ""));
+        if archive.is_empty() {
+            return Err(format!("Invalid archive path: {}", url));
+        }
+        Ok(Self {
+            archive: PathBuf::from(archive),
+            inner: inner.trim_matches('/').to_string(),
+        })
+    }
+
+    pub fn join(&self, name: &str) -> Self {
+        let inner = if self.inner.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}/{}", self.inner, name)
+        };
+        Self {
+            archive: self.archive.clone(),
+            inner,
+        }
+    }
+
+    pub fn file_name(&self) -> &str {
+        self.inner.rsplit('/').next().unwrap_or_default()
+    }
+
+    pub fn to_url(&self) -> String {
+        format!("{}{}{}{}", ARCHIVE_SCHEME, self.archive.to_string_lossy(), ENTRY_SEPARATOR, self.inner)
+    }
+}
+
+pub fn is_archive_path(path: &str) -> bool {
+    path.starts_with(ARCHIVE_SCHEME)
+}
+
+/// 폴더처럼 열 수 있는 압축 파일인지 (확장자 기준)
+pub fn is_archive_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ARCHIVE_EXTENSIONS.contains(&ext.to_string_lossy().to_lowercase().as_str()))
+}
+
+/// 압축 파일의 최상위 가상 폴더 경로
+pub fn root_url(archive: &str) -> String {
+    ArchivePath {
+        archive: PathBuf::from(archive),
+        inner: String::new(),
+    }
+    .to_url()
+}
+
+/// 폴더 목록의 압축 파일을 가상 폴더로 표시 (경로는 archive://)
+pub fn expose_archives(listing: &mut DirectoryListing) {
+    for entry in &mut listing.entries {
+        if !entry.is_dir && !entry.is_cloud_placeholder && is_archive_file(Path::new(&entry.name)) {
+            entry.is_dir = true;
+            entry.path = root_url(&entry.path);
+            entry.kind = None;
+        }
+    }
+}
+
+fn open(archive: &Path) -> Result<ZipArchive<BufReader<File>>, String> {
+    let file = File::open(crate::fs_path::to_fs_path(&archive.to_string_lossy()))
+        .map_err(|e| format!("Failed to open archive: {}", e))?;
+    ZipArchive::new(BufReader::new(file)).map_err(|e| format!("Failed to read archive: {}", e))
+}
+
+// 압축 파일 안 경로 → 항목 번호 (이름의 '\\'는 목록처럼 '/'로 바꿔 저장, by_name으로는 찾을 수 없음)
+// 같은 이름이 여러 번 있으면 앞의 항목
+fn build_entry_index(archive: &mut ZipArchive<BufReader<File>>) -> HashMap<String, usize> {
+    let mut entries = HashMap::new();
+    for index in 0..archive.len() {
+        let Ok(entry) = archive.by_index_raw(index) else {
+            continue;
+        };
+        if !entry.is_dir() {
+            let name = entry.name().replace('\\', "/").trim_end_matches('/').to_string();
+            entries.entry(name).or_insert(index);
+        }
+    }
+    entries
+}
+
+// 압축 파일의 항목 번호 목록 (압축 파일 경로, 수정 시간, 크기가 같으면 전에 만든 목록 사용)
+fn entry_index(
+    archive: &mut ZipArchive<BufReader<File>>,
+    key: (PathBuf, SystemTime, u64),
+) -> Arc<HashMap<String, usize>> {
+    if let Some(entries) = ENTRY_INDEX.lock().unwrap_or_else(|e| e.into_inner()).get(&key) {
+        return Arc::clone(entries);
+    }
+    let entries = Arc::new(build_entry_index(archive));
+    ENTRY_INDEX
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .put(key, Arc::clone(&entries));
+    entries
+}
+
+// macOS 압축 도구가 넣는 리소스 포크 폴더
+fn is_resource_fork(name: &str) -> bool {
+    name == "__MACOSX" || name.starts_with("__MACOSX/")
+}
+
+/// 압축 파일 안 폴더 읽기 (read_directory_contents와 같은 형태, 경로는 archive://)
+/// 폴더 항목이 따로 없는 압축 파일도 파일 경로에서 폴더를 만들어 표시
+pub fn read_directory(url: &str, skip: impl Fn(&str) -> bool) -> Result<DirectoryListing, String> {
+    let location = ArchivePath::parse(url)?;
+    let mut archive = open(&location.archive)?;
+    let prefix = if location.inner.is_empty() {
+        String::new()
+    } else {
+        format!("{}/", location.inner)
+    };
+
+    // 이름 → (폴더 여부, 크기)
+    let mut children: BTreeMap<String, (bool, Option<u64>)> = BTreeMap::new();
+    for index in 0..archive.len() {
+        let Ok(entry) = archive.by_index_raw(index) else {
+            continue;
+        };
+        let name = entry.name().replace('\\', "/");
+        if is_resource_fork(&name) {
+            continue;
+        }
+        let Some(rest) = name.strip_prefix(&prefix) else {
+            continue;
+        };
+        let rest = rest.trim_end_matches('/');
+        if rest.is_empty() {
+            continue;
+        }
+
+        match rest.split_once('/') {
+            Some((folder, _)) => {
+                children.insert(folder.to_string(), (true, None));
+            }
+            None if entry.is_dir() => {
+                children.insert(rest.to_string(), (true, None));
+            }
+            None => {
+                children.entry(rest.to_string()).or_insert((false, Some(entry.size())));
+            }
+        }
+    }
+
+    Ok(DirectoryListing {
+        entries: children
+            .into_iter()
+            .filter(|(name, _)| !skip(name))
+            .map(|(name, (is_dir, size))| {
+                let mut entry = DirectoryEntry::new(name.clone(), location.join(&name).to_url(), is_dir);
+                entry.size = size;
+                entry
+            })
+            .collect(),
+        ..DirectoryListing::default()
+    })
+}
+
+lazy_static! {
+    static ref SPOOL: Mutex<SpoolIndex> = Mutex::new(SpoolIndex::default());
+    static ref ENTRY_INDEX: Mutex<LruCache<(PathBuf, SystemTime, u64), Arc<HashMap<String, usize>>>> =
+        Mutex::new(LruCache::new(NonZeroUsize::new(ENTRY_INDEX_CACHE_SIZE).unwrap()));
+}
+
+/// 임시 파일 번호 (같은 항목을 동시에 꺼내도 임시 파일이 겹치지 않도록)
+static PARTIAL_SEQ: AtomicU64 = AtomicU64::new(0);
+
+// 스풀 디렉토리 (썸네일/뷰어용으로 꺼낸 압축 파일 항목)
+fn get_spool_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_cache_dir()
+        .map(|p| p.join("archive_spool"))
+        .map_err(|e| format!("Failed to get app cache dir: {}", e))
+}
+
+/// 압축 파일 항목을 스풀로 꺼내고 로컬 경로 반환 (이미 꺼냈으면 그대로)
+/// 꺼낸 파일의 수정 시간은 압축 파일의 수정 시간 (다시 꺼내도 썸네일 캐시 키 유지)
+pub fn spool(app: &AppHandle, url: &str) -> Result<PathBuf, String> {
+    let location = ArchivePath::parse(url)?;
+    let dir = get_spool_dir(app)?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create spool directory: {}", e))?;
+
+    let archive_metadata = fs::metadata(crate::fs_path::to_fs_path(&location.archive.to_string_lossy()))
+        .map_err(|e| format!("Failed to read archive: {}", e))?;
+    let archive_mtime = archive_metadata
+        .modified()
+        .map_err(|e| format!("Failed to read archive: {}", e))?;
+
+    // 경로 + 압축 파일 수정 시간/크기 해시 + 원래 파일명 (압축 파일이 바뀌면 새로 꺼냄, 확장자로 포맷 판별)
+    let modified = archive_mtime.duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_nanos());
+    let key = blake3::hash(format!("{}\n{}\n{}", url, modified, archive_metadata.len()).as_bytes()).to_hex();
+    let target = dir.join(format!("{}_{}", &key[..16], location.file_name()));
+
+    if let Ok(metadata) = fs::metadata(&target) {
+        SPOOL.lock().unwrap_or_else(|e| e.into_inner()).touch(&target, metadata.len());
+        return Ok(target);
+    }
+
+    // 임시 파일로 꺼낸 뒤 rename (중간에 실패해도 불완전한 파일을 쓰지 않음)
+    let seq = PARTIAL_SEQ.fetch_add(1, Ordering::Relaxed);
+    let partial = target.with_extension(format!("{}.partial", seq));
+    let result = (|| -> Result<u64, String> {
+        let mut archive = open(&location.archive)?;
+        let entries = entry_index(&mut archive, (location.archive.clone(), archive_mtime, archive_metadata.len()));
+        let index = *entries
+            .get(&location.inner)
+            .ok_or_else(|| format!("Failed to find {} in archive", location.inner))?;
+        let entry = archive
+            .by_index(index)
+            .map_err(|e| format!("Failed to read {} in archive: {}", location.inner, e))?;
+
+        // 선언 크기가 제한을 넘으면 거부, 실제 데이터는 선언 크기까지만 읽음
+        let declared = entry.size();
+        if declared > MAX_ENTRY_BYTES {
+            return Err(format!("Archive entry too large: {} ({} bytes)", location.inner, declared));
+        }
+        let mut file = File::create(&partial).map_err(|e| format!("Failed to create spool file: {}", e))?;
+        let size = std::io::copy(&mut entry.take(declared + 1), &mut file)
+            .map_err(|e| format!("Failed to extract {}: {}", location.inner, e))?;
+        if size > declared {
+            return Err(format!("Archive entry larger than declared: {}", location.inner));
+        }
+        file.set_modified(archive_mtime)
+            .map_err(|e| format!("Failed to set spool file time: {}", e))?;
+        Ok(size)
+    })()
+    .and_then(|size| {
+        fs::rename(&partial, &target).map_err(|e| format!("Failed to finish spool file: {}", e))?;
+        Ok(size)
+    });
+
+    let size = match result {
+        Ok(size) => size,
+        Err(e) => {
+            let _ = fs::remove_file(&partial);
+            return Err(e);
+        }
+    };
+
+    let evicted = {
+        let mut spool = SPOOL.lock().unwrap_or_else(|e| e.into_inner());
+        spool.touch(&target, size);
+        spool.evict(SPOOL_BUDGET_BYTES, &target)
+    };
+    for path in evicted {
+        let _ = fs::remove_file(path);
+    }
+    Ok(target)
+}
+
+/// 이전 실행에서 꺼낸 스풀 파일 정리 (사용 기록이 없으므로 모두 삭제)
+pub fn clean_spool(app: &AppHandle) {
+    if let Ok(dir) = get_spool_dir(app) {
+        let _ = fs::remove_dir_all(dir);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_path() {
+        let location = ArchivePath::parse("archive:///photos/delivery.zip!/day1/").unwrap();
+        assert_eq!(location.archive, PathBuf::from("/photos/delivery.zip"));
+        assert_eq!(location.inner, "day1");
+        assert_eq!(location.join("A001.jpg").to_url(), "archive:///photos/delivery.zip!/day1/A001.jpg");
+        assert_eq!(location.join("A001.jpg").file_name(), "A001.jpg");
+        assert_eq!(ArchivePath::parse("archive:///photos/delivery.zip").unwrap().inner, "");
+        assert!(ArchivePath::parse("/photos/delivery.zip").is_err());
+        assert!(is_archive_file(Path::new("delivery.ZIP")));
+    }
+
+    #[test]
+    fn test_find_backslash_entry() {
+        use std::io::Write;
+        use zip::write::SimpleFileOptions;
+
+        let path = std::env::temp_dir().join(format!("pixengine-archive-{}.zip", std::process::id()));
+        {
+            let mut writer = zip::ZipWriter::new(File::create(&path).unwrap());
+            writer.start_file("day1\\A001.jpg", SimpleFileOptions::default()).unwrap();
+            writer.write_all(b"jpeg").unwrap();
+            writer.finish().unwrap();
+        }
+
+        let mut archive = open(&path).unwrap();
+        let entries = build_entry_index(&mut archive);
+        let _ = fs::remove_file(&path);
+        assert_eq!(entries.get("day1/A001.jpg"), Some(&0));
+    }
+}
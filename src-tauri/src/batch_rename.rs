@@ -0,0 +1,199 @@
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::filename_template::{self, read_capture_info, TemplateContext};
+
+/// 되돌리기 기록 최대 보관 개수
+const MAX_UNDO_HISTORY: usize = 20;
+
+/// 되돌리기 기록 (배치별 (새 경로, 원래 경로) 목록, 가장 최근이 뒤)
+static UNDO_HISTORY: Mutex<Vec<Vec<(PathBuf, PathBuf)>>> = Mutex::new(Vec::new());
+
+/// 이름 변경 계획 항목 (미리보기/결과)
+#[derive(Debug, Clone, Serialize)]
+pub struct RenameEntry {
+    pub old_path: String,
+    pub new_path: String,
+    pub conflict: Option<String>, // 충돌 사유 (없으면 적용 가능)
+}
+
+/// 경로 비교 키 (Windows는 대소문자 무시)
+fn path_key(path: &Path) -> String {
+    let key = path.to_string_lossy().to_string();
+    if cfg!(target_os = "windows") {
+        key.to_lowercase()
+    } else {
+        key
+    }
+}
+
+/// 두 경로가 같은 파일인지 (대소문자를 구분하지 않는 볼륨에서 대소문자만 다른 이름)
+#[cfg(unix)]
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    match (fs::metadata(a), fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+/// 두 경로가 같은 파일인지 (canonicalize가 실제 이름으로 바꿔 줌)
+#[cfg(not(unix))]
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => path_key(&a) == path_key(&b),
+        _ => false,
+    }
+}
+
+/// 이름 변경 계획 생성 (충돌 검사 포함, 파일은 변경하지 않음)
+pub fn preview(paths: &[String], template: &str, start_sequence: usize) -> Result<Vec<RenameEntry>, String> {
+    let sources: HashSet<String> = paths.iter().map(|p| path_key(Path::new(p))).collect();
+    let mut plan = Vec::with_capacity(paths.len());
+
+    for (index, path) in paths.iter().enumerate() {
+        let source = Path::new(path);
+        if !source.is_file() {
+            plan.push(RenameEntry {
+                old_path: path.clone(),
+                new_path: path.clone(),
+                conflict: Some("Source file does not exist".to_string()),
+            });
+            continue;
+        }
+
+        let (date_taken, camera) = read_capture_info(source);
+        let file_name = filename_template::expand(template, &TemplateContext {
+            source,
+            date_taken,
+            camera: camera.as_deref(),
+            sequence: start_sequence + index,
+        })?;
+
+        let new_path = source.with_file_name(file_name);
+        // 대상 파일이 이번 배치에서 함께 이름이 바뀌는 파일이거나 원본 자신이면 충돌 아님 (대소문자만 바꾸는 경우)
+        let conflict = (new_path.exists()
+            && !sources.contains(&path_key(&new_path))
+            && path_key(&new_path) != path_key(source)
+            && !is_same_file(&new_path, source))
+            .then(|| "Target already exists".to_string());
+
+        plan.push(RenameEntry {
+            old_path: path.clone(),
+            new_path: new_path.to_string_lossy().to_string(),
+            conflict,
+        });
+    }
+
+    // 배치 내 대상 이름 중복
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for entry in &plan {
+        *counts.entry(path_key(Path::new(&entry.new_path))).or_default() += 1;
+    }
+    for entry in plan.iter_mut() {
+        if entry.conflict.is_none() && counts[&path_key(Path::new(&entry.new_path))] > 1 {
+            entry.conflict = Some("Duplicate target name".to_string());
+        }
+    }
+
+    Ok(plan)
+}
+
+/// 이름 변경 목록 적용 (임시 이름을 거쳐 A->B, B->A 같은 교차 변경 지원)
+/// 실패 시 이미 바뀐 파일은 원래 이름으로 복구
+fn apply_renames(renames: &[(PathBuf, PathBuf)]) -> Result<(), String> {
+    let mut staged: Vec<(PathBuf, &PathBuf, &PathBuf)> = Vec::with_capacity(renames.len());
+
+    for (index, (from, to)) in renames.iter().enumerate() {
+        let temp = from.with_file_name(format!(".pixengine-rename-{}-{}", std::process::id(), index));
+        if let Err(e) = fs::rename(from, &temp) {
+            for (temp, from, _) in staged.iter().rev() {
+                let _ = fs::rename(temp, from);
+            }
+            return Err(format!("Failed to rename {}: {}", from.display(), e));
+        }
+        staged.push((temp, from, to));
+    }
+
+    for (done, (temp, from, to)) in staged.iter().enumerate() {
+        if let Err(e) = fs::rename(temp, to) {
+            for (_, from, to) in staged[..done].iter().rev() {
+                let _ = fs::rename(to, from);
+            }
+            for (temp, from, _) in staged[done..].iter() {
+                let _ = fs::rename(temp, from);
+            }
+            return Err(format!("Failed to rename {}: {}", from.display(), e));
+        }
+    }
+
+    Ok(())
+}
+
+/// 템플릿으로 일괄 이름 변경 (충돌이 하나라도 있으면 아무것도 바꾸지 않음)
+pub fn batch_rename(paths: &[String], template: &str, start_sequence: usize) -> Result<Vec<RenameEntry>, String> {
+    let plan = preview(paths, template, start_sequence)?;
+
+    if let Some(entry) = plan.iter().find(|e| e.conflict.is_some()) {
+        return Err(format!(
+            "Rename conflict: {} ({})",
+            entry.new_path,
+            entry.conflict.as_deref().unwrap_or_default()
+        ));
+    }
+
+    let renames: Vec<(PathBuf, PathBuf)> = plan
+        .iter()
+        .filter(|e| e.old_path != e.new_path)
+        .map(|e| (PathBuf::from(&e.old_path), PathBuf::from(&e.new_path)))
+        .collect();
+
+    apply_renames(&renames)?;
+
+    if !renames.is_empty() {
+        let mut history = UNDO_HISTORY.lock().map_err(|_| "Rename history lock poisoned".to_string())?;
+        history.push(renames.into_iter().map(|(from, to)| (to, from)).collect());
+        if history.len() > MAX_UNDO_HISTORY {
+            history.remove(0);
+        }
+    }
+
+    Ok(plan)
+}
+
+/// 마지막 일괄 이름 변경 되돌리기
+pub fn undo_last() -> Result<Vec<RenameEntry>, String> {
+    let mut history = UNDO_HISTORY.lock().map_err(|_| "Rename history lock poisoned".to_string())?;
+    let renames = history.pop().ok_or_else(|| "Nothing to undo".to_string())?;
+
+    // 그 사이 원래 이름에 다른 파일이 생겼으면 되돌리지 않음 (대소문자만 바꾼 파일 자신은 제외)
+    let sources: HashSet<String> = renames.iter().map(|(from, _)| path_key(from)).collect();
+    let taken = |to: &PathBuf| {
+        to.exists()
+            && !sources.contains(&path_key(to))
+            && !renames.iter().any(|(from, _)| is_same_file(from, to))
+    };
+    if let Some((_, to)) = renames.iter().find(|(_, to)| taken(to)) {
+        let message = format!("Cannot undo rename, file already exists: {}", to.display());
+        history.push(renames);
+        return Err(message);
+    }
+
+    if let Err(e) = apply_renames(&renames) {
+        history.push(renames);
+        return Err(e);
+    }
+
+    Ok(renames
+        .iter()
+        .map(|(from, to)| RenameEntry {
+            old_path: from.to_string_lossy().to_string(),
+            new_path: to.to_string_lossy().to_string(),
+            conflict: None,
+        })
+        .collect())
+}
@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::metadata_store::{ImageRecord, MetadataStore};
+use crate::smart_album::{self, AlbumRule, MatchMode, RuleSet};
+
+/// 초점거리 구간 경계 (mm, 마지막 구간은 끝이 없음)
+const FOCAL_LENGTH_EDGES: [f64; 9] = [0.0, 16.0, 24.0, 35.0, 50.0, 70.0, 105.0, 200.0, 400.0];
+
+/// 통계 범위
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StatisticsScope {
+    /// 인덱싱된 모든 이미지
+    All,
+    Folder { path: String, include_subfolders: bool },
+    Selection { id: u64 },
+    SmartAlbum { id: i64 },
+}
+
+/// 이름별 수 (많은 순)
+#[derive(Debug, Clone, Serialize)]
+pub struct NamedCount {
+    pub name: String,
+    pub count: usize,
+}
+
+/// 값별 수 (값 오름차순)
+#[derive(Debug, Clone, Serialize)]
+pub struct ValueCount<T> {
+    pub value: T,
+    pub count: usize,
+}
+
+/// 초점거리 구간 (min 포함, max 미포함, max가 없으면 끝까지)
+#[derive(Debug, Clone, Serialize)]
+pub struct FocalLengthBin {
+    pub min: f64,
+    pub max: Option<f64>,
+    pub count: usize,
+}
+
+/// 촬영 통계 (get_capture_statistics 응답)
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureStatistics {
+    pub total: usize,
+    pub cameras: Vec<NamedCount>,
+    pub lenses: Vec<NamedCount>,
+    pub focal_lengths: Vec<FocalLengthBin>,
+    pub isos: Vec<ValueCount<u32>>,
+    /// 조리개 값 (소수 첫째 자리로 반올림)
+    pub apertures: Vec<ValueCount<f64>>,
+    /// 월별 촬영 수 ("YYYY-MM" 오름차순)
+    pub months: Vec<NamedCount>,
+    /// 카메라 정보가 없는 이미지 수 (스캔, 편집본 등)
+    pub without_exif: usize,
+}
+
+/// 레코드를 하나씩 받아 집계
+#[derive(Default)]
+struct Accumulator {
+    total: usize,
+    cameras: HashMap<String, usize>,
+    lenses: HashMap<String, usize>,
+    focal_lengths: [usize; FOCAL_LENGTH_EDGES.len()],
+    isos: BTreeMap<u32, usize>,
+    apertures: BTreeMap<i64, usize>,
+    months: BTreeMap<String, usize>,
+    without_exif: usize,
+}
+
+// 모델명에 제조사가 이미 들어 있으면 모델명만 ("Canon" + "Canon EOS R5" → "Canon EOS R5")
+fn camera_name(make: Option<&str>, model: Option<&str>) -> Option<String> {
+    let make = make.map(str::trim).filter(|make| !make.is_empty());
+    let model = model.map(str::trim).filter(|model| !model.is_empty());
+    match (make, model) {
+        (Some(make), Some(model)) if model.to_lowercase().starts_with(&make.to_lowercase()) => {
+            Some(model.to_string())
+        }
+        (Some(make), Some(model)) => Some(format!("{} {}", make, model)),
+        (None, Some(model)) => Some(model.to_string()),
+        (Some(_), None) | (None, None) => None,
+    }
+}
+
+fn focal_length_bin(focal_length: f64) -> usize {
+    FOCAL_LENGTH_EDGES
+        .iter()
+        .rposition(|edge| focal_length >= *edge)
+        .unwrap_or(0)
+}
+
+fn sorted_counts(counts: HashMap<String, usize>) -> Vec<NamedCount> {
+    let mut counts: Vec<NamedCount> = counts.into_iter().map(|(name, count)| NamedCount { name, count }).collect();
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    counts
+}
+
+impl Accumulator {
+    fn add(&mut self, record: &ImageRecord) {
+        self.total += 1;
+
+        match camera_name(record.camera_make.as_deref(), record.camera_model.as_deref()) {
+            Some(camera) => *self.cameras.entry(camera).or_default() += 1,
+            None => self.without_exif += 1,
+        }
+        if let Some(lens) = record.lens_model.as_deref().map(str::trim).filter(|lens| !lens.is_empty()) {
+            *self.lenses.entry(lens.to_string()).or_default() += 1;
+        }
+        if let Some(focal_length) = record.focal_length.filter(|f| *f > 0.0) {
+            self.focal_lengths[focal_length_bin(focal_length)] += 1;
+        }
+        if let Some(iso) = record.iso.filter(|iso| *iso > 0) {
+            *self.isos.entry(iso).or_default() += 1;
+        }
+        if let Some(aperture) = record.aperture.filter(|a| *a > 0.0) {
+            *self.apertures.entry((aperture * 10.0).round() as i64).or_default() += 1;
+        }
+        if let Some(month) = record.date_taken.as_deref().and_then(|date| date.get(..7)) {
+            *self.months.entry(month.to_string()).or_default() += 1;
+        }
+    }
+
+    fn finish(self) -> CaptureStatistics {
+        let focal_lengths = self
+            .focal_lengths
+            .iter()
+            .enumerate()
+            .map(|(i, count)| FocalLengthBin {
+                min: FOCAL_LENGTH_EDGES[i],
+                max: FOCAL_LENGTH_EDGES.get(i + 1).copied(),
+                count: *count,
+            })
+            .collect();
+
+        CaptureStatistics {
+            total: self.total,
+            cameras: sorted_counts(self.cameras),
+            lenses: sorted_counts(self.lenses),
+            focal_lengths,
+            isos: self.isos.into_iter().map(|(value, count)| ValueCount { value, count }).collect(),
+            apertures: self
+                .apertures
+                .into_iter()
+                .map(|(value, count)| ValueCount {
+                    value: value as f64 / 10.0,
+                    count,
+                })
+                .collect(),
+            months: self.months.into_iter().map(|(name, count)| NamedCount { name, count }).collect(),
+            without_exif: self.without_exif,
+        }
+    }
+}
+
+/// 범위 안의 인덱싱된 EXIF 집계 (선택 집합은 바뀐 파일을 먼저 다시 인덱싱)
+pub fn get_capture_statistics(store: &MetadataStore, scope: &StatisticsScope) -> Result<CaptureStatistics, String> {
+    let mut accumulator = Accumulator::default();
+
+    let rules = match scope {
+        StatisticsScope::All => RuleSet {
+            match_mode: MatchMode::All,
+            rules: Vec::new(),
+        },
+        StatisticsScope::Folder { path, include_subfolders } => RuleSet {
+            match_mode: MatchMode::All,
+            rules: vec![AlbumRule::Folder {
+                path: path.clone(),
+                include_subfolders: *include_subfolders,
+            }],
+        },
+        StatisticsScope::SmartAlbum { id } => smart_album::get_album(store, *id)?.rules,
+        StatisticsScope::Selection { id } => {
+            let paths = crate::selection::paths(*id)?;
+            store.index_files(&paths)?;
+            for record in store.get_records(&paths)?.values() {
+                accumulator.add(record);
+            }
+            return Ok(accumulator.finish());
+        }
+    };
+
+    let mut sql_params = Vec::new();
+    let where_clause = rules.to_sql(&mut sql_params);
+    store.for_each_where(&format!("WHERE {}", where_clause), &sql_params, |record| {
+        accumulator.add(&record)
+    })?;
+    Ok(accumulator.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_camera_name() {
+        assert_eq!(camera_name(Some("Canon"), Some("Canon EOS R5")).as_deref(), Some("Canon EOS R5"));
+        assert_eq!(camera_name(Some("SONY"), Some("ILCE-7M4")).as_deref(), Some("SONY ILCE-7M4"));
+        assert_eq!(camera_name(None, Some("X100V")).as_deref(), Some("X100V"));
+        assert_eq!(camera_name(Some("Apple"), Some(" ")), None);
+    }
+
+    #[test]
+    fn test_focal_length_bin() {
+        assert_eq!(focal_length_bin(12.0), 0);
+        assert_eq!(focal_length_bin(24.0), 2);
+        assert_eq!(focal_length_bin(85.0), 5);
+        assert_eq!(focal_length_bin(600.0), FOCAL_LENGTH_EDGES.len() - 1);
+    }
+}
@@ -0,0 +1,306 @@
+use chrono::{Duration, Local, NaiveDateTime, TimeZone};
+use exif::{Field, In, Tag, Value};
+use filetime::FileTime;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::fs;
+use std::io::BufReader;
+use std::path::Path;
+use xmp_toolkit::{xmp_ns, ToStringOptions, XmpMeta, XmpValue};
+
+use crate::metadata_strip::{rebuild_exif, rewrite_jpeg_metadata};
+
+const EXIF_FORMAT: &str = "%Y:%m:%d %H:%M:%S";
+const XMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+/// 인덱스와 응답에 쓰는 시간 형식
+pub const STORE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// 함께 바꾸는 EXIF 날짜 (exiftool의 AllDates와 같은 범위)
+const EXIF_DATE_TAGS: &[Tag] = &[Tag::DateTimeOriginal, Tag::DateTimeDigitized, Tag::DateTime];
+
+/// 함께 바꾸는 XMP 날짜 속성
+const XMP_DATE_PROPERTIES: &[(&str, &str)] = &[
+    (xmp_ns::EXIF, "DateTimeOriginal"),
+    (xmp_ns::EXIF, "DateTimeDigitized"),
+    (xmp_ns::XMP, "CreateDate"),
+    (xmp_ns::PHOTOSHOP, "DateCreated"),
+];
+
+/// 촬영 시간 변경 방법
+#[derive(Debug, Clone, Copy)]
+pub enum TimeChange {
+    /// 모든 날짜를 같은 만큼 이동 (카메라 시계/시간대 오류)
+    Shift(Duration),
+    /// 지정한 시간으로 설정
+    Set(NaiveDateTime),
+}
+
+impl TimeChange {
+    fn apply(&self, time: NaiveDateTime) -> Option<NaiveDateTime> {
+        match self {
+            TimeChange::Shift(offset) => time.checked_add_signed(*offset),
+            TimeChange::Set(value) => Some(*value),
+        }
+    }
+}
+
+/// 촬영 시간을 바꾼 파일 (시간은 "YYYY-MM-DD HH:MM:SS")
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureTimeChange {
+    pub path: String,
+    pub before: Option<String>,
+    pub after: String,
+}
+
+/// 촬영 시간 변경 실패 항목
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureTimeFailure {
+    pub path: String,
+    pub error: String,
+}
+
+/// 촬영 시간 변경 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureTimeSummary {
+    pub updated: Vec<CaptureTimeChange>,
+    pub failed: Vec<CaptureTimeFailure>,
+}
+
+/// 사용자 입력 시간 파싱 ("YYYY-MM-DD HH:MM:SS", "YYYY-MM-DDTHH:MM:SS", EXIF 형식)
+pub fn parse_datetime(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    [STORE_FORMAT, XMP_FORMAT, EXIF_FORMAT]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+}
+
+/// EXIF ASCII 날짜 필드 읽기 ("YYYY:MM:DD HH:MM:SS")
+pub fn exif_time(field: &Field) -> Option<NaiveDateTime> {
+    match &field.value {
+        Value::Ascii(values) => values
+            .first()
+            .and_then(|bytes| std::str::from_utf8(bytes).ok())
+            .and_then(|s| NaiveDateTime::parse_from_str(s.trim_end_matches('\0').trim(), EXIF_FORMAT).ok()),
+        _ => None,
+    }
+}
+
+fn exif_value(time: NaiveDateTime) -> Value {
+    Value::Ascii(vec![time.format(EXIF_FORMAT).to_string().into_bytes()])
+}
+
+/// EXIF 날짜 바꾸기, 지정 모드에서 DateTimeOriginal이 없으면 추가
+fn change_exif(tiff: &[u8], change: TimeChange) -> Result<Vec<u8>, String> {
+    let exif = exif::Reader::new()
+        .read_raw(tiff.to_vec())
+        .map_err(|e| format!("Failed to read EXIF: {}", e))?;
+
+    let mut fields: Vec<Field> = exif.fields().cloned().collect();
+    for field in fields
+        .iter_mut()
+        .filter(|field| field.ifd_num == In::PRIMARY && EXIF_DATE_TAGS.contains(&field.tag))
+    {
+        if let Some(time) = exif_time(field).and_then(|time| change.apply(time)) {
+            field.value = exif_value(time);
+        }
+    }
+
+    if let TimeChange::Set(value) = change {
+        if !fields.iter().any(|field| field.tag == Tag::DateTimeOriginal && field.ifd_num == In::PRIMARY) {
+            fields.push(Field {
+                tag: Tag::DateTimeOriginal,
+                ifd_num: In::PRIMARY,
+                value: exif_value(value),
+            });
+        }
+    }
+
+    let fields: Vec<&Field> = fields.iter().collect();
+    rebuild_exif(&exif, &fields)
+}
+
+/// XMP exif:DateTimeOriginal (RAW 등은 촬영 시간 변경을 XMP에만 기록하므로 EXIF보다 우선)
+pub fn xmp_capture_time(xmp: &XmpMeta) -> Option<NaiveDateTime> {
+    let value = xmp.property(xmp_ns::EXIF, "DateTimeOriginal")?.value;
+    NaiveDateTime::parse_from_str(value.get(..19)?, XMP_FORMAT).ok()
+}
+
+/// XMP 날짜 값 바꾸기 (소수 초/시간대 접미사는 유지)
+fn change_xmp_value(value: &str, change: TimeChange) -> Option<String> {
+    let (base, suffix) = value.split_at_checked(19)?;
+    let time = NaiveDateTime::parse_from_str(base, XMP_FORMAT).ok()?;
+    Some(format!("{}{}", change.apply(time)?.format(XMP_FORMAT), suffix))
+}
+
+/// 있는 XMP 날짜 속성만 바꾸기
+fn change_xmp(xmp: &mut XmpMeta, change: TimeChange) -> Result<(), String> {
+    for (namespace, name) in XMP_DATE_PROPERTIES {
+        let Some(value) = xmp.property(namespace, name).and_then(|v| change_xmp_value(&v.value, change)) else {
+            continue;
+        };
+        xmp.set_property(namespace, name, &XmpValue::from(value))
+            .map_err(|e| format!("Failed to set {}: {}", name, e))?;
+    }
+    Ok(())
+}
+
+// 현재 촬영 시간 (XMP DateTimeOriginal, 없으면 EXIF)
+fn read_capture_time(path: &str, source: &Path) -> Option<NaiveDateTime> {
+    if let Some(time) = crate::rating::read_xmp(path).as_ref().and_then(xmp_capture_time) {
+        return Some(time);
+    }
+    let file = fs::File::open(source).ok()?;
+    let exif = exif::Reader::new()
+        .read_from_container(&mut BufReader::new(file))
+        .ok()?;
+    exif.get_field(Tag::DateTimeOriginal, In::PRIMARY).and_then(exif_time)
+}
+
+/// JPEG: EXIF와 XMP 패킷을 메모리에서 바꿔 원자적으로 저장
+fn change_jpeg(path: &Path, change: TimeChange) -> Result<(), String> {
+    let data = fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let mut has_exif = false;
+    let changed = rewrite_jpeg_metadata(
+        &data,
+        |tiff| {
+            has_exif = true;
+            change_exif(tiff, change).map(Some)
+        },
+        |packet| {
+            let text = std::str::from_utf8(packet).map_err(|e| format!("Invalid XMP packet: {}", e))?;
+            let mut xmp: XmpMeta = text.parse().map_err(|e| format!("Failed to parse XMP: {}", e))?;
+            change_xmp(&mut xmp, change)?;
+            xmp.to_string_with_options(ToStringOptions::default().use_compact_format())
+                .map(String::into_bytes)
+                .map_err(|e| format!("Failed to write XMP: {}", e))
+        },
+        false,
+    )?;
+    if !has_exif {
+        return Err("No EXIF data to update".to_string());
+    }
+
+    crate::shutdown::write_atomic(path, &changed).map_err(|e| format!("Failed to write file: {}", e))
+}
+
+/// 그 밖의 형식(RAW 등): 원본 EXIF는 건드리지 않고 XMP에 기록 (XMP가 EXIF보다 우선)
+fn change_other(path: &str, after: NaiveDateTime, change: TimeChange) -> Result<(), String> {
+    crate::rating::update_xmp(path, |xmp| {
+        let had_original = xmp.property(xmp_ns::EXIF, "DateTimeOriginal").is_some();
+        change_xmp(xmp, change)?;
+        if !had_original {
+            xmp.set_property(
+                xmp_ns::EXIF,
+                "DateTimeOriginal",
+                &XmpValue::from(after.format(XMP_FORMAT).to_string()),
+            )
+            .map_err(|e| format!("Failed to set DateTimeOriginal: {}", e))?;
+        }
+        Ok(())
+    })
+}
+
+/// 한 파일의 촬영 시간 바꾸기
+/// preserve_mtime이면 파일 수정 시간 유지, 아니면 새 촬영 시간으로 맞춤
+pub fn change_capture_time(path: &str, change: TimeChange, preserve_mtime: bool) -> Result<CaptureTimeChange, String> {
+    let source = crate::fs_path::to_fs_path(path);
+    let modified = fs::metadata(&source)
+        .map(|m| FileTime::from_last_modification_time(&m))
+        .map_err(|e| format!("Failed to get file metadata: {}", e))?;
+
+    let before = read_capture_time(path, &source);
+    let after = match (before, change) {
+        (_, TimeChange::Set(value)) => value,
+        (Some(before), TimeChange::Shift(_)) => change
+            .apply(before)
+            .ok_or_else(|| "Capture time out of range".to_string())?,
+        (None, TimeChange::Shift(_)) => return Err("No capture time to shift".to_string()),
+    };
+
+    if crate::formats::is_jpeg_file(path) {
+        change_jpeg(&source, change)?;
+    } else {
+        change_other(path, after, change)?;
+    }
+
+    let mtime = if preserve_mtime {
+        modified
+    } else {
+        Local
+            .from_local_datetime(&after)
+            .earliest()
+            .map(|time| FileTime::from_unix_time(time.timestamp(), 0))
+            .unwrap_or(modified)
+    };
+    filetime::set_file_mtime(&source, mtime).map_err(|e| format!("Failed to set modified time: {}", e))?;
+
+    Ok(CaptureTimeChange {
+        path: path.to_string(),
+        before: before.map(|time| time.format(STORE_FORMAT).to_string()),
+        after: after.format(STORE_FORMAT).to_string(),
+    })
+}
+
+/// 여러 파일의 촬영 시간을 같은 만큼 이동
+pub fn shift_capture_time(paths: &[String], offset_seconds: i64, preserve_mtime: bool) -> CaptureTimeSummary {
+    let change = TimeChange::Shift(Duration::seconds(offset_seconds));
+    let results: Vec<(String, Result<CaptureTimeChange, String>)> = paths
+        .par_iter()
+        .map(|path| (path.clone(), change_capture_time(path, change, preserve_mtime)))
+        .collect();
+
+    let mut summary = CaptureTimeSummary {
+        updated: Vec::new(),
+        failed: Vec::new(),
+    };
+    for (path, result) in results {
+        match result {
+            Ok(changed) => summary.updated.push(changed),
+            Err(error) => summary.failed.push(CaptureTimeFailure { path, error }),
+        }
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shift_exif_and_xmp_dates() {
+        let original = Field {
+            tag: Tag::DateTimeOriginal,
+            ifd_num: In::PRIMARY,
+            value: Value::Ascii(vec![b"2024:01:15 23:30:00".to_vec()]),
+        };
+        let mut writer = exif::experimental::Writer::new();
+        writer.push_field(&original);
+        let mut tiff = std::io::Cursor::new(Vec::new());
+        writer.write(&mut tiff, false).unwrap();
+
+        let change = TimeChange::Shift(Duration::hours(1));
+        let changed = change_exif(tiff.get_ref(), change).unwrap();
+        let exif = exif::Reader::new().read_raw(changed).unwrap();
+        let field = exif.get_field(Tag::DateTimeOriginal, In::PRIMARY).unwrap();
+        assert_eq!(exif_time(field), parse_datetime("2024-01-16 00:30:00"));
+
+        assert_eq!(
+            change_xmp_value("2024-01-15T23:30:00.25+09:00", change).as_deref(),
+            Some("2024-01-16T00:30:00.25+09:00")
+        );
+    }
+
+    #[test]
+    fn test_xmp_capture_time() {
+        let mut xmp = XmpMeta::new().unwrap();
+        assert_eq!(xmp_capture_time(&xmp), None);
+
+        xmp.set_property(xmp_ns::EXIF, "DateTimeOriginal", &XmpValue::from("2024-01-16T00:30:00+09:00"))
+            .unwrap();
+        assert_eq!(xmp_capture_time(&xmp), parse_datetime("2024-01-16 00:30:00"));
+
+        // 두 번째 이동은 XMP에 기록된 시간에서 시작
+        change_xmp(&mut xmp, TimeChange::Shift(Duration::hours(1))).unwrap();
+        assert_eq!(xmp_capture_time(&xmp), parse_datetime("2024-01-16 01:30:00"));
+    }
+}
@@ -0,0 +1,298 @@
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::formats::is_image_file;
+use crate::metadata_store::MetadataStore;
+use crate::rating;
+
+/// Lightroom 카탈로그에서 이미지별 별점/픽/색상 라벨과 전체 경로 조회
+const LIGHTROOM_QUERY: &str = "
+    SELECT root.absolutePath, folder.pathFromRoot, file.idx_filename,
+           image.rating, image.pick, image.colorLabels
+    FROM Adobe_images image
+    JOIN AgLibraryFile file ON image.rootFile = file.id_local
+    JOIN AgLibraryFolder folder ON file.folder = folder.id_local
+    JOIN AgLibraryRootFolder root ON folder.rootFolder = root.id_local
+";
+
+/// Capture One 색상 태그 번호 → 라벨 이름
+const CAPTURE_ONE_COLORS: &[&str] = &["", "Red", "Orange", "Yellow", "Green", "Blue", "Pink", "Purple"];
+
+/// 카탈로그 경로 접두사 변경 (드라이브/폴더를 옮긴 경우)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathMapping {
+    pub from: String,
+    pub to: String,
+}
+
+/// 카탈로그 가져오기 옵션
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CatalogImportOptions {
+    /// 별점/색상 라벨을 이미지 XMP에도 기록 (픽은 XMP 표준 항목이 없어 저장소에만)
+    pub write_xmp: bool,
+    pub path_mappings: Vec<PathMapping>,
+    /// 경로로 찾지 못한 파일을 이 폴더(하위 포함)에서 파일명으로 찾기 (같은 이름이 여럿이면 건너뜀)
+    pub search_folder: Option<String>,
+}
+
+/// 카탈로그 항목 (path는 찾은 로컬 경로)
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogEntry {
+    pub path: String,
+    pub rating: i32,
+    /// 1: 선택, -1: 제외, 0: 없음
+    pub pick: i32,
+    pub color_label: Option<String>,
+}
+
+/// 적용 실패 항목
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogFailure {
+    pub path: String,
+    pub error: String,
+}
+
+/// 카탈로그 가져오기 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogImportSummary {
+    pub total: usize,
+    pub applied: Vec<CatalogEntry>,
+    /// 로컬에서 찾지 못한 카탈로그 경로
+    pub unmatched: Vec<String>,
+    pub failed: Vec<CatalogFailure>,
+}
+
+/// Lightroom 카탈로그 (.lrcat) 읽기 (읽기 전용, Lightroom이 열고 있으면 실패할 수 있음)
+fn read_lightroom(catalog: &Path) -> Result<Vec<CatalogEntry>, String> {
+    let conn = Connection::open_with_flags(catalog, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Failed to open Lightroom catalog: {}", e))?;
+    let mut stmt = conn
+        .prepare(LIGHTROOM_QUERY)
+        .map_err(|e| format!("Unsupported Lightroom catalog (close Lightroom and retry): {}", e))?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            let root: String = row.get(0)?;
+            let folder: String = row.get(1)?;
+            let file_name: String = row.get(2)?;
+            let rating: Option<f64> = row.get(3)?;
+            let pick: Option<f64> = row.get(4)?;
+            let color_label: Option<String> = row.get(5)?;
+
+            // Lightroom은 Windows에서도 '/' 구분자 사용
+            let path: PathBuf = Path::new(&format!("{}{}{}", root, folder, file_name)).components().collect();
+            Ok(CatalogEntry {
+                path: path.to_string_lossy().to_string(),
+                rating: rating.map(|r| r.round() as i32).unwrap_or(0).clamp(0, 5),
+                pick: pick.map(|p| p.signum() as i32).unwrap_or(0),
+                color_label: color_label.filter(|label| !label.is_empty()),
+            })
+        })
+        .map_err(|e| format!("Failed to read Lightroom catalog: {}", e))?;
+
+    rows.collect::<Result<_, _>>()
+        .map_err(|e| format!("Failed to read Lightroom catalog: {}", e))
+}
+
+// Capture One 설정 파일(.cos)에서 K/V 항목 찾기
+fn cos_value<'a>(document: &'a roxmltree::Document, keys: &[&str]) -> Option<&'a str> {
+    document
+        .descendants()
+        .find(|node| node.attribute("K").is_some_and(|key| keys.contains(&key)))
+        .and_then(|node| node.attribute("V"))
+}
+
+/// Capture One 세션 읽기: <이미지 폴더>/CaptureOne/Settings*/<파일명>.cos
+/// (별점/색상 태그만, Capture One에는 픽이 없음)
+fn read_capture_one(session: &Path) -> Result<Vec<CatalogEntry>, String> {
+    if !session.is_dir() {
+        return Err(format!("{}: Capture One session folder {}", crate::error::NOT_FOUND, session.display()));
+    }
+
+    let mut entries = Vec::new();
+    for entry in WalkDir::new(session).into_iter().filter_map(|e| e.ok()) {
+        let cos_path = entry.path();
+        let Some(image_name) = cos_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(".cos"))
+        else {
+            continue;
+        };
+
+        let Some(capture_one_dir) = cos_path.parent().and_then(Path::parent) else {
+            continue;
+        };
+        if !capture_one_dir
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().eq_ignore_ascii_case("CaptureOne"))
+        {
+            continue;
+        }
+        let Some(image_dir) = capture_one_dir.parent() else {
+            continue;
+        };
+
+        let Ok(content) = fs::read_to_string(cos_path) else {
+            continue;
+        };
+        let Ok(document) = roxmltree::Document::parse(&content) else {
+            tracing::warn!("Invalid Capture One settings file: {}", cos_path.display());
+            continue;
+        };
+
+        let rating = cos_value(&document, &["Basic_Rating", "Rating"])
+            .and_then(|v| v.parse::<f64>().ok())
+            .map(|r| (r.round() as i32).clamp(0, 5))
+            .unwrap_or(0);
+        let color_label = cos_value(&document, &["Basic_ColorTag", "ColorTag"])
+            .and_then(|v| v.parse::<usize>().ok())
+            .and_then(|tag| CAPTURE_ONE_COLORS.get(tag))
+            .filter(|label| !label.is_empty())
+            .map(|label| label.to_string());
+
+        entries.push(CatalogEntry {
+            path: image_dir.join(image_name).to_string_lossy().to_string(),
+            rating,
+            pick: 0,
+            color_label,
+        });
+    }
+    Ok(entries)
+}
+
+/// 카탈로그 경로 → 로컬 경로 (접두사 변경 후 존재 확인, 없으면 파일명으로 찾기)
+fn resolve_path(
+    catalog_path: &str,
+    mappings: &[PathMapping],
+    by_name: &HashMap<String, Vec<PathBuf>>,
+) -> Option<String> {
+    let mapped = mappings
+        .iter()
+        .find_map(|m| catalog_path.strip_prefix(m.from.as_str()).map(|rest| format!("{}{}", m.to, rest)))
+        .unwrap_or_else(|| catalog_path.to_string());
+    let mapped: PathBuf = Path::new(&mapped).components().collect();
+    if mapped.is_file() {
+        return Some(mapped.to_string_lossy().to_string());
+    }
+
+    let name = Path::new(catalog_path).file_name()?.to_string_lossy().to_lowercase();
+    match by_name.get(&name).map(Vec::as_slice) {
+        Some([only]) => Some(only.to_string_lossy().to_string()),
+        _ => None,
+    }
+}
+
+// 검색 폴더의 이미지 파일을 파일명(소문자)별로 수집
+fn index_by_name(folder: Option<&str>) -> HashMap<String, Vec<PathBuf>> {
+    let mut by_name: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    let Some(folder) = folder else {
+        return by_name;
+    };
+    for entry in WalkDir::new(folder).into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if entry.file_type().is_file() && is_image_file(path) {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            by_name.entry(name).or_default().push(path.to_path_buf());
+        }
+    }
+    by_name
+}
+
+/// Lightroom 카탈로그(.lrcat) 또는 Capture One 세션 폴더에서 별점/픽/색상 라벨 가져오기
+/// 메타데이터 저장소에 기록하고, write_xmp면 이미지 XMP에도 기록
+pub fn import_catalog(
+    store: &MetadataStore,
+    catalog: &Path,
+    options: &CatalogImportOptions,
+) -> Result<CatalogImportSummary, String> {
+    let is_lightroom = catalog
+        .extension()
+        .is_some_and(|ext| ext.to_string_lossy().eq_ignore_ascii_case("lrcat"));
+    let entries = if is_lightroom {
+        read_lightroom(catalog)?
+    } else {
+        read_capture_one(catalog)?
+    };
+
+    let by_name = index_by_name(options.search_folder.as_deref());
+    let mut summary = CatalogImportSummary {
+        total: entries.len(),
+        applied: Vec::new(),
+        unmatched: Vec::new(),
+        failed: Vec::new(),
+    };
+
+    for entry in entries {
+        // 표시할 값이 없는 항목은 건너뜀
+        if entry.rating == 0 && entry.pick == 0 && entry.color_label.is_none() {
+            continue;
+        }
+        let Some(path) = resolve_path(&entry.path, &options.path_mappings, &by_name) else {
+            summary.unmatched.push(entry.path);
+            continue;
+        };
+
+        let result = (|| {
+            if rating::uses_database(&path) {
+                // XMP를 쓸 수 없는 형식은 DB 별점으로 (다시 인덱싱해도 유지)
+                store.set_db_rating(&path, entry.rating)?;
+            } else {
+                if options.write_xmp {
+                    rating::write_rating(&path, entry.rating)?;
+                    rating::write_label(&path, entry.color_label.as_deref())?;
+                }
+                store.update_rating(&path, entry.rating)?;
+            }
+            store.update_labels(&path, entry.pick, entry.color_label.as_deref())
+        })();
+
+        match result {
+            Ok(()) => summary.applied.push(CatalogEntry { path, ..entry }),
+            Err(error) => summary.failed.push(CatalogFailure { path, error }),
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_lightroom() {
+        let dir = std::env::temp_dir().join(format!("pixengine-lrcat-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let catalog = dir.join("test.lrcat");
+        let _ = fs::remove_file(&catalog);
+
+        let conn = Connection::open(&catalog).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE AgLibraryRootFolder (id_local INTEGER, absolutePath TEXT);
+             CREATE TABLE AgLibraryFolder (id_local INTEGER, rootFolder INTEGER, pathFromRoot TEXT);
+             CREATE TABLE AgLibraryFile (id_local INTEGER, folder INTEGER, idx_filename TEXT);
+             CREATE TABLE Adobe_images (id_local INTEGER, rootFile INTEGER, rating REAL, pick REAL, colorLabels TEXT);
+             INSERT INTO AgLibraryRootFolder VALUES (1, '/photos/');
+             INSERT INTO AgLibraryFolder VALUES (2, 1, '2024/shoot/');
+             INSERT INTO AgLibraryFile VALUES (3, 2, 'IMG_0001.CR3');
+             INSERT INTO Adobe_images VALUES (4, 3, 4.0, -1.0, 'Red');",
+        )
+        .unwrap();
+        drop(conn);
+
+        let entries = read_lightroom(&catalog).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(Path::new(&entries[0].path), Path::new("/photos/2024/shoot/IMG_0001.CR3"));
+        assert_eq!(entries[0].rating, 4);
+        assert_eq!(entries[0].pick, -1);
+        assert_eq!(entries[0].color_label.as_deref(), Some("Red"));
+    }
+}
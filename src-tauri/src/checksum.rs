@@ -0,0 +1,322 @@
+use md5::Md5;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tauri::{AppHandle, Emitter};
+
+use crate::jobs::Job;
+
+/// 해시 계산 시 읽기 버퍼 크기
+const READ_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// 진행률 이벤트 전송 간격 (파일 수)
+const PROGRESS_EVERY: usize = 16;
+
+/// 체크섬 알고리즘
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChecksumAlgorithm {
+    Blake3,
+    Sha256,
+    Md5,
+}
+
+impl ChecksumAlgorithm {
+    /// 매니페스트 확장자로 알고리즘 판별 (없으면 해시 길이로 후보 추정, 64자는 SHA-256/BLAKE3 둘 다 가능)
+    fn candidates(manifest_path: &Path, sample_hash: Option<&str>) -> Vec<Self> {
+        let ext = manifest_path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase());
+
+        match ext.as_deref() {
+            Some("blake3") | Some("b3") => vec![ChecksumAlgorithm::Blake3],
+            Some("sha256") => vec![ChecksumAlgorithm::Sha256],
+            Some("md5") => vec![ChecksumAlgorithm::Md5],
+            _ => match sample_hash.map(str::len) {
+                Some(32) => vec![ChecksumAlgorithm::Md5],
+                Some(64) => vec![ChecksumAlgorithm::Sha256, ChecksumAlgorithm::Blake3],
+                _ => Vec::new(),
+            },
+        }
+    }
+}
+
+/// 파일별 체크섬 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct ChecksumEntry {
+    pub path: String,
+    pub hash: Option<String>,
+    pub error: Option<String>,
+}
+
+/// 체크섬 진행률 (checksum-progress 이벤트)
+#[derive(Debug, Clone, Serialize)]
+struct ChecksumProgress {
+    processed: usize,
+    total: usize,
+}
+
+/// 매니페스트 검증 실패 항목
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifyFailure {
+    pub path: String,
+    pub error: String,
+}
+
+/// 매니페스트 검증 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifyReport {
+    pub algorithm: ChecksumAlgorithm,
+    pub total: usize,
+    pub verified: usize,
+    pub mismatched: Vec<String>,
+    pub missing: Vec<String>,
+    pub failed: Vec<VerifyFailure>, // 읽기 오류
+}
+
+/// 알고리즘별 해셔
+enum Hasher {
+    Blake3(Box<blake3::Hasher>),
+    Sha256(Sha256),
+    Md5(Md5),
+}
+
+impl Hasher {
+    fn new(algorithm: ChecksumAlgorithm) -> Self {
+        match algorithm {
+            ChecksumAlgorithm::Blake3 => Hasher::Blake3(Box::new(blake3::Hasher::new())),
+            ChecksumAlgorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            ChecksumAlgorithm::Md5 => Hasher::Md5(Md5::new()),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Blake3(hasher) => {
+                hasher.update(data);
+            }
+            Hasher::Sha256(hasher) => hasher.update(data),
+            Hasher::Md5(hasher) => hasher.update(data),
+        }
+    }
+
+    fn finalize_hex(self) -> String {
+        match self {
+            Hasher::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+            Hasher::Sha256(hasher) => to_hex(&hasher.finalize()),
+            Hasher::Md5(hasher) => to_hex(&hasher.finalize()),
+        }
+    }
+}
+
+/// 파일 해시 계산 (소문자 16진수)
+pub fn hash_file(path: &Path, algorithm: ChecksumAlgorithm) -> Result<String, String> {
+    let mut file = fs::File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut buffer = vec![0u8; READ_BUFFER_SIZE];
+    let mut hasher = Hasher::new(algorithm);
+
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hasher.finalize_hex())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// 여러 파일의 체크섬 병렬 계산 (checksum-progress 이벤트 전송, 작업이 취소되면 남은 파일은 오류로 기록)
+pub fn compute_checksums(
+    app: &AppHandle,
+    paths: &[String],
+    algorithm: ChecksumAlgorithm,
+    job: &Job,
+) -> Vec<ChecksumEntry> {
+    let total = paths.len();
+    let processed = AtomicUsize::new(0);
+
+    paths
+        .par_iter()
+        .map(|path| {
+            let result = if job.is_cancelled() {
+                Err(crate::error::CANCELLED.to_string())
+            } else {
+                hash_file(Path::new(path), algorithm)
+            };
+
+            let done = processed.fetch_add(1, Ordering::Relaxed) + 1;
+            job.set_progress(done, total);
+            if done.is_multiple_of(PROGRESS_EVERY) || done == total {
+                let _ = app.emit("checksum-progress", ChecksumProgress { processed: done, total });
+            }
+
+            match result {
+                Ok(hash) => ChecksumEntry { path: path.clone(), hash: Some(hash), error: None },
+                Err(error) => ChecksumEntry { path: path.clone(), hash: None, error: Some(error) },
+            }
+        })
+        .collect()
+}
+
+/// 매니페스트 기준 상대 경로 (다른 드라이브 등 상대 경로가 안 되면 절대 경로)
+fn manifest_entry_path(manifest_dir: &Path, path: &Path) -> String {
+    path.strip_prefix(manifest_dir)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// 표준 체크섬 매니페스트 저장 (sha256sum/md5sum 호환: "<hash> *<path>")
+pub fn write_manifest(manifest_path: &Path, entries: &[ChecksumEntry]) -> Result<(), String> {
+    let manifest_dir = manifest_path.parent().unwrap_or(Path::new(""));
+
+    let content: String = entries
+        .iter()
+        .filter_map(|entry| {
+            let hash = entry.hash.as_ref()?;
+            Some(format!("{} *{}\n", hash, manifest_entry_path(manifest_dir, Path::new(&entry.path))))
+        })
+        .collect();
+
+    fs::write(manifest_path, content).map_err(|e| format!("Failed to write manifest: {}", e))
+}
+
+/// 매니페스트 한 줄 파싱 ("<hash>  <path>" 또는 "<hash> *<path>")
+fn parse_manifest_line(line: &str) -> Option<(String, String)> {
+    let line = line.trim_end_matches('\r');
+    if line.trim().is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let (hash, rest) = line.split_once(' ')?;
+    let path = rest.strip_prefix(' ').or_else(|| rest.strip_prefix('*')).unwrap_or(rest);
+    Some((hash.to_lowercase(), path.to_string()))
+}
+
+// 매니페스트 알고리즘 판별
+// 후보가 여럿이면 처음 있는 파일을 후보마다 계산해서 일치하는 알고리즘 선택 (일치하는 것이 없으면 첫 후보)
+fn detect_algorithm(manifest_path: &Path, entries: &[(String, PathBuf, String)]) -> Result<ChecksumAlgorithm, String> {
+    let candidates = ChecksumAlgorithm::candidates(manifest_path, entries.first().map(|(h, _, _)| h.as_str()));
+    if candidates.len() > 1 {
+        if let Some((expected, full_path, _)) = entries.iter().find(|(_, full_path, _)| full_path.is_file()) {
+            let matched = candidates
+                .iter()
+                .find(|&&algorithm| hash_file(full_path, algorithm).is_ok_and(|actual| &actual == expected));
+            if let Some(&algorithm) = matched {
+                return Ok(algorithm);
+            }
+        }
+    }
+    candidates
+        .first()
+        .copied()
+        .ok_or_else(|| format!("Unknown checksum format: {}", manifest_path.display()))
+}
+
+/// 매니페스트로 파일 무결성 검증 (불일치/누락 파일 보고, 작업이 취소되면 남은 파일은 읽기 오류로 보고)
+/// algorithm이 None이면 확장자와 해시로 판별
+pub fn verify_checksums(
+    app: &AppHandle,
+    manifest_path: &Path,
+    algorithm: Option<ChecksumAlgorithm>,
+    job: &Job,
+) -> Result<VerifyReport, String> {
+    let content = fs::read_to_string(manifest_path)
+        .map_err(|e| format!("Failed to read manifest: {}", e))?;
+    let manifest_dir = manifest_path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+    let entries: Vec<(String, PathBuf, String)> = content
+        .lines()
+        .filter_map(parse_manifest_line)
+        .map(|(hash, name)| {
+            let full_path = manifest_dir.join(&name);
+            (hash, full_path, name)
+        })
+        .collect();
+
+    let algorithm = match algorithm {
+        Some(algorithm) => algorithm,
+        None => detect_algorithm(manifest_path, &entries)?,
+    };
+
+    let total = entries.len();
+    let processed = AtomicUsize::new(0);
+
+    // (이름, 결과): Ok(true) 일치, Ok(false) 불일치, Err 읽기 오류, None 누락
+    let results: Vec<(String, Option<Result<bool, String>>)> = entries
+        .par_iter()
+        .map(|(expected, full_path, name)| {
+            let result = if job.is_cancelled() {
+                Some(Err(crate::error::CANCELLED.to_string()))
+            } else {
+                full_path
+                    .is_file()
+                    .then(|| hash_file(full_path, algorithm).map(|actual| &actual == expected))
+            };
+
+            let done = processed.fetch_add(1, Ordering::Relaxed) + 1;
+            job.set_progress(done, total);
+            if done.is_multiple_of(PROGRESS_EVERY) || done == total {
+                let _ = app.emit("checksum-progress", ChecksumProgress { processed: done, total });
+            }
+
+            (name.clone(), result)
+        })
+        .collect();
+
+    let mut report = VerifyReport {
+        algorithm,
+        total,
+        verified: 0,
+        mismatched: Vec::new(),
+        missing: Vec::new(),
+        failed: Vec::new(),
+    };
+
+    for (name, result) in results {
+        match result {
+            Some(Ok(true)) => report.verified += 1,
+            Some(Ok(false)) => report.mismatched.push(name),
+            Some(Err(error)) => report.failed.push(VerifyFailure { path: name, error }),
+            None => report.missing.push(name),
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_algorithm() {
+        let dir = std::env::temp_dir().join(format!("pixengine-checksum-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("A001.jpg");
+        fs::write(&file, b"photo").unwrap();
+        let manifest = dir.join("manifest.txt");
+
+        // 64자 해시는 실제 파일과 비교해서 SHA-256/BLAKE3 구분
+        for algorithm in [ChecksumAlgorithm::Blake3, ChecksumAlgorithm::Sha256] {
+            let entries = vec![(hash_file(&file, algorithm).unwrap(), file.clone(), "A001.jpg".to_string())];
+            assert_eq!(detect_algorithm(&manifest, &entries), Ok(algorithm));
+        }
+
+        let md5 = vec![("0".repeat(32), file.clone(), "A001.jpg".to_string())];
+        assert_eq!(detect_algorithm(&manifest, &md5), Ok(ChecksumAlgorithm::Md5));
+        assert!(detect_algorithm(&manifest, &[]).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
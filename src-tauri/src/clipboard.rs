@@ -1,4 +1,3 @@
-use std::path::PathBuf;
 use std::fs;
 use serde::{Deserialize, Serialize};
 
@@ -28,18 +27,9 @@ pub fn copy_files_to_clipboard(file_paths: Vec<String>, is_cut: bool) -> Result<
     let canonical_paths: Vec<String> = file_paths
         .iter()
         .map(|p| {
-            let path_buf = PathBuf::from(p);
-            match path_buf.canonicalize() {
-                Ok(canonical) => {
-                    // Windows UNC 경로 형식을 일반 경로로 변환
-                    let path_str = canonical.to_string_lossy().to_string();
-                    // "\\\\?\\" 접두사 제거 (Windows 확장 경로 형식)
-                    if path_str.starts_with("\\\\?\\") {
-                        path_str[4..].to_string()
-                    } else {
-                        path_str
-                    }
-                }
+            // 긴 경로/유니코드 형태를 실제 경로로 해석 후, 다른 앱이 읽을 수 있도록 확장 경로 접두사 제거
+            match crate::fs_path::to_fs_path(p).canonicalize() {
+                Ok(canonical) => crate::fs_path::strip_verbatim(&canonical.to_string_lossy()),
                 Err(_) => p.clone(),
             }
         })
@@ -174,14 +164,15 @@ pub fn paste_files(
     let is_cut = is_clipboard_cut_mode()?;
 
     // 대상 디렉토리 정규화
-    let dest_dir_canonical = PathBuf::from(&destination_dir)
+    let dest_dir = crate::fs_path::to_fs_path(&destination_dir);
+    let dest_dir_canonical = dest_dir
         .canonicalize()
         .map_err(|e| format!("Failed to resolve destination directory: {}", e))?;
 
     // 자기 자신에게 복사하는지 확인
     let mut self_copy_detected = false;
     for source in &source_files {
-        let source_path = PathBuf::from(source);
+        let source_path = crate::fs_path::to_fs_path(source);
 
         // 소스 파일의 부모 디렉토리 확인
         if let Some(source_parent) = source_path.parent() {
@@ -203,14 +194,14 @@ pub fn paste_files(
     let mut duplicates = Vec::new();
 
     for source in &source_files {
-        let source_path = PathBuf::from(source);
+        let source_path = crate::fs_path::to_fs_path(source);
         let file_name = source_path
             .file_name()
             .ok_or("Invalid file name")?
             .to_string_lossy()
             .to_string();
 
-        let dest_path = PathBuf::from(&destination_dir).join(&file_name);
+        let dest_path = crate::fs_path::to_fs_path(dest_dir.join(&file_name));
 
         // 이미 처리 결정된 파일인지 확인
         if overwrite_files.contains(&file_name) || skip_files.contains(&file_name) {
@@ -219,13 +210,8 @@ pub fn paste_files(
 
         // 중복 파일 발견
         if dest_path.exists() {
-            // 대상 경로에서 \\?\ 접두사 제거
-            let dest_str = dest_path.to_string_lossy().to_string();
-            let clean_dest = if dest_str.starts_with("\\\\?\\") {
-                dest_str[4..].to_string()
-            } else {
-                dest_str
-            };
+            // 대상 경로에서 \\?\ 접두사 제거 (NFC 표시 형태)
+            let clean_dest = crate::fs_path::to_display(&dest_path);
 
             duplicates.push(DuplicateFileInfo {
                 source: source.clone(),
@@ -242,7 +228,7 @@ pub fn paste_files(
 
     // 실제 파일 복사/이동 수행
     for source in &source_files {
-        let source_path = PathBuf::from(source);
+        let source_path = crate::fs_path::to_fs_path(source);
         let file_name = source_path
             .file_name()
             .ok_or("Invalid file name")?
@@ -254,7 +240,7 @@ pub fn paste_files(
             continue;
         }
 
-        let dest_path = PathBuf::from(&destination_dir).join(&file_name);
+        let dest_path = crate::fs_path::to_fs_path(dest_dir.join(&file_name));
 
         if is_cut {
             // 이동
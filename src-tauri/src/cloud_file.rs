@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use tauri::{AppHandle, Emitter};
+
+/// 다운로드 시 읽기 버퍼 크기
+const READ_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// 클라우드 전용 파일 처리 방식 (썸네일 생성 시)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CloudFileMode {
+    /// 다운로드하지 않음 (캐시된 썸네일만 표시, hydrate_files로 명시적 다운로드)
+    Skip,
+    /// 빠른 썸네일은 건너뛰고 유휴 시 HQ 썸네일 생성 때 다운로드
+    Defer,
+    /// 일반 파일처럼 바로 다운로드
+    Download,
+}
+
+/// 클라우드 플레이스홀더 여부 (Windows 파일 속성)
+/// RECALL_ON_DATA_ACCESS: 내용을 읽으면 다운로드, RECALL_ON_OPEN: 열면 다운로드, OFFLINE: 원격 저장
+#[cfg(target_os = "windows")]
+pub fn is_placeholder_metadata(metadata: &fs::Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+
+    const FILE_ATTRIBUTE_OFFLINE: u32 = 0x0000_1000;
+    const FILE_ATTRIBUTE_RECALL_ON_OPEN: u32 = 0x0004_0000;
+    const FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS: u32 = 0x0040_0000;
+
+    metadata.file_attributes()
+        & (FILE_ATTRIBUTE_OFFLINE | FILE_ATTRIBUTE_RECALL_ON_OPEN | FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS)
+        != 0
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn is_placeholder_metadata(_metadata: &fs::Metadata) -> bool {
+    false
+}
+
+/// 경로가 아직 다운로드되지 않은 클라우드 파일인지 확인 (속성만 읽으므로 다운로드를 유발하지 않음)
+pub fn is_placeholder(path: impl AsRef<Path>) -> bool {
+    fs::symlink_metadata(path)
+        .map(|metadata| is_placeholder_metadata(&metadata))
+        .unwrap_or(false)
+}
+
+/// 빠른 썸네일 생성에서 원본 읽기를 건너뛸지 (설정 기준)
+pub fn skip_fast_thumbnail(path: &str) -> bool {
+    crate::settings::current().cloud_file_mode != CloudFileMode::Download && is_placeholder(path)
+}
+
+/// HQ 썸네일 생성에서 원본 읽기를 건너뛸지 (설정 기준)
+pub fn skip_hq_thumbnail(path: &str) -> bool {
+    crate::settings::current().cloud_file_mode == CloudFileMode::Skip && is_placeholder(path)
+}
+
+/// 다운로드 결과 (파일별)
+#[derive(Debug, Clone, Serialize)]
+pub struct HydrateEntry {
+    pub path: String,
+    pub error: Option<String>,
+}
+
+/// 다운로드 진행률 (hydrate-progress 이벤트)
+#[derive(Debug, Clone, Serialize)]
+struct HydrateProgress {
+    processed: usize,
+    total: usize,
+    current_path: String,
+    bytes_read: u64,
+}
+
+/// 파일 전체를 읽어 클라우드 공급자가 다운로드하도록 함
+/// 읽은 바이트 수 반환
+fn hydrate_file(path: &Path, mut on_progress: impl FnMut(u64)) -> Result<u64, String> {
+    let mut file = fs::File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut buffer = vec![0u8; READ_BUFFER_SIZE];
+    let mut bytes_read = 0u64;
+
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        bytes_read += read as u64;
+        on_progress(bytes_read);
+    }
+
+    Ok(bytes_read)
+}
+
+/// 클라우드 전용 파일 명시적 다운로드 (hydrate-progress 이벤트 전송)
+/// 이미 로컬에 있는 파일은 읽지 않음
+pub fn hydrate_files(app: &AppHandle, paths: &[String]) -> Vec<HydrateEntry> {
+    let total = paths.len();
+
+    paths
+        .iter()
+        .enumerate()
+        .map(|(i, path)| {
+            let source = crate::fs_path::to_fs_path(path);
+            let emit = |bytes_read: u64| {
+                let _ = app.emit(
+                    "hydrate-progress",
+                    HydrateProgress {
+                        processed: i,
+                        total,
+                        current_path: path.clone(),
+                        bytes_read,
+                    },
+                );
+            };
+
+            let result = if is_placeholder(&source) {
+                hydrate_file(&source, emit)
+            } else {
+                Ok(0)
+            };
+
+            let _ = app.emit(
+                "hydrate-progress",
+                HydrateProgress {
+                    processed: i + 1,
+                    total,
+                    current_path: path.clone(),
+                    bytes_read: *result.as_ref().unwrap_or(&0),
+                },
+            );
+
+            if let Err(e) = &result {
+                tracing::warn!("Failed to hydrate {}: {}", path, e);
+            }
+            HydrateEntry { path: path.clone(), error: result.err() }
+        })
+        .collect()
+}
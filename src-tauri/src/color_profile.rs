@@ -0,0 +1,140 @@
+use lazy_static::lazy_static;
+use lru::LruCache;
+use qcms::{DataType, Intent, Profile, Transform};
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+
+/// 캐시할 변환 수 (카메라/편집 프로그램별 프로파일 종류가 많지 않음)
+const TRANSFORM_CACHE_SIZE: usize = 16;
+
+/// 변환할 픽셀 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PixelLayout {
+    Rgb8,
+    Rgba8,
+}
+
+impl PixelLayout {
+    fn data_type(self) -> DataType {
+        match self {
+            PixelLayout::Rgb8 => DataType::RGB8,
+            PixelLayout::Rgba8 => DataType::RGBA8,
+        }
+    }
+
+    fn bytes_per_pixel(self) -> usize {
+        match self {
+            PixelLayout::Rgb8 => 3,
+            PixelLayout::Rgba8 => 4,
+        }
+    }
+}
+
+/// 변환 방향
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Direction {
+    /// 이미지 프로파일 → sRGB
+    ToSrgb,
+    /// sRGB → 출력 장치 프로파일 (소프트 프루핑)
+    FromSrgb,
+}
+
+/// (ICC 프로파일 해시, 픽셀 형식, 변환 방향)
+type TransformKey = (blake3::Hash, PixelLayout, Direction);
+
+lazy_static! {
+    /// 프로파일별 변환 (None: sRGB이거나 해석할 수 없는 프로파일 → 변환 생략)
+    static ref TRANSFORM_CACHE: Mutex<LruCache<TransformKey, Option<Arc<Transform>>>> =
+        Mutex::new(LruCache::new(NonZeroUsize::new(TRANSFORM_CACHE_SIZE).unwrap()));
+}
+
+/// 색 관리 사용 여부 (설정)
+pub fn is_enabled() -> bool {
+    crate::settings::current().color_management
+}
+
+// RGB 프로파일 해석 (Gray/CMYK 프로파일은 픽셀 형식이 달라 None)
+fn parse_rgb_profile(icc: &[u8]) -> Option<Box<Profile>> {
+    // 헤더의 데이터 색 공간(16..20)이 RGB인 프로파일만 처리
+    if icc.get(16..20) != Some(b"RGB ".as_slice()) {
+        return None;
+    }
+    Profile::new_from_slice(icc, false)
+}
+
+/// sRGB 프로파일이거나 해석할 수 없는 프로파일인지 (변환할 필요 없음)
+pub fn is_srgb_or_unsupported(icc: &[u8]) -> bool {
+    parse_rgb_profile(icc).is_none_or(|profile| profile.is_sRGB())
+}
+
+/// ICC 프로파일 ↔ sRGB 변환 생성
+fn create_transform(icc: &[u8], layout: PixelLayout, direction: Direction) -> Option<Arc<Transform>> {
+    let mut profile = parse_rgb_profile(icc)?;
+    if profile.is_sRGB() {
+        return None;
+    }
+
+    let mut srgb = Profile::new_sRGB();
+    let (input, output) = match direction {
+        Direction::ToSrgb => {
+            srgb.precache_output_transform();
+            (&profile, &srgb)
+        }
+        Direction::FromSrgb => {
+            profile.precache_output_transform();
+            (&srgb, &profile)
+        }
+    };
+
+    Transform::new(input, output, layout.data_type(), Intent::default()).map(Arc::new)
+}
+
+fn get_transform(icc: &[u8], layout: PixelLayout, direction: Direction) -> Option<Arc<Transform>> {
+    let key = (blake3::hash(icc), layout, direction);
+
+    if let Ok(mut cache) = TRANSFORM_CACHE.lock() {
+        if let Some(transform) = cache.get(&key) {
+            return transform.clone();
+        }
+    }
+
+    let transform = create_transform(icc, layout, direction);
+    if transform.is_none() {
+        tracing::debug!("ICC profile skipped (sRGB or unsupported, {} bytes)", icc.len());
+    }
+
+    if let Ok(mut cache) = TRANSFORM_CACHE.lock() {
+        cache.put(key, transform.clone());
+    }
+    transform
+}
+
+/// 픽셀을 ICC 프로파일 색 공간에서 sRGB로 변환 (제자리 변환)
+/// 설정이 꺼져 있거나, 프로파일이 없거나 sRGB면 그대로 둠
+pub fn convert_to_srgb(icc: Option<&[u8]>, pixels: &mut [u8], layout: PixelLayout) {
+    let Some(icc) = icc.filter(|icc| !icc.is_empty()) else {
+        return;
+    };
+    if !is_enabled() || !pixels.len().is_multiple_of(layout.bytes_per_pixel()) {
+        return;
+    }
+
+    if let Some(transform) = get_transform(icc, layout, Direction::ToSrgb) {
+        transform.apply(pixels);
+    }
+}
+
+/// sRGB 픽셀을 ICC 프로파일 색 공간으로 변환 (제자리 변환, 소프트 프루핑용)
+/// 설정이 꺼져 있거나, 프로파일이 없거나 sRGB면 그대로 둠
+pub fn convert_from_srgb(icc: Option<&[u8]>, pixels: &mut [u8], layout: PixelLayout) {
+    let Some(icc) = icc.filter(|icc| !icc.is_empty()) else {
+        return;
+    };
+    if !is_enabled() || !pixels.len().is_multiple_of(layout.bytes_per_pixel()) {
+        return;
+    }
+
+    if let Some(transform) = get_transform(icc, layout, Direction::FromSrgb) {
+        transform.apply(pixels);
+    }
+}
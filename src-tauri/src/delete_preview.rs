@@ -0,0 +1,82 @@
+use serde::Serialize;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+use walkdir::WalkDir;
+
+use crate::formats::is_image_file;
+use crate::metadata_store::MetadataStore;
+
+/// 삭제 전 확인용 요약 (폴더 안의 모든 파일 기준)
+#[derive(Debug, Clone, Serialize)]
+pub struct DeletePreview {
+    pub path: String,
+    pub file_count: usize,
+    /// 하위 폴더 수 (삭제할 폴더 자신은 제외)
+    pub folder_count: usize,
+    pub total_size: u64,
+    /// 가장 최근 수정된 파일의 수정 시간 (Unix 초, 파일이 없으면 None)
+    pub newest_modified: Option<u64>,
+    pub image_count: usize,
+    /// 별점이 min_rating 이상인 이미지 수
+    pub rated_count: usize,
+    /// 픽(선택)으로 표시된 이미지 수
+    pub picked_count: usize,
+    /// 별점/픽이 있는 이미지가 하나라도 있음 (확인 창에서 경고)
+    pub has_protected: bool,
+}
+
+/// 폴더를 지우기 전에 지워질 내용 요약
+/// 별점은 인덱스 값 사용 (인덱싱되지 않았거나 바뀐 파일만 다시 읽음)
+pub fn preview(store: &MetadataStore, path: &Path, min_rating: i32) -> Result<DeletePreview, String> {
+    if !path.is_dir() {
+        return Err(format!("{}: folder {}", crate::error::NOT_FOUND, path.display()));
+    }
+
+    let mut summary = DeletePreview {
+        path: crate::fs_path::to_display(path),
+        file_count: 0,
+        folder_count: 0,
+        total_size: 0,
+        newest_modified: None,
+        image_count: 0,
+        rated_count: 0,
+        picked_count: 0,
+        has_protected: false,
+    };
+
+    let mut images = Vec::new();
+    for entry in WalkDir::new(path).min_depth(1).into_iter().filter_map(|e| e.ok()) {
+        if entry.file_type().is_dir() {
+            summary.folder_count += 1;
+            continue;
+        }
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        summary.file_count += 1;
+        if let Ok(metadata) = entry.metadata() {
+            summary.total_size += metadata.len();
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_secs());
+            summary.newest_modified = summary.newest_modified.max(modified);
+        }
+        if is_image_file(entry.path()) {
+            images.push(crate::fs_path::to_display(entry.path()));
+        }
+    }
+
+    summary.image_count = images.len();
+    summary.rated_count = store
+        .get_ratings(&images)?
+        .iter()
+        .filter(|(_, rating)| rating.is_some_and(|rating| rating >= min_rating))
+        .count();
+    summary.picked_count = store.get_picks(&images)?.values().filter(|pick| **pick > 0).count();
+    summary.has_protected = summary.rated_count > 0 || summary.picked_count > 0;
+
+    Ok(summary)
+}
@@ -0,0 +1,142 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::error::AppError;
+use crate::file_stack::FileStack;
+use crate::formats::{self, FormatCategory};
+
+/// 디렉토리 항목
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryEntry {
+    pub name: String,
+    pub path: String,
+    pub is_dir: bool,
+    /// 클라우드 전용 파일/폴더 (OneDrive 등, 접근 시 다운로드됨)
+    pub is_cloud_placeholder: bool,
+    /// 파일 종류 (폴더나 지원하지 않는 파일은 None)
+    pub kind: Option<FormatCategory>,
+    /// 소문자 확장자
+    pub extension: Option<String>,
+    /// 파일 크기 (바이트, 알 수 없으면 None)
+    pub size: Option<u64>,
+    /// 수정 시간 (Unix 초, 알 수 없으면 None)
+    pub modified: Option<u64>,
+}
+
+impl DirectoryEntry {
+    /// 이름으로 종류/확장자를 채운 항목 (크기/수정 시간은 알 때 따로 설정)
+    pub fn new(name: String, path: String, is_dir: bool) -> Self {
+        let (kind, extension) = if is_dir {
+            (None, None)
+        } else {
+            let extension = Path::new(&name)
+                .extension()
+                .map(|ext| ext.to_string_lossy().to_lowercase());
+            (formats::category(&name), extension)
+        };
+        Self {
+            name,
+            path,
+            is_dir,
+            is_cloud_placeholder: false,
+            kind,
+            extension,
+            size: None,
+            modified: None,
+        }
+    }
+}
+
+/// 읽지 못한 항목과 이유
+#[derive(Debug, Clone, Serialize)]
+pub struct DirectoryEntryError {
+    pub name: String,
+    pub path: String,
+    pub error: AppError,
+}
+
+/// 디렉토리 읽기 결과 (일부 항목을 읽지 못해도 나머지는 반환)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DirectoryListing {
+    pub entries: Vec<DirectoryEntry>,
+    pub errors: Vec<DirectoryEntryError>,
+    /// 같은 이름의 파일 묶음 (RAW+JPEG, HEIC+MOV), 대표가 아닌 파일은 그리드에서 숨김
+    pub stacks: Vec<FileStack>,
+}
+
+/// 로컬 디렉토리 읽기
+/// 메타데이터를 읽지 못한 항목은 errors에 이유와 함께 기록 (권한 없음, 깨진 링크 등)
+/// 종류는 확장자 기준 (파일 내용은 읽지 않음, 확장자와 다른 파일은 디코딩할 때 formats::detect가 판별)
+pub fn read_local(dir: &Path, skip: impl Fn(&str) -> bool) -> Result<DirectoryListing, String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read directory: {}", e))?;
+
+    let mut listing = DirectoryListing::default();
+
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                listing.errors.push(DirectoryEntryError {
+                    name: String::new(),
+                    path: dir.to_string_lossy().to_string(),
+                    error: AppError::from(e),
+                });
+                continue;
+            }
+        };
+
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        // 숨김/시스템 디렉토리 필터링
+        if skip(&name) {
+            continue;
+        }
+
+        // 링크가 아닌 항목의 속성 (플레이스홀더 확인용, 다운로드를 유발하지 않음)
+        let own_metadata = entry.metadata();
+        let is_placeholder = own_metadata.as_ref().map(crate::cloud_file::is_placeholder_metadata).unwrap_or(false);
+
+        // canonicalize로 심볼릭 링크/junction 해결 (플레이스홀더는 링크가 아니므로 생략)
+        let real_path: PathBuf = if is_placeholder {
+            path.clone()
+        } else {
+            fs::canonicalize(&path).unwrap_or_else(|_| path.clone())
+        };
+
+        // 실제 경로의 메타데이터 확인 (플레이스홀더는 자체 속성으로 충분)
+        let metadata = if is_placeholder {
+            own_metadata
+        } else {
+            fs::metadata(&real_path)
+        };
+
+        match metadata {
+            Ok(metadata) => {
+                let is_dir = metadata.is_dir();
+                let mut entry = DirectoryEntry::new(name, real_path.to_string_lossy().to_string(), is_dir);
+                entry.is_cloud_placeholder = is_placeholder;
+                if !is_dir {
+                    entry.size = Some(metadata.len());
+                    entry.modified = metadata
+                        .modified()
+                        .ok()
+                        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                        .map(|duration| duration.as_secs());
+                }
+                listing.entries.push(entry);
+            }
+            Err(e) => listing.errors.push(DirectoryEntryError {
+                name,
+                path: path.to_string_lossy().to_string(),
+                error: AppError::from(e),
+            }),
+        }
+    }
+
+    Ok(listing)
+}
@@ -0,0 +1,229 @@
+use serde::Serialize;
+use std::fs::File;
+use std::io::BufReader;
+use tauri::AppHandle;
+use tiff::decoder::{Decoder as TiffDecoder, DecodingResult};
+use tiff::ColorType;
+
+use crate::formats::{is_pdf_file, is_tiff_file};
+use crate::thumbnail::{self, ThumbnailResult, ThumbnailSource};
+
+/// 페이지 썸네일 크기 (긴 변 px, 일반 썸네일과 같음)
+const PAGE_THUMBNAIL_SIZE: u32 = 320;
+
+/// 문서 페이지 정보
+#[derive(Debug, Clone, Serialize)]
+pub struct PageInfo {
+    pub index: usize,
+    pub width: u32,
+    pub height: u32,
+}
+
+fn open_tiff(file_path: &str) -> Result<TiffDecoder<BufReader<File>>, String> {
+    let file = File::open(file_path).map_err(|e| format!("Failed to open file: {}", e))?;
+    TiffDecoder::new(BufReader::new(file)).map_err(|e| format!("Failed to read TIFF: {}", e))
+}
+
+fn check_supported(file_path: &str) -> Result<(), String> {
+    if is_pdf_file(file_path) {
+        return Err(format!("PDF pages are not supported: {}", file_path));
+    }
+    if !is_tiff_file(file_path) {
+        return Err(format!("Not a multi-page document: {}", file_path));
+    }
+    Ok(())
+}
+
+// 파일 시스템 접근용 경로 (원격/압축 파일 항목은 스풀로 받은 경로)
+fn local_source(app: &AppHandle, file_path: &str) -> Result<String, String> {
+    let path = if crate::remote_source::is_remote_path(file_path) {
+        crate::remote_source::spool(app, file_path)?
+    } else if crate::archive_source::is_archive_path(file_path) {
+        crate::archive_source::spool(app, file_path)?
+    } else {
+        return Ok(crate::fs_path::to_fs_string(file_path));
+    };
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// 문서의 페이지 목록 (TIFF는 IFD마다 한 페이지)
+pub fn get_pages(app: &AppHandle, file_path: &str) -> Result<Vec<PageInfo>, String> {
+    check_supported(file_path)?;
+    read_pages(&local_source(app, file_path)?)
+}
+
+fn read_pages(source: &str) -> Result<Vec<PageInfo>, String> {
+    let mut decoder = open_tiff(source)?;
+
+    let mut pages = Vec::new();
+    loop {
+        let (width, height) = decoder
+            .dimensions()
+            .map_err(|e| format!("Failed to read TIFF page {}: {}", pages.len(), e))?;
+        pages.push(PageInfo {
+            index: pages.len(),
+            width,
+            height,
+        });
+        if !decoder.more_images() {
+            break;
+        }
+        decoder
+            .next_image()
+            .map_err(|e| format!("Failed to read TIFF page {}: {}", pages.len(), e))?;
+    }
+    Ok(pages)
+}
+
+// TIFF 페이지 디코딩 (8/16비트 Gray/RGB, 알파 포함)
+fn decode_tiff_page(file_path: &str, page: usize) -> Result<image::DynamicImage, String> {
+    use image::{DynamicImage, ImageBuffer};
+
+    let mut decoder = open_tiff(file_path)?;
+    decoder
+        .seek_to_image(page)
+        .map_err(|e| format!("TIFF page {} not found: {}", page, e))?;
+    let (width, height) = decoder.dimensions().map_err(|e| format!("Failed to read TIFF page: {}", e))?;
+    let color = decoder.colortype().map_err(|e| format!("Failed to read TIFF page: {}", e))?;
+    let data = decoder
+        .read_image()
+        .map_err(|e| format!("Failed to decode TIFF page {}: {}", page, e))?;
+
+    let unsupported = || format!("Unsupported TIFF page format: {:?}", color);
+    let img = match (color, data) {
+        (ColorType::Gray(8), DecodingResult::U8(data)) => {
+            DynamicImage::ImageLuma8(ImageBuffer::from_raw(width, height, data).ok_or_else(unsupported)?)
+        }
+        (ColorType::GrayA(8), DecodingResult::U8(data)) => {
+            DynamicImage::ImageLumaA8(ImageBuffer::from_raw(width, height, data).ok_or_else(unsupported)?)
+        }
+        (ColorType::RGB(8), DecodingResult::U8(data)) => {
+            DynamicImage::ImageRgb8(ImageBuffer::from_raw(width, height, data).ok_or_else(unsupported)?)
+        }
+        (ColorType::RGBA(8), DecodingResult::U8(data)) => {
+            DynamicImage::ImageRgba8(ImageBuffer::from_raw(width, height, data).ok_or_else(unsupported)?)
+        }
+        (ColorType::Gray(16), DecodingResult::U16(data)) => {
+            DynamicImage::ImageLuma16(ImageBuffer::from_raw(width, height, data).ok_or_else(unsupported)?)
+        }
+        (ColorType::RGB(16), DecodingResult::U16(data)) => {
+            DynamicImage::ImageRgb16(ImageBuffer::from_raw(width, height, data).ok_or_else(unsupported)?)
+        }
+        (ColorType::RGBA(16), DecodingResult::U16(data)) => {
+            DynamicImage::ImageRgba16(ImageBuffer::from_raw(width, height, data).ok_or_else(unsupported)?)
+        }
+        _ => return Err(unsupported()),
+    };
+    Ok(thumbnail::tonemap_to_8bit(img))
+}
+
+// 페이지 썸네일 크기 (PAGE_THUMBNAIL_SIZE보다 크면 축소만, 작은 페이지는 원래 크기)
+fn page_thumbnail_size(width: u32, height: u32) -> (u32, u32) {
+    if width > PAGE_THUMBNAIL_SIZE || height > PAGE_THUMBNAIL_SIZE {
+        thumbnail::fit_within(width, height, PAGE_THUMBNAIL_SIZE)
+    } else {
+        (width, height)
+    }
+}
+
+/// 페이지 썸네일 (첫 페이지는 일반 썸네일과 같고, 나머지는 페이지 번호를 캐시 키에 포함)
+pub async fn generate_page_thumbnail(
+    app_handle: &tauri::AppHandle,
+    file_path: &str,
+    page: usize,
+) -> Result<ThumbnailResult, String> {
+    check_supported(file_path)?;
+    if page == 0 {
+        return thumbnail::generate_thumbnail(app_handle, file_path).await;
+    }
+
+    let source = thumbnail::resolve_source(app_handle, file_path).await?;
+    let mtime = thumbnail::get_file_mtime(&source)?;
+    let page_path = format!("{}#page={}", file_path, page);
+    let cache_key = thumbnail::generate_cache_key(&page_path, mtime);
+    let cache_path = thumbnail::get_cache_path_for(app_handle, &page_path, mtime)?;
+
+    // 같은 페이지를 만드는 중이면 끝날 때까지 기다렸다가 캐시에서 읽음
+    let _cache_lock = thumbnail::lock_cache_key(&cache_key).await;
+
+    if let Some((webp_data, width, height)) = thumbnail::read_cached_webp(&cache_path, &page_path)? {
+        return Ok(ThumbnailResult {
+            path: file_path.to_string(),
+            thumbnail_base64: thumbnail::encode_to_base64(&webp_data),
+            width,
+            height,
+            source: ThumbnailSource::Cache,
+            exif_metadata: None,
+            has_alpha: false,
+            is_low_quality: false,
+        });
+    }
+
+    let (pixels, width, height) = tokio::task::spawn_blocking(move || {
+        let img = decode_tiff_page(&source, page)?;
+        let (src_width, src_height) = (img.width(), img.height());
+        let (width, height) = page_thumbnail_size(src_width, src_height);
+
+        // 투명 페이지는 썸네일 배경색 위에 합성
+        let rgb = if img.color().has_alpha() {
+            let background = crate::settings::current().thumbnail_background_rgb();
+            thumbnail::composite_over_background(&img.into_rgba8().into_raw(), background)
+        } else {
+            img.into_rgb8().into_raw()
+        };
+        let resized = if (width, height) == (src_width, src_height) {
+            rgb
+        } else {
+            thumbnail::resize_fast(rgb, src_width, src_height, width, height, fast_image_resize::PixelType::U8x3)?
+        };
+        Ok::<_, String>((resized, width, height))
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))??;
+
+    let webp_data = thumbnail::encode_thumbnail_to_webp(&pixels, width, height, false, 60.0)?;
+    crate::shutdown::write_cache_atomic(&cache_path, &webp_data).map_err(|e| format!("Failed to write cache: {}", e))?;
+    // 원본 경로로 기록 (원본이 지워지거나 바뀌면 캐시 정리 대상)
+    crate::thumbnail_cache::record_entry(app_handle, &cache_key, file_path, mtime);
+
+    Ok(ThumbnailResult {
+        path: file_path.to_string(),
+        thumbnail_base64: thumbnail::encode_to_base64(&webp_data),
+        width,
+        height,
+        source: ThumbnailSource::DctScaling,
+        exif_metadata: None,
+        has_alpha: false,
+        is_low_quality: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tiff::encoder::{colortype, TiffEncoder};
+
+    #[test]
+    fn test_read_pages() {
+        let path = std::env::temp_dir().join(format!("pixengine-pages-{}.tif", std::process::id()));
+        {
+            let mut encoder = TiffEncoder::new(File::create(&path).unwrap()).unwrap();
+            encoder.write_image::<colortype::RGB8>(4, 2, &[0u8; 4 * 2 * 3]).unwrap();
+            encoder.write_image::<colortype::Gray8>(3, 5, &[0u8; 3 * 5]).unwrap();
+            encoder.write_image::<colortype::RGB8>(1, 1, &[0u8; 3]).unwrap();
+        }
+
+        let pages = read_pages(&path.to_string_lossy()).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(pages.len(), 3);
+        assert_eq!((pages[1].index, pages[1].width, pages[1].height), (1, 3, 5));
+    }
+
+    #[test]
+    fn test_page_thumbnail_size() {
+        assert_eq!(page_thumbnail_size(2480, 3508), (226, 320));
+        // 작은 페이지는 확대하지 않음
+        assert_eq!(page_thumbnail_size(200, 100), (200, 100));
+        assert_eq!(page_thumbnail_size(320, 320), (320, 320));
+    }
+}
@@ -0,0 +1,167 @@
+use serde::Serialize;
+use std::sync::mpsc::{self, Sender};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// 장치 변경 후 마운트가 끝날 때까지 대기 (연속 이벤트는 한 번으로 합침)
+const SETTLE_DELAY: Duration = Duration::from_millis(700);
+
+/// 드라이브 변경 감시 시작
+/// 이동식 드라이브 연결/해제 시 `drives-changed` 이벤트로 현재 드라이브 목록 전송
+pub fn spawn<T, F>(app: AppHandle, list_drives: F)
+where
+    T: Serialize + Clone,
+    F: Fn() -> T + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel::<()>();
+
+    if let Err(e) = start_device_listener(sender) {
+        tracing::error!("Failed to start drive watcher: {}", e);
+        return;
+    }
+
+    std::thread::spawn(move || {
+        while receiver.recv().is_ok() {
+            std::thread::sleep(SETTLE_DELAY);
+            while receiver.try_recv().is_ok() {}
+
+            crate::network_path::invalidate_mount_table();
+            let _ = app.emit("drives-changed", list_drives());
+        }
+    });
+}
+
+/// Windows: 숨김 윈도우로 WM_DEVICECHANGE 수신
+/// (메시지 전용 윈도우는 브로드캐스트를 받지 못하므로 보이지 않는 최상위 윈도우 사용)
+#[cfg(target_os = "windows")]
+fn start_device_listener(sender: Sender<()>) -> Result<(), String> {
+    use std::sync::{Mutex, OnceLock};
+    use windows::core::PCWSTR;
+    use windows::Win32::Foundation::{HINSTANCE, HWND, LPARAM, LRESULT, WPARAM};
+    use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, RegisterClassW,
+        TranslateMessage, HMENU, MSG, WINDOW_EX_STYLE, WINDOW_STYLE, WNDCLASSW,
+    };
+
+    // <dbt.h>
+    const WM_DEVICECHANGE: u32 = 0x0219;
+    const DBT_DEVICEARRIVAL: usize = 0x8000;
+    const DBT_DEVICEREMOVECOMPLETE: usize = 0x8004;
+
+    static DEVICE_SENDER: OnceLock<Mutex<Sender<()>>> = OnceLock::new();
+
+    unsafe extern "system" fn wndproc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+        if msg == WM_DEVICECHANGE
+            && (wparam.0 == DBT_DEVICEARRIVAL || wparam.0 == DBT_DEVICEREMOVECOMPLETE)
+        {
+            if let Some(Ok(sender)) = DEVICE_SENDER.get().map(|s| s.lock()) {
+                let _ = sender.send(());
+            }
+        }
+        DefWindowProcW(hwnd, msg, wparam, lparam)
+    }
+
+    DEVICE_SENDER
+        .set(Mutex::new(sender))
+        .map_err(|_| "Drive watcher already started".to_string())?;
+
+    std::thread::spawn(|| unsafe {
+        let class_name = crate::drives::to_wide("PixEngineDriveWatcher");
+
+        let instance = match GetModuleHandleW(None) {
+            Ok(module) => HINSTANCE::from(module),
+            Err(e) => {
+                tracing::error!("Failed to get module handle for drive watcher: {}", e);
+                return;
+            }
+        };
+
+        let class = WNDCLASSW {
+            lpfnWndProc: Some(wndproc),
+            hInstance: instance,
+            lpszClassName: PCWSTR(class_name.as_ptr()),
+            ..Default::default()
+        };
+
+        if RegisterClassW(&class) == 0 {
+            tracing::error!("Failed to register drive watcher window class");
+            return;
+        }
+
+        // 윈도우는 스레드가 끝날 때까지 유지 (앱 종료 시 함께 정리됨)
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE(0),
+            PCWSTR(class_name.as_ptr()),
+            PCWSTR(class_name.as_ptr()),
+            WINDOW_STYLE(0),
+            0,
+            0,
+            0,
+            0,
+            HWND::default(),
+            HMENU::default(),
+            instance,
+            None,
+        );
+
+        if let Err(e) = hwnd {
+            tracing::error!("Failed to create drive watcher window: {}", e);
+            return;
+        }
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, HWND::default(), 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+    });
+
+    Ok(())
+}
+
+/// macOS: /Volumes 디렉토리 감시 (볼륨 마운트/언마운트 시 항목 추가/삭제)
+#[cfg(target_os = "macos")]
+fn start_device_listener(sender: Sender<()>) -> Result<(), String> {
+    use notify::{RecursiveMode, Watcher};
+
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res {
+            if matches!(event.kind, notify::EventKind::Create(_) | notify::EventKind::Remove(_)) {
+                let _ = sender.send(());
+            }
+        }
+    })
+    .map_err(|e| format!("Failed to create watcher: {}", e))?;
+
+    watcher
+        .watch(std::path::Path::new("/Volumes"), RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch /Volumes: {}", e))?;
+
+    // 앱 수명 동안 감시 유지
+    std::mem::forget(watcher);
+    Ok(())
+}
+
+/// Linux: 마운트 테이블 폴링 (/proc/self/mounts 내용이 바뀌면 알림)
+#[cfg(target_os = "linux")]
+fn start_device_listener(sender: Sender<()>) -> Result<(), String> {
+    const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+    let read_mounts = || std::fs::read_to_string("/proc/self/mounts").unwrap_or_default();
+    let mut last = read_mounts();
+
+    std::thread::spawn(move || loop {
+        std::thread::sleep(POLL_INTERVAL);
+
+        let current = read_mounts();
+        if current != last {
+            last = current;
+            if sender.send(()).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(())
+}
@@ -0,0 +1,242 @@
+use serde::Serialize;
+
+use crate::network_path::{run_with_timeout, NETWORK_STAT_TIMEOUT};
+
+/// 드라이브 상세 정보 (용량, 파일 시스템, 종류)
+#[derive(Debug, Clone, Serialize)]
+pub struct DriveDetails {
+    pub name: String,
+    pub path: String,
+    pub total_bytes: Option<u64>,
+    pub free_bytes: Option<u64>, // 현재 사용자가 쓸 수 있는 여유 공간
+    pub file_system: Option<String>,
+    pub is_removable: bool,
+    pub is_network: bool,
+}
+
+/// 경로가 속한 볼륨의 용량 정보
+#[derive(Debug, Clone, Serialize)]
+pub struct SpaceInfo {
+    pub total_bytes: u64,
+    pub free_bytes: u64,
+}
+
+/// 네트워크 파일 시스템 이름 목록
+const NETWORK_FILE_SYSTEMS: &[&str] = &[
+    "nfs", "nfs4", "cifs", "smbfs", "smb2", "smb3", "afpfs", "webdav",
+    "davfs", "fuse.sshfs", "sshfs", "9p", "ncpfs", "fuse.rclone",
+];
+
+/// 파일 시스템 이름으로 네트워크 여부 확인
+pub fn is_network_file_system(fs_type: &str) -> bool {
+    let fs_type = fs_type.to_lowercase();
+    NETWORK_FILE_SYSTEMS.contains(&fs_type.as_str())
+}
+
+/// 드라이브 상세 정보 가져오기
+/// 응답 없는 네트워크 드라이브에서 멈추지 않도록 용량 조회에 타임아웃 적용 (시간 초과 시 용량 없음)
+pub fn get_drive_details(name: &str, path: &str) -> DriveDetails {
+    let space_path = path.to_string();
+    let space = run_with_timeout(NETWORK_STAT_TIMEOUT, move || get_space_info(&space_path))
+        .ok()
+        .and_then(Result::ok);
+    let (file_system, is_removable, is_network) = get_volume_kind(path);
+
+    DriveDetails {
+        name: name.to_string(),
+        path: path.to_string(),
+        total_bytes: space.as_ref().map(|s| s.total_bytes),
+        free_bytes: space.as_ref().map(|s| s.free_bytes),
+        file_system,
+        is_removable,
+        is_network,
+    }
+}
+
+/// Windows API용 null 종료 UTF-16 문자열
+#[cfg(target_os = "windows")]
+pub fn to_wide(value: &str) -> Vec<u16> {
+    value.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// Windows: 볼륨 용량 조회 (GetDiskFreeSpaceExW)
+#[cfg(target_os = "windows")]
+pub fn get_space_info(path: &str) -> Result<SpaceInfo, String> {
+    use windows::core::PCWSTR;
+    use windows::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    let wide_path = to_wide(path);
+    let mut free_available: u64 = 0;
+    let mut total: u64 = 0;
+
+    unsafe {
+        GetDiskFreeSpaceExW(
+            PCWSTR(wide_path.as_ptr()),
+            Some(&mut free_available as *mut u64),
+            Some(&mut total as *mut u64),
+            None,
+        )
+        .map_err(|e| format!("Failed to get disk space: {}", e))?;
+    }
+
+    Ok(SpaceInfo {
+        total_bytes: total,
+        free_bytes: free_available,
+    })
+}
+
+/// Unix: 볼륨 용량 조회 (statvfs)
+#[cfg(unix)]
+pub fn get_space_info(path: &str) -> Result<SpaceInfo, String> {
+    use std::ffi::CString;
+
+    let c_path = CString::new(path).map_err(|e| format!("Invalid path: {}", e))?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+
+    let result = unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) };
+    if result != 0 {
+        return Err(format!(
+            "Failed to get disk space: {}",
+            std::io::Error::last_os_error()
+        ));
+    }
+
+    let block_size = stat.f_frsize as u64;
+    Ok(SpaceInfo {
+        total_bytes: stat.f_blocks as u64 * block_size,
+        free_bytes: stat.f_bavail as u64 * block_size,
+    })
+}
+
+/// Windows: 파일 시스템 이름, 이동식/네트워크 여부 (GetVolumeInformationW, GetDriveTypeW)
+#[cfg(target_os = "windows")]
+fn get_volume_kind(path: &str) -> (Option<String>, bool, bool) {
+    use windows::core::PCWSTR;
+    use windows::Win32::Storage::FileSystem::{GetDriveTypeW, GetVolumeInformationW};
+
+    // GetDriveTypeW 반환값
+    const DRIVE_REMOVABLE: u32 = 2;
+    const DRIVE_REMOTE: u32 = 4;
+    const DRIVE_CDROM: u32 = 5;
+
+    let root = to_wide(path);
+
+    let mut fs_name = [0u16; 64];
+    let file_system = unsafe {
+        GetVolumeInformationW(PCWSTR(root.as_ptr()), None, None, None, None, Some(&mut fs_name))
+    }
+    .ok()
+    .map(|_| {
+        let len = fs_name.iter().position(|&c| c == 0).unwrap_or(fs_name.len());
+        String::from_utf16_lossy(&fs_name[..len])
+    })
+    .filter(|name| !name.is_empty());
+
+    let drive_type = unsafe { GetDriveTypeW(PCWSTR(root.as_ptr())) };
+
+    (
+        file_system,
+        drive_type == DRIVE_REMOVABLE || drive_type == DRIVE_CDROM,
+        drive_type == DRIVE_REMOTE,
+    )
+}
+
+/// macOS: 파일 시스템 이름, 이동식/네트워크 여부 (statfs)
+#[cfg(target_os = "macos")]
+fn get_volume_kind(path: &str) -> (Option<String>, bool, bool) {
+    use std::ffi::{CStr, CString};
+
+    // <sys/mount.h> MNT_REMOVABLE (이동식 미디어에 마운트됨)
+    const MNT_REMOVABLE: u32 = 0x0000_0200;
+
+    let Ok(c_path) = CString::new(path) else {
+        return (None, false, false);
+    };
+
+    let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return (None, false, false);
+    }
+
+    let fs_type = unsafe { CStr::from_ptr(stat.f_fstypename.as_ptr()) }
+        .to_string_lossy()
+        .to_string();
+
+    let is_local = stat.f_flags & libc::MNT_LOCAL as u32 != 0;
+    let is_network = !is_local || is_network_file_system(&fs_type);
+    let is_removable = stat.f_flags & MNT_REMOVABLE != 0;
+
+    (Some(fs_type), is_removable, is_network)
+}
+
+/// Linux: /proc/self/mounts 항목 (장치, 마운트 지점, 파일 시스템)
+#[cfg(target_os = "linux")]
+fn read_mounts() -> Vec<(String, String, String)> {
+    let mounts = std::fs::read_to_string("/proc/self/mounts").unwrap_or_default();
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let device = fields.next()?;
+            let mount_point = fields.next()?.replace("\\040", " ");
+            let fs_type = fields.next()?;
+            Some((device.to_string(), mount_point, fs_type.to_string()))
+        })
+        .collect()
+}
+
+/// Linux: /proc/self/mounts에서 마운트 정보 찾기
+#[cfg(target_os = "linux")]
+fn find_mount_entry(path: &str) -> Option<(String, String)> {
+    let path = std::path::Path::new(path);
+
+    // 가장 긴 마운트 포인트가 해당 경로의 볼륨
+    read_mounts()
+        .into_iter()
+        .filter(|(_, mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(_, mount_point, _)| mount_point.len())
+        .map(|(device, _, fs_type)| (device, fs_type))
+}
+
+/// Linux: 블록 장치가 이동식인지 확인 (/sys/block/<disk>/removable)
+#[cfg(target_os = "linux")]
+fn is_removable_device(device: &str) -> bool {
+    let Some(dev_name) = device.strip_prefix("/dev/") else {
+        return false;
+    };
+
+    // 파티션 이름(sdb1, mmcblk0p1)에서 디스크 이름 추출
+    let disk = if dev_name.starts_with("mmcblk") || dev_name.starts_with("nvme") {
+        dev_name.split('p').next().unwrap_or(dev_name).to_string()
+    } else {
+        dev_name.trim_end_matches(|c: char| c.is_ascii_digit()).to_string()
+    };
+
+    std::fs::read_to_string(format!("/sys/block/{}/removable", disk))
+        .map(|v| v.trim() == "1")
+        .unwrap_or(false)
+}
+
+/// Linux: 마운트 지점별 네트워크 볼륨 여부 (statvfs 없이 마운트 테이블만 확인하므로 블로킹 없음)
+#[cfg(target_os = "linux")]
+pub fn read_mount_table() -> Vec<(std::path::PathBuf, bool)> {
+    read_mounts()
+        .into_iter()
+        .map(|(device, mount_point, fs_type)| {
+            (mount_point.into(), is_network_file_system(&fs_type) || device.starts_with("//"))
+        })
+        .collect()
+}
+
+/// Linux: 파일 시스템 이름, 이동식/네트워크 여부
+#[cfg(target_os = "linux")]
+fn get_volume_kind(path: &str) -> (Option<String>, bool, bool) {
+    match find_mount_entry(path) {
+        Some((device, fs_type)) => {
+            let is_network = is_network_file_system(&fs_type) || device.starts_with("//");
+            let is_removable = is_removable_device(&device);
+            (Some(fs_type), is_removable, is_network)
+        }
+        None => (None, false, false),
+    }
+}
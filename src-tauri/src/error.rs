@@ -0,0 +1,220 @@
+use serde::Serialize;
+use std::fmt;
+use std::io;
+
+use crate::file_lock::READ_ONLY;
+use crate::network_path::SHARE_UNREACHABLE;
+use crate::remote_source::RemoteError;
+
+/// 대상(파일, 폴더, 작업 등)이 없을 때 메시지 접두사 (NotFound로 분류)
+pub const NOT_FOUND: &str = "Not found";
+
+/// 사용자가 작업을 취소했을 때 메시지 접두사 (Cancelled로 분류)
+pub const CANCELLED: &str = "Cancelled";
+
+/// 커맨드 공통 에러 타입
+/// 프론트엔드는 kind로 분기하고 message는 로그/기본 표시용으로 사용
+/// 직렬화 형태: { "kind": "not_found", "message": "..." }
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AppError {
+    NotFound { message: String },
+    PermissionDenied { message: String },
+    /// 읽기 전용 파일이나 쓰기 금지된 볼륨
+    ReadOnly { message: String },
+    AlreadyExists { message: String },
+    UnsupportedFormat { message: String },
+    InvalidInput { message: String },
+    NetworkUnreachable { message: String },
+    /// 원격 서버 로그인 실패
+    AuthFailed { message: String },
+    /// 처음 보는 SFTP 호스트 키 (사용자가 fingerprint를 확인하면 소스 설정의 host_key로 다시 연결)
+    UnknownHostKey { fingerprint: String, message: String },
+    /// 알려진 키와 다른 SFTP 호스트 키 (연결하지 않음)
+    HostKeyChanged { fingerprint: String, message: String },
+    Cancelled { message: String },
+    Io { code: Option<i32>, message: String }, // code: OS 에러 코드
+    Other { message: String },
+}
+
+impl AppError {
+    pub fn not_found(message: impl Into<String>) -> Self {
+        AppError::NotFound { message: message.into() }
+    }
+
+    pub fn already_exists(message: impl Into<String>) -> Self {
+        AppError::AlreadyExists { message: message.into() }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            AppError::NotFound { message }
+            | AppError::PermissionDenied { message }
+            | AppError::ReadOnly { message }
+            | AppError::AlreadyExists { message }
+            | AppError::UnsupportedFormat { message }
+            | AppError::InvalidInput { message }
+            | AppError::NetworkUnreachable { message }
+            | AppError::AuthFailed { message }
+            | AppError::UnknownHostKey { message, .. }
+            | AppError::HostKeyChanged { message, .. }
+            | AppError::Cancelled { message }
+            | AppError::Io { message, .. }
+            | AppError::Other { message } => message,
+        }
+    }
+
+    /// io::ErrorKind 기준 분류
+    fn from_io_kind(kind: io::ErrorKind, code: Option<i32>, message: String) -> Self {
+        match kind {
+            io::ErrorKind::NotFound => AppError::NotFound { message },
+            io::ErrorKind::PermissionDenied => AppError::PermissionDenied { message },
+            io::ErrorKind::AlreadyExists => AppError::AlreadyExists { message },
+            io::ErrorKind::InvalidInput => AppError::InvalidInput { message },
+            io::ErrorKind::TimedOut
+            | io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::NotConnected => AppError::NetworkUnreachable { message },
+            _ => AppError::Io { code, message },
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<io::Error> for AppError {
+    fn from(e: io::Error) -> Self {
+        AppError::from_io_kind(e.kind(), e.raw_os_error(), e.to_string())
+    }
+}
+
+/// 메시지 끝의 "(os error N)"에서 OS 에러 코드 추출
+pub(crate) fn parse_os_error_code(message: &str) -> Option<i32> {
+    let start = message.rfind("(os error ")? + "(os error ".len();
+    let end = message[start..].find(')')? + start;
+    message[start..end].parse().ok()
+}
+
+/// 모듈 내부의 String 에러 분류
+/// io::Error가 포함된 메시지는 OS 에러 코드로, 나머지는 메시지 접두사로만 판별 (경로 등 본문 내용은 보지 않음)
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        if message.starts_with(SHARE_UNREACHABLE) {
+            return AppError::NetworkUnreachable { message };
+        }
+        if message.starts_with(READ_ONLY) {
+            return AppError::ReadOnly { message };
+        }
+        if message.starts_with(NOT_FOUND) || message.starts_with("Path does not exist") {
+            return AppError::NotFound { message };
+        }
+        if message.starts_with(CANCELLED) {
+            return AppError::Cancelled { message };
+        }
+
+        if let Some(code) = parse_os_error_code(&message) {
+            let kind = io::Error::from_raw_os_error(code).kind();
+            return AppError::from_io_kind(kind, Some(code), message);
+        }
+
+        if message.starts_with("Permission denied") {
+            AppError::PermissionDenied { message }
+        } else if message.starts_with("Unsupported") {
+            AppError::UnsupportedFormat { message }
+        } else if message.starts_with("Invalid") {
+            AppError::InvalidInput { message }
+        } else {
+            AppError::Other { message }
+        }
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        AppError::from(message.to_string())
+    }
+}
+
+impl From<RemoteError> for AppError {
+    fn from(e: RemoteError) -> Self {
+        let message = e.to_string();
+        match e {
+            RemoteError::Connection(_) => AppError::NetworkUnreachable { message },
+            RemoteError::Auth(_) => AppError::AuthFailed { message },
+            RemoteError::UnknownHostKey { fingerprint, .. } => AppError::UnknownHostKey { fingerprint, message },
+            RemoteError::HostKeyChanged { fingerprint, .. } => AppError::HostKeyChanged { fingerprint, message },
+            RemoteError::NotFound(_) => AppError::NotFound { message },
+            RemoteError::Other(_) => AppError::Other { message },
+        }
+    }
+}
+
+impl From<tokio::task::JoinError> for AppError {
+    fn from(e: tokio::task::JoinError) -> Self {
+        AppError::Other { message: format!("Task failed: {}", e) }
+    }
+}
+
+impl From<tauri::Error> for AppError {
+    fn from(e: tauri::Error) -> Self {
+        AppError::Other { message: e.to_string() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_string() {
+        assert!(matches!(AppError::from("Path does not exist: /a".to_string()), AppError::NotFound { .. }));
+        assert!(matches!(
+            AppError::from(format!("{}: //server/share", SHARE_UNREACHABLE)),
+            AppError::NetworkUnreachable { .. }
+        ));
+        assert!(matches!(
+            AppError::from(format!("{}: /a.jpg (os error 13)", READ_ONLY)),
+            AppError::ReadOnly { .. }
+        ));
+        assert!(matches!(AppError::from("Something else".to_string()), AppError::Other { .. }));
+        assert!(matches!(AppError::from(format!("{}: job 3", NOT_FOUND)), AppError::NotFound { .. }));
+        assert!(matches!(AppError::from(format!("{}: export", CANCELLED)), AppError::Cancelled { .. }));
+        assert!(matches!(AppError::from("Unsupported PSD bit depth: 32".to_string()), AppError::UnsupportedFormat { .. }));
+
+        // 경로에 들어 있는 단어로는 분류하지 않음
+        assert!(matches!(
+            AppError::from("Failed to decode: /photos/not found/cancelled.jpg".to_string()),
+            AppError::Other { .. }
+        ));
+        assert!(matches!(
+            AppError::from("Metadata database error: disk I/O error".to_string()),
+            AppError::Other { .. }
+        ));
+
+        // ENOENT / ERROR_FILE_NOT_FOUND 모두 2
+        let io_message = format!("Failed to open file: {}", io::Error::from_raw_os_error(2));
+        assert!(matches!(AppError::from(io_message), AppError::NotFound { .. }));
+    }
+
+    #[test]
+    fn test_serialize_tagged() {
+        let json = serde_json::to_value(AppError::not_found("missing")).unwrap();
+        assert_eq!(json["kind"], "not_found");
+        assert_eq!(json["message"], "missing");
+
+        let json = serde_json::to_value(AppError::from(RemoteError::UnknownHostKey {
+            host: "nas".to_string(),
+            fingerprint: "SHA256:abc".to_string(),
+        }))
+        .unwrap();
+        assert_eq!(json["kind"], "unknown_host_key");
+        assert_eq!(json["fingerprint"], "SHA256:abc");
+    }
+}
@@ -0,0 +1,277 @@
+use image::DynamicImage;
+use serde::{Deserialize, Serialize};
+use rayon::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::color_profile::{self, PixelLayout};
+use crate::formats;
+use crate::jobs::Job;
+use crate::thumbnail;
+use crate::watermark::{self, WatermarkOptions};
+
+/// 빠른 내보내기 임시 폴더를 지우는 기준 (공유 앱이 파일을 읽을 시간)
+const QUICK_EXPORT_RETENTION: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// 내보내기 포맷 (Original은 원본 포맷 유지, 변환이 필요하면 같은 계열로 재인코딩)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Original,
+    Jpeg,
+    Png,
+    Webp,
+}
+
+/// 내보내기 옵션 (크기 조정/포맷 변환)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    /// 긴 변 최대 픽셀, None이면 원본 크기
+    pub max_long_edge: Option<u32>,
+    /// JPEG/WebP 품질 (1~100)
+    pub quality: u8,
+    /// 개인정보 메타데이터 제거 (원본 그대로 쓰는 JPEG은 메타데이터만 정리, 그 외 포맷은 재인코딩)
+    /// 재인코딩한 파일에는 원래 메타데이터가 들어가지 않음
+    pub strip_metadata: bool,
+    /// 워터마크 (있으면 항상 재인코딩)
+    pub watermark: Option<WatermarkOptions>,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            format: ExportFormat::Original,
+            max_long_edge: None,
+            quality: 90,
+            strip_metadata: false,
+            watermark: None,
+        }
+    }
+}
+
+impl ExportOptions {
+    /// 원본 파일을 그대로 쓰는지 (디코딩/재인코딩 없음)
+    pub fn is_passthrough(&self, path: &Path) -> bool {
+        self.format == ExportFormat::Original
+            && self.max_long_edge.is_none()
+            && self.watermark.is_none()
+            && (!self.strip_metadata || formats::is_jpeg_file(&path.to_string_lossy()))
+    }
+
+    // 실제 출력 포맷 (Original이면 원본 확장자로 판단, 그 외 포맷은 JPEG)
+    fn output_format(&self, path: &Path) -> ExportFormat {
+        if self.format != ExportFormat::Original {
+            return self.format;
+        }
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        match extension.as_str() {
+            "png" => ExportFormat::Png,
+            "webp" => ExportFormat::Webp,
+            _ => ExportFormat::Jpeg,
+        }
+    }
+}
+
+/// 공유용 빠른 내보내기 프리셋
+/// 변환한 파일에는 메타데이터가 들어가지 않음 (GPS 위치 등 제거)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuickExportPreset {
+    /// 이메일 첨부 (긴 변 2048px JPEG, 품질 80)
+    Email,
+    /// 메신저 (긴 변 1280px JPEG, 품질 75)
+    Messenger,
+    /// 웹 게시 (긴 변 1600px WebP, 품질 80)
+    Web,
+}
+
+impl QuickExportPreset {
+    pub fn options(self) -> ExportOptions {
+        let (format, max_long_edge, quality) = match self {
+            QuickExportPreset::Email => (ExportFormat::Jpeg, 2048, 80),
+            QuickExportPreset::Messenger => (ExportFormat::Jpeg, 1280, 75),
+            QuickExportPreset::Web => (ExportFormat::Webp, 1600, 80),
+        };
+        ExportOptions {
+            format,
+            max_long_edge: Some(max_long_edge),
+            quality,
+            ..ExportOptions::default()
+        }
+    }
+}
+
+/// 내보낸 파일 이름 (변환하면 확장자 변경)
+pub fn output_file_name(path: &Path, options: &ExportOptions) -> String {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if options.is_passthrough(path) {
+        return file_name;
+    }
+
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or(file_name);
+    let extension = match options.output_format(path) {
+        ExportFormat::Png => "png",
+        ExportFormat::Webp => "webp",
+        _ => "jpg",
+    };
+    format!("{}.{}", stem, extension)
+}
+
+// 원본 디코딩 (RAW는 내장 JPEG 미리보기), EXIF 방향 적용
+pub fn decode(path: &Path) -> Result<(DynamicImage, Option<Vec<u8>>), String> {
+    let source = crate::fs_path::to_fs_string(&path.to_string_lossy());
+
+    let (mut img, icc_profile) = if formats::is_raw_file(&source) {
+        let preview = thumbnail::extract_raw_preview(&source)?;
+        let img = image::load_from_memory(&preview)
+            .map_err(|e| format!("Failed to decode RAW preview: {}", e))?;
+        (img, None)
+    } else if formats::is_psd_file(&source) {
+        (crate::psd::decode(&source)?.0, None)
+    } else {
+        thumbnail::open_image_with_icc(&source)?
+    };
+
+    // 출력 파일에는 EXIF가 없으므로 픽셀을 회전
+    let orientation = thumbnail::extract_exif_metadata(&source)
+        .map(|metadata| metadata.orientation)
+        .unwrap_or(1);
+    if let Some(orientation) = image::metadata::Orientation::from_exif(orientation) {
+        img.apply_orientation(orientation);
+    }
+
+    Ok((thumbnail::tonemap_to_8bit(img), icc_profile))
+}
+
+/// 내보내기 옵션대로 변환한 파일 내용 (변환이 없으면 원본 그대로)
+pub fn render(path: &Path, options: &ExportOptions) -> Result<Vec<u8>, String> {
+    use fast_image_resize::PixelType;
+
+    if options.is_passthrough(path) {
+        let data = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if options.strip_metadata {
+            return crate::metadata_strip::strip_jpeg(&data, &crate::metadata_strip::StripOptions::default());
+        }
+        return Ok(data);
+    }
+
+    let format = options.output_format(path);
+    let (img, icc_profile) = decode(path)?;
+    let (src_width, src_height) = (img.width(), img.height());
+    let (width, height) = match options.max_long_edge {
+        Some(max) if src_width.max(src_height) > max => thumbnail::fit_within(src_width, src_height, max),
+        _ => (src_width, src_height),
+    };
+
+    // JPEG은 알파가 없으므로 흰 배경에 합성
+    let keep_alpha = img.color().has_alpha() && format != ExportFormat::Jpeg;
+    let (layout, pixel_type, mut pixels) = if img.color().has_alpha() {
+        (PixelLayout::Rgba8, PixelType::U8x4, img.into_rgba8().into_raw())
+    } else {
+        (PixelLayout::Rgb8, PixelType::U8x3, img.into_rgb8().into_raw())
+    };
+    if (width, height) != (src_width, src_height) {
+        pixels = thumbnail::resize_fast(pixels, src_width, src_height, width, height, pixel_type)?;
+    }
+    color_profile::convert_to_srgb(icc_profile.as_deref(), &mut pixels, layout);
+
+    if let Some(watermark) = &options.watermark {
+        let channels = if layout == PixelLayout::Rgba8 { 4 } else { 3 };
+        watermark::apply(&mut pixels, width, height, channels, watermark)?;
+    }
+
+    if layout == PixelLayout::Rgba8 && !keep_alpha {
+        pixels = thumbnail::composite_over_background(&pixels, [255, 255, 255]);
+    }
+
+    let quality = options.quality.clamp(1, 100);
+    match format {
+        ExportFormat::Png => {
+            let color = if keep_alpha { image::ExtendedColorType::Rgba8 } else { image::ExtendedColorType::Rgb8 };
+            let mut data = Vec::new();
+            image::ImageEncoder::write_image(
+                image::codecs::png::PngEncoder::new(&mut data),
+                &pixels,
+                width,
+                height,
+                color,
+            )
+            .map_err(|e| format!("Failed to encode PNG: {}", e))?;
+            Ok(data)
+        }
+        ExportFormat::Webp => thumbnail::encode_thumbnail_to_webp(&pixels, width, height, keep_alpha, quality as f32),
+        _ => thumbnail::encode_thumbnail_to_jpeg_with_quality(&pixels, width, height, quality),
+    }
+}
+
+// 빠른 내보내기 임시 폴더 (호출마다 하위 폴더 생성)
+fn quick_export_root() -> PathBuf {
+    std::env::temp_dir().join("pixengine-quick-export")
+}
+
+// 오래된 빠른 내보내기 폴더 정리
+fn clean_quick_exports(root: &Path) {
+    let Ok(entries) = fs::read_dir(root) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let expired = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > QUICK_EXPORT_RETENTION);
+        if expired {
+            let _ = fs::remove_dir_all(entry.path());
+        }
+    }
+}
+
+/// 프리셋으로 임시 폴더에 내보내고 결과 파일 경로 반환 (공유/첨부용, 작업이 취소되면 오류)
+pub fn quick_export(paths: &[String], preset: QuickExportPreset, job: &Job) -> Result<Vec<PathBuf>, String> {
+    let root = quick_export_root();
+    clean_quick_exports(&root);
+
+    let dir = root.join(chrono::Local::now().format("%Y%m%d-%H%M%S-%3f").to_string());
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create export folder: {}", e))?;
+
+    let options = ExportOptions {
+        watermark: crate::settings::current().quick_export_watermarks.get(&preset).cloned(),
+        ..preset.options()
+    };
+    let rendered: Vec<(String, Vec<u8>)> = paths
+        .par_iter()
+        .map(|path| {
+            if job.is_cancelled() {
+                return Err(format!("{}: export", crate::error::CANCELLED));
+            }
+            let source = Path::new(path);
+            let data = render(source, &options).map_err(|e| format!("{}: {}", path, e))?;
+            job.advance(paths.len());
+            Ok((output_file_name(source, &options), data))
+        })
+        .collect::<Result<_, String>>()?;
+
+    // 같은 이름은 _1, _2 ... 로 구분
+    rendered
+        .into_iter()
+        .map(|(file_name, data)| {
+            let target = crate::import::unique_destination(dir.join(file_name));
+            fs::write(&target, data).map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+            Ok(target)
+        })
+        .collect()
+}
@@ -0,0 +1,241 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::Manager;
+
+/// 최근 폴더 최대 보관 개수
+const MAX_RECENT_FOLDERS: usize = 20;
+
+/// 파일 읽기/쓰기 동시 실행 방지
+static FOLDERS_FILE_LOCK: Mutex<()> = Mutex::new(());
+
+/// 즐겨찾기 폴더
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FavoriteFolder {
+    pub path: String,
+    pub display_name: Option<String>,
+    pub added_at: i64,
+}
+
+/// 최근 연 폴더
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentFolder {
+    pub path: String,
+    pub opened_at: i64,
+}
+
+/// 즐겨찾기 + 존재 여부 (목록 조회용)
+#[derive(Debug, Clone, Serialize)]
+pub struct FavoriteFolderStatus {
+    pub path: String,
+    pub name: String, // display_name이 없으면 폴더명
+    pub display_name: Option<String>,
+    pub added_at: i64,
+    pub exists: bool,
+}
+
+/// 폴더 목록 파일 내용 (favorites는 저장된 순서가 곧 표시 순서)
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct FoldersState {
+    #[serde(default)]
+    favorites: Vec<FavoriteFolder>,
+    #[serde(default)]
+    recent: Vec<RecentFolder>,
+}
+
+// 폴더 목록 파일 경로 가져오기
+fn get_folders_state_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|p| p.join("folders.json"))
+        .map_err(|e| format!("Failed to get app data dir: {}", e))
+}
+
+fn load_state(app: &tauri::AppHandle) -> Result<FoldersState, String> {
+    let path = get_folders_state_path(app)?;
+    if !path.exists() {
+        return Ok(FoldersState::default());
+    }
+
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    match serde_json::from_str(&content) {
+        Ok(state) => Ok(state),
+        Err(e) => {
+            // 손상된 파일은 .bak으로 보관하고 빈 상태로 시작 (다음 저장이 덮어쓰지 않도록)
+            let backup = path.with_extension("json.bak");
+            tracing::warn!("Invalid folders file, moved to {}: {}", backup.display(), e);
+            fs::rename(&path, &backup).map_err(|e| format!("Failed to back up folders file: {}", e))?;
+            Ok(FoldersState::default())
+        }
+    }
+}
+
+fn save_state(app: &tauri::AppHandle, state: &FoldersState) -> Result<(), String> {
+    let path = get_folders_state_path(app)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let content = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())
+}
+
+/// 상태 파일을 잠근 채로 수정 후 저장
+fn update_state<T>(
+    app: &tauri::AppHandle,
+    f: impl FnOnce(&mut FoldersState) -> Result<T, String>,
+) -> Result<T, String> {
+    let _guard = FOLDERS_FILE_LOCK.lock().map_err(|_| "Folders state lock poisoned".to_string())?;
+    let mut state = load_state(app)?;
+    let result = f(&mut state)?;
+    save_state(app, &state)?;
+    Ok(result)
+}
+
+/// 경로 비교 (Windows는 대소문자 무시)
+fn same_path(a: &str, b: &str) -> bool {
+    let a = a.trim_end_matches(['/', '\\']);
+    let b = b.trim_end_matches(['/', '\\']);
+
+    if cfg!(target_os = "windows") {
+        a.eq_ignore_ascii_case(b)
+    } else {
+        a == b
+    }
+}
+
+fn folder_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+fn to_status(favorite: &FavoriteFolder) -> FavoriteFolderStatus {
+    FavoriteFolderStatus {
+        path: favorite.path.clone(),
+        name: favorite
+            .display_name
+            .clone()
+            .unwrap_or_else(|| folder_name(&favorite.path)),
+        display_name: favorite.display_name.clone(),
+        added_at: favorite.added_at,
+        exists: Path::new(&favorite.path).is_dir(),
+    }
+}
+
+/// 즐겨찾기 목록 (존재하지 않는 경로는 exists: false로 표시)
+pub fn list_favorites(app: &tauri::AppHandle) -> Result<Vec<FavoriteFolderStatus>, String> {
+    let _guard = FOLDERS_FILE_LOCK.lock().map_err(|_| "Folders state lock poisoned".to_string())?;
+    let state = load_state(app)?;
+    Ok(state.favorites.iter().map(to_status).collect())
+}
+
+/// 즐겨찾기 추가 (이미 있으면 표시 이름만 갱신)
+pub fn add_favorite(
+    app: &tauri::AppHandle,
+    path: String,
+    display_name: Option<String>,
+) -> Result<Vec<FavoriteFolderStatus>, String> {
+    if !Path::new(&path).is_dir() {
+        return Err(format!("{}: folder {}", crate::error::NOT_FOUND, path));
+    }
+
+    let display_name = display_name.filter(|name| !name.trim().is_empty());
+
+    update_state(app, |state| {
+        if let Some(existing) = state.favorites.iter_mut().find(|f| same_path(&f.path, &path)) {
+            existing.display_name = display_name;
+        } else {
+            state.favorites.push(FavoriteFolder {
+                path,
+                display_name,
+                added_at: chrono::Utc::now().timestamp(),
+            });
+        }
+        Ok(state.favorites.iter().map(to_status).collect())
+    })
+}
+
+/// 즐겨찾기 삭제
+pub fn remove_favorite(app: &tauri::AppHandle, path: &str) -> Result<Vec<FavoriteFolderStatus>, String> {
+    update_state(app, |state| {
+        state.favorites.retain(|f| !same_path(&f.path, path));
+        Ok(state.favorites.iter().map(to_status).collect())
+    })
+}
+
+/// 즐겨찾기 표시 이름 변경 (None이면 폴더명 사용)
+pub fn rename_favorite(
+    app: &tauri::AppHandle,
+    path: &str,
+    display_name: Option<String>,
+) -> Result<Vec<FavoriteFolderStatus>, String> {
+    update_state(app, |state| {
+        let favorite = state
+            .favorites
+            .iter_mut()
+            .find(|f| same_path(&f.path, path))
+            .ok_or_else(|| format!("{}: favorite {}", crate::error::NOT_FOUND, path))?;
+
+        favorite.display_name = display_name.filter(|name| !name.trim().is_empty());
+        Ok(state.favorites.iter().map(to_status).collect())
+    })
+}
+
+/// 즐겨찾기 순서 변경 (목록에 없는 항목은 기존 순서대로 뒤에 배치)
+pub fn reorder_favorites(
+    app: &tauri::AppHandle,
+    ordered_paths: Vec<String>,
+) -> Result<Vec<FavoriteFolderStatus>, String> {
+    update_state(app, |state| {
+        let mut remaining = std::mem::take(&mut state.favorites);
+        let mut reordered = Vec::with_capacity(remaining.len());
+
+        for path in &ordered_paths {
+            if let Some(pos) = remaining.iter().position(|f| same_path(&f.path, path)) {
+                reordered.push(remaining.remove(pos));
+            }
+        }
+        reordered.extend(remaining);
+
+        state.favorites = reordered;
+        Ok(state.favorites.iter().map(to_status).collect())
+    })
+}
+
+/// 최근 폴더 기록 (가장 최근이 앞)
+pub fn record_recent(app: &tauri::AppHandle, path: &str) -> Result<(), String> {
+    update_state(app, |state| {
+        state.recent.retain(|r| !same_path(&r.path, path));
+        state.recent.insert(0, RecentFolder {
+            path: path.to_string(),
+            opened_at: chrono::Utc::now().timestamp(),
+        });
+        state.recent.truncate(MAX_RECENT_FOLDERS);
+        Ok(())
+    })
+}
+
+/// 최근 폴더 목록 (존재하지 않는 폴더 제외)
+pub fn get_recent(app: &tauri::AppHandle, limit: Option<usize>) -> Result<Vec<RecentFolder>, String> {
+    let _guard = FOLDERS_FILE_LOCK.lock().map_err(|_| "Folders state lock poisoned".to_string())?;
+    let state = load_state(app)?;
+
+    Ok(state
+        .recent
+        .into_iter()
+        .filter(|r| Path::new(&r.path).is_dir())
+        .take(limit.unwrap_or(MAX_RECENT_FOLDERS))
+        .collect())
+}
+
+/// 최근 폴더 기록 삭제
+pub fn clear_recent(app: &tauri::AppHandle) -> Result<(), String> {
+    update_state(app, |state| {
+        state.recent.clear();
+        Ok(())
+    })
+}
@@ -0,0 +1,141 @@
+use std::fs::{self, OpenOptions};
+use std::future::Future;
+use std::io;
+use std::time::Duration;
+
+/// 읽기 전용 파일 오류 메시지 접두사 (AppError::ReadOnly로 분류)
+pub const READ_ONLY: &str = "File is read-only";
+
+/// 재시도 대기 시간 상한
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(10);
+
+/// 다른 프로세스가 사용 중인 파일 오류인지 (카메라 전송, 백신 검사 등)
+/// Windows: ERROR_SHARING_VIOLATION(32), ERROR_LOCK_VIOLATION(33), 그 밖: EBUSY(16)
+pub fn is_locked_io(e: &io::Error) -> bool {
+    #[cfg(target_os = "windows")]
+    const LOCKED_CODES: &[i32] = &[32, 33];
+    #[cfg(not(target_os = "windows"))]
+    const LOCKED_CODES: &[i32] = &[16];
+
+    e.raw_os_error().is_some_and(|code| LOCKED_CODES.contains(&code))
+}
+
+/// 모듈의 String 오류 중 사용 중인 파일 오류 ("(os error N)" 기준)
+pub fn is_locked_error(message: &str) -> bool {
+    crate::error::parse_os_error_code(message)
+        .is_some_and(|code| is_locked_io(&io::Error::from_raw_os_error(code)))
+}
+
+/// 쓰기 금지된 볼륨 오류인지 (잠금 스위치를 올린 SD 카드 등)
+/// Windows: ERROR_WRITE_PROTECT(19), 그 밖: EROFS(30)
+fn is_write_protected_io(e: &io::Error) -> bool {
+    #[cfg(target_os = "windows")]
+    const WRITE_PROTECT_CODE: i32 = 19;
+    #[cfg(not(target_os = "windows"))]
+    const WRITE_PROTECT_CODE: i32 = 30;
+
+    e.raw_os_error() == Some(WRITE_PROTECT_CODE)
+}
+
+/// 모듈의 String 오류 중 읽기 전용 파일 오류 (ensure_writable 기준)
+pub fn is_read_only_error(message: &str) -> bool {
+    message.starts_with(READ_ONLY)
+}
+
+/// 읽기 전용 속성 설정/해제
+pub fn set_read_only(path: &str, read_only: bool) -> io::Result<()> {
+    let mut permissions = fs::metadata(path)?.permissions();
+    #[allow(clippy::permissions_set_readonly_false)]
+    permissions.set_readonly(read_only);
+    fs::set_permissions(path, permissions)
+}
+
+/// 쓰기 전에 파일을 쓸 수 있는지 확인 (내용은 바꾸지 않음)
+/// 읽기 전용 속성이면 auto_clear일 때 해제하고 true 반환 (쓴 뒤 set_read_only로 복원)
+/// 쓸 수 없으면 READ_ONLY로 시작하는 오류, 사용 중인 파일은 통과 (쓰기에서 재시도)
+pub fn ensure_writable(path: &str, auto_clear: bool) -> Result<bool, String> {
+    let read_only_attribute = fs::metadata(path)
+        .map_err(|e| format!("Failed to get file metadata: {}", e))?
+        .permissions()
+        .readonly();
+    if read_only_attribute {
+        if !auto_clear {
+            return Err(format!("{}: {}", READ_ONLY, path));
+        }
+        set_read_only(path, false).map_err(|e| format!("{}: {} ({})", READ_ONLY, path, e))?;
+    }
+
+    match OpenOptions::new().write(true).open(path) {
+        Ok(_) => Ok(read_only_attribute),
+        Err(e) if is_locked_io(&e) => Ok(read_only_attribute),
+        Err(e) => {
+            if read_only_attribute {
+                let _ = set_read_only(path, true);
+            }
+            if is_write_protected_io(&e) || e.kind() == io::ErrorKind::PermissionDenied {
+                Err(format!("{}: {} ({})", READ_ONLY, path, e))
+            } else {
+                Err(format!("Failed to open file for writing: {}", e))
+            }
+        }
+    }
+}
+
+/// n번째 재시도 전 대기 시간 (설정의 첫 대기 시간에서 2배씩 증가)
+fn retry_delay(attempt: u32) -> Duration {
+    let base = Duration::from_millis(crate::settings::current().lock_retry_delay_ms);
+    base.saturating_mul(1 << attempt.min(16)).min(MAX_RETRY_DELAY)
+}
+
+/// 사용 중인 파일 오류면 지수 백오프로 재시도 (그 밖의 오류는 바로 반환)
+pub async fn retry_locked<T, F, Fut>(mut operation: F) -> Result<T, String>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, String>>,
+{
+    let attempts = crate::settings::current().lock_retry_attempts;
+    let mut attempt = 0;
+    loop {
+        match operation().await {
+            Err(e) if attempt < attempts && is_locked_error(&e) => {
+                let delay = retry_delay(attempt);
+                tracing::debug!("File locked, retrying in {:?}: {}", delay, e);
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// retry_locked의 동기 버전 (rayon 작업 등 블로킹 스레드용)
+pub fn retry_locked_blocking<T>(mut operation: impl FnMut() -> io::Result<T>) -> io::Result<T> {
+    let attempts = crate::settings::current().lock_retry_attempts;
+    let mut attempt = 0;
+    loop {
+        match operation() {
+            Err(e) if attempt < attempts && is_locked_io(&e) => {
+                std::thread::sleep(retry_delay(attempt));
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_locked_error() {
+        #[cfg(target_os = "windows")]
+        let locked = io::Error::from_raw_os_error(32);
+        #[cfg(not(target_os = "windows"))]
+        let locked = io::Error::from_raw_os_error(16);
+
+        assert!(is_locked_error(&format!("Failed to open file: {}", locked)));
+        assert!(!is_locked_error(&format!("Failed to open file: {}", io::Error::from_raw_os_error(2))));
+        assert!(!is_locked_error("Failed to decode JPEG: unexpected EOF"));
+    }
+}
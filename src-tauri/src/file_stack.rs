@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::directory::DirectoryEntry;
+use crate::formats;
+
+/// 같은 이름의 파일 묶음에서 대표 파일 선택 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StackPolicy {
+    /// 묶지 않음 (모든 파일 표시)
+    Off,
+    /// RAW를 대표로 표시
+    PreferRaw,
+    /// JPEG/HEIC를 대표로 표시
+    #[default]
+    PreferJpeg,
+}
+
+/// 묶음 안의 파일 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StackMemberKind {
+    Raw,
+    Jpeg,
+    Heic,
+    Video,
+}
+
+/// 묶음 구성 파일
+#[derive(Debug, Clone, Serialize)]
+pub struct StackMember {
+    pub path: String,
+    pub kind: StackMemberKind,
+}
+
+/// 같은 이름(확장자 제외)을 가진 파일 묶음 (RAW+JPEG, HEIC+MOV 등)
+#[derive(Debug, Clone, Serialize)]
+pub struct FileStack {
+    /// 그리드에 표시할 대표 파일
+    pub primary: String,
+    /// 대표 파일을 포함한 모든 구성 파일
+    pub members: Vec<StackMember>,
+}
+
+// 라이브 포토의 동영상, 썸네일을 만들 수 없는 HEIC도 묶음에는 포함
+fn member_kind(name: &str) -> Option<StackMemberKind> {
+    if formats::is_raw_file(name) {
+        Some(StackMemberKind::Raw)
+    } else if formats::is_jpeg_file(name) {
+        Some(StackMemberKind::Jpeg)
+    } else if formats::is_heif_file(name) {
+        Some(StackMemberKind::Heic)
+    } else if formats::is_video_file(name) {
+        Some(StackMemberKind::Video)
+    } else {
+        None
+    }
+}
+
+// 대표 파일 종류 우선순위 (동영상은 대표가 되지 않음)
+fn primary_rank(kind: StackMemberKind, policy: StackPolicy) -> Option<u8> {
+    match (kind, policy) {
+        (StackMemberKind::Video, _) => None,
+        (StackMemberKind::Raw, StackPolicy::PreferRaw) => Some(0),
+        (StackMemberKind::Raw, _) => Some(2),
+        (StackMemberKind::Jpeg, _) => Some(1),
+        (StackMemberKind::Heic, StackPolicy::PreferRaw) => Some(2),
+        (StackMemberKind::Heic, _) => Some(0),
+    }
+}
+
+/// 디렉토리 항목에서 짝을 이루는 파일 묶음 찾기
+/// 확장자를 뺀 이름이 같고(대소문자 무시) 두 개 이상이며 대표가 될 사진이 있는 경우만 묶음
+pub fn detect(entries: &[DirectoryEntry], policy: StackPolicy) -> Vec<FileStack> {
+    if policy == StackPolicy::Off {
+        return Vec::new();
+    }
+
+    let mut groups: BTreeMap<String, Vec<StackMember>> = BTreeMap::new();
+    for entry in entries.iter().filter(|entry| !entry.is_dir) {
+        let Some(kind) = member_kind(&entry.name) else {
+            continue;
+        };
+        let Some(stem) = Path::new(&entry.name).file_stem() else {
+            continue;
+        };
+        groups
+            .entry(stem.to_string_lossy().to_lowercase())
+            .or_default()
+            .push(StackMember {
+                path: entry.path.clone(),
+                kind,
+            });
+    }
+
+    groups
+        .into_values()
+        .filter(|members| members.len() > 1)
+        .filter_map(|mut members| {
+            members.sort_by(|a, b| a.kind.cmp(&b.kind).then_with(|| a.path.cmp(&b.path)));
+            let primary = members
+                .iter()
+                .filter_map(|member| primary_rank(member.kind, policy).map(|rank| (rank, member)))
+                .min_by_key(|(rank, _)| *rank)?
+                .1
+                .path
+                .clone();
+            Some(FileStack { primary, members })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str) -> DirectoryEntry {
+        DirectoryEntry::new(name.to_string(), format!("/photos/{}", name), false)
+    }
+
+    #[test]
+    fn test_detect() {
+        let entries = vec![
+            file("DSC_0001.NEF"),
+            file("DSC_0001.JPG"),
+            file("IMG_0002.HEIC"),
+            file("IMG_0002.MOV"),
+            file("clip.mov"),
+            file("single.jpg"),
+        ];
+
+        let stacks = detect(&entries, StackPolicy::PreferRaw);
+        assert_eq!(stacks.len(), 2);
+        assert_eq!(stacks[0].primary, "/photos/DSC_0001.NEF");
+        assert_eq!(stacks[1].primary, "/photos/IMG_0002.HEIC");
+
+        let stacks = detect(&entries, StackPolicy::PreferJpeg);
+        assert_eq!(stacks[0].primary, "/photos/DSC_0001.JPG");
+        assert_eq!(stacks[0].members.len(), 2);
+
+        assert!(detect(&entries, StackPolicy::Off).is_empty());
+    }
+}
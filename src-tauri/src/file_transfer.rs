@@ -0,0 +1,351 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tauri::{AppHandle, Emitter};
+use walkdir::WalkDir;
+
+use crate::import::unique_destination;
+use crate::jobs::Job;
+
+/// 진행률 이벤트 최소 간격 (밀리초)
+const PROGRESS_INTERVAL_MS: u128 = 200;
+
+/// 복사 단위 (이 크기마다 취소 확인과 바이트 진행률 갱신)
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// 대상 폴더에 같은 이름의 파일이 있을 때 처리 (폴더는 항상 합침)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    /// 기존 파일 유지
+    #[default]
+    Skip,
+    /// 기존 파일 덮어쓰기
+    Overwrite,
+    /// 원본이 더 최근에 수정된 경우만 덮어쓰기
+    OverwriteIfNewer,
+    /// _1, _2 ... 를 붙여 둘 다 유지
+    KeepBoth,
+}
+
+/// 폴더 복사/이동 진행률 (folder-transfer-progress 이벤트)
+#[derive(Debug, Clone, Serialize)]
+pub struct TransferProgress {
+    pub files_done: usize,
+    pub files_total: usize,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    pub current_file: String,
+}
+
+/// 복사/이동 실패 항목
+#[derive(Debug, Clone, Serialize)]
+pub struct TransferFailure {
+    pub path: String,
+    pub error: String,
+}
+
+/// 폴더 복사/이동 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct TransferSummary {
+    /// 대상 폴더 경로 (dest 아래 원본 폴더 이름)
+    pub dest_path: String,
+    pub transferred: usize,
+    pub skipped: usize,
+    pub failed: Vec<TransferFailure>,
+    pub bytes_transferred: u64,
+    /// 작업이 취소되어 남은 파일은 처리하지 않음 (이동은 원본에 남음)
+    pub cancelled: bool,
+}
+
+enum FileOutcome {
+    Transferred(u64),
+    Skipped,
+}
+
+// 원본 폴더 아래 파일 목록 (상대 경로, 크기), 빈 폴더도 만들 수 있게 폴더 목록도 반환
+fn collect_entries(source: &Path) -> (Vec<PathBuf>, Vec<(PathBuf, u64)>) {
+    let mut dirs = Vec::new();
+    let mut files = Vec::new();
+    for entry in WalkDir::new(source).min_depth(1).into_iter().filter_map(|e| e.ok()) {
+        let Ok(relative) = entry.path().strip_prefix(source) else {
+            continue;
+        };
+        if entry.file_type().is_dir() {
+            dirs.push(relative.to_path_buf());
+        } else if entry.file_type().is_file() {
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            files.push((relative.to_path_buf(), size));
+        }
+    }
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    (dirs, files)
+}
+
+fn modified(path: &Path) -> Option<std::time::SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+// 충돌 정책 적용 후 실제로 쓸 대상 경로 (None이면 건너뜀)
+fn resolve_target(source: &Path, target: PathBuf, policy: ConflictPolicy) -> Option<PathBuf> {
+    if !target.exists() {
+        return Some(target);
+    }
+    match policy {
+        ConflictPolicy::Skip => None,
+        ConflictPolicy::Overwrite => Some(target),
+        ConflictPolicy::OverwriteIfNewer => {
+            let newer = matches!((modified(source), modified(&target)), (Some(src), Some(dst)) if src > dst);
+            newer.then_some(target)
+        }
+        ConflictPolicy::KeepBoth => Some(unique_destination(target)),
+    }
+}
+
+// 파일 하나 복사 (CHUNK_SIZE마다 취소 확인), 원본 수정 시간 유지
+// 같은 폴더의 .partial 파일에 쓴 뒤 rename (실패/취소돼도 덮어쓸 기존 파일은 그대로)
+fn copy_file(source: &Path, target: &Path, job: &Job, on_chunk: &mut dyn FnMut(u64)) -> Result<u64, String> {
+    let mut partial_name = target.file_name().unwrap_or_default().to_os_string();
+    partial_name.push(".partial");
+    let partial = target.with_file_name(partial_name);
+
+    let mut reader = BufReader::new(File::open(source).map_err(|e| format!("Failed to open file: {}", e))?);
+    let mut writer = BufWriter::new(File::create(&partial).map_err(|e| format!("Failed to create file: {}", e))?);
+
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    let mut copied = 0u64;
+    let result = loop {
+        if job.is_cancelled() {
+            break Err(format!("{}: transfer", crate::error::CANCELLED));
+        }
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break writer.flush().map_err(|e| format!("Failed to write file: {}", e)),
+            Ok(read) => read,
+            Err(e) => break Err(format!("Failed to read file: {}", e)),
+        };
+        if let Err(e) = writer.write_all(&buffer[..read]) {
+            break Err(format!("Failed to write file: {}", e));
+        }
+        copied += read as u64;
+        on_chunk(read as u64);
+    };
+    drop(writer);
+
+    let result = result.and_then(|_| {
+        if let Some(modified) = modified(source) {
+            let _ = filetime::set_file_mtime(&partial, filetime::FileTime::from_system_time(modified));
+        }
+        fs::rename(&partial, target).map_err(|e| format!("Failed to write file: {}", e))
+    });
+    if let Err(e) = result {
+        let _ = fs::remove_file(&partial);
+        return Err(e);
+    }
+    Ok(copied)
+}
+
+// 심볼릭 링크와 상대 경로를 푼 경로 (아직 없는 부분은 있는 상위 폴더를 풀고 이어 붙임)
+fn resolve_path(path: &Path) -> PathBuf {
+    let mut existing = path;
+    let mut rest = Vec::new();
+    loop {
+        if let Ok(resolved) = existing.canonicalize() {
+            return rest.iter().rev().fold(resolved, |acc, part| acc.join(part));
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                rest.push(name.to_os_string());
+                existing = parent;
+            }
+            _ => return path.to_path_buf(),
+        }
+    }
+}
+
+/// 폴더를 dest 아래로 복사 또는 이동 (같은 이름 폴더가 있으면 합침)
+/// 이동은 같은 볼륨이면 이름 변경, 다른 볼륨이면 복사 후 원본 삭제
+/// 진행 중에는 folder-transfer-progress 이벤트 전송 (파일 수와 바이트)
+pub fn transfer_folder(
+    app: &AppHandle,
+    source: &Path,
+    dest: &Path,
+    policy: ConflictPolicy,
+    is_move: bool,
+    job: &Job,
+) -> Result<TransferSummary, String> {
+    if !source.is_dir() {
+        return Err(format!("{}: source folder {}", crate::error::NOT_FOUND, source.display()));
+    }
+    let name = source
+        .file_name()
+        .ok_or_else(|| format!("Invalid source folder: {}", source.display()))?;
+    let target_root = dest.join(name);
+    if resolve_path(&target_root).starts_with(resolve_path(source)) {
+        return Err("Cannot copy or move a folder into itself".to_string());
+    }
+    fs::create_dir_all(dest).map_err(|e| format!("Failed to create destination: {}", e))?;
+
+    let mut summary = TransferSummary {
+        dest_path: crate::fs_path::to_display(&target_root),
+        transferred: 0,
+        skipped: 0,
+        failed: Vec::new(),
+        bytes_transferred: 0,
+        cancelled: false,
+    };
+
+    // 같은 볼륨의 이동은 폴더째 이름 변경 (대상이 있으면 합쳐야 하므로 파일 단위로 처리)
+    if is_move && !target_root.exists() && fs::rename(source, &target_root).is_ok() {
+        let (_, files) = collect_entries(&target_root);
+        summary.transferred = files.len();
+        summary.bytes_transferred = files.iter().map(|(_, size)| size).sum();
+        job.set_progress(files.len(), files.len());
+        let _ = app.emit("folder-transfer-progress", TransferProgress {
+            files_done: files.len(),
+            files_total: files.len(),
+            bytes_done: summary.bytes_transferred,
+            bytes_total: summary.bytes_transferred,
+            current_file: target_root.to_string_lossy().to_string(),
+        });
+        return Ok(summary);
+    }
+
+    let (dirs, files) = collect_entries(source);
+    let bytes_total: u64 = files.iter().map(|(_, size)| size).sum();
+    fs::create_dir_all(&target_root).map_err(|e| format!("Failed to create folder: {}", e))?;
+    for dir in &dirs {
+        fs::create_dir_all(target_root.join(dir)).map_err(|e| format!("Failed to create folder: {}", e))?;
+    }
+
+    let mut last_emit: Option<Instant> = None;
+    let mut bytes_done: u64 = 0;
+    let mut emit_progress = |files_done: usize, bytes_done: u64, current: &Path, force: bool| {
+        if force || last_emit.is_none_or(|t| t.elapsed().as_millis() >= PROGRESS_INTERVAL_MS) {
+            last_emit = Some(Instant::now());
+            let _ = app.emit("folder-transfer-progress", TransferProgress {
+                files_done,
+                files_total: files.len(),
+                bytes_done,
+                bytes_total,
+                current_file: current.to_string_lossy().to_string(),
+            });
+        }
+    };
+
+    for (index, (relative, size)) in files.iter().enumerate() {
+        if job.is_cancelled() {
+            summary.cancelled = true;
+            break;
+        }
+        let source_file = source.join(relative);
+        let outcome = match resolve_target(&source_file, target_root.join(relative), policy) {
+            None => Ok(FileOutcome::Skipped),
+            // 이동은 같은 볼륨이면 파일 이름 변경으로 끝냄
+            Some(target) if is_move && fs::rename(&source_file, &target).is_ok() => {
+                bytes_done += size;
+                Ok(FileOutcome::Transferred(*size))
+            }
+            Some(target) => {
+                let files_done = index;
+                let copied = copy_file(&source_file, &target, job, &mut |chunk| {
+                    bytes_done += chunk;
+                    emit_progress(files_done, bytes_done, &source_file, false);
+                });
+                copied.and_then(|copied| {
+                    if is_move {
+                        fs::remove_file(&source_file)
+                            .map_err(|e| format!("Copied but failed to remove source: {}", e))?;
+                    }
+                    Ok(FileOutcome::Transferred(copied))
+                })
+            }
+        };
+
+        match outcome {
+            Ok(FileOutcome::Transferred(bytes)) => {
+                summary.transferred += 1;
+                summary.bytes_transferred += bytes;
+            }
+            Ok(FileOutcome::Skipped) => {
+                summary.skipped += 1;
+                bytes_done += size;
+            }
+            // 파일 복사 중 취소 (쓰던 .partial 파일은 이미 삭제됨)
+            Err(_) if job.is_cancelled() => {
+                summary.cancelled = true;
+                break;
+            }
+            Err(error) => summary.failed.push(TransferFailure {
+                path: source_file.to_string_lossy().to_string(),
+                error,
+            }),
+        }
+
+        job.set_progress(index + 1, files.len());
+        emit_progress(index + 1, bytes_done, &source_file, index + 1 == files.len());
+    }
+
+    // 이동: 비워진 원본 폴더 정리 (건너뛰거나 실패한 파일이 남은 폴더는 유지)
+    if is_move {
+        let mut emptied: Vec<&PathBuf> = dirs.iter().collect();
+        emptied.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+        for dir in emptied {
+            let _ = fs::remove_dir(source.join(dir));
+        }
+        let _ = fs::remove_dir(source);
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_target() {
+        let dir = std::env::temp_dir().join(format!("pixengine-transfer-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("source.jpg");
+        let existing = dir.join("photo.jpg");
+        fs::write(&source, b"new").unwrap();
+        fs::write(&existing, b"old").unwrap();
+
+        let missing = dir.join("missing.jpg");
+        assert_eq!(resolve_target(&source, missing.clone(), ConflictPolicy::Skip), Some(missing));
+        assert_eq!(resolve_target(&source, existing.clone(), ConflictPolicy::Skip), None);
+        assert_eq!(
+            resolve_target(&source, existing.clone(), ConflictPolicy::Overwrite),
+            Some(existing.clone())
+        );
+        assert_eq!(
+            resolve_target(&source, existing.clone(), ConflictPolicy::KeepBoth),
+            Some(dir.join("photo_1.jpg"))
+        );
+
+        let older = filetime::FileTime::from_unix_time(1_000_000, 0);
+        filetime::set_file_mtime(&existing, older).unwrap();
+        assert_eq!(
+            resolve_target(&source, existing.clone(), ConflictPolicy::OverwriteIfNewer),
+            Some(existing.clone())
+        );
+        filetime::set_file_mtime(&source, older).unwrap();
+        assert_eq!(resolve_target(&source, existing, ConflictPolicy::OverwriteIfNewer), None);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_resolve_path() {
+        let dir = std::env::temp_dir().join(format!("pixengine-transfer-resolve-{}", std::process::id()));
+        fs::create_dir_all(dir.join("photos")).unwrap();
+
+        let resolved = resolve_path(&dir.join("photos/../photos/new/sub"));
+        assert!(resolved.starts_with(resolve_path(&dir.join("photos"))));
+        assert!(resolved.ends_with("new/sub"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
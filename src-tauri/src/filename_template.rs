@@ -0,0 +1,155 @@
+use chrono::{DateTime, Local, NaiveDateTime};
+use exif::{In, Tag};
+use std::fs;
+use std::io::BufReader;
+use std::path::Path;
+
+/// 파일명 템플릿에 쓰이는 파일 정보
+pub struct TemplateContext<'a> {
+    pub source: &'a Path,
+    pub date_taken: NaiveDateTime,
+    pub camera: Option<&'a str>,
+    pub sequence: usize,
+}
+
+/// 촬영 날짜와 카메라 모델 (EXIF가 없으면 파일 수정 시간 사용)
+pub fn read_capture_info(path: &Path) -> (NaiveDateTime, Option<String>) {
+    let exif_data = fs::File::open(path).ok().and_then(|file| {
+        exif::Reader::new()
+            .read_from_container(&mut BufReader::new(file))
+            .ok()
+    });
+
+    let get_ascii = |tag: Tag| -> Option<String> {
+        let field = exif_data.as_ref()?.get_field(tag, In::PRIMARY)?;
+        if let exif::Value::Ascii(ref vec) = field.value {
+            vec.first()
+                .and_then(|bytes| std::str::from_utf8(bytes).ok())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        } else {
+            None
+        }
+    };
+
+    let date_taken = get_ascii(Tag::DateTimeOriginal)
+        .or_else(|| get_ascii(Tag::DateTime))
+        .and_then(|s| crate::metadata_store::normalize_exif_datetime(&s))
+        .and_then(|s| NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S").ok())
+        .unwrap_or_else(|| {
+            fs::metadata(path)
+                .and_then(|m| m.modified())
+                .map(|t| DateTime::<Local>::from(t).naive_local())
+                .unwrap_or_else(|_| Local::now().naive_local())
+        });
+
+    (date_taken, get_ascii(Tag::Model))
+}
+
+/// 파일명에 쓸 수 없는 문자 치환
+pub fn sanitize_component(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// 토큰 하나 치환
+/// {date_taken[:형식]} (기본 %Y%m%d_%H%M%S), {date}, {time}, {sequence[:자릿수]} (기본 4),
+/// {camera}, {original_name}
+fn expand_token(token: &str, ctx: &TemplateContext) -> Result<String, String> {
+    let (name, arg) = match token.split_once(':') {
+        Some((name, arg)) => (name, Some(arg)),
+        None => (token, None),
+    };
+
+    match name {
+        "original_name" => Ok(ctx
+            .source
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default()),
+        "date_taken" => {
+            // 잘못된 형식 문자열은 to_string()에서 패닉하므로 write!로 에러 처리
+            use std::fmt::Write;
+            let format = arg.unwrap_or("%Y%m%d_%H%M%S");
+            let mut value = String::new();
+            write!(value, "{}", ctx.date_taken.format(format))
+                .map_err(|_| format!("Invalid date format: {}", format))?;
+            Ok(value)
+        }
+        "date" => Ok(ctx.date_taken.format("%Y%m%d").to_string()),
+        "time" => Ok(ctx.date_taken.format("%H%M%S").to_string()),
+        "sequence" => {
+            let width = match arg {
+                Some(arg) => arg
+                    .parse::<usize>()
+                    .map_err(|_| format!("Invalid sequence width: {}", arg))?,
+                None => 4,
+            };
+            Ok(format!("{:0width$}", ctx.sequence, width = width))
+        }
+        "camera" => Ok(ctx.camera.unwrap_or("Unknown").to_string()),
+        _ => Err(format!("Unknown template token: {{{}}}", token)),
+    }
+}
+
+/// 템플릿 토큰 치환 (파일명 외 메타데이터 값 등에도 사용)
+pub fn expand_tokens(template: &str, ctx: &TemplateContext) -> Result<String, String> {
+    let mut text = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| format!("Unclosed token in template: {}", template))?;
+        text.push_str(&expand_token(&rest[start + 1..start + end], ctx)?);
+        rest = &rest[start + end + 1..];
+    }
+    text.push_str(rest);
+    Ok(text)
+}
+
+/// 템플릿을 적용한 새 파일명 (확장자는 원본 유지)
+pub fn expand(template: &str, ctx: &TemplateContext) -> Result<String, String> {
+    // 잘못된 날짜 형식 등으로 생긴 경로 문자 제거
+    let stem = sanitize_component(&expand_tokens(template, ctx)?);
+    if stem.is_empty() {
+        return Err("Template produced an empty file name".to_string());
+    }
+
+    Ok(match ctx.source.extension() {
+        Some(ext) => format!("{}.{}", stem, ext.to_string_lossy()),
+        None => stem,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand() {
+        let date = NaiveDateTime::parse_from_str("2024-05-17 09:30:05", "%Y-%m-%d %H:%M:%S").unwrap();
+        let ctx = TemplateContext {
+            source: Path::new("DSC_0001.NEF"),
+            date_taken: date,
+            camera: Some("Z 8"),
+            sequence: 7,
+        };
+
+        assert_eq!(expand("{date}_{time}_{sequence}", &ctx).unwrap(), "20240517_093005_0007.NEF");
+        assert_eq!(expand("{date_taken:%Y-%m-%d}_{sequence:2}", &ctx).unwrap(), "2024-05-17_07.NEF");
+        assert_eq!(expand("{camera}-{original_name}", &ctx).unwrap(), "Z 8-DSC_0001.NEF");
+        assert!(expand("{unknown}", &ctx).is_err());
+        assert!(expand("{date", &ctx).is_err());
+        assert!(expand("{date_taken:%Q}", &ctx).is_err());
+    }
+}
@@ -0,0 +1,212 @@
+use serde::{Deserialize, Serialize};
+
+use crate::metadata_store::{ImageRecord, MetadataStore};
+
+/// 방향 조건
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrientationFilter {
+    Landscape,
+    Portrait,
+    Square,
+}
+
+/// 이미지 필터 (모든 조건 AND, 없는 조건은 무시)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImageFilter {
+    /// 최소 별점 (포함)
+    pub min_rating: Option<i32>,
+    /// 최대 별점 (포함, 0이면 별점 없음만)
+    pub max_rating: Option<i32>,
+    /// 확장자 목록 (대소문자 무시, 점 없이)
+    pub extensions: Option<Vec<String>>,
+    /// 카메라 모델 (대소문자 무시, 부분 일치)
+    pub camera_model: Option<String>,
+    pub orientation: Option<OrientationFilter>,
+    /// 촬영일 시작 ("YYYY-MM-DD" 또는 "YYYY-MM-DD HH:MM:SS", 포함)
+    pub date_from: Option<String>,
+    /// 촬영일 끝 (날짜만 주면 그날 전체 포함)
+    pub date_to: Option<String>,
+}
+
+impl ImageFilter {
+    /// 메타데이터(DB)가 필요한 조건이 있는지 (확장자 외)
+    fn needs_metadata(&self) -> bool {
+        self.min_rating.is_some()
+            || self.max_rating.is_some()
+            || self.camera_model.is_some()
+            || self.orientation.is_some()
+            || self.date_from.is_some()
+            || self.date_to.is_some()
+    }
+
+    fn matches_extension(&self, path: &str) -> bool {
+        let Some(extensions) = &self.extensions else {
+            return true;
+        };
+        let extension = std::path::Path::new(path)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        extensions
+            .iter()
+            .any(|e| e.trim_start_matches('.').eq_ignore_ascii_case(&extension))
+    }
+
+    /// 메타데이터 조건 검사 (인덱싱되지 않은 파일은 메타데이터 조건을 만족하지 않음)
+    fn matches_record(&self, record: Option<&ImageRecord>) -> bool {
+        if !self.needs_metadata() {
+            return true;
+        }
+        let Some(record) = record else {
+            return false;
+        };
+
+        if self.min_rating.is_some_and(|min| record.rating < min)
+            || self.max_rating.is_some_and(|max| record.rating > max)
+        {
+            return false;
+        }
+
+        if let Some(model) = &self.camera_model {
+            let wanted = model.to_lowercase();
+            let matched = record
+                .camera_model
+                .as_ref()
+                .is_some_and(|m| m.to_lowercase().contains(&wanted));
+            if !matched {
+                return false;
+            }
+        }
+
+        if let Some(orientation) = self.orientation {
+            let Some((width, height)) = display_dimensions(record) else {
+                return false;
+            };
+            let actual = match width.cmp(&height) {
+                std::cmp::Ordering::Greater => OrientationFilter::Landscape,
+                std::cmp::Ordering::Less => OrientationFilter::Portrait,
+                std::cmp::Ordering::Equal => OrientationFilter::Square,
+            };
+            if actual != orientation {
+                return false;
+            }
+        }
+
+        if self.date_from.is_some() || self.date_to.is_some() {
+            let Some(date_taken) = record.date_taken.as_deref() else {
+                return false;
+            };
+            if self.date_from.as_deref().is_some_and(|from| date_taken < from) {
+                return false;
+            }
+            // "YYYY-MM-DD"만 주면 접두사 비교로 그날 전체 포함
+            if let Some(to) = self.date_to.as_deref() {
+                if date_taken > to && !date_taken.starts_with(to) {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    /// 인덱싱된 레코드가 모든 조건을 만족하는지 (확장자 포함)
+    pub(crate) fn matches(&self, record: &ImageRecord) -> bool {
+        self.matches_extension(&record.path) && self.matches_record(Some(record))
+    }
+}
+
+/// EXIF 방향을 적용한 표시 크기 (5-8은 90도 회전)
+fn display_dimensions(record: &ImageRecord) -> Option<(u32, u32)> {
+    let (width, height) = (record.width?, record.height?);
+    if (5..=8).contains(&record.orientation) {
+        Some((height, width))
+    } else {
+        Some((width, height))
+    }
+}
+
+/// 조건에 맞는 경로만 반환 (입력 순서 유지)
+pub fn filter_image_paths(
+    store: &MetadataStore,
+    paths: Vec<String>,
+    filter: &ImageFilter,
+) -> Result<Vec<String>, String> {
+    // 확장자 조건을 먼저 적용해 메타데이터 조회 대상 축소
+    let paths: Vec<String> = paths
+        .into_iter()
+        .filter(|path| filter.matches_extension(path))
+        .collect();
+
+    if !filter.needs_metadata() {
+        return Ok(paths);
+    }
+
+    // 변경된 파일은 먼저 다시 인덱싱
+    store.index_files(&paths)?;
+    let records = store.get_records(&paths)?;
+
+    Ok(paths
+        .into_iter()
+        .filter(|path| filter.matches_record(records.get(path)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(rating: i32, date_taken: &str, width: u32, height: u32, orientation: u8) -> ImageRecord {
+        ImageRecord {
+            path: "/photos/a.jpg".to_string(),
+            folder: "/photos".to_string(),
+            file_name: "a.jpg".to_string(),
+            extension: "jpg".to_string(),
+            file_size: 0,
+            mtime: 0,
+            date_taken: Some(date_taken.to_string()),
+            camera_make: None,
+            camera_model: Some("ILCE-7M4".to_string()),
+            lens_model: None,
+            focal_length: None,
+            aperture: None,
+            iso: None,
+            width: Some(width),
+            height: Some(height),
+            orientation,
+            rating,
+        }
+    }
+
+    #[test]
+    fn test_combined_filter() {
+        let filter = ImageFilter {
+            min_rating: Some(3),
+            camera_model: Some("ilce".to_string()),
+            orientation: Some(OrientationFilter::Portrait),
+            date_from: Some("2024-05-01".to_string()),
+            date_to: Some("2024-05-31".to_string()),
+            ..Default::default()
+        };
+
+        // 6000x4000이지만 90도 회전 (orientation 6) → 세로
+        assert!(filter.matches_record(Some(&record(4, "2024-05-31 18:00:00", 6000, 4000, 6))));
+        assert!(!filter.matches_record(Some(&record(4, "2024-06-01 00:00:00", 6000, 4000, 6))));
+        assert!(!filter.matches_record(Some(&record(2, "2024-05-10 12:00:00", 6000, 4000, 6))));
+        assert!(!filter.matches_record(Some(&record(4, "2024-05-10 12:00:00", 6000, 4000, 1))));
+        assert!(!filter.matches_record(None));
+    }
+
+    #[test]
+    fn test_extension_filter() {
+        let filter = ImageFilter {
+            extensions: Some(vec![".NEF".to_string(), "jpg".to_string()]),
+            ..Default::default()
+        };
+        assert!(filter.matches_extension("/photos/DSC_0001.nef"));
+        assert!(filter.matches_extension("/photos/IMG_0001.JPG"));
+        assert!(!filter.matches_extension("/photos/IMG_0001.png"));
+    }
+}
@@ -0,0 +1,171 @@
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use crate::formats::is_image_file;
+use crate::network_path::{run_with_timeout, NETWORK_LIST_TIMEOUT, SHARE_UNREACHABLE};
+
+/// 파일 이름 → (크기, 수정 시간 ms)
+type Listing = BTreeMap<String, (u64, u64)>;
+
+struct FolderSnapshot {
+    hash: String,
+    entries: Listing,
+}
+
+lazy_static! {
+    /// 폴더별 마지막 스냅샷 (폴더 감시가 안 되는 네트워크 폴더의 폴링용)
+    static ref SNAPSHOTS: DashMap<String, FolderSnapshot> = DashMap::new();
+}
+
+/// snapshot_folder 응답
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotInfo {
+    pub folder: String,
+    pub files: usize,
+    /// 목록 해시 (이름, 크기, 수정 시간 기준)
+    pub hash: String,
+}
+
+/// 마지막 스냅샷과 비교한 변경 (경로 목록)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FolderDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+    /// 이전 스냅샷이 없어 이번 목록을 기준으로 저장만 함
+    pub baseline: bool,
+    pub hash: String,
+}
+
+// 폴더의 이미지 목록 (네트워크 폴더가 응답하지 않으면 타임아웃)
+fn list(folder: &str) -> Result<Listing, String> {
+    let dir = crate::fs_path::to_fs_path(folder);
+    run_with_timeout(NETWORK_LIST_TIMEOUT, move || -> Result<Listing, String> {
+        let entries = fs::read_dir(&dir).map_err(|e| format!("{}: {} ({})", SHARE_UNREACHABLE, dir.display(), e))?;
+
+        let mut listing = Listing::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !is_image_file(&path) {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if !metadata.is_file() {
+                continue;
+            }
+            let mtime = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(SystemTime::UNIX_EPOCH).ok())
+                .map_or(0, |duration| duration.as_millis() as u64);
+            listing.insert(entry.file_name().to_string_lossy().to_string(), (metadata.len(), mtime));
+        }
+        Ok(listing)
+    })?
+}
+
+fn listing_hash(listing: &Listing) -> String {
+    let mut hasher = blake3::Hasher::new();
+    for (name, (size, mtime)) in listing {
+        hasher.update(name.as_bytes());
+        hasher.update(&[0]);
+        hasher.update(&size.to_le_bytes());
+        hasher.update(&mtime.to_le_bytes());
+    }
+    hasher.finalize().to_hex().to_string()
+}
+
+// 두 목록의 차이 (추가, 삭제, 수정된 이름)
+fn diff_listings(old: &Listing, new: &Listing) -> (Vec<String>, Vec<String>, Vec<String>) {
+    let mut added = Vec::new();
+    let mut modified = Vec::new();
+    for (name, entry) in new {
+        match old.get(name) {
+            None => added.push(name.clone()),
+            Some(previous) if previous != entry => modified.push(name.clone()),
+            Some(_) => {}
+        }
+    }
+    let removed = old.keys().filter(|name| !new.contains_key(*name)).cloned().collect();
+    (added, removed, modified)
+}
+
+fn to_paths(folder: &str, names: Vec<String>) -> Vec<String> {
+    let folder = PathBuf::from(folder);
+    names
+        .into_iter()
+        .map(|name| folder.join(name).to_string_lossy().to_string())
+        .collect()
+}
+
+/// 폴더 목록 스냅샷 저장 (이전 스냅샷은 교체)
+pub fn snapshot_folder(folder: &str) -> Result<SnapshotInfo, String> {
+    let entries = list(folder)?;
+    let hash = listing_hash(&entries);
+    let info = SnapshotInfo {
+        folder: folder.to_string(),
+        files: entries.len(),
+        hash: hash.clone(),
+    };
+    SNAPSHOTS.insert(folder.to_string(), FolderSnapshot { hash, entries });
+    Ok(info)
+}
+
+/// 마지막 스냅샷과 지금 목록 비교 후 스냅샷 갱신 (스냅샷이 없으면 기준만 저장)
+pub fn diff_folder(folder: &str) -> Result<FolderDiff, String> {
+    let entries = list(folder)?;
+    let hash = listing_hash(&entries);
+
+    let mut diff = FolderDiff {
+        hash: hash.clone(),
+        ..FolderDiff::default()
+    };
+    match SNAPSHOTS.get(folder) {
+        None => diff.baseline = true,
+        Some(previous) if previous.hash == hash => return Ok(diff),
+        Some(previous) => {
+            let (added, removed, modified) = diff_listings(&previous.entries, &entries);
+            diff.added = to_paths(folder, added);
+            diff.removed = to_paths(folder, removed);
+            diff.modified = to_paths(folder, modified);
+        }
+    }
+
+    SNAPSHOTS.insert(folder.to_string(), FolderSnapshot { hash, entries });
+    Ok(diff)
+}
+
+/// 폴더의 스냅샷 삭제 (폴링 중지 시)
+pub fn forget(folder: &str) {
+    SNAPSHOTS.remove(folder);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_listings() {
+        let old: Listing = [("a.jpg", (10, 1)), ("b.jpg", (20, 1)), ("c.jpg", (30, 1))]
+            .into_iter()
+            .map(|(name, entry)| (name.to_string(), entry))
+            .collect();
+        let new: Listing = [("a.jpg", (10, 1)), ("b.jpg", (20, 2)), ("d.jpg", (40, 1))]
+            .into_iter()
+            .map(|(name, entry)| (name.to_string(), entry))
+            .collect();
+
+        let (added, removed, modified) = diff_listings(&old, &new);
+        assert_eq!(added, vec!["d.jpg"]);
+        assert_eq!(removed, vec!["c.jpg"]);
+        assert_eq!(modified, vec!["b.jpg"]);
+        assert_ne!(listing_hash(&old), listing_hash(&new));
+    }
+}
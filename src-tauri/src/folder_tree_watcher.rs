@@ -0,0 +1,206 @@
+use notify_debouncer_full::{
+    new_debouncer,
+    notify::{
+        event::{ModifyKind, RenameMode},
+        EventKind, RecursiveMode, Watcher,
+    },
+    DebounceEventResult, Debouncer, FileIdMap,
+};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::fs_path;
+
+/// 폴더 이름 변경 (from → to)
+#[derive(Debug, Clone, Serialize)]
+pub struct FolderRename {
+    pub from: String,
+    pub to: String,
+}
+
+/// 하위 폴더 변경 (folder-tree-changed 이벤트, 감시 중인 폴더 하나 기준)
+#[derive(Debug, Clone, Serialize)]
+pub struct FolderTreeChange {
+    pub parent: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub renamed: Vec<FolderRename>,
+}
+
+/// 감시 중인 폴더별 마지막으로 확인한 하위 폴더 목록
+type Snapshots = HashMap<PathBuf, BTreeSet<PathBuf>>;
+
+// 바로 아래 하위 폴더 목록
+fn list_subfolders(folder: &Path) -> BTreeSet<PathBuf> {
+    fs::read_dir(folder)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+                .map(|entry| entry.path())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// 이벤트가 난 폴더들을 다시 읽어 이전 목록과 비교 (이름 변경 이벤트로 추가/삭제 쌍을 이름 변경으로 묶음)
+fn diff_changes(
+    snapshots: &mut Snapshots,
+    parents: &HashSet<PathBuf>,
+    renames: &[(PathBuf, PathBuf)],
+) -> Vec<FolderTreeChange> {
+    let mut changes = Vec::new();
+    for parent in parents {
+        let Some(previous) = snapshots.get_mut(parent) else {
+            continue;
+        };
+        let current = list_subfolders(parent);
+        let mut added: Vec<&PathBuf> = current.difference(previous).collect();
+        let mut removed: Vec<&PathBuf> = previous.difference(&current).collect();
+
+        let mut renamed = Vec::new();
+        for (from, to) in renames {
+            if let (Some(r), Some(a)) = (
+                removed.iter().position(|path| *path == from),
+                added.iter().position(|path| *path == to),
+            ) {
+                removed.remove(r);
+                added.remove(a);
+                renamed.push(FolderRename {
+                    from: fs_path::to_display(from),
+                    to: fs_path::to_display(to),
+                });
+            }
+        }
+
+        if !added.is_empty() || !removed.is_empty() || !renamed.is_empty() {
+            changes.push(FolderTreeChange {
+                parent: fs_path::to_display(parent),
+                added: added.into_iter().map(|path| fs_path::to_display(path)).collect(),
+                removed: removed.into_iter().map(|path| fs_path::to_display(path)).collect(),
+                renamed,
+            });
+        }
+        *previous = current;
+    }
+    changes
+}
+
+/// 폴더 패널용 폴더 구조 감시 (펼쳐진 폴더의 하위 폴더 생성/삭제/이름 변경)
+/// 파일 변경은 FolderWatcher가 처리하므로 여기서는 폴더만 봄
+pub struct FolderTreeWatcher {
+    debouncer: Option<Debouncer<notify::RecommendedWatcher, FileIdMap>>,
+}
+
+impl FolderTreeWatcher {
+    pub fn new() -> Self {
+        Self { debouncer: None }
+    }
+
+    /// 감시할 폴더 목록 교체 (각 폴더의 바로 아래 하위 폴더만, 없는 폴더는 건너뜀)
+    pub fn watch(&mut self, app: AppHandle, roots: &[String]) -> Result<(), String> {
+        self.stop();
+
+        let roots: Vec<PathBuf> = roots
+            .iter()
+            .map(fs_path::to_fs_path)
+            .filter(|path| path.is_dir())
+            .collect();
+        if roots.is_empty() {
+            return Ok(());
+        }
+
+        let snapshots: Arc<Mutex<Snapshots>> = Arc::new(Mutex::new(
+            roots.iter().map(|root| (root.clone(), list_subfolders(root))).collect(),
+        ));
+
+        let mut debouncer = new_debouncer(
+            Duration::from_millis(500),
+            None,
+            move |result: DebounceEventResult| {
+                let Ok(events) = result else {
+                    return;
+                };
+
+                let mut parents = HashSet::new();
+                let mut renames = Vec::new();
+                for event in &events {
+                    let structural = matches!(
+                        event.kind,
+                        EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(_))
+                    );
+                    if !structural {
+                        continue;
+                    }
+                    if let (EventKind::Modify(ModifyKind::Name(RenameMode::Both)), [from, to]) =
+                        (event.kind, event.paths.as_slice())
+                    {
+                        renames.push((from.clone(), to.clone()));
+                    }
+                    parents.extend(event.paths.iter().filter_map(|path| path.parent()).map(Path::to_path_buf));
+                }
+                if parents.is_empty() {
+                    return;
+                }
+
+                let changes = diff_changes(&mut snapshots.lock().unwrap(), &parents, &renames);
+                for change in changes {
+                    let _ = app.emit("folder-tree-changed", change);
+                }
+            },
+        )
+        .map_err(|e| format!("Failed to create watcher: {}", e))?;
+
+        for root in &roots {
+            if let Err(e) = debouncer.watcher().watch(root, RecursiveMode::NonRecursive) {
+                tracing::warn!("Failed to watch folder tree {}: {}", root.display(), e);
+            }
+        }
+
+        self.debouncer = Some(debouncer);
+        Ok(())
+    }
+
+    pub fn stop(&mut self) {
+        self.debouncer = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_changes() {
+        let root = std::env::temp_dir().join(format!("pixengine-folder-tree-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("keep")).unwrap();
+        fs::create_dir_all(root.join("old")).unwrap();
+        fs::create_dir_all(root.join("gone")).unwrap();
+
+        let mut snapshots: Snapshots = HashMap::from([(root.clone(), list_subfolders(&root))]);
+        fs::rename(root.join("old"), root.join("new")).unwrap();
+        fs::remove_dir(root.join("gone")).unwrap();
+        fs::create_dir(root.join("added")).unwrap();
+
+        let parents = HashSet::from([root.clone()]);
+        let renames = vec![(root.join("old"), root.join("new"))];
+        let changes = diff_changes(&mut snapshots, &parents, &renames);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].added, vec![fs_path::to_display(&root.join("added"))]);
+        assert_eq!(changes[0].removed, vec![fs_path::to_display(&root.join("gone"))]);
+        assert_eq!(changes[0].renamed.len(), 1);
+        assert_eq!(changes[0].renamed[0].to, fs_path::to_display(&root.join("new")));
+
+        // 바뀐 것이 없으면 이벤트 없음
+        assert!(diff_changes(&mut snapshots, &parents, &[]).is_empty());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -3,12 +3,22 @@ use notify_debouncer_full::{
     notify::{RecursiveMode, Watcher},
     DebounceEventResult,
 };
-use std::path::{Path, PathBuf};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use tauri::{AppHandle, Emitter};
+use tauri::{AppHandle, Emitter, Manager};
 use serde::{Serialize, Deserialize};
 
+use crate::file_lock;
+use crate::folder_snapshot;
+use crate::formats::is_image_file;
+use crate::metadata_store::MetadataStore;
+use crate::scheduler::{self, WorkClass};
+use crate::thumbnail;
+use crate::thumbnail_queue::ThumbnailFailure;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[allow(clippy::enum_variant_names)]
@@ -18,41 +28,222 @@ pub enum FolderChangeEvent {
     FileModified { path: String },
 }
 
-// 지원하는 이미지 확장자 목록
-const IMAGE_EXTENSIONS: &[&str] = &[
-    "jpg", "jpeg",  // JPEG
-    "png",          // PNG
-    "gif",          // GIF
-    "bmp",          // BMP
-    "webp",         // WebP
-    "tiff", "tif",  // TIFF
-    "exr",          // OpenEXR
-    "avif",         // AVIF
-    "ico",          // ICO
-    "svg",          // SVG
-    // RAW 포맷 (EXIF 썸네일 지원)
-    "nef", "nrw",           // Nikon
-    "cr2", "crw",           // Canon
-    "arw", "srf", "sr2",    // Sony
-    "dng",                  // Adobe
-    "raf",                  // Fuji
-    "orf",                  // Olympus
-    "rw2",                  // Panasonic
-    "pef",                  // Pentax
-];
-
-fn is_image_file(path: &Path) -> bool {
-    if let Some(ext) = path.extension() {
-        let ext_str = ext.to_string_lossy().to_lowercase();
-        IMAGE_EXTENSIONS.contains(&ext_str.as_str())
-    } else {
-        false
+/// 파일 변경을 메타데이터 저장소에 반영
+fn sync_metadata_store(app: &AppHandle, event: &FolderChangeEvent) {
+    let Some(store) = app.try_state::<Arc<MetadataStore>>() else {
+        return;
+    };
+
+    let result = match event {
+        FolderChangeEvent::FileRemoved { path } => store.remove(std::slice::from_ref(path)),
+        FolderChangeEvent::FileAdded { path } | FolderChangeEvent::FileModified { path } => {
+            store.index_files(std::slice::from_ref(path)).map(|_| ())
+        }
+    };
+
+    if let Err(e) = result {
+        tracing::warn!("Failed to sync metadata store: {}", e);
+    }
+}
+
+/// 추가/수정된 파일의 썸네일을 바로 생성해 thumbnail-completed 전송 (그리드에 빈칸이 보이지 않도록)
+/// 수정된 파일은 이전 캐시를 지운 뒤 다시 생성
+fn spawn_thumbnail_refresh(app: &AppHandle, path: String, invalidate: bool) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if invalidate {
+            let (handle, cache_path) = (app.clone(), path.clone());
+            let _ = tokio::task::spawn_blocking(move || crate::thumbnail_cache::invalidate(&handle, &cache_path)).await;
+        }
+
+        // 이름 변경 등으로 이미 없어진 파일은 건너뜀
+        if !crate::fs_path::to_fs_path(&path).exists() {
+            return;
+        }
+
+        // 보이는 항목과 같은 최우선 예산으로 생성
+        let generated = file_lock::retry_locked(|| async {
+            let _permit = scheduler::acquire(WorkClass::LqVisible).await;
+            thumbnail::generate_thumbnail(&app, &path).await
+        })
+        .await;
+        match generated {
+            Ok(result) => {
+                let _ = app.emit("thumbnail-completed", &result);
+            }
+            Err(e) => {
+                tracing::warn!("Failed to generate thumbnail for watched file {}: {}", path, e);
+                let _ = app.emit("thumbnail-failed", ThumbnailFailure::new(&path, e));
+            }
+        }
+    });
+}
+
+/// 파일 변경 하나를 반영하고 프론트엔드로 folder-change 전송 (감시 이벤트, 폴링 공통)
+fn handle_change(app: &AppHandle, rating_refresh: &Arc<Mutex<RatingRefreshState>>, evt: FolderChangeEvent) {
+    // 인덱싱된 메타데이터 동기화
+    sync_metadata_store(app, &evt);
+
+    // 새 파일이 편집본(_edit 등)이면 원본과 연결
+    if let FolderChangeEvent::FileAdded { path } = &evt {
+        crate::versions::on_file_added(app, path);
+    }
+
+    // 수정된 파일은 별점(XMP) 다시 읽기
+    if let FolderChangeEvent::FileModified { path } = &evt {
+        schedule_rating_refresh(app, rating_refresh, path.clone());
+    }
+
+    // 프론트엔드로 이벤트 전송 (썸네일은 생성되는 대로 thumbnail-completed로)
+    let refresh = match &evt {
+        FolderChangeEvent::FileAdded { path } => Some((path.clone(), false)),
+        FolderChangeEvent::FileModified { path } => Some((path.clone(), true)),
+        FolderChangeEvent::FileRemoved { .. } => None,
+    };
+    let _ = app.emit("folder-change", evt);
+    if let Some((path, invalidate)) = refresh {
+        spawn_thumbnail_refresh(app, path, invalidate);
+    }
+}
+
+/// 감시 실패 시 폴더 목록 비교 주기
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 감시 대신 주기적으로 폴더 목록을 비교해 변경 반영 (감시 세션이 바뀌면 종료)
+/// 이미 폴링 중이면 아무것도 하지 않음
+fn start_polling(
+    app: &AppHandle,
+    folder: &str,
+    rating_refresh: &Arc<Mutex<RatingRefreshState>>,
+    session: &Arc<AtomicU64>,
+    generation: u64,
+    polling: &Arc<AtomicBool>,
+) {
+    if session.load(Ordering::SeqCst) != generation || polling.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    tracing::warn!("Folder watch failed, falling back to polling: {}", folder);
+    let _ = app.emit("folder-watch-fallback", serde_json::json!({ "path": folder }));
+
+    let app = app.clone();
+    let folder = folder.to_string();
+    let rating_refresh = Arc::clone(rating_refresh);
+    let session = Arc::clone(session);
+    tauri::async_runtime::spawn(async move {
+        // 처음 목록을 기준으로 저장
+        let baseline = folder.clone();
+        let _ = tokio::task::spawn_blocking(move || folder_snapshot::snapshot_folder(&baseline)).await;
+
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            if session.load(Ordering::SeqCst) != generation {
+                break;
+            }
+
+            let poll_folder = folder.clone();
+            let diff = match tokio::task::spawn_blocking(move || folder_snapshot::diff_folder(&poll_folder)).await {
+                Ok(Ok(diff)) => diff,
+                Ok(Err(e)) => {
+                    tracing::debug!("Failed to poll folder {}: {}", folder, e);
+                    continue;
+                }
+                Err(_) => continue,
+            };
+            if session.load(Ordering::SeqCst) != generation {
+                break;
+            }
+
+            let events = diff
+                .added
+                .into_iter()
+                .map(|path| FolderChangeEvent::FileAdded { path })
+                .chain(diff.removed.into_iter().map(|path| FolderChangeEvent::FileRemoved { path }))
+                .chain(diff.modified.into_iter().map(|path| FolderChangeEvent::FileModified { path }));
+            for evt in events {
+                handle_change(&app, &rating_refresh, evt);
+            }
+        }
+
+        folder_snapshot::forget(&folder);
+    });
+}
+
+/// 외부 별점 변경 재확인 대기 시간 (파일별)
+/// Lightroom 등은 저장 중 파일을 여러 번 수정하므로 마지막 수정 후 한 번만 읽음
+const RATING_REFRESH_DELAY: Duration = Duration::from_millis(1000);
+
+/// 파일별 별점 재확인 상태
+#[derive(Default)]
+struct RatingRefreshState {
+    /// 파일별 마지막 수정 이벤트 번호 (더 늦은 이벤트가 오면 이전 예약은 무시)
+    generations: HashMap<String, u64>,
+    /// 마지막으로 알려진 별점 (바뀐 경우에만 이벤트 전송)
+    known: HashMap<String, i32>,
+}
+
+/// 외부 프로그램이 바꾼 별점(XMP)을 다시 읽어 rating-changed 이벤트 전송
+fn schedule_rating_refresh(app: &AppHandle, state: &Arc<Mutex<RatingRefreshState>>, path: String) {
+    // DB에 별점을 저장하는 형식은 파일이 바뀌어도 별점이 그대로
+    if crate::rating::uses_database(&path) {
+        return;
     }
+
+    let generation = {
+        let mut state = state.lock().unwrap();
+        let generation = state.generations.entry(path.clone()).or_insert(0);
+        *generation += 1;
+        *generation
+    };
+
+    let app = app.clone();
+    let state = Arc::clone(state);
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(RATING_REFRESH_DELAY).await;
+
+        // 대기 중에 같은 파일이 다시 수정됐으면 나중 예약에 맡김
+        if state.lock().unwrap().generations.get(&path) != Some(&generation) {
+            return;
+        }
+
+        let read_path = path.clone();
+        let rating = match tokio::task::spawn_blocking(move || crate::rating::read_rating(&read_path)).await {
+            Ok(Ok(rating)) => rating,
+            Ok(Err(e)) => {
+                tracing::debug!("Failed to re-read rating for {}: {}", path, e);
+                return;
+            }
+            Err(_) => return,
+        };
+
+        {
+            let mut state = state.lock().unwrap();
+            state.generations.remove(&path);
+            if state.known.insert(path.clone(), rating) == Some(rating) {
+                return;
+            }
+        }
+
+        if let Some(store) = app.try_state::<Arc<MetadataStore>>() {
+            if let Err(e) = store.update_rating(&path, rating) {
+                tracing::warn!("Failed to update indexed rating: {}", e);
+            }
+        }
+
+        let _ = app.emit("rating-changed", serde_json::json!({
+            "path": path,
+            "rating": rating
+        }));
+    });
 }
 
 pub struct FolderWatcher {
     _debouncer: Arc<Mutex<Option<notify_debouncer_full::Debouncer<notify::RecommendedWatcher, notify_debouncer_full::FileIdMap>>>>,
     current_path: Arc<Mutex<Option<PathBuf>>>,
+    rating_refresh: Arc<Mutex<RatingRefreshState>>,
+    /// 감시 세션 번호 (폴더가 바뀌거나 감시를 멈추면 증가, 이전 폴링 종료용)
+    session: Arc<AtomicU64>,
+    /// 현재 세션이 목록 비교 폴링 중인지
+    polling: Arc<AtomicBool>,
 }
 
 impl FolderWatcher {
@@ -60,6 +251,9 @@ impl FolderWatcher {
         Self {
             _debouncer: Arc::new(Mutex::new(None)),
             current_path: Arc::new(Mutex::new(None)),
+            rating_refresh: Arc::new(Mutex::new(RatingRefreshState::default())),
+            session: Arc::new(AtomicU64::new(0)),
+            polling: Arc::new(AtomicBool::new(false)),
         }
     }
 
@@ -73,6 +267,18 @@ impl FolderWatcher {
         // 현재 감시 중인 경로 업데이트
         *self.current_path.lock().unwrap() = Some(path.clone());
 
+        // 이전 폴더의 별점 재확인 상태 초기화
+        *self.rating_refresh.lock().unwrap() = RatingRefreshState::default();
+        let rating_refresh = Arc::clone(&self.rating_refresh);
+
+        // 새 감시 세션 (이전 폴더의 폴링은 다음 주기에 종료)
+        let generation = self.session.fetch_add(1, Ordering::SeqCst) + 1;
+        self.polling.store(false, Ordering::SeqCst);
+        let (session, polling) = (Arc::clone(&self.session), Arc::clone(&self.polling));
+        let fallback_app = app.clone();
+        let fallback_refresh = Arc::clone(&rating_refresh);
+        let watched_folder = folder_path.clone();
+
         // 디바운서 생성 (500ms 디바운싱)
         let debouncer = new_debouncer(
             Duration::from_millis(500),
@@ -81,6 +287,19 @@ impl FolderWatcher {
                 match result {
                     Ok(events) => {
                         for event in events {
+                            // 이름 변경은 사용자 지정 순서에서 같은 위치 유지
+                            if let (
+                                notify::EventKind::Modify(notify::event::ModifyKind::Name(notify::event::RenameMode::Both)),
+                                [from, to],
+                            ) = (event.kind, event.paths.as_slice())
+                            {
+                                crate::manual_order::rename_path(
+                                    &app,
+                                    &from.to_string_lossy(),
+                                    &to.to_string_lossy(),
+                                );
+                            }
+
                             for path in &event.paths {
                                 // 이미지 파일만 처리
                                 if !is_image_file(path) {
@@ -103,16 +322,17 @@ impl FolderWatcher {
                                 };
 
                                 if let Some(evt) = change_event {
-                                    // 프론트엔드로 이벤트 전송
-                                    let _ = app.emit("folder-change", evt);
+                                    handle_change(&app, &rating_refresh, evt);
                                 }
                             }
                         }
                     }
                     Err(errors) => {
                         for error in errors {
-                            eprintln!("Folder watcher error: {:?}", error);
+                            tracing::error!("Folder watcher error: {:?}", error);
                         }
+                        // 감시가 깨진 폴더(네트워크 공유 등)는 목록 비교로 대신 감지
+                        start_polling(&app, &watched_folder, &rating_refresh, &session, generation, &polling);
                     }
                 }
             },
@@ -125,10 +345,12 @@ impl FolderWatcher {
         }
 
         let mut new_debouncer = debouncer;
-        new_debouncer
-            .watcher()
-            .watch(&path, RecursiveMode::NonRecursive)
-            .map_err(|e| format!("Failed to watch folder: {}", e))?;
+        if let Err(e) = new_debouncer.watcher().watch(&path, RecursiveMode::NonRecursive) {
+            // 감시를 지원하지 않는 폴더(일부 네트워크 공유)는 목록 비교 폴링으로 대신 감지
+            tracing::warn!("Failed to watch folder {}: {}", folder_path, e);
+            start_polling(&fallback_app, &folder_path, &fallback_refresh, &self.session, generation, &self.polling);
+            return Ok(());
+        }
 
         *debouncer_guard = Some(new_debouncer);
 
@@ -141,6 +363,15 @@ impl FolderWatcher {
             drop(d);
         }
         *self.current_path.lock().unwrap() = None;
+
+        // 진행 중인 폴링 종료
+        self.session.fetch_add(1, Ordering::SeqCst);
+        self.polling.store(false, Ordering::SeqCst);
+    }
+
+    /// 앱에서 직접 쓴 별점 기록 (감시 이벤트로 같은 값을 다시 보내지 않도록)
+    pub fn remember_rating(&self, path: &str, rating: i32) {
+        self.rating_refresh.lock().unwrap().known.insert(path.to_string(), rating);
     }
 
     #[allow(dead_code)]
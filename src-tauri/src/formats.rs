@@ -0,0 +1,358 @@
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// 파일 형식 분류 (그리드 배지 표시용)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FormatCategory {
+    Jpeg,
+    Raw,
+    /// 그 밖의 래스터 이미지 (PNG, WebP, TIFF 등)
+    Image,
+    Vector,
+    /// 문서 형식 (PSD/PSB, PDF)
+    Document,
+    Video,
+}
+
+/// 형식별 정보와 지원 기능
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct FormatInfo {
+    /// 소문자 확장자
+    pub extension: &'static str,
+    pub name: &'static str,
+    pub category: FormatCategory,
+    /// 디코딩 없이 꺼낼 수 있는 내장 미리보기 (EXIF 썸네일, RAW 미리보기, PSD 썸네일 리소스)
+    pub embedded_preview: bool,
+    /// 썸네일/뷰어/내보내기에서 디코딩 가능 (아니면 목록에만 표시)
+    pub decodable: bool,
+    /// 여러 페이지를 가질 수 있음
+    pub multi_page: bool,
+    /// 파일에 XMP 별점/라벨을 쓸 수 있음 (아니면 앱 DB에 저장)
+    pub xmp: bool,
+}
+
+const fn format(
+    extension: &'static str,
+    name: &'static str,
+    category: FormatCategory,
+    embedded_preview: bool,
+    decodable: bool,
+) -> FormatInfo {
+    FormatInfo {
+        extension,
+        name,
+        category,
+        embedded_preview,
+        decodable,
+        multi_page: false,
+        xmp: true,
+    }
+}
+
+const fn raw(extension: &'static str, name: &'static str) -> FormatInfo {
+    format(extension, name, FormatCategory::Raw, true, true)
+}
+
+/// 지원 형식 목록 (감시/썸네일/목록/내보내기가 모두 이 목록을 기준으로 판단)
+const FORMATS: &[FormatInfo] = &[
+    format("jpg", "JPEG", FormatCategory::Jpeg, true, true),
+    format("jpeg", "JPEG", FormatCategory::Jpeg, true, true),
+    format("png", "PNG", FormatCategory::Image, false, true),
+    format("gif", "GIF", FormatCategory::Image, false, true),
+    // XMP 스마트 핸들러가 없는 형식은 xmp: false (별점은 앱 DB에 저장)
+    FormatInfo {
+        xmp: false,
+        ..format("bmp", "BMP", FormatCategory::Image, false, true)
+    },
+    FormatInfo {
+        xmp: false,
+        ..format("webp", "WebP", FormatCategory::Image, false, true)
+    },
+    FormatInfo {
+        multi_page: true,
+        ..format("tiff", "TIFF", FormatCategory::Image, false, true)
+    },
+    FormatInfo {
+        multi_page: true,
+        ..format("tif", "TIFF", FormatCategory::Image, false, true)
+    },
+    FormatInfo {
+        xmp: false,
+        ..format("exr", "OpenEXR", FormatCategory::Image, false, true)
+    },
+    FormatInfo {
+        xmp: false,
+        ..format("avif", "AVIF", FormatCategory::Image, false, true)
+    },
+    FormatInfo {
+        xmp: false,
+        ..format("ico", "ICO", FormatCategory::Image, false, true)
+    },
+    FormatInfo {
+        xmp: false,
+        ..format("svg", "SVG", FormatCategory::Vector, false, true)
+    },
+    format("psd", "Photoshop", FormatCategory::Document, true, true),
+    format("psb", "Photoshop (Large)", FormatCategory::Document, true, true),
+    // RAW 포맷 (EXIF 내장 미리보기, CR3는 EXIF 구조가 달라 제외)
+    raw("nef", "Nikon RAW"),
+    raw("nrw", "Nikon RAW"),
+    raw("cr2", "Canon RAW"),
+    raw("crw", "Canon RAW"),
+    raw("arw", "Sony RAW"),
+    raw("srf", "Sony RAW"),
+    raw("sr2", "Sony RAW"),
+    raw("dng", "Adobe DNG"),
+    raw("raf", "Fujifilm RAW"),
+    raw("orf", "Olympus RAW"),
+    raw("rw2", "Panasonic RAW"),
+    raw("pef", "Pentax RAW"),
+    // 목록/묶음에만 쓰는 형식 (디코딩하지 않음)
+    FormatInfo {
+        xmp: false,
+        ..format("heic", "HEIC", FormatCategory::Image, false, false)
+    },
+    FormatInfo {
+        xmp: false,
+        ..format("heif", "HEIF", FormatCategory::Image, false, false)
+    },
+    format("mov", "QuickTime", FormatCategory::Video, false, false),
+    format("mp4", "MPEG-4", FormatCategory::Video, false, false),
+    FormatInfo {
+        multi_page: true,
+        xmp: false,
+        ..format("pdf", "PDF", FormatCategory::Document, false, false)
+    },
+];
+
+/// 확장자로 형식 찾기 (대소문자 무시)
+pub fn lookup(path: impl AsRef<Path>) -> Option<&'static FormatInfo> {
+    let extension = path.as_ref().extension()?.to_string_lossy().to_lowercase();
+    FORMATS.iter().find(|format| format.extension == extension)
+}
+
+fn by_extension(extension: &str) -> Option<&'static FormatInfo> {
+    FORMATS.iter().find(|format| format.extension == extension)
+}
+
+/// 매직 바이트 확인에 읽는 파일 앞부분 크기
+const SNIFF_BYTES: usize = 64;
+
+/// 파일 앞부분(매직 바이트)으로 형식 판별 (모르는 내용은 None)
+/// 제조사 표시가 없는 TIFF 기반 RAW(NEF, ARW, DNG 등)는 TIFF로 판별
+pub fn sniff(head: &[u8]) -> Option<&'static FormatInfo> {
+    let extension = match head {
+        [0xFF, 0xD8, 0xFF, ..] => "jpg",
+        [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, ..] => "png",
+        [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => "gif",
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => "webp",
+        [b'I', b'I', 0x1A, 0, 0, 0, b'H', b'E', b'A', b'P', b'C', b'C', b'D', b'R', ..] => "crw",
+        [b'I', b'I', b'*', 0, _, _, _, _, b'C', b'R', ..] => "cr2",
+        [b'I', b'I', b'R', b'O' | b'S', ..] => "orf",
+        [b'I', b'I', b'U', 0, ..] => "rw2",
+        [b'I', b'I', b'*' | b'+', 0, ..] | [b'M', b'M', 0, b'*' | b'+', ..] => "tif",
+        _ if head.starts_with(b"FUJIFILMCCD-RAW") => "raf",
+        [0x76, 0x2F, 0x31, 0x01, ..] => "exr",
+        [b'8', b'B', b'P', b'S', 0, 1, ..] => "psd",
+        [b'8', b'B', b'P', b'S', 0, 2, ..] => "psb",
+        [b'%', b'P', b'D', b'F', ..] => "pdf",
+        [_, _, _, _, b'f', b't', b'y', b'p', brand @ ..] => match brand.get(..4)? {
+            b"avif" | b"avis" => "avif",
+            b"heic" | b"heix" | b"heim" | b"heis" | b"hevc" | b"hevx" => "heic",
+            b"mif1" | b"msf1" => "heif",
+            b"qt  " => "mov",
+            // Canon CR3 등 지원하지 않는 ISO 컨테이너
+            b"crx " => return None,
+            _ => "mp4",
+        },
+        [0, 0, 1, 0, ..] => "ico",
+        [b'B', b'M', ..] => "bmp",
+        _ => {
+            // SVG는 텍스트 (BOM, 공백 뒤의 XML 선언이나 svg 태그)
+            let text = head.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(head);
+            let start = text.iter().position(|b| !b.is_ascii_whitespace())?;
+            let text = &text[start..];
+            if text.starts_with(b"<?xml") || text.starts_with(b"<svg") {
+                "svg"
+            } else {
+                return None;
+            }
+        }
+    };
+    by_extension(extension)
+}
+
+// 확장자 형식과 내용 형식이 같은 것으로 볼 수 있는지
+fn compatible(by_name: &FormatInfo, by_content: &FormatInfo) -> bool {
+    const HEIF_FAMILY: &[&str] = &["heic", "heif", "avif"];
+    by_name.name == by_content.name
+        // RAW는 대부분 TIFF 구조, 제조사마다 표시가 달라 RAW끼리는 구분하지 않음
+        || (by_name.category == FormatCategory::Raw
+            && (by_content.extension == "tif" || by_content.category == FormatCategory::Raw))
+        || (by_name.category == FormatCategory::Video && by_content.category == FormatCategory::Video)
+        || (HEIF_FAMILY.contains(&by_name.extension) && HEIF_FAMILY.contains(&by_content.extension))
+}
+
+/// 확장자와 내용이 다른 파일
+#[derive(Debug, Clone, Serialize)]
+pub struct FormatMismatch {
+    pub path: String,
+    /// 확장자 기준 형식
+    pub extension: &'static str,
+    /// 내용 기준 형식 (썸네일/뷰어는 이 형식으로 디코딩)
+    pub detected: &'static str,
+    pub detected_name: &'static str,
+}
+
+lazy_static! {
+    /// 앱 시작 후 발견한 확장자 불일치 파일 (경로별)
+    static ref MISMATCHES: Mutex<HashMap<String, FormatMismatch>> = Mutex::new(HashMap::new());
+}
+
+/// 파일 내용으로 확인한 형식 (확장자와 다르면 내용 형식을 쓰고 불일치 기록)
+/// source는 읽을 경로 (원격 파일은 스풀 경로), file_path는 확장자와 보고용 경로
+/// 읽을 수 없거나 내용으로 판별하지 못하면 확장자 형식
+pub fn detect(source: &Path, file_path: &str) -> Option<&'static FormatInfo> {
+    let by_name = lookup(file_path);
+
+    let mut head = Vec::with_capacity(SNIFF_BYTES);
+    let read = File::open(source).and_then(|file| file.take(SNIFF_BYTES as u64).read_to_end(&mut head));
+    let Some(by_content) = read.ok().and_then(|_| sniff(&head)) else {
+        return by_name;
+    };
+
+    match by_name {
+        Some(by_name) if compatible(by_name, by_content) => Some(by_name),
+        Some(by_name) => {
+            record_mismatch(file_path, by_name, by_content);
+            Some(by_content)
+        }
+        None => Some(by_content),
+    }
+}
+
+fn record_mismatch(file_path: &str, by_name: &FormatInfo, by_content: &'static FormatInfo) {
+    let mut mismatches = MISMATCHES.lock().unwrap();
+    if mismatches.contains_key(file_path) {
+        return;
+    }
+    tracing::warn!(
+        "File extension does not match content: {} (.{} is {})",
+        file_path,
+        by_name.extension,
+        by_content.name
+    );
+    mismatches.insert(
+        file_path.to_string(),
+        FormatMismatch {
+            path: file_path.to_string(),
+            extension: by_name.extension,
+            detected: by_content.extension,
+            detected_name: by_content.name,
+        },
+    );
+}
+
+/// 앱 시작 후 발견한 확장자 불일치 파일 목록
+pub fn mismatches() -> Vec<FormatMismatch> {
+    MISMATCHES.lock().unwrap().values().cloned().collect()
+}
+
+/// 형식 분류 (모르는 형식은 None)
+pub fn category(path: impl AsRef<Path>) -> Option<FormatCategory> {
+    lookup(path).map(|format| format.category)
+}
+
+/// 지원 형식 목록 (UI 파일 필터/배지용)
+pub fn supported_formats() -> Vec<FormatInfo> {
+    FORMATS.to_vec()
+}
+
+/// 썸네일을 만들 수 있는 이미지 파일인지 확인 (감시/가져오기/색인 대상)
+pub fn is_image_file(path: &Path) -> bool {
+    lookup(path).is_some_and(|format| format.decodable)
+}
+
+pub fn is_jpeg_file(file_path: &str) -> bool {
+    category(file_path) == Some(FormatCategory::Jpeg)
+}
+
+pub fn is_raw_file(file_path: &str) -> bool {
+    category(file_path) == Some(FormatCategory::Raw)
+}
+
+pub fn is_svg_file(file_path: &str) -> bool {
+    category(file_path) == Some(FormatCategory::Vector)
+}
+
+pub fn is_video_file(file_path: &str) -> bool {
+    category(file_path) == Some(FormatCategory::Video)
+}
+
+/// 파일에 XMP 별점을 쓸 수 있는 형식인지 (모르는 형식은 false)
+pub fn supports_xmp(file_path: &str) -> bool {
+    lookup(file_path).is_some_and(|format| format.xmp)
+}
+
+fn has_extension(file_path: &str, extensions: &[&str]) -> bool {
+    lookup(file_path).is_some_and(|format| extensions.contains(&format.extension))
+}
+
+pub fn is_psd_file(file_path: &str) -> bool {
+    has_extension(file_path, &["psd", "psb"])
+}
+
+pub fn is_tiff_file(file_path: &str) -> bool {
+    has_extension(file_path, &["tif", "tiff"])
+}
+
+pub fn is_pdf_file(file_path: &str) -> bool {
+    has_extension(file_path, &["pdf"])
+}
+
+pub fn is_heif_file(file_path: &str) -> bool {
+    has_extension(file_path, &["heic", "heif"])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        assert!(is_jpeg_file("/photos/IMG_0001.JPEG"));
+        assert!(is_raw_file("DSC_0001.NEF"));
+        assert!(!is_raw_file("IMG_0001.CR3"));
+        assert!(is_image_file(Path::new("scan.tif")));
+        assert!(!is_image_file(Path::new("IMG_0002.HEIC")));
+        assert_eq!(category("clip.MOV"), Some(FormatCategory::Video));
+        assert_eq!(category("notes.txt"), None);
+        assert!(lookup("scan.tiff").is_some_and(|format| format.multi_page));
+        assert!(supports_xmp("IMG_0001.jpg"));
+        assert!(!supports_xmp("render.EXR"));
+    }
+
+    #[test]
+    fn test_sniff() {
+        let extension = |head: &[u8]| sniff(head).map(|format| format.extension);
+        assert_eq!(extension(&[0xFF, 0xD8, 0xFF, 0xE1]), Some("jpg"));
+        assert_eq!(extension(b"\x89PNG\r\n\x1a\n\0\0"), Some("png"));
+        assert_eq!(extension(b"RIFF\0\0\0\0WEBPVP8 "), Some("webp"));
+        assert_eq!(extension(b"II*\0\x10\0\0\0CR\x02\0"), Some("cr2"));
+        assert_eq!(extension(b"MM\0*\0\0\0\x08"), Some("tif"));
+        assert_eq!(extension(b"\0\0\0\x1cftypheic"), Some("heic"));
+        assert_eq!(extension(b"  <svg xmlns="), Some("svg"));
+        assert_eq!(extension(b"hello"), None);
+
+        // TIFF 구조의 RAW는 불일치가 아님, PNG 내용의 .jpg는 불일치
+        let tiff = sniff(b"II*\0").unwrap();
+        assert!(compatible(lookup("DSC_0001.NEF").unwrap(), tiff));
+        assert!(!compatible(lookup("photo.jpg").unwrap(), sniff(b"\x89PNG\r\n\x1a\n").unwrap()));
+    }
+}
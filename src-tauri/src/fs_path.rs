@@ -0,0 +1,164 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use unicode_normalization::UnicodeNormalization;
+
+/// \\?\ 접두사 없이 다룰 수 있는 최대 경로 길이 (MAX_PATH 260 - 8.3 파일명 여유 12)
+#[cfg(target_os = "windows")]
+const LONG_PATH_THRESHOLD: usize = 248;
+
+/// Windows 확장 경로 접두사
+const VERBATIM_PREFIX: &str = r"\\?\";
+const VERBATIM_UNC_PREFIX: &str = r"\\?\UNC\";
+
+/// 경로 문자열을 NFC로 정규화 (macOS/NAS의 NFD 파일명을 프론트엔드와 같은 형태로)
+pub fn normalize_unicode(path: &str) -> String {
+    path.nfc().collect()
+}
+
+/// \\?\ 접두사 제거 (\\?\UNC\server\share → \\server\share)
+pub fn strip_verbatim(path: &str) -> String {
+    if let Some(rest) = path.strip_prefix(VERBATIM_UNC_PREFIX) {
+        format!(r"\\{}", rest)
+    } else if let Some(rest) = path.strip_prefix(VERBATIM_PREFIX) {
+        rest.to_string()
+    } else {
+        path.to_string()
+    }
+}
+
+/// 프론트엔드/클립보드로 보낼 경로 (접두사 제거 + NFC)
+pub fn to_display(path: &Path) -> String {
+    normalize_unicode(&strip_verbatim(&path.to_string_lossy()))
+}
+
+/// 파일 시스템 호출용 경로
+/// - Windows: 긴 경로는 \\?\ (UNC는 \\?\UNC\) 접두사
+/// - 경로가 없으면 다른 유니코드 형태(NFC/NFD)로 저장된 실제 경로를 찾음
+/// - 새로 만들 경로처럼 끝까지 찾지 못한 부분은 그대로 둠
+pub fn to_fs_path(path: impl AsRef<Path>) -> PathBuf {
+    let extended = extend_long_path(path.as_ref());
+    if extended.exists() {
+        return extended;
+    }
+    resolve_unicode_form(&extended).unwrap_or(extended)
+}
+
+/// to_fs_path의 문자열 버전 (&str 인자를 받는 함수 호출용)
+pub fn to_fs_string(path: &str) -> String {
+    to_fs_path(path).to_string_lossy().to_string()
+}
+
+/// Windows 긴 경로에 \\?\ 접두사 추가
+/// \\?\ 경로는 '/', '.', '..'을 해석하지 않으므로 미리 정리
+#[cfg(target_os = "windows")]
+fn extend_long_path(path: &Path) -> PathBuf {
+    let path_str = path.to_string_lossy();
+    if path_str.len() < LONG_PATH_THRESHOLD
+        || path_str.starts_with(VERBATIM_PREFIX)
+        || !path.is_absolute()
+    {
+        return path.to_path_buf();
+    }
+
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+
+    let normalized = normalized.to_string_lossy().replace('/', "\\");
+    match normalized.strip_prefix(r"\\") {
+        Some(unc) => PathBuf::from(format!("{}{}", VERBATIM_UNC_PREFIX, unc)),
+        None => PathBuf::from(format!("{}{}", VERBATIM_PREFIX, normalized)),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn extend_long_path(path: &Path) -> PathBuf {
+    path.to_path_buf()
+}
+
+/// 존재하는 가장 가까운 상위 경로부터 내려가며 NFC 기준으로 같은 이름의 항목을 찾음
+/// 바뀐 부분이 없으면 None
+fn resolve_unicode_form(path: &Path) -> Option<PathBuf> {
+    let components: Vec<Component> = path.components().collect();
+
+    // 존재하는 가장 가까운 상위 경로
+    let mut existing = components.len();
+    let mut base = PathBuf::new();
+    while existing > 0 {
+        base = components[..existing].iter().collect();
+        if base.exists() {
+            break;
+        }
+        existing -= 1;
+    }
+    if existing == 0 {
+        return None;
+    }
+
+    let mut resolved = base;
+    let mut changed = false;
+    for (i, component) in components[existing..].iter().enumerate() {
+        let Component::Normal(name) = component else {
+            resolved.push(component.as_os_str());
+            continue;
+        };
+
+        let wanted = normalize_unicode(&name.to_string_lossy());
+        let matched = fs::read_dir(&resolved).ok().and_then(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.file_name())
+                .find(|entry_name| normalize_unicode(&entry_name.to_string_lossy()) == wanted)
+        });
+
+        match matched {
+            Some(entry_name) => {
+                changed |= entry_name.as_os_str() != *name;
+                resolved.push(entry_name);
+            }
+            None => {
+                // 나머지는 아직 없는 경로 (새로 만들 파일 등)
+                for rest in &components[existing + i..] {
+                    resolved.push(rest.as_os_str());
+                }
+                break;
+            }
+        }
+    }
+
+    changed.then_some(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_verbatim() {
+        assert_eq!(strip_verbatim(r"\\?\C:\Photos"), r"C:\Photos");
+        assert_eq!(strip_verbatim(r"\\?\UNC\nas\share\a.jpg"), r"\\nas\share\a.jpg");
+        assert_eq!(strip_verbatim("/home/user"), "/home/user");
+    }
+
+    #[test]
+    fn test_resolve_nfd_name() {
+        let dir = std::env::temp_dir().join(format!("pixengine-fs-path-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        // NFD로 저장된 파일을 NFC 경로로 찾기 ("사진")
+        let nfd_name: String = "사진.jpg".nfd().collect();
+        fs::write(dir.join(&nfd_name), b"").unwrap();
+
+        let nfc_path = dir.join("사진.jpg");
+        assert!(to_fs_path(&nfc_path).exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
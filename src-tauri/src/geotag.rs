@@ -0,0 +1,456 @@
+use chrono::{DateTime, Duration, FixedOffset, Local, NaiveDateTime, TimeZone, Timelike, Utc};
+use exif::{Context, Field, In, Rational, Tag, Value};
+use filetime::FileTime;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::BufReader;
+use std::path::Path;
+use xmp_toolkit::{xmp_ns, ToStringOptions, XmpMeta, XmpValue};
+
+use crate::capture_time::exif_time;
+use crate::metadata_strip::{rebuild_exif, rewrite_jpeg_metadata};
+
+/// 두 트랙 포인트 사이를 보간해도 되는 최대 간격
+const DEFAULT_MAX_GAP_SECONDS: i64 = 300;
+
+/// 트랙 밖(앞/뒤)이나 간격이 큰 구간에서 가장 가까운 포인트를 쓸 최대 시간 차이
+const DEFAULT_MAX_NEAREST_SECONDS: i64 = 120;
+
+/// 이 간격 이하로 보간하면 높은 신뢰도
+const CLOSE_GAP_SECONDS: i64 = 60;
+
+/// 지오태깅 옵션
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GeotagOptions {
+    /// 카메라 시계의 UTC 오프셋(분), None이면 EXIF OffsetTimeOriginal, 그것도 없으면 시스템 시간대
+    pub utc_offset_minutes: Option<i32>,
+    /// 카메라 시계 오차(초, 카메라가 빠르면 양수), 촬영 시간에서 빼서 맞춤
+    pub clock_offset_seconds: i64,
+    pub max_gap_seconds: i64,
+    pub max_nearest_seconds: i64,
+    /// GPS가 이미 있는 파일도 덮어쓰기
+    pub overwrite: bool,
+    /// 파일은 수정하지 않고 매칭 결과만 반환
+    pub dry_run: bool,
+}
+
+impl Default for GeotagOptions {
+    fn default() -> Self {
+        Self {
+            utc_offset_minutes: None,
+            clock_offset_seconds: 0,
+            max_gap_seconds: DEFAULT_MAX_GAP_SECONDS,
+            max_nearest_seconds: DEFAULT_MAX_NEAREST_SECONDS,
+            overwrite: false,
+            dry_run: false,
+        }
+    }
+}
+
+/// GPX 트랙 포인트
+#[derive(Debug, Clone, Copy)]
+struct TrackPoint {
+    time: DateTime<Utc>,
+    latitude: f64,
+    longitude: f64,
+    altitude: Option<f64>,
+}
+
+/// 매칭 신뢰도
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchConfidence {
+    /// 가까운 두 포인트 사이 보간
+    High,
+    /// 간격이 큰 두 포인트 사이 보간
+    Medium,
+    /// 가장 가까운 포인트 사용 (트랙 밖이거나 간격이 너무 큼)
+    Low,
+}
+
+/// 파일별 매칭 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct GeotagMatch {
+    pub path: String,
+    /// 보정한 촬영 시간 (UTC, RFC 3339)
+    pub capture_time: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub altitude: Option<f64>,
+    pub confidence: MatchConfidence,
+    /// 가장 가까운 트랙 포인트와의 시간 차이(초)
+    pub time_difference_seconds: i64,
+}
+
+/// 위치를 넣지 않은 파일
+#[derive(Debug, Clone, Serialize)]
+pub struct GeotagSkip {
+    pub path: String,
+    pub reason: String,
+}
+
+/// 지오태깅 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct GeotagSummary {
+    pub dry_run: bool,
+    pub matched: Vec<GeotagMatch>,
+    pub skipped: Vec<GeotagSkip>,
+    /// 매칭은 됐지만 쓰기에 실패한 파일
+    pub failed: Vec<GeotagSkip>,
+}
+
+/// GPX 파일에서 시간이 있는 트랙/경로 포인트 읽기 (시간순 정렬)
+fn parse_gpx(content: &str) -> Result<Vec<TrackPoint>, String> {
+    let document = roxmltree::Document::parse(content).map_err(|e| format!("Invalid GPX file: {}", e))?;
+
+    let mut points: Vec<TrackPoint> = document
+        .descendants()
+        .filter(|node| matches!(node.tag_name().name(), "trkpt" | "rtept"))
+        .filter_map(|node| {
+            let child_text = |name: &str| {
+                node.children()
+                    .find(|child| child.tag_name().name() == name)
+                    .and_then(|child| child.text())
+                    .map(str::trim)
+            };
+            Some(TrackPoint {
+                time: DateTime::parse_from_rfc3339(child_text("time")?).ok()?.with_timezone(&Utc),
+                latitude: node.attribute("lat")?.parse().ok()?,
+                longitude: node.attribute("lon")?.parse().ok()?,
+                altitude: child_text("ele").and_then(|ele| ele.parse().ok()),
+            })
+        })
+        .collect();
+
+    if points.is_empty() {
+        return Err("GPX file has no timestamped track points".to_string());
+    }
+    points.sort_by_key(|point| point.time);
+    Ok(points)
+}
+
+/// 시간으로 위치 찾기: (위치, 신뢰도, 가장 가까운 포인트와의 시간 차이)
+fn locate(points: &[TrackPoint], time: DateTime<Utc>, options: &GeotagOptions) -> Option<(TrackPoint, MatchConfidence, i64)> {
+    let index = points.partition_point(|point| point.time <= time);
+    let before = index.checked_sub(1).and_then(|i| points.get(i));
+    let after = points.get(index);
+
+    let distance = |point: &TrackPoint| (time - point.time).num_seconds().abs();
+    let nearest = [before, after]
+        .into_iter()
+        .flatten()
+        .min_by_key(|point| distance(point))?;
+
+    if let (Some(before), Some(after)) = (before, after) {
+        let gap = (after.time - before.time).num_seconds();
+        if gap <= options.max_gap_seconds {
+            let ratio = if gap == 0 {
+                0.0
+            } else {
+                (time - before.time).num_milliseconds() as f64 / (after.time - before.time).num_milliseconds() as f64
+            };
+            let lerp = |a: f64, b: f64| a + (b - a) * ratio;
+            let point = TrackPoint {
+                time,
+                latitude: lerp(before.latitude, after.latitude),
+                longitude: lerp(before.longitude, after.longitude),
+                altitude: before.altitude.zip(after.altitude).map(|(a, b)| lerp(a, b)),
+            };
+            let confidence = if gap <= CLOSE_GAP_SECONDS {
+                MatchConfidence::High
+            } else {
+                MatchConfidence::Medium
+            };
+            return Some((point, confidence, distance(nearest)));
+        }
+    }
+
+    (distance(nearest) <= options.max_nearest_seconds)
+        .then(|| (TrackPoint { time, ..*nearest }, MatchConfidence::Low, distance(nearest)))
+}
+
+/// 파일의 EXIF 촬영 정보
+struct CaptureInfo {
+    time: NaiveDateTime,
+    /// OffsetTimeOriginal ("+09:00")
+    offset: Option<FixedOffset>,
+    has_gps: bool,
+}
+
+fn read_capture_info(path: &Path) -> Option<CaptureInfo> {
+    let file = fs::File::open(path).ok()?;
+    let exif = exif::Reader::new()
+        .read_from_container(&mut BufReader::new(file))
+        .ok()?;
+
+    let offset = exif
+        .get_field(Tag::OffsetTimeOriginal, In::PRIMARY)
+        .and_then(|field| match &field.value {
+            Value::Ascii(values) => values.first().and_then(|bytes| std::str::from_utf8(bytes).ok()),
+            _ => None,
+        })
+        .and_then(|offset| DateTime::parse_from_rfc3339(&format!("2000-01-01T00:00:00{}", offset.trim())).ok())
+        .map(|time| *time.offset());
+
+    Some(CaptureInfo {
+        time: exif.get_field(Tag::DateTimeOriginal, In::PRIMARY).and_then(exif_time)?,
+        offset,
+        has_gps: exif.get_field(Tag::GPSLatitude, In::PRIMARY).is_some(),
+    })
+}
+
+/// 촬영 시간(카메라 시계) → UTC
+fn to_utc(info: &CaptureInfo, options: &GeotagOptions) -> Option<DateTime<Utc>> {
+    let time = info.time - Duration::seconds(options.clock_offset_seconds);
+    let offset = options
+        .utc_offset_minutes
+        .and_then(|minutes| FixedOffset::east_opt(minutes * 60))
+        .or(info.offset);
+
+    match offset {
+        Some(offset) => offset.from_local_datetime(&time).single().map(|t| t.with_timezone(&Utc)),
+        None => Local.from_local_datetime(&time).earliest().map(|t| t.with_timezone(&Utc)),
+    }
+}
+
+// 도 → (도, 분, 초) 유리수
+fn to_dms(value: f64) -> Vec<Rational> {
+    let value = value.abs();
+    let degrees = value.trunc();
+    let minutes = ((value - degrees) * 60.0).trunc();
+    let seconds = ((value - degrees) * 60.0 - minutes) * 60.0;
+    vec![
+        Rational { num: degrees as u32, denom: 1 },
+        Rational { num: minutes as u32, denom: 1 },
+        Rational { num: (seconds * 10000.0).round() as u32, denom: 10000 },
+    ]
+}
+
+fn ascii(value: &str) -> Value {
+    Value::Ascii(vec![value.as_bytes().to_vec()])
+}
+
+/// EXIF GPS 필드 목록
+fn gps_fields(point: &TrackPoint) -> Vec<Field> {
+    let field = |tag: Tag, value: Value| Field { tag, ifd_num: In::PRIMARY, value };
+    let time = point.time;
+    let mut fields = vec![
+        field(Tag::GPSVersionID, Value::Byte(vec![2, 3, 0, 0])),
+        field(Tag::GPSLatitudeRef, ascii(if point.latitude >= 0.0 { "N" } else { "S" })),
+        field(Tag::GPSLatitude, Value::Rational(to_dms(point.latitude))),
+        field(Tag::GPSLongitudeRef, ascii(if point.longitude >= 0.0 { "E" } else { "W" })),
+        field(Tag::GPSLongitude, Value::Rational(to_dms(point.longitude))),
+        field(
+            Tag::GPSTimeStamp,
+            Value::Rational(
+                [time.hour(), time.minute(), time.second()]
+                    .into_iter()
+                    .map(|num| Rational { num, denom: 1 })
+                    .collect(),
+            ),
+        ),
+        field(Tag::GPSDateStamp, ascii(&time.format("%Y:%m:%d").to_string())),
+        field(Tag::GPSMapDatum, ascii("WGS-84")),
+    ];
+    if let Some(altitude) = point.altitude {
+        fields.push(field(Tag::GPSAltitudeRef, Value::Byte(vec![u8::from(altitude < 0.0)])));
+        fields.push(field(
+            Tag::GPSAltitude,
+            Value::Rational(vec![Rational { num: (altitude.abs() * 100.0).round() as u32, denom: 100 }]),
+        ));
+    }
+    fields
+}
+
+/// XMP 좌표 형식 ("DDD,MM.mmmmmmK")
+fn xmp_coordinate(value: f64, positive: char, negative: char) -> String {
+    let abs = value.abs();
+    let degrees = abs.trunc();
+    format!(
+        "{},{:.6}{}",
+        degrees as u32,
+        (abs - degrees) * 60.0,
+        if value >= 0.0 { positive } else { negative }
+    )
+}
+
+/// XMP 좌표 읽기 ("DDD,MM.mmmmK" 또는 "DDD,MM,SSK") → 도
+pub fn parse_xmp_coordinate(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let direction = value.chars().last()?;
+    let sign = match direction.to_ascii_uppercase() {
+        'N' | 'E' => 1.0,
+        'S' | 'W' => -1.0,
+        _ => return None,
+    };
+
+    let parts: Vec<f64> = value[..value.len() - 1]
+        .split(',')
+        .map(|part| part.trim().parse().ok())
+        .collect::<Option<_>>()?;
+    let degrees = match parts.as_slice() {
+        [degrees, minutes] => degrees + minutes / 60.0,
+        [degrees, minutes, seconds] => degrees + minutes / 60.0 + seconds / 3600.0,
+        _ => return None,
+    };
+    Some(sign * degrees)
+}
+
+/// XMP에 GPS 속성 쓰기 (기존 GPS 속성은 교체)
+fn set_xmp_gps(xmp: &mut XmpMeta, point: &TrackPoint) -> Result<(), String> {
+    for name in ["GPSLatitude", "GPSLongitude", "GPSAltitude", "GPSAltitudeRef", "GPSTimeStamp", "GPSVersionID", "GPSMapDatum"] {
+        let _ = xmp.delete_property(xmp_ns::EXIF, name);
+    }
+
+    let mut properties = vec![
+        ("GPSVersionID", "2.3.0.0".to_string()),
+        ("GPSLatitude", xmp_coordinate(point.latitude, 'N', 'S')),
+        ("GPSLongitude", xmp_coordinate(point.longitude, 'E', 'W')),
+        ("GPSTimeStamp", point.time.format("%Y-%m-%dT%H:%M:%SZ").to_string()),
+        ("GPSMapDatum", "WGS-84".to_string()),
+    ];
+    if let Some(altitude) = point.altitude {
+        properties.push(("GPSAltitudeRef", u8::from(altitude < 0.0).to_string()));
+        properties.push(("GPSAltitude", format!("{}/100", (altitude.abs() * 100.0).round() as u32)));
+    }
+
+    for (name, value) in properties {
+        xmp.set_property(xmp_ns::EXIF, name, &XmpValue::from(value))
+            .map_err(|e| format!("Failed to set {}: {}", name, e))?;
+    }
+    Ok(())
+}
+
+/// JPEG: EXIF GPS IFD 교체 + 기존 XMP 패킷 갱신
+fn write_jpeg(path: &Path, point: &TrackPoint) -> Result<(), String> {
+    let data = fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let gps = gps_fields(point);
+    let tagged = rewrite_jpeg_metadata(
+        &data,
+        |tiff| {
+            let exif = exif::Reader::new()
+                .read_raw(tiff.to_vec())
+                .map_err(|e| format!("Failed to read EXIF: {}", e))?;
+            let fields: Vec<&Field> = exif
+                .fields()
+                .filter(|field| field.tag.context() != Context::Gps)
+                .chain(gps.iter())
+                .collect();
+            rebuild_exif(&exif, &fields).map(Some)
+        },
+        |packet| {
+            let text = std::str::from_utf8(packet).map_err(|e| format!("Invalid XMP packet: {}", e))?;
+            let mut xmp: XmpMeta = text.parse().map_err(|e| format!("Failed to parse XMP: {}", e))?;
+            set_xmp_gps(&mut xmp, point)?;
+            xmp.to_string_with_options(ToStringOptions::default().use_compact_format())
+                .map(String::into_bytes)
+                .map_err(|e| format!("Failed to write XMP: {}", e))
+        },
+        false,
+    )?;
+
+    // 원본 수정 시간 유지
+    let modified = fs::metadata(path).map(|m| FileTime::from_last_modification_time(&m)).ok();
+    crate::shutdown::write_atomic(path, &tagged).map_err(|e| format!("Failed to write file: {}", e))?;
+    if let Some(modified) = modified {
+        let _ = filetime::set_file_mtime(path, modified);
+    }
+    Ok(())
+}
+
+fn write_location(path: &str, point: &TrackPoint) -> Result<(), String> {
+    if crate::formats::is_jpeg_file(path) {
+        write_jpeg(&crate::fs_path::to_fs_path(path), point)
+    } else {
+        // RAW 등은 원본 EXIF를 건드리지 않고 XMP에 기록
+        crate::rating::update_xmp(path, |xmp| set_xmp_gps(xmp, point))
+    }
+}
+
+/// GPX 트랙과 촬영 시간을 맞춰 GPS 위치 기록 (dry_run이면 결과만 반환)
+pub fn geotag_from_gpx(paths: &[String], gpx_path: &Path, options: &GeotagOptions) -> Result<GeotagSummary, String> {
+    let content = fs::read_to_string(gpx_path).map_err(|e| format!("Failed to read GPX file: {}", e))?;
+    let points = parse_gpx(&content)?;
+
+    let results: Vec<Result<GeotagMatch, (GeotagSkip, bool)>> = paths
+        .par_iter()
+        .map(|path| {
+            let skip = |reason: &str| GeotagSkip { path: path.clone(), reason: reason.to_string() };
+
+            let info = read_capture_info(&crate::fs_path::to_fs_path(path)).ok_or_else(|| (skip("No capture time"), false))?;
+            if info.has_gps && !options.overwrite {
+                return Err((skip("Already has GPS"), false));
+            }
+            let time = to_utc(&info, options).ok_or_else(|| (skip("Invalid capture time"), false))?;
+            let (point, confidence, difference) =
+                locate(&points, time, options).ok_or_else(|| (skip("Outside of GPS track"), false))?;
+
+            if !options.dry_run {
+                write_location(path, &point).map_err(|error| (skip(&error), true))?;
+            }
+            Ok(GeotagMatch {
+                path: path.clone(),
+                capture_time: time.to_rfc3339(),
+                latitude: point.latitude,
+                longitude: point.longitude,
+                altitude: point.altitude,
+                confidence,
+                time_difference_seconds: difference,
+            })
+        })
+        .collect();
+
+    let mut summary = GeotagSummary {
+        dry_run: options.dry_run,
+        matched: Vec::new(),
+        skipped: Vec::new(),
+        failed: Vec::new(),
+    };
+    for result in results {
+        match result {
+            Ok(matched) => summary.matched.push(matched),
+            Err((skip, true)) => summary.failed.push(skip),
+            Err((skip, false)) => summary.skipped.push(skip),
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gpx_interpolation() {
+        let gpx = r#"<gpx xmlns="http://www.topografix.com/GPX/1/1"><trk><trkseg>
+            <trkpt lat="37.0" lon="127.0"><ele>10</ele><time>2024-05-01T03:00:00Z</time></trkpt>
+            <trkpt lat="37.1" lon="127.2"><ele>20</ele><time>2024-05-01T03:00:40Z</time></trkpt>
+            <trkpt lat="38.0" lon="128.0"><time>2024-05-01T04:00:00Z</time></trkpt>
+        </trkseg></trk></gpx>"#;
+        let points = parse_gpx(gpx).unwrap();
+        let options = GeotagOptions::default();
+
+        // 카메라 시간 12:00:10 KST = 03:00:10 UTC
+        let info = CaptureInfo {
+            time: NaiveDateTime::parse_from_str("2024-05-01 12:00:10", "%Y-%m-%d %H:%M:%S").unwrap(),
+            offset: FixedOffset::east_opt(9 * 3600),
+            has_gps: false,
+        };
+        let time = to_utc(&info, &options).unwrap();
+        let (point, confidence, difference) = locate(&points, time, &options).unwrap();
+        assert_eq!(confidence, MatchConfidence::High);
+        assert_eq!(difference, 10);
+        assert!((point.latitude - 37.025).abs() < 1e-9);
+        assert!((point.longitude - 127.05).abs() < 1e-9);
+        assert_eq!(point.altitude, Some(12.5));
+
+        // 큰 간격 구간 가운데는 매칭하지 않음
+        let middle = DateTime::parse_from_rfc3339("2024-05-01T03:30:00Z").unwrap().with_timezone(&Utc);
+        assert!(locate(&points, middle, &options).is_none());
+
+        assert_eq!(xmp_coordinate(-37.5, 'N', 'S'), "37,30.000000S");
+        assert_eq!(parse_xmp_coordinate("37,30.000000S"), Some(-37.5));
+    }
+}
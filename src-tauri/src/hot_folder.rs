@@ -0,0 +1,254 @@
+use notify_debouncer_full::{
+    new_debouncer,
+    notify::{event::ModifyKind, EventKind, RecursiveMode, Watcher},
+    DebounceEventResult, Debouncer, FileIdMap,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::formats::is_image_file;
+use crate::metadata_store::MetadataStore;
+
+/// 파일 크기 확인 간격 (테더링 프로그램이 쓰는 중인지 판단)
+const STABLE_CHECK_INTERVAL: Duration = Duration::from_millis(300);
+
+/// 파일 쓰기 완료 최대 대기 시간
+const STABLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 가져온 파일 식별 (경로, 수정 시간, 크기), 같은 이름으로 다시 쓴 파일은 새 파일로 처리
+type FileKey = (String, SystemTime, u64);
+
+/// 처리 중인 경로와 가져오기가 끝난 파일
+#[derive(Default)]
+struct IngestState {
+    in_progress: HashSet<String>,
+    processed: HashSet<FileKey>,
+}
+
+/// 핫 폴더 옵션
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HotFolderOptions {
+    /// 새 파일이 들어오면 프론트엔드에서 자동 선택
+    pub auto_select: bool,
+    /// 백업 폴더 (새 파일을 복사, None이면 복사 안 함)
+    pub backup_path: Option<String>,
+}
+
+/// hot-folder-file-added 이벤트
+#[derive(Debug, Clone, Serialize)]
+struct HotFolderFileAdded {
+    path: String,
+    auto_select: bool,
+}
+
+/// hot-folder-backup 이벤트
+#[derive(Debug, Clone, Serialize)]
+struct HotFolderBackup {
+    path: String,
+    backup_path: Option<String>,
+    error: Option<String>,
+}
+
+/// 테더링 촬영 폴더 감시 (새 파일을 바로 썸네일 생성, 선택적으로 백업)
+pub struct HotFolder {
+    debouncer: Option<Debouncer<notify::RecommendedWatcher, FileIdMap>>,
+}
+
+impl HotFolder {
+    pub fn new() -> Self {
+        Self { debouncer: None }
+    }
+
+    pub fn enable(&mut self, app: AppHandle, folder_path: &str, options: HotFolderOptions) -> Result<(), String> {
+        let path = crate::fs_path::to_fs_path(folder_path);
+        if !path.is_dir() {
+            return Err(format!("Invalid folder path: {}", folder_path));
+        }
+        if let Some(backup) = &options.backup_path {
+            if Path::new(backup) == Path::new(folder_path) {
+                return Err(format!("Invalid backup path: same as hot folder ({})", backup));
+            }
+        }
+
+        self.disable();
+
+        // 처리 중이거나 이미 가져온 파일 (임시 이름 → 최종 이름 변경 등으로 이벤트가 여러 번 올 수 있음)
+        let state = Arc::new(Mutex::new(IngestState::default()));
+        let options = Arc::new(options);
+
+        let mut debouncer = new_debouncer(
+            Duration::from_millis(500),
+            None,
+            move |result: DebounceEventResult| {
+                let Ok(events) = result else {
+                    return;
+                };
+                for event in events {
+                    // 새로 만들어졌거나 이름이 바뀌어 들어온 파일만
+                    if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(_))) {
+                        continue;
+                    }
+                    for path in &event.paths {
+                        if !is_image_file(path) || !path.is_file() {
+                            continue;
+                        }
+                        let path_str = crate::fs_path::to_display(path);
+                        if !state.lock().unwrap_or_else(|e| e.into_inner()).in_progress.insert(path_str.clone()) {
+                            continue;
+                        }
+
+                        let app = app.clone();
+                        let options = Arc::clone(&options);
+                        let state = Arc::clone(&state);
+                        tauri::async_runtime::spawn(async move {
+                            ingest(app, path_str, options, state).await;
+                        });
+                    }
+                }
+            },
+        )
+        .map_err(|e| format!("Failed to create watcher: {}", e))?;
+
+        debouncer
+            .watcher()
+            .watch(&path, RecursiveMode::NonRecursive)
+            .map_err(|e| format!("Failed to watch folder: {}", e))?;
+
+        tracing::info!("Hot folder enabled: {}", folder_path);
+        self.debouncer = Some(debouncer);
+        Ok(())
+    }
+
+    pub fn disable(&mut self) {
+        if self.debouncer.take().is_some() {
+            tracing::info!("Hot folder disabled");
+        }
+    }
+}
+
+/// 파일 크기가 더 이상 변하지 않을 때까지 대기 (쓰기 완료 판단)
+/// 반환: 쓰기가 끝난 파일의 식별 키, 시간 안에 끝나지 않으면 None
+async fn wait_until_written(path: &str) -> Option<FileKey> {
+    let source = crate::fs_path::to_fs_path(path);
+    let deadline = Instant::now() + STABLE_TIMEOUT;
+    let mut last_size = None;
+
+    while Instant::now() < deadline {
+        let metadata = fs::metadata(&source).ok();
+        let size = metadata.as_ref().map(|m| m.len());
+        if size.is_some_and(|size| size > 0) && size == last_size {
+            let modified = metadata.and_then(|m| m.modified().ok()).unwrap_or(SystemTime::UNIX_EPOCH);
+            return Some((path.to_string(), modified, size.unwrap_or_default()));
+        }
+        last_size = size;
+        tokio::time::sleep(STABLE_CHECK_INTERVAL).await;
+    }
+    None
+}
+
+/// 백업 폴더로 복사
+/// 같은 이름에 내용까지 같은 파일이 있으면 건너뛰고, 내용이 다르면 번호를 붙인 이름으로 복사 (기존 백업은 덮어쓰지 않음)
+fn backup_file(path: &str, backup_dir: &str) -> Result<String, String> {
+    let source = crate::fs_path::to_fs_path(path);
+    let file_name = source
+        .file_name()
+        .ok_or_else(|| format!("Invalid file path: {}", path))?;
+    let backup_dir = crate::fs_path::to_fs_path(backup_dir);
+    fs::create_dir_all(&backup_dir).map_err(|e| format!("Failed to create backup folder: {}", e))?;
+
+    let existing = backup_dir.join(file_name);
+    let source_len = fs::metadata(&source).map_err(|e| e.to_string())?.len();
+    if fs::metadata(&existing).is_ok_and(|m| m.len() == source_len)
+        && crate::import::hash_file(&existing)? == crate::import::hash_file(&source)?
+    {
+        return Ok(crate::fs_path::to_display(&existing));
+    }
+    let target = crate::import::unique_destination(existing);
+
+    // 임시 이름으로 복사 후 rename (복사 중 종료돼도 불완전한 백업이 남지 않음)
+    let mut partial_name = file_name.to_os_string();
+    partial_name.push(".partial");
+    let partial = backup_dir.join(partial_name);
+    fs::copy(&source, &partial)
+        .and_then(|_| fs::rename(&partial, &target))
+        .map_err(|e| {
+            let _ = fs::remove_file(&partial);
+            format!("Failed to copy to backup folder: {}", e)
+        })?;
+
+    Ok(crate::fs_path::to_display(&target))
+}
+
+/// 새 파일 처리 후 처리 중 표시 해제 (성공한 파일만 가져온 파일로 기록, 실패하면 다음 이벤트에서 다시 시도)
+async fn ingest(app: AppHandle, path: String, options: Arc<HotFolderOptions>, state: Arc<Mutex<IngestState>>) {
+    let key = wait_until_written(&path).await;
+    let already_processed = key
+        .as_ref()
+        .is_some_and(|key| state.lock().unwrap_or_else(|e| e.into_inner()).processed.contains(key));
+
+    let imported = match &key {
+        None => {
+            tracing::warn!("Hot folder file was not completed in time: {}", path);
+            false
+        }
+        Some(_) if already_processed => false,
+        Some(_) => import(&app, &path, &options).await,
+    };
+
+    let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+    state.in_progress.remove(&path);
+    if let Some(key) = key.filter(|_| imported) {
+        state.processed.insert(key);
+    }
+}
+
+/// 가져오기: 썸네일 (큐를 거치지 않고 바로) → 알림 → 백업, 반환: 썸네일과 백업이 모두 성공했는지
+async fn import(app: &AppHandle, path: &str, options: &HotFolderOptions) -> bool {
+    let path = path.to_string();
+
+    if let Some(store) = app.try_state::<Arc<MetadataStore>>() {
+        let store = Arc::clone(&store);
+        let index_path = path.clone();
+        let _ = tokio::task::spawn_blocking(move || store.index_files(&[index_path])).await;
+    }
+
+    match crate::thumbnail::generate_thumbnail(app, &path).await {
+        Ok(result) => {
+            let _ = app.emit("thumbnail-completed", &result);
+        }
+        Err(e) => {
+            tracing::warn!("Failed to generate hot folder thumbnail for {}: {}", path, e);
+            return false;
+        }
+    }
+
+    let _ = app.emit("hot-folder-file-added", HotFolderFileAdded {
+        path: path.clone(),
+        auto_select: options.auto_select,
+    });
+
+    if let Some(backup_dir) = options.backup_path.clone() {
+        let backup_source = path.clone();
+        let result = tokio::task::spawn_blocking(move || backup_file(&backup_source, &backup_dir))
+            .await
+            .unwrap_or_else(|e| Err(e.to_string()));
+
+        if let Err(e) = &result {
+            tracing::warn!("Hot folder backup failed for {}: {}", path, e);
+        }
+        let succeeded = result.is_ok();
+        let _ = app.emit("hot-folder-backup", HotFolderBackup {
+            path,
+            backup_path: result.as_ref().ok().cloned(),
+            error: result.err(),
+        });
+        return succeeded;
+    }
+    true
+}
@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::Manager;
+
+/// HQ 생성 진행 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HqGenerationState {
+    Running,
+    /// 앱 종료/취소로 중단됨 (다음 시작 시 이어서 처리)
+    Interrupted,
+    Completed,
+}
+
+/// 폴더별 HQ 생성 진행 기록
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HqProgressRecord {
+    /// 생성 작업 식별자 (시작할 때마다 새로 발급, 이어서 처리하면 유지)
+    pub token: String,
+    pub folder: String,
+    pub total: usize,
+    pub remaining: Vec<String>,
+    pub state: HqGenerationState,
+    pub updated_at: u64,
+}
+
+/// get_hq_generation_status 응답
+#[derive(Debug, Clone, Serialize)]
+pub struct HqGenerationStatus {
+    pub token: String,
+    pub state: HqGenerationState,
+    pub total: usize,
+    pub completed: usize,
+    pub remaining: usize,
+}
+
+impl From<&HqProgressRecord> for HqGenerationStatus {
+    fn from(record: &HqProgressRecord) -> Self {
+        Self {
+            token: record.token.clone(),
+            state: record.state,
+            total: record.total,
+            completed: record.total.saturating_sub(record.remaining.len()),
+            remaining: record.remaining.len(),
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn new_token() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    format!("{:x}", nanos)
+}
+
+/// 경로 목록의 대표 폴더 (첫 경로의 상위 폴더)
+pub fn folder_of(paths: &[String]) -> Option<String> {
+    let parent = Path::new(paths.first()?).parent()?;
+    Some(parent.to_string_lossy().to_string())
+}
+
+// 진행 기록 파일 경로 (폴더 경로 해시)
+fn get_record_path(app: &tauri::AppHandle, folder: &str) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join("hq_progress");
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create progress directory: {}", e))?;
+
+    let key = blake3::hash(folder.as_bytes()).to_hex();
+    Ok(dir.join(format!("{}.json", &key[..16])))
+}
+
+pub fn load(app: &tauri::AppHandle, folder: &str) -> Option<HqProgressRecord> {
+    let path = get_record_path(app, folder).ok()?;
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str::<HqProgressRecord>(&content)
+        .ok()
+        .filter(|record| record.folder == folder)
+}
+
+pub fn save(app: &tauri::AppHandle, record: &mut HqProgressRecord) -> Result<(), String> {
+    record.updated_at = now_secs();
+    let path = get_record_path(app, &record.folder)?;
+    let json = serde_json::to_string(record)
+        .map_err(|e| format!("Failed to serialize HQ progress: {}", e))?;
+    crate::shutdown::write_atomic(&path, json.as_bytes())
+        .map_err(|e| format!("Failed to write HQ progress: {}", e))
+}
+
+/// 생성 시작: 중단된 기록이 있으면 남은 항목만 이어서 처리
+/// 기록에 없는 경로(새 파일)는 HQ 캐시가 없을 때만 포함
+/// 반환값: (진행 기록, 처리할 경로)
+pub fn begin(app: &tauri::AppHandle, image_paths: Vec<String>) -> (Option<HqProgressRecord>, Vec<String>) {
+    let Some(folder) = folder_of(&image_paths) else {
+        return (None, image_paths);
+    };
+
+    let previous = load(app, &folder).filter(|record| record.state != HqGenerationState::Completed);
+
+    let (token, total, paths) = match previous {
+        Some(record) => {
+            let remaining: HashSet<&String> = record.remaining.iter().collect();
+            let paths: Vec<String> = image_paths
+                .into_iter()
+                .filter(|path| {
+                    remaining.contains(path) || !crate::thumbnail::has_hq_thumbnail(app, path)
+                })
+                .collect();
+            tracing::info!(
+                "Resuming HQ generation for {} ({} of {} remaining)",
+                folder,
+                paths.len(),
+                record.total
+            );
+            // 전체 수는 처음 시작했을 때 기준 (새 파일이 있으면 그만큼 늘어남)
+            let total = record.total.max(paths.len());
+            (record.token, total, paths)
+        }
+        None => (new_token(), image_paths.len(), image_paths),
+    };
+
+    let mut record = HqProgressRecord {
+        token,
+        folder,
+        total,
+        remaining: paths.clone(),
+        state: HqGenerationState::Running,
+        updated_at: 0,
+    };
+    if let Err(e) = save(app, &mut record) {
+        tracing::warn!("Failed to save HQ progress: {}", e);
+    }
+
+    (Some(record), paths)
+}
+
+/// 진행 기록 갱신 (남은 경로와 상태)
+pub fn update(
+    app: &tauri::AppHandle,
+    record: &mut HqProgressRecord,
+    remaining: Vec<String>,
+    state: HqGenerationState,
+) {
+    record.remaining = remaining;
+    record.state = state;
+    if let Err(e) = save(app, record) {
+        tracing::warn!("Failed to save HQ progress: {}", e);
+    }
+}
+
+/// 폴더의 HQ 생성 상태 (기록이 없으면 None)
+/// 실행 중으로 기록됐지만 해당 작업의 워커가 없으면 비정상 종료된 것이므로 Interrupted로 보고
+pub fn status(app: &tauri::AppHandle, folder: &str, running_token: Option<&str>) -> Option<HqGenerationStatus> {
+    let record = load(app, folder)?;
+    let mut status = HqGenerationStatus::from(&record);
+    if status.state == HqGenerationState::Running && running_token != Some(record.token.as_str()) {
+        status.state = HqGenerationState::Interrupted;
+    }
+    Some(status)
+}
@@ -1,4 +1,23 @@
+use serde::Serialize;
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 전원/발열 상태 캐시 유지 시간 (확인 비용이 커서 매번 조회하지 않음)
+const CONDITIONS_CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// 마지막으로 조회한 시스템 상태 (전역)
+static CONDITIONS_CACHE: Mutex<Option<(Instant, SystemConditions)>> = Mutex::new(None);
+
+/// 전원/발열 상태
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct SystemConditions {
+    /// 배터리로 동작 중 (배터리가 없거나 확인 불가면 false)
+    pub on_battery: bool,
+    /// 배터리 잔량 (%)
+    pub battery_percent: Option<u8>,
+    /// 발열로 인한 성능 제한 중 (지원하지 않는 플랫폼은 None)
+    pub thermal_throttled: Option<bool>,
+}
 
 /// 현재 앱 윈도우 핸들 저장 (전역)
 static APP_WINDOW_HANDLE: Mutex<Option<isize>> = Mutex::new(None);
@@ -77,3 +96,160 @@ pub fn should_generate_hq(threshold_ms: u64) -> bool {
     // 앱이 포그라운드에 있으면 유휴 시간 확인
     get_idle_time_ms() >= threshold_ms
 }
+
+/// Windows 전원 상태 (발열 상태는 공개 API가 없어 확인하지 않음)
+#[cfg(target_os = "windows")]
+fn read_system_conditions() -> SystemConditions {
+    use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    // BatteryFlag: 128 = 배터리 없음, BatteryLifePercent: 255 = 알 수 없음
+    const BATTERY_FLAG_NO_BATTERY: u8 = 128;
+    const BATTERY_PERCENT_UNKNOWN: u8 = 255;
+
+    let mut status = SYSTEM_POWER_STATUS::default();
+    if unsafe { GetSystemPowerStatus(&mut status) }.is_err()
+        || status.BatteryFlag & BATTERY_FLAG_NO_BATTERY != 0
+    {
+        return SystemConditions::default();
+    }
+
+    SystemConditions {
+        on_battery: status.ACLineStatus == 0,
+        battery_percent: (status.BatteryLifePercent != BATTERY_PERCENT_UNKNOWN)
+            .then_some(status.BatteryLifePercent),
+        thermal_throttled: None,
+    }
+}
+
+/// macOS 전원/발열 상태 (pmset 출력 파싱)
+#[cfg(target_os = "macos")]
+fn read_system_conditions() -> SystemConditions {
+    use std::process::Command;
+
+    let run = |args: &[&str]| {
+        Command::new("pmset")
+            .args(args)
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+            .unwrap_or_default()
+    };
+
+    // 예: "Now drawing from 'Battery Power'\n -InternalBattery-0 (id=...)\t87%; discharging; ..."
+    let batt = run(&["-g", "batt"]);
+    let battery_percent = batt
+        .split('%')
+        .next()
+        .and_then(|head| head.rsplit(|c: char| !c.is_ascii_digit()).next())
+        .and_then(|digits| digits.parse().ok())
+        .filter(|_| batt.contains("InternalBattery"));
+
+    // 예: "CPU_Speed_Limit 	= 100" (100 미만이면 발열로 클럭 제한 중)
+    let therm = run(&["-g", "therm"]);
+    let thermal_throttled = therm
+        .lines()
+        .find(|line| line.contains("CPU_Speed_Limit"))
+        .and_then(|line| line.split('=').nth(1))
+        .and_then(|value| value.trim().parse::<u32>().ok())
+        .map(|limit| limit < 100);
+
+    SystemConditions {
+        on_battery: batt.contains("'Battery Power'"),
+        battery_percent,
+        thermal_throttled,
+    }
+}
+
+/// Linux 전원/발열 상태 (sysfs)
+#[cfg(target_os = "linux")]
+fn read_system_conditions() -> SystemConditions {
+    use std::fs;
+    use std::path::Path;
+
+    let read = |path: &Path| fs::read_to_string(path).map(|s| s.trim().to_string()).ok();
+
+    let mut has_battery = false;
+    let mut ac_online = false;
+    let mut battery_percent = None;
+
+    if let Ok(entries) = fs::read_dir("/sys/class/power_supply") {
+        for entry in entries.flatten() {
+            let supply = entry.path();
+            match read(&supply.join("type")).as_deref() {
+                Some("Mains") | Some("USB") => {
+                    ac_online |= read(&supply.join("online")).as_deref() == Some("1");
+                }
+                Some("Battery") => {
+                    has_battery = true;
+                    battery_percent = battery_percent
+                        .or_else(|| read(&supply.join("capacity")).and_then(|c| c.parse().ok()));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // passive 트립 포인트(발열 시 클럭 제한 시작 온도)를 넘은 온도 센서가 있으면 제한 중으로 간주
+    let mut thermal_throttled = None;
+    if let Ok(zones) = fs::read_dir("/sys/class/thermal") {
+        for zone in zones.flatten().map(|e| e.path()) {
+            let Some(temp) = read(&zone.join("temp")).and_then(|t| t.parse::<i64>().ok()) else {
+                continue;
+            };
+
+            for trip in 0.. {
+                let Some(trip_type) = read(&zone.join(format!("trip_point_{}_type", trip))) else {
+                    break;
+                };
+                if trip_type != "passive" {
+                    continue;
+                }
+                if let Some(trip_temp) = read(&zone.join(format!("trip_point_{}_temp", trip)))
+                    .and_then(|t| t.parse::<i64>().ok())
+                {
+                    let throttled = trip_temp > 0 && temp >= trip_temp;
+                    thermal_throttled = Some(thermal_throttled.unwrap_or(false) || throttled);
+                }
+            }
+        }
+    }
+
+    SystemConditions {
+        on_battery: has_battery && !ac_online,
+        battery_percent: if has_battery { battery_percent } else { None },
+        thermal_throttled,
+    }
+}
+
+/// 기타 플랫폼은 항상 AC 전원으로 간주
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+fn read_system_conditions() -> SystemConditions {
+    SystemConditions::default()
+}
+
+/// 현재 전원/발열 상태 (5초간 캐시)
+pub fn get_system_conditions() -> SystemConditions {
+    if let Ok(cache) = CONDITIONS_CACHE.lock() {
+        if let Some((checked_at, conditions)) = *cache {
+            if checked_at.elapsed() < CONDITIONS_CACHE_TTL {
+                return conditions;
+            }
+        }
+    }
+
+    let conditions = read_system_conditions();
+    if let Ok(mut cache) = CONDITIONS_CACHE.lock() {
+        *cache = Some((Instant::now(), conditions));
+    }
+    conditions
+}
+
+/// 전원/발열 상태 때문에 HQ 썸네일 생성을 미뤄야 하는지 확인
+pub fn should_defer_hq(defer_on_battery: bool, defer_on_thermal: bool) -> bool {
+    if !defer_on_battery && !defer_on_thermal {
+        return false;
+    }
+
+    let conditions = get_system_conditions();
+    (defer_on_battery && conditions.on_battery)
+        || (defer_on_thermal && conditions.thermal_throttled == Some(true))
+}
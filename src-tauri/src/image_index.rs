@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::RwLock;
+
+use crate::filtering::{self, ImageFilter};
+use crate::formats::is_image_file;
+use crate::metadata_store::MetadataStore;
+use crate::sorting::{self, SortKey, SortOrder};
+
+/// 폴더 정렬 옵션
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct FolderSort {
+    pub key: SortKey,
+    pub order: SortOrder,
+}
+
+impl Default for FolderSort {
+    fn default() -> Self {
+        Self {
+            key: SortKey::Name,
+            order: SortOrder::Asc,
+        }
+    }
+}
+
+/// 현재 폴더의 정렬/필터된 이미지 목록 (프론트엔드와 썸네일 큐가 같은 인덱스 사용)
+#[derive(Debug, Default)]
+pub struct ImageIndex {
+    paths: Vec<String>,
+}
+
+/// 폴더 열기 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct FolderIndexInfo {
+    pub folder: String,
+    pub total: usize,
+}
+
+/// 이미지 목록 페이지
+#[derive(Debug, Clone, Serialize)]
+pub struct ImagePage {
+    pub offset: usize,
+    pub total: usize,
+    pub paths: Vec<String>,
+}
+
+/// 폴더의 이미지 파일 목록 (하위 폴더 제외, 항목별 canonicalize 없이 빠르게)
+fn list_image_files(folder: &Path) -> Result<Vec<String>, String> {
+    let source = crate::fs_path::to_fs_path(folder);
+    let entries = fs::read_dir(&source)
+        .map_err(|e| format!("Failed to read directory: {}", e))?;
+
+    Ok(entries
+        .flatten()
+        .filter(|entry| entry.file_type().map(|t| !t.is_dir()).unwrap_or(false))
+        .map(|entry| folder.join(entry.file_name()))
+        .filter(|path| is_image_file(path))
+        .map(|path| crate::fs_path::normalize_unicode(&path.to_string_lossy()))
+        .collect())
+}
+
+/// 폴더 이미지 목록 생성 (필터 → 정렬, manual_order는 사용자 지정 순서 정렬용)
+pub fn build(
+    store: &MetadataStore,
+    folder: &str,
+    sort: FolderSort,
+    filter: Option<&ImageFilter>,
+    manual_order: Option<&[String]>,
+) -> Result<Vec<String>, String> {
+    let mut paths = list_image_files(Path::new(folder))?;
+
+    if let Some(filter) = filter {
+        paths = filtering::filter_image_paths(store, paths, filter)?;
+    }
+
+    Ok(sorting::sort_image_paths(store, paths, sort.key, sort.order, manual_order)?.paths)
+}
+
+impl ImageIndex {
+    /// 목록 교체
+    pub fn replace(&mut self, folder: String, paths: Vec<String>) -> FolderIndexInfo {
+        let total = paths.len();
+        self.paths = paths;
+        FolderIndexInfo { folder, total }
+    }
+
+    /// 전체 목록 (정렬/필터 적용된 순서)
+    pub fn paths(&self) -> &[String] {
+        &self.paths
+    }
+
+    /// offset부터 count개 (범위를 넘으면 있는 만큼)
+    pub fn page(&self, offset: usize, count: usize) -> ImagePage {
+        let start = offset.min(self.paths.len());
+        let end = offset.saturating_add(count).min(self.paths.len());
+        ImagePage {
+            offset: start,
+            total: self.paths.len(),
+            paths: self.paths[start..end].to_vec(),
+        }
+    }
+}
+
+/// 관리 상태 타입
+pub type SharedImageIndex = RwLock<ImageIndex>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_bounds() {
+        let mut index = ImageIndex::default();
+        index.replace("/photos".to_string(), (0..5).map(|i| format!("/photos/{}.jpg", i)).collect());
+
+        assert_eq!(index.page(3, 10).paths, vec!["/photos/3.jpg", "/photos/4.jpg"]);
+        assert!(index.page(10, 10).paths.is_empty());
+        assert_eq!(index.page(0, 2).total, 5);
+    }
+}
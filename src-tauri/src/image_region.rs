@@ -0,0 +1,150 @@
+use lazy_static::lazy_static;
+use lru::LruCache;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use crate::color_profile::{self, PixelLayout};
+use crate::thumbnail;
+use crate::transform::CropRect;
+
+/// 디코딩한 원본을 보관할 이미지 수 (60MP RGB 한 장이 약 180MB)
+const FRAME_CACHE_SIZE: usize = 2;
+
+/// 영역 WebP 품질 (픽셀 확인용이라 높게)
+const REGION_QUALITY: f32 = 95.0;
+
+/// 한 번에 요청할 수 있는 최대 출력 크기 (긴 변 px)
+const MAX_REGION_EDGE: u32 = 8192;
+
+/// 디코딩한 원본 (방향 적용, sRGB)
+struct Frame {
+    rgb: Vec<u8>,
+    width: u32,
+    height: u32,
+}
+
+lazy_static! {
+    /// 캐시 키(경로+수정 시간)별 디코딩 결과
+    static ref FRAMES: Mutex<LruCache<String, Arc<Frame>>> =
+        Mutex::new(LruCache::new(NonZeroUsize::new(FRAME_CACHE_SIZE).unwrap()));
+    /// 디코딩은 한 번에 하나 (같은 이미지를 동시에 요청해도 한 번만 디코딩)
+    static ref DECODE_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// 확대 보기 영역 (WebP)
+#[derive(Debug, Clone, Serialize)]
+pub struct ImageRegion {
+    pub data_base64: String,
+    /// 출력 크기
+    pub width: u32,
+    pub height: u32,
+    /// 원본 전체 크기 (방향 적용 후)
+    pub source_width: u32,
+    pub source_height: u32,
+}
+
+fn cached_frame(cache_key: &str) -> Option<Arc<Frame>> {
+    FRAMES.lock().ok()?.get(cache_key).cloned()
+}
+
+// 원본 전체 디코딩 (캐시에 있으면 재사용)
+fn load_frame(source: &str) -> Result<Arc<Frame>, String> {
+    let mtime = thumbnail::get_file_mtime(source)?;
+    let cache_key = thumbnail::generate_cache_key(source, mtime);
+    if let Some(frame) = cached_frame(&cache_key) {
+        return Ok(frame);
+    }
+
+    let _guard = DECODE_LOCK.lock().map_err(|_| "Decode lock poisoned".to_string())?;
+    if let Some(frame) = cached_frame(&cache_key) {
+        return Ok(frame);
+    }
+
+    let (img, icc_profile) = crate::export::decode(Path::new(source))?;
+    let (width, height) = (img.width(), img.height());
+    let mut rgb = if img.color().has_alpha() {
+        thumbnail::composite_over_background(&img.into_rgba8().into_raw(), [255, 255, 255])
+    } else {
+        img.into_rgb8().into_raw()
+    };
+    color_profile::convert_to_srgb(icc_profile.as_deref(), &mut rgb, PixelLayout::Rgb8);
+
+    let frame = Arc::new(Frame { rgb, width, height });
+    if let Ok(mut frames) = FRAMES.lock() {
+        frames.put(cache_key, frame.clone());
+    }
+    Ok(frame)
+}
+
+/// 영역 범위 확인 (방향 적용 후 이미지 기준)
+fn check_rect(rect: CropRect, width: u32, height: u32) -> Result<(), String> {
+    if rect.width == 0 || rect.height == 0 {
+        return Err("Invalid region: empty".to_string());
+    }
+    if rect.x.checked_add(rect.width).is_none_or(|right| right > width)
+        || rect.y.checked_add(rect.height).is_none_or(|bottom| bottom > height)
+    {
+        return Err(format!("Invalid region: outside of {}x{} image", width, height));
+    }
+    Ok(())
+}
+
+/// 원본의 일부 영역을 지정한 배율로 디코딩 (1.0이면 100% 픽셀)
+/// 원본은 처음 한 번만 디코딩해 캐시하고, 이후에는 영역만 잘라 보냄
+pub fn get_region(path: &str, rect: CropRect, scale: f32) -> Result<ImageRegion, String> {
+    let source = crate::fs_path::to_fs_string(path);
+    let frame = load_frame(&source)?;
+    check_rect(rect, frame.width, frame.height)?;
+
+    let width = ((rect.width as f32 * scale).round() as u32).max(1);
+    let height = ((rect.height as f32 * scale).round() as u32).max(1);
+    if width.max(height) > MAX_REGION_EDGE {
+        return Err(format!("Region too large: {}x{} (max {}px)", width, height, MAX_REGION_EDGE));
+    }
+
+    let row_bytes = rect.width as usize * 3;
+    let mut region = vec![0u8; row_bytes * rect.height as usize];
+    region.par_chunks_mut(row_bytes).enumerate().for_each(|(row, dst)| {
+        let start = ((rect.y as usize + row) * frame.width as usize + rect.x as usize) * 3;
+        dst.copy_from_slice(&frame.rgb[start..start + row_bytes]);
+    });
+    if (width, height) != (rect.width, rect.height) {
+        region = thumbnail::resize_fast(
+            region,
+            rect.width,
+            rect.height,
+            width,
+            height,
+            fast_image_resize::PixelType::U8x3,
+        )?;
+    }
+
+    // 캐시한 원본은 sRGB로 두고 보낼 영역만 소프트 프루핑
+    crate::monitor_profile::soft_proof(&mut region, PixelLayout::Rgb8);
+
+    let webp = thumbnail::encode_thumbnail_to_webp(&region, width, height, false, REGION_QUALITY)?;
+    Ok(ImageRegion {
+        data_base64: thumbnail::encode_to_base64(&webp),
+        width,
+        height,
+        source_width: frame.width,
+        source_height: frame.height,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_rect() {
+        let rect = |x, y, width, height| CropRect { x, y, width, height };
+        assert!(check_rect(rect(0, 0, 100, 100), 100, 100).is_ok());
+        assert!(check_rect(rect(50, 50, 51, 10), 100, 100).is_err());
+        assert!(check_rect(rect(0, 0, 0, 10), 100, 100).is_err());
+        assert!(check_rect(rect(u32::MAX, 0, 1, 1), 100, 100).is_err());
+    }
+}
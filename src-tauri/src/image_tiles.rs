@@ -0,0 +1,253 @@
+use lazy_static::lazy_static;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tauri::Manager;
+
+use crate::color_profile::{self, PixelLayout};
+use crate::thumbnail;
+
+/// 타일 한 변 크기 (px)
+pub const TILE_SIZE: u32 = 512;
+
+/// 보관할 타일 피라미드 수 (넘으면 오래 안 쓴 것부터 삭제)
+const MAX_PYRAMIDS: usize = 16;
+
+/// 타일 WebP 품질
+const TILE_QUALITY: f32 = 85.0;
+
+// 피라미드 정보 파일 (모든 타일을 쓴 뒤 마지막에 저장, 있으면 완성된 피라미드)
+const INFO_FILE: &str = "pyramid.json";
+
+lazy_static! {
+    /// 생성 중인 피라미드 (같은 이미지를 동시에 요청하면 한 번만 생성)
+    static ref GENERATING: Mutex<HashMap<String, Arc<Mutex<()>>>> = Mutex::new(HashMap::new());
+}
+
+/// 타일 피라미드 정보 (Deep Zoom 방식)
+/// 레벨 0은 1x1, 레벨마다 2배씩 커져 max_level이 원본 크기
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PyramidInfo {
+    pub width: u32,
+    pub height: u32,
+    pub tile_size: u32,
+    pub max_level: u32,
+}
+
+impl PyramidInfo {
+    fn new(width: u32, height: u32) -> Self {
+        let long_edge = width.max(height).max(1);
+        Self {
+            width,
+            height,
+            tile_size: TILE_SIZE,
+            max_level: u32::BITS - (long_edge - 1).leading_zeros(),
+        }
+    }
+
+    /// 레벨별 이미지 크기
+    pub fn level_size(&self, level: u32) -> (u32, u32) {
+        let shift = self.max_level - level.min(self.max_level);
+        (
+            self.width.div_ceil(1 << shift).max(1),
+            self.height.div_ceil(1 << shift).max(1),
+        )
+    }
+
+    /// 레벨별 타일 개수 (가로, 세로)
+    pub fn tile_count(&self, level: u32) -> (u32, u32) {
+        let (width, height) = self.level_size(level);
+        (width.div_ceil(self.tile_size), height.div_ceil(self.tile_size))
+    }
+}
+
+/// 타일 이미지 (WebP)
+#[derive(Debug, Clone, Serialize)]
+pub struct ImageTile {
+    pub data_base64: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+// 타일 캐시 폴더 (썸네일 캐시와 분리, 이미지마다 하위 폴더)
+fn tiles_root(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|p| p.join("tiles"))
+        .map_err(|e| format!("Failed to get app data directory: {}", e))
+}
+
+fn tile_path(dir: &Path, level: u32, x: u32, y: u32) -> PathBuf {
+    dir.join(level.to_string()).join(format!("{}_{}.webp", x, y))
+}
+
+// 오래 안 쓴 피라미드 정리 (최근 MAX_PYRAMIDS개 유지)
+fn evict_old_pyramids(root: &Path, keep: &Path) {
+    let Ok(entries) = fs::read_dir(root) else {
+        return;
+    };
+    let mut pyramids: Vec<(PathBuf, std::time::SystemTime)> = entries
+        .flatten()
+        .filter(|entry| entry.path() != keep)
+        .filter_map(|entry| {
+            let modified = entry.metadata().and_then(|metadata| metadata.modified()).ok()?;
+            Some((entry.path(), modified))
+        })
+        .collect();
+    if pyramids.len() < MAX_PYRAMIDS {
+        return;
+    }
+
+    pyramids.sort_by(|a, b| b.1.cmp(&a.1));
+    for (path, _) in pyramids.into_iter().skip(MAX_PYRAMIDS - 1) {
+        let _ = fs::remove_dir_all(path);
+    }
+}
+
+// 원본을 한 번 디코딩해 모든 레벨의 타일 생성 (레벨마다 절반으로 축소)
+fn generate(source: &Path, dir: &Path) -> Result<PyramidInfo, String> {
+    let (img, icc_profile) = crate::export::decode(source)?;
+    let info = PyramidInfo::new(img.width(), img.height());
+
+    // 타일은 불투명 RGB (투명 영역은 흰 배경)
+    let mut pixels = if img.color().has_alpha() {
+        thumbnail::composite_over_background(&img.into_rgba8().into_raw(), [255, 255, 255])
+    } else {
+        img.into_rgb8().into_raw()
+    };
+    color_profile::convert_to_srgb(icc_profile.as_deref(), &mut pixels, PixelLayout::Rgb8);
+
+    let (mut width, mut height) = (info.width, info.height);
+    for level in (0..=info.max_level).rev() {
+        let (level_width, level_height) = info.level_size(level);
+        if (level_width, level_height) != (width, height) {
+            pixels = thumbnail::resize_fast(
+                pixels,
+                width,
+                height,
+                level_width,
+                level_height,
+                fast_image_resize::PixelType::U8x3,
+            )?;
+            (width, height) = (level_width, level_height);
+        }
+        write_level(&pixels, width, height, &info, level, dir)?;
+    }
+
+    let json = serde_json::to_string(&info).map_err(|e| e.to_string())?;
+    fs::write(dir.join(INFO_FILE), json).map_err(|e| format!("Failed to write pyramid info: {}", e))?;
+    Ok(info)
+}
+
+// 한 레벨의 타일을 잘라 WebP로 저장
+fn write_level(pixels: &[u8], width: u32, height: u32, info: &PyramidInfo, level: u32, dir: &Path) -> Result<(), String> {
+    let level_dir = dir.join(level.to_string());
+    fs::create_dir_all(&level_dir).map_err(|e| format!("Failed to create tile directory: {}", e))?;
+
+    let (columns, rows) = info.tile_count(level);
+    let tiles: Vec<(u32, u32)> = (0..rows).flat_map(|y| (0..columns).map(move |x| (x, y))).collect();
+    tiles.par_iter().try_for_each(|&(x, y)| {
+        let left = x * info.tile_size;
+        let top = y * info.tile_size;
+        let tile_width = info.tile_size.min(width - left);
+        let tile_height = info.tile_size.min(height - top);
+
+        let mut tile = Vec::with_capacity((tile_width * tile_height * 3) as usize);
+        for row in top..top + tile_height {
+            let start = ((row * width + left) * 3) as usize;
+            tile.extend_from_slice(&pixels[start..start + (tile_width * 3) as usize]);
+        }
+
+        let webp = thumbnail::encode_thumbnail_to_webp(&tile, tile_width, tile_height, false, TILE_QUALITY)?;
+        fs::write(tile_path(dir, level, x, y), webp).map_err(|e| format!("Failed to write tile: {}", e))
+    })
+}
+
+/// 타일 피라미드 준비 (캐시가 없거나 원본이 바뀌었으면 생성)
+/// 큰 파노라마도 원본은 한 번만 디코딩하고, 이후 타일은 캐시에서 읽음
+pub fn ensure_pyramid(app: &tauri::AppHandle, path: &str) -> Result<(PyramidInfo, PathBuf), String> {
+    let source = crate::fs_path::to_fs_string(path);
+    let mtime = thumbnail::get_file_mtime(&source)?;
+    let cache_key = thumbnail::generate_cache_key(path, mtime);
+    let root = tiles_root(app)?;
+    let dir = root.join(&cache_key);
+
+    let lock = GENERATING
+        .lock()
+        .map_err(|_| "Tile lock poisoned".to_string())?
+        .entry(cache_key.clone())
+        .or_default()
+        .clone();
+    let result = {
+        let _guard = lock.lock().map_err(|_| "Tile lock poisoned".to_string())?;
+        let cached = fs::read_to_string(dir.join(INFO_FILE))
+            .ok()
+            .and_then(|json| serde_json::from_str::<PyramidInfo>(&json).ok());
+        match cached {
+            Some(info) => Ok(info),
+            None => {
+                // 중간에 실패한 피라미드는 지우고 처음부터 생성
+                let _ = fs::remove_dir_all(&dir);
+                evict_old_pyramids(&root, &dir);
+                fs::create_dir_all(&dir).map_err(|e| format!("Failed to create tile directory: {}", e))?;
+                tracing::info!("Generating tile pyramid: {}", path);
+                generate(Path::new(&source), &dir).inspect_err(|_| {
+                    let _ = fs::remove_dir_all(&dir);
+                })
+            }
+        }
+    };
+    // 기다리는 요청이 없으면 잠금 정리
+    if let Ok(mut generating) = GENERATING.lock() {
+        if Arc::strong_count(&lock) <= 2 {
+            generating.remove(&cache_key);
+        }
+    }
+
+    // 최근 사용 시간 갱신 (정리 순서 기준)
+    let _ = filetime::set_file_mtime(&dir, filetime::FileTime::now());
+    result.map(|info| (info, dir))
+}
+
+/// 타일 하나 읽기 (WebP)
+pub fn get_tile(app: &tauri::AppHandle, path: &str, level: u32, x: u32, y: u32) -> Result<ImageTile, String> {
+    let (info, dir) = ensure_pyramid(app, path)?;
+    if level > info.max_level {
+        return Err(format!("Invalid tile level: {} (0-{})", level, info.max_level));
+    }
+    let (columns, rows) = info.tile_count(level);
+    if x >= columns || y >= rows {
+        return Err(format!("Invalid tile: {}/{}_{} ({}x{} tiles)", level, x, y, columns, rows));
+    }
+
+    let (level_width, level_height) = info.level_size(level);
+    let data = fs::read(tile_path(&dir, level, x, y)).map_err(|e| format!("Failed to read tile: {}", e))?;
+    Ok(ImageTile {
+        data_base64: thumbnail::encode_to_base64(&data),
+        width: info.tile_size.min(level_width - x * info.tile_size),
+        height: info.tile_size.min(level_height - y * info.tile_size),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pyramid_levels() {
+        let info = PyramidInfo::new(30000, 3000);
+        // 2^15 = 32768 ≥ 30000
+        assert_eq!(info.max_level, 15);
+        assert_eq!(info.level_size(15), (30000, 3000));
+        assert_eq!(info.level_size(14), (15000, 1500));
+        assert_eq!(info.level_size(0), (1, 1));
+        assert_eq!(info.tile_count(15), (59, 6));
+
+        let single = PyramidInfo::new(1, 1);
+        assert_eq!(single.max_level, 0);
+        assert_eq!(single.tile_count(0), (1, 1));
+    }
+}
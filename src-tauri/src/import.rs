@@ -0,0 +1,369 @@
+use chrono::NaiveDateTime;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tauri::{AppHandle, Emitter};
+use walkdir::WalkDir;
+
+use crate::formats::is_image_file;
+use crate::jobs::Job;
+use crate::filename_template::{self, read_capture_info, sanitize_component, TemplateContext};
+use crate::metadata_store::MetadataStore;
+
+/// 가져오기 기록 테이블 스키마 (파일 해시 기준 중복 판단)
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS import_ledger (
+        hash        TEXT PRIMARY KEY,
+        source_name TEXT NOT NULL,
+        dest_path   TEXT NOT NULL,
+        imported_at INTEGER NOT NULL
+    );
+";
+
+/// 진행률 이벤트 최소 간격 (밀리초)
+const PROGRESS_INTERVAL_MS: u128 = 200;
+
+/// 가져오기 옵션
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImportOptions {
+    /// 날짜 폴더 템플릿 (YYYY, MM, DD 치환, 예: "YYYY/YYYY-MM-DD")
+    pub folder_template: String,
+    /// 파일명 템플릿 (filename_template 토큰), None이면 원본 이름
+    pub rename_template: Option<String>,
+    /// 이미 가져온 파일 건너뛰기
+    pub skip_duplicates: bool,
+    /// 하위 폴더 포함 (카드의 DCIM/100XXXXX 등)
+    pub include_subfolders: bool,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            folder_template: "YYYY/YYYY-MM-DD".to_string(),
+            rename_template: None,
+            skip_duplicates: true,
+            include_subfolders: true,
+        }
+    }
+}
+
+/// 가져오기 진행률 (import-progress 이벤트)
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportProgress {
+    pub processed: usize,
+    pub total: usize,
+    pub copied: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub bytes_done: u64,
+    pub total_bytes: u64,
+    pub current_file: String,
+    pub eta_secs: Option<u64>,
+}
+
+/// 가져오기 실패 항목
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportFailure {
+    pub path: String,
+    pub error: String,
+}
+
+/// 가져오기 결과 (import-completed 이벤트)
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportSummary {
+    pub copied: Vec<String>, // 복사된 대상 경로
+    pub skipped: usize,
+    pub failed: Vec<ImportFailure>,
+}
+
+/// 가져오기 기록 테이블 생성
+pub fn init_schema(store: &MetadataStore) -> Result<(), String> {
+    store.with_conn(|conn| conn.execute_batch(SCHEMA))
+}
+
+/// 파일 내용 해시 (blake3)
+pub fn hash_file(path: &Path) -> Result<String, String> {
+    let file = fs::File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut hasher = blake3::Hasher::new();
+    hasher
+        .update_reader(BufReader::new(file))
+        .map_err(|e| format!("Failed to hash file: {}", e))?;
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+/// 복사하면서 원본 해시 계산 (원본은 한 번만 읽음), 반환: 원본 해시
+fn copy_with_hash(source: &Path, target: &Path) -> Result<String, String> {
+    let mut reader = fs::File::open(source).map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut writer = fs::File::create(target).map_err(|e| format!("Failed to create file: {}", e))?;
+    let mut hasher = blake3::Hasher::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+    loop {
+        let read = reader.read(&mut buffer).map_err(|e| format!("Failed to read file: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        writer
+            .write_all(&buffer[..read])
+            .map_err(|e| format!("Failed to copy file: {}", e))?;
+    }
+    writer.sync_all().map_err(|e| format!("Failed to copy file: {}", e))?;
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+/// 날짜 폴더 템플릿 적용 ("YYYY/YYYY-MM-DD" -> "2024/2024-05-17")
+fn expand_folder_template(template: &str, date: &NaiveDateTime) -> PathBuf {
+    let expanded = template
+        .replace("YYYY", &date.format("%Y").to_string())
+        .replace("MM", &date.format("%m").to_string())
+        .replace("DD", &date.format("%d").to_string());
+
+    expanded
+        .split(['/', '\\'])
+        .map(sanitize_component)
+        .filter(|part| !part.is_empty() && part != "." && part != "..")
+        .collect()
+}
+
+/// 대상 경로가 이미 있으면 _1, _2 ... 를 붙인 경로 반환
+pub fn unique_destination(path: PathBuf) -> PathBuf {
+    if !path.exists() {
+        return path;
+    }
+
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let ext = path.extension().map(|e| e.to_string_lossy().to_string());
+    let parent = path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+    (1..)
+        .map(|i| {
+            let name = match &ext {
+                Some(ext) => format!("{}_{}.{}", stem, i, ext),
+                None => format!("{}_{}", stem, i),
+            };
+            parent.join(name)
+        })
+        .find(|candidate| !candidate.exists())
+        .unwrap_or(path)
+}
+
+/// 원본 폴더의 이미지 파일 수집
+fn collect_sources(source: &Path, include_subfolders: bool) -> Vec<(PathBuf, u64)> {
+    let max_depth = if include_subfolders { usize::MAX } else { 1 };
+
+    let mut files: Vec<(PathBuf, u64)> = WalkDir::new(source)
+        .max_depth(max_depth)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && is_image_file(e.path()))
+        .filter(|e| !e.file_name().to_string_lossy().starts_with('.'))
+        .map(|e| {
+            let size = e.metadata().map(|m| m.len()).unwrap_or(0);
+            (e.into_path(), size)
+        })
+        .collect();
+
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    files
+}
+
+/// 파일 하나 가져오기 (복사 후 원본 수정 시간 유지)
+/// 원본 해시로 중복 확인 → 대상 폴더의 임시 파일로 복사하면서 해시 → 복사본 해시 확인 → 최종 이름으로 rename
+/// 반환값: 복사된 경로 (중복이면 None)
+fn import_file(
+    store: &MetadataStore,
+    source: &Path,
+    dest_root: &Path,
+    options: &ImportOptions,
+    sequence: usize,
+) -> Result<Option<PathBuf>, String> {
+    let source_name = source
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let partial = dest_root.join(format!("{}.partial", source_name));
+
+    let result = copy_and_place(store, source, &partial, dest_root, options, sequence);
+    if !matches!(result, Ok(Some(_))) {
+        let _ = fs::remove_file(&partial);
+    }
+    let Some((dest_path, hash)) = result? else {
+        return Ok(None);
+    };
+
+    store.with_conn(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO import_ledger (hash, source_name, dest_path, imported_at) \
+             VALUES (?1, ?2, ?3, ?4)",
+            params![hash, source_name, dest_path.to_string_lossy(), chrono::Utc::now().timestamp()],
+        )
+    })?;
+
+    Ok(Some(dest_path))
+}
+
+// 임시 파일로 복사 후 확인해서 대상 경로로 옮김, 반환: (대상 경로, 원본 해시), 중복이면 None
+fn copy_and_place(
+    store: &MetadataStore,
+    source: &Path,
+    partial: &Path,
+    dest_root: &Path,
+    options: &ImportOptions,
+    sequence: usize,
+) -> Result<Option<(PathBuf, String)>, String> {
+    // 중복이면 대상 폴더에 아무것도 쓰지 않도록 원본 해시로 먼저 확인
+    let source_hash = if options.skip_duplicates {
+        let hash = hash_file(source)?;
+        let exists = store.with_conn(|conn| {
+            conn.query_row("SELECT 1 FROM import_ledger WHERE hash = ?1", params![hash], |_| Ok(()))
+                .optional()
+        })?;
+        if exists.is_some() {
+            return Ok(None);
+        }
+        Some(hash)
+    } else {
+        None
+    };
+
+    let hash = copy_with_hash(source, partial)?;
+
+    // 쓴 내용이 원본과 같은지 확인 (카드 읽기/디스크 쓰기 오류, 확인 후 원본이 바뀐 경우)
+    if source_hash.is_some_and(|expected| expected != hash) || hash_file(partial)? != hash {
+        return Err(format!("Copied file does not match source: {}", source.display()));
+    }
+
+    let (date, camera) = read_capture_info(source);
+    let dest_dir = dest_root.join(expand_folder_template(&options.folder_template, &date));
+    fs::create_dir_all(&dest_dir)
+        .map_err(|e| format!("Failed to create folder: {}", e))?;
+
+    let file_name = match &options.rename_template {
+        Some(template) => filename_template::expand(template, &TemplateContext {
+            source,
+            date_taken: date,
+            camera: camera.as_deref(),
+            sequence,
+        })?,
+        None => source
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+    };
+
+    if let Ok(modified) = fs::metadata(source).and_then(|m| m.modified()) {
+        let _ = filetime::set_file_mtime(partial, filetime::FileTime::from_system_time(modified));
+    }
+    let dest_path = unique_destination(dest_dir.join(file_name));
+    fs::rename(partial, &dest_path).map_err(|e| format!("Failed to move file: {}", e))?;
+    Ok(Some((dest_path, hash)))
+}
+
+/// 메모리 카드 등에서 사진 가져오기
+/// 진행 중에는 import-progress, 끝나면 import-completed 이벤트 전송 (작업이 취소되면 남은 파일은 건너뜀)
+pub fn import_from_device(
+    app: &AppHandle,
+    store: &MetadataStore,
+    source: &Path,
+    dest: &Path,
+    options: &ImportOptions,
+    job: &Job,
+) -> Result<ImportSummary, String> {
+    if !source.is_dir() {
+        return Err(format!("{}: source folder {}", crate::error::NOT_FOUND, source.display()));
+    }
+    fs::create_dir_all(dest).map_err(|e| format!("Failed to create destination: {}", e))?;
+
+    let files = collect_sources(source, options.include_subfolders);
+    let total_bytes: u64 = files.iter().map(|(_, size)| size).sum();
+
+    let mut summary = ImportSummary {
+        copied: Vec::new(),
+        skipped: 0,
+        failed: Vec::new(),
+    };
+
+    let started = Instant::now();
+    let mut last_emit: Option<Instant> = None;
+    let mut bytes_done: u64 = 0;
+
+    for (index, (path, size)) in files.iter().enumerate() {
+        if job.is_cancelled() {
+            break;
+        }
+        match import_file(store, path, dest, options, summary.copied.len() + 1) {
+            Ok(Some(dest_path)) => summary.copied.push(dest_path.to_string_lossy().to_string()),
+            Ok(None) => summary.skipped += 1,
+            Err(error) => summary.failed.push(ImportFailure {
+                path: path.to_string_lossy().to_string(),
+                error,
+            }),
+        }
+        bytes_done += size;
+        job.set_progress(index + 1, files.len());
+
+        let is_last = index + 1 == files.len();
+        if is_last || last_emit.is_none_or(|t| t.elapsed().as_millis() >= PROGRESS_INTERVAL_MS) {
+            last_emit = Some(Instant::now());
+
+            // 처리한 바이트 기준으로 남은 시간 추정
+            let elapsed = started.elapsed().as_secs_f64();
+            let eta_secs = (bytes_done > 0 && elapsed > 0.0).then(|| {
+                let rate = bytes_done as f64 / elapsed;
+                (total_bytes.saturating_sub(bytes_done) as f64 / rate).round() as u64
+            });
+
+            let _ = app.emit("import-progress", ImportProgress {
+                processed: index + 1,
+                total: files.len(),
+                copied: summary.copied.len(),
+                skipped: summary.skipped,
+                failed: summary.failed.len(),
+                bytes_done,
+                total_bytes,
+                current_file: path.to_string_lossy().to_string(),
+                eta_secs,
+            });
+        }
+    }
+
+    let _ = app.emit("import-completed", &summary);
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_folder_template() {
+        let date = NaiveDateTime::parse_from_str("2024-05-17 09:30:05", "%Y-%m-%d %H:%M:%S").unwrap();
+
+        assert_eq!(
+            expand_folder_template("YYYY/YYYY-MM-DD", &date),
+            PathBuf::from("2024").join("2024-05-17")
+        );
+        assert_eq!(
+            expand_folder_template("../YYYY", &date),
+            PathBuf::from("2024")
+        );
+    }
+
+    #[test]
+    fn test_copy_with_hash() {
+        let dir = std::env::temp_dir().join(format!("pixengine-import-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("A001.jpg");
+        let partial = dir.join("A001.jpg.partial");
+        fs::write(&source, vec![7u8; 3 * 1024 * 1024 + 5]).unwrap();
+
+        let hash = copy_with_hash(&source, &partial).unwrap();
+        assert_eq!(hash, hash_file(&source).unwrap());
+        assert_eq!(hash, hash_file(&partial).unwrap());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,195 @@
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use xmp_toolkit::{xmp_ns, OpenFileOptions, XmpFile, XmpMeta, XmpValue};
+
+use crate::filename_template::{self, read_capture_info, TemplateContext};
+
+/// IPTC Core 항목 (XMP photoshop:, Iptc4xmpCore:, dc: 로 저장)
+/// 쓰기에서는 None이면 그대로 두고, 빈 문자열이면 삭제
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IptcMetadata {
+    /// 설명/캡션 (dc:description)
+    pub description: Option<String>,
+    /// 헤드라인 (photoshop:Headline)
+    pub headline: Option<String>,
+    /// 제목 (dc:title)
+    pub title: Option<String>,
+    /// 작성자 (dc:creator 첫 항목)
+    pub creator: Option<String>,
+    /// 크레딧 (photoshop:Credit)
+    pub credit: Option<String>,
+    /// 세부 위치 (Iptc4xmpCore:Location)
+    pub sublocation: Option<String>,
+    pub city: Option<String>,
+    /// 주/도 (photoshop:State)
+    pub state: Option<String>,
+    pub country: Option<String>,
+    /// ISO 국가 코드 (Iptc4xmpCore:CountryCode)
+    pub country_code: Option<String>,
+}
+
+/// XMP 속성 형태
+#[derive(Clone, Copy)]
+enum FieldKind {
+    Text,
+    /// 언어별 대체 텍스트 (x-default)
+    LangAlt,
+    /// 순서 있는 목록 (첫 항목만 사용)
+    Seq,
+}
+
+/// 항목별 (형태, 네임스페이스, 속성 이름, 값)
+fn fields(metadata: &IptcMetadata) -> [(FieldKind, &'static str, &'static str, Option<&String>); 10] {
+    [
+        (FieldKind::LangAlt, xmp_ns::DC, "description", metadata.description.as_ref()),
+        (FieldKind::Text, xmp_ns::PHOTOSHOP, "Headline", metadata.headline.as_ref()),
+        (FieldKind::LangAlt, xmp_ns::DC, "title", metadata.title.as_ref()),
+        (FieldKind::Seq, xmp_ns::DC, "creator", metadata.creator.as_ref()),
+        (FieldKind::Text, xmp_ns::PHOTOSHOP, "Credit", metadata.credit.as_ref()),
+        (FieldKind::Text, xmp_ns::IPTC_CORE, "Location", metadata.sublocation.as_ref()),
+        (FieldKind::Text, xmp_ns::PHOTOSHOP, "City", metadata.city.as_ref()),
+        (FieldKind::Text, xmp_ns::PHOTOSHOP, "State", metadata.state.as_ref()),
+        (FieldKind::Text, xmp_ns::PHOTOSHOP, "Country", metadata.country.as_ref()),
+        (FieldKind::Text, xmp_ns::IPTC_CORE, "CountryCode", metadata.country_code.as_ref()),
+    ]
+}
+
+fn read_field(xmp: &XmpMeta, kind: FieldKind, namespace: &str, name: &str) -> Option<String> {
+    let value = match kind {
+        FieldKind::Text => xmp.property(namespace, name).map(|v| v.value),
+        FieldKind::LangAlt => xmp.localized_text(namespace, name, None, "x-default").map(|(v, _)| v.value),
+        FieldKind::Seq => xmp.property_array(namespace, name).next().map(|v| v.value),
+    };
+    value.filter(|v| !v.is_empty())
+}
+
+fn write_field(xmp: &mut XmpMeta, kind: FieldKind, namespace: &str, name: &str, value: &str) -> Result<(), String> {
+    let _ = xmp.delete_property(namespace, name);
+    if value.is_empty() {
+        return Ok(());
+    }
+
+    match kind {
+        FieldKind::Text => xmp.set_property(namespace, name, &XmpValue::from(value)),
+        FieldKind::LangAlt => xmp.set_localized_text(namespace, name, None, "x-default", value),
+        FieldKind::Seq => {
+            let array = XmpValue::from(name).set_is_array(true).set_is_ordered(true);
+            xmp.append_array_item(namespace, &array, &XmpValue::from(value))
+        }
+    }
+    .map_err(|e| format!("Failed to set {}: {}", name, e))
+}
+
+fn from_xmp(xmp: &XmpMeta) -> IptcMetadata {
+    let empty = IptcMetadata::default();
+    let values: Vec<Option<String>> = fields(&empty)
+        .iter()
+        .map(|(kind, namespace, name, _)| read_field(xmp, *kind, namespace, name))
+        .collect();
+
+    let mut values = values.into_iter();
+    let mut next = || values.next().flatten();
+    IptcMetadata {
+        description: next(),
+        headline: next(),
+        title: next(),
+        creator: next(),
+        credit: next(),
+        sublocation: next(),
+        city: next(),
+        state: next(),
+        country: next(),
+        country_code: next(),
+    }
+}
+
+/// 이미지의 IPTC 항목 읽기 (XMP가 없으면 빈 값)
+pub fn read_iptc(file_path: &str) -> Result<IptcMetadata, String> {
+    let mut xmp_file = XmpFile::new().map_err(|e| format!("Failed to initialize XMP: {}", e))?;
+    if xmp_file
+        .open_file(file_path, OpenFileOptions::default().only_xmp())
+        .is_err()
+    {
+        return Ok(IptcMetadata::default());
+    }
+    Ok(xmp_file.xmp().map(|xmp| from_xmp(&xmp)).unwrap_or_default())
+}
+
+/// IPTC 쓰기 실패 항목
+#[derive(Debug, Clone, Serialize)]
+pub struct IptcFailure {
+    pub path: String,
+    pub error: String,
+}
+
+/// IPTC 항목을 여러 이미지에 쓰기
+/// 값에 파일명 템플릿 토큰({date_taken:%Y}, {sequence}, {camera}, {original_name} 등)을 쓰면 파일별로 치환
+pub fn write_iptc(paths: &[String], metadata: &IptcMetadata) -> Vec<IptcFailure> {
+    paths
+        .par_iter()
+        .enumerate()
+        .filter_map(|(index, path)| {
+            write_one(path, index + 1, metadata)
+                .err()
+                .map(|error| IptcFailure { path: path.clone(), error })
+        })
+        .collect()
+}
+
+fn write_one(path: &str, sequence: usize, metadata: &IptcMetadata) -> Result<(), String> {
+    let fields = fields(metadata);
+
+    // 템플릿 토큰이 있을 때만 촬영 정보 읽기
+    let capture_info = fields
+        .iter()
+        .any(|(_, _, _, value)| value.is_some_and(|v| v.contains('{')))
+        .then(|| read_capture_info(Path::new(path)));
+
+    let mut values = Vec::new();
+    for (kind, namespace, name, value) in fields {
+        let Some(value) = value else {
+            continue;
+        };
+        let value = match &capture_info {
+            Some((date_taken, camera)) if value.contains('{') => {
+                let ctx = TemplateContext {
+                    source: Path::new(path),
+                    date_taken: *date_taken,
+                    camera: camera.as_deref(),
+                    sequence,
+                };
+                filename_template::expand_tokens(value, &ctx)?
+            }
+            _ => value.clone(),
+        };
+        values.push((kind, namespace, name, value));
+    }
+
+    crate::rating::update_xmp(path, |xmp| {
+        for (kind, namespace, name, value) in &values {
+            write_field(xmp, *kind, namespace, name, value.trim())?;
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iptc_roundtrip() {
+        let mut xmp = XmpMeta::new().unwrap();
+        write_field(&mut xmp, FieldKind::LangAlt, xmp_ns::DC, "description", "Sunset").unwrap();
+        write_field(&mut xmp, FieldKind::Seq, xmp_ns::DC, "creator", "Jane Doe").unwrap();
+        write_field(&mut xmp, FieldKind::Text, xmp_ns::PHOTOSHOP, "Credit", "Agency").unwrap();
+
+        let metadata = from_xmp(&xmp);
+        assert_eq!(metadata.description.as_deref(), Some("Sunset"));
+        assert_eq!(metadata.creator.as_deref(), Some("Jane Doe"));
+        assert_eq!(metadata.credit.as_deref(), Some("Agency"));
+        assert_eq!(metadata.headline, None);
+    }
+}
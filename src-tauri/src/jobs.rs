@@ -0,0 +1,254 @@
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter};
+
+/// job-progress 이벤트 최소 간격 (마지막 항목은 항상 전송)
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// 목록에 남겨 두는 끝난 작업 수 (오래된 것부터 정리)
+const FINISHED_JOBS_KEPT: usize = 50;
+
+/// 작업 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    Thumbnails,
+    HqThumbnails,
+    Export,
+    Import,
+    Checksum,
+    Upload,
+    Transfer,
+    Backup,
+}
+
+/// 작업 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// 작업 정보 (list_jobs 응답, job-progress/job-finished 이벤트)
+#[derive(Debug, Clone, Serialize)]
+pub struct JobInfo {
+    pub id: u64,
+    pub kind: JobKind,
+    /// 활동 패널에 표시할 설명
+    pub label: String,
+    pub state: JobState,
+    pub completed: usize,
+    pub total: usize,
+    /// 시작/종료 시각 (unix 초)
+    pub started_at: u64,
+    pub finished_at: Option<u64>,
+    pub error: Option<String>,
+}
+
+struct JobEntry {
+    info: JobInfo,
+    cancelled: Arc<AtomicBool>,
+    /// 작업 모듈의 기존 취소 함수 (전역 취소 플래그를 쓰는 작업)
+    on_cancel: Option<fn()>,
+}
+
+lazy_static! {
+    static ref JOBS: Mutex<BTreeMap<u64, JobEntry>> = Mutex::new(BTreeMap::new());
+}
+
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(1);
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// 실행 중인 작업 (진행 보고, 취소 확인)
+/// finish 없이 drop되면 취소 여부에 따라 완료/취소로 끝냄
+pub struct Job {
+    id: u64,
+    app: AppHandle,
+    cancelled: Arc<AtomicBool>,
+    completed: AtomicUsize,
+    last_emit: Mutex<Option<Instant>>,
+    finished: AtomicBool,
+}
+
+impl Job {
+    /// 작업 등록 후 job-progress 이벤트 전송
+    pub fn start(app: &AppHandle, kind: JobKind, label: impl Into<String>, total: usize) -> Self {
+        Self::register(app, kind, label.into(), total, None)
+    }
+
+    /// 취소 시 작업 모듈의 기존 취소 함수도 호출 (HQ 생성, 업로드 등)
+    pub fn start_with_cancel(
+        app: &AppHandle,
+        kind: JobKind,
+        label: impl Into<String>,
+        total: usize,
+        on_cancel: fn(),
+    ) -> Self {
+        Self::register(app, kind, label.into(), total, Some(on_cancel))
+    }
+
+    fn register(app: &AppHandle, kind: JobKind, label: String, total: usize, on_cancel: Option<fn()>) -> Self {
+        let id = NEXT_JOB_ID.fetch_add(1, Ordering::SeqCst);
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let info = JobInfo {
+            id,
+            kind,
+            label,
+            state: JobState::Running,
+            completed: 0,
+            total,
+            started_at: now_secs(),
+            finished_at: None,
+            error: None,
+        };
+        let _ = app.emit("job-progress", &info);
+        JOBS.lock().unwrap().insert(
+            id,
+            JobEntry {
+                info,
+                cancelled: Arc::clone(&cancelled),
+                on_cancel,
+            },
+        );
+
+        Self {
+            id,
+            app: app.clone(),
+            cancelled,
+            completed: AtomicUsize::new(0),
+            last_emit: Mutex::new(None),
+            finished: AtomicBool::new(false),
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// 진행 상태 갱신 (이벤트는 PROGRESS_INTERVAL마다, 마지막 항목은 항상)
+    pub fn set_progress(&self, completed: usize, total: usize) {
+        let info = {
+            let mut jobs = JOBS.lock().unwrap();
+            let Some(entry) = jobs.get_mut(&self.id) else {
+                return;
+            };
+            entry.info.completed = completed;
+            entry.info.total = total;
+            entry.info.clone()
+        };
+        self.completed.store(completed, Ordering::SeqCst);
+
+        let mut last_emit = self.last_emit.lock().unwrap();
+        if completed >= total || last_emit.is_none_or(|t| t.elapsed() >= PROGRESS_INTERVAL) {
+            *last_emit = Some(Instant::now());
+            let _ = self.app.emit("job-progress", &info);
+        }
+    }
+
+    /// 항목 하나 완료 (병렬 작업용)
+    pub fn advance(&self, total: usize) {
+        let completed = self.completed.fetch_add(1, Ordering::SeqCst) + 1;
+        self.set_progress(completed, total);
+    }
+
+    /// 작업 모듈에서 직접 취소된 경우 (전역 취소 플래그 등) 취소로 끝냄
+    pub fn finish_cancelled(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.finish(Ok(()));
+    }
+
+    /// 작업 종료 (취소됐으면 결과와 관계없이 취소로 기록) 후 job-finished 이벤트 전송
+    pub fn finish(&self, result: Result<(), String>) {
+        if self.finished.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        let info = {
+            let mut jobs = JOBS.lock().unwrap();
+            let Some(entry) = jobs.get_mut(&self.id) else {
+                return;
+            };
+            entry.info.state = match (&result, self.is_cancelled()) {
+                (_, true) => JobState::Cancelled,
+                (Ok(()), false) => JobState::Completed,
+                (Err(_), false) => JobState::Failed,
+            };
+            entry.info.error = result.err();
+            entry.info.finished_at = Some(now_secs());
+            let info = entry.info.clone();
+            prune_finished(&mut jobs);
+            info
+        };
+        let _ = self.app.emit("job-finished", &info);
+    }
+
+    /// 작업 결과를 기록하고 그대로 반환
+    pub fn finish_with<T>(&self, result: Result<T, String>) -> Result<T, String> {
+        self.finish(result.as_ref().map(|_| ()).map_err(Clone::clone));
+        result
+    }
+}
+
+impl Drop for Job {
+    fn drop(&mut self) {
+        self.finish(Ok(()));
+    }
+}
+
+// 끝난 작업은 최근 FINISHED_JOBS_KEPT개만 유지 (id 순 = 시작 순)
+fn prune_finished(jobs: &mut BTreeMap<u64, JobEntry>) {
+    let finished: Vec<u64> = jobs
+        .iter()
+        .filter(|(_, entry)| entry.info.state != JobState::Running)
+        .map(|(id, _)| *id)
+        .collect();
+    let excess = finished.len().saturating_sub(FINISHED_JOBS_KEPT);
+    for id in finished.into_iter().take(excess) {
+        jobs.remove(&id);
+    }
+}
+
+/// 작업 목록 (실행 중인 작업과 최근 끝난 작업, 시작 순)
+pub fn list() -> Vec<JobInfo> {
+    JOBS.lock().unwrap().values().map(|entry| entry.info.clone()).collect()
+}
+
+/// 실행 중인 같은 종류의 작업 모두 취소 요청 (작업 ID를 모르는 기존 취소 커맨드용)
+pub fn cancel_kind(kind: JobKind) {
+    let jobs = JOBS.lock().unwrap();
+    for entry in jobs.values() {
+        if entry.info.kind == kind && entry.info.state == JobState::Running {
+            entry.cancelled.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+/// 작업 취소 요청 (작업은 진행 중인 항목을 마치고 멈춤)
+pub fn cancel(id: u64) -> Result<(), String> {
+    let on_cancel = {
+        let jobs = JOBS.lock().unwrap();
+        let entry = jobs.get(&id).ok_or_else(|| format!("{}: job {}", crate::error::NOT_FOUND, id))?;
+        if entry.info.state != JobState::Running {
+            return Err(format!("Job is not running: {}", id));
+        }
+        entry.cancelled.store(true, Ordering::SeqCst);
+        entry.on_cancel
+    };
+    if let Some(on_cancel) = on_cancel {
+        on_cancel();
+    }
+    Ok(())
+}
@@ -0,0 +1,751 @@
+/// 8x8 블록의 DCT 계수 (지그재그 순서)
+type Block = [i16; 64];
+
+const STD_LUMA_DC_BITS: [u8; 16] = [
+    0x00, 0x01, 0x05, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+const STD_LUMA_DC_VALUES: [u8; 12] = [
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B,
+];
+const STD_CHROMA_DC_BITS: [u8; 16] = [
+    0x00, 0x03, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+const STD_CHROMA_DC_VALUES: [u8; 12] = [
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B,
+];
+const STD_LUMA_AC_BITS: [u8; 16] = [
+    0x00, 0x02, 0x01, 0x03, 0x03, 0x02, 0x04, 0x03, 0x05, 0x05, 0x04, 0x04, 0x00, 0x00, 0x01, 0x7D,
+];
+const STD_LUMA_AC_VALUES: [u8; 162] = [
+    0x01, 0x02, 0x03, 0x00, 0x04, 0x11, 0x05, 0x12, 0x21, 0x31, 0x41, 0x06, 0x13, 0x51, 0x61, 0x07,
+    0x22, 0x71, 0x14, 0x32, 0x81, 0x91, 0xA1, 0x08, 0x23, 0x42, 0xB1, 0xC1, 0x15, 0x52, 0xD1, 0xF0,
+    0x24, 0x33, 0x62, 0x72, 0x82, 0x09, 0x0A, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x25, 0x26, 0x27, 0x28,
+    0x29, 0x2A, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3A, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49,
+    0x4A, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58, 0x59, 0x5A, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69,
+    0x6A, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7A, 0x83, 0x84, 0x85, 0x86, 0x87, 0x88, 0x89,
+    0x8A, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9A, 0xA2, 0xA3, 0xA4, 0xA5, 0xA6, 0xA7,
+    0xA8, 0xA9, 0xAA, 0xB2, 0xB3, 0xB4, 0xB5, 0xB6, 0xB7, 0xB8, 0xB9, 0xBA, 0xC2, 0xC3, 0xC4, 0xC5,
+    0xC6, 0xC7, 0xC8, 0xC9, 0xCA, 0xD2, 0xD3, 0xD4, 0xD5, 0xD6, 0xD7, 0xD8, 0xD9, 0xDA, 0xE1, 0xE2,
+    0xE3, 0xE4, 0xE5, 0xE6, 0xE7, 0xE8, 0xE9, 0xEA, 0xF1, 0xF2, 0xF3, 0xF4, 0xF5, 0xF6, 0xF7, 0xF8,
+    0xF9, 0xFA,
+];
+const STD_CHROMA_AC_BITS: [u8; 16] = [
+    0x00, 0x02, 0x01, 0x02, 0x04, 0x04, 0x03, 0x04, 0x07, 0x05, 0x04, 0x04, 0x00, 0x01, 0x02, 0x77,
+];
+const STD_CHROMA_AC_VALUES: [u8; 162] = [
+    0x00, 0x01, 0x02, 0x03, 0x11, 0x04, 0x05, 0x21, 0x31, 0x06, 0x12, 0x41, 0x51, 0x07, 0x61, 0x71,
+    0x13, 0x22, 0x32, 0x81, 0x08, 0x14, 0x42, 0x91, 0xA1, 0xB1, 0xC1, 0x09, 0x23, 0x33, 0x52, 0xF0,
+    0x15, 0x62, 0x72, 0xD1, 0x0A, 0x16, 0x24, 0x34, 0xE1, 0x25, 0xF1, 0x17, 0x18, 0x19, 0x1A, 0x26,
+    0x27, 0x28, 0x29, 0x2A, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3A, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48,
+    0x49, 0x4A, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58, 0x59, 0x5A, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68,
+    0x69, 0x6A, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7A, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87,
+    0x88, 0x89, 0x8A, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9A, 0xA2, 0xA3, 0xA4, 0xA5,
+    0xA6, 0xA7, 0xA8, 0xA9, 0xAA, 0xB2, 0xB3, 0xB4, 0xB5, 0xB6, 0xB7, 0xB8, 0xB9, 0xBA, 0xC2, 0xC3,
+    0xC4, 0xC5, 0xC6, 0xC7, 0xC8, 0xC9, 0xCA, 0xD2, 0xD3, 0xD4, 0xD5, 0xD6, 0xD7, 0xD8, 0xD9, 0xDA,
+    0xE2, 0xE3, 0xE4, 0xE5, 0xE6, 0xE7, 0xE8, 0xE9, 0xEA, 0xF2, 0xF3, 0xF4, 0xF5, 0xF6, 0xF7, 0xF8,
+    0xF9, 0xFA,
+];
+
+/// 인코딩에 쓰는 DC/AC 허프만 표 (DHT의 BITS, HUFFVAL)
+struct HuffmanSpec<'a> {
+    dc_bits: &'a [u8],
+    dc_values: &'a [u8],
+    ac_bits: &'a [u8],
+    ac_values: &'a [u8],
+}
+
+const STD_LUMA: HuffmanSpec<'static> = HuffmanSpec {
+    dc_bits: &STD_LUMA_DC_BITS,
+    dc_values: &STD_LUMA_DC_VALUES,
+    ac_bits: &STD_LUMA_AC_BITS,
+    ac_values: &STD_LUMA_AC_VALUES,
+};
+
+const STD_CHROMA: HuffmanSpec<'static> = HuffmanSpec {
+    dc_bits: &STD_CHROMA_DC_BITS,
+    dc_values: &STD_CHROMA_DC_VALUES,
+    ac_bits: &STD_CHROMA_AC_BITS,
+    ac_values: &STD_CHROMA_AC_VALUES,
+};
+
+/// 디코딩용 허프만 표 (JPEG 규격 F.2.2.3의 MAXCODE/VALPTR/MINCODE)
+struct HuffmanDecoder {
+    maxcode: [i32; 17],
+    valptr: [i32; 17],
+    mincode: [i32; 17],
+    values: Vec<u8>,
+}
+
+impl HuffmanDecoder {
+    fn new(bits: &[u8], values: &[u8]) -> Self {
+        let mut table = Self {
+            maxcode: [-1; 17],
+            valptr: [0; 17],
+            mincode: [0; 17],
+            values: values.to_vec(),
+        };
+        let mut code = 0i32;
+        let mut index = 0i32;
+        for length in 1..=16 {
+            let count = bits[length - 1] as i32;
+            if count > 0 {
+                table.valptr[length] = index;
+                table.mincode[length] = code;
+                code += count;
+                index += count;
+                table.maxcode[length] = code - 1;
+            }
+            code <<= 1;
+        }
+        table
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Result<u8, String> {
+        let mut code = reader.bit()?;
+        for length in 1..=16 {
+            if code <= self.maxcode[length] {
+                let index = self.valptr[length] + code - self.mincode[length];
+                return self
+                    .values
+                    .get(index as usize)
+                    .copied()
+                    .ok_or_else(|| "Invalid Huffman code".to_string());
+            }
+            code = (code << 1) | reader.bit()?;
+        }
+        Err("Invalid Huffman code".to_string())
+    }
+}
+
+/// 인코딩용 허프만 표 (기호 -> (코드, 길이))
+fn huffman_codes(bits: &[u8], values: &[u8]) -> [(u16, u8); 256] {
+    let mut codes = [(0u16, 0u8); 256];
+    let mut code = 0u16;
+    let mut index = 0;
+    for length in 1..=16u8 {
+        for _ in 0..bits[length as usize - 1] {
+            codes[values[index] as usize] = (code, length);
+            code += 1;
+            index += 1;
+        }
+        code <<= 1;
+    }
+    codes
+}
+
+/// 압축 데이터 비트 읽기 (0xFF 뒤의 채움 바이트 0x00 제거)
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    byte: u8,
+    remaining: u8,
+}
+
+impl<'a> BitReader<'a> {
+    fn bit(&mut self) -> Result<i32, String> {
+        if self.remaining == 0 {
+            let byte = *self.data.get(self.pos).ok_or("Unexpected end of JPEG data")?;
+            if byte == 0xFF {
+                match self.data.get(self.pos + 1) {
+                    Some(0x00) => self.pos += 1,
+                    _ => return Err("Unexpected marker in JPEG data".to_string()),
+                }
+            }
+            self.pos += 1;
+            self.byte = byte;
+            self.remaining = 8;
+        }
+        self.remaining -= 1;
+        Ok(((self.byte >> self.remaining) & 1) as i32)
+    }
+
+    fn receive_extend(&mut self, size: u8) -> Result<i16, String> {
+        if size == 0 {
+            return Ok(0);
+        }
+        let mut value = 0i32;
+        for _ in 0..size {
+            value = (value << 1) | self.bit()?;
+        }
+        if value < 1 << (size - 1) {
+            value += 1 - (1 << size);
+        }
+        Ok(value as i16)
+    }
+
+    /// 재시작 마커(RSTn) 건너뛰기 (남은 채움 비트는 버림)
+    fn restart(&mut self) -> Result<(), String> {
+        self.remaining = 0;
+        while self.data.get(self.pos) == Some(&0xFF) && self.data.get(self.pos + 1) == Some(&0xFF) {
+            self.pos += 1;
+        }
+        match self.data.get(self.pos..self.pos + 2) {
+            Some([0xFF, 0xD0..=0xD7]) => {
+                self.pos += 2;
+                Ok(())
+            }
+            _ => Err("Missing JPEG restart marker".to_string()),
+        }
+    }
+}
+
+/// 압축 데이터 비트 쓰기 (0xFF 뒤에 0x00 채움)
+struct BitWriter {
+    out: Vec<u8>,
+    acc: u32,
+    count: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, bits: u16, length: u8) {
+        for i in (0..length).rev() {
+            self.acc = (self.acc << 1) | ((bits >> i) & 1) as u32;
+            self.count += 1;
+            if self.count == 8 {
+                let byte = self.acc as u8;
+                self.out.push(byte);
+                if byte == 0xFF {
+                    self.out.push(0x00);
+                }
+                self.acc = 0;
+                self.count = 0;
+            }
+        }
+    }
+
+    /// 마지막 바이트를 1로 채움
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            let padding = 8 - self.count;
+            self.write((1 << padding) - 1, padding);
+        }
+        self.out
+    }
+}
+
+/// 값을 표현하는 비트 수 (허프만 기호의 크기 범주)
+fn magnitude_size(value: i32) -> u8 {
+    (32 - value.unsigned_abs().leading_zeros()) as u8
+}
+
+fn write_value(writer: &mut BitWriter, value: i32, size: u8) {
+    let bits = if value < 0 { value - 1 } else { value };
+    writer.write((bits & ((1 << size) - 1)) as u16, size);
+}
+
+struct Component {
+    id: u8,
+    /// SOF의 샘플링 바이트 (h << 4 | v)
+    sampling: u8,
+    h: usize,
+    v: usize,
+    quant_table: u8,
+    dc_table: usize,
+    ac_table: usize,
+    blocks_w: usize,
+    blocks: Vec<Block>,
+}
+
+/// 세그먼트 길이 읽기 (마커 위치 기준)
+fn segment_end(data: &[u8], pos: usize) -> Result<usize, String> {
+    let length = data
+        .get(pos + 2..pos + 4)
+        .map(|b| u16::from_be_bytes([b[0], b[1]]) as usize)
+        .ok_or("Invalid JPEG structure")?;
+    let end = pos + 2 + length;
+    if length < 2 || end > data.len() {
+        return Err("Invalid JPEG structure".to_string());
+    }
+    Ok(end)
+}
+
+/// 프레임 헤더(SOF0/SOF1) 읽기 (너비, 높이, 성분)
+/// 회색조(1성분)와 YCbCr(3성분)만 허용 (다시 쓸 때 첫 성분은 밝기 표, 나머지는 색차 표를 씀)
+fn parse_frame(payload: &[u8]) -> Result<(usize, usize, Vec<Component>), String> {
+    if payload.len() < 6 || payload[0] != 8 {
+        return Err("Only 8-bit JPEG can be cropped losslessly".to_string());
+    }
+    let height = u16::from_be_bytes([payload[1], payload[2]]) as usize;
+    let width = u16::from_be_bytes([payload[3], payload[4]]) as usize;
+    let count = payload[5] as usize;
+    if width == 0 || height == 0 || count == 0 || count > 4 || payload.len() < 6 + count * 3 {
+        return Err("Invalid JPEG frame header".to_string());
+    }
+    if count != 1 && count != 3 {
+        return Err("Only grayscale and YCbCr JPEG can be cropped losslessly".to_string());
+    }
+    let components = payload[6..6 + count * 3]
+        .chunks_exact(3)
+        .map(|c| Component {
+            id: c[0],
+            sampling: c[1],
+            h: (c[1] >> 4).clamp(1, 4) as usize,
+            v: (c[1] & 0x0F).clamp(1, 4) as usize,
+            quant_table: c[2],
+            dc_table: 0,
+            ac_table: 0,
+            blocks_w: 0,
+            blocks: Vec::new(),
+        })
+        .collect();
+    Ok((width, height, components))
+}
+
+// 스캔 헤더의 성분 수 확인 (성분마다 스캔이 따로 있으면 자를 수 없음)
+fn check_scan(payload: &[u8], components: usize) -> Result<usize, String> {
+    let count = *payload.first().ok_or("Invalid JPEG scan header")? as usize;
+    if count != components {
+        return Err("Multi-scan JPEG cannot be cropped losslessly".to_string());
+    }
+    Ok(count)
+}
+
+/// MCU 크기 (px, 단일 성분은 샘플링과 관계없이 블록 하나가 MCU)
+fn mcu_dimensions(components: &[Component]) -> (usize, usize) {
+    if components.len() == 1 {
+        return (8, 8);
+    }
+    let h_max = components.iter().map(|c| c.h).max().unwrap_or(1);
+    let v_max = components.iter().map(|c| c.v).max().unwrap_or(1);
+    (8 * h_max, 8 * v_max)
+}
+
+/// 자를 수 있는 JPEG 정보와 DCT 계수
+struct Decoded {
+    sof_marker: u8,
+    width: usize,
+    height: usize,
+    /// MCU 크기 (px)
+    mcu_width: usize,
+    mcu_height: usize,
+    components: Vec<Component>,
+    /// 그대로 복사할 세그먼트 (APPn, DQT, COM 등)
+    segments: Vec<u8>,
+}
+
+fn decode(data: &[u8]) -> Result<Decoded, String> {
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        return Err("Not a JPEG file".to_string());
+    }
+
+    let mut dc_tables: [Option<HuffmanDecoder>; 4] = Default::default();
+    let mut ac_tables: [Option<HuffmanDecoder>; 4] = Default::default();
+    let mut restart_interval = 0usize;
+    let mut frame: Option<(u8, usize, usize, Vec<Component>)> = None;
+    let mut segments = Vec::new();
+
+    let mut pos = 2;
+    loop {
+        if data.get(pos) != Some(&0xFF) {
+            return Err("Invalid JPEG structure".to_string());
+        }
+        let marker = *data.get(pos + 1).ok_or("Invalid JPEG structure")?;
+        match marker {
+            0xFF => {
+                pos += 1;
+                continue;
+            }
+            0x01 | 0xD0..=0xD7 => {
+                pos += 2;
+                continue;
+            }
+            0xD9 => return Err("No image data in JPEG".to_string()),
+            _ => {}
+        }
+
+        let end = segment_end(data, pos)?;
+        let payload = &data[pos + 4..end];
+        match marker {
+            0xC0 | 0xC1 => {
+                let (width, height, components) = parse_frame(payload)?;
+                frame = Some((marker, width, height, components));
+            }
+            0xC2..=0xCF if marker != 0xC4 && marker != 0xC8 && marker != 0xCC => {
+                return Err("Only baseline JPEG can be cropped losslessly".to_string());
+            }
+            0xC4 => {
+                let mut table = payload;
+                while table.len() >= 17 {
+                    let (class, id) = (table[0] >> 4, (table[0] & 0x0F) as usize);
+                    let bits = &table[1..17];
+                    let count: usize = bits.iter().map(|&b| b as usize).sum();
+                    let values = table.get(17..17 + count).ok_or("Invalid JPEG Huffman table")?;
+                    if id > 3 || class > 1 {
+                        return Err("Invalid JPEG Huffman table".to_string());
+                    }
+                    let decoder = Some(HuffmanDecoder::new(bits, values));
+                    if class == 0 {
+                        dc_tables[id] = decoder;
+                    } else {
+                        ac_tables[id] = decoder;
+                    }
+                    table = &table[17 + count..];
+                }
+            }
+            0xDD => {
+                let interval = payload.get(..2).ok_or("Invalid JPEG restart interval")?;
+                restart_interval = u16::from_be_bytes([interval[0], interval[1]]) as usize;
+            }
+            0xDA => {
+                let (sof_marker, width, height, mut components) = frame.ok_or("JPEG scan before frame header")?;
+                let count = check_scan(payload, components.len())?;
+                for selector in payload.get(1..1 + count * 2).ok_or("Invalid JPEG scan header")?.chunks_exact(2) {
+                    let component = components
+                        .iter_mut()
+                        .find(|c| c.id == selector[0])
+                        .ok_or("Unknown JPEG scan component")?;
+                    component.dc_table = (selector[1] >> 4) as usize & 3;
+                    component.ac_table = (selector[1] & 0x0F) as usize & 3;
+                }
+
+                // 단일 성분은 샘플링과 관계없이 블록 하나가 MCU
+                if components.len() == 1 {
+                    components[0].h = 1;
+                    components[0].v = 1;
+                }
+                let (mcu_width, mcu_height) = mcu_dimensions(&components);
+                let mcus_x = width.div_ceil(mcu_width);
+                let mcus_y = height.div_ceil(mcu_height);
+                for component in &mut components {
+                    component.blocks_w = mcus_x * component.h;
+                    component.blocks = vec![[0; 64]; component.blocks_w * mcus_y * component.v];
+                }
+
+                let mut reader = BitReader { data, pos: end, byte: 0, remaining: 0 };
+                let mut predictions = vec![0i16; components.len()];
+                for mcu in 0..mcus_x * mcus_y {
+                    if restart_interval > 0 && mcu > 0 && mcu % restart_interval == 0 {
+                        reader.restart()?;
+                        predictions.fill(0);
+                    }
+                    let (mx, my) = (mcu % mcus_x, mcu / mcus_x);
+                    for (component, prediction) in components.iter_mut().zip(predictions.iter_mut()) {
+                        let dc = dc_tables[component.dc_table].as_ref().ok_or("Missing JPEG Huffman table")?;
+                        let ac = ac_tables[component.ac_table].as_ref().ok_or("Missing JPEG Huffman table")?;
+                        for by in 0..component.v {
+                            for bx in 0..component.h {
+                                let index = (my * component.v + by) * component.blocks_w + mx * component.h + bx;
+                                let block = &mut component.blocks[index];
+
+                                let size = dc.decode(&mut reader)?;
+                                *prediction = prediction.wrapping_add(reader.receive_extend(size)?);
+                                block[0] = *prediction;
+
+                                let mut k = 1;
+                                while k < 64 {
+                                    let symbol = ac.decode(&mut reader)?;
+                                    let (run, size) = ((symbol >> 4) as usize, symbol & 0x0F);
+                                    if size == 0 {
+                                        if run != 15 {
+                                            break;
+                                        }
+                                        k += 16;
+                                        continue;
+                                    }
+                                    k += run;
+                                    if k > 63 {
+                                        return Err("Invalid JPEG coefficient data".to_string());
+                                    }
+                                    block[k] = reader.receive_extend(size)?;
+                                    k += 1;
+                                }
+                            }
+                        }
+                    }
+                }
+
+                return Ok(Decoded {
+                    sof_marker,
+                    width,
+                    height,
+                    mcu_width,
+                    mcu_height,
+                    components,
+                    segments,
+                });
+            }
+            _ => segments.extend_from_slice(&data[pos..end]),
+        }
+        pos = end;
+    }
+}
+
+/// MCU 크기 (px), 무손실로 자를 수 없는 JPEG이면 오류
+/// 첫 스캔 헤더까지만 읽음 (압축 데이터는 해독하지 않음)
+pub fn mcu_size(data: &[u8]) -> Result<(u32, u32), String> {
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        return Err("Not a JPEG file".to_string());
+    }
+
+    let mut components: Option<Vec<Component>> = None;
+    let mut pos = 2;
+    loop {
+        if data.get(pos) != Some(&0xFF) {
+            return Err("Invalid JPEG structure".to_string());
+        }
+        let marker = *data.get(pos + 1).ok_or("Invalid JPEG structure")?;
+        match marker {
+            0xFF => {
+                pos += 1;
+                continue;
+            }
+            0x01 | 0xD0..=0xD7 => {
+                pos += 2;
+                continue;
+            }
+            0xD9 => return Err("No image data in JPEG".to_string()),
+            _ => {}
+        }
+
+        let end = segment_end(data, pos)?;
+        let payload = &data[pos + 4..end];
+        match marker {
+            0xC0 | 0xC1 => components = Some(parse_frame(payload)?.2),
+            0xC2..=0xCF if marker != 0xC4 && marker != 0xC8 && marker != 0xCC => {
+                return Err("Only baseline JPEG can be cropped losslessly".to_string());
+            }
+            0xDA => {
+                let components = components.ok_or("JPEG scan before frame header")?;
+                check_scan(payload, components.len())?;
+                let (width, height) = mcu_dimensions(&components);
+                return Ok((width as u32, height as u32));
+            }
+            _ => {}
+        }
+        pos = end;
+    }
+}
+
+/// JPEG 무손실 자르기 (DCT 계수를 그대로 옮겨 다시 씀, jpegtran -crop과 같은 방식)
+/// 기준선(baseline) 순차 허프만 JPEG만 지원, x/y는 MCU 경계여야 함 (크기는 자유)
+/// 잘린 데이터는 표준 허프만 표로 다시 인코딩하고 재시작 마커는 쓰지 않음
+pub fn crop(data: &[u8], x: u32, y: u32, width: u32, height: u32) -> Result<Vec<u8>, String> {
+    let decoded = decode(data)?;
+    let (x, y, width, height) = (x as usize, y as usize, width as usize, height as usize);
+    if x % decoded.mcu_width != 0 || y % decoded.mcu_height != 0 {
+        return Err(format!(
+            "Crop origin is not aligned to {}x{} MCU",
+            decoded.mcu_width, decoded.mcu_height
+        ));
+    }
+    if width == 0 || height == 0 || x + width > decoded.width || y + height > decoded.height {
+        return Err("Crop rectangle is outside of the image".to_string());
+    }
+
+    write_cropped(&decoded, x, y, width, height, [&STD_LUMA, &STD_CHROMA])
+}
+
+/// 자른 영역의 계수를 주어진 허프만 표(밝기, 색차)로 다시 써서 JPEG 만들기
+/// 원본의 허프만 표는 쓰지 않음 (최적화된 표에는 자른 뒤 달라지는 DC 차이 값의 부호가 없을 수 있음)
+fn write_cropped(
+    decoded: &Decoded,
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+    [luma_spec, chroma_spec]: [&HuffmanSpec; 2],
+) -> Result<Vec<u8>, String> {
+    let mut out = vec![0xFF, 0xD8];
+    out.extend_from_slice(&decoded.segments);
+
+    // 프레임 헤더 (새 크기)
+    let count = decoded.components.len();
+    out.extend_from_slice(&[0xFF, decoded.sof_marker]);
+    out.extend_from_slice(&((8 + count * 3) as u16).to_be_bytes());
+    out.push(8);
+    out.extend_from_slice(&(height as u16).to_be_bytes());
+    out.extend_from_slice(&(width as u16).to_be_bytes());
+    out.push(count as u8);
+    for component in &decoded.components {
+        out.extend_from_slice(&[component.id, component.sampling, component.quant_table]);
+    }
+
+    // 허프만 표 (0: 밝기, 1: 색차)
+    let mut tables: Vec<(u8, &[u8], &[u8])> = vec![
+        (0x00, luma_spec.dc_bits, luma_spec.dc_values),
+        (0x10, luma_spec.ac_bits, luma_spec.ac_values),
+    ];
+    if count > 1 {
+        tables.push((0x01, chroma_spec.dc_bits, chroma_spec.dc_values));
+        tables.push((0x11, chroma_spec.ac_bits, chroma_spec.ac_values));
+    }
+    let length: usize = 2 + tables.iter().map(|(_, bits, values)| 1 + bits.len() + values.len()).sum::<usize>();
+    out.extend_from_slice(&[0xFF, 0xC4]);
+    out.extend_from_slice(&(length as u16).to_be_bytes());
+    for (class_id, bits, values) in &tables {
+        out.push(*class_id);
+        out.extend_from_slice(bits);
+        out.extend_from_slice(values);
+    }
+
+    // 스캔 헤더
+    out.extend_from_slice(&[0xFF, 0xDA]);
+    out.extend_from_slice(&((6 + count * 2) as u16).to_be_bytes());
+    out.push(count as u8);
+    for (index, component) in decoded.components.iter().enumerate() {
+        out.extend_from_slice(&[component.id, if index == 0 { 0x00 } else { 0x11 }]);
+    }
+    out.extend_from_slice(&[0, 63, 0]);
+
+    let luma = (
+        huffman_codes(luma_spec.dc_bits, luma_spec.dc_values),
+        huffman_codes(luma_spec.ac_bits, luma_spec.ac_values),
+    );
+    let chroma = (
+        huffman_codes(chroma_spec.dc_bits, chroma_spec.dc_values),
+        huffman_codes(chroma_spec.ac_bits, chroma_spec.ac_values),
+    );
+
+    let mcus_x = width.div_ceil(decoded.mcu_width);
+    let mcus_y = height.div_ceil(decoded.mcu_height);
+    let (mx0, my0) = (x / decoded.mcu_width, y / decoded.mcu_height);
+    let mut writer = BitWriter { out, acc: 0, count: 0 };
+    let mut predictions = vec![0i32; count];
+
+    for my in 0..mcus_y {
+        for mx in 0..mcus_x {
+            for (index, component) in decoded.components.iter().enumerate() {
+                let (dc_codes, ac_codes) = if index == 0 { &luma } else { &chroma };
+                for by in 0..component.v {
+                    for bx in 0..component.h {
+                        let source = ((my0 + my) * component.v + by) * component.blocks_w + (mx0 + mx) * component.h + bx;
+                        let block = &component.blocks[source];
+
+                        let diff = block[0] as i32 - predictions[index];
+                        predictions[index] = block[0] as i32;
+                        let size = magnitude_size(diff);
+                        if size > 11 {
+                            return Err("DC coefficient out of range".to_string());
+                        }
+                        let (code, length) = dc_codes[size as usize];
+                        writer.write(code, length);
+                        write_value(&mut writer, diff, size);
+
+                        let mut run = 0;
+                        for &coefficient in &block[1..] {
+                            if coefficient == 0 {
+                                run += 1;
+                                continue;
+                            }
+                            while run > 15 {
+                                let (code, length) = ac_codes[0xF0];
+                                writer.write(code, length);
+                                run -= 16;
+                            }
+                            let size = magnitude_size(coefficient as i32);
+                            if size > 10 {
+                                return Err("AC coefficient out of range".to_string());
+                            }
+                            let (code, length) = ac_codes[(run << 4) | size as usize];
+                            writer.write(code, length);
+                            write_value(&mut writer, coefficient as i32, size);
+                            run = 0;
+                        }
+                        if run > 0 {
+                            let (code, length) = ac_codes[0x00];
+                            writer.write(code, length);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let mut out = writer.finish();
+    out.extend_from_slice(&[0xFF, 0xD9]);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_pixels(data: &[u8]) -> (Vec<u8>, usize, usize) {
+        let mut decoder = jpeg_decoder::Decoder::new(data);
+        let pixels = decoder.decode().unwrap();
+        let info = decoder.info().unwrap();
+        (pixels, info.width as usize, info.height as usize)
+    }
+
+    #[test]
+    fn test_lossless_crop_matches_source() {
+        let (width, height) = (64usize, 48usize);
+        let gray: Vec<u8> = (0..width * height).map(|i| ((i % width) * 3 + (i / width) * 5) as u8).collect();
+        let mut jpeg = Vec::new();
+        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg, 85)
+            .encode(&gray, width as u32, height as u32, image::ExtendedColorType::L8)
+            .unwrap();
+
+        assert_eq!(mcu_size(&jpeg), Ok((8, 8)));
+        assert!(crop(&jpeg, 4, 8, 16, 16).is_err());
+
+        let cropped = crop(&jpeg, 16, 8, 27, 30).unwrap();
+        let (source, _, _) = decode_pixels(&jpeg);
+        let (pixels, crop_width, crop_height) = decode_pixels(&cropped);
+        assert_eq!((crop_width, crop_height), (27, 30));
+        for y in 0..crop_height {
+            for x in 0..crop_width {
+                assert_eq!(pixels[y * crop_width + x], source[(y + 8) * width + x + 16]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mcu_size_from_headers() {
+        // SOF0 (Y 2x1, Cb/Cr 1x1) + SOS, 압축 데이터 없음
+        let sof = [0xFF, 0xC0, 0, 17, 8, 0, 16, 0, 32, 3, 1, 0x21, 0, 2, 0x11, 1, 3, 0x11, 1];
+        let sos = [0xFF, 0xDA, 0, 12, 3, 1, 0x00, 2, 0x11, 3, 0x11, 0, 63, 0];
+        let mut jpeg = vec![0xFF, 0xD8];
+        jpeg.extend_from_slice(&sof);
+        jpeg.extend_from_slice(&sos);
+        assert_eq!(mcu_size(&jpeg), Ok((16, 8)));
+
+        // 프로그레시브는 자를 수 없음
+        jpeg[3] = 0xC2;
+        assert!(mcu_size(&jpeg).is_err());
+    }
+
+    #[test]
+    fn test_lossless_crop_non_standard_tables() {
+        let (width, height) = (64usize, 48usize);
+        let rgb: Vec<u8> = (0..width * height)
+            .flat_map(|i| {
+                let (x, y) = (i % width, i / width);
+                [(x * 4) as u8, (y * 5) as u8, ((x + y) * 2) as u8]
+            })
+            .collect();
+        let mut jpeg = Vec::new();
+        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg, 90)
+            .encode(&rgb, width as u32, height as u32, image::ExtendedColorType::Rgb8)
+            .unwrap();
+
+        // 표준 표와 다른 허프만 표로 다시 쓴 원본 (DC는 모두 4비트, AC는 모두 8비트 부호)
+        let mut dc_bits = [0u8; 16];
+        dc_bits[3] = 12;
+        let mut ac_bits = [0u8; 16];
+        ac_bits[7] = 162;
+        let custom = HuffmanSpec {
+            dc_bits: &dc_bits,
+            dc_values: &STD_LUMA_DC_VALUES,
+            ac_bits: &ac_bits,
+            ac_values: &STD_CHROMA_AC_VALUES,
+        };
+        let standard = decode(&jpeg).unwrap();
+        let fixture = write_cropped(&standard, 0, 0, width, height, [&custom, &custom]).unwrap();
+        assert_eq!(decode_pixels(&fixture).0, decode_pixels(&jpeg).0);
+
+        let source = decode(&fixture).unwrap();
+        let (x, y) = (source.mcu_width, source.mcu_height);
+        let cropped = decode(&crop(&fixture, x as u32, y as u32, 30, 20).unwrap()).unwrap();
+        assert_eq!((cropped.width, cropped.height), (30, 20));
+        for (component, original) in cropped.components.iter().zip(&source.components) {
+            // 한 MCU 건너서 자름 (성분마다 MCU 하나가 h x v 블록)
+            let (offset_x, offset_y) = (original.h, original.v);
+            for (index, block) in component.blocks.iter().enumerate() {
+                let (bx, by) = (index % component.blocks_w, index / component.blocks_w);
+                assert_eq!(block, &original.blocks[(by + offset_y) * original.blocks_w + bx + offset_x]);
+            }
+        }
+    }
+
+}
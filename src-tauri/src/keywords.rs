@@ -0,0 +1,237 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+use xmp_toolkit::{xmp_ns, OpenFileOptions, XmpFile, XmpMeta, XmpValue};
+
+const XMP_NS_LIGHTROOM: &str = "http://ns.adobe.com/lightroom/1.0/";
+const XMP_NS_DARKTABLE: &str = "http://darktable.sf.net/";
+const XMP_NS_DIGIKAM: &str = "http://www.digikam.org/ns/1.0/";
+
+/// 계층 키워드 구분자 (Lightroom hierarchicalSubject 방식, 예: "장소|서울")
+const HIERARCHY_SEPARATOR: char = '|';
+
+/// digiKam TagsList 계층 구분자
+const DIGIKAM_SEPARATOR: char = '/';
+
+/// darktable 색상 라벨 번호 → 이름
+const DARKTABLE_COLORS: &[&str] = &["Red", "Yellow", "Green", "Blue", "Purple"];
+
+static REGISTER_NAMESPACES: Once = Once::new();
+
+/// 키워드를 쓰는 방식 (다른 프로그램과 호환)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeywordDialect {
+    /// Adobe 방식: 이미지 XMP에 dc:subject + lr:hierarchicalSubject (RAW는 이름.xmp 사이드카)
+    #[default]
+    Standard,
+    /// darktable 방식: 이름.확장자.xmp 사이드카에 dc:subject + lr:hierarchicalSubject
+    Darktable,
+    /// digiKam 방식: 이름.확장자.xmp 사이드카에 digiKam:TagsList("/" 계층) 추가
+    Digikam,
+}
+
+/// 사이드카 XMP에서 읽은 값 (darktable 편집 기록 등은 무시)
+#[derive(Debug, Clone, Serialize)]
+pub struct SidecarMetadata {
+    pub path: String,
+    pub rating: Option<i32>,
+    /// darktable 별점 -1 (제외)
+    pub rejected: bool,
+    pub color_labels: Vec<String>,
+    /// 계층은 "|"로 구분
+    pub keywords: Vec<String>,
+}
+
+fn register_namespaces() {
+    REGISTER_NAMESPACES.call_once(|| {
+        for (uri, prefix) in [
+            (XMP_NS_LIGHTROOM, "lr"),
+            (XMP_NS_DARKTABLE, "darktable"),
+            (XMP_NS_DIGIKAM, "digiKam"),
+        ] {
+            if let Err(e) = XmpMeta::register_namespace(uri, prefix) {
+                tracing::warn!("Failed to register XMP namespace {}: {}", uri, e);
+            }
+        }
+    });
+}
+
+/// 사이드카 경로: darktable/digiKam은 이름.확장자.xmp, Adobe는 이름.xmp
+fn sidecar_path(image: &Path, dialect: KeywordDialect) -> PathBuf {
+    match dialect {
+        KeywordDialect::Standard => image.with_extension("xmp"),
+        KeywordDialect::Darktable | KeywordDialect::Digikam => {
+            let mut name = image.as_os_str().to_owned();
+            name.push(".xmp");
+            PathBuf::from(name)
+        }
+    }
+}
+
+/// 있는 사이드카 찾기 (이름.확장자.xmp 우선)
+fn find_sidecar(image: &Path) -> Option<PathBuf> {
+    [sidecar_path(image, KeywordDialect::Darktable), sidecar_path(image, KeywordDialect::Standard)]
+        .into_iter()
+        .find(|path| path.is_file())
+}
+
+fn read_xmp_file(path: &Path) -> Result<XmpMeta, String> {
+    register_namespaces();
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read sidecar: {}", e))?;
+    content.parse().map_err(|e| format!("Failed to parse sidecar {}: {}", path.display(), e))
+}
+
+fn array_values(xmp: &XmpMeta, namespace: &str, name: &str) -> Vec<String> {
+    xmp.property_array(namespace, name).map(|item| item.value).collect()
+}
+
+/// XMP에서 키워드 읽기 (digiKam TagsList → lr:hierarchicalSubject → dc:subject 순으로 있는 것)
+pub fn keywords_from_xmp(xmp: &XmpMeta) -> Vec<String> {
+    let digikam = array_values(xmp, XMP_NS_DIGIKAM, "TagsList");
+    if !digikam.is_empty() {
+        return digikam
+            .into_iter()
+            .map(|tag| tag.replace(DIGIKAM_SEPARATOR, &HIERARCHY_SEPARATOR.to_string()))
+            .collect();
+    }
+
+    let hierarchical = array_values(xmp, XMP_NS_LIGHTROOM, "hierarchicalSubject");
+    if !hierarchical.is_empty() {
+        return hierarchical;
+    }
+
+    array_values(xmp, xmp_ns::DC, "subject")
+}
+
+/// 키워드 기록 (기존 키워드 항목은 교체)
+fn set_keywords(xmp: &mut XmpMeta, keywords: &[String], dialect: KeywordDialect) -> Result<(), String> {
+    register_namespaces();
+    for (namespace, name) in [
+        (xmp_ns::DC, "subject"),
+        (XMP_NS_LIGHTROOM, "hierarchicalSubject"),
+        (XMP_NS_DIGIKAM, "TagsList"),
+    ] {
+        let _ = xmp.delete_property(namespace, name);
+    }
+
+    let append = |xmp: &mut XmpMeta, namespace: &str, name: &str, ordered: bool, value: &str| {
+        let array = XmpValue::from(name).set_is_array(true).set_is_ordered(ordered);
+        xmp.append_array_item(namespace, &array, &XmpValue::from(value))
+            .map_err(|e| format!("Failed to write keyword {}: {}", value, e))
+    };
+
+    // dc:subject에는 계층의 마지막 이름만 (중복 제거)
+    let mut leaves: Vec<&str> = Vec::new();
+    for keyword in keywords {
+        let leaf = keyword.rsplit(HIERARCHY_SEPARATOR).next().unwrap_or(keyword).trim();
+        if !leaf.is_empty() && !leaves.contains(&leaf) {
+            leaves.push(leaf);
+        }
+    }
+    for leaf in leaves {
+        append(xmp, xmp_ns::DC, "subject", false, leaf)?;
+    }
+
+    for keyword in keywords.iter().filter(|k| k.contains(HIERARCHY_SEPARATOR)) {
+        append(xmp, XMP_NS_LIGHTROOM, "hierarchicalSubject", false, keyword)?;
+    }
+
+    if dialect == KeywordDialect::Digikam {
+        for keyword in keywords {
+            let tag = keyword.replace(HIERARCHY_SEPARATOR, &DIGIKAM_SEPARATOR.to_string());
+            append(xmp, XMP_NS_DIGIKAM, "TagsList", true, &tag)?;
+        }
+    }
+    Ok(())
+}
+
+/// 사이드카 XMP 읽기 (darktable/digiKam/Adobe 형식), 사이드카가 없으면 None
+pub fn read_sidecar(image: &Path) -> Result<Option<SidecarMetadata>, String> {
+    let Some(path) = find_sidecar(image) else {
+        return Ok(None);
+    };
+    let xmp = read_xmp_file(&path)?;
+
+    let rating = xmp
+        .property(xmp_ns::XMP, "Rating")
+        .and_then(|value| value.value.parse::<i32>().ok());
+
+    // darktable은 번호 목록, Adobe/digiKam은 xmp:Label
+    let mut color_labels: Vec<String> = array_values(&xmp, XMP_NS_DARKTABLE, "colorlabels")
+        .iter()
+        .filter_map(|value| value.parse::<usize>().ok())
+        .filter_map(|index| DARKTABLE_COLORS.get(index))
+        .map(|label| label.to_string())
+        .collect();
+    if let Some(label) = xmp.property(xmp_ns::XMP, "Label").filter(|label| !label.value.is_empty()) {
+        if !color_labels.contains(&label.value) {
+            color_labels.push(label.value);
+        }
+    }
+
+    Ok(Some(SidecarMetadata {
+        path: path.to_string_lossy().to_string(),
+        rating: rating.map(|r| r.clamp(0, 5)),
+        rejected: rating == Some(-1),
+        color_labels,
+        keywords: keywords_from_xmp(&xmp),
+    }))
+}
+
+/// 이미지 키워드 읽기 (사이드카 우선, 없으면 이미지 내장 XMP)
+pub fn read_keywords(image: &Path) -> Result<Vec<String>, String> {
+    if let Some(sidecar) = find_sidecar(image) {
+        return Ok(keywords_from_xmp(&read_xmp_file(&sidecar)?));
+    }
+
+    register_namespaces();
+    let mut xmp_file = XmpFile::new().map_err(|e| format!("Failed to initialize XMP: {}", e))?;
+    if xmp_file
+        .open_file(image, OpenFileOptions::default().only_xmp())
+        .is_err()
+    {
+        return Ok(Vec::new());
+    }
+    Ok(xmp_file.xmp().map(|xmp| keywords_from_xmp(&xmp)).unwrap_or_default())
+}
+
+/// 이미지 키워드 쓰기 (설정된 호환 방식으로)
+pub fn write_keywords(image: &Path, keywords: &[String], dialect: KeywordDialect) -> Result<(), String> {
+    let path = image.to_string_lossy();
+    if dialect == KeywordDialect::Standard && !crate::formats::is_raw_file(&path) {
+        return crate::rating::update_xmp(&path, |xmp| set_keywords(xmp, keywords, dialect));
+    }
+
+    // RAW와 darktable/digiKam 방식은 사이드카에 기록 (기존 사이드카 내용 유지)
+    let sidecar = sidecar_path(image, dialect);
+    let mut xmp = if sidecar.is_file() {
+        read_xmp_file(&sidecar)?
+    } else {
+        XmpMeta::new().map_err(|e| format!("Failed to create XMP: {}", e))?
+    };
+    set_keywords(&mut xmp, keywords, dialect)?;
+
+    let content = xmp
+        .to_string_with_options(Default::default())
+        .map_err(|e| format!("Failed to serialize XMP: {}", e))?;
+    crate::shutdown::write_atomic(&sidecar, content.as_bytes())
+        .map_err(|e| format!("Failed to write sidecar: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digikam_keywords_roundtrip() {
+        let mut xmp = XmpMeta::new().unwrap();
+        let keywords = vec!["Places|Seoul".to_string(), "Family".to_string()];
+        set_keywords(&mut xmp, &keywords, KeywordDialect::Digikam).unwrap();
+
+        assert_eq!(array_values(&xmp, XMP_NS_DIGIKAM, "TagsList"), vec!["Places/Seoul", "Family"]);
+        assert_eq!(array_values(&xmp, xmp_ns::DC, "subject"), vec!["Seoul", "Family"]);
+        assert_eq!(keywords_from_xmp(&xmp), keywords);
+    }
+}
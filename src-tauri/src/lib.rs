@@ -1,23 +1,111 @@
 use tauri::{Emitter, Manager, PhysicalPosition, PhysicalSize, State};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 mod thumbnail;
 mod thumbnail_queue;
+mod thumbnail_cache;
 mod idle_detector;
 mod rating;
 mod clipboard;
+mod folder_snapshot;
 mod folder_watcher;
+mod metadata_store;
+mod smart_album;
+mod favorites;
+mod drives;
+mod drive_watcher;
+mod network_path;
+mod error;
+mod logging;
+mod import;
+mod filename_template;
+mod batch_rename;
+mod checksum;
+mod settings;
+mod gpu_resize;
+mod color_profile;
+mod fs_path;
+mod directory;
+mod cloud_file;
+mod sorting;
+mod filtering;
+mod image_index;
+mod shutdown;
+mod hq_progress;
+mod windows;
+mod shortcuts;
+mod tray;
+mod single_instance;
+mod open_target;
+mod hot_folder;
+mod remote_source;
+mod remote_ftp;
+mod remote_sftp;
+mod remote_webdav;
+mod export;
+mod upload;
+mod metadata_strip;
+mod catalog_import;
+mod keywords;
+mod iptc;
+mod capture_time;
+mod geotag;
+mod metadata_scan;
+mod scheduler;
+mod file_lock;
+mod preview_filter;
+mod jpeg_lossless;
+mod transform;
+mod watermark;
+mod image_tiles;
+mod image_region;
+mod document_pages;
+mod psd;
+mod file_stack;
+mod formats;
+mod rating_queue;
+mod thumbnail_warm;
+mod jobs;
+mod file_transfer;
+mod delete_preview;
+mod folder_tree_watcher;
+mod manual_order;
+mod selection;
+mod review_notes;
+mod versions;
+mod search_index;
+mod timeline;
+mod capture_stats;
+mod app_data;
+mod local_cache;
+mod pending_ratings;
+mod thumbnail_eta;
+mod thumbnail_perf;
+mod placeholder;
+mod archive_source;
+mod spool_index;
+mod wallpaper;
+mod share;
+mod monitor_profile;
 
 use thumbnail_queue::ThumbnailQueueManager;
 use folder_watcher::FolderWatcher;
+use metadata_store::MetadataStore;
+use error::AppError;
 
 // 경로 검증 함수
 fn validate_path(path: &str) -> Result<PathBuf, String> {
-    let path_buf = PathBuf::from(path);
+    // 네트워크 경로는 canonicalize가 느리거나 실패하므로 별도 처리 (타임아웃 적용)
+    if network_path::is_network_path(path) {
+        return network_path::validate_network_path(path);
+    }
+
+    // 긴 경로(Windows)와 NFC/NFD 형태 차이 처리
+    let path_buf = fs_path::to_fs_path(path);
 
     // 경로가 존재하는지 확인
     if !path_buf.exists() {
@@ -86,7 +174,7 @@ fn is_hidden_or_system_dir(name: &str) -> bool {
     false
 }
 
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 struct DriveInfo {
     name: String,
     path: String,
@@ -114,11 +202,16 @@ struct LayoutState {
     thumbnail_width: u32,
 }
 
-// 윈도우 상태 파일 경로 가져오기
-fn get_window_state_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+// 윈도우 상태 파일 경로 가져오기 (메인 윈도우 외에는 라벨별 파일)
+fn get_window_state_path(app: &tauri::AppHandle, label: &str) -> Result<PathBuf, String> {
+    let file_name = if label == "main" {
+        "window-state.json".to_string()
+    } else {
+        format!("window-state-{}.json", label)
+    };
     app.path()
         .app_data_dir()
-        .map(|p| p.join("window-state.json"))
+        .map(|p| p.join(file_name))
         .map_err(|e| format!("Failed to get app data dir: {}", e))
 }
 
@@ -139,8 +232,8 @@ fn get_dockview_layout_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
 }
 
 // 저장된 윈도우 상태 로드
-fn load_window_state(app: &tauri::AppHandle) -> Option<WindowState> {
-    let path = get_window_state_path(app).ok()?;
+fn load_window_state(app: &tauri::AppHandle, label: &str) -> Option<WindowState> {
+    let path = get_window_state_path(app, label).ok()?;
     if path.exists() {
         let content = fs::read_to_string(path).ok()?;
         serde_json::from_str(&content).ok()
@@ -149,17 +242,33 @@ fn load_window_state(app: &tauri::AppHandle) -> Option<WindowState> {
     }
 }
 
-// 윈도우 상태 저장
+// 저장된 윈도우 상태를 라벨에 맞춰 복원
+fn restore_window_state(window: &tauri::WebviewWindow) {
+    if let Some(state) = load_window_state(window.app_handle(), window.label()) {
+        // 최대화 상태일 때도 먼저 일반 위치/크기를 설정해야 함
+        // (복원 시 사용할 크기/위치를 Tauri에 알려주기 위함)
+        let _ = window.set_size(PhysicalSize::new(state.width, state.height));
+        let _ = window.set_position(PhysicalPosition::new(state.x, state.y));
+
+        // 최대화 상태면 설정 후 최대화 실행
+        if state.maximized {
+            let _ = window.maximize();
+        }
+    }
+}
+
+// 윈도우 상태 저장 (호출한 윈도우의 라벨별로 저장)
 #[tauri::command]
 fn save_window_state(
     app: tauri::AppHandle,
+    window: tauri::Window,
     x: i32,
     y: i32,
     width: u32,
     height: u32,
     maximized: bool,
-) -> Result<(), String> {
-    let path = get_window_state_path(&app)?;
+) -> Result<(), AppError> {
+    let path = get_window_state_path(&app, window.label())?;
 
     // 기존 상태 로드 (있으면)
     let mut state = if path.exists() {
@@ -205,7 +314,7 @@ fn save_window_state(
 
 // 프론트엔드 준비 완료 시 윈도우 표시
 #[tauri::command]
-fn show_window(window: tauri::Window) -> Result<(), String> {
+fn show_window(window: tauri::Window) -> Result<(), AppError> {
     window.show().map_err(|e| e.to_string())?;
     Ok(())
 }
@@ -217,7 +326,7 @@ fn save_layout_state(
     folder_width: u32,
     metadata_height: u32,
     thumbnail_width: u32,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let state = LayoutState {
         folder_width,
         metadata_height,
@@ -239,7 +348,7 @@ fn save_layout_state(
 
 // 레이아웃 상태 로드
 #[tauri::command]
-fn load_layout_state(app: tauri::AppHandle) -> Result<Option<LayoutState>, String> {
+fn load_layout_state(app: tauri::AppHandle) -> Result<Option<LayoutState>, AppError> {
     let path = get_layout_state_path(&app)?;
     if path.exists() {
         let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
@@ -252,7 +361,7 @@ fn load_layout_state(app: tauri::AppHandle) -> Result<Option<LayoutState>, Strin
 
 // dockview 레이아웃 저장
 #[tauri::command]
-fn save_dockview_layout(app: tauri::AppHandle, layout: serde_json::Value) -> Result<(), String> {
+fn save_dockview_layout(app: tauri::AppHandle, layout: serde_json::Value) -> Result<(), AppError> {
     let path = get_dockview_layout_path(&app)?;
 
     // 디렉토리가 없으면 생성
@@ -268,7 +377,7 @@ fn save_dockview_layout(app: tauri::AppHandle, layout: serde_json::Value) -> Res
 
 // dockview 레이아웃 로드
 #[tauri::command]
-fn load_dockview_layout(app: tauri::AppHandle) -> Result<Option<serde_json::Value>, String> {
+fn load_dockview_layout(app: tauri::AppHandle) -> Result<Option<serde_json::Value>, AppError> {
     let path = get_dockview_layout_path(&app)?;
     if path.exists() {
         let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
@@ -347,12 +456,52 @@ fn get_drives() -> Vec<DriveInfo> {
     drives
 }
 
+// 드라이브 상세 정보 가져오기 (용량, 파일 시스템, 이동식/네트워크 여부)
+#[tauri::command]
+async fn get_drive_details() -> Result<Vec<drives::DriveDetails>, AppError> {
+    tokio::task::spawn_blocking(|| {
+        get_drives()
+            .iter()
+            .map(|drive| drives::get_drive_details(&drive.name, &drive.path))
+            .collect()
+    })
+    .await
+    .map_err(AppError::from)
+}
+
+// 경로가 속한 볼륨의 여유 공간 확인 (대용량 복사 전 경고용)
+#[tauri::command]
+async fn get_free_space(path: String) -> Result<drives::SpaceInfo, AppError> {
+    tokio::task::spawn_blocking(move || drives::get_space_info(&path))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+        .map_err(AppError::from)
+}
+
 // 서브디렉토리 존재 여부 확인
 #[tauri::command]
-fn has_subdirectories(path: &str) -> Result<bool, String> {
+fn has_subdirectories(path: &str) -> Result<bool, AppError> {
+    // 원격 경로 (remote://)
+    if remote_source::is_remote_path(path) {
+        let listing = remote_source::read_directory(path, is_hidden_or_system_dir)?;
+        return Ok(listing.entries.iter().any(|entry| entry.is_dir));
+    }
+
+    // 압축 파일 안 (archive://)
+    if archive_source::is_archive_path(path) {
+        let listing = archive_source::read_directory(path, is_hidden_or_system_dir)?;
+        return Ok(listing.entries.iter().any(|entry| entry.is_dir));
+    }
+
     // 경로 검증
     let validated_path = validate_path(path)?;
 
+    // 네트워크 경로: 항목별 canonicalize 없이 확인
+    if network_path::is_network_path(path) {
+        let entries = network_path::list_network_directory(&validated_path)?;
+        return Ok(entries.iter().any(|(_, _, is_dir)| *is_dir));
+    }
+
     if let Ok(entries) = fs::read_dir(validated_path) {
         for entry in entries.flatten() {
             let entry_path = entry.path();
@@ -361,9 +510,9 @@ fn has_subdirectories(path: &str) -> Result<bool, String> {
             let real_path = fs::canonicalize(&entry_path)
                 .unwrap_or_else(|_| entry_path.clone());
 
-            // 실제 경로의 메타데이터로 디렉토리 확인
+            // 실제 경로의 메타데이터로 디렉토리 확인 (zip 파일은 가상 폴더)
             if let Ok(metadata) = fs::metadata(&real_path) {
-                if metadata.is_dir() {
+                if metadata.is_dir() || archive_source::is_archive_file(&real_path) {
                     return Ok(true);
                 }
             }
@@ -423,48 +572,93 @@ fn get_documents_folder() -> Option<FolderInfo> {
     }
 }
 
-// 디렉토리 내용 읽기
+// 디렉토리 내용 읽기 (읽지 못한 항목은 errors에 이유와 함께 반환)
+// 같은 이름의 RAW+JPEG, HEIC+MOV는 설정한 묶음 방식에 따라 stacks로 반환
 #[tauri::command]
-fn read_directory_contents(path: &str) -> Result<Vec<serde_json::Value>, String> {
-    // 경로 검증
-    let validated_path = validate_path(path)?;
+fn read_directory_contents(path: &str) -> Result<directory::DirectoryListing, AppError> {
+    let mut listing = list_directory(path)?;
+    listing.stacks = file_stack::detect(&listing.entries, settings::current().stack_policy);
+    Ok(listing)
+}
+
+// 폴더의 이미지 파일만 (종류/확장자/크기/수정 시간 포함, 하위 폴더 제외)
+#[tauri::command]
+async fn list_images(path: String) -> Result<directory::DirectoryListing, AppError> {
+    tokio::task::spawn_blocking(move || {
+        let mut listing = list_directory(&path)?;
+        listing
+            .entries
+            .retain(|entry| !entry.is_dir && formats::is_image_file(Path::new(&entry.name)));
+        listing.stacks = file_stack::detect(&listing.entries, settings::current().stack_policy);
+        Ok(listing)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
 
-    let entries = fs::read_dir(validated_path)
-        .map_err(|e| format!("Failed to read directory: {}", e))?;
+// 지원 형식 목록 (확장자, 분류, 내장 미리보기/디코딩/다중 페이지 지원 여부)
+#[tauri::command]
+fn get_supported_formats() -> Vec<formats::FormatInfo> {
+    formats::supported_formats()
+}
 
-    let mut results = Vec::new();
+// 확장자와 내용이 다른 파일 목록 (앱 시작 후 썸네일 생성/디코딩 중 발견)
+#[tauri::command]
+fn get_format_mismatches() -> Vec<formats::FormatMismatch> {
+    formats::mismatches()
+}
 
-    for entry in entries.flatten() {
-        let path = entry.path();
-        let name = entry.file_name().to_string_lossy().to_string();
+// 묶음 대표 파일 선택 방식 변경 (off, prefer_raw, prefer_jpeg)
+#[tauri::command]
+fn set_stack_policy(app: tauri::AppHandle, policy: file_stack::StackPolicy) -> Result<settings::Settings, AppError> {
+    Ok(settings::update(&app, serde_json::json!({ "stack_policy": policy }))?)
+}
 
-        // 숨김/시스템 디렉토리 필터링
-        if is_hidden_or_system_dir(&name) {
-            continue;
-        }
+// 디렉토리 항목 읽기 (원격/압축 파일/네트워크/로컬)
+// 로컬/네트워크 폴더의 zip 파일은 archive:// 가상 폴더로 표시
+fn list_directory(path: &str) -> Result<directory::DirectoryListing, AppError> {
+    // 원격 경로 (remote://): 항목 경로도 remote://
+    if remote_source::is_remote_path(path) {
+        return Ok(remote_source::read_directory(path, is_hidden_or_system_dir)?);
+    }
 
-        // canonicalize로 심볼릭 링크/junction 해결
-        let real_path = fs::canonicalize(&path)
-            .unwrap_or_else(|_| path.clone());
+    // 압축 파일 안 (archive://): 항목 경로도 archive://
+    if archive_source::is_archive_path(path) {
+        return Ok(archive_source::read_directory(path, is_hidden_or_system_dir)?);
+    }
 
-        // 실제 경로의 메타데이터 확인
-        if let Ok(metadata) = fs::metadata(&real_path) {
-            let is_dir = metadata.is_dir();
+    let mut listing = list_filesystem_directory(path)?;
+    archive_source::expose_archives(&mut listing);
+    Ok(listing)
+}
 
-            results.push(serde_json::json!({
-                "name": name,
-                "path": real_path.to_string_lossy().to_string(),
-                "isDir": is_dir,
-            }));
-        }
+// 파일 시스템 디렉토리 항목 읽기 (네트워크/로컬)
+fn list_filesystem_directory(path: &str) -> Result<directory::DirectoryListing, AppError> {
+    // 경로 검증
+    let validated_path = validate_path(path)?;
+
+    // 네트워크 경로: 항목별 canonicalize 생략 (느리고 실패하기 쉬움)
+    if network_path::is_network_path(path) {
+        let entries = network_path::list_network_directory(&validated_path)?;
+
+        return Ok(directory::DirectoryListing {
+            entries: entries
+                .into_iter()
+                .filter(|(name, _, _)| !is_hidden_or_system_dir(name))
+                .map(|(name, entry_path, is_dir)| {
+                    directory::DirectoryEntry::new(name, entry_path.to_string_lossy().to_string(), is_dir)
+                })
+                .collect(),
+            ..directory::DirectoryListing::default()
+        });
     }
 
-    Ok(results)
+    Ok(directory::read_local(&validated_path, is_hidden_or_system_dir)?)
 }
 
 // 이미지 파일들의 총 용량 계산
 #[tauri::command]
-async fn calculate_images_total_size(paths: Vec<String>) -> Result<u64, String> {
+async fn calculate_images_total_size(paths: Vec<String>) -> Result<u64, AppError> {
     tokio::task::spawn_blocking(move || {
         let mut total_size: u64 = 0;
 
@@ -485,40 +679,101 @@ async fn calculate_images_total_size(paths: Vec<String>) -> Result<u64, String>
 async fn generate_thumbnail_for_image(
     app: tauri::AppHandle,
     file_path: String,
-) -> Result<thumbnail::ThumbnailResult, String> {
-    thumbnail::generate_thumbnail(&app, &file_path).await
+) -> Result<thumbnail::ThumbnailResult, AppError> {
+    let _permit = scheduler::acquire(scheduler::WorkClass::Viewer).await;
+    thumbnail::generate_thumbnail(&app, &file_path).await.map_err(AppError::from)
+}
+
+// 그리드 셀 크기와 화면 배율에 맞는 썸네일 (HiDPI면 큰 단계, 보이는 셀의 기본 썸네일 교체용)
+#[tauri::command]
+async fn generate_tier_thumbnail(
+    app: tauri::AppHandle,
+    file_path: String,
+    cell_size: f64,
+    device_pixel_ratio: f64,
+) -> Result<thumbnail::ThumbnailResult, AppError> {
+    let size = thumbnail::select_tier(cell_size, device_pixel_ratio, settings::current().hidpi_thumbnail_size);
+    let _permit = scheduler::acquire(scheduler::WorkClass::Hq).await;
+    thumbnail::generate_tier_thumbnail(&app, &file_path, size).await.map_err(AppError::from)
+}
+
+// 큐가 파일에 닿기 전 그리드에 먼저 그릴 자리 표시 (캐시, EXIF 썸네일, 크기/평균 색 중 가장 빠른 것)
+#[tauri::command]
+async fn get_fast_placeholder(
+    app: tauri::AppHandle,
+    path: String,
+) -> Result<Option<placeholder::FastPlaceholder>, AppError> {
+    tokio::task::spawn_blocking(move || placeholder::get_fast_placeholder(&app, &path))
+        .await?
+        .map_err(AppError::from)
+}
+
+// 다중 페이지 문서(TIFF)의 페이지 목록
+#[tauri::command]
+async fn get_document_pages(app: tauri::AppHandle, path: String) -> Result<Vec<document_pages::PageInfo>, AppError> {
+    Ok(tokio::task::spawn_blocking(move || document_pages::get_pages(&app, &path)).await??)
+}
+
+// 페이지 썸네일 생성 (page는 0부터, 페이지별로 캐시)
+#[tauri::command]
+async fn generate_page_thumbnail(
+    app: tauri::AppHandle,
+    path: String,
+    page: usize,
+) -> Result<thumbnail::ThumbnailResult, AppError> {
+    let _permit = scheduler::acquire(scheduler::WorkClass::Viewer).await;
+    document_pages::generate_page_thumbnail(&app, &path, page).await.map_err(AppError::from)
+}
+
+// PSD/PSB 정보 (전체 해상도 합성 이미지가 없으면 저해상도 내장 썸네일만 표시 가능)
+#[tauri::command]
+async fn get_psd_info(path: String) -> Result<psd::PsdInfo, AppError> {
+    let _permit = scheduler::acquire(scheduler::WorkClass::Viewer).await;
+    Ok(tokio::task::spawn_blocking(move || psd::get_info(&fs_path::to_fs_string(&path))).await??)
 }
 
 // 이미지 파일에서 고해상도 JPEG 미리보기 추출 (캔버스 출력용)
 // JPG: EXIF 썸네일 또는 원본, RAW: 내장 JPEG 미리보기
 #[tauri::command]
-async fn extract_raw_preview_image(file_path: String) -> Result<String, String> {
+async fn extract_raw_preview_image(file_path: String) -> Result<String, AppError> {
     use base64::{engine::general_purpose::STANDARD, Engine};
 
-    let jpeg_data = thumbnail::extract_raw_preview(&file_path)?;
+    let _permit = scheduler::acquire(scheduler::WorkClass::Viewer).await;
+    let jpeg_data = thumbnail::extract_raw_preview(&fs_path::to_fs_string(&file_path))?;
     Ok(STANDARD.encode(&jpeg_data))
 }
 
 // 썸네일 배치 생성 시작
 #[tauri::command]
 async fn start_thumbnail_generation(
+    app: tauri::AppHandle,
     image_paths: Vec<String>,
     queue: State<'_, Arc<Mutex<ThumbnailQueueManager>>>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
+    // 이전에 완료된 썸네일 먼저 표시 (생성 결과보다 먼저 도착하도록 시작 전에 전송)
+    thumbnail_warm::emit(&app, image_paths.clone()).await;
+
     let queue = queue.lock().await;
     queue.initialize(image_paths).await;
     queue.start_worker().await;
     Ok(())
 }
 
-// 썸네일 우선순위 업데이트
+// 썸네일 우선순위 업데이트 (그리드에 보이는 인덱스 범위 [start, end), 위아래 buffer개 미리 생성)
 #[tauri::command]
-async fn update_thumbnail_priorities(
-    visible_indices: Vec<usize>,
+async fn update_visible_range(
+    start: usize,
+    end: usize,
+    buffer: usize,
     queue: State<'_, Arc<Mutex<ThumbnailQueueManager>>>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
+    if start > end {
+        return Err(AppError::InvalidInput {
+            message: format!("Invalid visible range: {}..{}", start, end),
+        });
+    }
     let queue = queue.lock().await;
-    queue.update_priorities(visible_indices).await;
+    queue.update_visible_range(start, end, buffer).await;
     Ok(())
 }
 
@@ -526,7 +781,7 @@ async fn update_thumbnail_priorities(
 #[tauri::command]
 async fn pause_thumbnail_generation(
     queue: State<'_, Arc<Mutex<ThumbnailQueueManager>>>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let queue = queue.lock().await;
     queue.pause().await;
     Ok(())
@@ -536,7 +791,7 @@ async fn pause_thumbnail_generation(
 #[tauri::command]
 async fn resume_thumbnail_generation(
     queue: State<'_, Arc<Mutex<ThumbnailQueueManager>>>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let queue = queue.lock().await;
     queue.resume().await;
     Ok(())
@@ -546,18 +801,65 @@ async fn resume_thumbnail_generation(
 #[tauri::command]
 async fn get_completed_thumbnails(
     queue: State<'_, Arc<Mutex<ThumbnailQueueManager>>>,
-) -> Result<std::collections::HashMap<String, thumbnail::ThumbnailResult>, String> {
+) -> Result<std::collections::HashMap<String, thumbnail::ThumbnailResult>, AppError> {
     let queue = queue.lock().await;
     Ok(queue.get_all_completed().await)
 }
 
+// 앱 시작 후 형식별 썸네일 생성 통계 (느린 폴더의 병목 확인용)
+#[tauri::command]
+async fn get_thumbnail_perf_report() -> Result<thumbnail_perf::ThumbnailPerfReport, AppError> {
+    Ok(thumbnail_perf::report())
+}
+
+// 생성에 실패한 썸네일 목록 (깨진 이미지 표시용)
+#[tauri::command]
+async fn get_failed_thumbnails(
+    queue: State<'_, Arc<Mutex<ThumbnailQueueManager>>>,
+) -> Result<Vec<thumbnail_queue::ThumbnailFailure>, AppError> {
+    let queue = queue.lock().await;
+    Ok(queue.get_failed().await)
+}
+
+// 실패한 썸네일 다시 생성 (잠겨 있던 파일 등)
+#[tauri::command]
+async fn retry_failed_thumbnails(
+    queue: State<'_, Arc<Mutex<ThumbnailQueueManager>>>,
+) -> Result<usize, AppError> {
+    let queue = queue.lock().await;
+    let count = queue.retry_failed().await;
+    if count > 0 {
+        queue.start_worker().await;
+    }
+    Ok(count)
+}
+
+// 이미지 전체 디코딩으로 손상 여부 검사 (실패한 항목만 반환)
+#[tauri::command]
+async fn validate_images(paths: Vec<String>) -> Result<Vec<thumbnail_queue::ThumbnailFailure>, AppError> {
+    use rayon::prelude::*;
+
+    tokio::task::spawn_blocking(move || {
+        paths
+            .par_iter()
+            .filter_map(|path| {
+                thumbnail::validate_image(&fs_path::to_fs_string(path))
+                    .err()
+                    .map(|error| thumbnail_queue::ThumbnailFailure::new(path, error))
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e).into())
+}
+
 // HQ 썸네일 존재 여부로 이미지 분류
 #[tauri::command]
-fn classify_hq_thumbnails(
+async fn classify_hq_thumbnails(
     image_paths: Vec<String>,
     app_handle: tauri::AppHandle,
-) -> Result<thumbnail::HqThumbnailClassification, String> {
-    Ok(thumbnail::classify_hq_thumbnails(&app_handle, image_paths))
+) -> Result<thumbnail::HqThumbnailClassification, AppError> {
+    Ok(tokio::task::spawn_blocking(move || thumbnail::classify_hq_thumbnails(&app_handle, image_paths)).await?)
 }
 
 // 기존 HQ 썸네일 즉시 로드 (유휴 시간 대기 없음)
@@ -565,7 +867,7 @@ fn classify_hq_thumbnails(
 async fn load_existing_hq_thumbnails(
     image_paths: Vec<String>,
     app_handle: tauri::AppHandle,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     thumbnail_queue::load_existing_hq_thumbnails(app_handle, image_paths).await;
     Ok(())
 }
@@ -575,21 +877,30 @@ async fn load_existing_hq_thumbnails(
 async fn start_hq_thumbnail_generation(
     image_paths: Vec<String>,
     app_handle: tauri::AppHandle,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     thumbnail_queue::start_hq_thumbnail_worker(app_handle, image_paths).await;
     Ok(())
 }
 
+// 커서 이후에 완료된 HQ 썸네일 (창이 숨겨진 동안 보내지 않은 결과)
+#[tauri::command]
+async fn get_hq_results_since(
+    app_handle: tauri::AppHandle,
+    cursor: u64,
+) -> Result<thumbnail_queue::HqResultsPage, AppError> {
+    Ok(thumbnail_queue::get_hq_results_since(&app_handle, cursor).await)
+}
+
 // 고화질 DCT 썸네일 생성 취소
 #[tauri::command]
-fn cancel_hq_thumbnail_generation() -> Result<(), String> {
+fn cancel_hq_thumbnail_generation() -> Result<(), AppError> {
     thumbnail_queue::cancel_hq_thumbnail_generation();
     Ok(())
 }
 
 // HQ 생성 뷰포트 경로 업데이트
 #[tauri::command]
-async fn update_hq_viewport_paths(paths: Vec<String>) -> Result<(), String> {
+async fn update_hq_viewport_paths(paths: Vec<String>) -> Result<(), AppError> {
     thumbnail_queue::update_hq_viewport_paths(paths).await;
     Ok(())
 }
@@ -606,7 +917,7 @@ struct ImageInfo {
 }
 
 #[tauri::command]
-async fn get_image_info(file_path: String) -> Result<ImageInfo, String> {
+async fn get_image_info(file_path: String) -> Result<ImageInfo, AppError> {
     use image::ImageReader;
     use std::time::SystemTime;
 
@@ -731,7 +1042,7 @@ struct ExifMetadata {
 
 // EXIF 메타데이터 추출
 #[tauri::command]
-async fn get_exif_metadata(file_path: String) -> Result<ExifMetadata, String> {
+async fn get_exif_metadata(file_path: String) -> Result<ExifMetadata, AppError> {
     use std::io::BufReader;
 
     let file = fs::File::open(&file_path)
@@ -743,6 +1054,11 @@ async fn get_exif_metadata(file_path: String) -> Result<ExifMetadata, String> {
         .read_from_container(&mut reader)
         .map_err(|e| format!("Failed to read EXIF data: {}", e))?;
 
+    Ok(build_exif_metadata(&exif_data, fs::metadata(&file_path).ok().as_ref()))
+}
+
+// EXIF → 메타데이터 패널 항목 (파일 정보 포함)
+fn build_exif_metadata(exif_data: &exif::Exif, file_metadata: Option<&fs::Metadata>) -> ExifMetadata {
     // EXIF 필드 읽기 헬퍼 함수
     let get_field_string = |tag: exif::Tag| -> Option<String> {
         exif_data.get_field(tag, exif::In::PRIMARY)
@@ -892,9 +1208,8 @@ async fn get_exif_metadata(file_path: String) -> Result<ExifMetadata, String> {
         }
     };
 
-    // 파일 메타데이터 가져오기
-    let file_metadata = fs::metadata(&file_path).ok();
-    let file_size = file_metadata.as_ref().map(|m| m.len());
+    // 파일 메타데이터
+    let file_size = file_metadata.map(|m| m.len());
 
     // 수정 시간 가져오기
     let modified_time = file_metadata.and_then(|metadata| {
@@ -912,7 +1227,7 @@ async fn get_exif_metadata(file_path: String) -> Result<ExifMetadata, String> {
         })
     });
 
-    Ok(ExifMetadata {
+    ExifMetadata {
         // 카메라 정보
         camera_make: get_field_ascii(exif::Tag::Make),
         camera_model: get_field_ascii(exif::Tag::Model),
@@ -955,132 +1270,111 @@ async fn get_exif_metadata(file_path: String) -> Result<ExifMetadata, String> {
         // 파일 정보
         file_size,
         modified_time,
-    })
-}
-
-// 경량 메타데이터 (정렬용)
-#[derive(Serialize)]
-struct LightMetadata {
-    path: String,
-    file_size: Option<u64>,
-    modified_time: Option<String>,
-    date_taken: Option<String>,
-    rating: Option<i32>, // XMP 별점 (0-5)
+    }
 }
 
 // 여러 이미지의 경량 메타데이터를 배치로 가져오기 (정렬용)
 #[tauri::command]
-async fn get_images_light_metadata(file_paths: Vec<String>) -> Result<Vec<LightMetadata>, String> {
-    use std::io::BufReader;
+async fn get_images_light_metadata(
+    app: tauri::AppHandle,
+    file_paths: Vec<String>,
+) -> Result<Vec<metadata_scan::LightMetadata>, AppError> {
     use rayon::prelude::*;
 
     // 병렬로 메타데이터 추출 (Rayon 사용)
-    let results: Vec<LightMetadata> = file_paths
+    let mut results: Vec<metadata_scan::LightMetadata> = file_paths
         .par_iter()
-        .map(|path| {
-            // 파일 메타데이터 (크기, 수정시간)
-            let file_metadata = fs::metadata(path).ok();
-            let file_size = file_metadata.as_ref().map(|m| m.len());
-
-            let modified_time = file_metadata.as_ref().and_then(|m| {
-                m.modified().ok().map(|time| {
-                    use chrono::{DateTime, Utc};
-                    let datetime: DateTime<Utc> = time.into();
-                    datetime.format("%Y-%m-%d %H:%M:%S").to_string()
-                })
-            });
-
-            // EXIF에서 촬영 날짜만 빠르게 추출
-            let date_taken = fs::File::open(path).ok().and_then(|file| {
-                let mut reader = BufReader::new(file);
-                let exif_reader = exif::Reader::new();
-                exif_reader.read_from_container(&mut reader).ok().and_then(|exif_data| {
-                    // DateTimeOriginal만 추출
-                    exif_data.get_field(exif::Tag::DateTimeOriginal, exif::In::PRIMARY)
-                        .and_then(|field| {
-                            if let exif::Value::Ascii(ref vec) = field.value {
-                                vec.first().and_then(|bytes| {
-                                    std::str::from_utf8(bytes).ok().and_then(|date_str| {
-                                        let trimmed = date_str.trim();
-                                        if let Some((date_part, time_part)) = trimmed.split_once(' ') {
-                                            let formatted_date = date_part.replace(':', "-");
-                                            Some(format!("{} {}", formatted_date, time_part))
-                                        } else {
-                                            None
-                                        }
-                                    })
-                                })
-                            } else {
-                                None
-                            }
-                        })
-                })
-            });
-
-            // XMP 별점 읽기 (실패해도 계속 진행)
-            let rating = rating::read_rating(path).ok().filter(|&r| r > 0);
-
-            LightMetadata {
-                path: path.clone(),
-                file_size,
-                modified_time,
-                date_taken,
-                rating,
-            }
-        })
+        .map(|path| metadata_scan::read_light_metadata(path))
         .collect();
+    metadata_scan::merge_database_ratings(&app, &mut results);
+    metadata_scan::merge_color_grids(&app, &mut results);
 
     Ok(results)
 }
 
-// XMP Rating 읽기
+// 경량 메타데이터 스캔 시작 (이전 스캔 취소, light-metadata-batch 이벤트로 묶음 전송)
+#[tauri::command]
+async fn start_light_metadata_scan(app: tauri::AppHandle, file_paths: Vec<String>) -> Result<u64, AppError> {
+    Ok(metadata_scan::start_scan(app, file_paths))
+}
+
+// 경량 메타데이터 스캔 취소 (폴더 변경 시)
+#[tauri::command]
+async fn cancel_light_metadata_scan() -> Result<(), AppError> {
+    metadata_scan::cancel_scan();
+    Ok(())
+}
+
+// XMP Rating 읽기 (XMP를 쓸 수 없는 형식은 DB 별점)
 #[tauri::command]
-async fn read_image_rating(file_path: String) -> Result<i32, String> {
+async fn read_image_rating(store: State<'_, Arc<MetadataStore>>, file_path: String) -> Result<i32, AppError> {
+    let store = Arc::clone(&store);
     // 백그라운드 스레드에서 실행 (파일 I/O 블로킹)
     tokio::task::spawn_blocking(move || {
+        if rating::uses_database(&file_path) {
+            let ratings = rating::database_ratings(&store, [&file_path])?;
+            return Ok(ratings.get(&file_path).copied().unwrap_or(0));
+        }
         rating::read_rating(&file_path)
     })
     .await
     .map_err(|e| format!("Task failed: {}", e))?
+    .map_err(AppError::from)
 }
 
-// XMP Rating 배치 읽기 (여러 파일)
+// XMP Rating 배치 읽기 (여러 파일, XMP를 쓸 수 없는 형식은 DB 별점)
 #[tauri::command]
-async fn read_image_ratings_batch(file_paths: Vec<String>) -> Result<Vec<(String, Option<i32>)>, String> {
+async fn read_image_ratings_batch(
+    store: State<'_, Arc<MetadataStore>>,
+    file_paths: Vec<String>,
+) -> Result<Vec<(String, Option<i32>)>, AppError> {
+    let store = Arc::clone(&store);
     // 백그라운드 스레드에서 병렬 처리
     tokio::task::spawn_blocking(move || {
-        Ok(rating::read_ratings_batch(file_paths))
+        let database = rating::database_ratings(&store, &file_paths)?;
+        let mut ratings = rating::read_ratings_batch(file_paths);
+        for (path, rating) in &mut ratings {
+            if let Some(&stored) = database.get(path.as_str()) {
+                *rating = Some(stored);
+            }
+        }
+        Ok(ratings)
     })
     .await
     .map_err(|e| format!("Task failed: {}", e))?
 }
 
-// XMP Rating 쓰기
+// 파일별 별점 저장 위치 (XMP / DB / 쓰기 대기, 그리드 표시용)
 #[tauri::command]
-async fn write_image_rating(app: tauri::AppHandle, file_path: String, rating: i32) -> Result<(), String> {
-    let file_path_clone = file_path.clone();
-
-    // 백그라운드 스레드에서 실행 (파일 I/O 블로킹)
-    tokio::task::spawn_blocking(move || {
-        rating::write_rating(&file_path_clone, rating)
-    })
-    .await
-    .map_err(|e| format!("Task failed: {}", e))??;
+async fn get_rating_sources(
+    store: State<'_, Arc<MetadataStore>>,
+    paths: Vec<String>,
+) -> Result<Vec<(String, rating::RatingSource)>, AppError> {
+    let store = Arc::clone(&store);
+    tokio::task::spawn_blocking(move || rating::rating_sources(&store, paths)).await?.map_err(AppError::from)
+}
 
-    // 별점 변경 이벤트 발생
-    app.emit("rating-changed", serde_json::json!({
-        "path": file_path,
-        "rating": rating
-    })).map_err(|e| format!("Failed to emit event: {}", e))?;
+// 별점 일괄 조회 (메타데이터 DB 캐시, 그리드 별점 배지용)
+#[tauri::command]
+async fn get_ratings_batch(
+    store: State<'_, Arc<MetadataStore>>,
+    paths: Vec<String>,
+) -> Result<Vec<(String, Option<i32>)>, AppError> {
+    let store = Arc::clone(&store);
+    Ok(tokio::task::spawn_blocking(move || store.get_ratings(&paths)).await??)
+}
 
-    Ok(())
+// XMP Rating 쓰기 (파일별 쓰기 큐에 예약, 완료되면 rating-changed, 실패/충돌은 rating-write-failed)
+#[tauri::command]
+async fn write_image_rating(app: tauri::AppHandle, file_path: String, rating: i32) -> Result<(), AppError> {
+    rating_queue::enqueue(&app, file_path, rating).map_err(AppError::from)
 }
 
 // 폴더 생성
 #[tauri::command]
-async fn create_folder(parent_path: String, folder_name: String) -> Result<(), String> {
+async fn create_folder(parent_path: String, folder_name: String) -> Result<(), AppError> {
     tokio::task::spawn_blocking(move || {
-        let new_path = PathBuf::from(&parent_path).join(&folder_name);
+        let new_path = fs_path::to_fs_path(PathBuf::from(&parent_path).join(&folder_name));
         fs::create_dir(&new_path)
             .map_err(|e| format!("폴더 생성 실패: {}", e))?;
         Ok(())
@@ -1091,14 +1385,14 @@ async fn create_folder(parent_path: String, folder_name: String) -> Result<(), S
 
 // 폴더 이름 변경
 #[tauri::command]
-async fn rename_folder(old_path: String, new_name: String) -> Result<(), String> {
+async fn rename_folder(old_path: String, new_name: String) -> Result<(), AppError> {
     tokio::task::spawn_blocking(move || {
-        let old_path_buf = PathBuf::from(&old_path);
+        let old_path_buf = fs_path::to_fs_path(&old_path);
         let parent = old_path_buf.parent()
             .ok_or("부모 디렉토리를 찾을 수 없습니다")?;
         let new_path = parent.join(&new_name);
 
-        fs::rename(&old_path, &new_path)
+        fs::rename(&old_path_buf, &new_path)
             .map_err(|e| format!("이름 변경 실패: {}", e))?;
         Ok(())
     })
@@ -1108,47 +1402,155 @@ async fn rename_folder(old_path: String, new_name: String) -> Result<(), String>
 
 // 파일 이름 변경
 #[tauri::command]
-async fn rename_file(old_path: String, new_name: String) -> Result<String, String> {
-    tokio::task::spawn_blocking(move || {
-        let old_path_buf = PathBuf::from(&old_path);
+async fn rename_file(old_path: String, new_name: String) -> Result<String, AppError> {
+    tokio::task::spawn_blocking(move || -> Result<String, AppError> {
+        let old_path_buf = fs_path::to_fs_path(&old_path);
         let parent = old_path_buf.parent()
-            .ok_or("부모 디렉토리를 찾을 수 없습니다")?;
+            .ok_or_else(|| AppError::not_found("부모 디렉토리를 찾을 수 없습니다"))?;
         let new_path = parent.join(&new_name);
 
         // 이미 존재하는 파일인지 확인
         if new_path.exists() && new_path != old_path_buf {
-            return Err("같은 이름의 파일이 이미 존재합니다.".to_string());
+            return Err(AppError::already_exists("같은 이름의 파일이 이미 존재합니다."));
         }
 
-        fs::rename(&old_path, &new_path)
+        fs::rename(&old_path_buf, &new_path)
             .map_err(|e| format!("이름 변경 실패: {}", e))?;
 
         // 새 경로 반환
-        Ok(new_path.to_string_lossy().to_string())
+        Ok(fs_path::to_display(&new_path))
+    })
+    .await?
+}
+
+// 일괄 이름 변경 미리보기 (변경 전→후 목록과 충돌 여부)
+#[tauri::command]
+async fn preview_batch_rename(
+    paths: Vec<String>,
+    template: String,
+    start_sequence: Option<usize>,
+) -> Result<Vec<batch_rename::RenameEntry>, AppError> {
+    tokio::task::spawn_blocking(move || {
+        batch_rename::preview(&paths, &template, start_sequence.unwrap_or(1))
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+    .map_err(AppError::from)
+}
+
+// 템플릿으로 일괄 이름 변경 (충돌이 있으면 적용하지 않음)
+#[tauri::command]
+async fn batch_rename(
+    paths: Vec<String>,
+    template: String,
+    start_sequence: Option<usize>,
+) -> Result<Vec<batch_rename::RenameEntry>, AppError> {
+    tokio::task::spawn_blocking(move || {
+        batch_rename::batch_rename(&paths, &template, start_sequence.unwrap_or(1))
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+    .map_err(AppError::from)
+}
+
+// 마지막 일괄 이름 변경 되돌리기
+#[tauri::command]
+async fn undo_batch_rename() -> Result<Vec<batch_rename::RenameEntry>, AppError> {
+    tokio::task::spawn_blocking(batch_rename::undo_last)
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+        .map_err(AppError::from)
+}
+
+// 폴더를 dest 아래로 복사 (같은 이름 폴더는 합치고, 파일 충돌은 conflict_policy로 처리)
+#[tauri::command]
+async fn copy_folder(
+    app: tauri::AppHandle,
+    src: String,
+    dest: String,
+    conflict_policy: Option<file_transfer::ConflictPolicy>,
+) -> Result<file_transfer::TransferSummary, AppError> {
+    transfer_folder(app, src, dest, conflict_policy.unwrap_or_default(), false).await
+}
+
+// 폴더를 dest 아래로 이동 (다른 볼륨이면 복사 후 원본 삭제)
+#[tauri::command]
+async fn move_folder(
+    app: tauri::AppHandle,
+    src: String,
+    dest: String,
+    conflict_policy: Option<file_transfer::ConflictPolicy>,
+) -> Result<file_transfer::TransferSummary, AppError> {
+    transfer_folder(app, src, dest, conflict_policy.unwrap_or_default(), true).await
+}
+
+async fn transfer_folder(
+    app: tauri::AppHandle,
+    src: String,
+    dest: String,
+    policy: file_transfer::ConflictPolicy,
+    is_move: bool,
+) -> Result<file_transfer::TransferSummary, AppError> {
+    let source = validate_path(&src)?;
+    let dest = fs_path::to_fs_path(&dest);
+
+    tokio::task::spawn_blocking(move || {
+        let label = if is_move { "폴더 이동" } else { "폴더 복사" };
+        let job = jobs::Job::start(&app, jobs::JobKind::Transfer, label, 0);
+        job.finish_with(file_transfer::transfer_folder(&app, &source, &dest, policy, is_move, &job))
     })
     .await
     .map_err(|e| format!("Task failed: {}", e))?
+    .map_err(AppError::from)
+}
+
+// 폴더 삭제 전 확인용 요약 (파일 수, 용량, 최근 수정 시간, min_rating 이상 별점/픽 이미지 수)
+#[tauri::command]
+async fn preview_delete(
+    store: State<'_, Arc<MetadataStore>>,
+    path: String,
+    min_rating: Option<i32>,
+) -> Result<delete_preview::DeletePreview, AppError> {
+    let path = validate_path(&path)?;
+    let store = Arc::clone(&store);
+    tokio::task::spawn_blocking(move || delete_preview::preview(&store, &path, min_rating.unwrap_or(1)))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+        .map_err(AppError::from)
 }
 
 // 폴더 삭제
 #[tauri::command]
-async fn delete_folder(path: String) -> Result<(), String> {
+async fn delete_folder(path: String) -> Result<(), AppError> {
     tokio::task::spawn_blocking(move || {
-        fs::remove_dir_all(&path)
-            .map_err(|e| format!("폴더 삭제 실패: {}", e))?;
+        let path = fs_path::to_fs_path(&path);
+        if settings::current().delete_to_trash {
+            trash::delete(&path)
+                .map_err(|e| format!("폴더 삭제 실패: {}", e))?;
+        } else {
+            fs::remove_dir_all(&path)
+                .map_err(|e| format!("폴더 삭제 실패: {}", e))?;
+        }
         Ok(())
     })
     .await
     .map_err(|e| format!("Task failed: {}", e))?
 }
 
-// 파일들 삭제 (휴지통으로 이동)
+// 파일들 삭제 (설정에 따라 휴지통 이동 또는 영구 삭제)
 #[tauri::command]
-async fn delete_files(file_paths: Vec<String>) -> Result<(), String> {
+async fn delete_files(file_paths: Vec<String>) -> Result<(), AppError> {
     tokio::task::spawn_blocking(move || {
+        let to_trash = settings::current().delete_to_trash;
         for path in &file_paths {
-            trash::delete(path)
-                .map_err(|e| format!("파일 삭제 실패 ({}): {}", path, e))?;
+            let target = fs_path::to_fs_path(path);
+            if to_trash {
+                trash::delete(&target)
+                    .map_err(|e| format!("파일 삭제 실패 ({}): {}", path, e))?;
+            } else {
+                fs::remove_file(&target)
+                    .map_err(|e| format!("파일 삭제 실패 ({}): {}", path, e))?;
+            }
         }
         Ok(())
     })
@@ -1158,12 +1560,13 @@ async fn delete_files(file_paths: Vec<String>) -> Result<(), String> {
 
 // 파일 경로들을 클립보드에 복사
 #[tauri::command]
-async fn copy_files_to_clipboard(file_paths: Vec<String>, is_cut: bool) -> Result<(), String> {
+async fn copy_files_to_clipboard(file_paths: Vec<String>, is_cut: bool) -> Result<(), AppError> {
     tokio::task::spawn_blocking(move || {
         clipboard::copy_files_to_clipboard(file_paths, is_cut)
     })
     .await
     .map_err(|e| format!("Task failed: {}", e))?
+    .map_err(AppError::from)
 }
 
 // 클립보드에서 파일 붙여넣기
@@ -1172,12 +1575,13 @@ async fn paste_files_from_clipboard(
     destination_dir: String,
     overwrite_files: Vec<String>,
     skip_files: Vec<String>,
-) -> Result<Vec<clipboard::DuplicateFileInfo>, String> {
+) -> Result<Vec<clipboard::DuplicateFileInfo>, AppError> {
     tokio::task::spawn_blocking(move || {
         clipboard::paste_files(destination_dir, overwrite_files, skip_files)
     })
     .await
     .map_err(|e| format!("Task failed: {}", e))?
+    .map_err(AppError::from)
 }
 
 // 폴더 감시 시작
@@ -1186,107 +1590,1719 @@ async fn start_folder_watch(
     app: tauri::AppHandle,
     watcher: State<'_, Arc<Mutex<FolderWatcher>>>,
     folder_path: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
+    // 압축 파일 안은 바뀌지 않으므로 감시하지 않음
+    if archive_source::is_archive_path(&folder_path) {
+        return Ok(());
+    }
+
     let watcher = watcher.lock().await;
-    watcher.watch_folder(app, folder_path)
+    watcher.watch_folder(app.clone(), folder_path.clone())?;
+
+    // 최근 폴더 기록 (실패해도 감시는 계속)
+    if let Err(e) = favorites::record_recent(&app, &folder_path) {
+        tracing::warn!("Failed to record recent folder: {}", e);
+    }
+    Ok(())
 }
 
 // 폴더 감시 중지
 #[tauri::command]
 async fn stop_folder_watch(
     watcher: State<'_, Arc<Mutex<FolderWatcher>>>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let watcher = watcher.lock().await;
     watcher.stop_watching();
     Ok(())
 }
 
-#[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run() {
-    tauri::Builder::default()
-        .plugin(tauri_plugin_opener::init())
-        .plugin(tauri_plugin_fs::init())
-        .setup(|app| {
-            let window = app.get_webview_window("main")
-                .ok_or("Failed to get main window")?;
+// 폴더 목록 스냅샷 저장 (감시가 안 되는 네트워크 폴더의 변경 감지 기준)
+#[tauri::command]
+async fn snapshot_folder(path: String) -> Result<folder_snapshot::SnapshotInfo, AppError> {
+    tokio::task::spawn_blocking(move || folder_snapshot::snapshot_folder(&path))
+        .await?
+        .map_err(AppError::from)
+}
 
-            // 윈도우 핸들을 idle_detector에 설정 (Windows만)
-            #[cfg(target_os = "windows")]
-            {
-                use raw_window_handle::{HasWindowHandle, RawWindowHandle};
-                if let Ok(handle) = window.window_handle() {
-                    if let RawWindowHandle::Win32(win32_handle) = handle.as_raw() {
-                        idle_detector::set_app_window_handle(win32_handle.hwnd.get());
-                    }
-                }
-            }
+// 마지막 스냅샷과 비교한 추가/삭제/수정 파일 (비교 후 스냅샷 갱신)
+#[tauri::command]
+async fn diff_folder(path: String) -> Result<folder_snapshot::FolderDiff, AppError> {
+    tokio::task::spawn_blocking(move || folder_snapshot::diff_folder(&path))
+        .await?
+        .map_err(AppError::from)
+}
 
-            // 저장된 윈도우 상태 복원
-            if let Some(state) = load_window_state(app.handle()) {
-                // 최대화 상태일 때도 먼저 일반 위치/크기를 설정해야 함
-                // (복원 시 사용할 크기/위치를 Tauri에 알려주기 위함)
-                let _ = window.set_size(PhysicalSize::new(state.width, state.height));
-                let _ = window.set_position(PhysicalPosition::new(state.x, state.y));
-
-                // 최대화 상태면 설정 후 최대화 실행
-                if state.maximized {
-                    let _ = window.maximize();
-                }
-            }
+// 폴더 패널 구조 감시 (roots의 하위 폴더 생성/삭제/이름 변경 시 folder-tree-changed, 호출마다 목록 교체)
+#[tauri::command]
+async fn watch_folder_tree(
+    app: tauri::AppHandle,
+    tree_watcher: State<'_, Arc<Mutex<folder_tree_watcher::FolderTreeWatcher>>>,
+    roots: Vec<String>,
+) -> Result<(), AppError> {
+    tree_watcher.lock().await.watch(app, &roots).map_err(AppError::from)
+}
 
-            // 썸네일 큐 매니저 초기화
-            let queue_manager = ThumbnailQueueManager::new(app.handle().clone());
-            app.manage(Arc::new(Mutex::new(queue_manager)));
+// 폴더 패널 구조 감시 중지
+#[tauri::command]
+async fn stop_folder_tree_watch(
+    tree_watcher: State<'_, Arc<Mutex<folder_tree_watcher::FolderTreeWatcher>>>,
+) -> Result<(), AppError> {
+    tree_watcher.lock().await.stop();
+    Ok(())
+}
 
-            // 폴더 감시자 초기화
-            let folder_watcher = FolderWatcher::new();
-            app.manage(Arc::new(Mutex::new(folder_watcher)));
+// 이미지 메타데이터 인덱싱 (변경된 파일만 다시 읽음)
+#[tauri::command]
+async fn index_images(
+    store: State<'_, Arc<MetadataStore>>,
+    image_paths: Vec<String>,
+) -> Result<usize, AppError> {
+    let store = Arc::clone(&store);
+    tokio::task::spawn_blocking(move || {
+        store.index_files(&image_paths).map(|changed| changed.len())
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+    .map_err(AppError::from)
+}
 
-            Ok(())
-        })
-        .plugin(tauri_plugin_store::Builder::new().build())
-        .invoke_handler(tauri::generate_handler![
-            save_window_state,
-            show_window,
-            save_layout_state,
-            load_layout_state,
-            save_dockview_layout,
-            load_dockview_layout,
-            get_drives,
-            has_subdirectories,
-            get_picture_folder,
-            get_desktop_folder,
-            get_documents_folder,
-            read_directory_contents,
-            calculate_images_total_size,
-            generate_thumbnail_for_image,
-            extract_raw_preview_image,
-            start_thumbnail_generation,
-            update_thumbnail_priorities,
-            pause_thumbnail_generation,
-            resume_thumbnail_generation,
-            get_completed_thumbnails,
+// 스마트 앨범 생성
+#[tauri::command]
+async fn create_smart_album(
+    store: State<'_, Arc<MetadataStore>>,
+    name: String,
+    rules: smart_album::RuleSet,
+) -> Result<smart_album::SmartAlbum, AppError> {
+    smart_album::create_album(&store, &name, rules).map_err(AppError::from)
+}
+
+// 스마트 앨범 수정
+#[tauri::command]
+async fn update_smart_album(
+    store: State<'_, Arc<MetadataStore>>,
+    id: i64,
+    name: String,
+    rules: smart_album::RuleSet,
+) -> Result<smart_album::SmartAlbum, AppError> {
+    smart_album::update_album(&store, id, &name, rules).map_err(AppError::from)
+}
+
+// 스마트 앨범 삭제
+#[tauri::command]
+async fn delete_smart_album(
+    store: State<'_, Arc<MetadataStore>>,
+    id: i64,
+) -> Result<(), AppError> {
+    smart_album::delete_album(&store, id).map_err(AppError::from)
+}
+
+// 스마트 앨범 목록
+#[tauri::command]
+async fn list_smart_albums(
+    store: State<'_, Arc<MetadataStore>>,
+) -> Result<Vec<smart_album::SmartAlbum>, AppError> {
+    smart_album::list_albums(&store).map_err(AppError::from)
+}
+
+// 스마트 앨범 평가 (결과는 smart-album-results 이벤트로 스트리밍)
+#[tauri::command]
+async fn evaluate_smart_album(
+    app: tauri::AppHandle,
+    store: State<'_, Arc<MetadataStore>>,
+    id: i64,
+) -> Result<usize, AppError> {
+    let store = Arc::clone(&store);
+    tokio::task::spawn_blocking(move || {
+        let album = smart_album::get_album(&store, id)?;
+        smart_album::evaluate_streaming(&app, &store, &album)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+    .map_err(AppError::from)
+}
+
+// 검토 메모 추가 (markup은 이미지 크기 기준 0~1 영역과 설명)
+#[tauri::command]
+async fn add_review_note(
+    store: State<'_, Arc<MetadataStore>>,
+    path: String,
+    text: String,
+    markup: Option<Vec<review_notes::NoteRect>>,
+) -> Result<review_notes::ReviewNote, AppError> {
+    review_notes::add_note(&store, &path, &text, markup.unwrap_or_default()).map_err(AppError::from)
+}
+
+// 검토 메모 수정
+#[tauri::command]
+async fn update_review_note(
+    store: State<'_, Arc<MetadataStore>>,
+    id: i64,
+    text: String,
+    markup: Option<Vec<review_notes::NoteRect>>,
+) -> Result<review_notes::ReviewNote, AppError> {
+    review_notes::update_note(&store, id, &text, markup.unwrap_or_default()).map_err(AppError::from)
+}
+
+// 검토 메모 삭제
+#[tauri::command]
+async fn delete_review_note(
+    store: State<'_, Arc<MetadataStore>>,
+    id: i64,
+) -> Result<(), AppError> {
+    review_notes::delete_note(&store, id).map_err(AppError::from)
+}
+
+// 이미지의 검토 메모 목록
+#[tauri::command]
+async fn get_review_notes(
+    store: State<'_, Arc<MetadataStore>>,
+    path: String,
+) -> Result<Vec<review_notes::ReviewNote>, AppError> {
+    review_notes::get_notes(&store, &path).map_err(AppError::from)
+}
+
+// 폴더의 검토 메모를 CSV/JSON으로 내보내기 (내보낸 메모 수 반환)
+#[tauri::command]
+async fn export_review_notes(
+    store: State<'_, Arc<MetadataStore>>,
+    folder: String,
+    dest_path: String,
+    format: review_notes::NoteExportFormat,
+) -> Result<usize, AppError> {
+    let store = Arc::clone(&store);
+    tokio::task::spawn_blocking(move || {
+        review_notes::export_folder(&store, &folder, Path::new(&dest_path), format)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+    .map_err(AppError::from)
+}
+
+// 이미지의 모든 버전 (원본과 편집/내보내기 파생 파일, 뷰어 버전 전환용)
+#[tauri::command]
+async fn get_versions(
+    store: State<'_, Arc<MetadataStore>>,
+    path: String,
+) -> Result<versions::VersionSet, AppError> {
+    let store = Arc::clone(&store);
+    tokio::task::spawn_blocking(move || versions::get_versions(&store, &path))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+        .map_err(AppError::from)
+}
+
+// 파생 파일을 원본에 직접 연결
+#[tauri::command]
+async fn link_version(
+    store: State<'_, Arc<MetadataStore>>,
+    original: String,
+    path: String,
+) -> Result<(), AppError> {
+    versions::link(&store, &original, &path).map_err(AppError::from)
+}
+
+// 즐겨찾기 폴더 추가
+#[tauri::command]
+fn add_favorite_folder(
+    app: tauri::AppHandle,
+    path: String,
+    display_name: Option<String>,
+) -> Result<Vec<favorites::FavoriteFolderStatus>, AppError> {
+    favorites::add_favorite(&app, path, display_name).map_err(AppError::from)
+}
+
+// 즐겨찾기 폴더 삭제
+#[tauri::command]
+fn remove_favorite_folder(app: tauri::AppHandle, path: String) -> Result<Vec<favorites::FavoriteFolderStatus>, AppError> {
+    favorites::remove_favorite(&app, &path).map_err(AppError::from)
+}
+
+// 즐겨찾기 폴더 목록 (존재 여부 검증 포함)
+#[tauri::command]
+fn list_favorite_folders(app: tauri::AppHandle) -> Result<Vec<favorites::FavoriteFolderStatus>, AppError> {
+    favorites::list_favorites(&app).map_err(AppError::from)
+}
+
+// 즐겨찾기 표시 이름 변경
+#[tauri::command]
+fn rename_favorite_folder(
+    app: tauri::AppHandle,
+    path: String,
+    display_name: Option<String>,
+) -> Result<Vec<favorites::FavoriteFolderStatus>, AppError> {
+    favorites::rename_favorite(&app, &path, display_name).map_err(AppError::from)
+}
+
+// 즐겨찾기 순서 변경
+#[tauri::command]
+fn reorder_favorite_folders(
+    app: tauri::AppHandle,
+    ordered_paths: Vec<String>,
+) -> Result<Vec<favorites::FavoriteFolderStatus>, AppError> {
+    favorites::reorder_favorites(&app, ordered_paths).map_err(AppError::from)
+}
+
+// 최근 연 폴더 목록
+#[tauri::command]
+fn get_recent_folders(app: tauri::AppHandle, limit: Option<usize>) -> Result<Vec<favorites::RecentFolder>, AppError> {
+    favorites::get_recent(&app, limit).map_err(AppError::from)
+}
+
+// 최근 연 폴더 기록 삭제
+#[tauri::command]
+fn clear_recent_folders(app: tauri::AppHandle) -> Result<(), AppError> {
+    favorites::clear_recent(&app).map_err(AppError::from)
+}
+
+// 메모리 카드에서 사진 가져오기 (날짜 폴더 정리, 중복 건너뛰기)
+#[tauri::command]
+async fn import_from_device(
+    app: tauri::AppHandle,
+    store: State<'_, Arc<MetadataStore>>,
+    source: String,
+    dest: String,
+    options: Option<import::ImportOptions>,
+) -> Result<import::ImportSummary, AppError> {
+    let source_path = validate_path(&source)?;
+    let store = Arc::clone(&store);
+    let options = options.unwrap_or_default();
+
+    tokio::task::spawn_blocking(move || {
+        let job = jobs::Job::start(&app, jobs::JobKind::Import, "사진 가져오기", 0);
+        job.finish_with(import::import_from_device(
+            &app,
+            &store,
+            &source_path,
+            &PathBuf::from(dest),
+            &options,
+            &job,
+        ))
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+    .map_err(AppError::from)
+}
+
+// 체크섬 계산 (manifest_path가 있으면 .sha256/.md5 매니페스트로 저장)
+#[tauri::command]
+async fn compute_checksums(
+    app: tauri::AppHandle,
+    paths: Vec<String>,
+    algorithm: checksum::ChecksumAlgorithm,
+    manifest_path: Option<String>,
+) -> Result<Vec<checksum::ChecksumEntry>, AppError> {
+    tokio::task::spawn_blocking(move || {
+        let job = jobs::Job::start(&app, jobs::JobKind::Checksum, "체크섬 계산", paths.len());
+        let entries = checksum::compute_checksums(&app, &paths, algorithm, &job);
+        if let Some(manifest_path) = manifest_path {
+            job.finish_with(checksum::write_manifest(Path::new(&manifest_path), &entries))?;
+        }
+        Ok(entries)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
+// 체크섬 매니페스트로 파일 무결성 검증 (algorithm이 없으면 매니페스트에서 판별)
+#[tauri::command]
+async fn verify_checksums(
+    app: tauri::AppHandle,
+    manifest_path: String,
+    algorithm: Option<checksum::ChecksumAlgorithm>,
+) -> Result<checksum::VerifyReport, AppError> {
+    tokio::task::spawn_blocking(move || {
+        let job = jobs::Job::start(&app, jobs::JobKind::Checksum, "체크섬 검증", 0);
+        job.finish_with(checksum::verify_checksums(&app, Path::new(&manifest_path), algorithm, &job))
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+    .map_err(AppError::from)
+}
+
+// 썸네일 캐시 통계 (원본이 삭제/수정된 항목과 정리 가능한 용량)
+#[tauri::command]
+async fn get_thumbnail_cache_stats(
+    app: tauri::AppHandle,
+    store: State<'_, Arc<MetadataStore>>,
+) -> Result<thumbnail_cache::CacheReport, AppError> {
+    let store = Arc::clone(&store);
+    tokio::task::spawn_blocking(move || thumbnail_cache::scan(&app, &store))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+        .map_err(AppError::from)
+}
+
+// 썸네일 캐시 폴더 변경 (기존 캐시 이동, target_dir이 없으면 앱 데이터 폴더로 되돌림)
+#[tauri::command]
+async fn relocate_thumbnail_cache(
+    app: tauri::AppHandle,
+    target_dir: Option<String>,
+) -> Result<thumbnail_cache::CacheRelocateResult, AppError> {
+    tokio::task::spawn_blocking(move || {
+        let job = jobs::Job::start(&app, jobs::JobKind::Transfer, "썸네일 캐시 이동", 0);
+        job.finish_with(thumbnail_cache::relocate(&app, target_dir.as_deref(), &job))
+    })
+    .await?
+    .map_err(AppError::from)
+}
+
+// 폴더별 캐시(.pixengine) 만들기 (copy_existing이면 앱 캐시의 썸네일 복사)
+#[tauri::command]
+async fn enable_local_cache(
+    app: tauri::AppHandle,
+    folder: String,
+    copy_existing: Option<bool>,
+) -> Result<local_cache::LocalCacheInfo, AppError> {
+    let validated_path = validate_path(&folder)?;
+    tokio::task::spawn_blocking(move || {
+        local_cache::enable(&app, &validated_path, copy_existing.unwrap_or(true))
+    })
+    .await?
+    .map_err(AppError::from)
+}
+
+// root 아래 폴더별 캐시 목록 (폴더, 파일 수, 용량)
+#[tauri::command]
+async fn find_local_caches(root: String) -> Result<Vec<local_cache::LocalCacheInfo>, AppError> {
+    let validated_path = validate_path(&root)?;
+    Ok(tokio::task::spawn_blocking(move || local_cache::find(&validated_path)).await?)
+}
+
+// root 아래 폴더별 캐시 정리 (remove_all이면 .pixengine 폴더 삭제, 아니면 오래된 썸네일만)
+#[tauri::command]
+async fn clean_local_caches(
+    root: String,
+    remove_all: Option<bool>,
+) -> Result<thumbnail_cache::CacheCleanupResult, AppError> {
+    let validated_path = validate_path(&root)?;
+    Ok(tokio::task::spawn_blocking(move || local_cache::clean(&validated_path, remove_all.unwrap_or(false))).await?)
+}
+
+// 읽기 전용이라 파일에 쓰지 못하고 앱에 보관 중인 별점 목록
+#[tauri::command]
+async fn get_pending_ratings(
+    store: State<'_, Arc<MetadataStore>>,
+) -> Result<Vec<pending_ratings::PendingRating>, AppError> {
+    let store = Arc::clone(&store);
+    tokio::task::spawn_blocking(move || pending_ratings::list(&store)).await?.map_err(AppError::from)
+}
+
+// 보관 중인 별점 중 쓸 수 있게 된 파일을 바로 쓰기 (큐에 넣은 수 반환)
+#[tauri::command]
+async fn flush_pending_ratings(
+    app: tauri::AppHandle,
+    store: State<'_, Arc<MetadataStore>>,
+) -> Result<usize, AppError> {
+    let store = Arc::clone(&store);
+    tokio::task::spawn_blocking(move || pending_ratings::flush(&app, &store)).await?.map_err(AppError::from)
+}
+
+// 썸네일 캐시 정리 (사용자 확인 후 호출)
+#[tauri::command]
+async fn clean_thumbnail_cache(
+    app: tauri::AppHandle,
+    store: State<'_, Arc<MetadataStore>>,
+    include_untracked: Option<bool>,
+) -> Result<thumbnail_cache::CacheCleanupResult, AppError> {
+    let store = Arc::clone(&store);
+    tokio::task::spawn_blocking(move || {
+        thumbnail_cache::clean(&app, &store, include_untracked.unwrap_or(false))
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+    .map_err(AppError::from)
+}
+
+// 로그 레벨 변경 (error, warn, info, debug, trace)
+#[tauri::command]
+fn set_log_level(level: String) -> Result<(), AppError> {
+    logging::set_level(&level).map_err(AppError::from)
+}
+
+// 현재 로그 레벨
+#[tauri::command]
+fn get_log_level() -> String {
+    logging::get_level()
+}
+
+// 최근 로그 가져오기 (지원 요청 시 로그 내보내기용)
+#[tauri::command]
+async fn get_recent_logs(app: tauri::AppHandle, max_lines: Option<usize>) -> Result<Vec<String>, AppError> {
+    tokio::task::spawn_blocking(move || logging::get_recent_logs(&app, max_lines.unwrap_or(500)))
+        .await?
+        .map_err(AppError::from)
+}
+
+// 현재 설정 가져오기
+#[tauri::command]
+fn get_settings() -> settings::Settings {
+    settings::current()
+}
+
+// 설정 변경 (patch에 포함된 항목만 변경, settings-changed 이벤트 전송)
+#[tauri::command]
+async fn update_settings(app: tauri::AppHandle, patch: serde_json::Value) -> Result<settings::Settings, AppError> {
+    tokio::task::spawn_blocking(move || settings::update(&app, patch))
+        .await?
+        .map_err(AppError::from)
+}
+
+// 전원/발열 상태 가져오기
+#[tauri::command]
+fn get_system_conditions() -> idle_detector::SystemConditions {
+    idle_detector::get_system_conditions()
+}
+
+// GPU 리사이징 사용 가능 여부 (설정 화면 표시용)
+#[tauri::command]
+async fn is_gpu_resize_available() -> Result<bool, AppError> {
+    Ok(tokio::task::spawn_blocking(gpu_resize::is_available).await?)
+}
+
+// 클라우드 전용 파일 다운로드 (hydrate-progress 이벤트로 진행률 전송)
+#[tauri::command]
+async fn hydrate_files(
+    app: tauri::AppHandle,
+    paths: Vec<String>,
+) -> Result<Vec<cloud_file::HydrateEntry>, AppError> {
+    Ok(tokio::task::spawn_blocking(move || cloud_file::hydrate_files(&app, &paths)).await?)
+}
+
+// 이미지 경로 정렬 (인덱싱된 메타데이터 사용, 원래 인덱스 매핑 포함)
+// 사용자 지정 순서는 첫 경로가 있는 폴더의 저장된 순서 사용
+#[tauri::command]
+async fn sort_image_paths(
+    app: tauri::AppHandle,
+    store: State<'_, Arc<MetadataStore>>,
+    paths: Vec<String>,
+    key: sorting::SortKey,
+    order: sorting::SortOrder,
+) -> Result<sorting::SortResult, AppError> {
+    let store = Arc::clone(&store);
+    tokio::task::spawn_blocking(move || {
+        let manual = (key == sorting::SortKey::Manual)
+            .then(|| hq_progress::folder_of(&paths))
+            .flatten()
+            .and_then(|folder| manual_order::load(&app, &folder));
+        sorting::sort_image_paths(&store, paths, key, order, manual.as_deref())
+    })
+    .await?
+    .map_err(AppError::from)
+}
+
+// 폴더의 사용자 지정 순서 저장 (드래그 정렬, 빈 목록이면 삭제)
+#[tauri::command]
+async fn set_manual_order(app: tauri::AppHandle, folder: String, ordered_paths: Vec<String>) -> Result<(), AppError> {
+    tokio::task::spawn_blocking(move || manual_order::save(&app, &folder, ordered_paths))
+        .await?
+        .map_err(AppError::from)
+}
+
+// 폴더의 사용자 지정 순서 (저장된 적 없으면 빈 목록)
+#[tauri::command]
+async fn get_manual_order(app: tauri::AppHandle, folder: String) -> Result<Vec<String>, AppError> {
+    Ok(tokio::task::spawn_blocking(move || manual_order::load(&app, &folder).unwrap_or_default()).await?)
+}
+
+// 빠른 검색 (파일명/키워드/카메라/렌즈/캡션/폴더 접두어 일치, 관련도 순, folder가 있으면 그 안에서만)
+#[tauri::command]
+async fn quick_search(
+    store: State<'_, Arc<MetadataStore>>,
+    text: String,
+    folder: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<String>, AppError> {
+    let store = Arc::clone(&store);
+    tokio::task::spawn_blocking(move || {
+        search_index::quick_search(
+            &store,
+            &text,
+            folder.as_deref(),
+            limit.unwrap_or(search_index::DEFAULT_SEARCH_LIMIT),
+        )
+    })
+    .await?
+    .map_err(AppError::from)
+}
+
+// 타임라인 (인덱싱된 촬영일을 일/월/년 구간별로 집계, 구간별 대표 썸네일 키 포함)
+#[tauri::command]
+async fn get_timeline(
+    store: State<'_, Arc<MetadataStore>>,
+    buckets: timeline::TimelineBuckets,
+    filter: Option<filtering::ImageFilter>,
+) -> Result<Vec<timeline::TimelineBucket>, AppError> {
+    let store = Arc::clone(&store);
+    tokio::task::spawn_blocking(move || timeline::get_timeline(&store, buckets, filter.as_ref()))
+        .await?
+        .map_err(AppError::from)
+}
+
+// 앱 데이터 내보내기 (설정, 라이브러리 DB, 선택 시 썸네일 캐시를 zip 하나로, 기본은 설정+라이브러리)
+#[tauri::command]
+async fn export_app_data(
+    app: tauri::AppHandle,
+    store: State<'_, Arc<MetadataStore>>,
+    target_zip: String,
+    sections: Option<Vec<app_data::DataSection>>,
+) -> Result<app_data::ExportSummary, AppError> {
+    let store = Arc::clone(&store);
+    let sections =
+        sections.unwrap_or_else(|| vec![app_data::DataSection::Settings, app_data::DataSection::Library]);
+
+    tokio::task::spawn_blocking(move || {
+        let job = jobs::Job::start(&app, jobs::JobKind::Backup, "앱 데이터 내보내기", 0);
+        job.finish_with(app_data::export_app_data(&app, &store, Path::new(&target_zip), &sections, &job))
+    })
+    .await?
+    .map_err(AppError::from)
+}
+
+// 백업 파일 정보 (포함 항목, 만든 버전)
+#[tauri::command]
+async fn read_app_data_backup(source_zip: String) -> Result<app_data::BackupManifest, AppError> {
+    tokio::task::spawn_blocking(move || app_data::read_manifest(Path::new(&source_zip)))
+        .await?
+        .map_err(AppError::from)
+}
+
+// 앱 데이터 가져오기 (sections가 없으면 백업의 모든 항목, 다시 시작해야 적용)
+#[tauri::command]
+async fn import_app_data(
+    app: tauri::AppHandle,
+    source_zip: String,
+    sections: Option<Vec<app_data::DataSection>>,
+) -> Result<app_data::ImportSummary, AppError> {
+    tokio::task::spawn_blocking(move || {
+        let job = jobs::Job::start(&app, jobs::JobKind::Backup, "앱 데이터 가져오기", 0);
+        job.finish_with(app_data::import_app_data(&app, Path::new(&source_zip), sections.as_deref(), &job))
+    })
+    .await?
+    .map_err(AppError::from)
+}
+
+// 촬영 통계 (카메라/렌즈별 수, 초점거리/ISO/조리개 분포, 월별 촬영 수)
+#[tauri::command]
+async fn get_capture_statistics(
+    store: State<'_, Arc<MetadataStore>>,
+    scope: capture_stats::StatisticsScope,
+) -> Result<capture_stats::CaptureStatistics, AppError> {
+    let store = Arc::clone(&store);
+    tokio::task::spawn_blocking(move || capture_stats::get_capture_statistics(&store, &scope))
+        .await?
+        .map_err(AppError::from)
+}
+
+// 이미지 경로 필터링 (인덱싱된 메타데이터 기준, 입력 순서 유지)
+#[tauri::command]
+async fn filter_image_paths(
+    store: State<'_, Arc<MetadataStore>>,
+    paths: Vec<String>,
+    filter: filtering::ImageFilter,
+) -> Result<Vec<String>, AppError> {
+    let store = Arc::clone(&store);
+    tokio::task::spawn_blocking(move || filtering::filter_image_paths(&store, paths, &filter))
+        .await?
+        .map_err(AppError::from)
+}
+
+// 이미지 폴더 열기 (정렬/필터된 목록을 백엔드에 보관하고 썸네일 생성 시작)
+// 썸네일 큐와 update_visible_range는 이 목록의 인덱스를 사용
+#[tauri::command]
+async fn open_image_folder(
+    app: tauri::AppHandle,
+    store: State<'_, Arc<MetadataStore>>,
+    index: State<'_, Arc<image_index::SharedImageIndex>>,
+    queue: State<'_, Arc<Mutex<ThumbnailQueueManager>>>,
+    path: String,
+    sort: Option<image_index::FolderSort>,
+    filter: Option<filtering::ImageFilter>,
+) -> Result<image_index::FolderIndexInfo, AppError> {
+    let validated_path = validate_path(&path)?;
+    let folder = fs_path::to_display(&validated_path);
+
+    let store = Arc::clone(&store);
+    let build_folder = folder.clone();
+    let build_app = app.clone();
+    let paths = tokio::task::spawn_blocking(move || {
+        let sort = sort.unwrap_or_default();
+        let manual = (sort.key == sorting::SortKey::Manual)
+            .then(|| manual_order::load(&build_app, &build_folder))
+            .flatten();
+        image_index::build(&store, &build_folder, sort, filter.as_ref(), manual.as_deref())
+    })
+    .await??;
+
+    let info = index
+        .write()
+        .map_err(|_| "Image index lock poisoned".to_string())?
+        .replace(folder, paths.clone());
+
+    thumbnail_warm::emit(&app, paths.clone()).await;
+
+    let queue = queue.lock().await;
+    queue.initialize(paths).await;
+    queue.start_worker().await;
+
+    Ok(info)
+}
+
+// 열린 폴더의 이미지 목록 일부 가져오기
+#[tauri::command]
+fn get_image_page(
+    index: State<'_, Arc<image_index::SharedImageIndex>>,
+    offset: usize,
+    count: usize,
+) -> Result<image_index::ImagePage, AppError> {
+    let index = index
+        .read()
+        .map_err(|_| "Image index lock poisoned".to_string())?;
+    Ok(index.page(offset, count))
+}
+
+// 선택 집합 생성 (대량 선택은 이후 경로 목록 대신 id로 전달)
+#[tauri::command]
+fn create_selection(paths: Vec<String>) -> u64 {
+    selection::create(paths)
+}
+
+// 선택 집합 해제
+#[tauri::command]
+fn release_selection(id: u64) {
+    selection::release(id);
+}
+
+// 선택에 경로 추가 (선택 수 반환)
+#[tauri::command]
+fn selection_add(id: u64, paths: Vec<String>) -> Result<usize, AppError> {
+    selection::add(id, paths).map_err(AppError::from)
+}
+
+// 선택에서 경로 제외 (선택 수 반환)
+#[tauri::command]
+fn selection_remove(id: u64, paths: Vec<String>) -> Result<usize, AppError> {
+    selection::remove(id, &paths).map_err(AppError::from)
+}
+
+// 열린 폴더 목록의 start..=end 범위를 선택에 추가 (선택 수 반환)
+#[tauri::command]
+fn selection_add_range(
+    index: State<'_, Arc<image_index::SharedImageIndex>>,
+    id: u64,
+    start: usize,
+    end: usize,
+) -> Result<usize, AppError> {
+    let index = index
+        .read()
+        .map_err(|_| "Image index lock poisoned".to_string())?;
+    selection::add_range(id, index.paths(), start, end).map_err(|message| AppError::InvalidInput { message })
+}
+
+// 열린 폴더 목록 기준으로 선택 반전 (선택 수 반환)
+#[tauri::command]
+fn selection_invert(
+    index: State<'_, Arc<image_index::SharedImageIndex>>,
+    id: u64,
+) -> Result<usize, AppError> {
+    let index = index
+        .read()
+        .map_err(|_| "Image index lock poisoned".to_string())?;
+    selection::invert(id, index.paths()).map_err(AppError::from)
+}
+
+// 선택된 경로 (선택한 순서)
+#[tauri::command]
+fn get_selection_paths(id: u64) -> Result<Vec<String>, AppError> {
+    selection::paths(id).map_err(AppError::from)
+}
+
+// 선택 통계 (수, 전체 크기, 별점별 수)
+#[tauri::command]
+async fn selection_stats(
+    store: State<'_, Arc<MetadataStore>>,
+    id: u64,
+) -> Result<selection::SelectionStats, AppError> {
+    let store = Arc::clone(&store);
+    tokio::task::spawn_blocking(move || selection::stats(&store, id))
+        .await?
+        .map_err(AppError::from)
+}
+
+// 이전 실행에서 종료 시 남은 썸네일 작업 가져오기 (한 번만 반환)
+#[tauri::command]
+fn take_queue_resume_state(
+    app: tauri::AppHandle,
+) -> Result<Option<shutdown::QueueResumeState>, AppError> {
+    shutdown::take_resume_state(&app).map_err(AppError::from)
+}
+
+// 폴더의 HQ 썸네일 생성 진행 상태 (중단된 작업은 다음 시작 시 이어서 처리)
+#[tauri::command]
+fn get_hq_generation_status(
+    app: tauri::AppHandle,
+    folder: String,
+) -> Result<Option<hq_progress::HqGenerationStatus>, AppError> {
+    let running_token = thumbnail_queue::current_hq_token();
+    Ok(hq_progress::status(&app, &folder, running_token.as_deref()))
+}
+
+// 이미지 한 장을 보는 보조 뷰어 윈도우 열기 (듀얼 모니터 컬링용)
+#[tauri::command]
+fn open_viewer_window(app: tauri::AppHandle, path: String) -> Result<String, AppError> {
+    windows::open_window(&app, windows::WindowKind::Viewer, vec![path]).map_err(AppError::from)
+}
+
+// 여러 이미지를 나란히 비교하는 보조 윈도우 열기
+#[tauri::command]
+fn open_compare_window(app: tauri::AppHandle, paths: Vec<String>) -> Result<String, AppError> {
+    if paths.len() < 2 {
+        return Err(AppError::InvalidInput {
+            message: "Compare window needs at least two images".to_string(),
+        });
+    }
+    windows::open_window(&app, windows::WindowKind::Compare, paths).map_err(AppError::from)
+}
+
+// 보조 윈도우가 표시할 내용 (메인 윈도우는 None)
+#[tauri::command]
+fn get_window_payload(window: tauri::Window) -> Option<windows::WindowPayload> {
+    windows::payload(window.label())
+}
+
+// 전역 단축키 설정 (저장 후 등록, 등록하지 못한 항목과 이유를 반환)
+#[tauri::command]
+async fn set_global_shortcuts(
+    app: tauri::AppHandle,
+    bindings: Vec<shortcuts::ShortcutBinding>,
+) -> Result<shortcuts::ShortcutRegistration, AppError> {
+    let patch = serde_json::json!({ "global_shortcuts": &bindings });
+    let settings_app = app.clone();
+    tokio::task::spawn_blocking(move || settings::update(&settings_app, patch)).await??;
+    Ok(shortcuts::apply(&app, &bindings))
+}
+
+// 프론트엔드가 open-target 리스너 등록을 마침 (실행 인자로 받은 폴더/이미지가 있으면 이때 전송)
+#[tauri::command]
+fn frontend_ready(app: tauri::AppHandle) {
+    open_target::frontend_ready(&app);
+}
+
+// 핫 폴더 모드 시작 (테더링 프로그램이 넣는 새 파일을 바로 썸네일 생성, 선택적으로 자동 선택/백업)
+#[tauri::command]
+async fn enable_hot_folder(
+    app: tauri::AppHandle,
+    hot_folder: State<'_, Arc<Mutex<hot_folder::HotFolder>>>,
+    path: String,
+    options: Option<hot_folder::HotFolderOptions>,
+) -> Result<(), AppError> {
+    let mut hot_folder = hot_folder.lock().await;
+    hot_folder.enable(app, &path, options.unwrap_or_default())?;
+    Ok(())
+}
+
+// 핫 폴더 모드 중지
+#[tauri::command]
+async fn disable_hot_folder(
+    hot_folder: State<'_, Arc<Mutex<hot_folder::HotFolder>>>,
+) -> Result<(), AppError> {
+    hot_folder.lock().await.disable();
+    Ok(())
+}
+
+// 원격 소스 추가 (FTP/SFTP, 연결 확인 후 등록, 경로는 remote://<id>/...)
+#[tauri::command]
+async fn add_remote_source(
+    app: tauri::AppHandle,
+    config: remote_source::RemoteSourceConfig,
+) -> Result<Vec<remote_source::RemoteSourceConfig>, AppError> {
+    tokio::task::spawn_blocking(move || remote_source::add_source(&app, config)).await??;
+    Ok(remote_source::list_sources())
+}
+
+// 원격 소스 삭제
+#[tauri::command]
+fn remove_remote_source(
+    app: tauri::AppHandle,
+    id: String,
+) -> Result<Vec<remote_source::RemoteSourceConfig>, AppError> {
+    remote_source::remove_source(&app, &id)?;
+    Ok(remote_source::list_sources())
+}
+
+// 원격 소스 목록 (비밀번호 제외)
+#[tauri::command]
+fn list_remote_sources() -> Vec<remote_source::RemoteSourceConfig> {
+    remote_source::list_sources()
+}
+
+// 원격 파일을 로컬 스풀로 받기 (뷰어에서 원본 표시용, 로컬 경로 반환)
+#[tauri::command]
+async fn spool_remote_file(app: tauri::AppHandle, path: String) -> Result<String, AppError> {
+    let spooled = tokio::task::spawn_blocking(move || remote_source::spool(&app, &path)).await??;
+    Ok(spooled.to_string_lossy().to_string())
+}
+
+// 압축 파일 항목을 로컬 스풀로 꺼내기 (뷰어에서 원본 표시용, 로컬 경로 반환)
+#[tauri::command]
+async fn spool_archive_entry(app: tauri::AppHandle, path: String) -> Result<String, AppError> {
+    let spooled = tokio::task::spawn_blocking(move || archive_source::spool(&app, &path)).await??;
+    Ok(spooled.to_string_lossy().to_string())
+}
+
+// 이미지를 바탕화면으로 설정 (mode: fill/fit/center, 기본 fill)
+#[tauri::command]
+async fn set_as_wallpaper(
+    app: tauri::AppHandle,
+    path: String,
+    mode: Option<wallpaper::WallpaperMode>,
+) -> Result<(), AppError> {
+    tokio::task::spawn_blocking(move || wallpaper::set_as_wallpaper(&app, &path, mode.unwrap_or_default()))
+        .await?
+        .map_err(AppError::from)
+}
+
+// 선택한 파일을 OS 공유 UI로 보내기 (Windows 공유, macOS 공유 메뉴/AirDrop)
+#[tauri::command]
+async fn share_files(app: tauri::AppHandle, window: tauri::WebviewWindow, paths: Vec<String>) -> Result<(), AppError> {
+    tokio::task::spawn_blocking(move || share::share_files(&app, &window, &paths))
+        .await?
+        .map_err(AppError::from)
+}
+
+// 메인 윈도우가 있는 모니터의 ICC 프로파일 정보 (소프트 프루핑 적용 여부 포함)
+#[tauri::command]
+fn get_monitor_profile() -> monitor_profile::MonitorProfileInfo {
+    monitor_profile::current()
+}
+
+// 업로드 프로필 저장 (비밀 키는 키체인에 보관)
+#[tauri::command]
+fn save_upload_profile(
+    app: tauri::AppHandle,
+    profile: upload::UploadProfile,
+) -> Result<Vec<upload::UploadProfile>, AppError> {
+    upload::validate_profile(&profile).map_err(|message| AppError::InvalidInput { message })?;
+    upload::save_profile(&app, profile)?;
+    Ok(upload::list_profiles(&app))
+}
+
+// 업로드 프로필 삭제
+#[tauri::command]
+fn remove_upload_profile(app: tauri::AppHandle, id: String) -> Result<Vec<upload::UploadProfile>, AppError> {
+    upload::remove_profile(&app, &id)?;
+    Ok(upload::list_profiles(&app))
+}
+
+// 업로드 프로필 목록 (비밀 키 제외)
+#[tauri::command]
+fn list_upload_profiles(app: tauri::AppHandle) -> Vec<upload::UploadProfile> {
+    upload::list_profiles(&app)
+}
+
+// S3 호환 스토리지로 이미지 업로드 (프로필의 크기 조정/포맷 변환 적용)
+#[tauri::command]
+async fn upload_images(
+    app: tauri::AppHandle,
+    paths: Vec<String>,
+    target_profile: String,
+) -> Result<upload::UploadSummary, AppError> {
+    Ok(tokio::task::spawn_blocking(move || {
+        let job = jobs::Job::start(&app, jobs::JobKind::Upload, "업로드", paths.len());
+        job.finish_with(upload::upload_images(&app, &paths, &target_profile, &job))
+    })
+    .await??)
+}
+
+// 진행 중인 업로드 취소
+#[tauri::command]
+fn cancel_upload() {
+    upload::cancel();
+}
+
+// 백그라운드 작업 목록 (실행 중인 작업과 최근 끝난 작업)
+#[tauri::command]
+fn list_jobs() -> Vec<jobs::JobInfo> {
+    jobs::list()
+}
+
+// 백그라운드 작업 취소
+#[tauri::command]
+fn cancel_job(id: u64) -> Result<(), AppError> {
+    jobs::cancel(id).map_err(AppError::from)
+}
+
+// 공유용 빠른 내보내기 (임시 폴더에 저장, copy_to_clipboard면 결과 파일을 클립보드에 복사)
+// selection_id가 있으면 paths 대신 선택 집합의 경로 사용
+#[tauri::command]
+async fn quick_export(
+    app: tauri::AppHandle,
+    paths: Option<Vec<String>>,
+    selection_id: Option<u64>,
+    preset: export::QuickExportPreset,
+    copy_to_clipboard: Option<bool>,
+) -> Result<Vec<String>, AppError> {
+    let paths = match selection_id {
+        Some(id) => selection::paths(id)?,
+        None => paths.unwrap_or_default(),
+    };
+    tokio::task::spawn_blocking(move || {
+        let job = jobs::Job::start(&app, jobs::JobKind::Export, "빠른 내보내기", paths.len());
+        let exported: Vec<String> = job.finish_with(export::quick_export(&paths, preset, &job))?
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        if copy_to_clipboard.unwrap_or(false) {
+            clipboard::copy_files_to_clipboard(exported.clone(), false)?;
+        }
+        Ok(exported)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
+// 개인정보 메타데이터 제거 (GPS, 시리얼 번호, 소유자, XMP 편집 기록)
+// 기본은 사본 저장, 원본 수정(in_place)은 confirm_in_place가 있어야 실행
+#[tauri::command]
+async fn strip_metadata(
+    paths: Vec<String>,
+    options: Option<metadata_strip::StripOptions>,
+    confirm_in_place: Option<bool>,
+) -> Result<metadata_strip::StripSummary, AppError> {
+    let options = options.unwrap_or_default();
+    if options.in_place && !confirm_in_place.unwrap_or(false) {
+        return Err(AppError::InvalidInput {
+            message: "In-place metadata stripping requires confirmation".to_string(),
+        });
+    }
+
+    tokio::task::spawn_blocking(move || metadata_strip::strip_metadata(&paths, &options))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+        .map_err(AppError::from)
+}
+
+// Lightroom 카탈로그/Capture One 세션에서 별점, 픽, 색상 라벨 가져오기
+#[tauri::command]
+async fn import_catalog_ratings(
+    app: tauri::AppHandle,
+    store: State<'_, Arc<MetadataStore>>,
+    watcher: State<'_, Arc<Mutex<FolderWatcher>>>,
+    catalog_path: String,
+    options: Option<catalog_import::CatalogImportOptions>,
+) -> Result<catalog_import::CatalogImportSummary, AppError> {
+    let store = Arc::clone(&store);
+    let options = options.unwrap_or_default();
+
+    let summary = tokio::task::spawn_blocking(move || {
+        catalog_import::import_catalog(&store, Path::new(&catalog_path), &options)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))??;
+
+    // XMP에 쓴 별점은 폴더 감시의 외부 변경 이벤트로 다시 알리지 않음
+    let watcher = watcher.lock().await;
+    for entry in &summary.applied {
+        watcher.remember_rating(&entry.path, entry.rating);
+    }
+    drop(watcher);
+
+    let _ = app.emit("catalog-import-completed", &summary);
+    Ok(summary)
+}
+
+// 이미지 키워드 읽기 (사이드카 우선, 계층은 "|"로 구분)
+#[tauri::command]
+async fn get_image_keywords(file_path: String) -> Result<Vec<String>, AppError> {
+    Ok(tokio::task::spawn_blocking(move || keywords::read_keywords(Path::new(&file_path))).await??)
+}
+
+// 이미지 키워드 쓰기 (설정의 keyword_dialect 방식: 표준/darktable/digiKam)
+#[tauri::command]
+async fn write_image_keywords(file_path: String, keywords: Vec<String>) -> Result<(), AppError> {
+    let dialect = settings::current().keyword_dialect;
+    Ok(tokio::task::spawn_blocking(move || {
+        keywords::write_keywords(Path::new(&file_path), &keywords, dialect)
+    })
+    .await??)
+}
+
+// 사이드카 XMP의 별점/색상 라벨/키워드 읽기 (darktable/digiKam/Adobe)
+#[tauri::command]
+async fn read_sidecar_metadata(file_path: String) -> Result<Option<keywords::SidecarMetadata>, AppError> {
+    Ok(tokio::task::spawn_blocking(move || keywords::read_sidecar(Path::new(&file_path))).await??)
+}
+
+// IPTC 캡션/제목/헤드라인/작성자/크레딧/위치 읽기
+#[tauri::command]
+async fn get_iptc_metadata(file_path: String) -> Result<iptc::IptcMetadata, AppError> {
+    Ok(tokio::task::spawn_blocking(move || iptc::read_iptc(&file_path)).await??)
+}
+
+// IPTC 항목을 선택한 이미지 모두에 쓰기 (None은 유지, 빈 값은 삭제, 템플릿 토큰은 파일별 치환)
+#[tauri::command]
+async fn write_iptc_metadata(
+    paths: Vec<String>,
+    metadata: iptc::IptcMetadata,
+) -> Result<Vec<iptc::IptcFailure>, AppError> {
+    if paths.is_empty() {
+        return Err(AppError::InvalidInput {
+            message: "No images selected".to_string(),
+        });
+    }
+    Ok(tokio::task::spawn_blocking(move || iptc::write_iptc(&paths, &metadata)).await?)
+}
+
+// 여러 이미지의 촬영 시간을 같은 만큼 이동 (카메라 시계/시간대 오류 보정)
+#[tauri::command]
+async fn shift_capture_time(
+    store: State<'_, Arc<MetadataStore>>,
+    paths: Vec<String>,
+    offset_seconds: i64,
+    preserve_mtime: Option<bool>,
+) -> Result<capture_time::CaptureTimeSummary, AppError> {
+    if offset_seconds == 0 {
+        return Err(AppError::InvalidInput {
+            message: "Time offset must not be zero".to_string(),
+        });
+    }
+
+    let preserve_mtime = preserve_mtime.unwrap_or(true);
+    let summary = tokio::task::spawn_blocking(move || {
+        capture_time::shift_capture_time(&paths, offset_seconds, preserve_mtime)
+    })
+    .await?;
+
+    // 파일은 이미 바뀌었으므로 인덱스 갱신 실패는 기록만 (다시 스캔하면 XMP/EXIF에서 읽음)
+    for changed in &summary.updated {
+        if let Err(e) = store.update_date_taken(&changed.path, &changed.after) {
+            tracing::warn!("Failed to update indexed capture time for {}: {}", changed.path, e);
+        }
+    }
+    Ok(summary)
+}
+
+// 이미지 촬영 시간 지정 ("YYYY-MM-DD HH:MM:SS")
+#[tauri::command]
+async fn set_capture_time(
+    store: State<'_, Arc<MetadataStore>>,
+    path: String,
+    datetime: String,
+    preserve_mtime: Option<bool>,
+) -> Result<capture_time::CaptureTimeChange, AppError> {
+    let Some(value) = capture_time::parse_datetime(&datetime) else {
+        return Err(AppError::InvalidInput {
+            message: format!("Invalid date/time: {}", datetime),
+        });
+    };
+
+    let change = capture_time::TimeChange::Set(value);
+    let preserve_mtime = preserve_mtime.unwrap_or(true);
+    let changed = tokio::task::spawn_blocking(move || {
+        capture_time::change_capture_time(&path, change, preserve_mtime)
+    })
+    .await??;
+
+    if let Err(e) = store.update_date_taken(&changed.path, &changed.after) {
+        tracing::warn!("Failed to update indexed capture time for {}: {}", changed.path, e);
+    }
+    Ok(changed)
+}
+
+// GPX 트랙으로 지오태깅 (dry_run이면 매칭 결과 미리보기만)
+#[tauri::command]
+async fn geotag_from_gpx(
+    paths: Vec<String>,
+    gpx_path: String,
+    options: Option<geotag::GeotagOptions>,
+) -> Result<geotag::GeotagSummary, AppError> {
+    let options = options.unwrap_or_default();
+    tokio::task::spawn_blocking(move || geotag::geotag_from_gpx(&paths, Path::new(&gpx_path), &options))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+        .map_err(AppError::from)
+}
+
+// GPS 위치 (도 단위, 남/서는 음수)
+#[derive(Serialize)]
+struct GpsPosition {
+    latitude: f64,
+    longitude: f64,
+    altitude: Option<f64>,
+}
+
+// 이미지 상세 정보 (메타데이터 패널용 통합 응답)
+#[derive(Serialize)]
+struct ImageDetails {
+    path: String,
+    width: Option<u32>,
+    height: Option<u32>,
+    file_size: u64,
+    modified_time: Option<String>,
+    date_taken: Option<String>,
+    exif: Option<ExifMetadata>,
+    rating: i32,
+    color_label: Option<String>,
+    keywords: Vec<String>,
+    gps: Option<GpsPosition>,
+}
+
+// 이미지 상세 정보 한 번에 가져오기 (파일은 한 번만 읽고 EXIF/XMP/크기를 모두 추출)
+#[tauri::command]
+async fn get_image_details(path: String) -> Result<ImageDetails, AppError> {
+    tokio::task::spawn_blocking(move || read_image_details(path))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+}
+
+fn read_image_details(path: String) -> Result<ImageDetails, AppError> {
+    use std::io::BufReader;
+
+    // 파일 전체를 읽지 않고 필요한 부분만 읽음 (EXIF 컨테이너, 이미지 헤더, XMP 패킷)
+    let fs_path = fs_path::to_fs_path(&path);
+    let file_metadata = fs::metadata(&fs_path).map_err(|e| format!("Failed to get file metadata: {}", e))?;
+    let open = || fs::File::open(&fs_path).map(BufReader::new);
+
+    let exif_data = open()
+        .ok()
+        .and_then(|mut reader| exif::Reader::new().read_from_container(&mut reader).ok());
+    let xmp = rating::read_xmp(&fs_path.to_string_lossy());
+    let sidecar = keywords::read_sidecar(&fs_path).ok().flatten();
+
+    // 크기: 헤더만 읽고, 디코더가 없는 형식(RAW 등)은 EXIF 크기
+    let exif_uint = |tag: exif::Tag| {
+        exif_data
+            .as_ref()
+            .and_then(|exif| exif.get_field(tag, exif::In::PRIMARY))
+            .and_then(|field| field.value.get_uint(0))
+    };
+    let dimensions = open()
+        .ok()
+        .and_then(|reader| image::ImageReader::new(reader).with_guessed_format().ok())
+        .and_then(|reader| reader.into_dimensions().ok());
+    let (width, height) = match dimensions {
+        Some((width, height)) => (Some(width), Some(height)),
+        None => (exif_uint(exif::Tag::PixelXDimension), exif_uint(exif::Tag::PixelYDimension)),
+    };
+
+    let exif = exif_data.as_ref().map(|exif| build_exif_metadata(exif, Some(&file_metadata)));
+    let date_taken = exif
+        .as_ref()
+        .and_then(|exif| exif.date_time_original.clone())
+        .or_else(|| {
+            exif_data
+                .as_ref()
+                .and_then(|exif| exif.get_field(exif::Tag::DateTime, exif::In::PRIMARY))
+                .and_then(|field| metadata_store::normalize_exif_datetime(&field.display_value().to_string()))
+        });
+
+    // 별점/라벨: 내장 XMP 우선, 없으면 사이드카
+    let xmp_value = |name: &str| {
+        xmp.as_ref()
+            .and_then(|xmp| xmp.property(xmp_toolkit::xmp_ns::XMP, name))
+            .map(|value| value.value)
+            .filter(|value| !value.is_empty())
+    };
+    let rating = xmp_value("Rating")
+        .and_then(|value| value.parse::<i32>().ok())
+        .or_else(|| sidecar.as_ref().and_then(|sidecar| sidecar.rating))
+        .unwrap_or(0);
+    let color_label = xmp_value("Label")
+        .or_else(|| sidecar.as_ref().and_then(|sidecar| sidecar.color_labels.first().cloned()));
+
+    // 키워드: 사이드카 우선
+    let keywords = match &sidecar {
+        Some(sidecar) => sidecar.keywords.clone(),
+        None => xmp.as_ref().map(keywords::keywords_from_xmp).unwrap_or_default(),
+    };
+
+    let gps = exif_data
+        .as_ref()
+        .and_then(exif_gps_position)
+        .or_else(|| xmp.as_ref().and_then(xmp_gps_position));
+
+    Ok(ImageDetails {
+        path,
+        width,
+        height,
+        file_size: file_metadata.len(),
+        modified_time: exif.as_ref().and_then(|exif| exif.modified_time.clone()),
+        date_taken,
+        exif,
+        rating,
+        color_label,
+        keywords,
+        gps,
+    })
+}
+
+// EXIF GPS → 도 단위 위치
+fn exif_gps_position(exif_data: &exif::Exif) -> Option<GpsPosition> {
+    let coordinate = |tag: exif::Tag, ref_tag: exif::Tag, negative: &str| -> Option<f64> {
+        let exif::Value::Rational(ref dms) = exif_data.get_field(tag, exif::In::PRIMARY)?.value else {
+            return None;
+        };
+        let [degrees, minutes, seconds] = dms.get(..3)? else {
+            return None;
+        };
+        let value = degrees.to_f64() + minutes.to_f64() / 60.0 + seconds.to_f64() / 3600.0;
+        let reference = exif_data.get_field(ref_tag, exif::In::PRIMARY)?.display_value().to_string();
+        Some(if reference.contains(negative) { -value } else { value })
+    };
+
+    let altitude = exif_data
+        .get_field(exif::Tag::GPSAltitude, exif::In::PRIMARY)
+        .and_then(|field| match field.value {
+            exif::Value::Rational(ref altitude) => altitude.first().map(|r| r.to_f64()),
+            _ => None,
+        });
+    let below_sea_level = exif_data
+        .get_field(exif::Tag::GPSAltitudeRef, exif::In::PRIMARY)
+        .and_then(|field| field.value.get_uint(0))
+        == Some(1);
+
+    Some(GpsPosition {
+        latitude: coordinate(exif::Tag::GPSLatitude, exif::Tag::GPSLatitudeRef, "S")?,
+        longitude: coordinate(exif::Tag::GPSLongitude, exif::Tag::GPSLongitudeRef, "W")?,
+        altitude: altitude.map(|altitude| if below_sea_level { -altitude } else { altitude }),
+    })
+}
+
+// XMP GPS (RAW 지오태깅 결과 등) → 도 단위 위치
+fn xmp_gps_position(xmp: &xmp_toolkit::XmpMeta) -> Option<GpsPosition> {
+    let value = |name: &str| xmp.property(xmp_toolkit::xmp_ns::EXIF, name).map(|value| value.value);
+    let altitude = value("GPSAltitude").and_then(|altitude| {
+        let (num, denom) = altitude.split_once('/').unwrap_or((&altitude, "1"));
+        let altitude = num.parse::<f64>().ok()? / denom.parse::<f64>().ok()?;
+        Some(if value("GPSAltitudeRef").as_deref() == Some("1") { -altitude } else { altitude })
+    });
+
+    Some(GpsPosition {
+        latitude: geotag::parse_xmp_coordinate(&value("GPSLatitude")?)?,
+        longitude: geotag::parse_xmp_coordinate(&value("GPSLongitude")?)?,
+        altitude,
+    })
+}
+
+// LQ/HQ/뷰어 공용 동시 작업 예산 변경 (None은 유지), 적용된 예산 반환
+#[tauri::command]
+fn set_worker_budget(cpu: Option<usize>, io: Option<usize>) -> scheduler::WorkerBudget {
+    let current = scheduler::budget();
+    scheduler::set_budget(scheduler::WorkerBudget {
+        cpu: cpu.unwrap_or(current.cpu),
+        io: io.unwrap_or(current.io),
+    })
+}
+
+// 노출/대비/화이트밸런스/흑백 조정을 적용한 미리보기 (원본은 그대로)
+#[tauri::command]
+async fn render_preview(
+    file_path: String,
+    adjustments: preview_filter::PreviewAdjustments,
+) -> Result<preview_filter::PreviewImage, AppError> {
+    adjustments
+        .validate()
+        .map_err(|message| AppError::InvalidInput { message })?;
+
+    let _permit = scheduler::acquire(scheduler::WorkClass::Viewer).await;
+    tokio::task::spawn_blocking(move || {
+        preview_filter::render_preview(&fs_path::to_fs_string(&file_path), &adjustments)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+    .map_err(AppError::from)
+}
+
+// 워터마크 적용 샘플 썸네일 (내보내기 설정 미리보기, 원본은 그대로)
+#[tauri::command]
+async fn preview_watermark(
+    path: String,
+    watermark: watermark::WatermarkOptions,
+    max_size: Option<u32>,
+) -> Result<preview_filter::PreviewImage, AppError> {
+    watermark
+        .validate()
+        .map_err(|message| AppError::InvalidInput { message })?;
+    if let Some(max_size) = max_size.filter(|size| !(64..=2048).contains(size)) {
+        return Err(AppError::InvalidInput {
+            message: format!("Invalid max_size: {} (64-2048)", max_size),
+        });
+    }
+
+    let _permit = scheduler::acquire(scheduler::WorkClass::Viewer).await;
+    tokio::task::spawn_blocking(move || watermark::render_preview(Path::new(&path), &watermark, max_size))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+        .map_err(AppError::from)
+}
+
+// 큰 이미지(파노라마 등) 타일 피라미드 정보, 처음 요청하면 타일 생성
+#[tauri::command]
+async fn get_image_pyramid(app: tauri::AppHandle, path: String) -> Result<image_tiles::PyramidInfo, AppError> {
+    let _permit = scheduler::acquire(scheduler::WorkClass::Viewer).await;
+    Ok(tokio::task::spawn_blocking(move || image_tiles::ensure_pyramid(&app, &path).map(|(info, _)| info)).await??)
+}
+
+// 타일 하나 (레벨 0은 1x1, max_level이 원본 크기)
+#[tauri::command]
+async fn get_image_tile(
+    app: tauri::AppHandle,
+    path: String,
+    level: u32,
+    x: u32,
+    y: u32,
+) -> Result<image_tiles::ImageTile, AppError> {
+    let _permit = scheduler::acquire(scheduler::WorkClass::Viewer).await;
+    Ok(tokio::task::spawn_blocking(move || image_tiles::get_tile(&app, &path, level, x, y)).await??)
+}
+
+// 확대 보기용 영역 디코딩 (rect는 방향 적용 후 원본 픽셀, scale 1.0이면 100%)
+#[tauri::command]
+async fn get_image_region(
+    path: String,
+    rect: transform::CropRect,
+    scale: Option<f32>,
+) -> Result<image_region::ImageRegion, AppError> {
+    let scale = scale.unwrap_or(1.0);
+    if !scale.is_finite() || scale <= 0.0 || scale > 1.0 {
+        return Err(AppError::InvalidInput {
+            message: format!("Invalid scale: {} (0-1)", scale),
+        });
+    }
+
+    let _permit = scheduler::acquire(scheduler::WorkClass::Viewer).await;
+    Ok(tokio::task::spawn_blocking(move || image_region::get_region(&path, rect, scale)).await??)
+}
+
+// 무손실 자르기 정렬 단위 (JPEG MCU 크기, 불가능하면 null)
+#[tauri::command]
+async fn get_lossless_crop_alignment(path: String) -> Result<Option<(u32, u32)>, AppError> {
+    Ok(tokio::task::spawn_blocking(move || transform::lossless_alignment(&path)).await?)
+}
+
+// 이미지 자르기/수평 보정 (JPEG은 가능하면 무손실), 저장 후 캐시 갱신
+#[tauri::command]
+async fn crop_image(
+    app: tauri::AppHandle,
+    store: State<'_, Arc<MetadataStore>>,
+    path: String,
+    rect: transform::CropRect,
+    angle: Option<f32>,
+    output: String,
+    options: Option<transform::CropOptions>,
+) -> Result<transform::CropResult, AppError> {
+    let angle = angle.unwrap_or(0.0);
+    let options = options.unwrap_or_default();
+    let store = store.inner().clone();
+    let handle = app.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let result = transform::crop_image(&path, rect, angle, &output, &options)?;
+
+        // 저장한 파일의 이전 썸네일 캐시(단계별, 폴더별 포함) 삭제, 인덱스 갱신 실패는 기록만
+        thumbnail_cache::invalidate(&handle, &result.output);
+        if let Err(e) = store.index_files(std::slice::from_ref(&result.output)) {
+            tracing::warn!("Failed to index cropped image {}: {}", result.output, e);
+        }
+        Ok::<_, String>(result)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))??;
+
+    let _ = app.emit("image-modified", &result);
+    Ok(result)
+}
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    tauri::Builder::default()
+        // 두 번째 실행은 기존 인스턴스로 인자를 넘기고 종료 (캐시 동시 사용 방지, 가장 먼저 등록)
+        .plugin(tauri_plugin_single_instance::init(single_instance::handle_second_instance))
+        .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_fs::init())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(shortcuts::handle_shortcut)
+                .build(),
+        )
+        .on_window_event(|window, event| {
+            tray::handle_window_event(window, event);
+            monitor_profile::handle_window_event(window, event);
+        })
+        .setup(|app| {
+            // 로깅 초기화 (실패해도 앱은 계속 실행)
+            if let Err(e) = logging::init(app.handle()) {
+                eprintln!("{}", e);
+            }
+
+            // 설정 로드 (실패 시 기본값)
+            if let Err(e) = settings::load(app.handle()) {
+                tracing::warn!("Failed to load settings: {}", e);
+            }
+
+            // 탐색기/Finder에서 파일·폴더로 실행한 경우 (프론트엔드 준비 후 open-target 전송)
+            if let Some(target) = single_instance::launch_target() {
+                open_target::dispatch(app.handle(), target);
+            }
+
+            // 저장된 전역 단축키 등록 (실패한 항목은 로그만 남김)
+            shortcuts::apply(app.handle(), &settings::current().global_shortcuts);
+
+            let window = app.get_webview_window("main")
+                .ok_or("Failed to get main window")?;
+
+            // 윈도우 핸들을 idle_detector에 설정 (Windows만)
+            #[cfg(target_os = "windows")]
+            {
+                use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+                if let Ok(handle) = window.window_handle() {
+                    if let RawWindowHandle::Win32(win32_handle) = handle.as_raw() {
+                        idle_detector::set_app_window_handle(win32_handle.hwnd.get());
+                    }
+                }
+            }
+
+            // 저장된 윈도우 상태 복원
+            restore_window_state(&window);
+
+            // 윈도우가 있는 모니터의 ICC 프로파일 (소프트 프루핑용)
+            monitor_profile::refresh(&window.as_ref().window());
+
+            // 썸네일 큐 매니저 초기화
+            let queue_manager = ThumbnailQueueManager::new(app.handle().clone());
+            app.manage(Arc::new(Mutex::new(queue_manager)));
+
+            // 트레이 아이콘 (백그라운드 작업 일시정지/재개, 진행률 툴팁)
+            if let Err(e) = tray::init(app.handle()) {
+                tracing::warn!("{}", e);
+            }
+
+            // 열린 폴더의 정렬/필터된 이미지 목록
+            app.manage(Arc::new(image_index::SharedImageIndex::default()));
+
+            // 테더링 촬영 핫 폴더 (사용할 때만 감시)
+            app.manage(Arc::new(Mutex::new(hot_folder::HotFolder::new())));
+
+            // 폴더 패널 구조 감시 (펼쳐진 폴더의 하위 폴더 변경)
+            app.manage(Arc::new(Mutex::new(folder_tree_watcher::FolderTreeWatcher::new())));
+
+            // 폴더 감시자 초기화
+            let folder_watcher = FolderWatcher::new();
+            app.manage(Arc::new(Mutex::new(folder_watcher)));
+
+            // 메타데이터 저장소 초기화 (스마트 앨범 라이브 업데이트 구독)
+            let db_path = metadata_store::get_db_path(app.handle())?;
+            app_data::apply_pending_restore(&db_path);
+            let store = Arc::new(MetadataStore::open(&db_path)?);
+            smart_album::init_schema(&store)?;
+            import::init_schema(&store)?;
+            thumbnail_cache::init_schema(&store)?;
+            review_notes::init_schema(&store)?;
+            versions::init_schema(&store)?;
+            search_index::init_schema(&store)?;
+            pending_ratings::init_schema(&store)?;
+            smart_album::spawn_live_updates(app.handle().clone(), Arc::clone(&store));
+            search_index::spawn_updates(Arc::clone(&store));
+            pending_ratings::spawn_flush(app.handle().clone(), Arc::clone(&store));
+            app.manage(store);
+
+            // 이동식 드라이브 연결/해제 감시 (폴더 트리 자동 갱신)
+            drive_watcher::spawn(app.handle().clone(), get_drives);
+
+            // 이전 실행에서 비정상 종료로 남은 임시 캐시 파일 정리
+            if let Ok(cache_dir) = thumbnail::get_cache_dir(app.handle()) {
+                shutdown::remove_partial_writes(&cache_dir);
+            }
+            remote_source::clean_partial_spool(app.handle());
+            archive_source::clean_spool(app.handle());
+
+            // 저장된 원격 소스 (FTP/SFTP) 등록
+            remote_source::load_sources(app.handle());
+
+            Ok(())
+        })
+        .plugin(tauri_plugin_store::Builder::new().build())
+        .invoke_handler(tauri::generate_handler![
+            save_window_state,
+            show_window,
+            save_layout_state,
+            load_layout_state,
+            save_dockview_layout,
+            load_dockview_layout,
+            get_drives,
+            get_drive_details,
+            get_free_space,
+            has_subdirectories,
+            get_picture_folder,
+            get_desktop_folder,
+            get_documents_folder,
+            read_directory_contents,
+            calculate_images_total_size,
+            generate_thumbnail_for_image,
+            generate_tier_thumbnail,
+            get_fast_placeholder,
+            extract_raw_preview_image,
+            start_thumbnail_generation,
+            update_visible_range,
+            pause_thumbnail_generation,
+            resume_thumbnail_generation,
+            get_completed_thumbnails,
             classify_hq_thumbnails,
             load_existing_hq_thumbnails,
             start_hq_thumbnail_generation,
             cancel_hq_thumbnail_generation,
+            get_hq_results_since,
             update_hq_viewport_paths,
             get_image_info,
             get_exif_metadata,
             get_images_light_metadata,
             read_image_rating,
             read_image_ratings_batch,
+            get_ratings_batch,
             write_image_rating,
             create_folder,
             rename_folder,
             rename_file,
+            preview_batch_rename,
+            batch_rename,
+            undo_batch_rename,
+            preview_delete,
             delete_folder,
+            copy_folder,
+            move_folder,
             delete_files,
             copy_files_to_clipboard,
             paste_files_from_clipboard,
             start_folder_watch,
-            stop_folder_watch
+            stop_folder_watch,
+            snapshot_folder,
+            diff_folder,
+            watch_folder_tree,
+            stop_folder_tree_watch,
+            index_images,
+            create_smart_album,
+            update_smart_album,
+            delete_smart_album,
+            list_smart_albums,
+            evaluate_smart_album,
+            add_review_note,
+            update_review_note,
+            delete_review_note,
+            get_review_notes,
+            export_review_notes,
+            get_versions,
+            link_version,
+            add_favorite_folder,
+            remove_favorite_folder,
+            list_favorite_folders,
+            rename_favorite_folder,
+            reorder_favorite_folders,
+            get_recent_folders,
+            clear_recent_folders,
+            import_from_device,
+            compute_checksums,
+            verify_checksums,
+            get_thumbnail_cache_stats,
+            clean_thumbnail_cache,
+            relocate_thumbnail_cache,
+            enable_local_cache,
+            find_local_caches,
+            clean_local_caches,
+            get_pending_ratings,
+            get_rating_sources,
+            flush_pending_ratings,
+            set_log_level,
+            get_log_level,
+            get_recent_logs,
+            get_settings,
+            update_settings,
+            get_system_conditions,
+            is_gpu_resize_available,
+            hydrate_files,
+            sort_image_paths,
+            set_manual_order,
+            get_manual_order,
+            filter_image_paths,
+            quick_search,
+            get_timeline,
+            get_capture_statistics,
+            export_app_data,
+            read_app_data_backup,
+            import_app_data,
+            open_image_folder,
+            get_image_page,
+            create_selection,
+            release_selection,
+            selection_add,
+            selection_remove,
+            selection_add_range,
+            selection_invert,
+            get_selection_paths,
+            selection_stats,
+            take_queue_resume_state,
+            get_hq_generation_status,
+            open_viewer_window,
+            open_compare_window,
+            get_window_payload,
+            set_global_shortcuts,
+            frontend_ready,
+            enable_hot_folder,
+            disable_hot_folder,
+            add_remote_source,
+            remove_remote_source,
+            list_remote_sources,
+            spool_remote_file,
+            spool_archive_entry,
+            set_as_wallpaper,
+            share_files,
+            get_monitor_profile,
+            save_upload_profile,
+            remove_upload_profile,
+            list_upload_profiles,
+            upload_images,
+            cancel_upload,
+            quick_export,
+            list_jobs,
+            cancel_job,
+            strip_metadata,
+            import_catalog_ratings,
+            get_image_keywords,
+            write_image_keywords,
+            read_sidecar_metadata,
+            get_iptc_metadata,
+            write_iptc_metadata,
+            shift_capture_time,
+            set_capture_time,
+            geotag_from_gpx,
+            get_image_details,
+            start_light_metadata_scan,
+            cancel_light_metadata_scan,
+            set_worker_budget,
+            get_failed_thumbnails,
+            get_thumbnail_perf_report,
+            validate_images,
+            retry_failed_thumbnails,
+            render_preview,
+            crop_image,
+            get_lossless_crop_alignment,
+            preview_watermark,
+            get_image_pyramid,
+            get_image_tile,
+            get_image_region,
+            get_document_pages,
+            generate_page_thumbnail,
+            get_psd_info,
+            set_stack_policy,
+            list_images,
+            get_supported_formats,
+            get_format_mismatches
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| match event {
+            // 종료 시 워커 정리 및 남은 작업 저장
+            tauri::RunEvent::ExitRequested { .. } | tauri::RunEvent::Exit => shutdown::shutdown(app),
+            // macOS: Finder에서 "PixEngine으로 열기" (Apple open-file 이벤트)
+            #[cfg(target_os = "macos")]
+            tauri::RunEvent::Opened { urls } => open_target::open_urls(app, &urls),
+            _ => {}
+        });
 }
@@ -0,0 +1,277 @@
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::formats::is_image_file;
+use crate::thumbnail::{generate_cache_key, get_cache_path, get_file_mtime};
+use crate::thumbnail_cache::CacheCleanupResult;
+
+/// 폴더별 캐시 폴더 이름 (사진 폴더 안, 숨김)
+pub const LOCAL_CACHE_DIR: &str = ".pixengine";
+
+const THUMBNAILS_DIR: &str = "thumbnails";
+const METADATA_FILE: &str = "metadata.json";
+
+/// 폴더별 캐시(.pixengine) 사용 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LocalCachePolicy {
+    /// .pixengine 폴더 무시 (앱 캐시만 사용)
+    Off,
+    /// .pixengine 폴더가 있으면 먼저 읽고 새 썸네일도 저장 (앱 캐시는 읽기만)
+    PreferLocal,
+    /// 앱 캐시를 먼저 읽고 없으면 .pixengine 폴더에서 읽음 (새 썸네일은 앱 캐시에 저장)
+    PreferCentral,
+}
+
+/// 폴더별 캐시 정보
+#[derive(Debug, Clone, Serialize)]
+pub struct LocalCacheInfo {
+    /// 사진 폴더 (.pixengine의 상위 폴더)
+    pub folder: String,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// 감지한 폴더별 캐시 (읽기 전용 드라이브면 쓰지 않음)
+#[derive(Debug, Clone)]
+struct LocalCacheDir {
+    root: PathBuf,
+    writable: bool,
+}
+
+lazy_static! {
+    /// 폴더별 감지 결과 (썸네일마다 폴더를 확인하지 않도록, 만들거나 지우면 다시 감지)
+    static ref DETECTED: DashMap<PathBuf, Option<LocalCacheDir>> = DashMap::new();
+}
+
+// 쓰기 가능한지 임시 파일로 확인
+fn is_writable(dir: &Path) -> bool {
+    let probe = dir.join(".write-test");
+    let writable = fs::write(&probe, b"").is_ok();
+    let _ = fs::remove_file(probe);
+    writable
+}
+
+fn detect(folder: &Path) -> Option<LocalCacheDir> {
+    if let Some(detected) = DETECTED.get(folder) {
+        return detected.clone();
+    }
+
+    let root = folder.join(LOCAL_CACHE_DIR);
+    let detected = root.is_dir().then(|| {
+        let thumbnails = root.join(THUMBNAILS_DIR);
+        let writable = fs::create_dir_all(&thumbnails).is_ok() && is_writable(&thumbnails);
+        LocalCacheDir { root, writable }
+    });
+    DETECTED.insert(folder.to_path_buf(), detected.clone());
+    detected
+}
+
+// root 아래 폴더의 감지 결과 삭제
+fn forget(root: &Path) {
+    DETECTED.retain(|folder, _| !folder.starts_with(root));
+}
+
+// 폴더별 캐시의 키는 파일 이름 기준 (드라이브 문자/마운트 위치가 바뀌어도 그대로 사용)
+fn local_thumbnail_path(root: &Path, file_name: &str, mtime: u64) -> PathBuf {
+    root.join(THUMBNAILS_DIR)
+        .join(format!("{}.webp", generate_cache_key(file_name, mtime)))
+}
+
+/// 썸네일 캐시 파일 위치 (있는 파일은 설정 순서대로 찾고, 없으면 새로 저장할 위치)
+pub fn resolve(central: PathBuf, file_path: &str, mtime: u64) -> PathBuf {
+    let policy = crate::settings::local_cache_policy();
+    if policy == LocalCachePolicy::Off {
+        return central;
+    }
+
+    let path = crate::fs_path::to_fs_path(file_path);
+    let (Some(folder), Some(file_name)) = (path.parent(), path.file_name()) else {
+        return central;
+    };
+    let Some(local_dir) = detect(folder) else {
+        return central;
+    };
+    let local = local_thumbnail_path(&local_dir.root, &file_name.to_string_lossy(), mtime);
+
+    let preferred = match policy {
+        LocalCachePolicy::PreferLocal => [&local, &central],
+        LocalCachePolicy::PreferCentral | LocalCachePolicy::Off => [&central, &local],
+    };
+    if let Some(existing) = preferred.into_iter().find(|path| path.exists()) {
+        return existing.clone();
+    }
+
+    if policy == LocalCachePolicy::PreferLocal && local_dir.writable {
+        local
+    } else {
+        central
+    }
+}
+
+/// 폴더별 캐시를 쓰는 폴더의 메타데이터 파일 (PreferLocal이고 쓸 수 있을 때만)
+pub fn metadata_path(folder: &str) -> Option<PathBuf> {
+    if crate::settings::local_cache_policy() != LocalCachePolicy::PreferLocal {
+        return None;
+    }
+    detect(&crate::fs_path::to_fs_path(folder))
+        .filter(|local_dir| local_dir.writable)
+        .map(|local_dir| local_dir.root.join(METADATA_FILE))
+}
+
+/// 파일의 폴더에 폴더별 캐시가 있는지 (설정이 Off면 항상 false)
+pub fn is_active(file_path: &str) -> bool {
+    crate::settings::local_cache_policy() != LocalCachePolicy::Off
+        && crate::fs_path::to_fs_path(file_path)
+            .parent()
+            .is_some_and(|folder| detect(folder).is_some())
+}
+
+/// Windows: 숨김 속성 설정 (다른 OS는 점으로 시작하는 이름이라 숨겨짐)
+#[cfg(target_os = "windows")]
+fn set_hidden(path: &Path) {
+    use windows::core::PCWSTR;
+    use windows::Win32::Storage::FileSystem::{
+        GetFileAttributesW, SetFileAttributesW, FILE_ATTRIBUTE_HIDDEN, FILE_FLAGS_AND_ATTRIBUTES,
+        INVALID_FILE_ATTRIBUTES,
+    };
+
+    let wide = crate::drives::to_wide(&path.to_string_lossy());
+    unsafe {
+        let attributes = GetFileAttributesW(PCWSTR(wide.as_ptr()));
+        if attributes != INVALID_FILE_ATTRIBUTES {
+            let _ = SetFileAttributesW(
+                PCWSTR(wide.as_ptr()),
+                FILE_FLAGS_AND_ATTRIBUTES(attributes | FILE_ATTRIBUTE_HIDDEN.0),
+            );
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn set_hidden(_path: &Path) {}
+
+// 폴더의 이미지 파일 (이름, 수정 시간)
+fn folder_images(folder: &Path) -> Vec<(String, PathBuf, u64)> {
+    let Ok(entries) = fs::read_dir(folder) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && is_image_file(path))
+        .filter_map(|path| {
+            let name = path.file_name()?.to_string_lossy().to_string();
+            let mtime = get_file_mtime(&path.to_string_lossy()).ok()?;
+            Some((name, path, mtime))
+        })
+        .collect()
+}
+
+fn cache_size(root: &Path) -> (usize, u64) {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .fold((0, 0), |(files, bytes), entry| {
+            (files + 1, bytes + entry.metadata().map(|m| m.len()).unwrap_or(0))
+        })
+}
+
+fn info(folder: &Path, root: &Path) -> LocalCacheInfo {
+    let (files, bytes) = cache_size(root);
+    LocalCacheInfo {
+        folder: crate::fs_path::to_display(folder),
+        files,
+        bytes,
+    }
+}
+
+/// 폴더에 .pixengine 폴더 생성 (copy_existing이면 앱 캐시의 썸네일을 복사)
+pub fn enable(app_handle: &tauri::AppHandle, folder: &Path, copy_existing: bool) -> Result<LocalCacheInfo, String> {
+    let root = folder.join(LOCAL_CACHE_DIR);
+    let thumbnails = root.join(THUMBNAILS_DIR);
+    fs::create_dir_all(&thumbnails).map_err(|e| format!("Failed to create local cache: {}", e))?;
+    if !is_writable(&thumbnails) {
+        return Err(format!("Folder is not writable: {}", folder.display()));
+    }
+    set_hidden(&root);
+    forget(folder);
+
+    if copy_existing {
+        for (name, path, mtime) in folder_images(folder) {
+            let key = generate_cache_key(&crate::fs_path::to_display(&path), mtime);
+            let Ok(central) = get_cache_path(app_handle, &key) else {
+                continue;
+            };
+            let local = local_thumbnail_path(&root, &name, mtime);
+            if central.exists() && !local.exists() {
+                let _ = fs::copy(&central, &local);
+            }
+        }
+    }
+
+    Ok(info(folder, &root))
+}
+
+/// root 아래 모든 폴더별 캐시 찾기 (root 자신 포함)
+pub fn find(root: &Path) -> Vec<LocalCacheInfo> {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| entry.file_type().is_dir())
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name() == LOCAL_CACHE_DIR)
+        .filter_map(|entry| {
+            let folder = entry.path().parent()?;
+            Some(info(folder, entry.path()))
+        })
+        .collect()
+}
+
+// 원본이 없어졌거나 수정된 썸네일 삭제
+fn prune(folder: &Path, root: &Path, result: &mut CacheCleanupResult) {
+    let valid: HashSet<String> = folder_images(folder)
+        .iter()
+        .map(|(name, _, mtime)| format!("{}.webp", generate_cache_key(name, *mtime)))
+        .collect();
+
+    let Ok(entries) = fs::read_dir(root.join(THUMBNAILS_DIR)) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if valid.contains(entry.file_name().to_string_lossy().as_ref()) {
+            continue;
+        }
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        if fs::remove_file(entry.path()).is_ok() {
+            result.deleted += 1;
+            result.freed_bytes += size;
+        }
+    }
+}
+
+/// root 아래 폴더별 캐시 정리 (remove_all이면 .pixengine 폴더 삭제, 아니면 오래된 썸네일만)
+pub fn clean(root: &Path, remove_all: bool) -> CacheCleanupResult {
+    let mut result = CacheCleanupResult { deleted: 0, freed_bytes: 0 };
+
+    for cache in find(root) {
+        let folder = crate::fs_path::to_fs_path(&cache.folder);
+        let cache_root = folder.join(LOCAL_CACHE_DIR);
+        if !remove_all {
+            prune(&folder, &cache_root, &mut result);
+        } else if fs::remove_dir_all(&cache_root).is_ok() {
+            result.deleted += cache.files;
+            result.freed_bytes += cache.bytes;
+        } else {
+            tracing::warn!("Failed to remove local cache: {}", cache_root.display());
+        }
+    }
+
+    forget(root);
+    result
+}
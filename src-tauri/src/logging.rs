@@ -0,0 +1,137 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tauri::Manager;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+
+/// 로그 파일 이름 접두사/확장자 (pixengine.YYYY-MM-DD.log)
+const LOG_FILE_PREFIX: &str = "pixengine";
+const LOG_FILE_SUFFIX: &str = "log";
+
+/// 보관할 로그 파일 수 (하루 1개)
+const MAX_LOG_FILES: usize = 7;
+
+/// 사용 가능한 로그 레벨
+const LOG_LEVELS: &[&str] = &["error", "warn", "info", "debug", "trace"];
+
+/// 런타임 로그 레벨 변경용 핸들
+static FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// 파일 쓰기 스레드 유지 (drop되면 남은 로그가 기록되지 않음)
+static WRITER_GUARD: OnceLock<WorkerGuard> = OnceLock::new();
+
+/// 현재 로그 레벨
+static CURRENT_LEVEL: std::sync::Mutex<String> = std::sync::Mutex::new(String::new());
+
+/// 로그 디렉토리 가져오기
+pub fn get_log_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    app_handle.path()
+        .app_log_dir()
+        .map_err(|e| format!("Failed to get app log dir: {}", e))
+}
+
+fn default_level() -> &'static str {
+    if cfg!(debug_assertions) { "debug" } else { "info" }
+}
+
+/// 로깅 초기화 (로그 파일 + 개발 빌드는 콘솔 출력)
+pub fn init(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let log_dir = get_log_dir(app_handle)?;
+    fs::create_dir_all(&log_dir)
+        .map_err(|e| format!("Failed to create log directory: {}", e))?;
+
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix(LOG_FILE_SUFFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(&log_dir)
+        .map_err(|e| format!("Failed to create log file: {}", e))?;
+
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+    let _ = WRITER_GUARD.set(guard);
+
+    let level = default_level();
+    let (filter, handle) = reload::Layer::new(EnvFilter::new(level));
+    let _ = FILTER_HANDLE.set(handle);
+    if let Ok(mut current) = CURRENT_LEVEL.lock() {
+        *current = level.to_string();
+    }
+
+    let file_layer = fmt::layer().with_writer(writer).with_ansi(false);
+    let console_layer = cfg!(debug_assertions).then(|| fmt::layer().with_writer(std::io::stderr));
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(file_layer)
+        .with(console_layer)
+        .try_init()
+        .map_err(|e| format!("Failed to initialize logging: {}", e))
+}
+
+/// 로그 레벨 변경 (error, warn, info, debug, trace)
+pub fn set_level(level: &str) -> Result<(), String> {
+    let level = level.to_lowercase();
+    if !LOG_LEVELS.contains(&level.as_str()) {
+        return Err(format!("Invalid log level: {}", level));
+    }
+
+    let handle = FILTER_HANDLE.get().ok_or("Logging is not initialized")?;
+    handle
+        .reload(EnvFilter::new(&level))
+        .map_err(|e| format!("Failed to set log level: {}", e))?;
+
+    if let Ok(mut current) = CURRENT_LEVEL.lock() {
+        *current = level.clone();
+    }
+    tracing::info!("Log level changed to {}", level);
+    Ok(())
+}
+
+/// 현재 로그 레벨
+pub fn get_level() -> String {
+    CURRENT_LEVEL
+        .lock()
+        .map(|level| level.clone())
+        .unwrap_or_default()
+}
+
+/// 최근 로그 줄 (오래된 파일부터 이어서 마지막 max_lines줄)
+pub fn get_recent_logs(app_handle: &tauri::AppHandle, max_lines: usize) -> Result<Vec<String>, String> {
+    let log_dir = get_log_dir(app_handle)?;
+    if !log_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    // 파일명에 날짜가 들어가므로 이름순 = 시간순
+    let mut files: Vec<PathBuf> = fs::read_dir(&log_dir)
+        .map_err(|e| format!("Failed to read log directory: {}", e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .map(|n| n.to_string_lossy().starts_with(LOG_FILE_PREFIX))
+                .unwrap_or(false)
+        })
+        .collect();
+    files.sort();
+
+    let mut lines: Vec<String> = Vec::new();
+    for file in files.iter().rev() {
+        let content = fs::read_to_string(file).unwrap_or_default();
+        let mut file_lines: Vec<String> = content.lines().map(str::to_string).collect();
+        file_lines.append(&mut lines);
+        lines = file_lines;
+
+        if lines.len() >= max_lines {
+            break;
+        }
+    }
+
+    let skip = lines.len().saturating_sub(max_lines);
+    Ok(lines.split_off(skip))
+}
@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+/// 폴더별 사용자 지정 순서 (드래그로 정한 순서, SortKey::Manual)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ManualOrder {
+    folder: String,
+    paths: Vec<String>,
+}
+
+// 순서 파일 경로 (폴더 경로 해시)
+fn get_order_path(app: &tauri::AppHandle, folder: &str) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join("manual_order");
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create manual order directory: {}", e))?;
+
+    let key = blake3::hash(folder.as_bytes()).to_hex();
+    Ok(dir.join(format!("{}.json", &key[..16])))
+}
+
+fn parent_folder(path: &str) -> Option<String> {
+    Path::new(path).parent().map(|parent| parent.to_string_lossy().to_string())
+}
+
+/// 폴더의 사용자 지정 순서 (저장된 적 없으면 None)
+pub fn load(app: &tauri::AppHandle, folder: &str) -> Option<Vec<String>> {
+    let path = get_order_path(app, folder).ok()?;
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str::<ManualOrder>(&content)
+        .ok()
+        .filter(|order| order.folder == folder)
+        .map(|order| order.paths)
+}
+
+/// 사용자 지정 순서 저장 (중복 경로는 처음 위치만, 빈 목록이면 삭제)
+pub fn save(app: &tauri::AppHandle, folder: &str, ordered_paths: Vec<String>) -> Result<(), String> {
+    let path = get_order_path(app, folder)?;
+    if ordered_paths.is_empty() {
+        let _ = fs::remove_file(path);
+        return Ok(());
+    }
+
+    let mut seen = HashSet::new();
+    let paths: Vec<String> = ordered_paths.into_iter().filter(|path| seen.insert(path.clone())).collect();
+    let order = ManualOrder {
+        folder: folder.to_string(),
+        paths,
+    };
+    let json = serde_json::to_string(&order).map_err(|e| format!("Failed to serialize manual order: {}", e))?;
+    crate::shutdown::write_atomic(&path, json.as_bytes()).map_err(|e| format!("Failed to write manual order: {}", e))
+}
+
+/// 파일 이름 변경/이동 반영 (같은 폴더면 같은 위치 유지, 다른 폴더로 옮겨졌으면 목록에서 제외)
+pub fn rename_path(app: &tauri::AppHandle, from: &str, to: &str) {
+    let Some(folder) = parent_folder(from) else {
+        return;
+    };
+    let Some(mut paths) = load(app, &folder) else {
+        return;
+    };
+    let Some(position) = paths.iter().position(|path| path == from) else {
+        return;
+    };
+
+    if parent_folder(to).as_deref() == Some(folder.as_str()) {
+        paths[position] = to.to_string();
+    } else {
+        paths.remove(position);
+    }
+    if let Err(e) = save(app, &folder, paths) {
+        tracing::warn!("Failed to update manual order for {}: {}", folder, e);
+    }
+}
@@ -0,0 +1,182 @@
+use rayon::prelude::*;
+use serde::Serialize;
+use std::fs;
+use std::io::BufReader;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::metadata_store::MetadataStore;
+use crate::rating;
+use crate::settings;
+
+/// 한 번에 보내는 항목 수
+const BATCH_SIZE: usize = 200;
+
+/// 스캔 최대 스레드 수 (썸네일 워커와 CPU를 나눠 씀)
+const MAX_SCAN_THREADS: usize = 4;
+
+/// 현재 스캔 번호 (새 스캔이나 취소 시 증가, 번호가 다른 스캔은 중단)
+static SCAN_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// 경량 메타데이터 (정렬용)
+#[derive(Debug, Clone, Serialize)]
+pub struct LightMetadata {
+    pub path: String,
+    pub file_size: Option<u64>,
+    pub modified_time: Option<String>,
+    pub date_taken: Option<String>,
+    pub rating: Option<i32>, // XMP 별점 (0-5, XMP를 쓸 수 없는 형식은 DB 별점)
+    /// 자리 표시용 4x4 평균 색 격자 (썸네일을 만든 적이 있는 파일만)
+    pub color_grid: Option<String>,
+}
+
+/// 스캔 묶음 이벤트 (light-metadata-batch)
+#[derive(Debug, Clone, Serialize)]
+pub struct LightMetadataBatch {
+    pub scan_id: u64,
+    pub items: Vec<LightMetadata>,
+    pub completed: usize,
+    pub total: usize,
+    /// 마지막 묶음
+    pub done: bool,
+}
+
+/// 파일 하나의 경량 메타데이터 (크기, 수정 시간, 촬영 날짜, 별점)
+pub fn read_light_metadata(path: &str) -> LightMetadata {
+    // 파일 메타데이터 (크기, 수정시간)
+    let file_metadata = fs::metadata(path).ok();
+    let file_size = file_metadata.as_ref().map(|m| m.len());
+
+    let modified_time = file_metadata.as_ref().and_then(|m| {
+        m.modified().ok().map(|time| {
+            use chrono::{DateTime, Utc};
+            let datetime: DateTime<Utc> = time.into();
+            datetime.format("%Y-%m-%d %H:%M:%S").to_string()
+        })
+    });
+
+    // EXIF에서 촬영 날짜(DateTimeOriginal)만 빠르게 추출
+    let date_taken = crate::file_lock::retry_locked_blocking(|| fs::File::open(path)).ok().and_then(|file| {
+        let exif_data = exif::Reader::new()
+            .read_from_container(&mut BufReader::new(file))
+            .ok()?;
+        let field = exif_data.get_field(exif::Tag::DateTimeOriginal, exif::In::PRIMARY)?;
+        match field.value {
+            exif::Value::Ascii(ref vec) => vec
+                .first()
+                .and_then(|bytes| std::str::from_utf8(bytes).ok())
+                .and_then(crate::metadata_store::normalize_exif_datetime),
+            _ => None,
+        }
+    });
+
+    // XMP 별점 읽기 (실패해도 계속 진행)
+    let rating = rating::read_rating(path).ok().filter(|&r| r > 0);
+
+    LightMetadata {
+        path: path.to_string(),
+        file_size,
+        modified_time,
+        date_taken,
+        rating,
+        color_grid: None,
+    }
+}
+
+/// XMP를 쓸 수 없는 형식의 별점을 DB 값으로 채움 (저장소가 없으면 그대로)
+pub fn merge_database_ratings(app: &AppHandle, items: &mut [LightMetadata]) {
+    let Some(store) = app.try_state::<Arc<MetadataStore>>() else {
+        return;
+    };
+    let ratings = match rating::database_ratings(&store, items.iter().map(|item| &item.path)) {
+        Ok(ratings) => ratings,
+        Err(e) => {
+            tracing::warn!("Failed to read database ratings: {}", e);
+            return;
+        }
+    };
+    for item in items {
+        if let Some(&rating) = ratings.get(&item.path) {
+            item.rating = Some(rating).filter(|&r| r > 0);
+        }
+    }
+}
+
+/// 썸네일 생성 때 저장한 색 격자 채움 (저장소가 없으면 그대로)
+pub fn merge_color_grids(app: &AppHandle, items: &mut [LightMetadata]) {
+    let Some(store) = app.try_state::<Arc<MetadataStore>>() else {
+        return;
+    };
+    let paths: Vec<String> = items.iter().map(|item| item.path.clone()).collect();
+    let mut grids = match store.get_color_grids(&paths) {
+        Ok(grids) => grids,
+        Err(e) => {
+            tracing::warn!("Failed to read color grids: {}", e);
+            return;
+        }
+    };
+    for item in items {
+        item.color_grid = grids.remove(&item.path);
+    }
+}
+
+/// 진행 중인 스캔 취소 (폴더를 바꿀 때)
+pub fn cancel_scan() {
+    SCAN_GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+/// 경량 메타데이터 스캔 시작 (이전 스캔은 취소), 스캔 번호 반환
+/// 결과는 light-metadata-batch 이벤트로 묶어서 보냄
+pub fn start_scan(app: AppHandle, paths: Vec<String>) -> u64 {
+    let scan_id = SCAN_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let is_current = move || SCAN_GENERATION.load(Ordering::SeqCst) == scan_id;
+
+    std::thread::spawn(move || {
+        let threads = num_cpus::get()
+            .saturating_sub(settings::current().thumbnail_workers())
+            .clamp(1, MAX_SCAN_THREADS);
+        let pool = match rayon::ThreadPoolBuilder::new().num_threads(threads).build() {
+            Ok(pool) => pool,
+            Err(e) => {
+                tracing::error!("Failed to create metadata scan pool: {}", e);
+                return;
+            }
+        };
+
+        let total = paths.len();
+        let mut completed = 0;
+        let mut chunks = paths.chunks(BATCH_SIZE).peekable();
+        if chunks.peek().is_none() {
+            let _ = app.emit(
+                "light-metadata-batch",
+                LightMetadataBatch { scan_id, items: Vec::new(), completed: 0, total, done: true },
+            );
+            return;
+        }
+
+        while let Some(chunk) = chunks.next() {
+            let mut items: Vec<LightMetadata> = pool.install(|| {
+                chunk
+                    .par_iter()
+                    .filter(|_| is_current())
+                    .map(|path| read_light_metadata(path))
+                    .collect()
+            });
+            if !is_current() {
+                tracing::debug!("Light metadata scan {} cancelled ({}/{})", scan_id, completed, total);
+                return;
+            }
+
+            merge_database_ratings(&app, &mut items);
+            merge_color_grids(&app, &mut items);
+            completed += items.len();
+            let _ = app.emit(
+                "light-metadata-batch",
+                LightMetadataBatch { scan_id, items, completed, total, done: chunks.peek().is_none() },
+            );
+        }
+    });
+
+    scan_id
+}
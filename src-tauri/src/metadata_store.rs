@@ -0,0 +1,566 @@
+use exif::{In, Tag};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tauri::Manager;
+use tokio::sync::broadcast;
+
+use crate::rating;
+
+/// 메타데이터 DB 스키마
+const SCHEMA: &str = "
+    PRAGMA journal_mode = WAL;
+    PRAGMA synchronous = NORMAL;
+
+    CREATE TABLE IF NOT EXISTS images (
+        path         TEXT PRIMARY KEY,
+        folder       TEXT NOT NULL,
+        file_name    TEXT NOT NULL,
+        extension    TEXT NOT NULL,
+        file_size    INTEGER NOT NULL,
+        mtime        INTEGER NOT NULL,
+        date_taken   TEXT,
+        camera_make  TEXT,
+        camera_model TEXT,
+        lens_model   TEXT,
+        focal_length REAL,
+        aperture     REAL,
+        iso          INTEGER,
+        width        INTEGER,
+        height       INTEGER,
+        orientation  INTEGER NOT NULL DEFAULT 1,
+        rating       INTEGER NOT NULL DEFAULT 0
+    );
+
+    CREATE INDEX IF NOT EXISTS idx_images_folder ON images(folder);
+    CREATE INDEX IF NOT EXISTS idx_images_date_taken ON images(date_taken);
+
+    -- 픽(1: 선택, -1: 제외)과 색상 라벨 (다른 프로그램 카탈로그에서 가져온 값 등)
+    CREATE TABLE IF NOT EXISTS image_labels (
+        path        TEXT PRIMARY KEY,
+        pick        INTEGER NOT NULL DEFAULT 0,
+        color_label TEXT
+    );
+
+    -- XMP를 쓸 수 없는 형식(BMP, EXR, SVG 등)의 별점 (다시 인덱싱해도 유지)
+    CREATE TABLE IF NOT EXISTS db_ratings (
+        path   TEXT PRIMARY KEY,
+        rating INTEGER NOT NULL
+    );
+
+    -- 썸네일 생성 때 계산한 4x4 평균 색 격자 (처음 여는 폴더의 자리 표시용)
+    CREATE TABLE IF NOT EXISTS color_grids (
+        path TEXT PRIMARY KEY,
+        grid TEXT NOT NULL
+    );
+";
+
+/// 인덱싱된 이미지 메타데이터
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageRecord {
+    pub path: String,
+    pub folder: String,
+    pub file_name: String,
+    pub extension: String,
+    pub file_size: u64,
+    pub mtime: u64,
+    pub date_taken: Option<String>, // "YYYY-MM-DD HH:MM:SS"
+    pub camera_make: Option<String>,
+    pub camera_model: Option<String>,
+    pub lens_model: Option<String>,
+    pub focal_length: Option<f64>,
+    pub aperture: Option<f64>,
+    pub iso: Option<u32>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub orientation: u8,
+    pub rating: i32,
+}
+
+/// SQLite 기반 메타데이터 저장소
+/// 변경된 경로는 broadcast 채널로 구독자(스마트 앨범 등)에게 알림
+pub struct MetadataStore {
+    conn: Mutex<Connection>,
+    changes: broadcast::Sender<Vec<String>>,
+}
+
+/// 메타데이터 DB 파일 경로 가져오기
+pub fn get_db_path(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    app_handle.path()
+        .app_data_dir()
+        .map(|p| p.join("metadata.db"))
+        .map_err(|e| format!("Failed to get app data dir: {}", e))
+}
+
+impl MetadataStore {
+    /// DB 열기 (없으면 생성)
+    pub fn open(db_path: &Path) -> Result<Self, String> {
+        if let Some(parent) = db_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create metadata directory: {}", e))?;
+        }
+
+        let conn = Connection::open(db_path)
+            .map_err(|e| format!("Failed to open metadata database: {}", e))?;
+
+        conn.execute_batch(SCHEMA)
+            .map_err(|e| format!("Failed to initialize metadata database: {}", e))?;
+
+        let (changes, _) = broadcast::channel(64);
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            changes,
+        })
+    }
+
+    /// 메타데이터 변경 구독
+    pub fn subscribe(&self) -> broadcast::Receiver<Vec<String>> {
+        self.changes.subscribe()
+    }
+
+    /// 변경된 경로 알림 (구독자가 없으면 무시)
+    pub fn notify_changed(&self, paths: Vec<String>) {
+        if !paths.is_empty() {
+            let _ = self.changes.send(paths);
+        }
+    }
+
+    /// 커넥션 잠금 후 작업 실행
+    pub fn with_conn<T>(&self, f: impl FnOnce(&Connection) -> rusqlite::Result<T>) -> Result<T, String> {
+        let conn = self.conn.lock()
+            .map_err(|_| "Metadata database lock poisoned".to_string())?;
+        f(&conn).map_err(|e| format!("Metadata database error: {}", e))
+    }
+
+    /// 레코드 저장 (있으면 갱신)
+    pub fn upsert(&self, records: &[ImageRecord]) -> Result<(), String> {
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        self.with_conn(|conn| {
+            let tx = conn.unchecked_transaction()?;
+            {
+                let mut stmt = tx.prepare_cached(
+                    "INSERT OR REPLACE INTO images (path, folder, file_name, extension, file_size, mtime, \
+                     date_taken, camera_make, camera_model, lens_model, focal_length, aperture, iso, \
+                     width, height, orientation, rating) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, \
+                     COALESCE((SELECT rating FROM db_ratings WHERE path = ?1), ?17))",
+                )?;
+
+                for r in records {
+                    stmt.execute(params![
+                        r.path,
+                        r.folder,
+                        r.file_name,
+                        r.extension,
+                        r.file_size as i64,
+                        r.mtime as i64,
+                        r.date_taken,
+                        r.camera_make,
+                        r.camera_model,
+                        r.lens_model,
+                        r.focal_length,
+                        r.aperture,
+                        r.iso,
+                        r.width,
+                        r.height,
+                        r.orientation,
+                        r.rating,
+                    ])?;
+                }
+            }
+            tx.commit()
+        })?;
+
+        self.notify_changed(records.iter().map(|r| r.path.clone()).collect());
+        Ok(())
+    }
+
+    /// 별점만 갱신 (인덱싱된 경로만 해당)
+    pub fn update_rating(&self, path: &str, rating: i32) -> Result<(), String> {
+        let updated = self.with_conn(|conn| {
+            conn.execute("UPDATE images SET rating = ?1 WHERE path = ?2", params![rating, path])
+        })?;
+
+        if updated > 0 {
+            self.notify_changed(vec![path.to_string()]);
+        }
+        Ok(())
+    }
+
+    /// XMP를 쓸 수 없는 파일의 별점 저장 (0이면 삭제), 인덱싱된 별점도 갱신
+    pub fn set_db_rating(&self, path: &str, rating: i32) -> Result<(), String> {
+        self.with_conn(|conn| {
+            if rating == 0 {
+                conn.execute("DELETE FROM db_ratings WHERE path = ?1", params![path])
+            } else {
+                conn.execute(
+                    "INSERT INTO db_ratings (path, rating) VALUES (?1, ?2) \
+                     ON CONFLICT(path) DO UPDATE SET rating = excluded.rating",
+                    params![path, rating],
+                )
+            }
+        })?;
+        self.update_rating(path, rating)
+    }
+
+    /// DB에 저장된 별점 일괄 조회 (없는 경로는 빠짐)
+    pub fn get_db_ratings(&self, paths: &[String]) -> Result<HashMap<String, i32>, String> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached("SELECT rating FROM db_ratings WHERE path = ?1")?;
+            let mut ratings = HashMap::new();
+            for path in paths {
+                if let Some(rating) = stmt.query_row(params![path], |row| row.get(0)).optional()? {
+                    ratings.insert(path.clone(), rating);
+                }
+            }
+            Ok(ratings)
+        })
+    }
+
+    /// 자리 표시용 색 격자 저장 (같은 값이면 쓰지 않음)
+    pub fn set_color_grid(&self, path: &str, grid: &str) -> Result<(), String> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO color_grids (path, grid) VALUES (?1, ?2) \
+                 ON CONFLICT(path) DO UPDATE SET grid = excluded.grid WHERE grid != excluded.grid",
+                params![path, grid],
+            )
+        })?;
+        Ok(())
+    }
+
+    /// 색 격자 일괄 조회 (없는 경로는 빠짐)
+    pub fn get_color_grids(&self, paths: &[String]) -> Result<HashMap<String, String>, String> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached("SELECT grid FROM color_grids WHERE path = ?1")?;
+            let mut grids = HashMap::new();
+            for path in paths {
+                if let Some(grid) = stmt.query_row(params![path], |row| row.get(0)).optional()? {
+                    grids.insert(path.clone(), grid);
+                }
+            }
+            Ok(grids)
+        })
+    }
+
+    /// 별점과 수정 시간 갱신 (앱에서 별점을 쓴 뒤, 다시 인덱싱하지 않도록)
+    pub fn update_rating_mtime(&self, path: &str, rating: i32, mtime: u64) -> Result<(), String> {
+        let updated = self.with_conn(|conn| {
+            conn.execute(
+                "UPDATE images SET rating = ?1, mtime = ?2 WHERE path = ?3",
+                params![rating, mtime as i64, path],
+            )
+        })?;
+
+        if updated > 0 {
+            self.notify_changed(vec![path.to_string()]);
+        }
+        Ok(())
+    }
+
+    /// 촬영 시간만 갱신 ("YYYY-MM-DD HH:MM:SS", 인덱싱된 경로만 해당)
+    pub fn update_date_taken(&self, path: &str, date_taken: &str) -> Result<(), String> {
+        let updated = self.with_conn(|conn| {
+            conn.execute("UPDATE images SET date_taken = ?1 WHERE path = ?2", params![date_taken, path])
+        })?;
+
+        if updated > 0 {
+            self.notify_changed(vec![path.to_string()]);
+        }
+        Ok(())
+    }
+
+    /// 픽/색상 라벨 저장
+    pub fn update_labels(&self, path: &str, pick: i32, color_label: Option<&str>) -> Result<(), String> {
+        self.with_conn(|conn| {
+            conn.execute(
+                "INSERT INTO image_labels (path, pick, color_label) VALUES (?1, ?2, ?3) \
+                 ON CONFLICT(path) DO UPDATE SET pick = excluded.pick, color_label = excluded.color_label",
+                params![path, pick, color_label],
+            )
+        })?;
+
+        self.notify_changed(vec![path.to_string()]);
+        Ok(())
+    }
+
+    /// 경로별 픽 값 (픽이 없는 경로는 결과에 없음)
+    pub fn get_picks(&self, paths: &[String]) -> Result<HashMap<String, i32>, String> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached("SELECT pick FROM image_labels WHERE path = ?1 AND pick != 0")?;
+            let mut picks = HashMap::new();
+            for path in paths {
+                if let Some(pick) = stmt.query_row(params![path], |row| row.get(0)).optional()? {
+                    picks.insert(path.clone(), pick);
+                }
+            }
+            Ok(picks)
+        })
+    }
+
+    /// 레코드 삭제
+    pub fn remove(&self, paths: &[String]) -> Result<(), String> {
+        let removed = self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached("DELETE FROM images WHERE path = ?1")?;
+            let mut removed = Vec::new();
+            for path in paths {
+                if stmt.execute(params![path])? > 0 {
+                    removed.push(path.clone());
+                }
+            }
+            Ok(removed)
+        })?;
+
+        self.notify_changed(removed);
+        Ok(())
+    }
+
+    /// 저장된 mtime 조회 (변경 감지용)
+    fn get_mtimes(&self, paths: &[String]) -> Result<HashMap<String, u64>, String> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached("SELECT mtime FROM images WHERE path = ?1")?;
+            let mut mtimes = HashMap::with_capacity(paths.len());
+            for path in paths {
+                if let Some(mtime) = stmt
+                    .query_row(params![path], |row| row.get::<_, i64>(0))
+                    .optional()?
+                {
+                    mtimes.insert(path.clone(), mtime as u64);
+                }
+            }
+            Ok(mtimes)
+        })
+    }
+
+    /// 경로별 레코드 조회 (인덱싱되지 않은 경로는 결과에 없음)
+    pub fn get_records(&self, paths: &[String]) -> Result<HashMap<String, ImageRecord>, String> {
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare_cached(
+                "SELECT path, folder, file_name, extension, file_size, mtime, date_taken, camera_make, \
+                 camera_model, lens_model, focal_length, aperture, iso, width, height, orientation, rating \
+                 FROM images WHERE path = ?1",
+            )?;
+            let mut records = HashMap::with_capacity(paths.len());
+            for path in paths {
+                if let Some(record) = stmt.query_row(params![path], row_to_record).optional()? {
+                    records.insert(path.clone(), record);
+                }
+            }
+            Ok(records)
+        })
+    }
+
+    /// 여러 이미지의 별점 (DB 값 사용, mtime이 바뀌었거나 인덱싱되지 않은 파일만 병렬로 다시 읽음)
+    /// 읽을 수 없는 파일은 None
+    pub fn get_ratings(&self, paths: &[String]) -> Result<Vec<(String, Option<i32>)>, String> {
+        self.index_files(paths)?;
+        let records = self.get_records(paths)?;
+        Ok(paths
+            .iter()
+            .map(|path| (path.clone(), records.get(path).map(|record| record.rating)))
+            .collect())
+    }
+
+    /// 조건에 맞는 레코드 순회 (전체를 메모리에 올리지 않음)
+    /// clause는 "FROM images" 뒤에 붙는 WHERE/ORDER BY 절
+    pub fn for_each_where(
+        &self,
+        clause: &str,
+        sql_params: &[Value],
+        mut f: impl FnMut(ImageRecord),
+    ) -> Result<(), String> {
+        let sql = format!(
+            "SELECT path, folder, file_name, extension, file_size, mtime, date_taken, camera_make, \
+             camera_model, lens_model, focal_length, aperture, iso, width, height, orientation, rating \
+             FROM images {}",
+            clause
+        );
+        self.with_conn(|conn| {
+            let mut stmt = conn.prepare(&sql)?;
+            let mut rows = stmt.query(params_from_iter(sql_params.iter()))?;
+            while let Some(row) = rows.next()? {
+                f(row_to_record(row)?);
+            }
+            Ok(())
+        })
+    }
+
+    /// 촬영일이 있는 모든 레코드를 최신 촬영일부터 순회
+    pub fn for_each_dated(&self, f: impl FnMut(ImageRecord)) -> Result<(), String> {
+        self.for_each_where("WHERE date_taken IS NOT NULL ORDER BY date_taken DESC, path", &[], f)
+    }
+
+    /// 이미지 인덱싱 (mtime이 바뀐 파일만 다시 읽음, 병렬 처리)
+    /// 반환값: 새로 인덱싱된 경로 목록
+    pub fn index_files(&self, paths: &[String]) -> Result<Vec<String>, String> {
+        use rayon::prelude::*;
+
+        let known = self.get_mtimes(paths)?;
+
+        let records: Vec<ImageRecord> = paths
+            .par_iter()
+            .filter_map(|path| {
+                let mtime = file_mtime(path).ok()?;
+                if known.get(path) == Some(&mtime) {
+                    return None;
+                }
+                read_image_record(path).ok()
+            })
+            .collect();
+
+        let changed: Vec<String> = records.iter().map(|r| r.path.clone()).collect();
+        self.upsert(&records)?;
+
+        Ok(changed)
+    }
+}
+
+/// DB 행 → 레코드 (get_records의 SELECT 컬럼 순서)
+fn row_to_record(row: &rusqlite::Row) -> rusqlite::Result<ImageRecord> {
+    Ok(ImageRecord {
+        path: row.get(0)?,
+        folder: row.get(1)?,
+        file_name: row.get(2)?,
+        extension: row.get(3)?,
+        file_size: row.get::<_, i64>(4)? as u64,
+        mtime: row.get::<_, i64>(5)? as u64,
+        date_taken: row.get(6)?,
+        camera_make: row.get(7)?,
+        camera_model: row.get(8)?,
+        lens_model: row.get(9)?,
+        focal_length: row.get(10)?,
+        aperture: row.get(11)?,
+        iso: row.get(12)?,
+        width: row.get(13)?,
+        height: row.get(14)?,
+        orientation: row.get(15)?,
+        rating: row.get(16)?,
+    })
+}
+
+/// 파일 수정 시간 (초)
+fn file_mtime(path: &str) -> Result<u64, String> {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .map_err(|e| format!("Failed to get modified time: {}", e))?
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .map_err(|e| format!("Invalid system time: {}", e))
+}
+
+/// EXIF 날짜 형식 변환: "YYYY:MM:DD HH:MM:SS" -> "YYYY-MM-DD HH:MM:SS"
+pub fn normalize_exif_datetime(raw: &str) -> Option<String> {
+    let trimmed = raw.trim().trim_matches('"');
+    let (date_part, time_part) = trimmed.split_once(' ')?;
+    Some(format!("{} {}", date_part.replace(':', "-"), time_part))
+}
+
+/// 파일에서 인덱싱용 레코드 읽기 (EXIF 1회 파싱)
+pub fn read_image_record(path: &str) -> Result<ImageRecord, String> {
+    let metadata = fs::metadata(path)
+        .map_err(|e| format!("Failed to get file metadata: {}", e))?;
+
+    let mtime = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let file_path = Path::new(path);
+    let folder = file_path
+        .parent()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let file_name = file_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = file_path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    // EXIF 파싱 (실패해도 파일 정보는 저장)
+    let exif_data = fs::File::open(path).ok().and_then(|file| {
+        exif::Reader::new()
+            .read_from_container(&mut BufReader::new(file))
+            .ok()
+    });
+
+    let get_ascii = |tag: Tag| -> Option<String> {
+        let field = exif_data.as_ref()?.get_field(tag, In::PRIMARY)?;
+        if let exif::Value::Ascii(ref vec) = field.value {
+            vec.first()
+                .and_then(|bytes| std::str::from_utf8(bytes).ok())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        } else {
+            None
+        }
+    };
+
+    let get_rational = |tag: Tag| -> Option<f64> {
+        let field = exif_data.as_ref()?.get_field(tag, In::PRIMARY)?;
+        if let exif::Value::Rational(ref vec) = field.value {
+            vec.first().map(|r| r.to_f64())
+        } else {
+            None
+        }
+    };
+
+    let get_uint = |tag: Tag| -> Option<u32> {
+        exif_data.as_ref()?.get_field(tag, In::PRIMARY)?.value.get_uint(0)
+    };
+
+    // 촬영 시간 변경은 RAW 등에서 XMP에만 기록되므로 XMP가 우선
+    let xmp = rating::read_xmp(path);
+    let date_taken = xmp
+        .as_ref()
+        .and_then(crate::capture_time::xmp_capture_time)
+        .map(|time| time.format(crate::capture_time::STORE_FORMAT).to_string())
+        .or_else(|| {
+            get_ascii(Tag::DateTimeOriginal)
+                .or_else(|| get_ascii(Tag::DateTime))
+                .and_then(|s| normalize_exif_datetime(&s))
+        });
+
+    // EXIF에 크기 정보가 없으면 이미지 헤더에서 읽기
+    let (width, height) = match (get_uint(Tag::PixelXDimension), get_uint(Tag::PixelYDimension)) {
+        (Some(w), Some(h)) => (Some(w), Some(h)),
+        _ => match image::image_dimensions(path) {
+            Ok((w, h)) => (Some(w), Some(h)),
+            Err(_) => (None, None),
+        },
+    };
+
+    Ok(ImageRecord {
+        path: path.to_string(),
+        folder,
+        file_name,
+        extension,
+        file_size: metadata.len(),
+        mtime,
+        date_taken,
+        camera_make: get_ascii(Tag::Make),
+        camera_model: get_ascii(Tag::Model),
+        lens_model: get_ascii(Tag::LensModel),
+        focal_length: get_rational(Tag::FocalLength),
+        aperture: get_rational(Tag::FNumber),
+        iso: get_uint(Tag::PhotographicSensitivity),
+        width,
+        height,
+        orientation: get_uint(Tag::Orientation).map(|o| o as u8).unwrap_or(1),
+        rating: xmp.as_ref().map_or(0, rating::xmp_rating),
+    })
+}
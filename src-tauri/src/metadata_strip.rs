@@ -0,0 +1,373 @@
+use exif::experimental::Writer;
+use exif::{Context, In, Tag};
+use filetime::FileTime;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use xmp_toolkit::{xmp_ns, ToStringOptions, XmpMeta};
+
+/// JPEG APP1 세그먼트 식별자
+pub const EXIF_HEADER: &[u8] = b"Exif\0\0";
+pub const XMP_HEADER: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+const XMP_EXTENSION_HEADER: &[u8] = b"http://ns.adobe.com/xmp/extension/\0";
+
+/// XMP 네임스페이스 (xmp_ns에 없는 것)
+const XMP_NS_AUX: &str = "http://ns.adobe.com/exif/1.0/aux/";
+const XMP_NS_EXIF_EX: &str = "http://cipa.jp/exif/1.0/";
+
+/// XMP에 복사된 EXIF GPS 속성
+const XMP_GPS_PROPERTIES: &[&str] = &[
+    "GPSVersionID", "GPSLatitude", "GPSLongitude", "GPSAltitudeRef", "GPSAltitude", "GPSTimeStamp",
+    "GPSSatellites", "GPSStatus", "GPSMeasureMode", "GPSDOP", "GPSSpeedRef", "GPSSpeed", "GPSTrackRef",
+    "GPSTrack", "GPSImgDirectionRef", "GPSImgDirection", "GPSMapDatum", "GPSDestLatitude",
+    "GPSDestLongitude", "GPSDestBearingRef", "GPSDestBearing", "GPSDestDistanceRef", "GPSDestDistance",
+    "GPSProcessingMethod", "GPSAreaInformation", "GPSDifferential",
+];
+
+/// 메타데이터 제거 옵션 (방향과 색 프로파일은 항상 유지)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StripOptions {
+    /// GPS 위치
+    pub gps: bool,
+    /// 바디/렌즈 시리얼 번호 (시리얼이 들어 있는 제조사 MakerNote 포함)
+    pub serial_numbers: bool,
+    /// 카메라 소유자 이름
+    pub owner: bool,
+    /// XMP 편집 기록 (xmpMM:History 등)
+    pub xmp_history: bool,
+    /// 원본 파일 수정 (false면 사본에 저장)
+    pub in_place: bool,
+    /// 사본 저장 폴더, None이면 원본 옆에 "_stripped"를 붙여 저장
+    pub output_dir: Option<String>,
+}
+
+impl Default for StripOptions {
+    fn default() -> Self {
+        Self {
+            gps: true,
+            serial_numbers: true,
+            owner: true,
+            xmp_history: true,
+            in_place: false,
+            output_dir: None,
+        }
+    }
+}
+
+/// 메타데이터를 제거한 파일
+#[derive(Debug, Clone, Serialize)]
+pub struct StrippedFile {
+    pub source: String,
+    pub output: String,
+}
+
+/// 메타데이터 제거 실패 항목
+#[derive(Debug, Clone, Serialize)]
+pub struct StripFailure {
+    pub path: String,
+    pub error: String,
+}
+
+/// 메타데이터 제거 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct StripSummary {
+    pub stripped: Vec<StrippedFile>,
+    pub failed: Vec<StripFailure>,
+}
+
+// 제거할 EXIF 태그인지
+fn should_remove(tag: Tag, options: &StripOptions) -> bool {
+    (options.gps && tag.context() == Context::Gps)
+        || (options.serial_numbers && matches!(tag, Tag::BodySerialNumber | Tag::LensSerialNumber | Tag::MakerNote))
+        || (options.owner && tag == Tag::CameraOwnerName)
+}
+
+/// EXIF(TIFF 구조)에서 태그 제거, 남는 항목이 없으면 None
+fn strip_exif(tiff: &[u8], options: &StripOptions) -> Result<Option<Vec<u8>>, String> {
+    let exif = exif::Reader::new()
+        .read_raw(tiff.to_vec())
+        .map_err(|e| format!("Failed to read EXIF: {}", e))?;
+
+    let fields: Vec<&exif::Field> = exif
+        .fields()
+        .filter(|field| !should_remove(field.tag, options))
+        .collect();
+    if !fields.iter().any(|field| field.ifd_num == In::PRIMARY) {
+        return Ok(None);
+    }
+    rebuild_exif(&exif, &fields).map(Some)
+}
+
+/// 필드 목록으로 EXIF(TIFF 구조) 다시 쓰기 (원본의 내장 썸네일 유지)
+pub fn rebuild_exif(exif: &exif::Exif, fields: &[&exif::Field]) -> Result<Vec<u8>, String> {
+    // 내장 썸네일 유지
+    let thumbnail = exif
+        .get_field(Tag::JPEGInterchangeFormat, In::THUMBNAIL)
+        .and_then(|field| field.value.get_uint(0))
+        .zip(
+            exif.get_field(Tag::JPEGInterchangeFormatLength, In::THUMBNAIL)
+                .and_then(|field| field.value.get_uint(0)),
+        )
+        .and_then(|(offset, length)| exif.buf().get(offset as usize..(offset + length) as usize));
+
+    let mut writer = Writer::new();
+    for field in fields {
+        writer.push_field(field);
+    }
+    if let Some(thumbnail) = thumbnail {
+        writer.set_jpeg(thumbnail, In::THUMBNAIL);
+    }
+
+    let mut out = Cursor::new(Vec::new());
+    writer
+        .write(&mut out, exif.little_endian())
+        .map_err(|e| format!("Failed to write EXIF: {}", e))?;
+    Ok(out.into_inner())
+}
+
+/// XMP 패킷에서 위치/시리얼/소유자/편집 기록 속성 제거
+fn strip_xmp(packet: &[u8], options: &StripOptions) -> Result<Vec<u8>, String> {
+    let text = std::str::from_utf8(packet).map_err(|e| format!("Invalid XMP packet: {}", e))?;
+    let mut xmp: XmpMeta = text.parse().map_err(|e| format!("Failed to parse XMP: {}", e))?;
+
+    let mut removals: Vec<(&str, &str)> = Vec::new();
+    if options.gps {
+        removals.extend(XMP_GPS_PROPERTIES.iter().map(|name| (xmp_ns::EXIF, *name)));
+    }
+    if options.serial_numbers {
+        removals.extend([
+            (XMP_NS_AUX, "SerialNumber"),
+            (XMP_NS_AUX, "LensSerialNumber"),
+            (XMP_NS_EXIF_EX, "BodySerialNumber"),
+            (XMP_NS_EXIF_EX, "LensSerialNumber"),
+        ]);
+    }
+    if options.owner {
+        removals.extend([(XMP_NS_AUX, "OwnerName"), (XMP_NS_EXIF_EX, "CameraOwnerName")]);
+    }
+    if options.xmp_history {
+        removals.extend([
+            (xmp_ns::XMP_MM, "History"),
+            (xmp_ns::XMP_MM, "DerivedFrom"),
+            (xmp_ns::XMP_MM, "Ingredients"),
+            (xmp_ns::XMP_MM, "Pantry"),
+        ]);
+    }
+    for (namespace, name) in removals {
+        let _ = xmp.delete_property(namespace, name);
+    }
+
+    xmp.to_string_with_options(ToStringOptions::default().use_compact_format())
+        .map(String::into_bytes)
+        .map_err(|e| format!("Failed to write XMP: {}", e))
+}
+
+/// JPEG 세그먼트 쓰기 (마커 + 길이 + 내용)
+pub fn push_segment(out: &mut Vec<u8>, marker: u8, header: &[u8], body: &[u8]) -> Result<(), String> {
+    let length = header.len() + body.len() + 2;
+    if length > u16::MAX as usize {
+        return Err("Metadata segment too large".to_string());
+    }
+    out.extend_from_slice(&[0xFF, marker]);
+    out.extend_from_slice(&(length as u16).to_be_bytes());
+    out.extend_from_slice(header);
+    out.extend_from_slice(body);
+    Ok(())
+}
+
+/// JPEG 데이터에서 메타데이터 제거 (이미지 데이터는 재인코딩하지 않음)
+/// 방향(Orientation)은 EXIF에 남고 ICC 프로파일(APP2)은 그대로 유지
+pub fn strip_jpeg(data: &[u8], options: &StripOptions) -> Result<Vec<u8>, String> {
+    rewrite_jpeg_metadata(
+        data,
+        |tiff| strip_exif(tiff, options),
+        |packet| strip_xmp(packet, options),
+        options.xmp_history,
+    )
+}
+
+/// JPEG의 EXIF/XMP 세그먼트만 바꿔 쓰기 (이미지 데이터는 재인코딩하지 않음)
+/// rewrite_exif가 None을 반환하면 EXIF 세그먼트 제거
+pub fn rewrite_jpeg_metadata(
+    data: &[u8],
+    mut rewrite_exif: impl FnMut(&[u8]) -> Result<Option<Vec<u8>>, String>,
+    mut rewrite_xmp: impl FnMut(&[u8]) -> Result<Vec<u8>, String>,
+    drop_extended_xmp: bool,
+) -> Result<Vec<u8>, String> {
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        return Err("Not a JPEG file".to_string());
+    }
+
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(&data[..2]);
+    let mut pos = 2;
+
+    loop {
+        if pos + 2 > data.len() || data[pos] != 0xFF {
+            return Err("Invalid JPEG structure".to_string());
+        }
+        let marker = data[pos + 1];
+
+        // 채움 바이트
+        if marker == 0xFF {
+            pos += 1;
+            continue;
+        }
+        // SOS 이후(압축 데이터)와 EOI는 그대로
+        if marker == 0xDA || marker == 0xD9 {
+            out.extend_from_slice(&data[pos..]);
+            return Ok(out);
+        }
+        // 길이 없는 마커
+        if marker == 0x01 || (0xD0..=0xD7).contains(&marker) {
+            out.extend_from_slice(&data[pos..pos + 2]);
+            pos += 2;
+            continue;
+        }
+
+        if pos + 4 > data.len() {
+            return Err("Invalid JPEG structure".to_string());
+        }
+        let length = u16::from_be_bytes([data[pos + 2], data[pos + 3]]) as usize;
+        let end = pos + 2 + length;
+        if length < 2 || end > data.len() {
+            return Err("Invalid JPEG structure".to_string());
+        }
+        let payload = &data[pos + 4..end];
+
+        if marker == 0xE1 && payload.starts_with(EXIF_HEADER) {
+            if let Some(tiff) = rewrite_exif(&payload[EXIF_HEADER.len()..])? {
+                push_segment(&mut out, marker, EXIF_HEADER, &tiff)?;
+            }
+        } else if marker == 0xE1 && payload.starts_with(XMP_HEADER) {
+            let xmp = rewrite_xmp(&payload[XMP_HEADER.len()..])?;
+            push_segment(&mut out, marker, XMP_HEADER, &xmp)?;
+        } else if marker == 0xE1 && payload.starts_with(XMP_EXTENSION_HEADER) && drop_extended_xmp {
+            // 확장 XMP는 기본 패킷에서 참조가 사라지므로 제거
+        } else {
+            out.extend_from_slice(&data[pos..end]);
+        }
+        pos = end;
+    }
+}
+
+// 사본 저장 경로
+fn output_path(source: &Path, options: &StripOptions) -> PathBuf {
+    if options.in_place {
+        return source.to_path_buf();
+    }
+
+    let file_name = source.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    let target = match &options.output_dir {
+        Some(dir) => Path::new(dir).join(file_name),
+        None => {
+            let stem = source.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+            let name = match source.extension() {
+                Some(ext) => format!("{}_stripped.{}", stem, ext.to_string_lossy()),
+                None => format!("{}_stripped", stem),
+            };
+            source.with_file_name(name)
+        }
+    };
+    crate::import::unique_destination(target)
+}
+
+fn strip_file(path: &str, options: &StripOptions) -> Result<PathBuf, String> {
+    if !crate::formats::is_jpeg_file(path) {
+        return Err("Metadata stripping supports JPEG files only".to_string());
+    }
+
+    let source = crate::fs_path::to_fs_path(path);
+    let data = fs::read(&source).map_err(|e| format!("Failed to read file: {}", e))?;
+    let stripped = strip_jpeg(&data, options)?;
+
+    let target = output_path(&source, options);
+    if options.in_place {
+        // 원본 수정 시간 유지 (정렬/캐시 기준)
+        let modified = fs::metadata(&source).map(|m| FileTime::from_last_modification_time(&m)).ok();
+        crate::shutdown::write_atomic(&target, &stripped).map_err(|e| format!("Failed to write file: {}", e))?;
+        if let Some(modified) = modified {
+            let _ = filetime::set_file_mtime(&target, modified);
+        }
+    } else {
+        fs::write(&target, &stripped).map_err(|e| format!("Failed to write file: {}", e))?;
+    }
+    Ok(target)
+}
+
+/// 이미지에서 개인정보 메타데이터 제거 (기본은 사본 저장)
+pub fn strip_metadata(paths: &[String], options: &StripOptions) -> Result<StripSummary, String> {
+    if let Some(dir) = &options.output_dir {
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create output folder: {}", e))?;
+    }
+
+    let results: Vec<(String, Result<PathBuf, String>)> = paths
+        .par_iter()
+        .map(|path| (path.clone(), strip_file(path, options)))
+        .collect();
+
+    let mut summary = StripSummary {
+        stripped: Vec::new(),
+        failed: Vec::new(),
+    };
+    for (path, result) in results {
+        match result {
+            Ok(output) => summary.stripped.push(StrippedFile {
+                source: path,
+                output: output.to_string_lossy().to_string(),
+            }),
+            Err(error) => summary.failed.push(StripFailure { path, error }),
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_jpeg_removes_gps_keeps_orientation() {
+        use exif::{Field, Rational, Value};
+
+        let orientation = Field {
+            tag: Tag::Orientation,
+            ifd_num: In::PRIMARY,
+            value: Value::Short(vec![6]),
+        };
+        let latitude = Field {
+            tag: Tag::GPSLatitude,
+            ifd_num: In::PRIMARY,
+            value: Value::Rational(vec![Rational { num: 37, denom: 1 }; 3]),
+        };
+        let serial = Field {
+            tag: Tag::BodySerialNumber,
+            ifd_num: In::PRIMARY,
+            value: Value::Ascii(vec![b"123456".to_vec()]),
+        };
+        let mut writer = Writer::new();
+        writer.push_field(&orientation);
+        writer.push_field(&latitude);
+        writer.push_field(&serial);
+        let mut tiff = Cursor::new(Vec::new());
+        writer.write(&mut tiff, false).unwrap();
+
+        let mut jpeg = vec![0xFF, 0xD8];
+        push_segment(&mut jpeg, 0xE1, EXIF_HEADER, &tiff.into_inner()).unwrap();
+        push_segment(&mut jpeg, 0xE2, b"ICC_PROFILE\0", b"icc").unwrap();
+        jpeg.extend_from_slice(&[0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34, 0xFF, 0xD9]);
+
+        let stripped = strip_jpeg(&jpeg, &StripOptions::default()).unwrap();
+        let exif = exif::Reader::new()
+            .read_from_container(&mut Cursor::new(&stripped))
+            .unwrap();
+        assert!(exif.get_field(Tag::Orientation, In::PRIMARY).is_some());
+        assert!(exif.get_field(Tag::GPSLatitude, In::PRIMARY).is_none());
+        assert!(exif.get_field(Tag::BodySerialNumber, In::PRIMARY).is_none());
+        assert!(stripped.windows(11).any(|w| w == b"ICC_PROFILE"));
+        assert!(stripped.ends_with(&[0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34, 0xFF, 0xD9]));
+    }
+}
@@ -0,0 +1,269 @@
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use tauri::{Emitter, Window, WindowEvent};
+
+use crate::color_profile::{self, PixelLayout};
+
+/// 메인 윈도우가 있는 모니터의 ICC 프로파일 (None: 없거나 읽을 수 없음 → sRGB로 간주)
+struct MonitorState {
+    detected: bool,
+    monitor: Option<String>,
+    profile: Option<Arc<Vec<u8>>>,
+}
+
+static STATE: Mutex<MonitorState> = Mutex::new(MonitorState {
+    detected: false,
+    monitor: None,
+    profile: None,
+});
+
+/// 소프트 프루핑 프로파일 (설정의 파일 경로, 읽은 ICC 데이터, 경로가 바뀔 때만 다시 읽음)
+static PROOF_PROFILE: Mutex<Option<(String, Option<Arc<Vec<u8>>>)>> = Mutex::new(None);
+
+/// get_monitor_profile 응답, monitor-profile-changed 이벤트
+#[derive(Debug, Clone, Serialize)]
+pub struct MonitorProfileInfo {
+    /// 모니터 이름 (알 수 없으면 None)
+    pub monitor: Option<String>,
+    pub has_profile: bool,
+    /// 프로파일 설명 (ICC desc 태그)
+    pub description: Option<String>,
+    /// 프로파일 해시 (바뀌면 미리보기를 다시 요청)
+    pub hash: Option<String>,
+    /// 미리보기에 소프트 프루핑을 적용하는지 (설정이 켜져 있고 출력 장치 프로파일이 sRGB가 아닐 때)
+    pub soft_proof: bool,
+    /// 소프트 프루핑 출력 장치 프로파일 설명
+    pub proof_description: Option<String>,
+}
+
+/// ICC 프로파일 설명 (v2 desc, v4 mluc의 첫 번째 항목)
+pub fn profile_description(icc: &[u8]) -> Option<String> {
+    let read_u32 = |offset: usize| -> Option<u32> {
+        Some(u32::from_be_bytes(icc.get(offset..offset + 4)?.try_into().ok()?))
+    };
+
+    // 태그 테이블: 128바이트 헤더 다음 태그 수, 태그마다 (서명, 위치, 크기)
+    let tag_count = read_u32(128)? as usize;
+    let (offset, size) = (0..tag_count.min(256)).find_map(|index| {
+        let entry = 132 + index * 12;
+        if icc.get(entry..entry + 4)? != b"desc" {
+            return None;
+        }
+        Some((read_u32(entry + 4)? as usize, read_u32(entry + 8)? as usize))
+    })?;
+    let tag = icc.get(offset..offset.checked_add(size)?)?;
+
+    let text = match tag.get(..4)? {
+        // textDescriptionType: 서명, 예약 4바이트, ASCII 길이(NUL 포함), ASCII
+        b"desc" => {
+            let length = u32::from_be_bytes(tag.get(8..12)?.try_into().ok()?) as usize;
+            String::from_utf8_lossy(tag.get(12..12usize.checked_add(length)?)?).to_string()
+        }
+        // multiLocalizedUnicodeType: 서명, 예약, 항목 수, 항목 크기, 항목마다 (언어, 국가, 길이, 위치) UTF-16BE
+        b"mluc" => {
+            let length = u32::from_be_bytes(tag.get(20..24)?.try_into().ok()?) as usize;
+            let start = u32::from_be_bytes(tag.get(24..28)?.try_into().ok()?) as usize;
+            let units: Vec<u16> = tag
+                .get(start..start.checked_add(length)?)?
+                .chunks_exact(2)
+                .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        _ => return None,
+    };
+    let text = text.trim_end_matches('\0').trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Windows: 윈도우가 있는 모니터의 장치 컨텍스트에 연결된 ICC 프로파일 파일
+#[cfg(target_os = "windows")]
+fn read_profile(window: &Window) -> Option<Vec<u8>> {
+    use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+    use windows::core::{PCWSTR, PWSTR};
+    use windows::Win32::Foundation::{HWND, MAX_PATH};
+    use windows::Win32::Graphics::Gdi::{
+        CreateDCW, DeleteDC, GetMonitorInfoW, MonitorFromWindow, MONITORINFO, MONITORINFOEXW, MONITOR_DEFAULTTONEAREST,
+    };
+    use windows::Win32::UI::ColorSystem::GetICMProfileW;
+
+    let RawWindowHandle::Win32(handle) = window.window_handle().ok()?.as_raw() else {
+        return None;
+    };
+
+    unsafe {
+        let monitor = MonitorFromWindow(HWND(handle.hwnd.get() as *mut _), MONITOR_DEFAULTTONEAREST);
+        let mut info = MONITORINFOEXW::default();
+        info.monitorInfo.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+        if !GetMonitorInfoW(monitor, &mut info as *mut MONITORINFOEXW as *mut MONITORINFO).as_bool() {
+            return None;
+        }
+
+        let device = PCWSTR(info.szDevice.as_ptr());
+        let hdc = CreateDCW(device, device, PCWSTR::null(), None);
+        if hdc.is_invalid() {
+            return None;
+        }
+        let mut buffer = [0u16; MAX_PATH as usize];
+        let mut length = buffer.len() as u32;
+        let found = GetICMProfileW(hdc, &mut length, PWSTR(buffer.as_mut_ptr())).as_bool();
+        let _ = DeleteDC(hdc);
+        if !found {
+            return None;
+        }
+
+        let end = buffer.iter().position(|&unit| unit == 0).unwrap_or(buffer.len());
+        std::fs::read(String::from_utf16_lossy(&buffer[..end])).ok()
+    }
+}
+
+/// macOS: 윈도우가 있는 화면(NSScreen)의 색 공간 ICC 데이터 (메인 스레드에서 호출)
+#[cfg(target_os = "macos")]
+fn read_profile(window: &Window) -> Option<Vec<u8>> {
+    use objc2_app_kit::NSWindow;
+
+    let ns_window = window.ns_window().ok()?;
+    let ns_window: &NSWindow = unsafe { &*(ns_window as *const NSWindow) };
+    unsafe {
+        let data = ns_window.screen()?.colorSpace()?.ICCProfileData()?;
+        Some(data.bytes().to_vec())
+    }
+}
+
+/// 그 밖의 플랫폼은 모니터 프로파일을 읽지 않음 (sRGB로 간주)
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn read_profile(_window: &Window) -> Option<Vec<u8>> {
+    None
+}
+
+// 소프트 프루핑할 출력 장치 프로파일 (설정이 꺼져 있거나, 색 관리가 꺼져 있거나, 읽을 수 없거나 sRGB면 None)
+fn active_proof_profile() -> Option<Arc<Vec<u8>>> {
+    let settings = crate::settings::current();
+    if !settings.monitor_soft_proof || !color_profile::is_enabled() {
+        return None;
+    }
+    let path = settings.soft_proof_profile?;
+
+    let mut cached = PROOF_PROFILE.lock().ok()?;
+    if cached.as_ref().is_none_or(|(cached_path, _)| *cached_path != path) {
+        let profile = match std::fs::read(crate::fs_path::to_fs_path(&path)) {
+            Ok(icc) => Some(Arc::new(icc)),
+            Err(e) => {
+                tracing::warn!("Failed to read soft proof profile {}: {}", path, e);
+                None
+            }
+        };
+        *cached = Some((path, profile));
+    }
+    cached
+        .as_ref()
+        .and_then(|(_, profile)| profile.clone())
+        .filter(|icc| !color_profile::is_srgb_or_unsupported(icc))
+}
+
+fn info(state: &MonitorState) -> MonitorProfileInfo {
+    let profile = state.profile.as_deref().map(Vec::as_slice);
+    let proof = active_proof_profile();
+    MonitorProfileInfo {
+        monitor: state.monitor.clone(),
+        has_profile: profile.is_some(),
+        description: profile.and_then(profile_description),
+        hash: profile.map(|icc| blake3::hash(icc).to_hex()[..16].to_string()),
+        soft_proof: proof.is_some(),
+        proof_description: proof.as_deref().map(Vec::as_slice).and_then(profile_description),
+    }
+}
+
+/// 윈도우가 있는 모니터의 프로파일 다시 확인 (모니터가 바뀌었을 때만 읽음)
+/// 프로파일이 바뀌면 monitor-profile-changed 이벤트
+pub fn refresh(window: &Window) {
+    let monitor = window.current_monitor().ok().flatten().and_then(|monitor| monitor.name().cloned());
+    {
+        let Ok(state) = STATE.lock() else {
+            return;
+        };
+        if state.detected && state.monitor == monitor {
+            return;
+        }
+    }
+
+    let profile = read_profile(window).map(Arc::new);
+    let Ok(mut state) = STATE.lock() else {
+        return;
+    };
+    let changed = state.detected && state.profile != profile;
+    state.detected = true;
+    state.monitor = monitor;
+    state.profile = profile;
+
+    let info = info(&state);
+    tracing::info!(
+        "Monitor profile: {} ({})",
+        info.monitor.as_deref().unwrap_or("unknown"),
+        info.description.as_deref().unwrap_or("sRGB"),
+    );
+    if changed {
+        let _ = window.emit("monitor-profile-changed", &info);
+    }
+}
+
+/// 메인 윈도우가 다른 모니터로 옮겨지면 프로파일 다시 확인
+pub fn handle_window_event(window: &Window, event: &WindowEvent) {
+    if window.label() != "main" {
+        return;
+    }
+    if matches!(event, WindowEvent::Moved(_) | WindowEvent::ScaleFactorChanged { .. }) {
+        refresh(window);
+    }
+}
+
+/// 현재 모니터 프로파일 정보
+pub fn current() -> MonitorProfileInfo {
+    let state = STATE.lock().unwrap();
+    info(&state)
+}
+
+/// 미리보기 픽셀(sRGB)에 출력 장치의 색 재현을 흉내냄 (sRGB → 출력 장치 → sRGB)
+/// 결과는 sRGB로 두고 모니터 변환은 webview에 맡김 (info()의 soft_proof와 같은 조건)
+pub fn soft_proof(pixels: &mut [u8], layout: PixelLayout) {
+    let Some(profile) = active_proof_profile() else {
+        return;
+    };
+    color_profile::convert_from_srgb(Some(profile.as_slice()), pixels, layout);
+    color_profile::convert_to_srgb(Some(profile.as_slice()), pixels, layout);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 헤더 + 태그 테이블(desc 하나) + 태그 데이터
+    fn profile_with_desc(tag: &[u8]) -> Vec<u8> {
+        let mut icc = vec![0u8; 128];
+        icc.extend_from_slice(&1u32.to_be_bytes());
+        icc.extend_from_slice(b"desc");
+        icc.extend_from_slice(&144u32.to_be_bytes());
+        icc.extend_from_slice(&(tag.len() as u32).to_be_bytes());
+        icc.extend_from_slice(tag);
+        icc
+    }
+
+    #[test]
+    fn test_profile_description() {
+        let mut v2 = b"desc\0\0\0\0".to_vec();
+        v2.extend_from_slice(&9u32.to_be_bytes());
+        v2.extend_from_slice(b"Display\0\0");
+        assert_eq!(profile_description(&profile_with_desc(&v2)).as_deref(), Some("Display"));
+
+        let mut v4 = b"mluc\0\0\0\0".to_vec();
+        v4.extend_from_slice(&1u32.to_be_bytes());
+        v4.extend_from_slice(&12u32.to_be_bytes());
+        v4.extend_from_slice(b"enUS");
+        v4.extend_from_slice(&6u32.to_be_bytes());
+        v4.extend_from_slice(&28u32.to_be_bytes());
+        v4.extend_from_slice(&[0, b'P', 0, b'3', 0, b'!']);
+        assert_eq!(profile_description(&profile_with_desc(&v4)).as_deref(), Some("P3!"));
+
+        assert_eq!(profile_description(&[0u8; 64]), None);
+    }
+}
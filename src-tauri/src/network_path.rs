@@ -0,0 +1,219 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// 네트워크 경로 존재 확인 타임아웃
+pub const NETWORK_STAT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 네트워크 폴더 목록 읽기 타임아웃
+pub const NETWORK_LIST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 네트워크 공유 응답 없음 에러 메시지 접두사
+pub const SHARE_UNREACHABLE: &str = "Network share unreachable";
+
+/// 마운트 테이블 캐시 유지 시간 (드라이브 연결/해제 이벤트가 오면 바로 다시 읽음)
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+const MOUNT_TABLE_TTL: Duration = Duration::from_secs(10);
+
+/// 마지막으로 읽은 마운트 테이블 (읽은 시각, 마운트 지점별 네트워크 볼륨 여부)
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+static MOUNT_TABLE: std::sync::Mutex<Option<(std::time::Instant, std::sync::Arc<Vec<(PathBuf, bool)>>)>> =
+    std::sync::Mutex::new(None);
+
+/// UNC 경로인지 확인 (\\server\share, //server/share, \\?\UNC\server\share)
+pub fn is_unc_path(path: &str) -> bool {
+    if path.starts_with("\\\\?\\UNC\\") {
+        return true;
+    }
+    if path.starts_with("\\\\?\\") || path.starts_with("\\\\.\\") {
+        return false;
+    }
+    path.starts_with("\\\\") || path.starts_with("//")
+}
+
+/// Windows: 매핑된 네트워크 드라이브인지 확인 (GetDriveTypeW == DRIVE_REMOTE)
+#[cfg(target_os = "windows")]
+fn is_network_mount(path: &str) -> bool {
+    use windows::core::PCWSTR;
+    use windows::Win32::Storage::FileSystem::GetDriveTypeW;
+
+    const DRIVE_REMOTE: u32 = 4;
+
+    let bytes = path.as_bytes();
+    if bytes.len() < 2 || bytes[1] != b':' || !bytes[0].is_ascii_alphabetic() {
+        return false;
+    }
+
+    let root = crate::drives::to_wide(&format!("{}:\\", bytes[0] as char));
+    unsafe { GetDriveTypeW(PCWSTR(root.as_ptr())) == DRIVE_REMOTE }
+}
+
+/// macOS: 마운트 지점별 로컬이 아닌 볼륨 여부
+/// (getmntinfo + MNT_NOWAIT로 응답 없는 공유에서도 블로킹하지 않음)
+#[cfg(target_os = "macos")]
+fn read_mount_table() -> Vec<(PathBuf, bool)> {
+    use std::ffi::CStr;
+
+    let mut mounts: *mut libc::statfs = std::ptr::null_mut();
+    let count = unsafe { libc::getmntinfo(&mut mounts, libc::MNT_NOWAIT) };
+    if count <= 0 || mounts.is_null() {
+        return Vec::new();
+    }
+
+    let entries = unsafe { std::slice::from_raw_parts(mounts, count as usize) };
+    entries
+        .iter()
+        .map(|entry| {
+            let mount_point = unsafe { CStr::from_ptr(entry.f_mntonname.as_ptr()) }.to_string_lossy();
+            let fs_type = unsafe { CStr::from_ptr(entry.f_fstypename.as_ptr()) }.to_string_lossy();
+            let is_network = entry.f_flags & libc::MNT_LOCAL as u32 == 0
+                || crate::drives::is_network_file_system(&fs_type);
+            (PathBuf::from(mount_point.as_ref()), is_network)
+        })
+        .collect()
+}
+
+/// Linux: 마운트 지점별 네트워크 볼륨 여부 (/proc/self/mounts)
+#[cfg(target_os = "linux")]
+fn read_mount_table() -> Vec<(PathBuf, bool)> {
+    crate::drives::read_mount_table()
+}
+
+/// macOS/Linux: 캐시된 마운트 테이블 (MOUNT_TABLE_TTL이 지났으면 다시 읽음)
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn mount_table() -> std::sync::Arc<Vec<(PathBuf, bool)>> {
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    let mut cached = MOUNT_TABLE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((loaded, table)) = cached.as_ref() {
+        if loaded.elapsed() < MOUNT_TABLE_TTL {
+            return Arc::clone(table);
+        }
+    }
+
+    let table = Arc::new(read_mount_table());
+    *cached = Some((Instant::now(), Arc::clone(&table)));
+    table
+}
+
+/// macOS/Linux: 네트워크 볼륨에 있는 경로인지 확인 (가장 긴 마운트 지점 기준)
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn is_network_mount(path: &str) -> bool {
+    let target = Path::new(path);
+    mount_table()
+        .iter()
+        .filter(|(mount_point, _)| target.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.as_os_str().len())
+        .is_some_and(|(_, is_network)| *is_network)
+}
+
+/// 캐시된 마운트 테이블 버리기 (드라이브 연결/해제 시 호출)
+pub fn invalidate_mount_table() {
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
+    {
+        *MOUNT_TABLE.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+}
+
+/// 네트워크 경로 여부 (UNC 또는 네트워크 볼륨)
+pub fn is_network_path(path: &str) -> bool {
+    is_unc_path(path) || is_network_mount(path)
+}
+
+/// 작업을 별도 스레드에서 실행하고 타임아웃 적용
+/// 타임아웃 시 스레드는 백그라운드에서 끝날 때까지 남아 있음 (OS 호출은 취소 불가)
+pub fn run_with_timeout<T, F>(timeout: Duration, task: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let _ = sender.send(task());
+    });
+
+    receiver
+        .recv_timeout(timeout)
+        .map_err(|_| format!("{} (timed out after {}s)", SHARE_UNREACHABLE, timeout.as_secs()))
+}
+
+// 공유 폴더 접근 오류 메시지 (없는 경로, 권한 없음은 따로, 그 외는 연결 불가)
+fn access_error(path: &str, e: &std::io::Error) -> String {
+    match e.kind() {
+        std::io::ErrorKind::NotFound => format!("Path does not exist: {}", path),
+        std::io::ErrorKind::PermissionDenied => format!("Permission denied: {}", path),
+        _ => format!("{}: {} ({})", SHARE_UNREACHABLE, path, e),
+    }
+}
+
+/// 네트워크 경로 검증 (canonicalize 생략, 타임아웃 적용)
+pub fn validate_network_path(path: &str) -> Result<PathBuf, String> {
+    let path_buf = PathBuf::from(path);
+
+    // canonicalize를 하지 않으므로 상위 디렉토리 참조는 직접 차단
+    if path_buf.components().any(|c| c == Component::ParentDir) {
+        return Err(format!("Invalid path: {}", path));
+    }
+
+    let probe = path_buf.clone();
+    let result = run_with_timeout(NETWORK_STAT_TIMEOUT, move || fs::metadata(&probe))
+        .map_err(|e| format!("{}: {}", e, path))?;
+
+    match result {
+        Ok(_) => Ok(path_buf),
+        Err(e) => Err(access_error(path, &e)),
+    }
+}
+
+/// 네트워크 폴더의 항목 목록 (이름, 경로, 디렉토리 여부)
+/// 항목별 canonicalize/metadata 호출 없이 file_type만 사용
+pub fn list_network_directory(path: &Path) -> Result<Vec<(String, PathBuf, bool)>, String> {
+    let dir = path.to_path_buf();
+
+    run_with_timeout(NETWORK_LIST_TIMEOUT, move || -> Result<Vec<(String, PathBuf, bool)>, String> {
+        let entries = fs::read_dir(&dir).map_err(|e| access_error(&dir.to_string_lossy(), &e))?;
+
+        let mut results = Vec::new();
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let is_dir = match entry.file_type() {
+                // 심볼릭 링크는 대상 확인 필요
+                Ok(file_type) if file_type.is_symlink() => {
+                    fs::metadata(entry.path()).map(|m| m.is_dir()).unwrap_or(false)
+                }
+                Ok(file_type) => file_type.is_dir(),
+                Err(_) => continue,
+            };
+            results.push((name, entry.path(), is_dir));
+        }
+        Ok(results)
+    })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_unc_path() {
+        assert!(is_unc_path("\\\\server\\share\\photos"));
+        assert!(is_unc_path("//server/share/photos"));
+        assert!(is_unc_path("\\\\?\\UNC\\server\\share"));
+        assert!(!is_unc_path("\\\\?\\C:\\photos"));
+        assert!(!is_unc_path("C:\\photos"));
+        assert!(!is_unc_path("/home/user/photos"));
+    }
+
+    #[test]
+    fn test_access_error() {
+        use std::io::{Error, ErrorKind};
+
+        let path = "//server/share/photos";
+        assert!(access_error(path, &Error::from(ErrorKind::NotFound)).starts_with("Path does not exist"));
+        assert!(access_error(path, &Error::from(ErrorKind::PermissionDenied)).starts_with("Permission denied"));
+        assert!(access_error(path, &Error::from(ErrorKind::TimedOut)).starts_with(SHARE_UNREACHABLE));
+    }
+}
@@ -0,0 +1,52 @@
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+use crate::single_instance::LaunchTarget;
+
+/// 프론트엔드 준비 전에 받은 열 대상
+struct OpenTargetState {
+    frontend_ready: bool,
+    pending: Option<LaunchTarget>,
+}
+
+static STATE: Mutex<OpenTargetState> = Mutex::new(OpenTargetState {
+    frontend_ready: false,
+    pending: None,
+});
+
+/// 열 대상 전달: 프론트엔드가 준비됐으면 open-target 이벤트, 아니면 준비될 때까지 보관 (마지막 것만)
+pub fn dispatch(app: &AppHandle, target: LaunchTarget) {
+    let Ok(mut state) = STATE.lock() else {
+        return;
+    };
+    if state.frontend_ready {
+        let _ = app.emit("open-target", &target);
+    } else {
+        state.pending = Some(target);
+    }
+}
+
+/// 프론트엔드가 open-target 리스너를 등록한 뒤 호출 (보관된 대상이 있으면 바로 전송)
+pub fn frontend_ready(app: &AppHandle) {
+    let Ok(mut state) = STATE.lock() else {
+        return;
+    };
+    state.frontend_ready = true;
+    if let Some(target) = state.pending.take() {
+        let _ = app.emit("open-target", &target);
+    }
+}
+
+/// macOS Finder의 open-file 이벤트 (여러 파일이면 처음 열 수 있는 것, 지원하지 않는 파일은 건너뜀)
+#[cfg(target_os = "macos")]
+pub fn open_urls(app: &AppHandle, urls: &[tauri::Url]) {
+    let target = urls
+        .iter()
+        .filter_map(|url| url.to_file_path().ok())
+        .find_map(|path| crate::single_instance::target_for(&path));
+
+    match target {
+        Some(target) => dispatch(app, target),
+        None => tracing::warn!("No openable path in {} opened files", urls.len()),
+    }
+}
@@ -0,0 +1,113 @@
+use rusqlite::params;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::AppHandle;
+
+use crate::file_lock;
+use crate::metadata_store::MetadataStore;
+use crate::rating_queue;
+
+/// 읽기 전용이라 파일에 쓰지 못한 별점 (쓸 수 있게 되면 파일에 씀)
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS pending_ratings (
+        path       TEXT PRIMARY KEY,
+        rating     INTEGER NOT NULL,
+        created_at INTEGER NOT NULL
+    );
+";
+
+/// 다시 쓰기를 시도하는 간격
+const FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 파일에 쓰기 대기 중인 별점
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingRating {
+    pub path: String,
+    pub rating: i32,
+    pub created_at: i64,
+}
+
+/// 대기 별점 테이블 초기화
+pub fn init_schema(store: &MetadataStore) -> Result<(), String> {
+    store.with_conn(|conn| conn.execute_batch(SCHEMA))
+}
+
+/// 대기 별점 저장 (인덱싱된 별점도 갱신해서 그리드에는 바로 보임)
+pub fn add(store: &MetadataStore, path: &str, rating: i32) -> Result<(), String> {
+    let now = chrono::Utc::now().timestamp();
+    store.with_conn(|conn| {
+        conn.execute(
+            "INSERT INTO pending_ratings (path, rating, created_at) VALUES (?1, ?2, ?3) \
+             ON CONFLICT(path) DO UPDATE SET rating = excluded.rating",
+            params![path, rating, now],
+        )
+    })?;
+    store.update_rating(path, rating)
+}
+
+/// 파일에 쓴 별점 제거
+pub fn remove(store: &MetadataStore, path: &str) -> Result<(), String> {
+    store.with_conn(|conn| conn.execute("DELETE FROM pending_ratings WHERE path = ?1", params![path]))?;
+    Ok(())
+}
+
+/// 대기 별점 목록 (오래된 순)
+pub fn list(store: &MetadataStore) -> Result<Vec<PendingRating>, String> {
+    store.with_conn(|conn| {
+        let mut stmt = conn.prepare_cached(
+            "SELECT path, rating, created_at FROM pending_ratings ORDER BY created_at, path",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(PendingRating {
+                path: row.get(0)?,
+                rating: row.get(1)?,
+                created_at: row.get(2)?,
+            })
+        })?;
+        rows.collect()
+    })
+}
+
+// 지금 쓸 수 있는지 (설정상 속성을 해제할 수 있으면 읽기 전용 속성은 무시)
+fn is_writable(path: &str) -> bool {
+    let auto_clear = crate::settings::current().auto_clear_read_only;
+    match file_lock::ensure_writable(path, auto_clear) {
+        Ok(cleared) => {
+            if cleared {
+                let _ = file_lock::set_read_only(path, true);
+            }
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// 쓸 수 있게 된 대기 별점을 쓰기 큐에 넣음 (넣은 수 반환, 쓰고 나면 목록에서 제거됨)
+pub fn flush(app: &AppHandle, store: &MetadataStore) -> Result<usize, String> {
+    let mut queued = 0;
+    for pending in list(store)? {
+        if !is_writable(&pending.path) {
+            continue;
+        }
+        rating_queue::enqueue(app, pending.path, pending.rating)?;
+        queued += 1;
+    }
+    Ok(queued)
+}
+
+/// 대기 별점을 주기적으로 다시 쓰기 (SD 카드 잠금 해제, 속성 변경 등)
+pub fn spawn_flush(app: AppHandle, store: Arc<MetadataStore>) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(FLUSH_INTERVAL).await;
+            let (app, store) = (app.clone(), Arc::clone(&store));
+            match tokio::task::spawn_blocking(move || flush(&app, &store)).await {
+                Ok(Ok(0)) => {}
+                Ok(Ok(queued)) => tracing::info!("Queued {} pending rating writes", queued),
+                Ok(Err(e)) => tracing::warn!("Failed to flush pending ratings: {}", e),
+                Err(e) => tracing::warn!("Pending rating flush task failed: {}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,185 @@
+use serde::Serialize;
+use std::io::Cursor;
+use std::sync::Arc;
+use tauri::Manager;
+
+use crate::formats::is_jpeg_file;
+use crate::metadata_store::MetadataStore;
+use crate::thumbnail::{self, encode_to_base64, ExifThumbnailError};
+
+/// 색 격자 한 변의 칸 수
+const GRID_SIZE: usize = 4;
+
+/// 픽셀을 4x4 칸으로 나눈 칸별 평균 색 ("rrggbb" 16개를 왼쪽 위부터 행 순서로 이어 붙임)
+/// channels는 픽셀당 바이트 수 (RGB 3, RGBA 4, 알파는 무시)
+pub fn color_grid(pixels: &[u8], width: u32, height: u32, channels: usize) -> Option<String> {
+    let (width, height) = (width as usize, height as usize);
+    if width == 0 || height == 0 || channels < 3 || pixels.len() < width * height * channels {
+        return None;
+    }
+
+    let mut sums = [[0u64; 3]; GRID_SIZE * GRID_SIZE];
+    let mut counts = [0u64; GRID_SIZE * GRID_SIZE];
+    for y in 0..height {
+        let row = y * GRID_SIZE / height;
+        for x in 0..width {
+            let cell = row * GRID_SIZE + x * GRID_SIZE / width;
+            let px = &pixels[(y * width + x) * channels..][..3];
+            for (sum, &value) in sums[cell].iter_mut().zip(px) {
+                *sum += value as u64;
+            }
+            counts[cell] += 1;
+        }
+    }
+
+    // 이미지가 4px보다 작으면 빈 칸은 검정
+    Some(
+        sums.iter()
+            .zip(counts)
+            .map(|(sum, count)| {
+                let average = sum.map(|total| total.checked_div(count).unwrap_or(0) as u8);
+                hex_color(average).trim_start_matches('#').to_string()
+            })
+            .collect(),
+    )
+}
+
+/// 썸네일 생성 때 디코딩한 픽셀로 색 격자를 계산해 저장 (실패해도 썸네일에는 영향 없음)
+pub fn record_color_grid(app: &tauri::AppHandle, file_path: &str, pixels: &[u8], width: u32, height: u32, has_alpha: bool) {
+    let Some(store) = app.try_state::<Arc<MetadataStore>>() else {
+        return;
+    };
+    let Some(grid) = color_grid(pixels, width, height, if has_alpha { 4 } else { 3 }) else {
+        return;
+    };
+    if let Err(e) = store.set_color_grid(file_path, &grid) {
+        tracing::warn!("Failed to store color grid: {}", e);
+    }
+}
+
+/// 큐가 파일에 닿기 전에 그리드에 먼저 그릴 자리 표시 (가장 싸게 얻을 수 있는 것 하나)
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FastPlaceholder {
+    /// 캐시된 WebP 썸네일
+    Cached {
+        thumbnail_base64: String,
+        width: u32,
+        height: u32,
+        orientation: u8,
+    },
+    /// EXIF 내장 썸네일 (JPEG 바이트)
+    Exif {
+        thumbnail_base64: String,
+        width: u32,
+        height: u32,
+        orientation: u8,
+    },
+    /// 원본 크기와 평균 색 (JPEG만 DC 계수로 색 계산, 그 외 형식은 크기만)
+    Color {
+        width: u32,
+        height: u32,
+        orientation: u8,
+        color: Option<String>,
+    },
+}
+
+fn hex_color(rgb: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", rgb[0], rgb[1], rgb[2])
+}
+
+// EXIF 방향 (다운로드되지 않은 클라우드 파일은 읽지 않음)
+fn orientation(source: &str, cloud_only: bool) -> u8 {
+    if cloud_only {
+        return 1;
+    }
+    thumbnail::extract_exif_metadata(source).map_or(1, |metadata| metadata.orientation)
+}
+
+/// 자리 표시 조회 (캐시 → EXIF 썸네일 → 크기/색 순, 얻을 수 없으면 None)
+/// 원격 파일과 다운로드되지 않은 클라우드 파일은 캐시만 확인 (원본을 받지 않음)
+pub fn get_fast_placeholder(app: &tauri::AppHandle, file_path: &str) -> Result<Option<FastPlaceholder>, String> {
+    if crate::remote_source::is_remote_path(file_path) {
+        return Ok(None);
+    }
+    let source = crate::fs_path::to_fs_string(file_path);
+    let cloud_only = crate::cloud_file::skip_fast_thumbnail(&source);
+
+    // 1. 캐시된 썸네일
+    let mtime = thumbnail::get_file_mtime(&source)?;
+    let cache_path = thumbnail::get_cache_path_for(app, file_path, mtime)?;
+    if let Some((webp_data, width, height)) = thumbnail::read_cached_webp(&cache_path, file_path)? {
+        return Ok(Some(FastPlaceholder::Cached {
+            thumbnail_base64: encode_to_base64(&webp_data),
+            width,
+            height,
+            orientation: orientation(&source, cloud_only),
+        }));
+    }
+
+    if cloud_only {
+        return Ok(None);
+    }
+
+    // 2. EXIF 내장 썸네일 (파일 앞부분만 읽음)
+    if is_jpeg_file(file_path) {
+        match thumbnail::extract_exif_thumbnail(&source) {
+            Ok(exif_thumb) => {
+                let dimensions = image::ImageReader::new(Cursor::new(&exif_thumb))
+                    .with_guessed_format()
+                    .ok()
+                    .and_then(|reader| reader.into_dimensions().ok());
+                if let Some((width, height)) = dimensions {
+                    return Ok(Some(FastPlaceholder::Exif {
+                        thumbnail_base64: encode_to_base64(&exif_thumb),
+                        width,
+                        height,
+                        orientation: orientation(&source, false),
+                    }));
+                }
+            }
+            Err(ExifThumbnailError::NotFound) => {}
+            Err(e) => tracing::debug!("Failed to extract EXIF thumbnail from {}: {}", file_path, e),
+        }
+
+        // 3. JPEG은 DC 계수로 평균 색
+        let (width, height, rgb) = thumbnail::jpeg_dc_color(&source)?;
+        return Ok(Some(FastPlaceholder::Color {
+            width,
+            height,
+            orientation: orientation(&source, false),
+            color: Some(hex_color(rgb)),
+        }));
+    }
+
+    // 그 밖의 형식은 헤더의 크기만 (읽을 수 없는 형식은 None)
+    Ok(image::image_dimensions(&source).ok().map(|(width, height)| FastPlaceholder::Color {
+        width,
+        height,
+        orientation: 1,
+        color: None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_color() {
+        assert_eq!(hex_color([255, 8, 128]), "#ff0880");
+    }
+
+    #[test]
+    fn test_color_grid() {
+        // 8x4 이미지: 왼쪽 절반 빨강, 오른쪽 절반 파랑
+        let pixels: Vec<u8> = (0..4)
+            .flat_map(|_| (0..8).flat_map(|x| if x < 4 { [255, 0, 0] } else { [0, 0, 255] }))
+            .collect();
+        let grid = color_grid(&pixels, 8, 4, 3).unwrap();
+        assert_eq!(grid.len(), 16 * 6);
+        assert_eq!(&grid[..6], "ff0000");
+        assert_eq!(&grid[18..24], "0000ff");
+        assert!(color_grid(&pixels, 8, 5, 3).is_none());
+    }
+}
@@ -0,0 +1,194 @@
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::thumbnail;
+
+/// 미리보기 기본 최대 크기 (긴 변 px)
+const DEFAULT_MAX_SIZE: u32 = 2400;
+
+/// 흑백/세피아 변환
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToneMode {
+    #[default]
+    Color,
+    Monochrome,
+    Sepia,
+}
+
+/// 미리보기 조정값 (원본은 바꾸지 않음)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PreviewAdjustments {
+    /// 노출 (EV, -5~5)
+    pub exposure: f32,
+    /// 대비 (-100~100)
+    pub contrast: f32,
+    /// 색온도 (-100 차갑게 ~ 100 따뜻하게)
+    pub temperature: f32,
+    /// 색조 (-100 녹색 ~ 100 마젠타)
+    pub tint: f32,
+    pub tone: ToneMode,
+    /// 결과 최대 크기 (긴 변 px, None이면 2400)
+    pub max_size: Option<u32>,
+}
+
+impl PreviewAdjustments {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(-5.0..=5.0).contains(&self.exposure) {
+            return Err(format!("Invalid exposure: {} (-5 to 5)", self.exposure));
+        }
+        for (name, value) in [("contrast", self.contrast), ("temperature", self.temperature), ("tint", self.tint)] {
+            if !(-100.0..=100.0).contains(&value) {
+                return Err(format!("Invalid {}: {} (-100 to 100)", name, value));
+            }
+        }
+        if let Some(max_size) = self.max_size {
+            if !(64..=8192).contains(&max_size) {
+                return Err(format!("Invalid max_size: {} (64-8192)", max_size));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 필터를 적용한 미리보기 (WebP)
+#[derive(Debug, Clone, Serialize)]
+pub struct PreviewImage {
+    pub data_base64: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.040_45 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// 채널별 변환표 (노출/화이트밸런스는 선형 값에 곱하고, 대비는 감마 값에서 중간 회색 기준으로 적용)
+fn build_luts(adjustments: &PreviewAdjustments) -> [[u8; 256]; 3] {
+    let exposure = 2f32.powf(adjustments.exposure);
+    let temperature = adjustments.temperature / 100.0 * 0.3;
+    let tint = adjustments.tint / 100.0 * 0.2;
+    let gains = [
+        exposure * (1.0 + temperature),
+        exposure * (1.0 - tint),
+        exposure * (1.0 - temperature),
+    ];
+    let contrast = 1.0 + adjustments.contrast / 100.0;
+
+    let mut luts = [[0u8; 256]; 3];
+    for (lut, gain) in luts.iter_mut().zip(gains) {
+        for (value, out) in lut.iter_mut().enumerate() {
+            let linear = srgb_to_linear(value as f32 / 255.0) * gain;
+            let encoded = thumbnail::linear_to_srgb_u8(linear) as f32 / 255.0;
+            let contrasted = ((encoded - 0.5) * contrast + 0.5).clamp(0.0, 1.0);
+            *out = (contrasted * 255.0).round() as u8;
+        }
+    }
+    luts
+}
+
+/// RGB 픽셀에 조정값 적용
+pub fn apply_adjustments(rgb: &mut [u8], adjustments: &PreviewAdjustments) {
+    let luts = build_luts(adjustments);
+    let tone = adjustments.tone;
+
+    rgb.par_chunks_exact_mut(3).for_each(|px| {
+        let r = luts[0][px[0] as usize] as f32;
+        let g = luts[1][px[1] as usize] as f32;
+        let b = luts[2][px[2] as usize] as f32;
+
+        let out = match tone {
+            ToneMode::Color => [r, g, b],
+            ToneMode::Monochrome => {
+                let luma = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+                [luma, luma, luma]
+            }
+            ToneMode::Sepia => [
+                0.393 * r + 0.769 * g + 0.189 * b,
+                0.349 * r + 0.686 * g + 0.168 * b,
+                0.272 * r + 0.534 * g + 0.131 * b,
+            ],
+        };
+        for (dst, value) in px.iter_mut().zip(out) {
+            *dst = value.round().clamp(0.0, 255.0) as u8;
+        }
+    });
+}
+
+/// 미리보기용 디코딩 (JPEG/RAW는 뷰어와 같은 고해상도 미리보기, 그 밖은 원본)
+fn decode_preview(file_path: &str, max_size: u32) -> Result<(Vec<u8>, u32, u32), String> {
+    let img = if crate::formats::is_jpeg_file(file_path) || crate::formats::is_raw_file(file_path) {
+        let jpeg = thumbnail::extract_raw_preview(file_path)?;
+        image::load_from_memory(&jpeg).map_err(|e| format!("Failed to decode preview: {}", e))?
+    } else {
+        let (img, icc_profile) = thumbnail::open_image_with_icc(file_path)?;
+        let mut rgb = thumbnail::tonemap_to_8bit(img).into_rgb8();
+        crate::color_profile::convert_to_srgb(
+            icc_profile.as_deref(),
+            &mut rgb,
+            crate::color_profile::PixelLayout::Rgb8,
+        );
+        image::DynamicImage::ImageRgb8(rgb)
+    };
+
+    let (src_width, src_height) = (img.width(), img.height());
+    let rgb = img.into_rgb8().into_raw();
+    if src_width <= max_size && src_height <= max_size {
+        return Ok((rgb, src_width, src_height));
+    }
+
+    let (width, height) = thumbnail::fit_within(src_width, src_height, max_size);
+    let resized = thumbnail::resize_fast(
+        rgb,
+        src_width,
+        src_height,
+        width,
+        height,
+        fast_image_resize::PixelType::U8x3,
+    )?;
+    Ok((resized, width, height))
+}
+
+/// 조정값을 적용한 미리보기 렌더링 (WebP)
+pub fn render_preview(file_path: &str, adjustments: &PreviewAdjustments) -> Result<PreviewImage, String> {
+    let max_size = adjustments.max_size.unwrap_or(DEFAULT_MAX_SIZE);
+    let (mut rgb, width, height) = decode_preview(file_path, max_size)?;
+    apply_adjustments(&mut rgb, adjustments);
+    crate::monitor_profile::soft_proof(&mut rgb, crate::color_profile::PixelLayout::Rgb8);
+
+    let webp = thumbnail::encode_thumbnail_to_webp(&rgb, width, height, false, 85.0)?;
+    Ok(PreviewImage {
+        data_base64: thumbnail::encode_to_base64(&webp),
+        width,
+        height,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_adjustments() {
+        // 기본값은 그대로
+        let mut rgb = vec![10, 128, 250, 0, 64, 255];
+        apply_adjustments(&mut rgb, &PreviewAdjustments::default());
+        assert_eq!(rgb, vec![10, 128, 250, 0, 64, 255]);
+
+        // 흑백은 세 채널이 같고, 노출을 올리면 밝아짐
+        let adjustments = PreviewAdjustments {
+            exposure: 1.0,
+            tone: ToneMode::Monochrome,
+            ..Default::default()
+        };
+        let mut rgb = vec![10, 128, 250];
+        apply_adjustments(&mut rgb, &adjustments);
+        assert!(rgb[0] == rgb[1] && rgb[1] == rgb[2]);
+        assert!(rgb[0] > 110);
+    }
+}
@@ -0,0 +1,355 @@
+use image::{DynamicImage, RgbImage};
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+
+/// 이미지 리소스 ID: 썸네일 (Photoshop 5.0 이상, JPEG RGB)
+const RESOURCE_THUMBNAIL: u16 = 1036;
+/// 이미지 리소스 ID: 썸네일 (Photoshop 4.0, JPEG BGR)
+const RESOURCE_THUMBNAIL_LEGACY: u16 = 1033;
+
+/// 썸네일 리소스의 JPEG 앞 헤더 크기
+const THUMBNAIL_HEADER_LEN: usize = 28;
+
+/// 색 모드
+const MODE_GRAYSCALE: u16 = 1;
+const MODE_RGB: u16 = 3;
+const MODE_CMYK: u16 = 4;
+
+/// PSD 파일 헤더
+#[derive(Debug, Clone, Copy)]
+struct Header {
+    /// PSB (대용량 문서, 길이 필드가 더 큼)
+    large: bool,
+    channels: u16,
+    width: u32,
+    height: u32,
+    depth: u16,
+    color_mode: u16,
+}
+
+/// PSD 합성 이미지 정보
+#[derive(Debug, Clone, Serialize)]
+pub struct PsdInfo {
+    pub width: u32,
+    pub height: u32,
+    pub depth: u16,
+    /// 전체 해상도 합성 이미지 유무 ("호환성 최대화"를 끄고 저장하면 내장 썸네일만 있음)
+    pub has_full_composite: bool,
+}
+
+fn read_u16(reader: &mut impl Read) -> std::io::Result<u16> {
+    let mut buf = [0u8; 2];
+    reader.read_exact(&mut buf)?;
+    Ok(u16::from_be_bytes(buf))
+}
+
+fn read_u32(reader: &mut impl Read) -> std::io::Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_be_bytes(buf))
+}
+
+fn read_u64(reader: &mut impl Read) -> std::io::Result<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_be_bytes(buf))
+}
+
+fn read_header(reader: &mut impl Read) -> Result<Header, String> {
+    let mut signature = [0u8; 4];
+    reader
+        .read_exact(&mut signature)
+        .map_err(|e| format!("Failed to read PSD header: {}", e))?;
+    if &signature != b"8BPS" {
+        return Err("Not a PSD file".to_string());
+    }
+
+    read_header_fields(reader).map_err(|e| format!("Failed to read PSD header: {}", e))
+}
+
+fn read_header_fields(reader: &mut impl Read) -> std::io::Result<Header> {
+    let version = read_u16(reader)?;
+    let mut reserved = [0u8; 6];
+    reader.read_exact(&mut reserved)?;
+    let channels = read_u16(reader)?;
+    let height = read_u32(reader)?;
+    let width = read_u32(reader)?;
+    let depth = read_u16(reader)?;
+    let color_mode = read_u16(reader)?;
+    Ok(Header {
+        large: version == 2,
+        channels,
+        width,
+        height,
+        depth,
+        color_mode,
+    })
+}
+
+/// 파일을 열어 헤더를 읽고 이미지 리소스 섹션 위치로 이동
+/// 반환: (헤더, 리더, 이미지 리소스 섹션 길이)
+fn open(file_path: &str) -> Result<(Header, BufReader<File>, u32), String> {
+    let file = File::open(file_path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut reader = BufReader::new(file);
+    let header = read_header(&mut reader)?;
+
+    let seek = |reader: &mut BufReader<File>| -> std::io::Result<u32> {
+        let color_mode_len = read_u32(reader)?;
+        reader.seek(SeekFrom::Current(color_mode_len as i64))?;
+        read_u32(reader)
+    };
+    let resources_len = seek(&mut reader).map_err(|e| format!("Failed to read PSD sections: {}", e))?;
+    Ok((header, reader, resources_len))
+}
+
+/// 이미지 리소스 섹션에서 내장 썸네일 찾기 (리더는 섹션 시작 위치)
+fn find_thumbnail(reader: &mut impl Read, resources_len: u32) -> Result<Option<(u16, Vec<u8>)>, String> {
+    let mut resources = vec![0u8; resources_len as usize];
+    reader
+        .read_exact(&mut resources)
+        .map_err(|e| format!("Failed to read PSD resources: {}", e))?;
+
+    let mut pos = 0usize;
+    let mut legacy = None;
+    while pos + 12 <= resources.len() && &resources[pos..pos + 4] == b"8BIM" {
+        let id = u16::from_be_bytes([resources[pos + 4], resources[pos + 5]]);
+        // 이름 (파스칼 문자열, 길이 바이트 포함 짝수로 맞춤)
+        let name_len = resources[pos + 6] as usize;
+        let mut cursor = pos + 6 + ((name_len + 2) & !1);
+        let Some(size_bytes) = resources.get(cursor..cursor + 4) else {
+            break;
+        };
+        let size = u32::from_be_bytes([size_bytes[0], size_bytes[1], size_bytes[2], size_bytes[3]]) as usize;
+        cursor += 4;
+        let Some(data) = resources.get(cursor..cursor + size) else {
+            break;
+        };
+
+        if id == RESOURCE_THUMBNAIL && data.len() > THUMBNAIL_HEADER_LEN {
+            return Ok(Some((id, data[THUMBNAIL_HEADER_LEN..].to_vec())));
+        }
+        if id == RESOURCE_THUMBNAIL_LEGACY && data.len() > THUMBNAIL_HEADER_LEN {
+            legacy = Some((id, data[THUMBNAIL_HEADER_LEN..].to_vec()));
+        }
+        pos = cursor + ((size + 1) & !1);
+    }
+    Ok(legacy)
+}
+
+/// 내장 썸네일 (저해상도, 긴 변 160px 정도)
+fn embedded_thumbnail(file_path: &str) -> Result<DynamicImage, String> {
+    let (_, mut reader, resources_len) = open(file_path)?;
+    let (id, jpeg) = find_thumbnail(&mut reader, resources_len)?.ok_or("PSD has no embedded thumbnail")?;
+
+    let mut img = image::load_from_memory(&jpeg)
+        .map_err(|e| format!("Failed to decode PSD thumbnail: {}", e))?
+        .into_rgb8();
+    // Photoshop 4.0 썸네일은 BGR 순서
+    if id == RESOURCE_THUMBNAIL_LEGACY {
+        for px in img.pixels_mut() {
+            px.0.swap(0, 2);
+        }
+    }
+    Ok(DynamicImage::ImageRgb8(img))
+}
+
+/// PackBits(RLE) 한 줄 풀기
+fn unpack_bits(src: &[u8], dst: &mut Vec<u8>, row_len: usize) -> Result<(), String> {
+    let start = dst.len();
+    let mut pos = 0;
+    while pos < src.len() && dst.len() - start < row_len {
+        let n = src[pos] as i8;
+        pos += 1;
+        if n >= 0 {
+            let count = n as usize + 1;
+            let literal = src.get(pos..pos + count).ok_or("Truncated PSD RLE data")?;
+            dst.extend_from_slice(literal);
+            pos += count;
+        } else if n != -128 {
+            let value = *src.get(pos).ok_or("Truncated PSD RLE data")?;
+            dst.resize(dst.len() + (1 - n as isize) as usize, value);
+            pos += 1;
+        }
+    }
+    dst.resize(start + row_len, 0);
+    Ok(())
+}
+
+/// 합성 이미지 섹션 디코딩 (채널별 평면, 무압축 또는 RLE)
+/// 필요한 채널(Gray 1, RGB 3, CMYK 4)만 8비트로 읽음
+fn read_composite(header: &Header, reader: &mut (impl Read + Seek)) -> Result<RgbImage, String> {
+    let color_channels = match header.color_mode {
+        MODE_GRAYSCALE => 1,
+        MODE_RGB => 3,
+        MODE_CMYK => 4,
+        mode => return Err(format!("Unsupported PSD color mode: {}", mode)),
+    };
+    if header.depth != 8 && header.depth != 16 {
+        return Err(format!("Unsupported PSD bit depth: {}", header.depth));
+    }
+    if (header.channels as usize) < color_channels {
+        return Err(format!("Invalid PSD channel count: {}", header.channels));
+    }
+
+    let io_err = |e: std::io::Error| format!("Failed to read PSD image data: {}", e);
+    // 레이어/마스크 정보 섹션 건너뜀
+    let layer_len = if header.large { read_u64(reader).map_err(io_err)? } else { read_u32(reader).map_err(io_err)? as u64 };
+    reader.seek(SeekFrom::Current(layer_len as i64)).map_err(io_err)?;
+
+    let (width, height) = (header.width as usize, header.height as usize);
+    let bytes_per_sample = header.depth as usize / 8;
+    let row_len = width * bytes_per_sample;
+    let compression = read_u16(reader).map_err(io_err)?;
+
+    let mut planes: Vec<Vec<u8>> = Vec::with_capacity(color_channels);
+    match compression {
+        0 => {
+            for _ in 0..color_channels {
+                let mut plane = vec![0u8; row_len * height];
+                reader.read_exact(&mut plane).map_err(io_err)?;
+                planes.push(plane);
+            }
+        }
+        1 => {
+            // 모든 채널의 줄별 압축 길이 (PSB는 4바이트)
+            let row_count = header.channels as usize * height;
+            let mut lengths = Vec::with_capacity(row_count);
+            for _ in 0..row_count {
+                let len = if header.large { read_u32(reader).map_err(io_err)? as usize } else { read_u16(reader).map_err(io_err)? as usize };
+                lengths.push(len);
+            }
+
+            let mut buf = Vec::new();
+            for channel_lengths in lengths.chunks(height).take(color_channels) {
+                let mut plane = Vec::with_capacity(row_len * height);
+                for &len in channel_lengths {
+                    buf.resize(len, 0);
+                    reader.read_exact(&mut buf).map_err(io_err)?;
+                    unpack_bits(&buf, &mut plane, row_len)?;
+                }
+                planes.push(plane);
+            }
+        }
+        other => return Err(format!("Unsupported PSD compression: {}", other)),
+    }
+
+    // 16비트는 상위 바이트만 사용 (빅 엔디언)
+    let sample = |plane: &[u8], index: usize| plane[index * bytes_per_sample];
+    let mut rgb = Vec::with_capacity(width * height * 3);
+    for i in 0..width * height {
+        match header.color_mode {
+            MODE_GRAYSCALE => {
+                let v = sample(&planes[0], i);
+                rgb.extend_from_slice(&[v, v, v]);
+            }
+            MODE_CMYK => {
+                // PSD의 CMYK는 반전 저장 (255가 잉크 없음)
+                let k = sample(&planes[3], i) as u32;
+                for plane in &planes[..3] {
+                    rgb.push((sample(plane, i) as u32 * k / 255) as u8);
+                }
+            }
+            _ => {
+                for plane in &planes {
+                    rgb.push(sample(plane, i));
+                }
+            }
+        }
+    }
+    RgbImage::from_raw(header.width, header.height, rgb).ok_or_else(|| "Invalid PSD dimensions".to_string())
+}
+
+/// 합성 이미지가 모두 흰색인지 ("호환성 최대화"를 끄면 빈 흰색 이미지가 저장됨)
+fn is_blank(img: &RgbImage) -> bool {
+    img.as_raw().iter().all(|&v| v == 255)
+}
+
+/// 합성 이미지 디코딩 (전체 해상도가 없으면 내장 썸네일)
+/// 반환: (이미지, 저해상도 여부)
+pub fn decode(file_path: &str) -> Result<(DynamicImage, bool), String> {
+    let (header, mut reader, resources_len) = open(file_path)?;
+    reader
+        .seek(SeekFrom::Current(resources_len as i64))
+        .map_err(|e| format!("Failed to read PSD sections: {}", e))?;
+
+    match read_composite(&header, &mut reader) {
+        Ok(img) if !is_blank(&img) => Ok((DynamicImage::ImageRgb8(img), false)),
+        // 정말 흰 문서일 수 있으므로 썸네일이 없으면 그대로 사용
+        Ok(img) => Ok(embedded_thumbnail(file_path)
+            .map(|thumbnail| (thumbnail, true))
+            .unwrap_or((DynamicImage::ImageRgb8(img), false))),
+        Err(e) => {
+            tracing::debug!("PSD composite unavailable for {}: {}", file_path, e);
+            embedded_thumbnail(file_path).map(|img| (img, true))
+        }
+    }
+}
+
+/// 썸네일용 합성 이미지 (max_size 이내로 축소, RGB)
+pub fn generate_thumbnail(file_path: &str, max_size: u32) -> Result<(Vec<u8>, u32, u32), String> {
+    let (img, _) = decode(file_path)?;
+    let (src_width, src_height) = (img.width(), img.height());
+    let rgb = img.into_rgb8().into_raw();
+    if src_width <= max_size && src_height <= max_size {
+        return Ok((rgb, src_width, src_height));
+    }
+
+    let (width, height) = crate::thumbnail::fit_within(src_width, src_height, max_size);
+    let resized = crate::thumbnail::resize_fast(
+        rgb,
+        src_width,
+        src_height,
+        width,
+        height,
+        fast_image_resize::PixelType::U8x3,
+    )?;
+    Ok((resized, width, height))
+}
+
+/// PSD 정보 (크기, 전체 해상도 합성 이미지 유무)
+pub fn get_info(file_path: &str) -> Result<PsdInfo, String> {
+    let (header, _, _) = open(file_path)?;
+    let (_, low_res) = decode(file_path)?;
+    Ok(PsdInfo {
+        width: header.width,
+        height: header.height,
+        depth: header.depth,
+        has_full_composite: !low_res,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unpack_bits() {
+        // 리터럴 3바이트 + 반복 4바이트
+        let src = [0x02, 1, 2, 3, 0xFD, 9];
+        let mut dst = Vec::new();
+        unpack_bits(&src, &mut dst, 7).unwrap();
+        assert_eq!(dst, vec![1, 2, 3, 9, 9, 9, 9]);
+
+        // 잘린 데이터는 오류
+        let mut dst = Vec::new();
+        assert!(unpack_bits(&[0x05, 1], &mut dst, 6).is_err());
+    }
+
+    #[test]
+    fn test_read_composite_raw() {
+        // 2x1 RGB 8비트, 무압축
+        let header = Header {
+            large: false,
+            channels: 3,
+            width: 2,
+            height: 1,
+            depth: 8,
+            color_mode: MODE_RGB,
+        };
+        let mut data = vec![0, 0, 0, 0, 0, 0];
+        data.extend_from_slice(&[10, 20, 30, 40, 50, 60]);
+        let img = read_composite(&header, &mut std::io::Cursor::new(data)).unwrap();
+        assert_eq!(img.as_raw(), &vec![10, 30, 50, 20, 40, 60]);
+    }
+}
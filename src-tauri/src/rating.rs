@@ -1,7 +1,12 @@
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use xmp_toolkit::{XmpFile, XmpMeta, XmpValue};
 use exif::{In, Reader, Tag};
 
+use crate::file_lock;
+use crate::metadata_store::MetadataStore;
+
 const XMP_NS_XMP: &str = "http://ns.adobe.com/xap/1.0/";
 
 /// XMP Rating 읽기
@@ -26,6 +31,79 @@ pub fn read_rating(file_path: &str) -> Result<i32, String> {
     }
 }
 
+/// 파일의 XMP (없거나 열 수 없으면 None)
+pub fn read_xmp(file_path: &str) -> Option<XmpMeta> {
+    let mut xmp_file = XmpFile::new().ok()?;
+    xmp_file
+        .open_file(file_path, xmp_toolkit::OpenFileOptions::default().only_xmp())
+        .ok()?;
+    xmp_file.xmp()
+}
+
+/// XMP Rating 값 (없거나 잘못된 값이면 0)
+pub fn xmp_rating(xmp: &XmpMeta) -> i32 {
+    xmp.property(XMP_NS_XMP, "Rating")
+        .and_then(|rating| rating.value.parse().ok())
+        .unwrap_or(0)
+}
+
+/// 별점 저장 위치 (그리드 표시용)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RatingSource {
+    /// 파일의 XMP
+    Xmp,
+    /// XMP를 쓸 수 없는 형식이라 앱 DB에 저장
+    Database,
+    /// 읽기 전용이라 앱에 보관 중 (쓸 수 있게 되면 XMP에 씀)
+    Pending,
+}
+
+/// XMP를 쓸 수 없는 형식이라 별점을 앱 DB에 저장하는 파일인지
+pub fn uses_database(file_path: &str) -> bool {
+    !crate::formats::supports_xmp(file_path)
+}
+
+/// DB에 별점을 저장하는 파일들의 별점 (별점이 없으면 0, XMP 파일은 빠짐)
+pub fn database_ratings<'a>(
+    store: &MetadataStore,
+    file_paths: impl IntoIterator<Item = &'a String>,
+) -> Result<HashMap<String, i32>, String> {
+    let paths: Vec<String> = file_paths.into_iter().filter(|path| uses_database(path)).cloned().collect();
+    if paths.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let stored = store.get_db_ratings(&paths)?;
+    Ok(paths
+        .into_iter()
+        .map(|path| {
+            let rating = stored.get(&path).copied().unwrap_or(0);
+            (path, rating)
+        })
+        .collect())
+}
+
+/// 파일별 별점 저장 위치
+pub fn rating_sources(store: &MetadataStore, file_paths: Vec<String>) -> Result<Vec<(String, RatingSource)>, String> {
+    let pending: HashSet<String> = crate::pending_ratings::list(store)?
+        .into_iter()
+        .map(|pending| pending.path)
+        .collect();
+    Ok(file_paths
+        .into_iter()
+        .map(|path| {
+            let source = if uses_database(&path) {
+                RatingSource::Database
+            } else if pending.contains(&path) {
+                RatingSource::Pending
+            } else {
+                RatingSource::Xmp
+            };
+            (path, source)
+        })
+        .collect())
+}
+
 /// 여러 이미지의 별점을 배치로 읽기 (병렬 처리)
 pub fn read_ratings_batch(file_paths: Vec<String>) -> Vec<(String, Option<i32>)> {
     use rayon::prelude::*;
@@ -45,6 +123,53 @@ pub fn write_rating(file_path: &str, rating: i32) -> Result<(), String> {
         return Err(format!("유효하지 않은 별점: {}. 0-5 사이여야 합니다.", rating));
     }
 
+    update_xmp(file_path, |xmp| {
+        if rating == 0 {
+            // 0이면 Rating 프로퍼티 삭제 (unrated)
+            let _ = xmp.delete_property(XMP_NS_XMP, "Rating");
+        } else {
+            xmp.set_property(
+                XMP_NS_XMP,
+                "Rating",
+                &XmpValue::from(rating.to_string())
+            ).map_err(|e| format!("Rating 설정 실패: {}", e))?;
+        }
+        Ok(())
+    })
+}
+
+/// XMP Label(색상 라벨) 쓰기, None이면 삭제 (파일 수정 시간 복원 포함)
+pub fn write_label(file_path: &str, label: Option<&str>) -> Result<(), String> {
+    update_xmp(file_path, |xmp| {
+        match label {
+            Some(label) => xmp.set_property(XMP_NS_XMP, "Label", &XmpValue::from(label))
+                .map_err(|e| format!("Label 설정 실패: {}", e))?,
+            None => {
+                let _ = xmp.delete_property(XMP_NS_XMP, "Label");
+            }
+        }
+        Ok(())
+    })
+}
+
+/// 파일의 XMP를 수정해서 저장 (파일 수정 시간을 촬영 시간으로 복원)
+/// 읽기 전용 파일은 설정에 따라 속성을 잠시 해제하거나 READ_ONLY 오류 반환
+pub fn update_xmp(file_path: &str, update: impl FnOnce(&mut XmpMeta) -> Result<(), String>) -> Result<(), String> {
+    let auto_clear = crate::settings::current().auto_clear_read_only;
+    let cleared = file_lock::ensure_writable(file_path, auto_clear)?;
+
+    let result = write_xmp(file_path, update);
+
+    // 해제했던 읽기 전용 속성 복원
+    if cleared {
+        if let Err(e) = file_lock::set_read_only(file_path, true) {
+            tracing::warn!("Failed to restore read-only attribute for {}: {}", file_path, e);
+        }
+    }
+    result
+}
+
+fn write_xmp(file_path: &str, update: impl FnOnce(&mut XmpMeta) -> Result<(), String>) -> Result<(), String> {
     // EXIF에서 촬영 시간 읽기
     let original_datetime = read_exif_datetime(file_path)?;
 
@@ -65,17 +190,7 @@ pub fn write_rating(file_path: &str, rating: i32) -> Result<(), String> {
             None => XmpMeta::new().map_err(|e| format!("XMP 생성 실패: {}", e))?
         };
 
-        // Rating 프로퍼티 설정
-        if rating == 0 {
-            // 0이면 Rating 프로퍼티 삭제 (unrated)
-            let _ = xmp.delete_property(XMP_NS_XMP, "Rating");
-        } else {
-            xmp.set_property(
-                XMP_NS_XMP,
-                "Rating",
-                &XmpValue::from(rating.to_string())
-            ).map_err(|e| format!("Rating 설정 실패: {}", e))?;
-        }
+        update(&mut xmp)?;
 
         // XMP 업데이트
         xmp_file.put_xmp(&xmp).map_err(|e| format!("XMP 업데이트 실패: {}", e))?;
@@ -0,0 +1,244 @@
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::file_lock;
+use crate::folder_watcher::FolderWatcher;
+use crate::metadata_store::MetadataStore;
+use crate::pending_ratings;
+use crate::rating;
+use crate::thumbnail;
+
+lazy_static! {
+    /// 쓰기 작업이 진행 중인 파일과 아직 쓰지 않은 마지막 별점 (None이면 대기 없음)
+    static ref PENDING: Mutex<HashMap<String, Option<i32>>> = Mutex::new(HashMap::new());
+}
+
+/// 별점 쓰기 실패 이벤트 (rating-write-failed)
+#[derive(Debug, Clone, Serialize)]
+pub struct RatingWriteFailure {
+    pub path: String,
+    pub rating: i32,
+    /// 마지막으로 읽은 뒤 다른 프로그램이 파일을 수정해서 쓰지 않음
+    pub conflict: bool,
+    /// 읽기 전용 파일이나 쓰기 금지된 볼륨
+    pub read_only: bool,
+    /// 앱에 보관했다가 쓸 수 있게 되면 파일에 씀
+    pub pending: bool,
+    pub error: String,
+}
+
+enum WriteError {
+    /// 파일 수정 시간이 마지막으로 확인한 값과 다름
+    Conflict(u64),
+    /// 읽기 전용이라 쓰지 못함
+    ReadOnly(String),
+    Failed(String),
+}
+
+/// 별점 쓰기 예약 (파일마다 순서대로 쓰고, 쓰는 동안 들어온 요청은 마지막 값만 씀)
+pub fn enqueue(app: &AppHandle, path: String, rating: i32) -> Result<(), String> {
+    if !(0..=5).contains(&rating) {
+        return Err(format!("유효하지 않은 별점: {}. 0-5 사이여야 합니다.", rating));
+    }
+
+    {
+        let mut pending = PENDING.lock().unwrap();
+        if let Some(next) = pending.get_mut(&path) {
+            *next = Some(rating);
+            return Ok(());
+        }
+        pending.insert(path.clone(), Some(rating));
+    }
+
+    tauri::async_runtime::spawn(run(app.clone(), path));
+    Ok(())
+}
+
+/// 쓰기 대기 중인 파일 수 (종료 시 대기용)
+pub fn pending_count() -> usize {
+    PENDING.lock().unwrap().len()
+}
+
+// 다음에 쓸 별점 꺼내기 (없으면 작업 종료)
+fn take_next(path: &str) -> Option<i32> {
+    let mut pending = PENDING.lock().unwrap();
+    let next = pending.get_mut(path).and_then(Option::take);
+    if next.is_none() {
+        pending.remove(path);
+    }
+    next
+}
+
+// 파일별 쓰기 작업
+async fn run(app: AppHandle, path: String) {
+    let store = app.try_state::<Arc<MetadataStore>>().map(|store| Arc::clone(&store));
+
+    // 기준 수정 시간: 인덱싱할 때 읽은 값, 인덱싱되지 않았으면 지금 값
+    let mut expected_mtime = {
+        let (store, path) = (store.clone(), path.clone());
+        tokio::task::spawn_blocking(move || {
+            store
+                .and_then(|store| store.get_records(std::slice::from_ref(&path)).ok())
+                .and_then(|mut records| records.remove(&path))
+                .map(|record| record.mtime)
+                .or_else(|| thumbnail::get_file_mtime(&path).ok())
+        })
+        .await
+        .ok()
+        .flatten()
+    };
+
+    while let Some(rating) = take_next(&path) {
+        if let Some(watcher) = app.try_state::<Arc<tokio::sync::Mutex<FolderWatcher>>>() {
+            // 폴더 감시의 외부 변경 감지에서 중복 이벤트 방지
+            watcher.lock().await.remember_rating(&path, rating);
+        }
+
+        match write(&path, rating, expected_mtime, store.clone()).await {
+            Ok(mtime) => {
+                expected_mtime = Some(mtime);
+                let _ = app.emit("rating-changed", serde_json::json!({
+                    "path": path,
+                    "rating": rating
+                }));
+            }
+            Err(WriteError::Conflict(mtime)) => {
+                tracing::warn!("Rating write skipped, {} was modified externally", path);
+                // 밀린 요청도 바뀌기 전 상태를 보고 한 것이라 버림
+                PENDING.lock().unwrap().remove(&path);
+                let _ = app.emit("rating-write-failed", RatingWriteFailure {
+                    path: path.clone(),
+                    rating,
+                    conflict: true,
+                    read_only: false,
+                    pending: false,
+                    error: format!("File was modified externally (mtime {})", mtime),
+                });
+                refresh(&app, &path, store).await;
+                return;
+            }
+            Err(WriteError::ReadOnly(error)) => {
+                tracing::warn!("Rating for read-only file {} kept in app: {}", path, error);
+                let pending = keep_pending(&path, rating, store.clone()).await;
+                let _ = app.emit("rating-write-failed", RatingWriteFailure {
+                    path: path.clone(),
+                    rating,
+                    conflict: false,
+                    read_only: true,
+                    pending,
+                    error,
+                });
+            }
+            Err(WriteError::Failed(error)) => {
+                tracing::warn!("Failed to write rating for {}: {}", path, error);
+                let _ = app.emit("rating-write-failed", RatingWriteFailure {
+                    path: path.clone(),
+                    rating,
+                    conflict: false,
+                    read_only: false,
+                    pending: false,
+                    error,
+                });
+            }
+        }
+    }
+}
+
+// 충돌 확인 후 쓰기 (사용 중인 파일은 재시도), 쓴 뒤의 수정 시간 반환
+async fn write(
+    path: &str,
+    rating: i32,
+    expected_mtime: Option<u64>,
+    store: Option<Arc<MetadataStore>>,
+) -> Result<u64, WriteError> {
+    // XMP를 쓸 수 없는 형식은 앱 DB에 저장
+    if rating::uses_database(path) {
+        let Some(store) = store else {
+            return Err(WriteError::Failed("Metadata store is not available".to_string()));
+        };
+        let path = path.to_string();
+        return tokio::task::spawn_blocking(move || {
+            store.set_db_rating(&path, rating)?;
+            thumbnail::get_file_mtime(&path)
+        })
+        .await
+        .unwrap_or_else(|e| Err(format!("Task failed: {}", e)))
+        .map_err(WriteError::Failed);
+    }
+
+    let result = file_lock::retry_locked(|| {
+        let path = path.to_string();
+        async move {
+            tokio::task::spawn_blocking(move || {
+                let mtime = thumbnail::get_file_mtime(&path)?;
+                if expected_mtime.is_some_and(|expected| expected != mtime) {
+                    return Ok(Err(mtime));
+                }
+                rating::write_rating(&path, rating)?;
+                thumbnail::get_file_mtime(&path).map(Ok)
+            })
+            .await
+            .unwrap_or_else(|e| Err(format!("Task failed: {}", e)))
+        }
+    })
+    .await;
+
+    let mtime = match result {
+        Ok(Ok(mtime)) => mtime,
+        Ok(Err(mtime)) => return Err(WriteError::Conflict(mtime)),
+        Err(e) if file_lock::is_read_only_error(&e) => return Err(WriteError::ReadOnly(e)),
+        Err(e) => return Err(WriteError::Failed(e)),
+    };
+
+    // 인덱싱된 별점도 갱신 (스마트 앨범 라이브 업데이트), 대기 중이던 별점은 제거
+    if let Some(store) = store {
+        let path = path.to_string();
+        let updated = tokio::task::spawn_blocking(move || {
+            pending_ratings::remove(&store, &path)?;
+            store.update_rating_mtime(&path, rating, mtime)
+        })
+        .await;
+        if let Ok(Err(e)) = updated {
+            tracing::warn!("Failed to update indexed rating: {}", e);
+        }
+    }
+    Ok(mtime)
+}
+
+// 읽기 전용 파일의 별점을 앱에 보관 (보관했으면 true)
+async fn keep_pending(path: &str, rating: i32, store: Option<Arc<MetadataStore>>) -> bool {
+    let Some(store) = store else {
+        return false;
+    };
+    let path = path.to_string();
+    match tokio::task::spawn_blocking(move || pending_ratings::add(&store, &path, rating)).await {
+        Ok(Ok(())) => true,
+        Ok(Err(e)) => {
+            tracing::warn!("Failed to keep pending rating: {}", e);
+            false
+        }
+        Err(_) => false,
+    }
+}
+
+// 외부에서 바뀐 별점을 다시 읽어 알림
+async fn refresh(app: &AppHandle, path: &str, store: Option<Arc<MetadataStore>>) {
+    let read_path = path.to_string();
+    let rating = tokio::task::spawn_blocking(move || {
+        if let Some(store) = store {
+            let _ = store.index_files(std::slice::from_ref(&read_path));
+        }
+        rating::read_rating(&read_path)
+    })
+    .await;
+
+    if let Ok(Ok(rating)) = rating {
+        let _ = app.emit("rating-changed", serde_json::json!({
+            "path": path,
+            "rating": rating
+        }));
+    }
+}
@@ -0,0 +1,86 @@
+use std::io::{self, Write};
+use std::net::ToSocketAddrs;
+use std::str::FromStr;
+use suppaftp::list::File;
+use suppaftp::types::FileType;
+use suppaftp::{FtpError, FtpStream};
+
+use crate::remote_source::{
+    RemoteBackend, RemoteEntry, RemoteError, RemoteSourceConfig, RemoteStat, REMOTE_CONNECT_TIMEOUT,
+};
+
+const DEFAULT_PORT: u16 = 21;
+
+/// FTP 서버 연결
+pub struct FtpBackend {
+    stream: FtpStream,
+}
+
+impl Drop for FtpBackend {
+    fn drop(&mut self) {
+        let _ = self.stream.quit();
+    }
+}
+
+// FTP 오류 분류 (소켓 오류는 연결이 끊긴 것으로 보고 다시 연결)
+fn ftp_error(message: String, error: &FtpError) -> RemoteError {
+    match error {
+        FtpError::ConnectionError(_) => RemoteError::Connection(message),
+        _ => RemoteError::Other(message),
+    }
+}
+
+pub fn connect(config: &RemoteSourceConfig) -> Result<Box<dyn RemoteBackend>, RemoteError> {
+    let port = config.port.unwrap_or(DEFAULT_PORT);
+    let addr = (config.host.as_str(), port)
+        .to_socket_addrs()
+        .map_err(|e| RemoteError::Connection(format!("Failed to resolve {}: {}", config.host, e)))?
+        .next()
+        .ok_or_else(|| RemoteError::Connection(format!("Failed to resolve {}", config.host)))?;
+
+    let mut stream = FtpStream::connect_timeout(addr, REMOTE_CONNECT_TIMEOUT)
+        .map_err(|e| RemoteError::Connection(format!("Failed to connect to {}: {}", config.host, e)))?;
+    stream
+        .login(config.username.as_str(), config.password.as_deref().unwrap_or_default())
+        .map_err(|e| match e {
+            FtpError::ConnectionError(_) => RemoteError::Connection(format!("FTP login failed: {}", e)),
+            _ => RemoteError::Auth(format!("FTP login failed for {}: {}", config.username, e)),
+        })?;
+    stream
+        .transfer_type(FileType::Binary)
+        .map_err(|e| ftp_error(format!("Failed to set FTP binary mode: {}", e), &e))?;
+
+    Ok(Box::new(FtpBackend { stream }))
+}
+
+impl RemoteBackend for FtpBackend {
+    fn list(&mut self, path: &str) -> Result<Vec<RemoteEntry>, RemoteError> {
+        let lines = self
+            .stream
+            .list(Some(path))
+            .map_err(|e| ftp_error(format!("Failed to list {}: {}", path, e), &e))?;
+
+        // 해석할 수 없는 줄(서버별 요약 줄 등)은 건너뜀
+        Ok(lines
+            .iter()
+            .filter_map(|line| File::from_str(line).ok())
+            .map(|file| RemoteEntry {
+                name: file.name().to_string(),
+                is_dir: file.is_directory(),
+            })
+            .collect())
+    }
+
+    fn download(&mut self, path: &str, writer: &mut dyn Write) -> Result<u64, RemoteError> {
+        self.stream
+            .retr(path, |reader| io::copy(reader, writer).map_err(FtpError::ConnectionError))
+            .map_err(|e| ftp_error(format!("Failed to download {}: {}", path, e), &e))
+    }
+
+    // SIZE/MDTM (지원하지 않는 서버는 해당 값만 None)
+    fn stat(&mut self, path: &str) -> Result<Option<RemoteStat>, RemoteError> {
+        let size = self.stream.size(path).ok().map(|size| size as u64);
+        let modified = self.stream.mdtm(path).ok().map(|time| time.to_string());
+        Ok((size.is_some() || modified.is_some()).then_some(RemoteStat { size, modified }))
+    }
+}
@@ -0,0 +1,158 @@
+use base64::{engine::general_purpose::STANDARD_NO_PAD, Engine};
+use ssh2::{CheckResult, ErrorCode, HashType, KnownHostFileKind, Session, Sftp};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+
+use crate::remote_source::{
+    RemoteBackend, RemoteEntry, RemoteError, RemoteSourceConfig, RemoteStat, REMOTE_CONNECT_TIMEOUT,
+};
+
+const DEFAULT_PORT: u16 = 22;
+
+/// SFTP 상태 코드 SSH_FX_NO_SUCH_FILE
+const SFTP_NO_SUCH_FILE: i32 = 2;
+
+/// SFTP 서버 연결 (세션은 Sftp가 끝날 때까지 유지)
+pub struct SftpBackend {
+    sftp: Sftp,
+    _session: Session,
+}
+
+// ssh2 오류 분류 (세션 오류는 연결이 끊긴 것으로 보고 다시 연결)
+fn sftp_error(action: &str, path: &str, error: ssh2::Error) -> RemoteError {
+    let message = format!("Failed to {} {}: {}", action, path, error);
+    match error.code() {
+        ErrorCode::Session(_) => RemoteError::Connection(message),
+        ErrorCode::SFTP(SFTP_NO_SUCH_FILE) => RemoteError::NotFound(message),
+        ErrorCode::SFTP(_) => RemoteError::Other(message),
+    }
+}
+
+/// 호스트 키 지문 (OpenSSH 표기, SHA256:<base64>)
+fn host_key_fingerprint(session: &Session) -> Option<String> {
+    let hash = session.host_key_hash(HashType::Sha256)?;
+    Some(format!("SHA256:{}", STANDARD_NO_PAD.encode(hash)))
+}
+
+/// 서버 호스트 키 확인 (~/.ssh/known_hosts, 없으면 사용자가 확인한 소스 설정의 지문)
+/// 다르면 HostKeyChanged, 어디에도 없으면 UnknownHostKey (인증 전에 확인해서 비밀번호를 보내지 않음)
+fn verify_host_key(session: &Session, config: &RemoteSourceConfig, port: u16) -> Result<(), RemoteError> {
+    let (key, _) = session
+        .host_key()
+        .ok_or_else(|| RemoteError::Connection(format!("No host key from {}", config.host)))?;
+    let fingerprint = host_key_fingerprint(session)
+        .ok_or_else(|| RemoteError::Connection(format!("No host key from {}", config.host)))?;
+    let changed = || RemoteError::HostKeyChanged {
+        host: config.host.clone(),
+        fingerprint: fingerprint.clone(),
+    };
+
+    if let (Some(home), Ok(mut known_hosts)) = (dirs::home_dir(), session.known_hosts()) {
+        let file = home.join(".ssh").join("known_hosts");
+        if known_hosts.read_file(&file, KnownHostFileKind::OpenSSH).is_ok() {
+            match known_hosts.check_port(&config.host, port, key) {
+                CheckResult::Match => return Ok(()),
+                CheckResult::Mismatch => return Err(changed()),
+                CheckResult::NotFound | CheckResult::Failure => {}
+            }
+        }
+    }
+
+    match config.host_key.as_deref() {
+        Some(trusted) if trusted == fingerprint => Ok(()),
+        Some(_) => Err(changed()),
+        None => Err(RemoteError::UnknownHostKey {
+            host: config.host.clone(),
+            fingerprint,
+        }),
+    }
+}
+
+pub fn connect(config: &RemoteSourceConfig) -> Result<Box<dyn RemoteBackend>, RemoteError> {
+    let port = config.port.unwrap_or(DEFAULT_PORT);
+    let addr = (config.host.as_str(), port)
+        .to_socket_addrs()
+        .map_err(|e| RemoteError::Connection(format!("Failed to resolve {}: {}", config.host, e)))?
+        .next()
+        .ok_or_else(|| RemoteError::Connection(format!("Failed to resolve {}", config.host)))?;
+
+    let tcp = TcpStream::connect_timeout(&addr, REMOTE_CONNECT_TIMEOUT)
+        .map_err(|e| RemoteError::Connection(format!("Failed to connect to {}: {}", config.host, e)))?;
+
+    let mut session = Session::new().map_err(|e| format!("Failed to create SSH session: {}", e))?;
+    session.set_tcp_stream(tcp);
+    session.set_timeout(REMOTE_CONNECT_TIMEOUT.as_millis() as u32);
+    session
+        .handshake()
+        .map_err(|e| RemoteError::Connection(format!("SSH handshake failed with {}: {}", config.host, e)))?;
+    verify_host_key(&session, config, port)?;
+
+    // 비밀번호가 없으면 ssh-agent 키로 인증
+    match config.password.as_deref() {
+        Some(password) => session.userauth_password(&config.username, password),
+        None => session.userauth_agent(&config.username),
+    }
+    .map_err(|e| RemoteError::Auth(format!("SSH authentication failed for {}: {}", config.username, e)))?;
+
+    // 대용량 파일 전송은 시간 제한 없음 (연결/인증에만 적용)
+    session.set_timeout(0);
+
+    let sftp = session
+        .sftp()
+        .map_err(|e| RemoteError::Connection(format!("Failed to start SFTP: {}", e)))?;
+    Ok(Box::new(SftpBackend { sftp, _session: session }))
+}
+
+impl RemoteBackend for SftpBackend {
+    fn list(&mut self, path: &str) -> Result<Vec<RemoteEntry>, RemoteError> {
+        let entries = self
+            .sftp
+            .readdir(Path::new(path))
+            .map_err(|e| sftp_error("list", path, e))?;
+
+        Ok(entries
+            .into_iter()
+            .filter_map(|(entry_path, stat)| {
+                let name = entry_path.file_name()?.to_string_lossy().to_string();
+                Some(RemoteEntry {
+                    name,
+                    is_dir: stat.is_dir(),
+                })
+            })
+            .collect())
+    }
+
+    fn download(&mut self, path: &str, writer: &mut dyn Write) -> Result<u64, RemoteError> {
+        let mut file = self
+            .sftp
+            .open(Path::new(path))
+            .map_err(|e| sftp_error("open", path, e))?;
+        io::copy(&mut file, writer)
+            .map_err(|e| RemoteError::Connection(format!("Failed to download {}: {}", path, e)))
+    }
+
+    fn read_range(&mut self, path: &str, offset: u64, length: u64) -> Result<Option<Vec<u8>>, RemoteError> {
+        let mut file = self
+            .sftp
+            .open(Path::new(path))
+            .map_err(|e| sftp_error("open", path, e))?;
+        let read_error = |e: io::Error| RemoteError::Connection(format!("Failed to read {}: {}", path, e));
+        file.seek(SeekFrom::Start(offset)).map_err(read_error)?;
+
+        let mut data = Vec::new();
+        file.take(length).read_to_end(&mut data).map_err(read_error)?;
+        Ok(Some(data))
+    }
+
+    fn stat(&mut self, path: &str) -> Result<Option<RemoteStat>, RemoteError> {
+        let stat = self
+            .sftp
+            .stat(Path::new(path))
+            .map_err(|e| sftp_error("stat", path, e))?;
+        Ok(Some(RemoteStat {
+            size: stat.size,
+            modified: stat.mtime.map(|mtime| mtime.to_string()),
+        }))
+    }
+}
@@ -0,0 +1,631 @@
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+use crate::directory::{DirectoryEntry, DirectoryListing};
+use crate::spool_index::SpoolIndex;
+
+/// 원격 경로 접두사 (remote://<소스 ID>/<경로>)
+pub const REMOTE_SCHEME: &str = "remote://";
+
+/// 원격 서버 연결 타임아웃
+pub const REMOTE_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 썸네일용으로 먼저 받는 파일 앞부분 크기 (EXIF + 내장 썸네일이 들어가는 범위)
+const HEAD_SPOOL_BYTES: u64 = 512 * 1024;
+
+/// 받은 파일 스풀 최대 크기 (넘으면 오래 쓰지 않은 파일부터 삭제)
+const SPOOL_BUDGET_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+/// 스풀 사본 옆에 저장하는 원격 파일 정보 확장자
+const STAT_EXTENSION: &str = "stat";
+
+/// 서버 파일 정보를 확인한 뒤 다시 확인하지 않고 스풀 사본을 쓰는 시간
+const STAT_RECHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 소스마다 남겨 두는 쉬는 연결 수 (작업 중에는 연결을 꺼내 쓰므로 동시 작업은 연결을 더 만듦)
+const MAX_IDLE_CONNECTIONS: usize = 4;
+
+/// 키체인 항목 서비스 이름 (계정은 소스 ID)
+const KEYCHAIN_SERVICE: &str = "PixEngine Remote";
+
+/// 원격 서버 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RemoteProtocol {
+    Ftp,
+    Sftp,
+    Webdav,
+}
+
+/// 원격 소스 설정
+/// 비밀번호는 파일에 저장하지 않고 OS 키체인에 보관 (SFTP는 없으면 ssh-agent 사용)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteSourceConfig {
+    /// remote://<id>/ 경로에 쓰는 식별자
+    pub id: String,
+    pub name: String,
+    pub protocol: RemoteProtocol,
+    pub host: String,
+    /// None이면 기본 포트 (FTP 21, SFTP 22)
+    pub port: Option<u16>,
+    pub username: String,
+    #[serde(default, skip_serializing)]
+    pub password: Option<String>,
+    /// 사용자가 확인한 SFTP 서버 호스트 키 지문 (SHA256:..., known_hosts에 없는 서버)
+    #[serde(default)]
+    pub host_key: Option<String>,
+    /// 서버에서 보여줄 최상위 폴더
+    #[serde(default = "default_root")]
+    pub root: String,
+}
+
+fn default_root() -> String {
+    "/".to_string()
+}
+
+impl RemoteSourceConfig {
+    /// 소스 경로(/a/b) → 서버 경로 (root 기준)
+    pub fn server_path(&self, path: &str) -> String {
+        let root = self.root.trim_end_matches('/');
+        format!("{}/{}", root, path.trim_start_matches('/'))
+    }
+}
+
+/// 원격 디렉토리 항목
+#[derive(Debug, Clone)]
+pub struct RemoteEntry {
+    pub name: String,
+    pub is_dir: bool,
+}
+
+/// 원격 파일 크기와 수정 시간 (스풀 사본이 최신인지 비교용, 서버가 알려주는 값만)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteStat {
+    pub size: Option<u64>,
+    /// 서버 형식 그대로의 수정 시간 (비교에만 사용)
+    pub modified: Option<String>,
+}
+
+/// 원격 작업 오류 (연결 오류만 다시 연결해서 재시도)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteError {
+    /// 연결 실패, 끊김, 시간 초과
+    Connection(String),
+    /// 로그인 실패 (비밀번호, ssh-agent 키)
+    Auth(String),
+    /// known_hosts에도 소스 설정에도 없는 SFTP 호스트 키 (사용자가 지문을 확인하면 host_key로 저장)
+    UnknownHostKey { host: String, fingerprint: String },
+    /// 알려진 키와 다른 SFTP 호스트 키 (연결하지 않음)
+    HostKeyChanged { host: String, fingerprint: String },
+    NotFound(String),
+    Other(String),
+}
+
+impl fmt::Display for RemoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RemoteError::Connection(message)
+            | RemoteError::Auth(message)
+            | RemoteError::NotFound(message)
+            | RemoteError::Other(message) => f.write_str(message),
+            RemoteError::UnknownHostKey { host, fingerprint } => {
+                write!(f, "Unknown host key for {}: {}", host, fingerprint)
+            }
+            RemoteError::HostKeyChanged { host, fingerprint } => {
+                write!(f, "Host key for {} has changed: {}", host, fingerprint)
+            }
+        }
+    }
+}
+
+impl From<String> for RemoteError {
+    fn from(message: String) -> Self {
+        RemoteError::Other(message)
+    }
+}
+
+impl From<RemoteError> for String {
+    fn from(error: RemoteError) -> Self {
+        error.to_string()
+    }
+}
+
+/// 원격 서버 접근 (FTP/SFTP/WebDAV 프로토콜별 구현)
+pub trait RemoteBackend: Send {
+    /// 서버 경로의 항목 목록
+    fn list(&mut self, path: &str) -> Result<Vec<RemoteEntry>, RemoteError>;
+
+    /// 서버 파일을 writer로 전송, 전송한 바이트 수 반환
+    fn download(&mut self, path: &str, writer: &mut dyn Write) -> Result<u64, RemoteError>;
+
+    /// 서버 파일의 일부 구간 (파일 끝이면 더 짧음), 구간 읽기를 지원하지 않으면 None
+    fn read_range(&mut self, _path: &str, _offset: u64, _length: u64) -> Result<Option<Vec<u8>>, RemoteError> {
+        Ok(None)
+    }
+
+    /// 서버 파일의 크기와 수정 시간, 알 수 없으면 None
+    fn stat(&mut self, _path: &str) -> Result<Option<RemoteStat>, RemoteError> {
+        Ok(None)
+    }
+}
+
+/// 파싱된 원격 경로
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemotePath {
+    pub source_id: String,
+    /// 소스 기준 경로 (항상 '/'로 시작)
+    pub path: String,
+}
+
+impl RemotePath {
+    pub fn parse(url: &str) -> Result<Self, RemoteError> {
+        let rest = url
+            .strip_prefix(REMOTE_SCHEME)
+            .ok_or_else(|| RemoteError::Other(format!("Invalid remote path: {}", url)))?;
+        let (source_id, path) = rest.split_once('/').unwrap_or((rest, ""));
+        if source_id.is_empty() {
+            return Err(RemoteError::Other(format!("Invalid remote path: {}", url)));
+        }
+        Ok(Self {
+            source_id: source_id.to_string(),
+            path: format!("/{}", path.trim_matches('/')),
+        })
+    }
+
+    pub fn join(&self, name: &str) -> Self {
+        Self {
+            source_id: self.source_id.clone(),
+            path: format!("{}/{}", self.path.trim_end_matches('/'), name),
+        }
+    }
+
+    pub fn file_name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or_default()
+    }
+
+    pub fn to_url(&self) -> String {
+        format!("{}{}{}", REMOTE_SCHEME, self.source_id, self.path)
+    }
+}
+
+pub fn is_remote_path(path: &str) -> bool {
+    path.starts_with(REMOTE_SCHEME)
+}
+
+/// 등록된 소스 (설정과 쉬는 연결, 작업하는 동안에는 연결을 꺼내 쓰고 잠그지 않음)
+struct RemoteSource {
+    config: RemoteSourceConfig,
+    idle: Mutex<Vec<Box<dyn RemoteBackend>>>,
+}
+
+lazy_static! {
+    static ref SOURCES: Mutex<HashMap<String, Arc<RemoteSource>>> = Mutex::new(HashMap::new());
+    /// 스풀 사용 기록 (처음 사용할 때 스풀 폴더의 파일로 시작)
+    static ref SPOOL: Mutex<Option<SpoolIndex>> = Mutex::new(None);
+    /// 스풀 사본의 서버 파일 정보를 확인한 시각
+    static ref CHECKED: Mutex<HashMap<PathBuf, Instant>> = Mutex::new(HashMap::new());
+}
+
+fn connect(config: &RemoteSourceConfig) -> Result<Box<dyn RemoteBackend>, RemoteError> {
+    match config.protocol {
+        RemoteProtocol::Ftp => crate::remote_ftp::connect(config),
+        RemoteProtocol::Sftp => crate::remote_sftp::connect(config),
+        RemoteProtocol::Webdav => crate::remote_webdav::connect(config),
+    }
+}
+
+fn get_source(source_id: &str) -> Result<Arc<RemoteSource>, RemoteError> {
+    SOURCES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(source_id)
+        .cloned()
+        .ok_or_else(|| RemoteError::NotFound(format!("Remote source not found: {}", source_id)))
+}
+
+/// 소스 연결로 작업 실행 (쉬는 연결을 꺼내 쓰고 끝나면 돌려놓음)
+/// 연결 오류면 새로 연결해서 한 번 더 시도, 그 밖의 오류는 그대로 반환
+fn with_backend<T>(
+    source_id: &str,
+    mut f: impl FnMut(&RemoteSourceConfig, &mut dyn RemoteBackend) -> Result<T, RemoteError>,
+) -> Result<T, RemoteError> {
+    let source = get_source(source_id)?;
+    let idle = source.idle.lock().unwrap_or_else(|e| e.into_inner()).pop();
+
+    let mut last_error = None;
+    for backend in [idle, None] {
+        let mut backend = match backend {
+            Some(backend) => backend,
+            None => connect(&source.config)?,
+        };
+        match f(&source.config, backend.as_mut()) {
+            Err(RemoteError::Connection(e)) => {
+                tracing::warn!("Remote connection failed on {}, reconnecting: {}", source_id, e);
+                last_error = Some(RemoteError::Connection(e));
+            }
+            result => {
+                let mut idle = source.idle.lock().unwrap_or_else(|e| e.into_inner());
+                if idle.len() < MAX_IDLE_CONNECTIONS {
+                    idle.push(backend);
+                }
+                return result;
+            }
+        }
+    }
+    Err(last_error.expect("tried at least once"))
+}
+
+// 원격 소스 목록 파일 경로
+fn get_sources_path(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|p| p.join("remote_sources.json"))
+        .map_err(|e| format!("Failed to get app data dir: {}", e))
+}
+
+fn keychain_entry(source_id: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, source_id).map_err(|e| format!("Failed to access keychain: {}", e))
+}
+
+// 키체인에 저장한 비밀번호 (없으면 None)
+fn stored_password(source_id: &str) -> Option<String> {
+    match keychain_entry(source_id).and_then(|entry| entry.get_password().map_err(|e| e.to_string())) {
+        Ok(password) => Some(password),
+        Err(e) => {
+            tracing::debug!("No stored password for remote source {}: {}", source_id, e);
+            None
+        }
+    }
+}
+
+fn save_sources(app: &AppHandle) -> Result<(), String> {
+    let configs = list_sources();
+    let json = serde_json::to_string_pretty(&configs).map_err(|e| e.to_string())?;
+    crate::shutdown::write_atomic(&get_sources_path(app)?, json.as_bytes())
+        .map_err(|e| format!("Failed to save remote sources: {}", e))
+}
+
+/// 저장된 원격 소스 등록 (앱 시작 시, 비밀번호는 키체인에서 읽음, 연결은 처음 사용할 때)
+pub fn load_sources(app: &AppHandle) {
+    let Ok(content) = get_sources_path(app).and_then(|path| fs::read_to_string(path).map_err(|e| e.to_string())) else {
+        return;
+    };
+    let configs: Vec<RemoteSourceConfig> = match serde_json::from_str(&content) {
+        Ok(configs) => configs,
+        Err(e) => {
+            tracing::warn!("Invalid remote sources file: {}", e);
+            return;
+        }
+    };
+
+    let mut sources = SOURCES.lock().unwrap_or_else(|e| e.into_inner());
+    for mut config in configs {
+        config.password = stored_password(&config.id);
+        let id = config.id.clone();
+        sources.insert(id, Arc::new(RemoteSource { config, idle: Mutex::new(Vec::new()) }));
+    }
+}
+
+/// 원격 소스 추가 (연결해서 확인한 뒤 등록, 같은 ID면 교체, 비밀번호는 키체인에 저장)
+/// 처음 보는 SFTP 호스트 키면 UnknownHostKey (사용자가 지문을 확인하면 host_key에 넣어 다시 호출)
+pub fn add_source(app: &AppHandle, config: RemoteSourceConfig) -> Result<(), RemoteError> {
+    if config.id.is_empty() || config.id.contains('/') {
+        return Err(RemoteError::Other(format!("Invalid remote source id: {}", config.id)));
+    }
+
+    let backend = connect(&config)?;
+    if let Some(password) = &config.password {
+        keychain_entry(&config.id)?
+            .set_password(password)
+            .map_err(|e| format!("Failed to store password: {}", e))?;
+    }
+    let id = config.id.clone();
+    SOURCES
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(id, Arc::new(RemoteSource { config, idle: Mutex::new(vec![backend]) }));
+    Ok(save_sources(app)?)
+}
+
+/// 원격 소스 삭제 (키체인 항목 포함)
+pub fn remove_source(app: &AppHandle, id: &str) -> Result<(), String> {
+    SOURCES.lock().unwrap_or_else(|e| e.into_inner()).remove(id);
+    save_sources(app)?;
+
+    if let Ok(entry) = keychain_entry(id) {
+        let _ = entry.delete_credential();
+    }
+    Ok(())
+}
+
+/// 등록된 소스 설정 (비밀번호 제외)
+pub fn list_sources() -> Vec<RemoteSourceConfig> {
+    let sources = SOURCES.lock().unwrap_or_else(|e| e.into_inner());
+    let mut configs: Vec<RemoteSourceConfig> = sources
+        .values()
+        .map(|source| RemoteSourceConfig {
+            password: None,
+            ..source.config.clone()
+        })
+        .collect();
+    configs.sort_by(|a, b| a.name.cmp(&b.name));
+    configs
+}
+
+/// 원격 디렉토리 읽기 (read_directory_contents와 같은 형태, 경로는 remote://)
+pub fn read_directory(url: &str, skip: impl Fn(&str) -> bool) -> Result<DirectoryListing, RemoteError> {
+    let remote = RemotePath::parse(url)?;
+    let entries = with_backend(&remote.source_id, |config, backend| {
+        backend.list(&config.server_path(&remote.path))
+    })?;
+
+    Ok(DirectoryListing {
+        entries: entries
+            .into_iter()
+            .filter(|entry| entry.name != "." && entry.name != ".." && !skip(&entry.name))
+            .map(|entry| DirectoryEntry::new(entry.name.clone(), remote.join(&entry.name).to_url(), entry.is_dir))
+            .collect(),
+        ..DirectoryListing::default()
+    })
+}
+
+// 스풀 디렉토리 (썸네일/뷰어용으로 받은 원격 파일)
+fn get_spool_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_cache_dir()
+        .map(|p| p.join("remote_spool"))
+        .map_err(|e| format!("Failed to get app cache dir: {}", e))
+}
+
+// 원격 파일의 스풀 경로 (원격 경로 해시 + 원래 파일명, 확장자로 포맷 판별)
+fn spool_target(app: &AppHandle, remote: &RemotePath, url: &str, suffix: &str) -> Result<PathBuf, RemoteError> {
+    let dir = get_spool_dir(app)?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create spool directory: {}", e))?;
+
+    let key = blake3::hash(url.as_bytes()).to_hex();
+    Ok(dir.join(format!("{}{}_{}", &key[..16], suffix, remote.file_name())))
+}
+
+// 스풀 사본 옆의 원격 파일 정보 경로
+fn stat_path(target: &Path) -> PathBuf {
+    let mut name = target.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(STAT_EXTENSION);
+    target.with_file_name(name)
+}
+
+// 지금 서버 파일 정보 (확인할 수 없으면 None, 그때는 스풀 사본을 그대로 사용)
+fn remote_stat(remote: &RemotePath) -> Option<RemoteStat> {
+    with_backend(&remote.source_id, |config, backend| backend.stat(&config.server_path(&remote.path)))
+        .unwrap_or_else(|e| {
+            tracing::debug!("Failed to stat {}: {}", remote.to_url(), e);
+            None
+        })
+}
+
+// 스풀 사본의 서버 파일 정보를 STAT_RECHECK_INTERVAL 안에 확인했는지
+fn recently_checked(target: &Path) -> bool {
+    target.exists()
+        && CHECKED
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(target)
+            .is_some_and(|checked| checked.elapsed() < STAT_RECHECK_INTERVAL)
+}
+
+fn mark_checked(target: &Path) {
+    CHECKED
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(target.to_path_buf(), Instant::now());
+}
+
+// 스풀 사본이 있고 받을 때의 서버 파일 정보와 지금 정보가 같은지
+fn is_fresh(target: &Path, current: Option<&RemoteStat>) -> bool {
+    if !target.exists() {
+        return false;
+    }
+    let Some(current) = current else {
+        return true;
+    };
+    fs::read_to_string(stat_path(target))
+        .ok()
+        .and_then(|content| serde_json::from_str::<RemoteStat>(&content).ok())
+        .is_some_and(|stored| &stored == current)
+}
+
+// 받은 파일의 서버 정보 저장 (다음에 최신인지 비교)
+fn write_stat(target: &Path, stat: Option<&RemoteStat>) {
+    let path = stat_path(target);
+    let Some(stat) = stat else {
+        let _ = fs::remove_file(path);
+        return;
+    };
+    if let Ok(json) = serde_json::to_string(stat) {
+        if let Err(e) = crate::shutdown::write_atomic(&path, json.as_bytes()) {
+            tracing::warn!("Failed to write spool stat: {}", e);
+        }
+    }
+}
+
+// 스풀 파일 삭제 (서버 정보 파일 포함)
+fn remove_spooled(path: &Path) {
+    let _ = fs::remove_file(path);
+    let _ = fs::remove_file(stat_path(path));
+    CHECKED.lock().unwrap_or_else(|e| e.into_inner()).remove(path);
+}
+
+// 스풀 사용 기록 갱신 후 예산을 넘으면 오래 쓰지 않은 파일 삭제
+fn touch_spool(app: &AppHandle, target: &Path) {
+    let size = fs::metadata(target).map_or(0, |metadata| metadata.len());
+    let evicted = {
+        let mut spool = SPOOL.lock().unwrap_or_else(|e| e.into_inner());
+        let index = spool.get_or_insert_with(|| {
+            let dir = get_spool_dir(app).unwrap_or_default();
+            SpoolIndex::scan(&dir, |path| {
+                path.extension()
+                    .is_none_or(|ext| ext != STAT_EXTENSION && ext != "partial")
+            })
+        });
+        index.touch(target, size);
+        index.evict(SPOOL_BUDGET_BYTES, target)
+    };
+    for path in evicted {
+        remove_spooled(&path);
+    }
+}
+
+// 앞부분 스풀 삭제 (전체 파일을 받은 뒤)
+fn remove_head(app: &AppHandle, remote: &RemotePath, url: &str) {
+    let Ok(head) = spool_target(app, remote, url, "_head") else {
+        return;
+    };
+    if head.exists() {
+        remove_spooled(&head);
+        if let Some(index) = SPOOL.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+            index.remove(&head);
+        }
+    }
+}
+
+/// 원격 파일을 로컬 스풀로 받고 로컬 경로 반환
+/// 이미 받았어도 서버 파일의 크기/수정 시간이 바뀌었으면 다시 받음 (최근에 확인한 사본은 확인하지 않음)
+pub fn spool(app: &AppHandle, url: &str) -> Result<PathBuf, RemoteError> {
+    let remote = RemotePath::parse(url)?;
+    let target = spool_target(app, &remote, url, "")?;
+    if recently_checked(&target) {
+        touch_spool(app, &target);
+        return Ok(target);
+    }
+    let current = remote_stat(&remote);
+    if is_fresh(&target, current.as_ref()) {
+        mark_checked(&target);
+        touch_spool(app, &target);
+        return Ok(target);
+    }
+
+    // 임시 파일로 받은 뒤 rename (중간에 실패해도 불완전한 파일을 쓰지 않음)
+    let partial = target.with_extension("partial");
+    let result = with_backend(&remote.source_id, |config, backend| {
+        let mut file = fs::File::create(&partial)
+            .map_err(|e| RemoteError::Other(format!("Failed to create spool file: {}", e)))?;
+        backend.download(&config.server_path(&remote.path), &mut file)
+    })
+    .and_then(|_| {
+        fs::rename(&partial, &target).map_err(|e| RemoteError::Other(format!("Failed to finish spool file: {}", e)))
+    });
+
+    if let Err(e) = result {
+        let _ = fs::remove_file(&partial);
+        return Err(e);
+    }
+    write_stat(&target, current.as_ref());
+    mark_checked(&target);
+    remove_head(app, &remote, url);
+    touch_spool(app, &target);
+    Ok(target)
+}
+
+/// 원격 파일 앞부분만 스풀 (EXIF/내장 미리보기용, 범위 요청 지원 서버만)
+/// 반환: (로컬 경로, 파일 전체인지 여부), 구간 읽기를 지원하지 않으면 None
+pub fn spool_head(app: &AppHandle, url: &str) -> Result<Option<(PathBuf, bool)>, RemoteError> {
+    let remote = RemotePath::parse(url)?;
+    let full = spool_target(app, &remote, url, "")?;
+    let head = spool_target(app, &remote, url, "_head")?;
+    for (target, complete) in [(&full, true), (&head, false)] {
+        if recently_checked(target) {
+            touch_spool(app, target);
+            return Ok(Some((target.clone(), complete)));
+        }
+    }
+
+    let current = remote_stat(&remote);
+    for (target, complete) in [(&full, true), (&head, false)] {
+        if is_fresh(target, current.as_ref()) {
+            mark_checked(target);
+            touch_spool(app, target);
+            return Ok(Some((target.clone(), complete)));
+        }
+    }
+
+    let data = with_backend(&remote.source_id, |config, backend| {
+        backend.read_range(&config.server_path(&remote.path), 0, HEAD_SPOOL_BYTES)
+    })?;
+    let Some(data) = data else {
+        return Ok(None);
+    };
+
+    // 요청보다 짧으면 파일 전체를 받은 것
+    let (target, complete) = if (data.len() as u64) < HEAD_SPOOL_BYTES {
+        (full, true)
+    } else {
+        (head, false)
+    };
+    crate::shutdown::write_cache_atomic(&target, &data).map_err(|e| format!("Failed to write spool file: {}", e))?;
+    write_stat(&target, current.as_ref());
+    mark_checked(&target);
+    if complete {
+        remove_head(app, &remote, url);
+    }
+    touch_spool(app, &target);
+    Ok(Some((target, complete)))
+}
+
+/// 이전 실행에서 받다 만 스풀 파일 정리
+pub fn clean_partial_spool(app: &AppHandle) {
+    if let Ok(dir) = get_spool_dir(app) {
+        crate::shutdown::remove_partial_writes(&dir);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remote_path() {
+        let remote = RemotePath::parse("remote://studio/shoot/day1/").unwrap();
+        assert_eq!(remote.source_id, "studio");
+        assert_eq!(remote.path, "/shoot/day1");
+        assert_eq!(remote.join("A001.NEF").to_url(), "remote://studio/shoot/day1/A001.NEF");
+        assert_eq!(RemotePath::parse("remote://studio").unwrap().path, "/");
+        assert!(RemotePath::parse("remote:///shoot").is_err());
+        assert!(RemotePath::parse("/local/path").is_err());
+    }
+
+    #[test]
+    fn test_spool_freshness() {
+        let dir = std::env::temp_dir().join(format!("pixengine-remote-spool-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let target = dir.join("0123_A001.NEF");
+        let stat = RemoteStat {
+            size: Some(4),
+            modified: Some("20260101120000".to_string()),
+        };
+
+        assert!(!is_fresh(&target, Some(&stat)));
+        fs::write(&target, b"data").unwrap();
+        write_stat(&target, Some(&stat));
+        assert!(is_fresh(&target, Some(&stat)));
+        // 서버 정보를 알 수 없으면 (오프라인 등) 사본 사용
+        assert!(is_fresh(&target, None));
+
+        let changed = RemoteStat {
+            size: Some(5),
+            ..stat.clone()
+        };
+        assert!(!is_fresh(&target, Some(&changed)));
+
+        remove_spooled(&target);
+        assert!(!target.exists() && !stat_path(&target).exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
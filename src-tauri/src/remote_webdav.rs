@@ -0,0 +1,205 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use std::io::{self, Read, Write};
+
+use crate::remote_source::{
+    RemoteBackend, RemoteEntry, RemoteError, RemoteSourceConfig, RemoteStat, REMOTE_CONNECT_TIMEOUT,
+};
+
+/// 경로 세그먼트에서 인코딩하지 않는 문자 (RFC 3986 unreserved)
+const PATH_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'_').remove(b'.').remove(b'~');
+
+/// PROPFIND 요청 본문 (폴더 여부만 필요)
+const PROPFIND_BODY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:"><d:prop><d:resourcetype/></d:prop></d:propfind>"#;
+
+/// PROPFIND 응답 최대 크기
+const MAX_LISTING_BYTES: u64 = 32 * 1024 * 1024;
+
+/// WebDAV 서버 연결 (NAS 등)
+pub struct WebdavBackend {
+    agent: ureq::Agent,
+    /// 서버 주소 (scheme://host[:port], 끝 '/' 없음)
+    base_url: String,
+    authorization: Option<String>,
+}
+
+/// host가 URL이면 그대로, 아니면 http:// (포트는 설정에 있으면 추가)
+fn base_url(config: &RemoteSourceConfig) -> String {
+    let host = config.host.trim_end_matches('/');
+    let with_scheme = if host.starts_with("http://") || host.starts_with("https://") {
+        host.to_string()
+    } else {
+        format!("http://{}", host)
+    };
+    match config.port {
+        Some(port) => format!("{}:{}", with_scheme, port),
+        None => with_scheme,
+    }
+}
+
+pub fn connect(config: &RemoteSourceConfig) -> Result<Box<dyn RemoteBackend>, RemoteError> {
+    let agent = ureq::AgentBuilder::new()
+        .timeout_connect(REMOTE_CONNECT_TIMEOUT)
+        .build();
+    let authorization = (!config.username.is_empty()).then(|| {
+        let credentials = format!("{}:{}", config.username, config.password.as_deref().unwrap_or_default());
+        format!("Basic {}", STANDARD.encode(credentials))
+    });
+
+    let mut backend = WebdavBackend {
+        agent,
+        base_url: base_url(config),
+        authorization,
+    };
+
+    // 최상위 폴더 목록으로 연결/인증 확인
+    backend.list(&config.server_path("/"))?;
+    Ok(Box::new(backend))
+}
+
+impl WebdavBackend {
+    fn url(&self, path: &str) -> String {
+        let encoded: Vec<String> = path
+            .split('/')
+            .map(|segment| utf8_percent_encode(segment, PATH_SEGMENT).to_string())
+            .collect();
+        format!("{}{}", self.base_url, encoded.join("/"))
+    }
+
+    fn request(&self, method: &str, path: &str) -> ureq::Request {
+        let request = self.agent.request(method, &self.url(path));
+        match &self.authorization {
+            Some(authorization) => request.set("Authorization", authorization),
+            None => request,
+        }
+    }
+}
+
+fn request_error(path: &str, error: ureq::Error) -> RemoteError {
+    match error {
+        ureq::Error::Status(code @ (401 | 403), _) => {
+            RemoteError::Auth(format!("WebDAV access denied for {}: HTTP {}", path, code))
+        }
+        ureq::Error::Status(404, _) => RemoteError::NotFound(format!("Path does not exist: {}", path)),
+        ureq::Error::Status(code, _) => {
+            RemoteError::Other(format!("WebDAV request failed for {}: HTTP {}", path, code))
+        }
+        ureq::Error::Transport(e) => RemoteError::Connection(format!("WebDAV request failed for {}: {}", path, e)),
+    }
+}
+
+/// PROPFIND multistatus 응답 해석 (요청한 폴더 자신은 제외)
+fn parse_multistatus(body: &str, request_path: &str) -> Result<Vec<RemoteEntry>, String> {
+    let document = roxmltree::Document::parse(body)
+        .map_err(|e| format!("Invalid WebDAV response: {}", e))?;
+    let own_path = request_path.trim_end_matches('/');
+
+    Ok(document
+        .descendants()
+        .filter(|node| node.tag_name().name() == "response")
+        .filter_map(|response| {
+            let href = response
+                .descendants()
+                .find(|node| node.tag_name().name() == "href")?
+                .text()?;
+            let is_dir = response
+                .descendants()
+                .any(|node| node.tag_name().name() == "collection");
+
+            // href는 경로 또는 전체 URL
+            let decoded = percent_decode_str(href.trim()).decode_utf8_lossy();
+            let path = match decoded.split_once("://") {
+                Some((_, rest)) => rest.find('/').map(|i| &rest[i..]).unwrap_or("/").to_string(),
+                None => decoded.to_string(),
+            };
+            let path = path.trim_end_matches('/');
+            if path == own_path {
+                return None;
+            }
+
+            let name = path.rsplit('/').next()?.to_string();
+            (!name.is_empty()).then_some(RemoteEntry { name, is_dir })
+        })
+        .collect())
+}
+
+impl RemoteBackend for WebdavBackend {
+    fn list(&mut self, path: &str) -> Result<Vec<RemoteEntry>, RemoteError> {
+        let response = self
+            .request("PROPFIND", path)
+            .set("Depth", "1")
+            .set("Content-Type", "application/xml; charset=utf-8")
+            .send_string(PROPFIND_BODY)
+            .map_err(|e| request_error(path, e))?;
+
+        let mut body = String::new();
+        response
+            .into_reader()
+            .take(MAX_LISTING_BYTES)
+            .read_to_string(&mut body)
+            .map_err(|e| RemoteError::Connection(format!("Failed to read WebDAV response: {}", e)))?;
+
+        Ok(parse_multistatus(&body, path)?)
+    }
+
+    fn download(&mut self, path: &str, writer: &mut dyn Write) -> Result<u64, RemoteError> {
+        let response = self.request("GET", path).call().map_err(|e| request_error(path, e))?;
+        io::copy(&mut response.into_reader(), writer)
+            .map_err(|e| RemoteError::Connection(format!("Failed to download {}: {}", path, e)))
+    }
+
+    fn read_range(&mut self, path: &str, offset: u64, length: u64) -> Result<Option<Vec<u8>>, RemoteError> {
+        let response = self
+            .request("GET", path)
+            .set("Range", &format!("bytes={}-{}", offset, offset + length - 1))
+            .call()
+            .map_err(|e| request_error(path, e))?;
+
+        // 범위 요청을 무시하고 전체를 보내는 서버는 앞부분만 읽고 끊음
+        if response.status() != 206 && offset > 0 {
+            return Ok(None);
+        }
+
+        let mut data = Vec::new();
+        response
+            .into_reader()
+            .take(length)
+            .read_to_end(&mut data)
+            .map_err(|e| RemoteError::Connection(format!("Failed to read {}: {}", path, e)))?;
+        Ok(Some(data))
+    }
+
+    // HEAD 응답 헤더 (Last-Modified가 없으면 ETag로 비교)
+    fn stat(&mut self, path: &str) -> Result<Option<RemoteStat>, RemoteError> {
+        let response = self.request("HEAD", path).call().map_err(|e| request_error(path, e))?;
+        let size = response.header("Content-Length").and_then(|value| value.parse().ok());
+        let modified = response
+            .header("Last-Modified")
+            .or_else(|| response.header("ETag"))
+            .map(str::to_string);
+        Ok((size.is_some() || modified.is_some()).then_some(RemoteStat { size, modified }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_multistatus() {
+        let body = r#"<?xml version="1.0"?>
+<d:multistatus xmlns:d="DAV:">
+  <d:response><d:href>/photos/shoot/</d:href><d:propstat><d:prop><d:resourcetype><d:collection/></d:resourcetype></d:prop></d:propstat></d:response>
+  <d:response><d:href>/photos/shoot/raw/</d:href><d:propstat><d:prop><d:resourcetype><d:collection/></d:resourcetype></d:prop></d:propstat></d:response>
+  <d:response><d:href>http://nas.local/photos/shoot/%EC%82%AC%EC%A7%84%201.jpg</d:href><d:propstat><d:prop><d:resourcetype/></d:prop></d:propstat></d:response>
+</d:multistatus>"#;
+
+        let entries = parse_multistatus(body, "/photos/shoot").unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].name, "raw");
+        assert!(entries[0].is_dir);
+        assert_eq!(entries[1].name, "사진 1.jpg");
+        assert!(!entries[1].is_dir);
+    }
+}
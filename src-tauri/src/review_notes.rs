@@ -0,0 +1,247 @@
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::metadata_store::MetadataStore;
+
+/// 검토 메모 테이블 스키마 (이미지마다 여러 개, markup은 영역 목록 JSON)
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS review_notes (
+        id         INTEGER PRIMARY KEY AUTOINCREMENT,
+        path       TEXT NOT NULL,
+        folder     TEXT NOT NULL,
+        text       TEXT NOT NULL,
+        markup     TEXT NOT NULL DEFAULT '[]',
+        created_at INTEGER NOT NULL,
+        updated_at INTEGER NOT NULL
+    );
+
+    CREATE INDEX IF NOT EXISTS idx_review_notes_path ON review_notes(path);
+    CREATE INDEX IF NOT EXISTS idx_review_notes_folder ON review_notes(folder);
+";
+
+/// 이미지 위 표시 영역 (좌표와 크기는 이미지 크기 기준 0~1)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoteRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub comment: String,
+}
+
+/// 검토 메모
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewNote {
+    pub id: i64,
+    pub path: String,
+    pub text: String,
+    pub markup: Vec<NoteRect>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 메모 내보내기 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NoteExportFormat {
+    Csv,
+    Json,
+}
+
+/// 검토 메모 테이블 초기화
+pub fn init_schema(store: &MetadataStore) -> Result<(), String> {
+    store.with_conn(|conn| conn.execute_batch(SCHEMA))
+}
+
+fn folder_of(path: &str) -> String {
+    Path::new(path)
+        .parent()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn validate_markup(markup: &[NoteRect]) -> Result<(), String> {
+    let in_range = |v: f64| (0.0..=1.0).contains(&v);
+    match markup
+        .iter()
+        .find(|r| !(in_range(r.x) && in_range(r.y) && in_range(r.width) && in_range(r.height)))
+    {
+        Some(rect) => Err(format!("Invalid markup rect (must be within 0-1): {:?}", rect)),
+        None => Ok(()),
+    }
+}
+
+fn row_to_note(row: &rusqlite::Row) -> rusqlite::Result<ReviewNote> {
+    let markup: String = row.get(3)?;
+    Ok(ReviewNote {
+        id: row.get(0)?,
+        path: row.get(1)?,
+        text: row.get(2)?,
+        // 읽지 못한 표시 영역은 버리고 메모는 유지
+        markup: serde_json::from_str(&markup).unwrap_or_default(),
+        created_at: row.get(4)?,
+        updated_at: row.get(5)?,
+    })
+}
+
+/// 메모 추가
+pub fn add_note(store: &MetadataStore, path: &str, text: &str, markup: Vec<NoteRect>) -> Result<ReviewNote, String> {
+    validate_markup(&markup)?;
+    let markup_json = serde_json::to_string(&markup).map_err(|e| e.to_string())?;
+    let now = chrono::Utc::now().timestamp();
+
+    let id = store.with_conn(|conn| {
+        conn.execute(
+            "INSERT INTO review_notes (path, folder, text, markup, created_at, updated_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+            params![path, folder_of(path), text, markup_json, now],
+        )?;
+        Ok(conn.last_insert_rowid())
+    })?;
+
+    Ok(ReviewNote {
+        id,
+        path: path.to_string(),
+        text: text.to_string(),
+        markup,
+        created_at: now,
+        updated_at: now,
+    })
+}
+
+/// 메모 수정
+pub fn update_note(store: &MetadataStore, id: i64, text: &str, markup: Vec<NoteRect>) -> Result<ReviewNote, String> {
+    validate_markup(&markup)?;
+    let markup_json = serde_json::to_string(&markup).map_err(|e| e.to_string())?;
+
+    let updated = store.with_conn(|conn| {
+        conn.execute(
+            "UPDATE review_notes SET text = ?1, markup = ?2, updated_at = ?3 WHERE id = ?4",
+            params![text, markup_json, chrono::Utc::now().timestamp(), id],
+        )
+    })?;
+    if updated == 0 {
+        return Err(format!("{}: review note {}", crate::error::NOT_FOUND, id));
+    }
+
+    store.with_conn(|conn| {
+        conn.query_row(
+            "SELECT id, path, text, markup, created_at, updated_at FROM review_notes WHERE id = ?1",
+            params![id],
+            row_to_note,
+        )
+    })
+}
+
+/// 메모 삭제
+pub fn delete_note(store: &MetadataStore, id: i64) -> Result<(), String> {
+    store.with_conn(|conn| conn.execute("DELETE FROM review_notes WHERE id = ?1", params![id]))?;
+    Ok(())
+}
+
+/// 이미지의 메모 목록 (작성 순)
+pub fn get_notes(store: &MetadataStore, path: &str) -> Result<Vec<ReviewNote>, String> {
+    store.with_conn(|conn| {
+        let mut stmt = conn.prepare_cached(
+            "SELECT id, path, text, markup, created_at, updated_at FROM review_notes \
+             WHERE path = ?1 ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![path], row_to_note)?;
+        rows.collect()
+    })
+}
+
+fn folder_notes(store: &MetadataStore, folder: &str) -> Result<Vec<ReviewNote>, String> {
+    store.with_conn(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, path, text, markup, created_at, updated_at FROM review_notes \
+             WHERE folder = ?1 ORDER BY path, id",
+        )?;
+        let rows = stmt.query_map(params![folder], row_to_note)?;
+        rows.collect()
+    })
+}
+
+// CSV 필드 (쉼표/따옴표/줄바꿈이 있으면 따옴표로 감싸고 따옴표는 두 번)
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn format_time(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .map(|t| t.to_rfc3339())
+        .unwrap_or_default()
+}
+
+// 메모 하나당 한 줄, 표시 영역 설명은 " | "로 이어 붙임
+fn to_csv(notes: &[ReviewNote]) -> String {
+    let mut csv = String::from("path,file_name,text,markup,created_at,updated_at\n");
+    for note in notes {
+        let file_name = Path::new(&note.path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let markup: Vec<&str> = note.markup.iter().map(|rect| rect.comment.as_str()).collect();
+        let fields = [
+            note.path.clone(),
+            file_name,
+            note.text.clone(),
+            markup.join(" | "),
+            format_time(note.created_at),
+            format_time(note.updated_at),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// 폴더의 모든 메모를 CSV/JSON 파일로 내보내기
+/// 반환값: 내보낸 메모 수
+pub fn export_folder(
+    store: &MetadataStore,
+    folder: &str,
+    dest: &Path,
+    format: NoteExportFormat,
+) -> Result<usize, String> {
+    let notes = folder_notes(store, folder)?;
+    let content = match format {
+        NoteExportFormat::Csv => to_csv(&notes),
+        NoteExportFormat::Json => serde_json::to_string_pretty(&notes).map_err(|e| e.to_string())?,
+    };
+    fs::write(dest, content).map_err(|e| format!("Failed to write notes: {}", e))?;
+    Ok(notes.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a, b"), "\"a, b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("line\nbreak"), "\"line\nbreak\"");
+    }
+
+    #[test]
+    fn test_validate_markup() {
+        let rect = |x: f64| NoteRect {
+            x,
+            y: 0.1,
+            width: 0.5,
+            height: 0.5,
+            comment: String::new(),
+        };
+        assert!(validate_markup(&[rect(0.0), rect(1.0)]).is_ok());
+        assert!(validate_markup(&[rect(1.5)]).is_err());
+    }
+}
@@ -0,0 +1,177 @@
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tokio::sync::Notify;
+
+/// 작업 우선순위 (앞쪽이 높음)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkClass {
+    /// 뷰어 이미지 (사용자가 기다리는 작업, 예산과 관계없이 바로 실행)
+    Viewer,
+    /// 화면에 보이는 저화질 썸네일
+    LqVisible,
+    /// 나머지 저화질 썸네일
+    LqRest,
+    /// 고화질 썸네일
+    Hq,
+}
+
+impl WorkClass {
+    const COUNT: usize = 4;
+
+    fn rank(self) -> usize {
+        self as usize
+    }
+
+    /// 파일 전체를 읽는 작업 (I/O 예산 사용, 저화질은 파일 앞부분만 읽음)
+    fn uses_io(self) -> bool {
+        matches!(self, WorkClass::Viewer | WorkClass::Hq)
+    }
+}
+
+/// LQ/HQ/뷰어가 함께 쓰는 동시 작업 예산
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkerBudget {
+    /// 동시에 실행하는 디코딩/리사이즈 작업 수
+    pub cpu: usize,
+    /// 동시에 파일 전체를 읽는 작업 수 (디스크 포화 방지)
+    pub io: usize,
+}
+
+impl Default for WorkerBudget {
+    fn default() -> Self {
+        Self {
+            cpu: (num_cpus::get() * 3 / 4).max(2),
+            io: 4,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct SchedulerState {
+    budget: WorkerBudget,
+    cpu_in_use: usize,
+    io_in_use: usize,
+    waiting: [usize; WorkClass::COUNT],
+}
+
+impl SchedulerState {
+    /// 예산이 남아 있고 더 높은 우선순위 작업이 기다리지 않으면 실행 가능
+    fn can_run(&self, class: WorkClass) -> bool {
+        if class == WorkClass::Viewer {
+            return true;
+        }
+        if self.waiting[..class.rank()].iter().any(|&count| count > 0) {
+            return false;
+        }
+        self.cpu_in_use < self.budget.cpu && (!class.uses_io() || self.io_in_use < self.budget.io)
+    }
+}
+
+lazy_static! {
+    static ref STATE: Mutex<SchedulerState> = Mutex::new(SchedulerState::default());
+    static ref RELEASED: Notify = Notify::new();
+}
+
+/// 실행 허가 (drop 시 예산 반환)
+pub struct WorkPermit {
+    class: WorkClass,
+}
+
+impl Drop for WorkPermit {
+    fn drop(&mut self) {
+        {
+            let mut state = STATE.lock().unwrap();
+            state.cpu_in_use = state.cpu_in_use.saturating_sub(1);
+            if self.class.uses_io() {
+                state.io_in_use = state.io_in_use.saturating_sub(1);
+            }
+        }
+        RELEASED.notify_waiters();
+    }
+}
+
+// 대기 수 기록 (취소된 작업도 대기 수에서 빠지도록 drop에서 감소)
+struct WaitGuard {
+    class: WorkClass,
+}
+
+impl Drop for WaitGuard {
+    fn drop(&mut self) {
+        {
+            let mut state = STATE.lock().unwrap();
+            state.waiting[self.class.rank()] -= 1;
+        }
+        // 높은 우선순위 대기가 끝나면 낮은 우선순위가 진행할 수 있음
+        RELEASED.notify_waiters();
+    }
+}
+
+/// 우선순위에 따라 실행 허가 받기
+pub async fn acquire(class: WorkClass) -> WorkPermit {
+    STATE.lock().unwrap().waiting[class.rank()] += 1;
+    let _waiting = WaitGuard { class };
+
+    loop {
+        let released = RELEASED.notified();
+        tokio::pin!(released);
+        released.as_mut().enable();
+
+        {
+            let mut state = STATE.lock().unwrap();
+            if state.can_run(class) {
+                state.cpu_in_use += 1;
+                if class.uses_io() {
+                    state.io_in_use += 1;
+                }
+                return WorkPermit { class };
+            }
+        }
+        released.await;
+    }
+}
+
+/// 현재 예산
+pub fn budget() -> WorkerBudget {
+    STATE.lock().unwrap().budget
+}
+
+/// 예산 변경 (0은 1로), 늘어난 만큼 대기 중인 작업이 바로 진행
+pub fn set_budget(budget: WorkerBudget) -> WorkerBudget {
+    let budget = WorkerBudget {
+        cpu: budget.cpu.max(1),
+        io: budget.io.max(1),
+    };
+    STATE.lock().unwrap().budget = budget;
+    RELEASED.notify_waiters();
+    budget
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_and_budget() {
+        let mut state = SchedulerState {
+            budget: WorkerBudget { cpu: 2, io: 1 },
+            ..Default::default()
+        };
+
+        // 화면에 보이는 썸네일이 기다리면 HQ는 대기
+        state.waiting[WorkClass::LqVisible.rank()] = 1;
+        assert!(!state.can_run(WorkClass::Hq));
+        assert!(state.can_run(WorkClass::LqVisible));
+
+        // I/O 예산이 차면 HQ만 대기, 저화질은 CPU 예산 안에서 진행
+        state.waiting = [0; WorkClass::COUNT];
+        state.cpu_in_use = 1;
+        state.io_in_use = 1;
+        assert!(!state.can_run(WorkClass::Hq));
+        assert!(state.can_run(WorkClass::LqRest));
+
+        // 뷰어는 예산과 관계없이 실행
+        state.cpu_in_use = 2;
+        assert!(state.can_run(WorkClass::Viewer));
+    }
+}
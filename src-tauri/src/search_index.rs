@@ -0,0 +1,249 @@
+use rayon::prelude::*;
+use rusqlite::{params, OptionalExtension};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+use crate::metadata_store::{ImageRecord, MetadataStore};
+
+/// 전문 검색 테이블 스키마 (FTS5, 문서 id는 image_search_docs.id)
+/// 접두어 검색이 빠르도록 2~3글자 접두어 인덱스 포함
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS image_search_docs (
+        id    INTEGER PRIMARY KEY,
+        path  TEXT NOT NULL UNIQUE,
+        mtime INTEGER NOT NULL
+    );
+
+    CREATE VIRTUAL TABLE IF NOT EXISTS image_search USING fts5(
+        file_name, keywords, camera, lens, caption, folder,
+        tokenize = 'unicode61 remove_diacritics 2',
+        prefix = '2 3'
+    );
+";
+
+/// 열별 가중치 (file_name, keywords, camera, lens, caption, folder 순, bm25)
+const RANK_WEIGHTS: &str = "10.0, 5.0, 1.0, 1.0, 3.0, 2.0";
+
+/// 한 번에 검색 색인을 갱신할 경로 수 (처음 채울 때)
+const SYNC_CHUNK_SIZE: usize = 500;
+
+/// 검색 결과 최대 개수 기본값
+pub const DEFAULT_SEARCH_LIMIT: usize = 1000;
+
+/// 파일에서 읽는 검색어 (인덱스 DB에 없는 키워드와 캡션)
+struct SearchText {
+    keywords: String,
+    caption: String,
+}
+
+/// 검색 테이블 초기화
+pub fn init_schema(store: &MetadataStore) -> Result<(), String> {
+    store.with_conn(|conn| conn.execute_batch(SCHEMA))
+}
+
+// 키워드(계층은 마지막 이름까지 모두)와 설명/헤드라인/제목
+fn read_search_text(path: &str) -> SearchText {
+    let keywords = crate::keywords::read_keywords(Path::new(path))
+        .unwrap_or_default()
+        .iter()
+        .map(|keyword| keyword.replace('|', " "))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let caption = crate::iptc::read_iptc(path)
+        .map(|iptc| {
+            [iptc.description, iptc.headline, iptc.title]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .unwrap_or_default();
+    SearchText { keywords, caption }
+}
+
+/// 바뀐 경로의 검색 색인 갱신 (인덱싱된 mtime이 같으면 건너뜀, 인덱스에서 빠진 경로는 삭제)
+pub fn sync(store: &MetadataStore, paths: &[String]) -> Result<(), String> {
+    let records = store.get_records(paths)?;
+    let docs: HashMap<String, (i64, u64)> = store.with_conn(|conn| {
+        let mut stmt = conn.prepare_cached("SELECT id, mtime FROM image_search_docs WHERE path = ?1")?;
+        let mut docs = HashMap::new();
+        for path in paths {
+            let doc = stmt
+                .query_row(params![path], |row| Ok((row.get(0)?, row.get::<_, i64>(1)? as u64)))
+                .optional()?;
+            if let Some(doc) = doc {
+                docs.insert(path.clone(), doc);
+            }
+        }
+        Ok(docs)
+    })?;
+
+    let removed: Vec<i64> = paths
+        .iter()
+        .filter(|path| !records.contains_key(*path))
+        .filter_map(|path| docs.get(path).map(|(id, _)| *id))
+        .collect();
+
+    // 파일을 읽는 부분만 병렬
+    let changed: Vec<(&ImageRecord, SearchText)> = records
+        .values()
+        .filter(|record| docs.get(&record.path).map(|(_, mtime)| *mtime) != Some(record.mtime))
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|record| (record, read_search_text(&record.path)))
+        .collect();
+
+    if removed.is_empty() && changed.is_empty() {
+        return Ok(());
+    }
+
+    store.with_conn(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        for id in &removed {
+            tx.execute("DELETE FROM image_search WHERE rowid = ?1", params![id])?;
+            tx.execute("DELETE FROM image_search_docs WHERE id = ?1", params![id])?;
+        }
+
+        for (record, text) in &changed {
+            let id = match docs.get(&record.path) {
+                Some((id, _)) => {
+                    tx.execute("DELETE FROM image_search WHERE rowid = ?1", params![id])?;
+                    tx.execute(
+                        "UPDATE image_search_docs SET mtime = ?1 WHERE id = ?2",
+                        params![record.mtime as i64, id],
+                    )?;
+                    *id
+                }
+                None => {
+                    tx.execute(
+                        "INSERT INTO image_search_docs (path, mtime) VALUES (?1, ?2)",
+                        params![record.path, record.mtime as i64],
+                    )?;
+                    tx.last_insert_rowid()
+                }
+            };
+
+            let camera = [record.camera_make.as_deref(), record.camera_model.as_deref()]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" ");
+            tx.execute(
+                "INSERT INTO image_search (rowid, file_name, keywords, camera, lens, caption, folder) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    id,
+                    record.file_name,
+                    text.keywords,
+                    camera,
+                    record.lens_model.as_deref().unwrap_or_default(),
+                    text.caption,
+                    record.folder,
+                ],
+            )?;
+        }
+        tx.commit()
+    })
+}
+
+/// 입력을 FTS5 질의로 변환 (단어마다 접두어 검색, 모든 단어 포함)
+/// 따옴표로 감싸서 FTS 연산자(AND, NEAR, * 등)가 그대로 해석되지 않게 함
+fn fts_query(text: &str) -> Option<String> {
+    let terms: Vec<String> = text
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// 빠른 검색 (파일명, 키워드, 카메라, 렌즈, 캡션, 폴더 경로에서 접두어 일치, 관련도 순)
+/// folder가 있으면 그 폴더(하위 폴더 포함) 안에서만
+pub fn quick_search(
+    store: &MetadataStore,
+    text: &str,
+    folder: Option<&str>,
+    limit: usize,
+) -> Result<Vec<String>, String> {
+    let Some(query) = fts_query(text) else {
+        return Ok(Vec::new());
+    };
+    // 이름이 같은 접두어로 시작하는 다른 폴더("Photos2")는 제외되도록 구분자까지 비교
+    let folder_prefix = folder.map(|folder| {
+        format!("{}{}", folder.trim_end_matches(['/', '\\']), std::path::MAIN_SEPARATOR)
+    });
+
+    let sql = format!(
+        "SELECT d.path FROM image_search s JOIN image_search_docs d ON d.id = s.rowid \
+         WHERE image_search MATCH ?1 AND (?2 IS NULL OR substr(d.path, 1, length(?2)) = ?2) \
+         ORDER BY bm25(image_search, {}) LIMIT ?3",
+        RANK_WEIGHTS
+    );
+    store.with_conn(|conn| {
+        let mut stmt = conn.prepare_cached(&sql)?;
+        let rows = stmt.query_map(params![query, folder_prefix, limit as i64], |row| row.get(0))?;
+        rows.collect()
+    })
+}
+
+// 검색 색인에 없는 인덱싱된 이미지 채우기 (기능 추가 전에 인덱싱된 이미지)
+fn backfill(store: &MetadataStore) -> Result<(), String> {
+    let missing: Vec<String> = store.with_conn(|conn| {
+        let mut stmt = conn.prepare(
+            "SELECT path FROM images WHERE path NOT IN (SELECT path FROM image_search_docs)",
+        )?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        rows.collect()
+    })?;
+
+    if !missing.is_empty() {
+        tracing::info!("Building search index for {} images", missing.len());
+    }
+    for chunk in missing.chunks(SYNC_CHUNK_SIZE) {
+        sync(store, chunk)?;
+    }
+    Ok(())
+}
+
+/// 인덱싱 변경을 구독해 검색 색인 갱신 (시작할 때 빠진 항목 채움)
+pub fn spawn_updates(store: Arc<MetadataStore>) {
+    let mut receiver = store.subscribe();
+
+    tauri::async_runtime::spawn(async move {
+        let backfill_store = Arc::clone(&store);
+        if let Ok(Err(e)) = tokio::task::spawn_blocking(move || backfill(&backfill_store)).await {
+            tracing::warn!("Failed to build search index: {}", e);
+        }
+
+        loop {
+            match receiver.recv().await {
+                Ok(changed) => {
+                    let store = Arc::clone(&store);
+                    let result = tokio::task::spawn_blocking(move || sync(&store, &changed)).await;
+                    if let Ok(Err(e)) = result {
+                        tracing::warn!("Failed to update search index: {}", e);
+                    }
+                }
+                // 놓친 변경은 인덱스에 없는 항목 채우기로 보완 (바뀐 항목은 다음 변경 때 갱신)
+                Err(broadcast::error::RecvError::Lagged(_)) => {
+                    let store = Arc::clone(&store);
+                    let _ = tokio::task::spawn_blocking(move || backfill(&store)).await;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fts_query() {
+        assert_eq!(fts_query("IMG 서울").as_deref(), Some("\"IMG\"* \"서울\"*"));
+        assert_eq!(fts_query("say \"hi\"").as_deref(), Some("\"say\"* \"\"\"hi\"\"\"*"));
+        assert_eq!(fts_query("  "), None);
+    }
+}
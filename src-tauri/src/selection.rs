@@ -0,0 +1,171 @@
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use crate::metadata_store::MetadataStore;
+
+/// 선택 집합 (선택한 순서 유지)
+#[derive(Debug, Default)]
+struct Selection {
+    paths: Vec<String>,
+    members: HashSet<String>,
+}
+
+impl Selection {
+    fn add(&mut self, paths: impl IntoIterator<Item = String>) {
+        for path in paths {
+            if self.members.insert(path.clone()) {
+                self.paths.push(path);
+            }
+        }
+    }
+
+    fn remove(&mut self, paths: &[String]) {
+        let removed: HashSet<&String> = paths.iter().filter(|path| self.members.remove(*path)).collect();
+        if !removed.is_empty() {
+            self.paths.retain(|path| !removed.contains(path));
+        }
+    }
+
+    /// all 기준으로 선택 반전 (all 순서대로)
+    fn invert(&mut self, all: &[String]) {
+        let inverted: Vec<String> = all.iter().filter(|path| !self.members.contains(*path)).cloned().collect();
+        *self = Self::default();
+        self.add(inverted);
+    }
+}
+
+/// 선택 통계 (selection_stats 응답)
+#[derive(Debug, Clone, Serialize)]
+pub struct SelectionStats {
+    pub count: usize,
+    pub total_size: u64,
+    /// 별점별 수 (0~5번 인덱스 = 별점)
+    pub ratings: [usize; 6],
+    /// 메타데이터를 읽지 못한 파일 수 (크기/별점에서 제외)
+    pub unknown: usize,
+}
+
+lazy_static! {
+    static ref SELECTIONS: Mutex<HashMap<u64, Selection>> = Mutex::new(HashMap::new());
+}
+
+static NEXT_SELECTION_ID: AtomicU64 = AtomicU64::new(1);
+
+fn with_selection<T>(id: u64, f: impl FnOnce(&mut Selection) -> T) -> Result<T, String> {
+    let mut selections = SELECTIONS.lock().unwrap();
+    let selection = selections.get_mut(&id).ok_or_else(|| format!("{}: selection {}", crate::error::NOT_FOUND, id))?;
+    Ok(f(selection))
+}
+
+/// 선택 집합 생성 후 id 반환 (이후 작업은 경로 목록 대신 id로)
+pub fn create(paths: Vec<String>) -> u64 {
+    let id = NEXT_SELECTION_ID.fetch_add(1, Ordering::SeqCst);
+    let mut selection = Selection::default();
+    selection.add(paths);
+    SELECTIONS.lock().unwrap().insert(id, selection);
+    id
+}
+
+/// 선택 집합 해제
+pub fn release(id: u64) {
+    SELECTIONS.lock().unwrap().remove(&id);
+}
+
+/// 경로 추가 후 선택 수 반환
+pub fn add(id: u64, paths: Vec<String>) -> Result<usize, String> {
+    with_selection(id, |selection| {
+        selection.add(paths);
+        selection.paths.len()
+    })
+}
+
+/// 경로 제외 후 선택 수 반환
+pub fn remove(id: u64, paths: &[String]) -> Result<usize, String> {
+    with_selection(id, |selection| {
+        selection.remove(paths);
+        selection.paths.len()
+    })
+}
+
+/// 목록의 start..=end 범위 추가 (Shift 클릭, 순서는 상관없음) 후 선택 수 반환
+pub fn add_range(id: u64, all: &[String], start: usize, end: usize) -> Result<usize, String> {
+    let (start, end) = (start.min(end), start.max(end));
+    if all.is_empty() || start >= all.len() {
+        return Err(format!("Range out of bounds: {}..={} (total {})", start, end, all.len()));
+    }
+    let range = all[start..=end.min(all.len() - 1)].to_vec();
+    add(id, range)
+}
+
+/// 목록 기준으로 선택 반전 후 선택 수 반환
+pub fn invert(id: u64, all: &[String]) -> Result<usize, String> {
+    with_selection(id, |selection| {
+        selection.invert(all);
+        selection.paths.len()
+    })
+}
+
+/// 선택된 경로 (선택한 순서)
+pub fn paths(id: u64) -> Result<Vec<String>, String> {
+    with_selection(id, |selection| selection.paths.clone())
+}
+
+/// 선택 통계 (인덱싱된 메타데이터 사용, 바뀐 파일만 다시 읽음)
+pub fn stats(store: &MetadataStore, id: u64) -> Result<SelectionStats, String> {
+    let paths = paths(id)?;
+    store.index_files(&paths)?;
+    let records = store.get_records(&paths)?;
+
+    let mut stats = SelectionStats {
+        count: paths.len(),
+        total_size: 0,
+        ratings: [0; 6],
+        unknown: paths.len() - records.len(),
+    };
+    for record in records.values() {
+        stats.total_size += record.file_size;
+        stats.ratings[record.rating.clamp(0, 5) as usize] += 1;
+    }
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_selection_operations() {
+        let all = paths(&["a", "b", "c", "d", "e"]);
+        let mut selection = Selection::default();
+
+        selection.add(paths(&["c", "a", "c"]));
+        assert_eq!(selection.paths, paths(&["c", "a"]));
+
+        selection.remove(&paths(&["c", "x"]));
+        assert_eq!(selection.paths, paths(&["a"]));
+
+        selection.invert(&all);
+        assert_eq!(selection.paths, paths(&["b", "c", "d", "e"]));
+        assert!(!selection.members.contains("a"));
+    }
+
+    #[test]
+    fn test_add_range() {
+        let all = paths(&["a", "b", "c", "d"]);
+        let id = create(paths(&["a"]));
+
+        assert_eq!(add_range(id, &all, 2, 1), Ok(3));
+        assert_eq!(add_range(id, &all, 3, 10), Ok(4));
+        assert!(add_range(id, &all, 4, 5).is_err());
+
+        release(id);
+        assert!(add(id, Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,358 @@
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use tauri::{Emitter, Manager};
+
+use crate::cloud_file::CloudFileMode;
+use crate::export::QuickExportPreset;
+use crate::file_stack::StackPolicy;
+use crate::keywords::KeywordDialect;
+use crate::local_cache::LocalCachePolicy;
+use crate::shortcuts::ShortcutBinding;
+use crate::watermark::WatermarkOptions;
+
+/// 현재 설정 스키마 버전
+pub const SETTINGS_VERSION: u32 = 1;
+
+lazy_static! {
+    /// 현재 설정 (워커들이 실행 중에 읽음)
+    static ref SETTINGS: RwLock<Settings> = RwLock::new(Settings::default());
+}
+
+/// 앱 설정
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub version: u32,
+    /// 썸네일 생성 동시 작업 수 (None이면 CPU 코어의 25%)
+    pub thumbnail_workers: Option<usize>,
+    /// HQ 썸네일 최대 동시 생성 수 (None이면 CPU 코어의 절반)
+    pub hq_max_concurrent: Option<usize>,
+    /// HQ 썸네일 생성 전 유휴 시간 임계값 (밀리초)
+    pub idle_threshold_ms: u64,
+    /// 썸네일 캐시 최대 용량 (MB, None이면 제한 없음)
+    pub cache_max_mb: Option<u64>,
+    /// 썸네일 캐시 폴더 (None이면 앱 데이터 폴더, 기존 캐시를 옮겨야 하므로 relocate_thumbnail_cache로만 변경)
+    pub thumbnail_cache_dir: Option<String>,
+    /// 사진 폴더 안 .pixengine 캐시 사용 방식 (외장 드라이브와 함께 옮겨지는 캐시)
+    pub local_cache_policy: LocalCachePolicy,
+    /// RAW 썸네일에 큰 내장 미리보기 사용 (느리지만 선명함)
+    pub raw_prefer_full_preview: bool,
+    /// 삭제 시 휴지통으로 이동 (false면 영구 삭제)
+    pub delete_to_trash: bool,
+    /// 배터리로 동작 중이면 HQ 썸네일 생성 보류
+    pub defer_hq_on_battery: bool,
+    /// 발열로 성능이 제한되면 HQ 썸네일 생성 보류
+    pub defer_hq_on_thermal: bool,
+    /// 범용 포맷(PNG, TIFF 등) 썸네일 축소에 GPU 사용 (영역 평균 필터라 CPU Lanczos3보다 약간 덜 선명, 실패 시 CPU)
+    pub gpu_resize: bool,
+    /// ICC 색 프로파일을 sRGB로 변환 (끄면 빠르지만 광색역 사진이 흐리게 보임)
+    pub color_management: bool,
+    /// 투명 이미지 썸네일의 알파 유지 (false면 배경색 위에 합성)
+    pub thumbnail_keep_alpha: bool,
+    /// 투명 이미지 썸네일 배경색 (#RRGGBB)
+    pub thumbnail_background: String,
+    /// 클라우드 전용 파일(OneDrive 등) 썸네일 생성 시 다운로드 방식
+    pub cloud_file_mode: CloudFileMode,
+    /// 이미지 수가 이 값 이상이면 썸네일 완료 이벤트를 묶어서 전송
+    pub thumbnail_batch_min_images: usize,
+    /// 썸네일 완료 이벤트 묶음 전송 간격 (밀리초)
+    pub thumbnail_batch_interval_ms: u64,
+    /// 썸네일 완료 이벤트 묶음 최대 크기 (가득 차면 바로 전송)
+    pub thumbnail_batch_size: usize,
+    /// 전역 단축키 (앱 시작 시 등록)
+    pub global_shortcuts: Vec<ShortcutBinding>,
+    /// 메인 윈도우를 닫으면 트레이로 숨김 (백그라운드 작업 계속)
+    pub minimize_to_tray: bool,
+    /// 키워드를 쓰는 방식 (darktable/digiKam 사이드카 호환)
+    pub keyword_dialect: KeywordDialect,
+    /// EXIF 내장 썸네일 최소 크기 (긴 변 px, 0이면 제한 없음), 작으면 저화질로 표시하고 HQ로 교체
+    pub exif_thumbnail_min_size: u32,
+    /// 최소 크기보다 작은 EXIF 내장 썸네일은 쓰지 않고 바로 DCT로 생성
+    pub skip_low_quality_exif: bool,
+    /// 다른 프로세스가 사용 중인 파일의 재시도 횟수 (썸네일/메타데이터)
+    pub lock_retry_attempts: u32,
+    /// 첫 재시도 대기 시간 (밀리초, 재시도마다 2배)
+    pub lock_retry_delay_ms: u64,
+    /// 별점/라벨을 쓸 때 파일의 읽기 전용 속성을 잠시 해제 (쓴 뒤 복원)
+    pub auto_clear_read_only: bool,
+    /// 빠른 내보내기 프리셋별 워터마크 (없는 프리셋은 워터마크 없음)
+    pub quick_export_watermarks: HashMap<QuickExportPreset, WatermarkOptions>,
+    /// 같은 이름의 RAW+JPEG, HEIC+MOV 묶음 대표 파일 선택 방식
+    pub stack_policy: StackPolicy,
+    /// HiDPI 화면의 큰 그리드 셀용 썸네일 크기 (긴 변 px, 320이면 사용 안 함)
+    pub hidpi_thumbnail_size: u32,
+    /// 미리보기(조정 미리보기, 확대 영역)에서 soft_proof_profile 출력 장치의 색을 흉내냄 (소프트 프루핑)
+    /// 모니터 변환은 webview가 하므로 여기서는 하지 않음
+    pub monitor_soft_proof: bool,
+    /// 소프트 프루핑할 출력 장치 ICC 프로파일 파일 (프린터/용지, 다른 모니터 등)
+    pub soft_proof_profile: Option<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            thumbnail_workers: None,
+            hq_max_concurrent: None,
+            idle_threshold_ms: 3000,
+            cache_max_mb: None,
+            thumbnail_cache_dir: None,
+            local_cache_policy: LocalCachePolicy::Off,
+            raw_prefer_full_preview: false,
+            delete_to_trash: true,
+            defer_hq_on_battery: true,
+            defer_hq_on_thermal: true,
+            gpu_resize: false,
+            color_management: true,
+            thumbnail_keep_alpha: true,
+            thumbnail_background: "#ffffff".to_string(),
+            cloud_file_mode: CloudFileMode::Defer,
+            thumbnail_batch_min_images: 200,
+            thumbnail_batch_interval_ms: 100,
+            thumbnail_batch_size: 64,
+            global_shortcuts: Vec::new(),
+            minimize_to_tray: false,
+            keyword_dialect: KeywordDialect::Standard,
+            exif_thumbnail_min_size: 240,
+            skip_low_quality_exif: false,
+            lock_retry_attempts: 3,
+            lock_retry_delay_ms: 500,
+            auto_clear_read_only: false,
+            quick_export_watermarks: HashMap::new(),
+            stack_policy: StackPolicy::PreferJpeg,
+            hidpi_thumbnail_size: 640,
+            monitor_soft_proof: false,
+            soft_proof_profile: None,
+        }
+    }
+}
+
+impl Settings {
+    /// 썸네일 생성 동시 작업 수
+    pub fn thumbnail_workers(&self) -> usize {
+        self.thumbnail_workers
+            .unwrap_or_else(|| (num_cpus::get() / 4).max(1))
+    }
+
+    /// HQ 썸네일 최대 동시 생성 수
+    pub fn hq_max_concurrent(&self) -> usize {
+        self.hq_max_concurrent
+            .unwrap_or_else(|| (num_cpus::get() / 2).max(1))
+    }
+
+    /// 썸네일 배경색 RGB (형식이 잘못되면 흰색)
+    pub fn thumbnail_background_rgb(&self) -> [u8; 3] {
+        parse_hex_color(&self.thumbnail_background).unwrap_or([255, 255, 255])
+    }
+
+    /// 값 범위 검증
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(workers) = self.thumbnail_workers {
+            if !(1..=64).contains(&workers) {
+                return Err(format!("Invalid thumbnail_workers: {} (1-64)", workers));
+            }
+        }
+        if let Some(concurrent) = self.hq_max_concurrent {
+            if !(1..=64).contains(&concurrent) {
+                return Err(format!("Invalid hq_max_concurrent: {} (1-64)", concurrent));
+            }
+        }
+        if self.idle_threshold_ms > 600_000 {
+            return Err(format!("Invalid idle_threshold_ms: {} (0-600000)", self.idle_threshold_ms));
+        }
+        if let Some(max_mb) = self.cache_max_mb {
+            if max_mb < 100 {
+                return Err(format!("Invalid cache_max_mb: {} (minimum 100)", max_mb));
+            }
+        }
+        if let Some(dir) = &self.thumbnail_cache_dir {
+            if !std::path::Path::new(dir).is_absolute() {
+                return Err(format!("Invalid thumbnail_cache_dir: {} (absolute path required)", dir));
+            }
+        }
+        if let Some(profile) = &self.soft_proof_profile {
+            if !std::path::Path::new(profile).is_absolute() {
+                return Err(format!("Invalid soft_proof_profile: {} (absolute path required)", profile));
+            }
+        }
+        if !(10..=5000).contains(&self.thumbnail_batch_interval_ms) {
+            return Err(format!(
+                "Invalid thumbnail_batch_interval_ms: {} (10-5000)",
+                self.thumbnail_batch_interval_ms
+            ));
+        }
+        if !(1..=1000).contains(&self.thumbnail_batch_size) {
+            return Err(format!("Invalid thumbnail_batch_size: {} (1-1000)", self.thumbnail_batch_size));
+        }
+        if self.exif_thumbnail_min_size > 4096 {
+            return Err(format!(
+                "Invalid exif_thumbnail_min_size: {} (0-4096)",
+                self.exif_thumbnail_min_size
+            ));
+        }
+        if self.lock_retry_attempts > 10 {
+            return Err(format!("Invalid lock_retry_attempts: {} (0-10)", self.lock_retry_attempts));
+        }
+        if !(10..=10_000).contains(&self.lock_retry_delay_ms) {
+            return Err(format!("Invalid lock_retry_delay_ms: {} (10-10000)", self.lock_retry_delay_ms));
+        }
+        if !(320..=1280).contains(&self.hidpi_thumbnail_size) {
+            return Err(format!("Invalid hidpi_thumbnail_size: {} (320-1280)", self.hidpi_thumbnail_size));
+        }
+        if parse_hex_color(&self.thumbnail_background).is_none() {
+            return Err(format!("Invalid thumbnail_background: {} (#RRGGBB)", self.thumbnail_background));
+        }
+        for watermark in self.quick_export_watermarks.values() {
+            watermark.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// "#RRGGBB" 형식 색상 파싱
+pub fn parse_hex_color(value: &str) -> Option<[u8; 3]> {
+    let hex = value.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+// 설정 파일 경로 가져오기
+fn get_settings_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|p| p.join("settings.json"))
+        .map_err(|e| format!("Failed to get app data dir: {}", e))
+}
+
+/// 이전 스키마 버전의 설정을 현재 버전으로 변환
+/// 필드 이름/의미가 바뀌면 여기서 버전별로 변환 (없는 필드는 serde 기본값으로 채움)
+fn migrate(mut value: serde_json::Value) -> serde_json::Value {
+    let version = value.get("version").and_then(|v| v.as_u64()).unwrap_or(0);
+
+    if version < SETTINGS_VERSION as u64 {
+        tracing::info!("Migrating settings from version {} to {}", version, SETTINGS_VERSION);
+    }
+
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("version".to_string(), serde_json::json!(SETTINGS_VERSION));
+    }
+    value
+}
+
+fn save(app: &tauri::AppHandle, settings: &Settings) -> Result<(), String> {
+    let path = get_settings_path(app)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let content = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    fs::write(path, content).map_err(|e| e.to_string())
+}
+
+/// 설정 파일 로드 (앱 시작 시 1회, 손상되었거나 범위를 벗어나면 기본값)
+pub fn load(app: &tauri::AppHandle) -> Result<Settings, String> {
+    let path = get_settings_path(app)?;
+
+    let settings = if path.exists() {
+        let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+        let loaded = serde_json::from_str::<serde_json::Value>(&content)
+            .ok()
+            .and_then(|value| serde_json::from_value::<Settings>(migrate(value)).ok())
+            .filter(|settings| settings.validate().is_ok());
+
+        match loaded {
+            Some(settings) => settings,
+            None => {
+                tracing::warn!("Invalid settings file, using defaults");
+                Settings::default()
+            }
+        }
+    } else {
+        Settings::default()
+    };
+
+    *SETTINGS.write().map_err(|_| "Settings lock poisoned".to_string())? = settings.clone();
+    Ok(settings)
+}
+
+/// 현재 설정
+pub fn current() -> Settings {
+    SETTINGS.read().map(|s| s.clone()).unwrap_or_default()
+}
+
+/// 설정 일부 변경 (patch에 있는 필드만 갱신) 후 저장, settings-changed 이벤트 전송
+pub fn update(app: &tauri::AppHandle, patch: serde_json::Value) -> Result<Settings, String> {
+    let mut value = serde_json::to_value(current()).map_err(|e| e.to_string())?;
+
+    let (Some(target), Some(changes)) = (value.as_object_mut(), patch.as_object()) else {
+        return Err("Invalid settings patch".to_string());
+    };
+    for (key, change) in changes {
+        if key != "version" && key != "thumbnail_cache_dir" {
+            target.insert(key.clone(), change.clone());
+        }
+    }
+
+    let settings: Settings = serde_json::from_value(value)
+        .map_err(|e| format!("Invalid settings: {}", e))?;
+    apply(app, settings)
+}
+
+/// 썸네일 캐시 폴더 (설정 전체를 복사하지 않음, 썸네일마다 호출됨)
+pub fn thumbnail_cache_dir() -> Option<String> {
+    SETTINGS.read().ok().and_then(|s| s.thumbnail_cache_dir.clone())
+}
+
+/// 폴더별 캐시 사용 방식 (썸네일마다 호출됨)
+pub fn local_cache_policy() -> LocalCachePolicy {
+    SETTINGS.read().map(|s| s.local_cache_policy).unwrap_or(LocalCachePolicy::Off)
+}
+
+/// 썸네일 캐시 폴더 변경 (캐시 파일 이동은 thumbnail_cache::relocate)
+pub fn set_thumbnail_cache_dir(app: &tauri::AppHandle, dir: Option<String>) -> Result<Settings, String> {
+    apply(app, Settings {
+        thumbnail_cache_dir: dir,
+        ..current()
+    })
+}
+
+// 검증 후 저장, settings-changed 이벤트 전송
+fn apply(app: &tauri::AppHandle, settings: Settings) -> Result<Settings, String> {
+    settings.validate()?;
+
+    save(app, &settings)?;
+    *SETTINGS.write().map_err(|_| "Settings lock poisoned".to_string())? = settings.clone();
+
+    let _ = app.emit("settings-changed", &settings);
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_unversioned() {
+        let migrated = migrate(serde_json::json!({ "idle_threshold_ms": 5000 }));
+        let settings: Settings = serde_json::from_value(migrated).unwrap();
+        assert_eq!(settings.version, SETTINGS_VERSION);
+        assert_eq!(settings.idle_threshold_ms, 5000);
+        assert!(settings.delete_to_trash);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(Settings::default().validate().is_ok());
+        assert!(Settings { thumbnail_workers: Some(0), ..Default::default() }.validate().is_err());
+        assert!(Settings { cache_max_mb: Some(10), ..Default::default() }.validate().is_err());
+        assert!(Settings { thumbnail_background: "white".to_string(), ..Default::default() }.validate().is_err());
+    }
+}
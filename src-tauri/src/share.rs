@@ -0,0 +1,175 @@
+use std::path::PathBuf;
+use tauri::{AppHandle, WebviewWindow};
+
+// 공유할 로컬 파일 (원격/압축 파일 항목은 스풀로 받은 경로, 없는 파일은 오류)
+fn local_files(app: &AppHandle, paths: &[String]) -> Result<Vec<PathBuf>, String> {
+    paths
+        .iter()
+        .map(|path| {
+            let file = if crate::remote_source::is_remote_path(path) {
+                crate::remote_source::spool(app, path)?
+            } else if crate::archive_source::is_archive_path(path) {
+                crate::archive_source::spool(app, path)?
+            } else {
+                crate::fs_path::to_fs_path(path)
+            };
+            if !file.is_file() {
+                return Err(format!("{}: file {}", crate::error::NOT_FOUND, path));
+            }
+            Ok(file)
+        })
+        .collect()
+}
+
+// 메인 스레드에서 실행하고 결과 대기 (공유 UI는 메인 스레드에서만 띄울 수 있음)
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+fn run_on_main<F>(window: &WebviewWindow, f: F) -> Result<(), String>
+where
+    F: FnOnce() -> Result<(), String> + Send + 'static,
+{
+    let (sender, receiver) = std::sync::mpsc::channel();
+    window
+        .run_on_main_thread(move || {
+            let _ = sender.send(f());
+        })
+        .map_err(|e| format!("Failed to run on main thread: {}", e))?;
+    receiver
+        .recv()
+        .map_err(|_| "Share UI was not shown".to_string())?
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+    use tauri::WebviewWindow;
+    use windows::core::{factory, Interface, HSTRING};
+    use windows::ApplicationModel::DataTransfer::{DataRequestedEventArgs, DataTransferManager};
+    use windows::Foundation::Collections::IIterable;
+    use windows::Foundation::TypedEventHandler;
+    use windows::Storage::{IStorageItem, StorageFile};
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::System::WinRT::IDataTransferManagerInterop;
+
+    /// 이전 공유의 DataRequested 핸들러 (다음 공유 때 교체)
+    static DATA_REQUESTED: Mutex<Option<i64>> = Mutex::new(None);
+
+    fn hwnd(window: &WebviewWindow) -> Result<HWND, String> {
+        use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+        let handle = window
+            .window_handle()
+            .map_err(|e| format!("Failed to get window handle: {}", e))?;
+        match handle.as_raw() {
+            RawWindowHandle::Win32(win32_handle) => Ok(HWND(win32_handle.hwnd.get() as *mut _)),
+            _ => Err("Unexpected window handle".to_string()),
+        }
+    }
+
+    /// DataTransferManager 공유 UI (파일은 미리 StorageFile로 열어 둠, UI 스레드에서 비동기 대기를 하지 않도록)
+    pub fn show(window: &WebviewWindow, files: Vec<PathBuf>) -> Result<(), String> {
+        let items: Vec<Option<IStorageItem>> = files
+            .iter()
+            .map(|file| {
+                StorageFile::GetFileFromPathAsync(&HSTRING::from(file.as_os_str()))
+                    .and_then(|operation| operation.get())
+                    .and_then(|file| file.cast::<IStorageItem>())
+                    .map(Some)
+                    .map_err(|e| format!("Failed to open {}: {}", file.display(), e))
+            })
+            .collect::<Result<_, String>>()?;
+        let title = match files.as_slice() {
+            [file] => file.file_name().unwrap_or_default().to_string_lossy().to_string(),
+            _ => format!("사진 {}장", files.len()),
+        };
+        let hwnd = hwnd(window)?.0 as isize;
+
+        super::run_on_main(window, move || {
+            let hwnd = HWND(hwnd as *mut _);
+            let result = (|| -> windows::core::Result<()> {
+                let interop = factory::<DataTransferManager, IDataTransferManagerInterop>()?;
+                let manager: DataTransferManager = unsafe { interop.GetForWindow(hwnd)? };
+
+                let mut token = DATA_REQUESTED.lock().unwrap();
+                if let Some(previous) = token.take() {
+                    manager.RemoveDataRequested(previous)?;
+                }
+                *token = Some(manager.DataRequested(&TypedEventHandler::new(
+                    move |_, args: &Option<DataRequestedEventArgs>| {
+                        let Some(args) = args else {
+                            return Ok(());
+                        };
+                        let data = args.Request()?.Data()?;
+                        data.Properties()?.SetTitle(&HSTRING::from(title.as_str()))?;
+                        let list = IIterable::<IStorageItem>::try_from(items.clone())?;
+                        data.SetStorageItemsReadOnly(&list)
+                    },
+                ))?);
+                drop(token);
+
+                unsafe { interop.ShowShareUIForWindow(hwnd) }
+            })();
+            result.map_err(|e| format!("Failed to show share UI: {}", e))
+        })
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::cell::RefCell;
+    use std::path::PathBuf;
+    use objc2::rc::Retained;
+    use objc2_app_kit::{NSSharingServicePicker, NSView};
+    use objc2_foundation::{MainThreadMarker, NSArray, NSPoint, NSRect, NSRectEdge, NSSize, NSString, NSURL};
+    use tauri::WebviewWindow;
+
+    thread_local! {
+        /// 표시 중인 선택기 (닫힐 때까지 해제되지 않도록 다음 공유 전까지 보관)
+        static PICKER: RefCell<Option<Retained<NSSharingServicePicker>>> = const { RefCell::new(None) };
+    }
+
+    /// NSSharingServicePicker (AirDrop, 메일, 메시지 등, 창 가운데 위쪽에 표시)
+    pub fn show(window: &WebviewWindow, files: Vec<PathBuf>) -> Result<(), String> {
+        let ns_view = window.ns_view().map_err(|e| format!("Failed to get window view: {}", e))? as usize;
+
+        super::run_on_main(window, move || {
+            let mtm = MainThreadMarker::new().ok_or("Not on main thread")?;
+            let urls: Vec<Retained<NSURL>> = files
+                .iter()
+                .map(|file| unsafe { NSURL::fileURLWithPath(&NSString::from_str(&file.to_string_lossy())) })
+                .collect();
+            let items = NSArray::from_vec(urls);
+            let view: &NSView = unsafe { &*(ns_view as *const NSView) };
+
+            unsafe {
+                let picker = NSSharingServicePicker::initWithItems(mtm.alloc(), &Retained::cast::<NSArray>(items));
+                let bounds = view.bounds();
+                let anchor = NSRect::new(
+                    NSPoint::new(bounds.size.width / 2.0, bounds.size.height - 1.0),
+                    NSSize::new(1.0, 1.0),
+                );
+                picker.showRelativeToRect_ofView_preferredEdge(anchor, view, NSRectEdge::NSRectEdgeMinY);
+                PICKER.with(|slot| *slot.borrow_mut() = Some(picker));
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+mod platform {
+    use std::path::PathBuf;
+    use tauri::WebviewWindow;
+
+    pub fn show(_window: &WebviewWindow, _files: Vec<PathBuf>) -> Result<(), String> {
+        Err("Sharing is not supported on this platform".to_string())
+    }
+}
+
+/// 선택한 파일을 OS 공유 UI로 보내기 (선택기가 뜨면 바로 반환, 공유 완료는 기다리지 않음)
+pub fn share_files(app: &AppHandle, window: &WebviewWindow, paths: &[String]) -> Result<(), String> {
+    if paths.is_empty() {
+        return Err("No files to share".to_string());
+    }
+    let files = local_files(app, paths)?;
+    platform::show(window, files)
+}
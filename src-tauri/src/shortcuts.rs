@@ -0,0 +1,123 @@
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+/// 전역 단축키 동작 (다른 앱에 포커스가 있어도 동작, 테더링 촬영 중 검토용)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutAction {
+    ToggleSlideshow,
+    NextImage,
+    PreviousImage,
+    /// 별점 제거
+    Rate0,
+    Rate1,
+    Rate2,
+    Rate3,
+    Rate4,
+    Rate5,
+}
+
+/// 단축키 설정 항목
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShortcutBinding {
+    pub action: ShortcutAction,
+    /// 키 조합 (예: "CmdOrCtrl+Shift+1", "MediaPlayPause")
+    pub accelerator: String,
+}
+
+/// 등록 실패 항목
+#[derive(Debug, Clone, Serialize)]
+pub struct ShortcutFailure {
+    pub action: ShortcutAction,
+    pub accelerator: String,
+    pub reason: String,
+}
+
+/// set_global_shortcuts 결과
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ShortcutRegistration {
+    pub registered: Vec<ShortcutBinding>,
+    pub failed: Vec<ShortcutFailure>,
+}
+
+/// global-shortcut 이벤트 페이로드
+#[derive(Debug, Clone, Serialize)]
+struct ShortcutTriggered {
+    action: ShortcutAction,
+}
+
+lazy_static! {
+    /// 등록된 단축키 ID → 동작
+    static ref REGISTERED: Mutex<HashMap<u32, ShortcutAction>> = Mutex::new(HashMap::new());
+}
+
+/// 플러그인 핸들러: 눌렸을 때만 global-shortcut 이벤트 전송
+pub fn handle_shortcut(app: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) {
+    if event.state() != ShortcutState::Pressed {
+        return;
+    }
+
+    let action = REGISTERED
+        .lock()
+        .ok()
+        .and_then(|registered| registered.get(&shortcut.id()).copied());
+    if let Some(action) = action {
+        let _ = app.emit("global-shortcut", ShortcutTriggered { action });
+    }
+}
+
+/// 기존 단축키를 모두 해제하고 새로 등록
+/// 잘못된 키 조합, 목록 안의 중복, 다른 앱이 이미 사용 중인 키는 실패로 보고 (나머지는 계속 등록)
+pub fn apply(app: &AppHandle, bindings: &[ShortcutBinding]) -> ShortcutRegistration {
+    let manager = app.global_shortcut();
+    if let Err(e) = manager.unregister_all() {
+        tracing::warn!("Failed to unregister global shortcuts: {}", e);
+    }
+
+    let mut registered = HashMap::new();
+    let mut result = ShortcutRegistration::default();
+
+    for binding in bindings {
+        let mut fail = |reason: String| {
+            result.failed.push(ShortcutFailure {
+                action: binding.action,
+                accelerator: binding.accelerator.clone(),
+                reason,
+            });
+        };
+
+        let shortcut = match binding.accelerator.parse::<Shortcut>() {
+            Ok(shortcut) => shortcut,
+            Err(e) => {
+                fail(format!("Invalid accelerator: {}", e));
+                continue;
+            }
+        };
+
+        if let Some(existing) = registered.get(&shortcut.id()) {
+            fail(format!("Conflicts with {:?}", existing));
+            continue;
+        }
+
+        if let Err(e) = manager.register(shortcut) {
+            fail(format!("Already in use by another application: {}", e));
+            continue;
+        }
+
+        registered.insert(shortcut.id(), binding.action);
+        result.registered.push(binding.clone());
+    }
+
+    if !result.failed.is_empty() {
+        tracing::warn!("{} global shortcuts could not be registered", result.failed.len());
+    }
+
+    if let Ok(mut current) = REGISTERED.lock() {
+        *current = registered;
+    }
+    result
+}
@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+
+use crate::thumbnail_queue::{self, ThumbnailQueueManager};
+
+/// 종료 시 진행 중인 작업을 기다리는 최대 시간
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// 임시 파일 확장자 (쓰기 완료 후 rename)
+const PARTIAL_EXTENSION: &str = "partial";
+
+/// 종료 진행 중 플래그 (한 번만 실행)
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// 진행 중인 캐시 쓰기 수
+static PENDING_WRITES: AtomicUsize = AtomicUsize::new(0);
+
+/// 임시 파일 번호 (같은 파일을 동시에 써도 임시 파일이 겹치지 않도록)
+static PARTIAL_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// 다음 실행 시 이어서 처리할 썸네일 작업
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QueueResumeState {
+    pub thumbnail_pending: Vec<String>,
+    pub hq_pending: Vec<String>,
+}
+
+pub fn is_shutting_down() -> bool {
+    SHUTTING_DOWN.load(Ordering::SeqCst)
+}
+
+/// 파일을 원자적으로 저장 (임시 파일에 쓴 뒤 rename → 중간에 종료돼도 반쯤 쓰인 파일이 남지 않음)
+/// rename 전에 디스크에 기록 (설정, 사용자 파일 등 잃으면 안 되는 데이터용)
+pub fn write_atomic(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    write_via_partial(path, data, true)
+}
+
+/// 캐시 파일을 원자적으로 저장 (fsync 없음)
+/// 전원이 꺼지면 사라질 수 있지만 다시 만들 수 있으므로 썸네일 등 캐시에만 사용
+pub fn write_cache_atomic(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    write_via_partial(path, data, false)
+}
+
+fn write_via_partial(path: &Path, data: &[u8], durable: bool) -> std::io::Result<()> {
+    PENDING_WRITES.fetch_add(1, Ordering::SeqCst);
+
+    let seq = PARTIAL_SEQ.fetch_add(1, Ordering::Relaxed);
+    let partial_path = path.with_extension(format!("{}.{}", seq, PARTIAL_EXTENSION));
+    let result = fs::File::create(&partial_path)
+        .and_then(|mut file| {
+            file.write_all(data)?;
+            if durable {
+                file.sync_data()?;
+            }
+            Ok(())
+        })
+        .and_then(|_| fs::rename(&partial_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&partial_path);
+    }
+
+    PENDING_WRITES.fetch_sub(1, Ordering::SeqCst);
+    result
+}
+
+/// 이전 실행에서 남은 임시 파일 삭제 (비정상 종료 등)
+pub fn remove_partial_writes(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    let removed = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == PARTIAL_EXTENSION))
+        .filter(|path| fs::remove_file(path).is_ok())
+        .count();
+
+    if removed > 0 {
+        tracing::info!("Removed {} partial cache files", removed);
+    }
+}
+
+fn get_resume_state_path(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|p| p.join("queue_state.json"))
+        .map_err(|e| format!("Failed to get app data dir: {}", e))
+}
+
+fn save_resume_state(app: &AppHandle, state: &QueueResumeState) -> Result<(), String> {
+    let path = get_resume_state_path(app)?;
+    let json = serde_json::to_string(state)
+        .map_err(|e| format!("Failed to serialize queue state: {}", e))?;
+    write_atomic(&path, json.as_bytes())
+        .map_err(|e| format!("Failed to write queue state: {}", e))
+}
+
+/// 저장된 작업 상태를 읽고 삭제 (없으면 None)
+pub fn take_resume_state(app: &AppHandle) -> Result<Option<QueueResumeState>, String> {
+    let path = get_resume_state_path(app)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read queue state: {}", e))?;
+    let _ = fs::remove_file(&path);
+
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse queue state: {}", e))
+}
+
+/// 앱 종료 처리
+/// 1. 썸네일/HQ 워커에 새 작업을 주지 않도록 취소
+/// 2. 진행 중인 캐시 쓰기, HQ 배치, 별점 쓰기가 끝날 때까지 대기 (최대 SHUTDOWN_TIMEOUT)
+/// 3. 남은 작업 목록을 저장 (다음 실행 시 이어서 처리)
+pub fn shutdown(app: &AppHandle) {
+    if SHUTTING_DOWN.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    tracing::info!("Shutting down background workers");
+
+    thumbnail_queue::cancel_hq_thumbnail_generation();
+
+    let thumbnail_pending = match app.try_state::<Arc<Mutex<ThumbnailQueueManager>>>() {
+        Some(queue) => tauri::async_runtime::block_on(async {
+            queue.lock().await.drain_pending().await
+        }),
+        None => Vec::new(),
+    };
+
+    let deadline = Instant::now() + SHUTDOWN_TIMEOUT;
+    while (PENDING_WRITES.load(Ordering::SeqCst) > 0
+        || thumbnail_queue::is_hq_running()
+        || crate::rating_queue::pending_count() > 0)
+        && Instant::now() < deadline
+    {
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    if PENDING_WRITES.load(Ordering::SeqCst) > 0 {
+        tracing::warn!("Shutdown timed out with cache writes in progress");
+    }
+    if crate::rating_queue::pending_count() > 0 {
+        tracing::warn!("Shutdown timed out with rating writes in progress");
+    }
+
+    let state = QueueResumeState {
+        thumbnail_pending,
+        hq_pending: thumbnail_queue::take_hq_pending(),
+    };
+    if state.thumbnail_pending.is_empty() && state.hq_pending.is_empty() {
+        return;
+    }
+
+    match save_resume_state(app, &state) {
+        Ok(()) => tracing::info!(
+            "Saved queue state ({} thumbnails, {} HQ thumbnails pending)",
+            state.thumbnail_pending.len(),
+            state.hq_pending.len()
+        ),
+        Err(e) => tracing::warn!("Failed to save queue state: {}", e),
+    }
+}
@@ -0,0 +1,85 @@
+use serde::Serialize;
+use std::path::Path;
+use tauri::AppHandle;
+
+use crate::formats::is_image_file;
+use crate::fs_path::to_display;
+
+/// 실행 인자로 받은 열 대상 (open-target 이벤트)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchTarget {
+    /// 열 폴더 (이미지 경로면 상위 폴더)
+    pub folder: String,
+    /// 선택할 이미지 (폴더를 연 경우 None)
+    pub image: Option<String>,
+}
+
+/// 실행 인자에서 열 대상 찾기 (첫 번째 인자는 실행 파일, 옵션은 무시)
+/// 상대 경로는 실행한 위치(cwd) 기준, 존재하지 않는 경로는 건너뜀
+pub fn parse_args(args: &[String], cwd: &Path) -> Option<LaunchTarget> {
+    args.iter()
+        .skip(1)
+        .filter(|arg| !arg.starts_with('-'))
+        .map(|arg| cwd.join(arg))
+        .find_map(|path| target_for(&path))
+}
+
+/// 경로 검증: 폴더 또는 지원하는 이미지 파일만 (없는 경로는 None)
+pub fn target_for(path: &Path) -> Option<LaunchTarget> {
+    let path = crate::fs_path::to_fs_path(path).canonicalize().ok()?;
+
+    if path.is_dir() {
+        return Some(LaunchTarget {
+            folder: to_display(&path),
+            image: None,
+        });
+    }
+    if path.is_file() && is_image_file(&path) {
+        return Some(LaunchTarget {
+            folder: to_display(path.parent()?),
+            image: Some(to_display(&path)),
+        });
+    }
+    None
+}
+
+/// 이 인스턴스의 실행 인자로 받은 열 대상
+pub fn launch_target() -> Option<LaunchTarget> {
+    let args: Vec<String> = std::env::args().collect();
+    let cwd = std::env::current_dir().ok()?;
+    parse_args(&args, &cwd)
+}
+
+/// 두 번째 실행 시 호출: 기존 창을 앞으로 가져오고 인자를 open-target 이벤트로 전달
+pub fn handle_second_instance(app: &AppHandle, args: Vec<String>, cwd: String) {
+    tracing::info!("Second instance launched with {} arguments", args.len());
+    crate::tray::show_main_window(app);
+
+    if let Some(target) = parse_args(&args, Path::new(&cwd)) {
+        crate::open_target::dispatch(app, target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_args_skips_flags_and_missing_paths() {
+        let dir = std::env::temp_dir().join("pixengine_single_instance_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.jpg"), b"").unwrap();
+
+        let args: Vec<String> = ["pixengine", "--flag", "missing.jpg", "a.jpg"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let target = parse_args(&args, &dir).unwrap();
+        let expected = dir.canonicalize().unwrap().join("a.jpg");
+        assert_eq!(target.image.as_deref().map(Path::new), Some(expected.as_path()));
+
+        assert_eq!(parse_args(&args[..2], &dir), None);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,463 @@
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::broadcast;
+
+use crate::metadata_store::MetadataStore;
+
+/// 스마트 앨범 테이블 스키마
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS smart_albums (
+        id         INTEGER PRIMARY KEY AUTOINCREMENT,
+        name       TEXT NOT NULL,
+        rules      TEXT NOT NULL,
+        created_at INTEGER NOT NULL
+    );
+";
+
+/// 평가 결과 스트리밍 청크 크기
+const RESULT_CHUNK_SIZE: usize = 500;
+
+/// 라이브 업데이트 시 한 번에 검사할 경로 수 (SQLite 변수 제한 고려)
+const LIVE_CHECK_CHUNK_SIZE: usize = 500;
+
+/// 숫자 비교 연산자
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+impl CompareOp {
+    fn as_sql(&self) -> &'static str {
+        match self {
+            CompareOp::Eq => "=",
+            CompareOp::Ne => "!=",
+            CompareOp::Gt => ">",
+            CompareOp::Gte => ">=",
+            CompareOp::Lt => "<",
+            CompareOp::Lte => "<=",
+        }
+    }
+}
+
+/// 문자열 비교 연산자 (대소문자 무시)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextOp {
+    Equals,
+    Contains,
+    StartsWith,
+}
+
+/// 규칙 결합 방식
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchMode {
+    All,
+    Any,
+}
+
+/// 단일 규칙
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "field", rename_all = "snake_case")]
+pub enum AlbumRule {
+    Rating { op: CompareOp, value: i32 },
+    Iso { op: CompareOp, value: u32 },
+    FocalLength { op: CompareOp, value: f64 },
+    Aperture { op: CompareOp, value: f64 },
+    CameraMake { op: TextOp, value: String },
+    CameraModel { op: TextOp, value: String },
+    LensModel { op: TextOp, value: String },
+    Extension { values: Vec<String> },
+    Folder { path: String, include_subfolders: bool },
+    /// 날짜 범위 ("YYYY-MM-DD" 또는 "YYYY-MM-DD HH:MM:SS", 양 끝 포함)
+    DateTaken { from: Option<String>, to: Option<String> },
+}
+
+/// 규칙 집합
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleSet {
+    pub match_mode: MatchMode,
+    pub rules: Vec<AlbumRule>,
+}
+
+/// 저장된 스마트 앨범
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmartAlbum {
+    pub id: i64,
+    pub name: String,
+    pub rules: RuleSet,
+    pub created_at: i64,
+}
+
+/// 평가 결과 청크 이벤트
+#[derive(Debug, Clone, Serialize)]
+struct SmartAlbumResults {
+    album_id: i64,
+    paths: Vec<String>,
+    done: bool,
+}
+
+/// 라이브 업데이트 이벤트 (변경된 경로 중 일치/불일치)
+#[derive(Debug, Clone, Serialize)]
+struct SmartAlbumChanged {
+    album_id: i64,
+    matched: Vec<String>,
+    unmatched: Vec<String>,
+}
+
+/// LIKE 패턴 이스케이프
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+fn text_condition(column: &str, op: TextOp, value: &str, params: &mut Vec<Value>) -> String {
+    match op {
+        TextOp::Equals => {
+            params.push(Value::Text(value.to_string()));
+            format!("{} = ? COLLATE NOCASE", column)
+        }
+        TextOp::Contains => {
+            params.push(Value::Text(format!("%{}%", escape_like(value))));
+            format!("{} LIKE ? ESCAPE '\\'", column)
+        }
+        TextOp::StartsWith => {
+            params.push(Value::Text(format!("{}%", escape_like(value))));
+            format!("{} LIKE ? ESCAPE '\\'", column)
+        }
+    }
+}
+
+impl AlbumRule {
+    /// SQL 조건식으로 변환 (파라미터는 params에 추가)
+    fn to_sql(&self, params: &mut Vec<Value>) -> String {
+        match self {
+            AlbumRule::Rating { op, value } => {
+                params.push(Value::Integer(*value as i64));
+                format!("rating {} ?", op.as_sql())
+            }
+            AlbumRule::Iso { op, value } => {
+                params.push(Value::Integer(*value as i64));
+                format!("iso {} ?", op.as_sql())
+            }
+            AlbumRule::FocalLength { op, value } => {
+                params.push(Value::Real(*value));
+                format!("focal_length {} ?", op.as_sql())
+            }
+            AlbumRule::Aperture { op, value } => {
+                params.push(Value::Real(*value));
+                format!("aperture {} ?", op.as_sql())
+            }
+            AlbumRule::CameraMake { op, value } => text_condition("camera_make", *op, value, params),
+            AlbumRule::CameraModel { op, value } => text_condition("camera_model", *op, value, params),
+            AlbumRule::LensModel { op, value } => text_condition("lens_model", *op, value, params),
+            AlbumRule::Extension { values } => {
+                if values.is_empty() {
+                    return "0".to_string();
+                }
+                for ext in values {
+                    params.push(Value::Text(ext.trim_start_matches('.').to_lowercase()));
+                }
+                format!("extension IN ({})", vec!["?"; values.len()].join(", "))
+            }
+            AlbumRule::Folder { path, include_subfolders } => {
+                let folder = path.trim_end_matches(['/', '\\']).to_string();
+                params.push(Value::Text(folder.clone()));
+                if *include_subfolders {
+                    params.push(Value::Text(format!(
+                        "{}{}%",
+                        escape_like(&folder),
+                        std::path::MAIN_SEPARATOR
+                    )));
+                    "(folder = ? OR folder LIKE ? ESCAPE '\\')".to_string()
+                } else {
+                    "folder = ?".to_string()
+                }
+            }
+            AlbumRule::DateTaken { from, to } => {
+                let mut conditions = vec!["date_taken IS NOT NULL".to_string()];
+                if let Some(from) = from {
+                    params.push(Value::Text(from.clone()));
+                    conditions.push("date_taken >= ?".to_string());
+                }
+                if let Some(to) = to {
+                    // 날짜만 지정된 경우 해당 날짜 전체 포함
+                    let to = if to.len() == 10 {
+                        format!("{} 23:59:59", to)
+                    } else {
+                        to.clone()
+                    };
+                    params.push(Value::Text(to));
+                    conditions.push("date_taken <= ?".to_string());
+                }
+                format!("({})", conditions.join(" AND "))
+            }
+        }
+    }
+}
+
+impl RuleSet {
+    /// WHERE 절로 변환 (규칙이 없으면 모든 이미지 일치)
+    pub fn to_sql(&self, params: &mut Vec<Value>) -> String {
+        if self.rules.is_empty() {
+            return "1".to_string();
+        }
+
+        let joiner = match self.match_mode {
+            MatchMode::All => " AND ",
+            MatchMode::Any => " OR ",
+        };
+
+        let conditions: Vec<String> = self.rules.iter().map(|rule| rule.to_sql(params)).collect();
+        format!("({})", conditions.join(joiner))
+    }
+}
+
+/// 스마트 앨범 테이블 초기화
+pub fn init_schema(store: &MetadataStore) -> Result<(), String> {
+    store.with_conn(|conn| conn.execute_batch(SCHEMA))
+}
+
+fn row_to_album(row: &rusqlite::Row) -> rusqlite::Result<(i64, String, String, i64)> {
+    Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+}
+
+fn parse_album((id, name, rules, created_at): (i64, String, String, i64)) -> Result<SmartAlbum, String> {
+    let rules: RuleSet = serde_json::from_str(&rules)
+        .map_err(|e| format!("Invalid rules for album {}: {}", id, e))?;
+    Ok(SmartAlbum { id, name, rules, created_at })
+}
+
+/// 스마트 앨범 생성
+pub fn create_album(store: &MetadataStore, name: &str, rules: RuleSet) -> Result<SmartAlbum, String> {
+    let rules_json = serde_json::to_string(&rules).map_err(|e| e.to_string())?;
+    let created_at = chrono::Utc::now().timestamp();
+
+    let id = store.with_conn(|conn| {
+        conn.execute(
+            "INSERT INTO smart_albums (name, rules, created_at) VALUES (?1, ?2, ?3)",
+            params![name, rules_json, created_at],
+        )?;
+        Ok(conn.last_insert_rowid())
+    })?;
+
+    Ok(SmartAlbum {
+        id,
+        name: name.to_string(),
+        rules,
+        created_at,
+    })
+}
+
+/// 스마트 앨범 수정
+pub fn update_album(store: &MetadataStore, id: i64, name: &str, rules: RuleSet) -> Result<SmartAlbum, String> {
+    let rules_json = serde_json::to_string(&rules).map_err(|e| e.to_string())?;
+
+    let updated = store.with_conn(|conn| {
+        conn.execute(
+            "UPDATE smart_albums SET name = ?1, rules = ?2 WHERE id = ?3",
+            params![name, rules_json, id],
+        )
+    })?;
+
+    if updated == 0 {
+        return Err(format!("{}: smart album {}", crate::error::NOT_FOUND, id));
+    }
+
+    get_album(store, id)
+}
+
+/// 스마트 앨범 삭제
+pub fn delete_album(store: &MetadataStore, id: i64) -> Result<(), String> {
+    store.with_conn(|conn| conn.execute("DELETE FROM smart_albums WHERE id = ?1", params![id]))?;
+    Ok(())
+}
+
+/// 스마트 앨범 조회
+pub fn get_album(store: &MetadataStore, id: i64) -> Result<SmartAlbum, String> {
+    let row = store.with_conn(|conn| {
+        conn.query_row(
+            "SELECT id, name, rules, created_at FROM smart_albums WHERE id = ?1",
+            params![id],
+            row_to_album,
+        )
+    })?;
+    parse_album(row)
+}
+
+/// 모든 스마트 앨범 조회
+pub fn list_albums(store: &MetadataStore) -> Result<Vec<SmartAlbum>, String> {
+    let rows = store.with_conn(|conn| {
+        let mut stmt = conn.prepare("SELECT id, name, rules, created_at FROM smart_albums ORDER BY id")?;
+        let rows = stmt.query_map([], row_to_album)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+    })?;
+
+    rows.into_iter().map(parse_album).collect()
+}
+
+/// 스마트 앨범 평가 (청크 단위로 smart-album-results 이벤트 전송)
+/// 반환값: 일치한 이미지 총 개수
+pub fn evaluate_streaming(app: &AppHandle, store: &MetadataStore, album: &SmartAlbum) -> Result<usize, String> {
+    let mut sql_params = Vec::new();
+    let where_clause = album.rules.to_sql(&mut sql_params);
+    let sql = format!(
+        "SELECT path FROM images WHERE {} ORDER BY date_taken, path",
+        where_clause
+    );
+
+    store.with_conn(|conn| {
+        let mut stmt = conn.prepare(&sql)?;
+        let mut rows = stmt.query(params_from_iter(sql_params.iter()))?;
+
+        let mut total = 0;
+        let mut chunk = Vec::with_capacity(RESULT_CHUNK_SIZE);
+
+        while let Some(row) = rows.next()? {
+            chunk.push(row.get::<_, String>(0)?);
+            total += 1;
+
+            if chunk.len() >= RESULT_CHUNK_SIZE {
+                let _ = app.emit("smart-album-results", SmartAlbumResults {
+                    album_id: album.id,
+                    paths: std::mem::take(&mut chunk),
+                    done: false,
+                });
+            }
+        }
+
+        let _ = app.emit("smart-album-results", SmartAlbumResults {
+            album_id: album.id,
+            paths: chunk,
+            done: true,
+        });
+
+        Ok(total)
+    })
+}
+
+/// 변경된 경로들에 대해 각 앨범 일치 여부 재평가
+fn notify_album_changes(app: &AppHandle, store: &MetadataStore, changed: &[String]) -> Result<(), String> {
+    let albums = list_albums(store)?;
+
+    for album in albums {
+        let mut matched = Vec::new();
+
+        for chunk in changed.chunks(LIVE_CHECK_CHUNK_SIZE) {
+            let mut sql_params: Vec<Value> = chunk.iter().map(|p| Value::Text(p.clone())).collect();
+            let placeholders = vec!["?"; chunk.len()].join(", ");
+            let where_clause = album.rules.to_sql(&mut sql_params);
+            let sql = format!(
+                "SELECT path FROM images WHERE path IN ({}) AND {}",
+                placeholders, where_clause
+            );
+
+            let paths = store.with_conn(|conn| {
+                let mut stmt = conn.prepare(&sql)?;
+                let rows = stmt.query_map(params_from_iter(sql_params.iter()), |row| row.get::<_, String>(0))?;
+                rows.collect::<rusqlite::Result<Vec<_>>>()
+            })?;
+            matched.extend(paths);
+        }
+
+        let matched_set: HashSet<&String> = matched.iter().collect();
+        let unmatched: Vec<String> = changed
+            .iter()
+            .filter(|p| !matched_set.contains(p))
+            .cloned()
+            .collect();
+
+        let _ = app.emit("smart-album-changed", SmartAlbumChanged {
+            album_id: album.id,
+            matched,
+            unmatched,
+        });
+    }
+
+    Ok(())
+}
+
+/// 메타데이터 변경 구독 → 스마트 앨범 라이브 업데이트
+pub fn spawn_live_updates(app: AppHandle, store: Arc<MetadataStore>) {
+    let mut receiver = store.subscribe();
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            match receiver.recv().await {
+                Ok(changed) => {
+                    let app = app.clone();
+                    let store = Arc::clone(&store);
+                    let result = tokio::task::spawn_blocking(move || {
+                        notify_album_changes(&app, &store, &changed)
+                    })
+                    .await;
+
+                    if let Ok(Err(e)) = result {
+                        tracing::warn!("Failed to update smart albums: {}", e);
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => {
+                    // 변경 알림을 놓쳤으면 프론트엔드에 전체 재평가 요청
+                    let _ = app.emit("smart-album-stale", true);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_set_to_sql() {
+        let rules = RuleSet {
+            match_mode: MatchMode::All,
+            rules: vec![
+                AlbumRule::Rating { op: CompareOp::Gte, value: 4 },
+                AlbumRule::CameraModel { op: TextOp::Equals, value: "Z 8".to_string() },
+                AlbumRule::DateTaken {
+                    from: Some("2024-01-01".to_string()),
+                    to: Some("2024-01-31".to_string()),
+                },
+            ],
+        };
+
+        let mut params = Vec::new();
+        let sql = rules.to_sql(&mut params);
+
+        assert_eq!(
+            sql,
+            "(rating >= ? AND camera_model = ? COLLATE NOCASE AND \
+             (date_taken IS NOT NULL AND date_taken >= ? AND date_taken <= ?))"
+        );
+        assert_eq!(params.len(), 4);
+        assert_eq!(params[3], Value::Text("2024-01-31 23:59:59".to_string()));
+    }
+
+    #[test]
+    fn test_empty_rule_set_matches_all() {
+        let rules = RuleSet { match_mode: MatchMode::Any, rules: vec![] };
+        let mut params = Vec::new();
+        assert_eq!(rules.to_sql(&mut params), "1");
+        assert!(params.is_empty());
+    }
+
+    #[test]
+    fn test_escape_like() {
+        assert_eq!(escape_like("100%_a\\b"), "100\\%\\_a\\\\b");
+    }
+}
@@ -0,0 +1,158 @@
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::metadata_store::MetadataStore;
+
+/// 정렬 기준
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    Name,
+    Size,
+    Modified,
+    DateTaken,
+    Rating,
+    /// 사용자 지정 순서 (순서에 없는 파일은 뒤에 파일명 순)
+    Manual,
+}
+
+/// 정렬 방향
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+/// 정렬 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct SortResult {
+    /// 정렬된 경로
+    pub paths: Vec<String>,
+    /// 정렬된 각 경로의 원래 인덱스 (paths[i] == 입력[indices[i]])
+    pub indices: Vec<usize>,
+}
+
+/// 자연 정렬 비교 (대소문자 무시, 숫자는 값으로 비교: "IMG_2" < "IMG_10")
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+
+    loop {
+        match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(ac), Some(bc)) if ac.is_ascii_digit() && bc.is_ascii_digit() => {
+                let mut a_num = String::new();
+                while let Some(c) = a_chars.next_if(|c| c.is_ascii_digit()) {
+                    a_num.push(c);
+                }
+                let mut b_num = String::new();
+                while let Some(c) = b_chars.next_if(|c| c.is_ascii_digit()) {
+                    b_num.push(c);
+                }
+
+                // 앞자리 0을 제외한 길이 → 자릿값 순으로 비교 (큰 숫자도 오버플로 없음)
+                let a_digits = a_num.trim_start_matches('0');
+                let b_digits = b_num.trim_start_matches('0');
+                let ordering = a_digits
+                    .len()
+                    .cmp(&b_digits.len())
+                    .then_with(|| a_digits.cmp(b_digits))
+                    .then_with(|| a_num.len().cmp(&b_num.len()));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(ac), Some(bc)) => {
+                let ordering = ac.to_lowercase().cmp(bc.to_lowercase());
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}
+
+fn file_name(path: &str) -> &str {
+    Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(path)
+}
+
+/// 이미지 경로 정렬 (같은 값이면 파일명 자연 정렬 순)
+/// manual_order는 SortKey::Manual일 때 쓰는 폴더의 사용자 지정 순서
+pub fn sort_image_paths(
+    store: &MetadataStore,
+    paths: Vec<String>,
+    key: SortKey,
+    order: SortOrder,
+    manual_order: Option<&[String]>,
+) -> Result<SortResult, String> {
+    let positions: HashMap<&str, usize> = manual_order
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+        .map(|(position, path)| (path.as_str(), position))
+        .collect();
+
+    // 변경된 파일은 먼저 다시 인덱싱 (DB에 없는 파일은 오름차순에서 맨 앞)
+    let records = if matches!(key, SortKey::Name | SortKey::Manual) {
+        HashMap::new()
+    } else {
+        store.index_files(&paths)?;
+        store.get_records(&paths)?
+    };
+
+    let mut indices: Vec<usize> = (0..paths.len()).collect();
+    indices.par_sort_by(|&a, &b| {
+        let (a_path, b_path) = (&paths[a], &paths[b]);
+        let a_record = records.get(a_path);
+        let b_record = records.get(b_path);
+
+        let ordering = match key {
+            SortKey::Name => Ordering::Equal,
+            SortKey::Size => a_record.map(|r| r.file_size).cmp(&b_record.map(|r| r.file_size)),
+            SortKey::Modified => a_record.map(|r| r.mtime).cmp(&b_record.map(|r| r.mtime)),
+            SortKey::DateTaken => a_record
+                .and_then(|r| r.date_taken.as_ref())
+                .cmp(&b_record.and_then(|r| r.date_taken.as_ref())),
+            SortKey::Rating => a_record.map(|r| r.rating).cmp(&b_record.map(|r| r.rating)),
+            // 순서에 없는 파일(usize::MAX)은 뒤로
+            SortKey::Manual => {
+                let position = |path: &String| positions.get(path.as_str()).copied().unwrap_or(usize::MAX);
+                position(a_path).cmp(&position(b_path))
+            }
+        }
+        .then_with(|| natural_cmp(file_name(a_path), file_name(b_path)));
+
+        match order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+    });
+
+    let sorted = indices.iter().map(|&i| paths[i].clone()).collect();
+    Ok(SortResult { paths: sorted, indices })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_natural_cmp() {
+        assert_eq!(natural_cmp("IMG_2.jpg", "IMG_10.jpg"), Ordering::Less);
+        assert_eq!(natural_cmp("img_10.jpg", "IMG_9.jpg"), Ordering::Greater);
+        assert_eq!(natural_cmp("a.jpg", "A.jpg"), Ordering::Equal);
+        assert_eq!(natural_cmp("IMG_002.jpg", "IMG_2.jpg"), Ordering::Greater);
+        assert_eq!(natural_cmp("DSC", "DSC_0001"), Ordering::Less);
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// 스풀에 받은 파일 (크기, 마지막 사용 순번), 예산을 넘으면 오래 쓰지 않은 파일부터 삭제
+#[derive(Default)]
+pub struct SpoolIndex {
+    files: HashMap<PathBuf, (u64, u64)>,
+    total: u64,
+    clock: u64,
+}
+
+impl SpoolIndex {
+    /// 폴더에 이미 있는 파일로 시작 (이전 실행에서 받은 파일, 수정 시간 순서를 사용 순서로)
+    pub fn scan(dir: &Path, include: impl Fn(&Path) -> bool) -> Self {
+        let mut existing: Vec<(PathBuf, u64, u64)> = fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                let metadata = entry.metadata().ok().filter(|m| m.is_file())?;
+                let modified = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                    .map_or(0, |duration| duration.as_secs());
+                include(&path).then_some((path, metadata.len(), modified))
+            })
+            .collect();
+        existing.sort_by_key(|(_, _, modified)| *modified);
+
+        let mut index = Self::default();
+        for (path, size, _) in existing {
+            index.touch(&path, size);
+        }
+        index
+    }
+
+    pub fn touch(&mut self, path: &Path, size: u64) {
+        self.clock += 1;
+        if let Some(previous) = self.files.insert(path.to_path_buf(), (size, self.clock)) {
+            self.total -= previous.0;
+        }
+        self.total += size;
+    }
+
+    pub fn remove(&mut self, path: &Path) {
+        if let Some((size, _)) = self.files.remove(path) {
+            self.total -= size;
+        }
+    }
+
+    /// 예산을 넘으면 오래 쓰지 않은 파일부터 목록에서 빼고 삭제할 경로 반환 (keep은 남김)
+    pub fn evict(&mut self, budget: u64, keep: &Path) -> Vec<PathBuf> {
+        let mut by_age: Vec<(PathBuf, u64, u64)> = self
+            .files
+            .iter()
+            .filter(|(path, _)| path.as_path() != keep)
+            .map(|(path, (size, used))| (path.clone(), *size, *used))
+            .collect();
+        by_age.sort_by_key(|(_, _, used)| *used);
+
+        let mut evicted = Vec::new();
+        for (path, size, _) in by_age {
+            if self.total <= budget {
+                break;
+            }
+            self.files.remove(&path);
+            self.total -= size;
+            evicted.push(path);
+        }
+        evicted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spool_eviction() {
+        let mut index = SpoolIndex::default();
+        index.touch(Path::new("a"), 60);
+        index.touch(Path::new("b"), 30);
+        index.touch(Path::new("a"), 60);
+        index.touch(Path::new("c"), 30);
+
+        // b가 가장 오래 전에 사용됨
+        assert_eq!(index.evict(100, Path::new("c")), vec![PathBuf::from("b")]);
+        assert_eq!(index.total, 90);
+
+        index.remove(Path::new("a"));
+        assert_eq!(index.total, 30);
+    }
+}
@@ -1,17 +1,41 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::{self, File};
+use std::future::Future;
 use std::io::{BufReader, Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
-use std::time::SystemTime;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Instant, SystemTime};
 
 use base64::{engine::general_purpose::STANDARD, Engine};
 use exif::{In, Reader, Tag};
 use image::{ImageBuffer, RgbImage};
 use jpeg_decoder::Decoder as JpegDecoder;
+use lazy_static::lazy_static;
 use tauri::Manager;
 use webp::Encoder as WebPEncoder;
 
+use crate::formats::{self, is_jpeg_file, is_psd_file, FormatCategory};
+use crate::thumbnail_perf::{self, ThumbnailPath};
+
+lazy_static! {
+    /// 생성 중인 캐시 키별 잠금 (LQ/HQ 워커가 같은 캐시 파일을 동시에 만들지 않도록)
+    static ref CACHE_KEY_LOCKS: Mutex<HashMap<String, Weak<tokio::sync::Mutex<()>>>> = Mutex::new(HashMap::new());
+    /// 진행 중인 썸네일 요청 (같은 파일의 동시 요청은 한 번만 디코딩하고 결과를 나눠 받음)
+    static ref IN_FLIGHT: Mutex<HashMap<(InFlightKind, String), InFlight>> = Mutex::new(HashMap::new());
+}
+
+type InFlight = Arc<tokio::sync::OnceCell<Result<ThumbnailResult, String>>>;
+
+/// 진행 중인 요청 종류 (저화질/고화질 결과가 달라 따로 묶음)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum InFlightKind {
+    Thumbnail,
+    Hq,
+    /// HiDPI 썸네일 (크기별)
+    Tier(u32),
+}
+
 /// 썸네일 결과
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThumbnailResult {
@@ -21,6 +45,10 @@ pub struct ThumbnailResult {
     pub height: u32,
     pub source: ThumbnailSource,
     pub exif_metadata: Option<ExifMetadata>,
+    pub has_alpha: bool, // 투명 영역 포함 (RGBA WebP)
+    /// 설정한 최소 크기보다 작은 EXIF 내장 썸네일 (HQ로 교체 예정)
+    #[serde(default)]
+    pub is_low_quality: bool,
 }
 
 /// 썸네일 소스 (어디서 가져왔는지)
@@ -77,6 +105,18 @@ pub fn generate_cache_key(file_path: &str, mtime: u64) -> String {
     format!("{}", hash.to_hex())
 }
 
+/// 기본 썸네일 크기 (긴 변 px, 캐시와 HQ 썸네일)
+pub const BASE_THUMBNAIL_SIZE: u32 = 320;
+
+/// 크기 단계별 썸네일 캐시 키 (기본 크기는 기존 키, 큰 단계는 별도 키)
+pub fn generate_tier_cache_key(file_path: &str, mtime: u64, size: u32) -> String {
+    if size <= BASE_THUMBNAIL_SIZE {
+        return generate_cache_key(file_path, mtime);
+    }
+    let input = format!("{}:{}@{}", file_path, mtime, size);
+    format!("{}", blake3::hash(input.as_bytes()).to_hex())
+}
+
 /// 파일 수정 시간 가져오기
 pub fn get_file_mtime(path: &str) -> Result<u64, String> {
     let metadata = fs::metadata(path)
@@ -92,8 +132,16 @@ pub fn get_file_mtime(path: &str) -> Result<u64, String> {
     Ok(mtime)
 }
 
-/// 캐시 디렉토리 가져오기
+/// 캐시 디렉토리 가져오기 (설정에서 다른 디스크로 옮겼으면 그 폴더)
 pub fn get_cache_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
+    match crate::settings::thumbnail_cache_dir() {
+        Some(dir) => Ok(PathBuf::from(dir)),
+        None => get_default_cache_dir(app_handle),
+    }
+}
+
+/// 기본 캐시 디렉토리 (앱 데이터 폴더)
+pub fn get_default_cache_dir(app_handle: &tauri::AppHandle) -> Result<PathBuf, String> {
     let app_data = app_handle.path().app_data_dir()
         .map_err(|e| format!("Failed to get app data directory: {}", e))?;
 
@@ -118,9 +166,18 @@ pub fn get_cache_path(app_handle: &tauri::AppHandle, cache_key: &str) -> Result<
     Ok(cache_dir.join(format!("{}.webp", cache_key)))
 }
 
+/// 원본 파일의 캐시 파일 경로 (폴더별 .pixengine 캐시 설정 반영)
+pub fn get_cache_path_for(app_handle: &tauri::AppHandle, file_path: &str, mtime: u64) -> Result<PathBuf, String> {
+    let central = get_cache_path(app_handle, &generate_cache_key(file_path, mtime))?;
+    Ok(crate::local_cache::resolve(central, file_path, mtime))
+}
+
 /// 메타데이터 파일 경로 가져오기 (폴더별)
 #[allow(dead_code)]
 pub fn get_metadata_path(app_handle: &tauri::AppHandle, folder_path: &str) -> Result<PathBuf, String> {
+    if let Some(local) = crate::local_cache::metadata_path(folder_path) {
+        return Ok(local);
+    }
     let metadata_dir = get_metadata_dir(app_handle)?;
     fs::create_dir_all(&metadata_dir)
         .map_err(|e| format!("Failed to create metadata directory: {}", e))?;
@@ -229,100 +286,130 @@ pub fn extract_exif_metadata(file_path: &str) -> Result<ExifMetadata, String> {
     Ok(metadata)
 }
 
-/// EXIF 내장 썸네일 추출
-pub fn extract_exif_thumbnail(file_path: &str) -> Result<Vec<u8>, String> {
-    let mut file = File::open(file_path)
-        .map_err(|e| format!("Failed to open file: {}", e))?;
+/// EXIF 썸네일 탐색에 읽는 파일 앞부분 (APP1 세그먼트는 64KB를 넘지 않음)
+const EXIF_HEAD_BYTES: usize = 128 * 1024;
 
-    // JPEG 시그니처 확인
-    let mut buffer = [0u8; 2];
-    file.read_exact(&mut buffer)
-        .map_err(|e| format!("Failed to read JPEG header: {}", e))?;
+/// 긴 변이 최소 크기보다 작은 내장 썸네일인지 (0이면 제한 없음)
+fn is_low_quality_exif(img: &image::DynamicImage, min_size: u32) -> bool {
+    img.width().max(img.height()) < min_size
+}
 
-    if buffer != [0xFF, 0xD8] {
-        return Err("Not a JPEG file".to_string());
+/// EXIF 내장 썸네일 추출 실패 원인
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExifThumbnailError {
+    /// 내장 썸네일 없음 (DCT 생성으로 넘어감)
+    NotFound,
+    /// 파일을 읽을 수 없거나 구조가 잘못됨
+    Invalid(String),
+}
+
+impl std::fmt::Display for ExifThumbnailError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExifThumbnailError::NotFound => write!(f, "No embedded EXIF thumbnail"),
+            ExifThumbnailError::Invalid(message) => write!(f, "{}", message),
+        }
     }
+}
 
-    // APP1 (EXIF) 마커 찾기
-    let exif_offset = loop {
-        file.read_exact(&mut buffer)
-            .map_err(|e| format!("Failed to read marker: {}", e))?;
+/// EXIF 내장 썸네일 추출
+/// 파일 앞부분만 한 번에 읽고 메모리에서 마커를 탐색 (마커마다 seek/read 하지 않음)
+pub fn extract_exif_thumbnail(file_path: &str) -> Result<Vec<u8>, ExifThumbnailError> {
+    let file = File::open(file_path)
+        .map_err(|e| ExifThumbnailError::Invalid(format!("Failed to open file: {}", e)))?;
 
-        if buffer[0] != 0xFF {
-            return Err("Invalid JPEG marker".to_string());
+    let mut head = Vec::with_capacity(EXIF_HEAD_BYTES);
+    file.take(EXIF_HEAD_BYTES as u64)
+        .read_to_end(&mut head)
+        .map_err(|e| ExifThumbnailError::Invalid(format!("Failed to read JPEG header: {}", e)))?;
+
+    extract_exif_thumbnail_from(&head)
+}
+
+/// JPEG 앞부분에서 EXIF(APP1) 세그먼트의 TIFF 데이터 찾기
+/// 채움 바이트(0xFF)와 길이 없는 마커는 건너뛰고, SOS/EOI나 읽은 범위 끝에서 멈춤
+fn find_exif_segment(data: &[u8]) -> Result<&[u8], ExifThumbnailError> {
+    let invalid = |message: &str| ExifThumbnailError::Invalid(message.to_string());
+
+    // JPEG 시그니처 확인
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        return Err(invalid("Not a JPEG file"));
+    }
+
+    let mut pos = 2;
+    loop {
+        match data.get(pos) {
+            Some(0xFF) => {}
+            Some(_) => return Err(invalid("Invalid JPEG marker")),
+            None => return Err(ExifThumbnailError::NotFound),
         }
 
-        let marker = buffer[1];
+        // 마커 앞 채움 바이트
+        while data.get(pos + 1) == Some(&0xFF) {
+            pos += 1;
+        }
+        let Some(&marker) = data.get(pos + 1) else {
+            return Err(ExifThumbnailError::NotFound);
+        };
+
+        match marker {
+            // SOS 이후는 압축 데이터, EOI는 파일 끝 (메타데이터 세그먼트 없음)
+            0xDA | 0xD9 => return Err(ExifThumbnailError::NotFound),
+            // 길이 없는 마커 (TEM, RSTn)
+            0x01 | 0xD0..=0xD7 => {
+                pos += 2;
+                continue;
+            }
+            0x00 => return Err(invalid("Invalid JPEG marker")),
+            _ => {}
+        }
 
-        // 세그먼트 길이 읽기
-        file.read_exact(&mut buffer)
-            .map_err(|e| format!("Failed to read segment length: {}", e))?;
-        let length = u16::from_be_bytes(buffer) as u64;
+        let Some(length) = data.get(pos + 2..pos + 4).map(|b| u16::from_be_bytes([b[0], b[1]]) as usize) else {
+            return Err(ExifThumbnailError::NotFound);
+        };
+        if length < 2 {
+            return Err(invalid("Invalid JPEG segment length"));
+        }
+        let end = pos + 2 + length;
 
-        if marker == 0xE1 {
-            // APP1 (EXIF) 마커 발견
-            let current_pos = file.stream_position()
-                .map_err(|e| format!("Failed to get position: {}", e))?;
-            break current_pos;
+        if marker == 0xE1 && data.get(pos + 4..pos + 10) == Some(b"Exif\0\0".as_slice()) {
+            // 읽은 범위를 넘는 세그먼트는 탐색 범위 밖으로 취급
+            return data.get(pos + 10..end).ok_or(ExifThumbnailError::NotFound);
         }
 
         // 다음 마커로 이동
-        file.seek(SeekFrom::Current(length as i64 - 2))
-            .map_err(|e| format!("Failed to seek: {}", e))?;
-    };
-
-    // EXIF 데이터 읽기
-    file.seek(SeekFrom::Start(0))
-        .map_err(|e| format!("Failed to seek to start: {}", e))?;
+        pos = end;
+    }
+}
 
-    let exif_reader = Reader::new();
-    let mut bufreader = BufReader::new(&mut file);
+/// 메모리의 JPEG 앞부분에서 EXIF 내장 썸네일 추출
+pub fn extract_exif_thumbnail_from(data: &[u8]) -> Result<Vec<u8>, ExifThumbnailError> {
+    let tiff = find_exif_segment(data)?;
 
-    let exif = exif_reader
-        .read_from_container(&mut bufreader)
-        .map_err(|e| format!("Failed to read EXIF: {}", e))?;
+    let exif = Reader::new()
+        .read_raw(tiff.to_vec())
+        .map_err(|e| ExifThumbnailError::Invalid(format!("Failed to read EXIF: {}", e)))?;
 
-    // 썸네일 오프셋과 길이 찾기
-    let tiff_offset = exif
+    // 썸네일 오프셋과 길이 찾기 (TIFF 헤더 기준), 없으면 내장 썸네일 없음
+    let offset = exif
         .get_field(Tag::JPEGInterchangeFormat, In::THUMBNAIL)
-        .and_then(|field| {
-            if let exif::Value::Long(ref longs) = field.value {
-                longs.first().copied()
-            } else {
-                None
-            }
-        })
-        .ok_or("No thumbnail offset found")?;
+        .and_then(|field| field.value.get_uint(0))
+        .ok_or(ExifThumbnailError::NotFound)? as usize;
 
     let length = exif
         .get_field(Tag::JPEGInterchangeFormatLength, In::THUMBNAIL)
-        .and_then(|field| {
-            if let exif::Value::Long(ref longs) = field.value {
-                longs.first().copied()
-            } else {
-                None
-            }
-        })
-        .ok_or("No thumbnail length found")?;
-
-    // 썸네일 데이터 읽기
-    drop(bufreader);
-
-    let tiff_header_offset = exif_offset + 6; // "Exif\0\0"
-    let absolute_offset = tiff_header_offset + tiff_offset as u64;
-
-    file.seek(SeekFrom::Start(absolute_offset))
-        .map_err(|e| format!("Failed to seek to thumbnail: {}", e))?;
+        .and_then(|field| field.value.get_uint(0))
+        .ok_or(ExifThumbnailError::NotFound)? as usize;
 
-    let mut thumbnail_data = vec![0u8; length as usize];
-    file.read_exact(&mut thumbnail_data)
-        .map_err(|e| format!("Failed to read thumbnail: {}", e))?;
+    let thumbnail_data = tiff
+        .get(offset..offset + length)
+        .ok_or_else(|| ExifThumbnailError::Invalid("EXIF thumbnail is outside of the EXIF segment".to_string()))?;
 
     // JPEG 시그니처 확인
-    if thumbnail_data.len() >= 2 && thumbnail_data[0] == 0xFF && thumbnail_data[1] == 0xD8 {
-        Ok(thumbnail_data)
+    if thumbnail_data.starts_with(&[0xFF, 0xD8]) {
+        Ok(thumbnail_data.to_vec())
     } else {
-        Err("Invalid JPEG signature in EXIF thumbnail".to_string())
+        Err(ExifThumbnailError::Invalid("Invalid JPEG signature in EXIF thumbnail".to_string()))
     }
 }
 
@@ -347,30 +434,325 @@ pub fn generate_dct_thumbnail(file_path: &str, max_size: u16) -> Result<(Vec<u8>
         .info()
         .ok_or_else(|| "Failed to get image info".to_string())?;
 
-    Ok((pixels, info.width as u32, info.height as u32))
+    // RGB로 변환 (Grayscale, CMYK)
+    let mut rgb_data = jpeg_pixels_to_rgb(pixels, info.pixel_format)?;
+
+    // 색 프로파일 → sRGB (APP2 ICC_PROFILE)
+    if info.pixel_format == jpeg_decoder::PixelFormat::RGB24 {
+        crate::color_profile::convert_to_srgb(
+            decoder.icc_profile().as_deref(),
+            &mut rgb_data,
+            crate::color_profile::PixelLayout::Rgb8,
+        );
+    }
+
+    Ok((rgb_data, info.width as u32, info.height as u32))
 }
 
-/// 범용 이미지 포맷을 위한 썸네일 생성 (JPEG DCT 제외)
-pub fn generate_generic_thumbnail(file_path: &str, max_size: u32) -> Result<(Vec<u8>, u32, u32), String> {
-    // image 크레이트로 이미지 로드
-    let img = image::open(file_path)
+/// JPEG의 원본 크기와 평균 색 (1/8 스케일 디코딩 = 블록별 DC 계수만 사용)
+pub fn jpeg_dc_color(file_path: &str) -> Result<(u32, u32, [u8; 3]), String> {
+    let file = File::open(file_path)
+        .map_err(|e| format!("Failed to open file: {}", e))?;
+
+    let mut decoder = JpegDecoder::new(BufReader::new(file));
+    decoder
+        .read_info()
+        .map_err(|e| format!("Failed to read JPEG header: {}", e))?;
+    let original = decoder
+        .info()
+        .ok_or_else(|| "Failed to get image info".to_string())?;
+
+    // 가장 작은 배율(1/8)로 디코딩하면 8x8 블록마다 DC 값 한 픽셀
+    decoder
+        .scale(1, 1)
+        .map_err(|e| format!("Failed to set scale: {}", e))?;
+    let pixels = decoder
+        .decode()
+        .map_err(|e| format!("Failed to decode JPEG: {}", e))?;
+    let format = decoder
+        .info()
+        .ok_or_else(|| "Failed to get image info".to_string())?
+        .pixel_format;
+    let rgb = jpeg_pixels_to_rgb(pixels, format)?;
+
+    Ok((original.width as u32, original.height as u32, average_rgb(&rgb)))
+}
+
+// RGB24 픽셀의 평균 색
+fn average_rgb(rgb: &[u8]) -> [u8; 3] {
+    let mut sum = [0u64; 3];
+    let mut count = 0u64;
+    for px in rgb.chunks_exact(3) {
+        for (total, &value) in sum.iter_mut().zip(px) {
+            *total += value as u64;
+        }
+        count += 1;
+    }
+    if count == 0 {
+        return [0, 0, 0];
+    }
+    sum.map(|total| (total / count) as u8)
+}
+
+/// jpeg-decoder 출력을 RGB24로 변환
+fn jpeg_pixels_to_rgb(pixels: Vec<u8>, format: jpeg_decoder::PixelFormat) -> Result<Vec<u8>, String> {
+    use jpeg_decoder::PixelFormat;
+
+    match format {
+        PixelFormat::RGB24 => Ok(pixels),
+        // Grayscale → RGB
+        PixelFormat::L8 => Ok(pixels.iter().flat_map(|&p| [p, p, p]).collect()),
+        // 16비트 Grayscale (big-endian) → 상위 바이트 사용
+        PixelFormat::L16 => Ok(pixels.chunks_exact(2).flat_map(|p| [p[0], p[0], p[0]]).collect()),
+        // CMYK → RGB (인쇄용 JPEG, Adobe 반전은 디코더가 처리)
+        PixelFormat::CMYK32 => Ok(cmyk_to_rgb(&pixels)),
+    }
+}
+
+/// 단순 CMYK → RGB 변환 (R = (1-C)(1-K))
+fn cmyk_to_rgb(cmyk: &[u8]) -> Vec<u8> {
+    cmyk.chunks_exact(4)
+        .flat_map(|px| {
+            let k = 255 - px[3] as u32;
+            let channel = |c: u8| ((255 - c as u32) * k / 255) as u8;
+            [channel(px[0]), channel(px[1]), channel(px[2])]
+        })
+        .collect()
+}
+
+/// 선형 값(0~1) → sRGB 감마 (8비트)
+pub fn linear_to_srgb_u8(value: f32) -> u8 {
+    let v = value.clamp(0.0, 1.0);
+    let encoded = if v <= 0.003_130_8 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    };
+    (encoded * 255.0).round() as u8
+}
+
+/// 고비트/부동소수점 이미지를 8비트 표시용으로 변환
+/// - 32비트 float (EXR, float TIFF): 선형 값 → sRGB 감마, 1.0을 넘는 HDR이면 Reinhard 톤매핑
+/// - 16비트: 값 범위가 좁으면(과학/측정 장비의 10~14비트 데이터) 최대값 기준으로 늘림
+pub fn tonemap_to_8bit(img: image::DynamicImage) -> image::DynamicImage {
+    use image::DynamicImage;
+
+    match img {
+        DynamicImage::ImageRgb32F(_) | DynamicImage::ImageRgba32F(_) => {
+            let has_alpha = img.color().has_alpha();
+            let rgba = img.into_rgba32f();
+
+            let peak = rgba
+                .pixels()
+                .flat_map(|p| [p[0], p[1], p[2]])
+                .filter(|v| v.is_finite())
+                .fold(0.0f32, f32::max);
+            let is_hdr = peak > 1.0;
+
+            let (width, height) = rgba.dimensions();
+            let mut out = image::RgbaImage::new(width, height);
+            for (src, dst) in rgba.pixels().zip(out.pixels_mut()) {
+                let map = |v: f32| {
+                    let v = if v.is_finite() { v.max(0.0) } else { 0.0 };
+                    linear_to_srgb_u8(if is_hdr { v / (1.0 + v) } else { v })
+                };
+                let alpha = (src[3].clamp(0.0, 1.0) * 255.0).round() as u8;
+                *dst = image::Rgba([map(src[0]), map(src[1]), map(src[2]), alpha]);
+            }
+
+            if has_alpha {
+                DynamicImage::ImageRgba8(out)
+            } else {
+                DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(out).to_rgb8())
+            }
+        }
+        DynamicImage::ImageLuma16(_)
+        | DynamicImage::ImageLumaA16(_)
+        | DynamicImage::ImageRgb16(_)
+        | DynamicImage::ImageRgba16(_) => {
+            let has_alpha = img.color().has_alpha();
+            let mut rgba = img.into_rgba16();
+
+            let peak = rgba.pixels().flat_map(|p| [p[0], p[1], p[2]]).max().unwrap_or(0);
+            // 상위 2비트를 쓰지 않으면 좁은 범위 데이터로 간주
+            if peak > 0 && peak < u16::MAX / 4 {
+                let scale = u16::MAX as f32 / peak as f32;
+                for pixel in rgba.pixels_mut() {
+                    for c in 0..3 {
+                        pixel[c] = (pixel[c] as f32 * scale).min(u16::MAX as f32) as u16;
+                    }
+                }
+            }
+
+            if has_alpha {
+                DynamicImage::ImageRgba16(rgba)
+            } else {
+                DynamicImage::ImageRgb16(DynamicImage::ImageRgba16(rgba).to_rgb16())
+            }
+        }
+        other => other,
+    }
+}
+
+/// 비율을 유지하며 max_size 이내에 들어가는 크기 (최소 1px)
+pub fn fit_within(width: u32, height: u32, max_size: u32) -> (u32, u32) {
+    let scale = (max_size as f64 / width as f64).min(max_size as f64 / height as f64);
+    (
+        ((width as f64 * scale).round() as u32).max(1),
+        ((height as f64 * scale).round() as u32).max(1),
+    )
+}
+
+/// 직선(straight) 알파 RGBA를 배경색 위에 합성해 RGB로 변환
+pub fn composite_over_background(rgba: &[u8], background: [u8; 3]) -> Vec<u8> {
+    rgba.chunks_exact(4)
+        .flat_map(|px| {
+            let alpha = px[3] as u32;
+            let blend = |c: u8, bg: u8| ((c as u32 * alpha + bg as u32 * (255 - alpha) + 127) / 255) as u8;
+            [
+                blend(px[0], background[0]),
+                blend(px[1], background[1]),
+                blend(px[2], background[2]),
+            ]
+        })
+        .collect()
+}
+
+/// SIMD Lanczos3 리사이징 (RGBA는 premultiplied alpha로 처리해 투명 영역 색 번짐 방지)
+pub fn resize_fast(
+    pixels: Vec<u8>,
+    width: u32,
+    height: u32,
+    dst_width: u32,
+    dst_height: u32,
+    pixel_type: fast_image_resize::PixelType,
+) -> Result<Vec<u8>, String> {
+    use fast_image_resize::images::Image;
+    use fast_image_resize::{FilterType, ResizeAlg, ResizeOptions, Resizer};
+
+    let src = Image::from_vec_u8(width, height, pixels, pixel_type)
+        .map_err(|e| format!("Failed to prepare image for resize: {}", e))?;
+    let mut dst = Image::new(dst_width, dst_height, pixel_type);
+
+    let options = ResizeOptions::new()
+        .resize_alg(ResizeAlg::Convolution(FilterType::Lanczos3))
+        .use_alpha(true);
+    Resizer::new()
+        .resize(&src, &mut dst, &options)
+        .map_err(|e| format!("Failed to resize image: {}", e))?;
+
+    Ok(dst.into_vec())
+}
+
+/// 이미지와 내장 ICC 프로파일 로드 (JPEG APP2, PNG iCCP, TIFF, WebP 등 디코더가 지원하는 포맷)
+pub fn open_image_with_icc(file_path: &str) -> Result<(image::DynamicImage, Option<Vec<u8>>), String> {
+    use image::ImageDecoder;
+
+    let mut decoder = image::ImageReader::open(file_path)
+        .and_then(|reader| reader.with_guessed_format())
+        .map_err(|e| format!("Failed to open image: {}", e))?
+        .into_decoder()
         .map_err(|e| format!("Failed to open image: {}", e))?;
 
-    // 썸네일 생성 (비율 유지하며 max_size 이내로 축소)
-    let thumbnail = img.thumbnail(max_size, max_size);
+    let icc_profile = decoder.icc_profile().ok().flatten();
+    let img = image::DynamicImage::from_decoder(decoder)
+        .map_err(|e| format!("Failed to open image: {}", e))?;
 
-    // RGB8로 변환
-    let rgb_img = thumbnail.to_rgb8();
+    Ok((img, icc_profile))
+}
 
-    Ok((
-        rgb_img.into_raw(),
-        thumbnail.width(),
-        thumbnail.height(),
-    ))
+/// 이미지 전체 디코딩으로 손상 여부 확인 (잘린 파일, 깨진 압축 데이터)
+/// RAW는 내장 미리보기와 EXIF 구조를 확인
+pub fn validate_image(file_path: &str) -> Result<(), String> {
+    let category = formats::detect(Path::new(file_path), file_path).map(|format| format.category);
+    if category == Some(FormatCategory::Jpeg) {
+        let file = File::open(file_path).map_err(|e| format!("Failed to open file: {}", e))?;
+        JpegDecoder::new(BufReader::new(file))
+            .decode()
+            .map_err(|e| format!("Failed to decode JPEG: {}", e))?;
+    } else if category == Some(FormatCategory::Vector) {
+        generate_svg_thumbnail(file_path, 64)?;
+    } else if is_psd_file(file_path) {
+        crate::psd::decode(file_path)?;
+    } else if category == Some(FormatCategory::Raw) {
+        let preview = extract_jpeg_from_raw(file_path, In::PRIMARY)
+            .or_else(|_| extract_jpeg_from_raw(file_path, In::THUMBNAIL))?;
+        JpegDecoder::new(preview.as_slice())
+            .decode()
+            .map_err(|e| format!("Failed to decode RAW preview: {}", e))?;
+    } else {
+        open_image_with_icc(file_path)?;
+    }
+    Ok(())
+}
+
+/// 범용 이미지 포맷을 위한 썸네일 생성 (JPEG DCT 제외)
+/// 투명 이미지는 RGBA 그대로 반환 (thumbnail_keep_alpha가 꺼져 있으면 배경색 위에 합성)
+/// 반환: (픽셀, 너비, 높이, RGBA 여부)
+pub fn generate_generic_thumbnail(file_path: &str, max_size: u32) -> Result<(Vec<u8>, u32, u32, bool), String> {
+    use crate::color_profile::{self, PixelLayout};
+    use fast_image_resize::PixelType;
+
+    // image 크레이트로 이미지 로드 (ICC 프로파일 포함)
+    let (img, icc_profile) = open_image_with_icc(file_path)?;
+    let img = tonemap_to_8bit(img);
+
+    let settings = crate::settings::current();
+    let background = settings.thumbnail_background_rgb();
+    let (src_width, src_height) = (img.width(), img.height());
+    let has_alpha = img.color().has_alpha();
+    let keep_alpha = has_alpha && settings.thumbnail_keep_alpha;
+
+    // 비율 유지하며 max_size 이내로 (이미 작으면 원본 크기)
+    let (width, height) = if src_width > max_size || src_height > max_size {
+        fit_within(src_width, src_height, max_size)
+    } else {
+        (src_width, src_height)
+    };
+
+    // GPU 리사이징 (설정된 경우, 영역 평균 필터, 장치 없음/GPU 오류면 CPU Lanczos3로 진행)
+    let mut gpu_rgba = None;
+    if settings.gpu_resize && (width, height) != (src_width, src_height) {
+        match crate::gpu_resize::resize_rgba(&img.to_rgba8(), src_width, src_height, width, height) {
+            Ok(rgba) => gpu_rgba = Some(rgba),
+            Err(e) => tracing::debug!("GPU resize failed for {}: {}", file_path, e),
+        }
+    }
+
+    // 축소된 버퍼 (알파 또는 GPU 결과면 RGBA, 아니면 RGB)
+    let (mut resized, layout) = if let Some(rgba) = gpu_rgba {
+        (rgba, PixelLayout::Rgba8)
+    } else if has_alpha {
+        let rgba = img.into_rgba8().into_raw();
+        if (width, height) == (src_width, src_height) {
+            (rgba, PixelLayout::Rgba8)
+        } else {
+            (resize_fast(rgba, src_width, src_height, width, height, PixelType::U8x4)?, PixelLayout::Rgba8)
+        }
+    } else {
+        let rgb = img.into_rgb8().into_raw();
+        if (width, height) == (src_width, src_height) {
+            (rgb, PixelLayout::Rgb8)
+        } else {
+            (resize_fast(rgb, src_width, src_height, width, height, PixelType::U8x3)?, PixelLayout::Rgb8)
+        }
+    };
+
+    // 색 프로파일 → sRGB (축소 후 적용해 변환 비용 절감)
+    color_profile::convert_to_srgb(icc_profile.as_deref(), &mut resized, layout);
+
+    let pixels = match layout {
+        PixelLayout::Rgba8 if keep_alpha => resized,
+        PixelLayout::Rgba8 => composite_over_background(&resized, background),
+        PixelLayout::Rgb8 => resized,
+    };
+
+    Ok((pixels, width, height, keep_alpha))
 }
 
 /// SVG 파일을 위한 썸네일 생성
-pub fn generate_svg_thumbnail(file_path: &str, max_size: u32) -> Result<(Vec<u8>, u32, u32), String> {
+/// 반환: (픽셀, 너비, 높이, RGBA 여부) - 투명 영역이 있으면 RGBA
+pub fn generate_svg_thumbnail(file_path: &str, max_size: u32) -> Result<(Vec<u8>, u32, u32, bool), String> {
     use resvg::usvg::Tree;
 
     // SVG 파싱 (v0.45 API: Options 불필요, postprocess 자동 처리)
@@ -402,27 +784,26 @@ pub fn generate_svg_thumbnail(file_path: &str, max_size: u32) -> Result<(Vec<u8>
     let transform = resvg::tiny_skia::Transform::from_scale(scale, scale);
     resvg::render(&tree, transform, &mut pixmap.as_mut());
 
-    // RGBA → RGB 변환
-    let rgba_data = pixmap.data();
-    let rgb_data: Vec<u8> = rgba_data
-        .chunks_exact(4)
-        .flat_map(|rgba| [rgba[0], rgba[1], rgba[2]])
+    // tiny_skia는 premultiplied RGBA → 직선 알파로 변환
+    let rgba: Vec<u8> = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|p| {
+            let c = p.demultiply();
+            [c.red(), c.green(), c.blue(), c.alpha()]
+        })
         .collect();
 
-    Ok((rgb_data, width, height))
-}
+    let settings = crate::settings::current();
+    let keep_alpha = settings.thumbnail_keep_alpha && rgba.chunks_exact(4).any(|px| px[3] < 255);
+    let data = if keep_alpha {
+        rgba
+    } else {
+        composite_over_background(&rgba, settings.thumbnail_background_rgb())
+    };
 
-/// RAW 파일 확장자 목록 (EXIF 썸네일 추출 가능)
-const RAW_EXTENSIONS: &[&str] = &[
-    "nef", "nrw",           // Nikon
-    "cr2", "crw",           // Canon (CR3는 EXIF 구조 다름)
-    "arw", "srf", "sr2",    // Sony
-    "dng",                  // Adobe
-    "raf",                  // Fuji
-    "orf",                  // Olympus
-    "rw2",                  // Panasonic
-    "pef",                  // Pentax
-];
+    Ok((data, width, height, keep_alpha))
+}
 
 /// RAW 파일에서 JPEG 이미지 추출 (썸네일 또는 미리보기)
 /// ifd_index: In::PRIMARY (0번 IFD, 보통 작은 썸네일), In::THUMBNAIL (1번 IFD)
@@ -483,8 +864,13 @@ fn extract_jpeg_from_raw(file_path: &str, ifd: In) -> Result<Vec<u8>, String> {
 pub fn generate_raw_thumbnail(file_path: &str, max_size: u32) -> Result<(Vec<u8>, u32, u32), String> {
     use exif::In;
 
-    // 썸네일 IFD에서 JPEG 추출 시도
-    let thumbnail_jpeg = extract_jpeg_from_raw(file_path, In::THUMBNAIL)?;
+    // 썸네일 IFD에서 JPEG 추출 시도 (설정에 따라 PRIMARY IFD의 큰 미리보기 우선)
+    let thumbnail_jpeg = if crate::settings::current().raw_prefer_full_preview {
+        extract_jpeg_from_raw(file_path, In::PRIMARY)
+            .or_else(|_| extract_jpeg_from_raw(file_path, In::THUMBNAIL))?
+    } else {
+        extract_jpeg_from_raw(file_path, In::THUMBNAIL)?
+    };
 
     // JPEG 디코딩하여 크기 확인
     let img = image::load_from_memory(&thumbnail_jpeg)
@@ -513,9 +899,15 @@ pub fn generate_raw_thumbnail(file_path: &str, max_size: u32) -> Result<(Vec<u8>
 /// 이미지 파일에서 고해상도 JPEG 미리보기 추출 (캔버스 출력용)
 /// JPG: EXIF 썸네일 → DCT 리사이징 (2400px 이내)
 /// RAW: EXIF 내장 JPEG 미리보기 (PRIMARY → THUMBNAIL IFD)
+/// PSD/PSB: 합성 이미지 (2400px 이내)
 pub fn extract_raw_preview(file_path: &str) -> Result<Vec<u8>, String> {
     use exif::In;
 
+    if is_psd_file(file_path) {
+        let (rgb, width, height) = crate::psd::generate_thumbnail(file_path, 2400)?;
+        return encode_thumbnail_to_jpeg_with_quality(&rgb, width, height, 90);
+    }
+
     // JPG 파일인 경우: EXIF 썸네일 시도 → DCT 리사이징
     if is_jpeg_file(file_path) {
         // 1. EXIF 내장 썸네일 시도 (빠름, 고품질)
@@ -598,15 +990,17 @@ fn generate_jpeg_preview_scaled(file_path: &str, max_size: u32) -> Result<Vec<u8
     let scaled_info = decoder.info()
         .ok_or_else(|| "Failed to get scaled JPEG info".to_string())?;
 
-    // RGB로 변환 (필요 시)
-    let rgb_data = match scaled_info.pixel_format {
-        PixelFormat::RGB24 => pixels,
-        PixelFormat::L8 => {
-            // Grayscale → RGB 변환
-            pixels.iter().flat_map(|&p| [p, p, p]).collect()
-        },
-        _ => return Err("Unsupported JPEG pixel format".to_string()),
-    };
+    // RGB로 변환 (Grayscale, CMYK)
+    let mut rgb_data = jpeg_pixels_to_rgb(pixels, scaled_info.pixel_format)?;
+
+    // 색 프로파일 → sRGB (재인코딩 시 ICC가 빠지므로 미리 변환)
+    if scaled_info.pixel_format == PixelFormat::RGB24 {
+        crate::color_profile::convert_to_srgb(
+            decoder.icc_profile().as_deref(),
+            &mut rgb_data,
+            crate::color_profile::PixelLayout::Rgb8,
+        );
+    }
 
     // JPEG로 재인코딩 (품질 90)
     encode_thumbnail_to_jpeg_with_quality(
@@ -649,8 +1043,13 @@ pub fn encode_to_base64(data: &[u8]) -> String {
 }
 
 /// RGB 데이터를 WebP로 인코딩 (HQ 썸네일용, 고속 인코딩)
-pub fn encode_thumbnail_to_webp(rgb_data: &[u8], width: u32, height: u32, quality: f32) -> Result<Vec<u8>, String> {
-    let encoder = WebPEncoder::from_rgb(rgb_data, width, height);
+/// has_alpha면 RGBA, 아니면 RGB 픽셀 (RGBA는 VP8X 알파 플래그가 있는 WebP로 저장됨)
+pub fn encode_thumbnail_to_webp(pixels: &[u8], width: u32, height: u32, has_alpha: bool, quality: f32) -> Result<Vec<u8>, String> {
+    let encoder = if has_alpha {
+        WebPEncoder::from_rgba(pixels, width, height)
+    } else {
+        WebPEncoder::from_rgb(pixels, width, height)
+    };
 
     // 고속 인코딩 모드 (quality: 60 = 빠른 인코딩 + 충분한 품질)
     let webp_data = encoder.encode(quality);
@@ -658,105 +1057,167 @@ pub fn encode_thumbnail_to_webp(rgb_data: &[u8], width: u32, height: u32, qualit
     Ok(webp_data.to_vec())
 }
 
-/// 파일 확장자로 JPEG 여부 확인
-fn is_jpeg_file(file_path: &str) -> bool {
-    if let Some(ext) = Path::new(file_path).extension() {
-        let ext_str = ext.to_string_lossy().to_lowercase();
-        matches!(ext_str.as_str(), "jpg" | "jpeg")
-    } else {
-        false
+/// 파일 시스템 접근용 경로 (긴 경로, NFC/NFD 차이 처리)
+/// 원격 파일(remote://)은 로컬 스풀로 받은 경로, 압축 파일 항목(archive://)은 꺼낸 경로
+pub async fn resolve_source(app_handle: &tauri::AppHandle, file_path: &str) -> Result<String, String> {
+    if crate::archive_source::is_archive_path(file_path) {
+        let app_handle = app_handle.clone();
+        let url = file_path.to_string();
+        let spooled = tokio::task::spawn_blocking(move || crate::archive_source::spool(&app_handle, &url))
+            .await
+            .map_err(|e| format!("Task failed: {}", e))??;
+        return Ok(spooled.to_string_lossy().to_string());
+    }
+    if !crate::remote_source::is_remote_path(file_path) {
+        return Ok(crate::fs_path::to_fs_string(file_path));
     }
+
+    let app_handle = app_handle.clone();
+    let url = file_path.to_string();
+    let spooled = tokio::task::spawn_blocking(move || crate::remote_source::spool(&app_handle, &url))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))??;
+    Ok(spooled.to_string_lossy().to_string())
 }
 
-/// 파일 확장자로 SVG 여부 확인
-fn is_svg_file(file_path: &str) -> bool {
-    if let Some(ext) = Path::new(file_path).extension() {
-        let ext_str = ext.to_string_lossy().to_lowercase();
-        ext_str == "svg"
-    } else {
-        false
+/// 원격 JPEG은 앞부분만 받아 EXIF 썸네일 시도 (범위 요청 미지원 서버는 전체 다운로드)
+/// 반환: (접근 경로, 파일 앞부분만인지 여부)
+async fn resolve_head_source(app_handle: &tauri::AppHandle, file_path: &str) -> Result<(String, bool), String> {
+    if !crate::remote_source::is_remote_path(file_path) || !is_jpeg_file(file_path) {
+        return Ok((resolve_source(app_handle, file_path).await?, false));
+    }
+
+    let handle = app_handle.clone();
+    let url = file_path.to_string();
+    let head = tokio::task::spawn_blocking(move || crate::remote_source::spool_head(&handle, &url))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))??;
+    match head {
+        Some((path, complete)) => Ok((path.to_string_lossy().to_string(), !complete)),
+        None => Ok((resolve_source(app_handle, file_path).await?, false)),
     }
 }
 
-/// 파일 확장자로 RAW 여부 확인
-fn is_raw_file(file_path: &str) -> bool {
-    if let Some(ext) = Path::new(file_path).extension() {
-        let ext_str = ext.to_string_lossy().to_lowercase();
-        RAW_EXTENSIONS.contains(&ext_str.as_str())
-    } else {
-        false
+// 같은 파일의 요청이 진행 중이면 그 결과를 기다리고, 아니면 generate를 실행해 결과를 나눔
+// 먼저 실행한 요청이 취소되면 기다리던 요청이 대신 실행
+async fn share_in_flight<F, Fut>(kind: InFlightKind, file_path: &str, generate: F) -> Result<ThumbnailResult, String>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<ThumbnailResult, String>>,
+{
+    let key = (kind, file_path.to_string());
+    let in_flight = Arc::clone(IN_FLIGHT.lock().unwrap().entry(key.clone()).or_default());
+
+    let result = in_flight.get_or_init(generate).await.clone();
+
+    // 끝난 요청은 맵에서 제거 (그 뒤의 요청은 캐시에서 읽음)
+    let mut requests = IN_FLIGHT.lock().unwrap();
+    if requests.get(&key).is_some_and(|current| Arc::ptr_eq(current, &in_flight)) {
+        requests.remove(&key);
     }
+    result
 }
 
 /// 썸네일 생성 (캐시 우선, EXIF → DCT/Generic fallback)
+/// 같은 파일의 동시 요청(뷰어, 큐 워커 등)은 한 번만 생성
 pub async fn generate_thumbnail(app_handle: &tauri::AppHandle, file_path: &str) -> Result<ThumbnailResult, String> {
-    // 항상 원본 이미지에서 EXIF 메타데이터 추출 (orientation 정보 필수)
-    let exif_metadata = extract_exif_metadata(file_path).ok();
+    share_in_flight(InFlightKind::Thumbnail, file_path, || create_thumbnail(app_handle, file_path)).await
+}
+
+async fn create_thumbnail(app_handle: &tauri::AppHandle, file_path: &str) -> Result<ThumbnailResult, String> {
+    let started = Instant::now();
+
+    // 파일 시스템 접근용 경로 (원격 파일은 스풀 경로), 결과와 캐시 키는 요청 경로 기준
+    let (mut source, head_only) = resolve_head_source(app_handle, file_path).await?;
+
+    // 다운로드되지 않은 클라우드 파일은 원본을 읽지 않음 (읽으면 전체 다운로드됨)
+    let cloud_only = crate::cloud_file::skip_fast_thumbnail(&source);
+
+    // 원본 이미지에서 EXIF 메타데이터 추출 (orientation 정보 필수, 클라우드 파일은 캐시에서)
+    let exif_metadata = if cloud_only {
+        load_cached_exif_metadata(app_handle, file_path).ok()
+    } else {
+        extract_exif_metadata(&source).ok()
+    };
 
     // 1. EXIF 썸네일 추출 시도 (JPEG만 해당, 캐시 없이 항상 추출 - 매우 빠름)
-    if is_jpeg_file(file_path) {
-        if let Ok(exif_thumb) = extract_exif_thumbnail(file_path) {
-            let thumbnail_base64 = encode_to_base64(&exif_thumb);
-
-            let img = image::load_from_memory(&exif_thumb)
-                .map_err(|e| format!("Failed to decode EXIF thumbnail: {}", e))?;
-
-            return Ok(ThumbnailResult {
-                path: file_path.to_string(),
-                thumbnail_base64,
-                width: img.width(),
-                height: img.height(),
-                source: ThumbnailSource::ExifEmbedded,
-                exif_metadata,
-            });
+    if is_jpeg_file(file_path) && !cloud_only {
+        // 없거나 깨진 내장 썸네일은 DCT 생성으로 넘어감
+        let settings = crate::settings::current();
+        match extract_exif_thumbnail(&source) {
+            Ok(exif_thumb) => match image::load_from_memory(&exif_thumb) {
+                // 작은 내장 썸네일(160x120 등)은 설정에 따라 건너뛰거나 저화질로 표시
+                Ok(img) if settings.skip_low_quality_exif && is_low_quality_exif(&img, settings.exif_thumbnail_min_size) => {
+                    tracing::debug!("Skipping low quality EXIF thumbnail in {}", file_path);
+                }
+                Ok(img) => {
+                    let rgb = img.to_rgb8();
+                    crate::placeholder::record_color_grid(app_handle, file_path, rgb.as_raw(), rgb.width(), rgb.height(), false);
+                    thumbnail_perf::record(file_path, ThumbnailPath::Exif, started.elapsed(), 0);
+                    return Ok(ThumbnailResult {
+                        path: file_path.to_string(),
+                        thumbnail_base64: encode_to_base64(&exif_thumb),
+                        width: img.width(),
+                        height: img.height(),
+                        source: ThumbnailSource::ExifEmbedded,
+                        exif_metadata,
+                        has_alpha: false,
+                        is_low_quality: is_low_quality_exif(&img, settings.exif_thumbnail_min_size),
+                    });
+                }
+                Err(e) => tracing::debug!("Invalid EXIF thumbnail in {}: {}", file_path, e),
+            },
+            Err(ExifThumbnailError::NotFound) => {}
+            Err(e) => tracing::debug!("Failed to extract EXIF thumbnail from {}: {}", file_path, e),
         }
     }
 
+    // 앞부분만 받은 원격 파일은 EXIF 썸네일이 없으면 전체를 받아 계속
+    if head_only {
+        source = resolve_source(app_handle, file_path).await?;
+    }
+
     // 2. HQ 캐시 확인 (EXIF 썸네일이 없는 경우)
-    let mtime = get_file_mtime(file_path)?;
+    let mtime = get_file_mtime(&source)?;
     let cache_key = generate_cache_key(file_path, mtime);
-    let cache_path = get_cache_path(app_handle, &cache_key)?;
+    let cache_path = get_cache_path_for(app_handle, file_path, mtime)?;
 
-    if cache_path.exists() {
-        let webp_data = fs::read(&cache_path)
-            .map_err(|e| format!("Failed to read cache: {}", e))?;
-
-        let thumbnail_base64 = encode_to_base64(&webp_data);
-
-        // WebP 이미지 크기 추출
-        let (width, height) = extract_webp_dimensions(&webp_data).unwrap_or((320, 320));
+    // 같은 캐시 키를 만드는 중이면 끝날 때까지 기다렸다가 캐시에서 읽음
+    let _cache_lock = lock_cache_key(&cache_key).await;
 
+    if let Some((webp_data, width, height)) = read_cached_webp(&cache_path, file_path)? {
+        thumbnail_perf::record(file_path, ThumbnailPath::Cache, started.elapsed(), 0);
         return Ok(ThumbnailResult {
             path: file_path.to_string(),
-            thumbnail_base64,
+            thumbnail_base64: encode_to_base64(&webp_data),
             width,
             height,
             source: ThumbnailSource::Cache,
             exif_metadata,
+            has_alpha: webp_has_alpha(&webp_data),
+            is_low_quality: false,
         });
     }
 
+    // 클라우드 파일은 다운로드 후 (또는 HQ 생성 시) 처리
+    if cloud_only {
+        return Err(format!("Cloud file not downloaded: {}", file_path));
+    }
+
     // 3. 썸네일 생성 (포맷별 최적화)
-    let (rgb_data, width, height) = if is_jpeg_file(file_path) {
-        // JPEG: DCT 스케일링 (고속)
-        generate_dct_thumbnail(file_path, 320)?
-    } else if is_svg_file(file_path) {
-        // SVG: 벡터 렌더링
-        generate_svg_thumbnail(file_path, 320)?
-    } else if is_raw_file(file_path) {
-        // RAW: 내장 JPEG 미리보기 추출
-        generate_raw_thumbnail(file_path, 320)?
-    } else {
-        // 기타 포맷: 범용 이미지 디코딩 (PNG, WebP, GIF, TIFF, BMP, EXR, AVIF, ICO 등)
-        generate_generic_thumbnail(file_path, 320)?
-    };
+    let (pixels, width, height, has_alpha) = decode_thumbnail(&source, file_path, BASE_THUMBNAIL_SIZE)?;
+    crate::placeholder::record_color_grid(app_handle, file_path, &pixels, width, height, has_alpha);
 
     // WebP 인코딩 (품질 60 = 빠른 인코딩 + 충분한 품질, JPEG 70보다 2배 빠름)
-    let webp_data = encode_thumbnail_to_webp(&rgb_data, width, height, 60.0)?;
+    let webp_data = encode_thumbnail_to_webp(&pixels, width, height, has_alpha, 60.0)?;
 
     // HQ 캐시에 저장
-    fs::write(&cache_path, &webp_data)
+    crate::shutdown::write_cache_atomic(&cache_path, &webp_data)
         .map_err(|e| format!("Failed to write cache: {}", e))?;
+    crate::thumbnail_cache::record_entry(app_handle, &cache_key, file_path, mtime);
+
+    let decode_path = if is_jpeg_file(file_path) { ThumbnailPath::Dct } else { ThumbnailPath::Generic };
+    thumbnail_perf::record(file_path, decode_path, started.elapsed(), webp_data.len());
 
     let thumbnail_base64 = encode_to_base64(&webp_data);
 
@@ -767,9 +1228,41 @@ pub async fn generate_thumbnail(app_handle: &tauri::AppHandle, file_path: &str)
         height,
         source: ThumbnailSource::DctScaling,
         exif_metadata,
+        has_alpha,
+        is_low_quality: false,
     })
 }
 
+// 형식별 디코딩 후 max_size 안으로 축소 (픽셀, 너비, 높이, 알파 여부)
+// 형식은 파일 내용으로 판별 (확장자가 잘못된 파일도 맞는 디코더로)
+fn decode_thumbnail(source: &str, file_path: &str, max_size: u32) -> Result<(Vec<u8>, u32, u32, bool), String> {
+    let format = formats::detect(Path::new(source), file_path);
+    let category = format.map(|format| format.category);
+
+    let decoded = match category {
+        Some(FormatCategory::Jpeg) => {
+            // JPEG: DCT 스케일링 (고속)
+            let (rgb_data, width, height) = generate_dct_thumbnail(source, max_size.min(u16::MAX as u32) as u16)?;
+            (rgb_data, width, height, false)
+        }
+        // SVG: 벡터 렌더링
+        Some(FormatCategory::Vector) => generate_svg_thumbnail(source, max_size)?,
+        Some(FormatCategory::Raw) => {
+            // RAW: 내장 JPEG 미리보기 추출
+            let (rgb_data, width, height) = generate_raw_thumbnail(source, max_size)?;
+            (rgb_data, width, height, false)
+        }
+        Some(FormatCategory::Document) if format.is_some_and(|format| matches!(format.extension, "psd" | "psb")) => {
+            // PSD/PSB: 합성 이미지 (없으면 내장 썸네일)
+            let (rgb_data, width, height) = crate::psd::generate_thumbnail(source, max_size)?;
+            (rgb_data, width, height, false)
+        }
+        // 기타 포맷: 범용 이미지 디코딩 (PNG, WebP, GIF, TIFF, BMP, EXR, AVIF, ICO 등)
+        _ => generate_generic_thumbnail(source, max_size)?,
+    };
+    Ok(decoded)
+}
+
 /// 폴더별 EXIF 메타데이터 저장
 #[allow(dead_code)]
 pub fn save_folder_metadata(
@@ -807,7 +1300,6 @@ pub fn load_folder_metadata(app_handle: &tauri::AppHandle, folder_path: &str) ->
 }
 
 /// 개별 파일 EXIF 메타데이터 캐시에서 로드
-#[allow(dead_code)]
 fn load_cached_exif_metadata(app_handle: &tauri::AppHandle, file_path: &str) -> Result<ExifMetadata, String> {
     let parent_dir = Path::new(file_path)
         .parent()
@@ -823,44 +1315,66 @@ fn load_cached_exif_metadata(app_handle: &tauri::AppHandle, file_path: &str) ->
 }
 
 /// 고화질 DCT 썸네일 생성 (320px, WebP 포맷으로 고속 인코딩)
+/// 같은 파일의 동시 요청은 한 번만 생성
 pub async fn generate_hq_thumbnail(app_handle: &tauri::AppHandle, file_path: &str) -> Result<ThumbnailResult, String> {
-    let mtime = get_file_mtime(file_path)?;
-    let cache_key = generate_cache_key(file_path, mtime);
-    let cache_path = get_cache_path(app_handle, &cache_key)?;
+    share_in_flight(InFlightKind::Hq, file_path, || create_hq_thumbnail(app_handle, file_path)).await
+}
 
-    // 캐시 파일이 이미 존재하면 기존 HQ 썸네일 로드
-    if cache_path.exists() {
-        let webp_data = fs::read(&cache_path)
-            .map_err(|e| format!("Failed to read cached HQ thumbnail: {}", e))?;
+async fn create_hq_thumbnail(app_handle: &tauri::AppHandle, file_path: &str) -> Result<ThumbnailResult, String> {
+    let started = Instant::now();
+
+    // 파일 시스템 접근용 경로 (원격 파일은 스풀 경로), 결과와 캐시 키는 요청 경로 기준
+    let source = resolve_source(app_handle, file_path).await?;
+
+    let mtime = get_file_mtime(&source)?;
+    let cache_key = generate_cache_key(file_path, mtime);
+    let cache_path = get_cache_path_for(app_handle, file_path, mtime)?;
 
-        let thumbnail_base64 = encode_to_base64(&webp_data);
-        let exif_metadata = extract_exif_metadata(file_path).ok();
+    // 같은 캐시 키를 만드는 중이면 끝날 때까지 기다렸다가 캐시에서 읽음
+    let _cache_lock = lock_cache_key(&cache_key).await;
 
-        // WebP 이미지 크기 추출
-        let (width, height) = extract_webp_dimensions(&webp_data).unwrap_or((320, 320));
+    // 캐시 파일이 이미 존재하면 기존 HQ 썸네일 로드
+    if let Some((webp_data, width, height)) = read_cached_webp(&cache_path, file_path)? {
+        // 클라우드 파일은 EXIF를 위해 다운로드하지 않음 (캐시된 메타데이터 사용)
+        let exif_metadata = if crate::cloud_file::is_placeholder(&source) {
+            load_cached_exif_metadata(app_handle, file_path).ok()
+        } else {
+            extract_exif_metadata(&source).ok()
+        };
 
+        thumbnail_perf::record(file_path, ThumbnailPath::Cache, started.elapsed(), 0);
         return Ok(ThumbnailResult {
             path: file_path.to_string(),
-            thumbnail_base64,
+            thumbnail_base64: encode_to_base64(&webp_data),
             width,
             height,
             source: ThumbnailSource::Cache,
             exif_metadata,
+            has_alpha: webp_has_alpha(&webp_data),
+            is_low_quality: false,
         });
     }
 
+    // 클라우드 파일 다운로드 안 함 설정이면 건너뜀 (hydrate_files 후 생성)
+    if crate::cloud_file::skip_hq_thumbnail(&source) {
+        return Err(format!("Cloud file not downloaded: {}", file_path));
+    }
+
     // EXIF 메타데이터 추출
-    let exif_metadata = extract_exif_metadata(file_path).ok();
+    let exif_metadata = extract_exif_metadata(&source).ok();
 
-    // DCT 스케일링으로 320px 고화질 썸네일 생성
-    let (rgb_data, width, height) = generate_dct_thumbnail(file_path, 320)?;
+    // 320px 고화질 썸네일 생성 (JPEG은 DCT 스케일링, 확장자가 잘못된 파일은 내용 형식으로)
+    let (pixels, width, height, has_alpha) = decode_thumbnail(&source, file_path, BASE_THUMBNAIL_SIZE)?;
+    crate::placeholder::record_color_grid(app_handle, file_path, &pixels, width, height, has_alpha);
 
     // WebP 인코딩 (품질 60 = 빠른 인코딩 + 충분한 품질, JPEG 70보다 2배 빠름)
-    let webp_data = encode_thumbnail_to_webp(&rgb_data, width, height, 60.0)?;
+    let webp_data = encode_thumbnail_to_webp(&pixels, width, height, has_alpha, 60.0)?;
 
     // 캐시 저장
-    fs::write(&cache_path, &webp_data)
+    crate::shutdown::write_cache_atomic(&cache_path, &webp_data)
         .map_err(|e| format!("Failed to write HQ thumbnail cache: {}", e))?;
+    crate::thumbnail_cache::record_entry(app_handle, &cache_key, file_path, mtime);
+    thumbnail_perf::record(file_path, ThumbnailPath::Dct, started.elapsed(), webp_data.len());
 
     let thumbnail_base64 = encode_to_base64(&webp_data);
 
@@ -871,67 +1385,169 @@ pub async fn generate_hq_thumbnail(app_handle: &tauri::AppHandle, file_path: &st
         height,
         source: ThumbnailSource::DctScaling,
         exif_metadata,
+        has_alpha,
+        is_low_quality: false,
     })
 }
 
-/// WebP 파일의 이미지 크기 추출
-fn extract_webp_dimensions(webp_data: &[u8]) -> Option<(u32, u32)> {
-    // WebP 시그니처 확인: RIFF....WEBP
-    if webp_data.len() < 30 {
-        return None;
+/// 그리드 셀의 실제 픽셀 크기(셀 크기 × devicePixelRatio)에 맞는 썸네일 크기
+/// 기본 크기로 충분하면 기본 크기, 아니면 설정의 HiDPI 크기
+pub fn select_tier(cell_size: f64, device_pixel_ratio: f64, hidpi_size: u32) -> u32 {
+    let needed = cell_size * device_pixel_ratio.max(1.0);
+    if hidpi_size > BASE_THUMBNAIL_SIZE && needed > BASE_THUMBNAIL_SIZE as f64 {
+        hidpi_size
+    } else {
+        BASE_THUMBNAIL_SIZE
+    }
+}
+
+/// 크기 단계별 썸네일 (기본 크기는 HQ 썸네일, 큰 단계는 별도 캐시에 생성)
+/// 그리드는 기본 썸네일을 먼저 표시하고 보이는 셀만 큰 단계로 교체
+pub async fn generate_tier_thumbnail(app_handle: &tauri::AppHandle, file_path: &str, size: u32) -> Result<ThumbnailResult, String> {
+    if size <= BASE_THUMBNAIL_SIZE {
+        return generate_hq_thumbnail(app_handle, file_path).await;
+    }
+    share_in_flight(InFlightKind::Tier(size), file_path, || create_tier_thumbnail(app_handle, file_path, size)).await
+}
+
+async fn create_tier_thumbnail(app_handle: &tauri::AppHandle, file_path: &str, size: u32) -> Result<ThumbnailResult, String> {
+    let started = Instant::now();
+    let source = resolve_source(app_handle, file_path).await?;
+
+    // 폴더별 캐시는 파일 이름 기준이라 기본 크기만 저장, 큰 단계는 중앙 캐시에만 저장
+    let mtime = get_file_mtime(&source)?;
+    let cache_key = generate_tier_cache_key(file_path, mtime, size);
+    let cache_path = get_cache_path(app_handle, &cache_key)?;
+
+    let _cache_lock = lock_cache_key(&cache_key).await;
+
+    if let Some((webp_data, width, height)) = read_cached_webp(&cache_path, file_path)? {
+        thumbnail_perf::record(file_path, ThumbnailPath::Cache, started.elapsed(), 0);
+        return Ok(ThumbnailResult {
+            path: file_path.to_string(),
+            thumbnail_base64: encode_to_base64(&webp_data),
+            width,
+            height,
+            source: ThumbnailSource::Cache,
+            exif_metadata: None,
+            has_alpha: webp_has_alpha(&webp_data),
+            is_low_quality: false,
+        });
     }
 
-    if &webp_data[0..4] != b"RIFF" || &webp_data[8..12] != b"WEBP" {
-        return None;
+    if crate::cloud_file::skip_hq_thumbnail(&source) {
+        return Err(format!("Cloud file not downloaded: {}", file_path));
     }
 
-    // VP8/VP8L/VP8X 청크 찾기
-    let chunk_type = &webp_data[12..16];
+    let (pixels, width, height, has_alpha) = decode_thumbnail(&source, file_path, size)?;
+
+    // 큰 화면용이라 기본 썸네일보다 품질을 조금 높임
+    let webp_data = encode_thumbnail_to_webp(&pixels, width, height, has_alpha, 70.0)?;
+
+    // 캐시 인덱스에 기록 (파일 수정 시 invalidate, 캐시 정리 대상)
+    crate::shutdown::write_cache_atomic(&cache_path, &webp_data)
+        .map_err(|e| format!("Failed to write tier thumbnail cache: {}", e))?;
+    crate::thumbnail_cache::record_entry(app_handle, &cache_key, file_path, mtime);
 
-    match chunk_type {
-        b"VP8 " => {
-            // Lossy WebP - 바이트 26-29에 width/height
-            if webp_data.len() < 30 {
-                return None;
+    let decode_path = if is_jpeg_file(file_path) { ThumbnailPath::Dct } else { ThumbnailPath::Generic };
+    thumbnail_perf::record(file_path, decode_path, started.elapsed(), webp_data.len());
+
+    Ok(ThumbnailResult {
+        path: file_path.to_string(),
+        thumbnail_base64: encode_to_base64(&webp_data),
+        width,
+        height,
+        source: ThumbnailSource::DctScaling,
+        exif_metadata: None,
+        has_alpha,
+        is_low_quality: false,
+    })
+}
+
+/// 캐시 키 잠금 (같은 키를 만드는 요청은 순서대로, 잠금이 모두 풀린 키는 다음 호출에서 정리)
+pub async fn lock_cache_key(cache_key: &str) -> tokio::sync::OwnedMutexGuard<()> {
+    let lock = {
+        let mut locks = CACHE_KEY_LOCKS.lock().unwrap();
+        locks.retain(|_, lock| lock.strong_count() > 0);
+        match locks.get(cache_key).and_then(Weak::upgrade) {
+            Some(lock) => lock,
+            None => {
+                let lock = Arc::new(tokio::sync::Mutex::new(()));
+                locks.insert(cache_key.to_string(), Arc::downgrade(&lock));
+                lock
             }
-            let width = (u16::from_le_bytes([webp_data[26], webp_data[27]]) & 0x3FFF) as u32;
-            let height = (u16::from_le_bytes([webp_data[28], webp_data[29]]) & 0x3FFF) as u32;
-            Some((width, height))
         }
+    };
+    lock.lock_owned().await
+}
+
+/// 캐시된 WebP 읽기 (없으면 None)
+/// 잘리거나 섞여 쓰인 캐시는 지우고 None 반환 (호출한 쪽에서 다시 생성)
+pub fn read_cached_webp(cache_path: &Path, file_path: &str) -> Result<Option<(Vec<u8>, u32, u32)>, String> {
+    if !cache_path.exists() {
+        return Ok(None);
+    }
+    let webp_data = fs::read(cache_path).map_err(|e| format!("Failed to read cache: {}", e))?;
+
+    let valid = if is_complete_webp(&webp_data) {
+        webp_dimensions(&webp_data)
+    } else {
+        Err("Truncated WebP data".to_string())
+    };
+    match valid {
+        Ok((width, height)) => Ok(Some((webp_data, width, height))),
+        Err(e) => {
+            tracing::warn!("Corrupted thumbnail cache for {}, regenerating: {}", file_path, e);
+            let _ = fs::remove_file(cache_path);
+            Ok(None)
+        }
+    }
+}
+
+// RIFF 헤더의 크기와 실제 데이터 크기가 맞는지 (잘리거나 이어 쓰인 파일 확인)
+fn is_complete_webp(webp_data: &[u8]) -> bool {
+    if webp_data.len() < 12 || &webp_data[0..4] != b"RIFF" || &webp_data[8..12] != b"WEBP" {
+        return false;
+    }
+    let riff_size = u32::from_le_bytes([webp_data[4], webp_data[5], webp_data[6], webp_data[7]]) as usize;
+    webp_data.len() == riff_size + 8
+}
+
+/// WebP 헤더에서 이미지 크기 읽기 (픽셀은 디코딩하지 않음)
+pub fn webp_dimensions(webp_data: &[u8]) -> Result<(u32, u32), String> {
+    use image::ImageDecoder;
+
+    let decoder = image::codecs::webp::WebPDecoder::new(std::io::Cursor::new(webp_data))
+        .map_err(|e| format!("Invalid WebP header: {}", e))?;
+    Ok(decoder.dimensions())
+}
+
+/// WebP 데이터에 알파 채널이 있는지 확인
+/// VP8X: 플래그 바이트(20)의 알파 비트, VP8L: 헤더의 alpha_is_used 비트, VP8(lossy 단독): 알파 없음
+pub fn webp_has_alpha(webp_data: &[u8]) -> bool {
+    if webp_data.len() < 25 || &webp_data[0..4] != b"RIFF" || &webp_data[8..12] != b"WEBP" {
+        return false;
+    }
+
+    match &webp_data[12..16] {
+        b"VP8X" => webp_data[20] & 0x10 != 0,
         b"VP8L" => {
-            // Lossless WebP - 바이트 21-24에 packed bits
-            if webp_data.len() < 25 {
-                return None;
-            }
             let bits = u32::from_le_bytes([webp_data[21], webp_data[22], webp_data[23], webp_data[24]]);
-            let width = (bits & 0x3FFF) + 1;
-            let height = ((bits >> 14) & 0x3FFF) + 1;
-            Some((width, height))
+            (bits >> 28) & 1 == 1
         }
-        b"VP8X" => {
-            // Extended WebP - 바이트 24-29에 width/height (24-bit little endian)
-            if webp_data.len() < 30 {
-                return None;
-            }
-            let width = (u32::from_le_bytes([webp_data[24], webp_data[25], webp_data[26], 0]) & 0xFFFFFF) + 1;
-            let height = (u32::from_le_bytes([webp_data[27], webp_data[28], webp_data[29], 0]) & 0xFFFFFF) + 1;
-            Some((width, height))
-        }
-        _ => None,
+        _ => false,
     }
 }
 
 /// HQ 썸네일이 이미 존재하는지 확인 (캐시 파일 존재 여부)
 /// 이제 캐시는 모두 HQ 썸네일만 저장되므로 파일 존재만 확인
 pub fn has_hq_thumbnail(app_handle: &tauri::AppHandle, file_path: &str) -> bool {
-    match get_file_mtime(file_path) {
-        Ok(mtime) => {
-            let cache_key = generate_cache_key(file_path, mtime);
-            match get_cache_path(app_handle, &cache_key) {
-                Ok(cache_path) => cache_path.exists(),
-                Err(_) => false,
-            }
-        }
+    let source = crate::fs_path::to_fs_string(file_path);
+    match get_file_mtime(&source) {
+        Ok(mtime) => match get_cache_path_for(app_handle, file_path, mtime) {
+            Ok(cache_path) => cache_path.exists(),
+            Err(_) => false,
+        },
         Err(_) => false,
     }
 }
@@ -943,12 +1559,39 @@ pub struct HqThumbnailClassification {
     pub missing: Vec<String>,
 }
 
+/// 캐시 디렉토리 목록을 한 번만 읽고, 캐시 키 계산(stat)은 병렬 처리 (요청 순서 유지)
 pub fn classify_hq_thumbnails(app_handle: &tauri::AppHandle, image_paths: Vec<String>) -> HqThumbnailClassification {
+    use rayon::prelude::*;
+    use std::collections::HashSet;
+
+    let cached: HashSet<String> = get_cache_dir(app_handle)
+        .and_then(|dir| fs::read_dir(dir).map_err(|e| format!("Failed to read cache directory: {}", e)))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| entry.file_name().to_str()?.strip_suffix(".webp").map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let has_hq: Vec<bool> = image_paths
+        .par_iter()
+        .map(|path| {
+            get_file_mtime(&crate::fs_path::to_fs_string(path)).is_ok_and(|mtime| {
+                // 폴더별 캐시가 있는 폴더는 파일을 직접 확인
+                if crate::local_cache::is_active(path) {
+                    get_cache_path_for(app_handle, path, mtime).is_ok_and(|cache_path| cache_path.exists())
+                } else {
+                    cached.contains(&generate_cache_key(path, mtime))
+                }
+            })
+        })
+        .collect();
+
     let mut existing = Vec::new();
     let mut missing = Vec::new();
-
-    for path in image_paths {
-        if has_hq_thumbnail(app_handle, &path) {
+    for (path, has_hq) in image_paths.into_iter().zip(has_hq) {
+        if has_hq {
             existing.push(path);
         } else {
             missing.push(path);
@@ -957,3 +1600,140 @@ pub fn classify_hq_thumbnails(app_handle: &tauri::AppHandle, image_paths: Vec<St
 
     HqThumbnailClassification { existing, missing }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // EXIF(썸네일 포함)가 든 APP1 앞에 다른 세그먼트가 있는 JPEG 앞부분
+    fn jpeg_with_exif_thumbnail(thumbnail: &[u8]) -> Vec<u8> {
+        let mut writer = exif::experimental::Writer::new();
+        let orientation = exif::Field {
+            tag: Tag::Orientation,
+            ifd_num: In::PRIMARY,
+            value: exif::Value::Short(vec![1]),
+        };
+        writer.push_field(&orientation);
+        writer.set_jpeg(thumbnail, In::THUMBNAIL);
+        let mut tiff = std::io::Cursor::new(Vec::new());
+        writer.write(&mut tiff, false).unwrap();
+        let tiff = tiff.into_inner();
+
+        let mut data = vec![0xFF, 0xD8];
+        data.extend_from_slice(&[0xFF, 0xE0, 0x00, 0x06, b'J', b'F', b'I', b'F']);
+        data.extend_from_slice(&[0xFF, 0xE1]);
+        data.extend_from_slice(&((tiff.len() + 8) as u16).to_be_bytes());
+        data.extend_from_slice(b"Exif\0\0");
+        data.extend_from_slice(&tiff);
+        data.extend_from_slice(&[0xFF, 0xDA, 0x00, 0x02, 0x00, 0xFF, 0xD9]);
+        data
+    }
+
+    #[test]
+    fn test_extract_exif_thumbnail_from_head() {
+        let thumbnail = [0xFF, 0xD8, 0x01, 0x02, 0xFF, 0xD9];
+        let data = jpeg_with_exif_thumbnail(&thumbnail);
+        assert_eq!(extract_exif_thumbnail_from(&data).unwrap(), thumbnail);
+    }
+
+    #[test]
+    fn test_exif_scan_stops_at_sos() {
+        // 채움 바이트, 길이 없는 마커 뒤 APP1 없이 SOS
+        let data = [0xFF, 0xD8, 0xFF, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, 0xFF, 0xD0, 0xFF, 0xDA, 0x00, 0x02];
+        assert_eq!(extract_exif_thumbnail_from(&data), Err(ExifThumbnailError::NotFound));
+
+        // 채움 바이트가 있어도 EXIF 세그먼트를 찾음
+        let thumbnail = [0xFF, 0xD8, 0xFF, 0xD9];
+        let mut data = jpeg_with_exif_thumbnail(&thumbnail);
+        data.insert(2, 0xFF);
+        assert_eq!(extract_exif_thumbnail_from(&data).unwrap(), thumbnail);
+
+        // 세그먼트 중간에서 잘린 앞부분
+        assert_eq!(extract_exif_thumbnail_from(&data[..6]), Err(ExifThumbnailError::NotFound));
+        assert!(matches!(
+            extract_exif_thumbnail_from(&[0xFF, 0xD8, 0x12]),
+            Err(ExifThumbnailError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_extract_exif_thumbnail_large_file() {
+        // 앞부분만 읽으므로 뒤의 이미지 데이터 크기와 상관없이 같은 결과
+        let thumbnail = [0xFF, 0xD8, 0x01, 0x02, 0xFF, 0xD9];
+        let mut data = jpeg_with_exif_thumbnail(&thumbnail);
+        data.resize(EXIF_HEAD_BYTES * 16, 0xAB);
+        let path = std::env::temp_dir().join(format!("pixengine-exif-large-{}.jpg", std::process::id()));
+        fs::write(&path, &data).unwrap();
+
+        let result = extract_exif_thumbnail(&path.to_string_lossy());
+        let _ = fs::remove_file(&path);
+        assert_eq!(result.unwrap(), thumbnail);
+    }
+
+    #[test]
+    fn test_webp_dimensions() {
+        let webp = encode_thumbnail_to_webp(&vec![128; 30 * 20 * 3], 30, 20, false, 60.0).unwrap();
+        assert_eq!(webp_dimensions(&webp), Ok((30, 20)));
+
+        // 깨진 캐시는 320x320 대신 오류
+        assert!(webp_dimensions(&webp[..10]).is_err());
+        assert!(webp_dimensions(b"not a webp file at all, just text").is_err());
+    }
+
+    #[test]
+    fn test_validate_truncated_jpeg() {
+        let jpeg = encode_thumbnail_to_jpeg(&vec![200; 64 * 48 * 3], 64, 48).unwrap();
+        let path = std::env::temp_dir().join(format!("pixengine-validate-{}.jpg", std::process::id()));
+        let path_str = path.to_string_lossy().to_string();
+
+        fs::write(&path, &jpeg).unwrap();
+        assert!(validate_image(&path_str).is_ok());
+
+        fs::write(&path, &jpeg[..jpeg.len() / 2]).unwrap();
+        assert!(validate_image(&path_str).is_err());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_select_tier() {
+        assert_eq!(select_tier(150.0, 1.0, 640), BASE_THUMBNAIL_SIZE);
+        assert_eq!(select_tier(200.0, 2.0, 640), 640);
+        // 낮은 배율은 1로 취급
+        assert_eq!(select_tier(300.0, 0.5, 640), BASE_THUMBNAIL_SIZE);
+        assert_eq!(generate_tier_cache_key("a.jpg", 1, 320), generate_cache_key("a.jpg", 1));
+        assert_ne!(generate_tier_cache_key("a.jpg", 1, 640), generate_cache_key("a.jpg", 1));
+    }
+
+    #[test]
+    fn test_is_complete_webp() {
+        let webp = encode_thumbnail_to_webp(&vec![120; 32 * 24 * 3], 32, 24, false, 60.0).unwrap();
+        assert!(is_complete_webp(&webp));
+        assert!(!is_complete_webp(&webp[..webp.len() - 1]));
+
+        let mut doubled = webp.clone();
+        doubled.extend_from_slice(&webp);
+        assert!(!is_complete_webp(&doubled));
+    }
+
+    #[test]
+    fn test_average_rgb() {
+        assert_eq!(average_rgb(&[0, 0, 0, 255, 128, 64]), [127, 64, 32]);
+        assert_eq!(average_rgb(&[]), [0, 0, 0]);
+    }
+
+    #[test]
+    fn test_cmyk_to_rgb() {
+        assert_eq!(cmyk_to_rgb(&[0, 0, 0, 0]), vec![255, 255, 255]);
+        assert_eq!(cmyk_to_rgb(&[0, 0, 0, 255]), vec![0, 0, 0]);
+        assert_eq!(cmyk_to_rgb(&[255, 0, 0, 0]), vec![0, 255, 255]);
+    }
+
+    #[test]
+    fn test_tonemap_hdr_float() {
+        let hdr = image::Rgb32FImage::from_pixel(2, 2, image::Rgb([4.0, 0.5, 0.0]));
+        let mapped = tonemap_to_8bit(image::DynamicImage::ImageRgb32F(hdr)).to_rgb8();
+        let pixel = mapped.get_pixel(0, 0);
+        assert!(pixel[0] < 255 && pixel[0] > pixel[1]);
+        assert_eq!(pixel[2], 0);
+    }
+}
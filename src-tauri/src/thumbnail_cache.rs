@@ -0,0 +1,402 @@
+use rusqlite::params;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::Manager;
+
+use crate::metadata_store::MetadataStore;
+use crate::jobs::Job;
+use crate::thumbnail::{get_cache_dir, get_cache_path, get_cache_path_for, get_default_cache_dir, get_file_mtime};
+
+/// 캐시 인덱스 테이블 스키마
+/// 캐시 키는 경로+mtime 해시라 역산이 안 되므로 원본 경로를 따로 기록
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS thumbnail_cache (
+        cache_key TEXT PRIMARY KEY,
+        path      TEXT NOT NULL,
+        mtime     INTEGER NOT NULL
+    );
+";
+
+/// 항목 분류별 개수/용량
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CacheCategory {
+    pub count: usize,
+    pub bytes: u64,
+}
+
+/// 썸네일 캐시 통계
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CacheReport {
+    pub total: CacheCategory,
+    pub orphaned: CacheCategory,  // 원본 파일 없음
+    pub stale: CacheCategory,     // 원본이 수정됨 (mtime 변경)
+    pub untracked: CacheCategory, // 인덱스 기록 이전에 만들어진 항목 (원본 확인 불가)
+    pub reclaimable_bytes: u64,   // orphaned + stale
+}
+
+/// 정리 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheCleanupResult {
+    pub deleted: usize,
+    pub freed_bytes: u64,
+}
+
+/// 캐시 폴더 이동 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheRelocateResult {
+    pub cache_dir: String,
+    pub moved: usize,
+    pub moved_bytes: u64,
+    /// 옮기지 못한 항목 수 (다시 생성됨)
+    pub failed: usize,
+}
+
+/// 캐시 항목 상태
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EntryState {
+    Valid,
+    Orphaned,
+    Stale,
+    Untracked,
+}
+
+/// 캐시 인덱스 테이블 생성
+pub fn init_schema(store: &MetadataStore) -> Result<(), String> {
+    store.with_conn(|conn| conn.execute_batch(SCHEMA))
+}
+
+/// 캐시 파일 저장 후 원본 경로 기록 (저장소가 없으면 무시)
+pub fn record_entry(app_handle: &tauri::AppHandle, cache_key: &str, file_path: &str, mtime: u64) {
+    if let Some(store) = app_handle.try_state::<Arc<MetadataStore>>() {
+        let _ = store.with_conn(|conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO thumbnail_cache (cache_key, path, mtime) VALUES (?1, ?2, ?3)",
+                params![cache_key, file_path, mtime as i64],
+            )
+        });
+    }
+}
+
+/// 파일의 캐시 항목 모두 삭제 (수정 시간이 그대로인 편집도 다시 생성되도록), 삭제한 수 반환
+pub fn invalidate(app_handle: &tauri::AppHandle, file_path: &str) -> usize {
+    let mut removed = 0;
+
+    // 현재 수정 시간의 캐시 (폴더별 캐시 포함)
+    if let Ok(path) = get_file_mtime(file_path).and_then(|mtime| get_cache_path_for(app_handle, file_path, mtime)) {
+        if fs::remove_file(path).is_ok() {
+            removed += 1;
+        }
+    }
+
+    // 인덱스에 기록된 이전 캐시
+    let Some(store) = app_handle.try_state::<Arc<MetadataStore>>() else {
+        return removed;
+    };
+    let keys: Vec<String> = store
+        .with_conn(|conn| {
+            let mut stmt = conn.prepare_cached("SELECT cache_key FROM thumbnail_cache WHERE path = ?1")?;
+            let rows = stmt.query_map(params![file_path], |row| row.get(0))?;
+            rows.collect()
+        })
+        .unwrap_or_default();
+    for key in &keys {
+        if get_cache_path(app_handle, key).is_ok_and(|path| fs::remove_file(path).is_ok()) {
+            removed += 1;
+        }
+    }
+    let _ = store.with_conn(|conn| conn.execute("DELETE FROM thumbnail_cache WHERE path = ?1", params![file_path]));
+    removed
+}
+
+/// 캐시 디렉토리 스캔 후 항목별 상태 분류
+fn scan_entries(
+    app_handle: &tauri::AppHandle,
+    store: &MetadataStore,
+) -> Result<Vec<(PathBuf, String, u64, EntryState)>, String> {
+    let cache_dir = get_cache_dir(app_handle)?;
+    if !cache_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let index: HashMap<String, (String, u64)> = store.with_conn(|conn| {
+        let mut stmt = conn.prepare("SELECT cache_key, path, mtime FROM thumbnail_cache")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, (row.get::<_, String>(1)?, row.get::<_, i64>(2)? as u64)))
+        })?;
+        rows.collect()
+    })?;
+
+    let entries = fs::read_dir(&cache_dir)
+        .map_err(|e| format!("Failed to read cache directory: {}", e))?;
+
+    let mut results = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("webp") {
+            continue;
+        }
+
+        let Some(cache_key) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+            continue;
+        };
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+
+        let state = match index.get(&cache_key) {
+            None => EntryState::Untracked,
+            Some((source, mtime)) => match get_file_mtime(&crate::fs_path::to_fs_string(source)) {
+                Err(_) if !crate::fs_path::to_fs_path(source).exists() => EntryState::Orphaned,
+                Ok(current) if current != *mtime => EntryState::Stale,
+                _ => EntryState::Valid,
+            },
+        };
+
+        results.push((path, cache_key, size, state));
+    }
+
+    Ok(results)
+}
+
+/// 썸네일 캐시 통계 (정리 가능한 용량 포함)
+pub fn scan(app_handle: &tauri::AppHandle, store: &MetadataStore) -> Result<CacheReport, String> {
+    let mut report = CacheReport::default();
+
+    for (_, _, size, state) in scan_entries(app_handle, store)? {
+        report.total.count += 1;
+        report.total.bytes += size;
+
+        let category = match state {
+            EntryState::Valid => continue,
+            EntryState::Orphaned => &mut report.orphaned,
+            EntryState::Stale => &mut report.stale,
+            EntryState::Untracked => &mut report.untracked,
+        };
+        category.count += 1;
+        category.bytes += size;
+    }
+
+    report.reclaimable_bytes = report.orphaned.bytes + report.stale.bytes;
+    Ok(report)
+}
+
+/// 고아/오래된 캐시 삭제 (include_untracked면 원본 확인이 안 되는 항목도 삭제)
+pub fn clean(
+    app_handle: &tauri::AppHandle,
+    store: &MetadataStore,
+    include_untracked: bool,
+) -> Result<CacheCleanupResult, String> {
+    let mut result = CacheCleanupResult { deleted: 0, freed_bytes: 0 };
+    let mut removed_keys = Vec::new();
+
+    for (path, cache_key, size, state) in scan_entries(app_handle, store)? {
+        let should_delete = match state {
+            EntryState::Orphaned | EntryState::Stale => true,
+            EntryState::Untracked => include_untracked,
+            EntryState::Valid => false,
+        };
+
+        if should_delete && fs::remove_file(&path).is_ok() {
+            result.deleted += 1;
+            result.freed_bytes += size;
+            removed_keys.push(cache_key);
+        }
+    }
+
+    let cache_dir = get_cache_dir(app_handle)?;
+    store.with_conn(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        {
+            let mut stmt = tx.prepare_cached("DELETE FROM thumbnail_cache WHERE cache_key = ?1")?;
+            for key in &removed_keys {
+                stmt.execute(params![key])?;
+            }
+
+            // 캐시 파일이 이미 없는 인덱스 항목 정리
+            let keys: Vec<String> = tx
+                .prepare("SELECT cache_key FROM thumbnail_cache")?
+                .query_map([], |row| row.get(0))?
+                .collect::<rusqlite::Result<_>>()?;
+            for key in keys {
+                if !cache_dir.join(format!("{}.webp", key)).exists() {
+                    stmt.execute(params![key])?;
+                }
+            }
+        }
+        tx.commit()
+    })?;
+
+    Ok(result)
+}
+
+/// 캐시 용량이 max_bytes를 넘으면 오래된 항목부터 삭제 (캐시 파일(.webp)만 세고 지움)
+pub fn enforce_size_limit(app_handle: &tauri::AppHandle, max_bytes: u64) -> Result<CacheCleanupResult, String> {
+    let mut result = CacheCleanupResult { deleted: 0, freed_bytes: 0 };
+    let cache_dir = get_cache_dir(app_handle)?;
+    if !cache_dir.exists() {
+        return Ok(result);
+    }
+
+    let mut entries: Vec<(PathBuf, u64, std::time::SystemTime)> = fs::read_dir(&cache_dir)
+        .map_err(|e| format!("Failed to read cache directory: {}", e))?
+        .flatten()
+        .filter(|entry| entry.path().extension().and_then(|e| e.to_str()) == Some("webp"))
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            let modified = metadata.modified().ok()?;
+            Some((entry.path(), metadata.len(), modified))
+        })
+        .collect();
+
+    let mut total: u64 = entries.iter().map(|(_, size, _)| size).sum();
+    if total <= max_bytes {
+        return Ok(result);
+    }
+
+    entries.sort_by_key(|(_, _, modified)| *modified);
+    for (path, size, _) in entries {
+        if total <= max_bytes {
+            break;
+        }
+        if fs::remove_file(&path).is_ok() {
+            total -= size;
+            result.deleted += 1;
+            result.freed_bytes += size;
+        }
+    }
+
+    tracing::info!(
+        "Thumbnail cache limit enforced: {} files, {} bytes freed",
+        result.deleted,
+        result.freed_bytes
+    );
+    Ok(result)
+}
+
+// 캐시 파일 (.webp) 목록과 크기
+fn cache_files(dir: &Path) -> Vec<(PathBuf, u64)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.path().extension().and_then(|e| e.to_str()) == Some("webp"))
+        .map(|entry| (entry.path(), entry.metadata().map(|m| m.len()).unwrap_or(0)))
+        .collect()
+}
+
+/// 새 캐시 폴더 검증
+/// 용량 제한 정리가 폴더의 .webp 파일을 지우므로 비어 있거나 캐시 파일만 있는 폴더만 허용
+fn validate_target(current: &Path, target: &Path, required_bytes: u64) -> Result<(), String> {
+    if !target.is_absolute() {
+        return Err("Cache directory must be an absolute path".to_string());
+    }
+    if target.starts_with(current) || current.starts_with(target) {
+        return Err("Cache directory cannot be inside the current cache directory or contain it".to_string());
+    }
+
+    fs::create_dir_all(target).map_err(|e| format!("Failed to create cache directory: {}", e))?;
+    let has_other_files = fs::read_dir(target)
+        .map_err(|e| format!("Failed to read cache directory: {}", e))?
+        .flatten()
+        .any(|entry| entry.path().extension().and_then(|e| e.to_str()) != Some("webp"));
+    if has_other_files {
+        return Err("Cache directory must be empty".to_string());
+    }
+
+    let probe = target.join(".write-test");
+    fs::write(&probe, b"").map_err(|e| format!("Cache directory is not writable: {}", e))?;
+    let _ = fs::remove_file(&probe);
+
+    if let Ok(space) = crate::drives::get_space_info(&target.to_string_lossy()) {
+        if space.free_bytes < required_bytes {
+            return Err(format!(
+                "Not enough free space: {} bytes required, {} available",
+                required_bytes, space.free_bytes
+            ));
+        }
+    }
+    Ok(())
+}
+
+// 같은 볼륨이면 이름 변경, 아니면 복사 후 삭제 (이미 있는 항목은 원본만 삭제)
+fn move_cache_file(source: &Path, target: &Path) -> std::io::Result<()> {
+    if target.exists() {
+        return fs::remove_file(source);
+    }
+    if fs::rename(source, target).is_ok() {
+        return Ok(());
+    }
+    fs::copy(source, target)?;
+    fs::remove_file(source)
+}
+
+/// 캐시 폴더 변경 후 기존 캐시 이동 (target이 None이면 앱 데이터 폴더로 되돌림)
+/// 설정을 먼저 바꿔 새 썸네일은 바로 새 폴더에 저장, 옮기는 중 없는 항목은 다시 생성됨
+pub fn relocate(app_handle: &tauri::AppHandle, target: Option<&str>, job: &Job) -> Result<CacheRelocateResult, String> {
+    let current = get_cache_dir(app_handle)?;
+    let target_dir = match target {
+        Some(dir) => PathBuf::from(dir),
+        None => get_default_cache_dir(app_handle)?,
+    };
+
+    let files = cache_files(&current);
+    let mut result = CacheRelocateResult {
+        cache_dir: target_dir.to_string_lossy().to_string(),
+        moved: 0,
+        moved_bytes: 0,
+        failed: 0,
+    };
+    if target_dir == current {
+        return Ok(result);
+    }
+
+    let required_bytes = files.iter().map(|(_, size)| size).sum();
+    validate_target(&current, &target_dir, required_bytes)?;
+    crate::settings::set_thumbnail_cache_dir(app_handle, target.map(str::to_string))?;
+
+    for (i, (source, size)) in files.iter().enumerate() {
+        if job.is_cancelled() {
+            break;
+        }
+        let Some(name) = source.file_name() else {
+            continue;
+        };
+        match move_cache_file(source, &target_dir.join(name)) {
+            Ok(()) => {
+                result.moved += 1;
+                result.moved_bytes += size;
+            }
+            Err(e) => {
+                tracing::debug!("Failed to move cache file {}: {}", source.display(), e);
+                result.failed += 1;
+            }
+        }
+        job.set_progress(i + 1, files.len());
+    }
+
+    // 기존 폴더가 비었으면 삭제 (취소해서 남은 항목은 쓰이지 않으며 다시 생성됨)
+    let _ = fs::remove_dir(&current);
+
+    tracing::info!(
+        "Thumbnail cache relocated to {}: {} files ({} failed)",
+        result.cache_dir,
+        result.moved,
+        result.failed
+    );
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_target_rejects_nested() {
+        let current = std::env::temp_dir().join("pixengine-cache-current");
+        assert!(validate_target(&current, &current.join("sub"), 0).is_err());
+        assert!(validate_target(&current, &std::env::temp_dir(), 0).is_err());
+        assert!(validate_target(&current, Path::new("relative"), 0).is_err());
+    }
+}
@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+use crate::thumbnail::ThumbnailSource;
+
+/// 항목별 소요 시간 EMA 가중치 (클수록 최근 항목을 많이 반영)
+const EMA_ALPHA: f64 = 0.2;
+
+/// 추정을 보내기 시작하는 최소 완료 수 (처음 몇 개는 디스크 캐시 등으로 들쭉날쭉)
+const MIN_SAMPLES: usize = 5;
+
+/// 진행 이벤트의 남은 시간 추정 (샘플이 모이기 전에는 None)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ProgressEstimate {
+    /// 남은 예상 시간 (초)
+    #[serde(default)]
+    pub eta_seconds: Option<f64>,
+    /// 현재 처리 속도 (항목/초)
+    #[serde(default)]
+    pub items_per_second: Option<f64>,
+}
+
+// 소스별 소요 시간 통계
+#[derive(Debug, Clone, Copy, Default)]
+struct SourceTiming {
+    ema_secs: Option<f64>,
+    count: usize,
+}
+
+/// 썸네일 배치의 남은 시간 추정
+/// 소스(캐시/EXIF/DCT)마다 항목당 소요 시간 EMA를 따로 두고 지금까지의 소스 비율로 섞음
+/// 병렬 처리는 (항목 소요 시간 합 / 경과 시간)으로 동시 처리 수를 추정해 반영
+#[derive(Debug)]
+pub struct EtaEstimator {
+    started: Instant,
+    timings: [SourceTiming; 3],
+    /// 항목 소요 시간 합 (초, 동시 처리 수 추정용)
+    busy_secs: f64,
+}
+
+impl Default for EtaEstimator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn source_index(source: &ThumbnailSource) -> usize {
+    match source {
+        ThumbnailSource::Cache => 0,
+        ThumbnailSource::ExifEmbedded => 1,
+        ThumbnailSource::DctScaling => 2,
+    }
+}
+
+impl EtaEstimator {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            timings: [SourceTiming::default(); 3],
+            busy_secs: 0.0,
+        }
+    }
+
+    /// 항목 하나의 소요 시간 기록
+    pub fn record(&mut self, source: &ThumbnailSource, duration: Duration) {
+        let secs = duration.as_secs_f64();
+        let timing = &mut self.timings[source_index(source)];
+        timing.ema_secs = Some(match timing.ema_secs {
+            Some(ema) => ema + EMA_ALPHA * (secs - ema),
+            None => secs,
+        });
+        timing.count += 1;
+        self.busy_secs += secs;
+    }
+
+    // 소스 비율로 섞은 항목당 소요 시간
+    fn mixed_item_secs(&self) -> Option<f64> {
+        let samples: usize = self.timings.iter().map(|timing| timing.count).sum();
+        if samples < MIN_SAMPLES {
+            return None;
+        }
+        let weighted: f64 = self
+            .timings
+            .iter()
+            .filter_map(|timing| timing.ema_secs.map(|ema| ema * timing.count as f64))
+            .sum();
+        Some(weighted / samples as f64)
+    }
+
+    /// 남은 항목 수로 추정 계산
+    pub fn estimate(&self, remaining: usize) -> ProgressEstimate {
+        self.estimate_at(remaining, self.started.elapsed())
+    }
+
+    fn estimate_at(&self, remaining: usize, elapsed: Duration) -> ProgressEstimate {
+        let elapsed = elapsed.as_secs_f64();
+        let Some(item_secs) = self.mixed_item_secs().filter(|secs| *secs > 0.0) else {
+            return ProgressEstimate::default();
+        };
+        if elapsed <= 0.0 {
+            return ProgressEstimate::default();
+        }
+
+        // 동시 처리 수 (유휴 대기 등으로 1보다 작을 수 있음)
+        let concurrency = self.busy_secs / elapsed;
+        if concurrency <= 0.0 {
+            return ProgressEstimate::default();
+        }
+        let items_per_second = concurrency / item_secs;
+        ProgressEstimate {
+            eta_seconds: Some(remaining as f64 / items_per_second),
+            items_per_second: Some(items_per_second),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_mixes_sources() {
+        let mut estimator = EtaEstimator::new();
+        assert!(estimator.estimate_at(10, Duration::from_secs(1)).eta_seconds.is_none());
+
+        // 캐시 4개 0.01초, DCT 4개 0.2초 → 항목당 평균 0.105초
+        for _ in 0..4 {
+            estimator.record(&ThumbnailSource::Cache, Duration::from_millis(10));
+            estimator.record(&ThumbnailSource::DctScaling, Duration::from_millis(200));
+        }
+
+        // 합 0.84초를 0.42초 동안 처리 → 동시 처리 2개
+        let estimate = estimator.estimate_at(100, Duration::from_millis(420));
+        let items_per_second = estimate.items_per_second.unwrap();
+        assert!((items_per_second - 2.0 / 0.105).abs() < 1e-6);
+        assert!((estimate.eta_seconds.unwrap() - 100.0 / items_per_second).abs() < 1e-6);
+    }
+}
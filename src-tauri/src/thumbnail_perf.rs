@@ -0,0 +1,136 @@
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::formats::{self, FormatCategory};
+
+/// 썸네일을 어떤 경로로 만들었는지
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThumbnailPath {
+    /// EXIF 내장 썸네일 (디코딩 없음)
+    Exif,
+    /// 캐시된 WebP
+    Cache,
+    /// JPEG DCT 스케일링 디코딩
+    Dct,
+    /// 그 밖의 디코딩 (RAW 미리보기, PSD, SVG, 범용 이미지)
+    Generic,
+}
+
+// 형식별 누적 값
+#[derive(Debug, Clone, Copy, Default)]
+struct Counters {
+    exif_hits: u64,
+    cache_hits: u64,
+    dct_decodes: u64,
+    generic_decodes: u64,
+    total_ms: f64,
+    decode_ms: f64,
+    bytes_written: u64,
+}
+
+/// 형식별 썸네일 생성 통계
+#[derive(Debug, Clone, Serialize)]
+pub struct SourcePerf {
+    /// 형식 분류 (모르는 형식은 None)
+    pub category: Option<FormatCategory>,
+    pub exif_hits: u64,
+    pub cache_hits: u64,
+    pub dct_decodes: u64,
+    pub generic_decodes: u64,
+    /// 항목당 평균 소요 시간 (모든 경로)
+    pub average_ms: f64,
+    /// 디코딩한 항목의 평균 소요 시간 (DCT + 범용)
+    pub decode_average_ms: Option<f64>,
+    /// 캐시에 쓴 WebP 크기 합
+    pub bytes_written: u64,
+}
+
+/// get_thumbnail_perf_report 응답 (앱 시작 후 누적)
+#[derive(Debug, Clone, Serialize)]
+pub struct ThumbnailPerfReport {
+    /// 첫 기록부터 지난 시간 (초)
+    pub elapsed_secs: f64,
+    /// 항목 수가 많은 순
+    pub sources: Vec<SourcePerf>,
+}
+
+lazy_static! {
+    static ref STATS: Mutex<HashMap<Option<FormatCategory>, Counters>> = Mutex::new(HashMap::new());
+    static ref STARTED: Instant = Instant::now();
+}
+
+/// 썸네일 하나의 생성 결과 기록 (bytes_written은 캐시에 쓴 크기, 안 썼으면 0)
+pub fn record(file_path: &str, path: ThumbnailPath, elapsed: Duration, bytes_written: usize) {
+    lazy_static::initialize(&STARTED);
+    let ms = elapsed.as_secs_f64() * 1000.0;
+
+    let mut stats = STATS.lock().unwrap();
+    let counters = stats.entry(formats::category(file_path)).or_default();
+    match path {
+        ThumbnailPath::Exif => counters.exif_hits += 1,
+        ThumbnailPath::Cache => counters.cache_hits += 1,
+        ThumbnailPath::Dct => counters.dct_decodes += 1,
+        ThumbnailPath::Generic => counters.generic_decodes += 1,
+    }
+    if matches!(path, ThumbnailPath::Dct | ThumbnailPath::Generic) {
+        counters.decode_ms += ms;
+    }
+    counters.total_ms += ms;
+    counters.bytes_written += bytes_written as u64;
+}
+
+fn source_perf(category: Option<FormatCategory>, counters: &Counters) -> SourcePerf {
+    let total = counters.exif_hits + counters.cache_hits + counters.dct_decodes + counters.generic_decodes;
+    let decodes = counters.dct_decodes + counters.generic_decodes;
+    SourcePerf {
+        category,
+        exif_hits: counters.exif_hits,
+        cache_hits: counters.cache_hits,
+        dct_decodes: counters.dct_decodes,
+        generic_decodes: counters.generic_decodes,
+        average_ms: if total > 0 { counters.total_ms / total as f64 } else { 0.0 },
+        decode_average_ms: (decodes > 0).then(|| counters.decode_ms / decodes as f64),
+        bytes_written: counters.bytes_written,
+    }
+}
+
+/// 앱 시작 후 누적된 형식별 통계
+pub fn report() -> ThumbnailPerfReport {
+    let stats = STATS.lock().unwrap();
+    let mut sources: Vec<(u64, SourcePerf)> = stats
+        .iter()
+        .map(|(category, counters)| {
+            let perf = source_perf(*category, counters);
+            (perf.exif_hits + perf.cache_hits + perf.dct_decodes + perf.generic_decodes, perf)
+        })
+        .collect();
+    sources.sort_by(|a, b| b.0.cmp(&a.0));
+
+    ThumbnailPerfReport {
+        elapsed_secs: if stats.is_empty() { 0.0 } else { STARTED.elapsed().as_secs_f64() },
+        sources: sources.into_iter().map(|(_, perf)| perf).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_perf_averages() {
+        let counters = Counters {
+            cache_hits: 2,
+            dct_decodes: 2,
+            total_ms: 100.0,
+            decode_ms: 90.0,
+            ..Counters::default()
+        };
+        let perf = source_perf(Some(FormatCategory::Jpeg), &counters);
+        assert_eq!(perf.average_ms, 25.0);
+        assert_eq!(perf.decode_average_ms, Some(45.0));
+        assert_eq!(source_perf(None, &Counters::default()).decode_average_ms, None);
+    }
+}
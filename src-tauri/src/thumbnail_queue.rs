@@ -1,29 +1,57 @@
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use tokio::sync::{Mutex, RwLock};
-use tokio::time::{sleep, Duration};
+use tokio::time::{sleep, Duration, Instant};
 use tauri::{AppHandle, Emitter};
 use lazy_static::lazy_static;
 
 use crate::thumbnail::{self, ThumbnailResult};
 use crate::idle_detector;
+use crate::settings;
+use crate::thumbnail_cache;
+use crate::hq_progress::{self, HqGenerationState};
+use crate::scheduler::{self, WorkClass};
+use crate::file_lock;
+use crate::jobs::{Job, JobKind};
+use crate::thumbnail_eta::{EtaEstimator, ProgressEstimate};
 
 /// 고화질 썸네일 생성 취소 플래그 (전역)
 static HQ_GENERATION_CANCELLED: AtomicBool = AtomicBool::new(false);
 
+/// HQ 썸네일 워커 실행 중 플래그 (종료 시 대기용)
+static HQ_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// HQ 썸네일 생성 일시정지 플래그 (트레이 메뉴, 새 작업에도 유지)
+static HQ_PAUSED: AtomicBool = AtomicBool::new(false);
+
+/// HQ 우선순위 변경 횟수 (뷰포트/저화질 경로가 바뀌면 증가, 워커가 대기열을 다시 정렬)
+static HQ_PRIORITY_VERSION: AtomicU64 = AtomicU64::new(0);
+
 lazy_static! {
     /// HQ 생성 뷰포트 경로 (전역)
     static ref HQ_VIEWPORT_PATHS: Arc<RwLock<HashSet<String>>> = Arc::new(RwLock::new(HashSet::new()));
+    /// 취소된 HQ 작업의 남은 경로 (종료 시 저장용)
+    static ref HQ_PENDING: std::sync::Mutex<Vec<String>> = std::sync::Mutex::new(Vec::new());
+    /// 저화질 EXIF 썸네일로 표시된 경로 (뷰포트 다음으로 HQ 우선 생성)
+    static ref HQ_UPGRADE_PATHS: RwLock<HashSet<String>> = RwLock::new(HashSet::new());
+    /// 실행 중인 HQ 생성 작업 식별자 (진행 기록의 token)
+    static ref HQ_CURRENT_TOKEN: std::sync::Mutex<Option<String>> = std::sync::Mutex::new(None);
+    /// 완료된 HQ 결과 경로 (순번, 경로), 창이 숨겨진 동안 보내지 않은 결과를 나중에 가져감
+    static ref HQ_RESULT_LOG: std::sync::Mutex<VecDeque<(u64, String)>> = std::sync::Mutex::new(VecDeque::new());
 }
 
-// HQ 썸네일 생성 상수
-/// HQ 썸네일 최대 동시 생성 개수 (CPU 코어의 절반)
-fn get_hq_max_concurrent() -> usize {
-    (num_cpus::get() / 2).max(1)
-}
-/// 유휴 시간 감지 임계값 (밀리초)
-const IDLE_THRESHOLD_MS: u64 = 3000;
+/// HQ 진행 기록 저장 간격
+const HQ_PROGRESS_SAVE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// HQ 결과 기록 최대 개수 (오래된 것부터 버림)
+const HQ_RESULT_LOG_LIMIT: usize = 100_000;
+
+/// get_hq_results_since 한 번에 돌려주는 최대 결과 수
+const HQ_RESULTS_PAGE_SIZE: usize = 200;
+
+/// HQ 결과 순번 (get_hq_results_since의 커서)
+static HQ_RESULT_SEQ: AtomicU64 = AtomicU64::new(0);
 
 /// 썸네일 생성 요청
 #[derive(Debug, Clone)]
@@ -34,11 +62,161 @@ pub struct ThumbnailRequest {
 }
 
 /// 진행 상태
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ThumbnailProgress {
     pub completed: usize,
     pub total: usize,
     pub current_path: String,
+    /// 남은 시간과 처리 속도
+    #[serde(flatten, default)]
+    pub estimate: ProgressEstimate,
+}
+
+/// HQ 진행 상태 (thumbnail-hq-progress)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct HqProgress {
+    #[serde(flatten)]
+    pub progress: ThumbnailProgress,
+    /// 이 결과의 순번 (get_hq_results_since 커서)
+    pub cursor: u64,
+    /// 결과를 thumbnail-hq-completed로 보냈는지 (창이 숨겨져 있으면 진행 수만 보냄)
+    pub delivered: bool,
+}
+
+/// get_hq_results_since 응답
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct HqResultsPage {
+    pub results: Vec<ThumbnailResult>,
+    /// 다음 요청에 넘길 커서
+    pub cursor: u64,
+    pub has_more: bool,
+}
+
+/// 묶음 완료 이벤트 (thumbnail-completed-batch)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ThumbnailCompletedBatch {
+    pub results: Vec<ThumbnailResult>,
+    pub progress: ThumbnailProgress,
+}
+
+/// 썸네일 생성 실패 원인
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThumbnailFailureKind {
+    /// 파일이 없음 (이동/삭제)
+    Missing,
+    /// 다운로드되지 않은 클라우드 파일
+    CloudOnly,
+    /// 다른 프로세스가 사용 중 (재시도 후에도 실패)
+    Locked,
+    /// 디코딩 실패 (잘리거나 손상된 파일)
+    Corrupted,
+    /// 그 밖의 오류 (권한, 디스크 등)
+    Other,
+}
+
+impl ThumbnailFailureKind {
+    /// 오류 메시지와 파일 상태로 실패 원인 분류
+    pub fn classify(path: &str, error: &str) -> Self {
+        if !crate::fs_path::to_fs_path(path).exists() {
+            return ThumbnailFailureKind::Missing;
+        }
+        if error.starts_with("Cloud file not downloaded") {
+            return ThumbnailFailureKind::CloudOnly;
+        }
+        if file_lock::is_locked_error(error) {
+            return ThumbnailFailureKind::Locked;
+        }
+
+        let lower = error.to_lowercase();
+        let corrupted = ["decode", "failed to open image", "invalid", "parse", "eof", "truncated", "corrupt"];
+        if corrupted.iter().any(|keyword| lower.contains(keyword)) {
+            ThumbnailFailureKind::Corrupted
+        } else {
+            ThumbnailFailureKind::Other
+        }
+    }
+}
+
+/// 썸네일 생성 실패 항목 (thumbnail-failed 이벤트)
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ThumbnailFailure {
+    pub path: String,
+    pub kind: ThumbnailFailureKind,
+    pub error: String,
+}
+
+impl ThumbnailFailure {
+    pub fn new(path: &str, error: String) -> Self {
+        Self {
+            path: path.to_string(),
+            kind: ThumbnailFailureKind::classify(path, &error),
+            error,
+        }
+    }
+}
+
+/// 썸네일 완료 이벤트 묶음 전송
+/// 이미지가 많은 폴더에서 이미지마다 이벤트를 보내면 IPC가 밀리므로
+/// 일정 간격 또는 일정 개수마다 한 번에 전송
+struct CompletionBatcher {
+    pending: std::sync::Mutex<Vec<ThumbnailResult>>,
+    last_progress: std::sync::Mutex<Option<ThumbnailProgress>>,
+    batch_size: usize,
+}
+
+impl CompletionBatcher {
+    fn new(batch_size: usize) -> Self {
+        Self {
+            pending: std::sync::Mutex::new(Vec::new()),
+            last_progress: std::sync::Mutex::new(None),
+            batch_size,
+        }
+    }
+
+    /// 완료 결과 추가 (묶음이 가득 차면 바로 전송)
+    fn push(&self, app_handle: &AppHandle, result: ThumbnailResult, progress: ThumbnailProgress) {
+        let full = {
+            let mut pending = self.pending.lock().unwrap();
+            pending.push(result);
+            pending.len() >= self.batch_size
+        };
+        *self.last_progress.lock().unwrap() = Some(progress);
+
+        if full {
+            self.flush(app_handle);
+        }
+    }
+
+    /// 대기 중인 결과 전송
+    fn flush(&self, app_handle: &AppHandle) {
+        let results = std::mem::take(&mut *self.pending.lock().unwrap());
+        if results.is_empty() {
+            return;
+        }
+        let Some(progress) = self.last_progress.lock().unwrap().clone() else {
+            return;
+        };
+
+        let _ = app_handle.emit("thumbnail-progress", &progress);
+        let _ = app_handle.emit("thumbnail-completed-batch", ThumbnailCompletedBatch { results, progress });
+    }
+}
+
+/// 인덱스 범위 기준 우선순위 (낮을수록 먼저, 음수는 뷰포트 작업 예산 사용)
+/// 보이는 범위: 인덱스 순 음수, 버퍼 영역: 뷰포트와의 거리, 나머지: 버퍼 다음에 인덱스 순
+fn range_priority(index: usize, start: usize, end: usize, buffer: usize, total: usize) -> i32 {
+    let priority = if (start..end).contains(&index) {
+        index as i64 - total.max(end) as i64 - 1
+    } else {
+        let distance = if index < start { start - index - 1 } else { index - end };
+        if distance < buffer {
+            distance as i64
+        } else {
+            buffer as i64 + index as i64
+        }
+    };
+    priority.clamp(i32::MIN as i64, i32::MAX as i64) as i32
 }
 
 /// 썸네일 큐 관리자
@@ -47,6 +225,8 @@ pub struct ThumbnailQueueManager {
     queue: Arc<Mutex<VecDeque<ThumbnailRequest>>>,
     /// 완료된 썸네일들 (path -> result)
     completed: Arc<RwLock<HashMap<String, ThumbnailResult>>>,
+    /// 생성에 실패한 썸네일들 (path -> (인덱스, 실패 원인))
+    failed: Arc<RwLock<HashMap<String, (usize, ThumbnailFailure)>>>,
     /// 전체 이미지 수
     total: Arc<RwLock<usize>>,
     /// 일시정지 상태
@@ -62,6 +242,7 @@ impl ThumbnailQueueManager {
         Self {
             queue: Arc::new(Mutex::new(VecDeque::new())),
             completed: Arc::new(RwLock::new(HashMap::new())),
+            failed: Arc::new(RwLock::new(HashMap::new())),
             total: Arc::new(RwLock::new(0)),
             paused: Arc::new(RwLock::new(false)),
             is_processing: Arc::new(RwLock::new(false)),
@@ -78,6 +259,7 @@ impl ThumbnailQueueManager {
         // 기존 큐 초기화
         queue.clear();
         completed.clear();
+        self.failed.write().await.clear();
 
         // 전체 개수 설정
         *total = image_paths.len();
@@ -92,21 +274,15 @@ impl ThumbnailQueueManager {
         }
     }
 
-    /// 우선순위 업데이트 (뷰포트 내 이미지들)
-    pub async fn update_priorities(&self, visible_indices: Vec<usize>) {
+    /// 우선순위 업데이트 (가상 스크롤 그리드의 보이는 인덱스 범위 [start, end))
+    /// 보이는 범위를 먼저, 그다음 위아래 buffer개를 가까운 순으로 미리 생성
+    pub async fn update_visible_range(&self, start: usize, end: usize, buffer: usize) {
         let mut queue = self.queue.lock().await;
+        let total = *self.total.read().await;
 
-        // 뷰포트 내 이미지는 높은 우선순위 (음수로)
         let mut requests: Vec<_> = queue.drain(..).collect();
-
         for request in &mut requests {
-            if visible_indices.contains(&request.index) {
-                // 뷰포트 내: 음수 우선순위 (인덱스가 작을수록 더 높음)
-                request.priority = -(request.index as i32 + 1000);
-            } else {
-                // 뷰포트 밖: 인덱스 그대로
-                request.priority = request.index as i32;
-            }
+            request.priority = range_priority(request.index, start, end, buffer, total);
         }
 
         // 우선순위 순으로 정렬 (낮은 값이 먼저)
@@ -129,7 +305,6 @@ impl ThumbnailQueueManager {
     }
 
     /// 일시정지 상태 확인
-    #[allow(dead_code)]
     pub async fn is_paused(&self) -> bool {
         *self.paused.read().await
     }
@@ -153,6 +328,35 @@ impl ThumbnailQueueManager {
         completed.clone()
     }
 
+    /// 생성에 실패한 썸네일 목록
+    pub async fn get_failed(&self) -> Vec<ThumbnailFailure> {
+        let failed = self.failed.read().await;
+        failed.values().map(|(_, failure)| failure.clone()).collect()
+    }
+
+    /// 실패한 썸네일을 큐 앞에 다시 넣기, 다시 넣은 개수 반환
+    pub async fn retry_failed(&self) -> usize {
+        let mut failed: Vec<(usize, ThumbnailFailure)> = self.failed.write().await.drain().map(|(_, v)| v).collect();
+        failed.sort_by_key(|(index, _)| *index);
+
+        // 인덱스 순서대로 큐 앞에 추가
+        let mut queue = self.queue.lock().await;
+        for (index, failure) in failed.iter().rev() {
+            queue.push_front(ThumbnailRequest {
+                path: failure.path.clone(),
+                priority: *index as i32,
+                index: *index,
+            });
+        }
+        failed.len()
+    }
+
+    /// 대기 중인 작업을 모두 꺼내 반환 (종료 시, 워커는 진행 중인 작업만 마치고 멈춤)
+    pub async fn drain_pending(&self) -> Vec<String> {
+        let mut queue = self.queue.lock().await;
+        queue.drain(..).map(|request| request.path).collect()
+    }
+
     /// 큐에서 다음 작업 가져오기
     #[allow(dead_code)]
     async fn pop_next(&self) -> Option<ThumbnailRequest> {
@@ -162,6 +366,11 @@ impl ThumbnailQueueManager {
 
     /// 썸네일 생성 워커 시작
     pub async fn start_worker(&self) {
+        // 종료 중에는 새 작업을 시작하지 않음
+        if crate::shutdown::is_shutting_down() {
+            return;
+        }
+
         // 이미 실행 중이면 무시
         {
             let mut is_processing = self.is_processing.write().await;
@@ -173,6 +382,7 @@ impl ThumbnailQueueManager {
 
         let queue = Arc::clone(&self.queue);
         let completed = Arc::clone(&self.completed);
+        let failed = Arc::clone(&self.failed);
         let total = Arc::clone(&self.total);
         let paused = Arc::clone(&self.paused);
         let is_processing = Arc::clone(&self.is_processing);
@@ -180,19 +390,63 @@ impl ThumbnailQueueManager {
 
         // 워커 스레드 시작
         tokio::spawn(async move {
-            // CPU 코어의 25% 사용 (최소 1개)
-            let max_workers = (num_cpus::get() / 4).max(1);
+            // 동시 작업 수 (설정, 기본 CPU 코어의 25%)
+            let initial_settings = settings::current();
+            let mut max_workers = initial_settings.thumbnail_workers();
             let semaphore = Arc::new(tokio::sync::Semaphore::new(max_workers));
 
+            // 이미지가 많으면 완료 이벤트를 묶어서 전송 (적으면 이미지마다 전송)
+            let batcher = if *total.read().await >= initial_settings.thumbnail_batch_min_images {
+                let batcher = Arc::new(CompletionBatcher::new(initial_settings.thumbnail_batch_size));
+                let interval = Duration::from_millis(initial_settings.thumbnail_batch_interval_ms);
+                let flush_batcher = Arc::clone(&batcher);
+                let flush_app_handle = app_handle.clone();
+                let flush_task = tokio::spawn(async move {
+                    loop {
+                        sleep(interval).await;
+                        flush_batcher.flush(&flush_app_handle);
+                    }
+                });
+                Some((batcher, flush_task))
+            } else {
+                None
+            };
+
+            // 활동 패널에 표시하고 취소 요청을 받는 작업
+            let job = Arc::new(Job::start(&app_handle, JobKind::Thumbnails, "썸네일 생성", *total.read().await));
+            let estimator = Arc::new(std::sync::Mutex::new(EtaEstimator::new()));
+
             let mut handles = vec![];
 
             loop {
+                // 작업 취소 시 남은 요청을 버리고 진행 중인 작업만 마침
+                if job.is_cancelled() {
+                    queue.lock().await.clear();
+                    break;
+                }
+
                 // 일시정지 확인
                 if *paused.read().await {
                     tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
                     continue;
                 }
 
+                // 설정이 바뀌면 동시 작업 수 조정
+                let target_workers = settings::current().thumbnail_workers();
+                if target_workers > max_workers {
+                    semaphore.add_permits(target_workers - max_workers);
+                } else if target_workers < max_workers {
+                    // 줄어든 만큼 진행 중인 작업이 끝나는 대로 permit 회수
+                    let semaphore = Arc::clone(&semaphore);
+                    let excess = (max_workers - target_workers) as u32;
+                    tokio::spawn(async move {
+                        if let Ok(permits) = semaphore.acquire_many_owned(excess).await {
+                            permits.forget();
+                        }
+                    });
+                }
+                max_workers = target_workers;
+
                 // 큐에서 다음 작업 가져오기
                 let request = {
                     let mut q = queue.lock().await;
@@ -204,18 +458,39 @@ impl ThumbnailQueueManager {
                         let permit = match semaphore.clone().acquire_owned().await {
                             Ok(p) => p,
                             Err(e) => {
-                                eprintln!("Failed to acquire semaphore: {}", e);
+                                tracing::error!("Failed to acquire semaphore: {}", e);
                                 continue;
                             }
                         };
                         let completed_clone = Arc::clone(&completed);
+                        let failed_clone = Arc::clone(&failed);
                         let total_clone = Arc::clone(&total);
                         let app_handle_clone = app_handle.clone();
+                        let batcher = batcher.as_ref().map(|(batcher, _)| Arc::clone(batcher));
+                        let job = Arc::clone(&job);
+                        let estimator = Arc::clone(&estimator);
 
                         let handle = tokio::spawn(async move {
-                            // 썸네일 생성
-                            match thumbnail::generate_thumbnail(&app_handle_clone, &req.path).await {
-                                Ok(result) => {
+                            // 전역 예산 대기 (뷰포트 항목은 HQ보다 먼저)
+                            let class = if req.priority < 0 { WorkClass::LqVisible } else { WorkClass::LqRest };
+
+                            // 썸네일 생성 (사용 중인 파일은 예산을 반납하고 기다렸다가 재시도)
+                            // 소요 시간은 예산을 받은 뒤부터 (남은 시간 추정용)
+                            let generated = file_lock::retry_locked(|| async {
+                                let _work_permit = scheduler::acquire(class).await;
+                                let started = Instant::now();
+                                thumbnail::generate_thumbnail(&app_handle_clone, &req.path)
+                                    .await
+                                    .map(|result| (result, started.elapsed()))
+                            })
+                            .await;
+                            match generated {
+                                Ok((result, elapsed)) => {
+                                    // 저화질 내장 썸네일은 HQ를 먼저 생성해 교체
+                                    if result.is_low_quality {
+                                        request_hq_upgrade(req.path.clone()).await;
+                                    }
+
                                     // 완료 목록에 추가
                                     {
                                         let mut comp = completed_clone.write().await;
@@ -228,19 +503,34 @@ impl ThumbnailQueueManager {
                                         comp.len()
                                     };
                                     let total_count = *total_clone.read().await;
+                                    let estimate = {
+                                        let mut estimator = estimator.lock().unwrap();
+                                        estimator.record(&result.source, elapsed);
+                                        estimator.estimate(total_count.saturating_sub(completed_count))
+                                    };
 
                                     let progress = ThumbnailProgress {
                                         completed: completed_count,
                                         total: total_count,
                                         current_path: req.path.clone(),
+                                        estimate,
                                     };
-
-                                    // Tauri 이벤트 전송
-                                    let _ = app_handle_clone.emit("thumbnail-progress", &progress);
-                                    let _ = app_handle_clone.emit("thumbnail-completed", &result);
+                                    job.set_progress(completed_count, total_count);
+
+                                    // Tauri 이벤트 전송 (대용량 폴더는 묶어서)
+                                    match batcher {
+                                        Some(batcher) => batcher.push(&app_handle_clone, result, progress),
+                                        None => {
+                                            let _ = app_handle_clone.emit("thumbnail-progress", &progress);
+                                            let _ = app_handle_clone.emit("thumbnail-completed", &result);
+                                        }
+                                    }
                                 }
                                 Err(e) => {
-                                    eprintln!("Failed to generate thumbnail for {}: {}", req.path, e);
+                                    tracing::warn!("Failed to generate thumbnail for {}: {}", req.path, e);
+                                    let failure = ThumbnailFailure::new(&req.path, e);
+                                    failed_clone.write().await.insert(req.path.clone(), (req.index, failure.clone()));
+                                    let _ = app_handle_clone.emit("thumbnail-failed", &failure);
                                 }
                             }
 
@@ -261,6 +551,19 @@ impl ThumbnailQueueManager {
                 let _ = handle.await;
             }
 
+            // 남은 완료 이벤트 전송 (all-completed보다 먼저)
+            if let Some((batcher, flush_task)) = batcher {
+                flush_task.abort();
+                batcher.flush(&app_handle);
+            }
+
+            // 다음에 같은 폴더를 열 때 바로 표시하도록 완료 목록 기록
+            let results: Vec<ThumbnailResult> = completed.read().await.values().cloned().collect();
+            let warm_app_handle = app_handle.clone();
+            tokio::task::spawn_blocking(move || crate::thumbnail_warm::save(&warm_app_handle, results));
+
+            drop(job);
+
             // 처리 완료 플래그
             *is_processing.write().await = false;
 
@@ -289,13 +592,13 @@ pub async fn load_existing_hq_thumbnails(app_handle: AppHandle, image_paths: Vec
                         completed,
                         total,
                         current_path: path.clone(),
+                        estimate: ProgressEstimate::default(),
                     };
 
-                    let _ = app_handle.emit("thumbnail-hq-progress", &progress);
-                    let _ = app_handle.emit("thumbnail-hq-completed", &result);
+                    emit_hq_result(&app_handle, &result, progress);
                 }
                 Err(e) => {
-                    eprintln!("Failed to load existing HQ thumbnail for {}: {}", path, e);
+                    tracing::warn!("Failed to load existing HQ thumbnail for {}: {}", path, e);
                 }
             }
 
@@ -308,135 +611,372 @@ pub async fn load_existing_hq_thumbnails(app_handle: AppHandle, image_paths: Vec
     });
 }
 
-/// 고화질 DCT 썸네일 생성 워커 (유휴 상태에 따라 동적 병렬 처리)
-/// - 비유휴 상태: 뷰포트 우선 1개씩 순차 처리
-/// - 유휴 상태: 인덱스 순서로 3개 병렬 처리
+// 메인 창이 최소화되었거나 숨겨졌는지 (트레이로 숨김)
+fn is_main_window_hidden(app_handle: &AppHandle) -> bool {
+    use tauri::Manager;
+
+    app_handle.get_webview_window("main").is_some_and(|window| {
+        window.is_minimized().unwrap_or(false) || !window.is_visible().unwrap_or(true)
+    })
+}
+
+/// HQ 결과 전송 (창이 숨겨져 있으면 base64 결과는 보내지 않고 진행 수만 전송)
+fn emit_hq_result(app_handle: &AppHandle, result: &ThumbnailResult, progress: ThumbnailProgress) {
+    let cursor = HQ_RESULT_SEQ.fetch_add(1, Ordering::SeqCst) + 1;
+    {
+        let mut log = HQ_RESULT_LOG.lock().unwrap();
+        log.push_back((cursor, result.path.clone()));
+        if log.len() > HQ_RESULT_LOG_LIMIT {
+            log.pop_front();
+        }
+    }
+
+    let delivered = !is_main_window_hidden(app_handle);
+    let _ = app_handle.emit("thumbnail-hq-progress", &HqProgress { progress, cursor, delivered });
+    if delivered {
+        let _ = app_handle.emit("thumbnail-hq-completed", result);
+    }
+}
+
+/// 커서 이후에 완료된 HQ 결과 (창이 다시 보일 때 놓친 결과 가져오기, 캐시에서 읽음)
+pub async fn get_hq_results_since(app_handle: &AppHandle, cursor: u64) -> HqResultsPage {
+    let (entries, has_more) = {
+        let log = HQ_RESULT_LOG.lock().unwrap();
+        let start = log.partition_point(|(seq, _)| *seq <= cursor);
+        let entries: Vec<(u64, String)> = log.range(start..).take(HQ_RESULTS_PAGE_SIZE).cloned().collect();
+        let has_more = log.len() - start > entries.len();
+        (entries, has_more)
+    };
+
+    let next_cursor = entries.last().map_or(cursor, |(seq, _)| *seq);
+    let mut results = Vec::with_capacity(entries.len());
+    for (_, path) in entries {
+        match thumbnail::generate_hq_thumbnail(app_handle, &path).await {
+            Ok(result) => results.push(result),
+            Err(e) => tracing::debug!("Failed to reload HQ thumbnail for {}: {}", path, e),
+        }
+    }
+
+    HqResultsPage {
+        results,
+        cursor: next_cursor,
+        has_more,
+    }
+}
+
+/// HQ 생성 우선순위 (작을수록 먼저)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum HqTier {
+    /// 뷰포트에 보이는 항목 (유휴 대기 없이 처리)
+    Viewport,
+    /// 저화질 EXIF 썸네일로 표시된 항목
+    Upgrade,
+    Rest,
+}
+
+/// HQ 생성 대기열 (우선순위 역순으로 보관해 끝에서 꺼냄)
+/// 우선순위가 바뀔 때만 다시 정렬하므로 항목을 꺼낼 때마다 전체를 훑지 않음
+struct HqQueue {
+    items: Vec<(HqTier, usize, String)>,
+    version: Option<u64>,
+}
+
+impl HqQueue {
+    fn new(image_paths: Vec<String>) -> Self {
+        let mut items: Vec<_> = image_paths
+            .into_iter()
+            .enumerate()
+            .map(|(index, path)| (HqTier::Rest, index, path))
+            .collect();
+        items.reverse();
+        Self { items, version: None }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// 남은 경로 (원래 순서, 진행 기록용)
+    fn paths(&self) -> Vec<String> {
+        let mut items: Vec<_> = self.items.iter().map(|(_, index, path)| (*index, path.clone())).collect();
+        items.sort_unstable_by_key(|(index, _)| *index);
+        items.into_iter().map(|(_, path)| path).collect()
+    }
+
+    /// 뷰포트/저화질 경로가 바뀌었으면 다시 정렬
+    async fn refresh(&mut self) {
+        let version = HQ_PRIORITY_VERSION.load(Ordering::SeqCst);
+        if self.version == Some(version) {
+            return;
+        }
+        self.version = Some(version);
+
+        let viewport = HQ_VIEWPORT_PATHS.read().await;
+        let upgrades = HQ_UPGRADE_PATHS.read().await;
+        for (tier, _, path) in &mut self.items {
+            *tier = if viewport.contains(path) {
+                HqTier::Viewport
+            } else if upgrades.contains(path) {
+                HqTier::Upgrade
+            } else {
+                HqTier::Rest
+            };
+        }
+        self.items.sort_unstable_by_key(|(tier, index, _)| std::cmp::Reverse((*tier, *index)));
+    }
+
+    /// 다음 항목이 유휴 대기 없이 처리할 뷰포트 항목인지
+    fn next_is_visible(&self) -> bool {
+        self.items.last().is_some_and(|(tier, _, _)| *tier == HqTier::Viewport)
+    }
+
+    /// 우선순위 순으로 최대 count개 꺼내기 (only_visible이면 뷰포트 항목만)
+    async fn take(&mut self, count: usize, only_visible: bool) -> Vec<String> {
+        let mut batch = Vec::new();
+        while batch.len() < count && (!only_visible || self.next_is_visible()) {
+            let Some((tier, _, path)) = self.items.pop() else {
+                break;
+            };
+            if tier == HqTier::Upgrade {
+                HQ_UPGRADE_PATHS.write().await.remove(&path);
+            }
+            batch.push(path);
+        }
+        batch
+    }
+}
+
+// HQ 썸네일 하나 생성 후 진행 이벤트 전송
+async fn generate_hq_item(
+    app_handle: AppHandle,
+    path: String,
+    completed: Arc<AtomicUsize>,
+    total: usize,
+    job: Arc<Job>,
+    estimator: Arc<std::sync::Mutex<EtaEstimator>>,
+) {
+    let generated = file_lock::retry_locked(|| async {
+        let _permit = scheduler::acquire(WorkClass::Hq).await;
+        let started = Instant::now();
+        thumbnail::generate_hq_thumbnail(&app_handle, &path)
+            .await
+            .map(|result| (result, started.elapsed()))
+    })
+    .await;
+    match generated {
+        Ok((result, elapsed)) => {
+            let count = completed.fetch_add(1, Ordering::SeqCst) + 1;
+            job.set_progress(count, total);
+            let estimate = {
+                let mut estimator = estimator.lock().unwrap();
+                estimator.record(&result.source, elapsed);
+                estimator.estimate(total.saturating_sub(count))
+            };
+            let progress = ThumbnailProgress {
+                completed: count,
+                total,
+                current_path: path.clone(),
+                estimate,
+            };
+            emit_hq_result(&app_handle, &result, progress);
+        }
+        Err(e) => {
+            tracing::warn!("Failed to generate HQ thumbnail for {}: {}", path, e);
+        }
+    }
+}
+
+/// 고화질 DCT 썸네일 생성 워커 (우선순위 대기열, 유휴 상태에 따라 동적 병렬 처리)
+/// - 뷰포트 항목: 유휴 여부와 관계없이 바로 병렬 처리
+/// - 그 밖의 항목: 유휴 상태면 병렬, 비유휴 상태면 1개씩 순차 처리 (저화질 교체 항목 먼저)
 pub async fn start_hq_thumbnail_worker(app_handle: AppHandle, image_paths: Vec<String>) {
-    let total = image_paths.len();
+    // 종료 중에는 새 작업을 시작하지 않음 (취소 플래그도 유지)
+    if crate::shutdown::is_shutting_down() {
+        return;
+    }
 
     // 새 작업 시작 전 취소 플래그 초기화
     HQ_GENERATION_CANCELLED.store(false, Ordering::SeqCst);
 
+    HQ_RUNNING.store(true, Ordering::SeqCst);
+    HQ_PENDING.lock().unwrap().clear();
+
     tokio::spawn(async move {
+        // 중단된 진행 기록이 있으면 남은 항목만 이어서 처리
+        let begin_app_handle = app_handle.clone();
+        let (mut progress_record, image_paths) =
+            tokio::task::spawn_blocking(move || hq_progress::begin(&begin_app_handle, image_paths))
+                .await
+                .unwrap_or_default();
+        *HQ_CURRENT_TOKEN.lock().unwrap() = progress_record.as_ref().map(|record| record.token.clone());
+        let mut last_progress_save = Instant::now();
+
+        let total = image_paths.len();
         let completed = Arc::new(AtomicUsize::new(0));
-
-        // 이미지 경로와 인덱스를 함께 관리
-        let mut remaining: Vec<(usize, String)> = image_paths.into_iter().enumerate().collect();
-
-        while !remaining.is_empty() {
+        let job = Arc::new(Job::start_with_cancel(
+            &app_handle,
+            JobKind::HqThumbnails,
+            "고화질 썸네일 생성",
+            total,
+            cancel_hq_thumbnail_generation,
+        ));
+        let estimator = Arc::new(std::sync::Mutex::new(EtaEstimator::new()));
+
+        let mut queue = HqQueue::new(image_paths);
+        let mut deferred = false;
+
+        while !queue.is_empty() {
             // 취소 확인
             if HQ_GENERATION_CANCELLED.load(Ordering::SeqCst) {
-                eprintln!("HQ thumbnail generation cancelled");
+                tracing::info!("HQ thumbnail generation cancelled");
+                let pending = queue.paths();
+                if let Some(record) = progress_record.as_mut() {
+                    hq_progress::update(&app_handle, record, pending.clone(), HqGenerationState::Interrupted);
+                }
+                *HQ_PENDING.lock().unwrap() = pending;
+                *HQ_CURRENT_TOKEN.lock().unwrap() = None;
+                HQ_RUNNING.store(false, Ordering::SeqCst);
+                job.finish_cancelled();
                 let _ = app_handle.emit("thumbnail-hq-cancelled", true);
                 return;
             }
 
-            let is_idle = idle_detector::should_generate_hq(IDLE_THRESHOLD_MS);
-
-            if is_idle {
-                // 유휴 상태: 뷰포트 항목 우선, 최대 CPU 코어/2개 병렬 처리
-                let viewport = HQ_VIEWPORT_PATHS.read().await;
-                let batch_size = get_hq_max_concurrent().min(remaining.len());
-
-                let mut batch = Vec::new();
-
-                // 1. 먼저 뷰포트에 있는 항목들을 배치에 추가 (경로 기반)
-                let mut i = 0;
-                while i < remaining.len() && batch.len() < batch_size {
-                    if viewport.contains(&remaining[i].1) {
-                        batch.push(remaining.remove(i));
-                    } else {
-                        i += 1;
-                    }
+            // 진행 기록 주기적 저장 (비정상 종료 시에도 이어서 처리)
+            if last_progress_save.elapsed() >= HQ_PROGRESS_SAVE_INTERVAL {
+                if let Some(record) = progress_record.as_mut() {
+                    hq_progress::update(&app_handle, record, queue.paths(), HqGenerationState::Running);
                 }
+                last_progress_save = Instant::now();
+            }
 
-                // 2. 배치가 아직 안 찼으면 나머지를 앞에서부터 채움
-                while batch.len() < batch_size && !remaining.is_empty() {
-                    batch.push(remaining.remove(0));
-                }
-
-                drop(viewport); // RwLock 해제
-
-                let mut tasks = Vec::new();
-                for (_index, path) in batch {
-                    let app_handle = app_handle.clone();
-                    let completed = Arc::clone(&completed);
-
-                    let task = tokio::spawn(async move {
-                        match thumbnail::generate_hq_thumbnail(&app_handle, &path).await {
-                            Ok(result) => {
-                                let count = completed.fetch_add(1, Ordering::SeqCst) + 1;
-                                let progress = ThumbnailProgress {
-                                    completed: count,
-                                    total,
-                                    current_path: path.clone(),
-                                };
-                                let _ = app_handle.emit("thumbnail-hq-progress", &progress);
-                                let _ = app_handle.emit("thumbnail-hq-completed", &result);
-                            }
-                            Err(e) => {
-                                eprintln!("Failed to generate HQ thumbnail for {}: {}", path, e);
-                            }
-                        }
-                    });
-
-                    tasks.push(task);
-                }
+            // 일시정지 중이면 재개될 때까지 대기 (취소는 계속 확인)
+            if HQ_PAUSED.load(Ordering::SeqCst) {
+                sleep(Duration::from_millis(200)).await;
+                continue;
+            }
 
-                // 배치 완료 대기
-                for task in tasks {
-                    let _ = task.await;
+            // 설정은 매 배치마다 다시 읽음 (실행 중 변경 반영)
+            let current_settings = settings::current();
+
+            // 배터리/발열 상태면 전원 연결 또는 냉각될 때까지 보류
+            if idle_detector::should_defer_hq(
+                current_settings.defer_hq_on_battery,
+                current_settings.defer_hq_on_thermal,
+            ) {
+                if !deferred {
+                    deferred = true;
+                    tracing::info!("HQ thumbnail generation deferred (battery/thermal)");
+                    let _ = app_handle.emit("thumbnail-hq-deferred", true);
                 }
-            } else {
-                // 비유휴 상태: 뷰포트에 있는 것 우선, 1개씩 순차 처리
-                let viewport = HQ_VIEWPORT_PATHS.read().await;
-
-                // 뷰포트에 있는 항목 찾기 (경로 기반)
-                let viewport_item_pos = remaining.iter().position(|(_idx, path)| viewport.contains(path));
-
-                drop(viewport); // RwLock 해제
-
-                let item = if let Some(pos) = viewport_item_pos {
-                    // 뷰포트 항목 발견 - 우선 처리
-                    remaining.remove(pos)
-                } else {
-                    // 뷰포트 항목이 remaining에 없음 -> 순차 처리
-                    remaining.remove(0)
-                };
+                sleep(Duration::from_secs(2)).await;
+                continue;
+            }
+            if deferred {
+                deferred = false;
+                tracing::info!("HQ thumbnail generation resumed");
+                let _ = app_handle.emit("thumbnail-hq-deferred", false);
+            }
 
-                let (_index, path) = item;
+            // 뷰포트가 바뀌었으면 다시 정렬
+            queue.refresh().await;
 
-                // 1개씩 처리
-                match thumbnail::generate_hq_thumbnail(&app_handle, &path).await {
-                    Ok(result) => {
-                        let count = completed.fetch_add(1, Ordering::SeqCst) + 1;
-                        let progress = ThumbnailProgress {
-                            completed: count,
-                            total,
-                            current_path: path.clone(),
-                        };
-                        let _ = app_handle.emit("thumbnail-hq-progress", &progress);
-                        let _ = app_handle.emit("thumbnail-hq-completed", &result);
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to generate HQ thumbnail for {}: {}", path, e);
-                    }
-                }
+            let max_concurrent = current_settings.hq_max_concurrent();
+            let visible = queue.next_is_visible();
+            let is_idle = idle_detector::should_generate_hq(current_settings.idle_threshold_ms);
+            let batch = if visible {
+                queue.take(max_concurrent, true).await
+            } else if is_idle {
+                queue.take(max_concurrent, false).await
+            } else {
+                // 비유휴 상태: 1개씩 처리 (저화질/뷰어 작업이 기다리면 양보)
+                queue.take(1, false).await
+            };
+
+            let tasks: Vec<_> = batch
+                .into_iter()
+                .map(|path| {
+                    tokio::spawn(generate_hq_item(
+                        app_handle.clone(),
+                        path,
+                        Arc::clone(&completed),
+                        total,
+                        Arc::clone(&job),
+                        Arc::clone(&estimator),
+                    ))
+                })
+                .collect();
+
+            // 배치 완료 대기
+            for task in tasks {
+                let _ = task.await;
+            }
 
-                // UI 응답성을 위한 짧은 대기
+            // 비유휴 상태의 순차 처리는 UI 응답성을 위해 짧게 대기
+            if !visible && !is_idle {
                 sleep(Duration::from_millis(10)).await;
             }
         }
 
+        if let Some(record) = progress_record.as_mut() {
+            hq_progress::update(&app_handle, record, Vec::new(), HqGenerationState::Completed);
+        }
+        *HQ_CURRENT_TOKEN.lock().unwrap() = None;
+        HQ_RUNNING.store(false, Ordering::SeqCst);
+
+        // 캐시 용량 제한 적용 (설정된 경우)
+        if let Some(max_mb) = settings::current().cache_max_mb {
+            let app_handle = app_handle.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                thumbnail_cache::enforce_size_limit(&app_handle, max_mb * 1024 * 1024)
+            })
+            .await;
+            if let Ok(Err(e)) = result {
+                tracing::warn!("Failed to enforce thumbnail cache limit: {}", e);
+            }
+        }
+
         // 완료 이벤트 전송
         if !HQ_GENERATION_CANCELLED.load(Ordering::SeqCst) {
+            job.finish(Ok(()));
             let _ = app_handle.emit("thumbnail-hq-all-completed", true);
         } else {
+            job.finish_cancelled();
             let _ = app_handle.emit("thumbnail-hq-cancelled", true);
         }
     });
 }
 
+/// 저화질 EXIF 썸네일로 표시된 경로의 HQ 생성 우선 요청
+pub async fn request_hq_upgrade(path: String) {
+    HQ_UPGRADE_PATHS.write().await.insert(path);
+    HQ_PRIORITY_VERSION.fetch_add(1, Ordering::SeqCst);
+}
+
+/// HQ 썸네일 워커 실행 중 여부
+pub fn is_hq_running() -> bool {
+    HQ_RUNNING.load(Ordering::SeqCst)
+}
+
+/// 실행 중인 HQ 생성 작업 식별자
+pub fn current_hq_token() -> Option<String> {
+    HQ_CURRENT_TOKEN.lock().unwrap().clone()
+}
+
+/// 취소된 HQ 작업의 남은 경로 가져오기
+pub fn take_hq_pending() -> Vec<String> {
+    std::mem::take(&mut *HQ_PENDING.lock().unwrap())
+}
+
+/// HQ 썸네일 생성 일시정지/재개
+pub fn set_hq_paused(paused: bool) {
+    HQ_PAUSED.store(paused, Ordering::SeqCst);
+}
+
+/// HQ 썸네일 생성 일시정지 여부
+pub fn is_hq_paused() -> bool {
+    HQ_PAUSED.load(Ordering::SeqCst)
+}
+
 /// 고화질 썸네일 생성 취소
 pub fn cancel_hq_thumbnail_generation() {
     HQ_GENERATION_CANCELLED.store(true, Ordering::SeqCst);
@@ -447,4 +987,23 @@ pub async fn update_hq_viewport_paths(paths: Vec<String>) {
     let mut viewport = HQ_VIEWPORT_PATHS.write().await;
     viewport.clear();
     viewport.extend(paths.iter().cloned());
+    HQ_PRIORITY_VERSION.fetch_add(1, Ordering::SeqCst);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_priority() {
+        // 100장 중 40..50이 보이고 위아래 5장 버퍼
+        let priority = |index| range_priority(index, 40, 50, 5, 100);
+        assert!(priority(40) < 0 && priority(49) < 0);
+        assert!(priority(40) < priority(49));
+        assert!(priority(49) < priority(50));
+        assert_eq!(priority(50), priority(39));
+        assert!(priority(54) < priority(55));
+        assert!(priority(35) < priority(0));
+        assert!(priority(0) < priority(99));
+    }
 }
@@ -0,0 +1,146 @@
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{Emitter, Manager};
+
+use crate::thumbnail::{self, ExifMetadata, ThumbnailResult, ThumbnailSource};
+
+/// 폴더별 완료 썸네일 기록 (같은 폴더를 다시 열면 stat 없이 캐시에서 바로 표시)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WarmManifest {
+    folder: String,
+    entries: Vec<WarmEntry>,
+}
+
+/// 완료된 썸네일의 캐시 위치와 크기
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WarmEntry {
+    path: String,
+    cache_key: String,
+    width: u32,
+    height: u32,
+    has_alpha: bool,
+    /// 캐시 썸네일은 회전하지 않고 저장되므로 표시할 때 방향 정보 필요
+    exif_metadata: Option<ExifMetadata>,
+}
+
+// 기록 파일 경로 (폴더 경로 해시)
+fn get_manifest_path(app: &tauri::AppHandle, folder: &str) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join("warm_cache");
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create warm cache directory: {}", e))?;
+
+    let key = blake3::hash(folder.as_bytes()).to_hex();
+    Ok(dir.join(format!("{}.json", &key[..16])))
+}
+
+fn load(app: &tauri::AppHandle, folder: &str) -> Option<WarmManifest> {
+    let path = get_manifest_path(app, folder).ok()?;
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str::<WarmManifest>(&content)
+        .ok()
+        .filter(|manifest| manifest.folder == folder)
+}
+
+// 캐시 파일이 있는 경로만 기록 (EXIF 내장 썸네일만 쓴 경로는 제외)
+fn warm_entry(app: &tauri::AppHandle, result: &ThumbnailResult) -> Option<WarmEntry> {
+    let path = result.path.as_str();
+    let mtime = thumbnail::get_file_mtime(&crate::fs_path::to_fs_string(path)).ok()?;
+    let cache_key = thumbnail::generate_cache_key(path, mtime);
+    let webp_data = fs::read(thumbnail::get_cache_path(app, &cache_key).ok()?).ok()?;
+    let (width, height) = thumbnail::webp_dimensions(&webp_data).ok()?;
+    Some(WarmEntry {
+        path: path.to_string(),
+        cache_key,
+        width,
+        height,
+        has_alpha: thumbnail::webp_has_alpha(&webp_data),
+        exif_metadata: result.exif_metadata.clone(),
+    })
+}
+
+/// 완료된 썸네일을 폴더별로 기록 (캐시 키 계산은 병렬)
+pub fn save(app: &tauri::AppHandle, results: Vec<ThumbnailResult>) {
+    let mut folders: BTreeMap<String, Vec<ThumbnailResult>> = BTreeMap::new();
+    for result in results {
+        if let Some(parent) = Path::new(&result.path).parent() {
+            folders.entry(parent.to_string_lossy().to_string()).or_default().push(result);
+        }
+    }
+
+    for (folder, results) in folders {
+        let entries: Vec<WarmEntry> = results.par_iter().filter_map(|result| warm_entry(app, result)).collect();
+        if entries.is_empty() {
+            continue;
+        }
+
+        let count = entries.len();
+        let manifest = WarmManifest { folder, entries };
+        let result = get_manifest_path(app, &manifest.folder).and_then(|path| {
+            let json = serde_json::to_string(&manifest)
+                .map_err(|e| format!("Failed to serialize warm cache: {}", e))?;
+            crate::shutdown::write_atomic(&path, json.as_bytes())
+                .map_err(|e| format!("Failed to write warm cache: {}", e))
+        });
+        match result {
+            Ok(()) => tracing::debug!("Saved warm cache for {} ({} thumbnails)", manifest.folder, count),
+            Err(e) => tracing::warn!("Failed to save warm cache for {}: {}", manifest.folder, e),
+        }
+    }
+}
+
+/// 이전에 완료된 썸네일을 캐시에서 바로 읽기 (요청 목록에 있는 경로만, 병렬)
+/// 원본이 바뀐 경우는 이어지는 일반 생성에서 새 썸네일로 교체됨
+pub fn restore(app: &tauri::AppHandle, image_paths: &[String]) -> Vec<ThumbnailResult> {
+    let Some(folder) = crate::hq_progress::folder_of(image_paths) else {
+        return Vec::new();
+    };
+    let Some(manifest) = load(app, &folder) else {
+        return Vec::new();
+    };
+    let Ok(cache_dir) = thumbnail::get_cache_dir(app) else {
+        return Vec::new();
+    };
+
+    let requested: HashSet<&String> = image_paths.iter().collect();
+    manifest
+        .entries
+        .par_iter()
+        .filter(|entry| requested.contains(&entry.path))
+        .filter_map(|entry| {
+            let webp_data = fs::read(cache_dir.join(format!("{}.webp", entry.cache_key))).ok()?;
+            Some(ThumbnailResult {
+                path: entry.path.clone(),
+                thumbnail_base64: thumbnail::encode_to_base64(&webp_data),
+                width: entry.width,
+                height: entry.height,
+                source: ThumbnailSource::Cache,
+                exif_metadata: entry.exif_metadata.clone(),
+                has_alpha: entry.has_alpha,
+                is_low_quality: false,
+            })
+        })
+        .collect()
+}
+
+/// 이전 완료 썸네일을 thumbnail-warm-cache 이벤트로 전송 (생성 시작 전, 묶음 크기 단위)
+pub async fn emit(app: &tauri::AppHandle, image_paths: Vec<String>) {
+    let restore_app = app.clone();
+    let Ok(results) = tokio::task::spawn_blocking(move || restore(&restore_app, &image_paths)).await else {
+        return;
+    };
+    if results.is_empty() {
+        return;
+    }
+
+    tracing::debug!("Restored {} thumbnails from warm cache", results.len());
+    let batch_size = crate::settings::current().thumbnail_batch_size;
+    for chunk in results.chunks(batch_size) {
+        let _ = app.emit("thumbnail-warm-cache", chunk);
+    }
+}
@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+
+use crate::filtering::ImageFilter;
+use crate::metadata_store::{ImageRecord, MetadataStore};
+use crate::thumbnail::generate_cache_key;
+
+/// 구간마다 보여줄 대표 썸네일 수
+const REPRESENTATIVES_PER_BUCKET: usize = 4;
+
+/// 타임라인 구간 단위
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimelineBuckets {
+    Day,
+    Month,
+    Year,
+}
+
+impl TimelineBuckets {
+    /// date_taken("YYYY-MM-DD HH:MM:SS")에서 구간 키로 쓰는 앞부분 길이
+    fn key_len(self) -> usize {
+        match self {
+            TimelineBuckets::Day => 10,
+            TimelineBuckets::Month => 7,
+            TimelineBuckets::Year => 4,
+        }
+    }
+}
+
+/// 대표 썸네일 (cache_key로 썸네일 캐시 조회)
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineThumbnail {
+    pub path: String,
+    pub cache_key: String,
+    #[serde(skip)]
+    rating: i32,
+}
+
+/// 타임라인 구간 하나 ("2024-05-31", "2024-05", "2024")
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineBucket {
+    pub key: String,
+    pub count: usize,
+    /// 별점 높은 순, 같으면 최근 촬영 순
+    pub representatives: Vec<TimelineThumbnail>,
+}
+
+// 별점이 높은 이미지를 대표로 (최근 촬영부터 들어오므로 별점이 같으면 먼저 온 것 유지)
+fn offer_representative(representatives: &mut Vec<TimelineThumbnail>, record: &ImageRecord) {
+    let position = representatives
+        .iter()
+        .position(|thumbnail| thumbnail.rating < record.rating)
+        .unwrap_or(representatives.len());
+    if position >= REPRESENTATIVES_PER_BUCKET {
+        return;
+    }
+    representatives.insert(position, TimelineThumbnail {
+        path: record.path.clone(),
+        cache_key: generate_cache_key(&record.path, record.mtime),
+        rating: record.rating,
+    });
+    representatives.truncate(REPRESENTATIVES_PER_BUCKET);
+}
+
+/// 인덱싱된 촬영일을 구간별로 집계 (최근 구간부터, 촬영일 없는 이미지는 제외)
+pub fn get_timeline(
+    store: &MetadataStore,
+    buckets: TimelineBuckets,
+    filter: Option<&ImageFilter>,
+) -> Result<Vec<TimelineBucket>, String> {
+    let mut timeline: Vec<TimelineBucket> = Vec::new();
+
+    store.for_each_dated(|record| {
+        if filter.is_some_and(|filter| !filter.matches(&record)) {
+            return;
+        }
+        let Some(key) = record.date_taken.as_deref().and_then(|date| date.get(..buckets.key_len())) else {
+            return;
+        };
+
+        // 촬영일 내림차순이므로 같은 구간은 연속으로 들어옴
+        if timeline.last().is_none_or(|bucket| bucket.key != key) {
+            timeline.push(TimelineBucket {
+                key: key.to_string(),
+                count: 0,
+                representatives: Vec::new(),
+            });
+        }
+        if let Some(bucket) = timeline.last_mut() {
+            bucket.count += 1;
+            offer_representative(&mut bucket.representatives, &record);
+        }
+    })?;
+
+    Ok(timeline)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(path: &str, rating: i32) -> ImageRecord {
+        ImageRecord {
+            path: path.to_string(),
+            folder: String::new(),
+            file_name: path.to_string(),
+            extension: "jpg".to_string(),
+            file_size: 0,
+            mtime: 0,
+            date_taken: None,
+            camera_make: None,
+            camera_model: None,
+            lens_model: None,
+            focal_length: None,
+            aperture: None,
+            iso: None,
+            width: None,
+            height: None,
+            orientation: 1,
+            rating,
+        }
+    }
+
+    #[test]
+    fn test_offer_representative() {
+        let mut representatives = Vec::new();
+        for (path, rating) in [("a", 0), ("b", 3), ("c", 0), ("d", 5), ("e", 3), ("f", 1)] {
+            offer_representative(&mut representatives, &record(path, rating));
+        }
+        let paths: Vec<&str> = representatives.iter().map(|t| t.path.as_str()).collect();
+        assert_eq!(paths, ["d", "b", "e", "f"]);
+    }
+}
@@ -0,0 +1,417 @@
+use exif::{Field, In, Tag, Value};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::BufReader;
+use std::path::Path;
+use xmp_toolkit::{xmp_ns, OpenFileOptions, ToStringOptions, XmpFile, XmpMeta};
+
+use crate::color_profile::{self, PixelLayout};
+use crate::metadata_strip::{push_segment, rewrite_jpeg_metadata, EXIF_HEADER, XMP_HEADER};
+use crate::{jpeg_lossless, thumbnail};
+
+/// 수평 보정 최대 각도
+const MAX_STRAIGHTEN_DEGREES: f32 = 45.0;
+
+/// 자른 이미지에서 의미가 없어지는 EXIF 구조 태그 (크기, 스트립/타일 위치, 내장 썸네일)
+const EXIF_STRUCTURE_TAGS: &[Tag] = &[
+    Tag::ImageWidth,
+    Tag::ImageLength,
+    Tag::BitsPerSample,
+    Tag::Compression,
+    Tag::PhotometricInterpretation,
+    Tag::StripOffsets,
+    Tag::SamplesPerPixel,
+    Tag::RowsPerStrip,
+    Tag::StripByteCounts,
+    Tag::PlanarConfiguration,
+    Tag::JPEGInterchangeFormat,
+    Tag::JPEGInterchangeFormatLength,
+];
+
+/// 자를 영역 (방향 적용 후 이미지 기준 픽셀, 수평 보정 시 회전된 이미지 기준)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CropRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// 자르기 옵션
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CropOptions {
+    /// JPEG을 회전 없이 MCU 경계에 맞춰 자르면 재인코딩하지 않음 (맞지 않으면 재인코딩)
+    pub lossless: bool,
+    /// 재인코딩 품질 (JPEG/WebP, 1-100)
+    pub quality: u8,
+}
+
+impl Default for CropOptions {
+    fn default() -> Self {
+        Self { lossless: true, quality: 92 }
+    }
+}
+
+/// 자르기 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct CropResult {
+    pub source: String,
+    pub output: String,
+    pub width: u32,
+    pub height: u32,
+    /// 무손실로 잘랐는지
+    pub lossless: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Jpeg,
+    Png,
+    Webp,
+}
+
+fn output_format(path: &Path) -> Result<OutputFormat, String> {
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "jpg" | "jpeg" => Ok(OutputFormat::Jpeg),
+        "png" => Ok(OutputFormat::Png),
+        "webp" => Ok(OutputFormat::Webp),
+        _ => Err(format!("Unsupported output format: {}", extension)),
+    }
+}
+
+/// 자른 이미지용 EXIF (구조 태그/내장 썸네일 제거, 크기 갱신, 재인코딩하면 방향 초기화)
+/// 다시 쓸 수 없으면 None (EXIF 없이 저장)
+fn crop_exif(tiff: &[u8], width: u32, height: u32, reset_orientation: bool) -> Option<Vec<u8>> {
+    let exif = exif::Reader::new().read_raw(tiff.to_vec()).ok()?;
+
+    let mut fields: Vec<Field> = exif
+        .fields()
+        .filter(|field| field.ifd_num == In::PRIMARY && !EXIF_STRUCTURE_TAGS.contains(&field.tag))
+        .cloned()
+        .collect();
+    for field in &mut fields {
+        match field.tag {
+            Tag::PixelXDimension => field.value = Value::Long(vec![width]),
+            Tag::PixelYDimension => field.value = Value::Long(vec![height]),
+            Tag::Orientation if reset_orientation => field.value = Value::Short(vec![1]),
+            _ => {}
+        }
+    }
+
+    let mut writer = exif::experimental::Writer::new();
+    for field in &fields {
+        writer.push_field(field);
+    }
+    let mut out = std::io::Cursor::new(Vec::new());
+    match writer.write(&mut out, exif.little_endian()) {
+        Ok(()) => Some(out.into_inner()),
+        Err(e) => {
+            tracing::warn!("Failed to rewrite EXIF for cropped image: {}", e);
+            None
+        }
+    }
+}
+
+/// 자른 이미지용 XMP (방향/크기 속성 제거)
+fn crop_xmp(xmp: &mut XmpMeta) -> Result<Vec<u8>, String> {
+    for (namespace, name) in [
+        (xmp_ns::TIFF, "Orientation"),
+        (xmp_ns::TIFF, "ImageWidth"),
+        (xmp_ns::TIFF, "ImageLength"),
+        (xmp_ns::EXIF, "PixelXDimension"),
+        (xmp_ns::EXIF, "PixelYDimension"),
+    ] {
+        let _ = xmp.delete_property(namespace, name);
+    }
+    xmp.to_string_with_options(ToStringOptions::default().use_compact_format())
+        .map(String::into_bytes)
+        .map_err(|e| format!("Failed to write XMP: {}", e))
+}
+
+fn read_xmp(path: &Path) -> Option<XmpMeta> {
+    let mut xmp_file = XmpFile::new().ok()?;
+    xmp_file.open_file(path, OpenFileOptions::default().only_xmp()).ok()?;
+    xmp_file.xmp()
+}
+
+// 원본 EXIF 방향 (없으면 1)
+fn read_orientation(path: &Path) -> u32 {
+    fs::File::open(path)
+        .ok()
+        .and_then(|file| exif::Reader::new().read_from_container(&mut BufReader::new(file)).ok())
+        .and_then(|exif| exif.get_field(Tag::Orientation, In::PRIMARY).and_then(|f| f.value.get_uint(0)))
+        .unwrap_or(1)
+}
+
+/// 재인코딩한 JPEG의 SOI(와 JFIF APP0) 뒤에 EXIF/XMP 세그먼트 추가
+fn insert_metadata(jpeg: Vec<u8>, exif: Option<Vec<u8>>, xmp: Option<Vec<u8>>) -> Result<Vec<u8>, String> {
+    let mut insert_at = 2;
+    if jpeg.get(2..4) == Some(&[0xFF, 0xE0]) {
+        let length = jpeg.get(4..6).map(|b| u16::from_be_bytes([b[0], b[1]]) as usize).unwrap_or(0);
+        insert_at = (4 + length).min(jpeg.len());
+    }
+
+    let mut out = Vec::with_capacity(jpeg.len() + 64 * 1024);
+    out.extend_from_slice(&jpeg[..insert_at]);
+    if let Some(tiff) = exif {
+        push_segment(&mut out, 0xE1, EXIF_HEADER, &tiff)?;
+    }
+    if let Some(packet) = xmp {
+        push_segment(&mut out, 0xE1, XMP_HEADER, &packet)?;
+    }
+    out.extend_from_slice(&jpeg[insert_at..]);
+    Ok(out)
+}
+
+/// Catmull-Rom 가중치
+fn cubic_weights(t: f32) -> [f32; 4] {
+    [
+        ((-0.5 * t + 1.0) * t - 0.5) * t,
+        (1.5 * t - 2.5) * t * t + 1.0,
+        ((-1.5 * t + 2.0) * t + 0.5) * t,
+        (0.5 * t - 0.5) * t * t,
+    ]
+}
+
+/// 바이큐빅 보간 (가장자리 밖은 가장자리 픽셀 반복)
+fn sample_bicubic(rgba: &[u8], width: u32, height: u32, x: f32, y: f32, out: &mut [u8]) {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (wx, wy) = (cubic_weights(x - x0), cubic_weights(y - y0));
+    let (x0, y0) = (x0 as i64 - 1, y0 as i64 - 1);
+
+    let mut sum = [0f32; 4];
+    for (j, weight_y) in wy.iter().enumerate() {
+        let sy = (y0 + j as i64).clamp(0, height as i64 - 1) as usize;
+        for (i, weight_x) in wx.iter().enumerate() {
+            let sx = (x0 + i as i64).clamp(0, width as i64 - 1) as usize;
+            let pixel = &rgba[(sy * width as usize + sx) * 4..][..4];
+            let weight = weight_x * weight_y;
+            for (acc, &value) in sum.iter_mut().zip(pixel) {
+                *acc += value as f32 * weight;
+            }
+        }
+    }
+    for (dst, value) in out.iter_mut().zip(sum) {
+        *dst = value.round().clamp(0.0, 255.0) as u8;
+    }
+}
+
+/// 이미지를 중심 기준으로 회전(양수는 시계 방향)한 뒤 영역 자르기 (RGBA)
+fn rotate_crop(rgba: &[u8], width: u32, height: u32, rect: CropRect, degrees: f32) -> Vec<u8> {
+    let row_bytes = rect.width as usize * 4;
+    let mut out = vec![0u8; row_bytes * rect.height as usize];
+
+    if degrees == 0.0 {
+        out.par_chunks_mut(row_bytes).enumerate().for_each(|(row, dst)| {
+            let start = ((rect.y as usize + row) * width as usize + rect.x as usize) * 4;
+            dst.copy_from_slice(&rgba[start..start + row_bytes]);
+        });
+        return out;
+    }
+
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let (cx, cy) = (width as f32 / 2.0, height as f32 / 2.0);
+    out.par_chunks_mut(row_bytes).enumerate().for_each(|(row, dst)| {
+        let py = rect.y as f32 + row as f32 + 0.5 - cy;
+        for (column, pixel) in dst.chunks_exact_mut(4).enumerate() {
+            let px = rect.x as f32 + column as f32 + 0.5 - cx;
+            // 회전 전 원본 좌표 (역회전)
+            let sx = cos * px + sin * py + cx - 0.5;
+            let sy = -sin * px + cos * py + cy - 0.5;
+            sample_bicubic(rgba, width, height, sx, sy, pixel);
+        }
+    });
+    out
+}
+
+fn check_rect(rect: CropRect, width: u32, height: u32) -> Result<(), String> {
+    if rect.width == 0 || rect.height == 0 {
+        return Err("Invalid crop rectangle: empty".to_string());
+    }
+    if rect.x.checked_add(rect.width).is_none_or(|right| right > width)
+        || rect.y.checked_add(rect.height).is_none_or(|bottom| bottom > height)
+    {
+        return Err(format!("Invalid crop rectangle: outside of {}x{} image", width, height));
+    }
+    Ok(())
+}
+
+/// JPEG 무손실 자르기 시도 (방향 1, 회전 없음, MCU 경계일 때만)
+fn try_lossless(source: &Path, rect: CropRect, degrees: f32) -> Option<Vec<u8>> {
+    if degrees != 0.0 || read_orientation(source) != 1 {
+        return None;
+    }
+    let data = fs::read(source).ok()?;
+    let cropped = match jpeg_lossless::crop(&data, rect.x, rect.y, rect.width, rect.height) {
+        Ok(cropped) => cropped,
+        Err(e) => {
+            tracing::debug!("Lossless crop not possible for {}: {}", source.display(), e);
+            return None;
+        }
+    };
+
+    rewrite_jpeg_metadata(
+        &cropped,
+        |tiff| Ok(crop_exif(tiff, rect.width, rect.height, false)),
+        |packet| {
+            let text = std::str::from_utf8(packet).map_err(|e| format!("Invalid XMP packet: {}", e))?;
+            let mut xmp: XmpMeta = text.parse().map_err(|e| format!("Failed to parse XMP: {}", e))?;
+            crop_xmp(&mut xmp)
+        },
+        false,
+    )
+    .map_err(|e| tracing::warn!("Failed to carry metadata for lossless crop: {}", e))
+    .ok()
+}
+
+/// 무손실 자르기가 가능한 JPEG의 MCU 크기 (자르기 영역 시작점을 이 배수에 맞추면 무손실)
+pub fn lossless_alignment(path: &str) -> Option<(u32, u32)> {
+    if !crate::formats::is_jpeg_file(path) {
+        return None;
+    }
+    let source = crate::fs_path::to_fs_path(path);
+    if read_orientation(&source) != 1 {
+        return None;
+    }
+    jpeg_lossless::mcu_size(&fs::read(&source).ok()?).ok()
+}
+
+/// 재인코딩으로 자르기 (수평 보정, 다른 형식 출력)
+fn render_cropped(
+    source: &Path,
+    rect: CropRect,
+    degrees: f32,
+    format: OutputFormat,
+    quality: u8,
+) -> Result<Vec<u8>, String> {
+    let (img, icc_profile) = crate::export::decode(source)?;
+    let (width, height) = (img.width(), img.height());
+    check_rect(rect, width, height)?;
+
+    let has_alpha = img.color().has_alpha() && format != OutputFormat::Jpeg;
+    let mut rgba = img.into_rgba8().into_raw();
+    color_profile::convert_to_srgb(icc_profile.as_deref(), &mut rgba, PixelLayout::Rgba8);
+    let cropped = rotate_crop(&rgba, width, height, rect, degrees);
+
+    let pixels = if has_alpha {
+        cropped
+    } else {
+        thumbnail::composite_over_background(&cropped, [255, 255, 255])
+    };
+    let quality = quality.clamp(1, 100);
+
+    match format {
+        OutputFormat::Jpeg => {
+            let jpeg = thumbnail::encode_thumbnail_to_jpeg_with_quality(&pixels, rect.width, rect.height, quality)?;
+            let exif = fs::File::open(source)
+                .ok()
+                .and_then(|file| exif::Reader::new().read_from_container(&mut BufReader::new(file)).ok())
+                .and_then(|exif| crop_exif(exif.buf(), rect.width, rect.height, true));
+            let xmp = read_xmp(source).and_then(|mut xmp| crop_xmp(&mut xmp).ok());
+            insert_metadata(jpeg, exif, xmp)
+        }
+        OutputFormat::Png => {
+            let color = if has_alpha { image::ExtendedColorType::Rgba8 } else { image::ExtendedColorType::Rgb8 };
+            let mut data = Vec::new();
+            image::ImageEncoder::write_image(
+                image::codecs::png::PngEncoder::new(&mut data),
+                &pixels,
+                rect.width,
+                rect.height,
+                color,
+            )
+            .map_err(|e| format!("Failed to encode PNG: {}", e))?;
+            Ok(data)
+        }
+        OutputFormat::Webp => {
+            thumbnail::encode_thumbnail_to_webp(&pixels, rect.width, rect.height, has_alpha, quality as f32)
+        }
+    }
+}
+
+/// PNG/WebP 출력에 원본 XMP 복사 (EXIF는 XMP에 있는 항목만 유지)
+fn copy_xmp(source: &Path, target: &Path) -> Result<(), String> {
+    let Some(mut xmp) = read_xmp(source) else {
+        return Ok(());
+    };
+    crop_xmp(&mut xmp)?;
+
+    let mut xmp_file = XmpFile::new().map_err(|e| format!("Failed to initialize XMP: {}", e))?;
+    xmp_file
+        .open_file(target, OpenFileOptions::default().for_update().use_smart_handler())
+        .map_err(|e| format!("Failed to open output for XMP: {}", e))?;
+    xmp_file.put_xmp(&xmp).map_err(|e| format!("Failed to write XMP: {}", e))?;
+    xmp_file.close();
+    Ok(())
+}
+
+/// 이미지 자르기/수평 보정 후 output에 저장 (원본 경로면 덮어쓰기)
+/// degrees: 수평 보정 각도 (양수는 시계 방향, ±45도 이내)
+pub fn crop_image(
+    path: &str,
+    rect: CropRect,
+    degrees: f32,
+    output: &str,
+    options: &CropOptions,
+) -> Result<CropResult, String> {
+    if !degrees.is_finite() || degrees.abs() > MAX_STRAIGHTEN_DEGREES {
+        return Err(format!("Invalid straighten angle: {} (-45 to 45)", degrees));
+    }
+
+    let source = crate::fs_path::to_fs_path(path);
+    let target = crate::fs_path::to_fs_path(output);
+    let format = output_format(&target)?;
+
+    let lossless = if options.lossless && format == OutputFormat::Jpeg && crate::formats::is_jpeg_file(path) {
+        try_lossless(&source, rect, degrees)
+    } else {
+        None
+    };
+    let is_lossless = lossless.is_some();
+    let data = match lossless {
+        Some(data) => data,
+        None => render_cropped(&source, rect, degrees, format, options.quality)?,
+    };
+
+    crate::shutdown::write_atomic(&target, &data).map_err(|e| format!("Failed to write file: {}", e))?;
+    if format != OutputFormat::Jpeg {
+        if let Err(e) = copy_xmp(&source, &target) {
+            tracing::warn!("Failed to copy XMP to {}: {}", output, e);
+        }
+    }
+
+    Ok(CropResult {
+        source: path.to_string(),
+        output: output.to_string(),
+        width: rect.width,
+        height: rect.height,
+        lossless: is_lossless,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotate_crop() {
+        // 가로 그라데이션 4x4
+        let rgba: Vec<u8> = (0..16).flat_map(|i| [(i % 4 * 60) as u8, 0, 0, 255]).collect();
+        let rect = CropRect { x: 1, y: 1, width: 2, height: 2 };
+
+        let cropped = rotate_crop(&rgba, 4, 4, rect, 0.0);
+        assert_eq!(cropped.chunks(4).map(|p| p[0]).collect::<Vec<_>>(), vec![60, 120, 60, 120]);
+
+        // 180도 회전하면 좌우가 바뀜
+        let rotated = rotate_crop(&rgba, 4, 4, rect, 180.0);
+        assert_eq!(rotated.chunks(4).map(|p| p[0]).collect::<Vec<_>>(), vec![120, 60, 120, 60]);
+
+        assert!(check_rect(CropRect { x: 3, y: 0, width: 2, height: 1 }, 4, 4).is_err());
+    }
+}
@@ -0,0 +1,244 @@
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Emitter, Listener, Manager, Window, WindowEvent, Wry};
+
+use crate::settings;
+use crate::thumbnail_queue::{self, ThumbnailProgress, ThumbnailQueueManager};
+
+const TRAY_ID: &str = "main";
+
+/// 툴팁 갱신 최소 간격 (진행 이벤트마다 갱신하지 않음)
+const TOOLTIP_UPDATE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 백그라운드 작업 일시정지 상태 (background-work-paused 이벤트)
+#[derive(Debug, Clone, Serialize)]
+pub struct BackgroundWorkState {
+    pub indexing_paused: bool,
+    pub hq_paused: bool,
+}
+
+#[derive(Debug, Default)]
+struct TrayProgress {
+    /// (완료, 전체)
+    thumbnail: Option<(usize, usize)>,
+    hq: Option<(usize, usize)>,
+    last_update: Option<Instant>,
+}
+
+/// 트레이 메뉴 항목과 진행 상태 (관리 상태)
+pub struct TrayState {
+    indexing_item: MenuItem<Wry>,
+    hq_item: MenuItem<Wry>,
+    progress: Mutex<TrayProgress>,
+}
+
+fn indexing_label(paused: bool) -> &'static str {
+    if paused {
+        "썸네일 생성 재개"
+    } else {
+        "썸네일 생성 일시정지"
+    }
+}
+
+fn hq_label(paused: bool) -> &'static str {
+    if paused {
+        "고화질 썸네일 생성 재개"
+    } else {
+        "고화질 썸네일 생성 일시정지"
+    }
+}
+
+/// 메인 윈도우 표시 (트레이로 숨겼거나 최소화된 경우)
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn tooltip_text(progress: &TrayProgress, state: &BackgroundWorkState) -> String {
+    let mut lines = vec!["PixEngine".to_string()];
+    if let Some((completed, total)) = progress.thumbnail {
+        let suffix = if state.indexing_paused { " (일시정지)" } else { "" };
+        lines.push(format!("썸네일 {}/{}{}", completed, total, suffix));
+    }
+    if let Some((completed, total)) = progress.hq {
+        let suffix = if state.hq_paused { " (일시정지)" } else { "" };
+        lines.push(format!("고화질 썸네일 {}/{}{}", completed, total, suffix));
+    }
+    lines.join("\n")
+}
+
+async fn background_state(app: &AppHandle) -> BackgroundWorkState {
+    let indexing_paused = match app.try_state::<Arc<tokio::sync::Mutex<ThumbnailQueueManager>>>() {
+        Some(queue) => queue.lock().await.is_paused().await,
+        None => false,
+    };
+    BackgroundWorkState {
+        indexing_paused,
+        hq_paused: thumbnail_queue::is_hq_paused(),
+    }
+}
+
+/// 메뉴 문구와 툴팁을 현재 상태로 갱신
+async fn refresh(app: &AppHandle) -> BackgroundWorkState {
+    let state = background_state(app).await;
+    if let Some(tray_state) = app.try_state::<TrayState>() {
+        let _ = tray_state.indexing_item.set_text(indexing_label(state.indexing_paused));
+        let _ = tray_state.hq_item.set_text(hq_label(state.hq_paused));
+        if let (Some(tray), Ok(progress)) = (app.tray_by_id(TRAY_ID), tray_state.progress.lock()) {
+            let _ = tray.set_tooltip(Some(tooltip_text(&progress, &state)));
+        }
+    }
+    state
+}
+
+/// 진행 상태 반영 (force가 아니면 TOOLTIP_UPDATE_INTERVAL마다 한 번만 툴팁 갱신)
+fn update_progress(app: &AppHandle, force: bool, apply: impl FnOnce(&mut TrayProgress)) {
+    let Some(tray_state) = app.try_state::<TrayState>() else {
+        return;
+    };
+    let Ok(mut progress) = tray_state.progress.lock() else {
+        return;
+    };
+    apply(&mut progress);
+
+    let due = progress
+        .last_update
+        .is_none_or(|last| last.elapsed() >= TOOLTIP_UPDATE_INTERVAL);
+    if !force && !due {
+        return;
+    }
+    progress.last_update = Some(Instant::now());
+    drop(progress);
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        refresh(&app).await;
+    });
+}
+
+fn progress_value(payload: &str) -> Option<(usize, usize)> {
+    let progress: ThumbnailProgress = serde_json::from_str(payload).ok()?;
+    (progress.completed < progress.total).then_some((progress.completed, progress.total))
+}
+
+fn listen_progress(app: &AppHandle) {
+    let handle = app.clone();
+    app.listen("thumbnail-progress", move |event| {
+        let value = progress_value(event.payload());
+        update_progress(&handle, value.is_none(), |progress| progress.thumbnail = value);
+    });
+
+    let handle = app.clone();
+    app.listen("thumbnail-all-completed", move |_| {
+        update_progress(&handle, true, |progress| progress.thumbnail = None);
+    });
+
+    let handle = app.clone();
+    app.listen("thumbnail-hq-progress", move |event| {
+        let value = progress_value(event.payload());
+        update_progress(&handle, value.is_none(), |progress| progress.hq = value);
+    });
+
+    for finished in ["thumbnail-hq-all-completed", "thumbnail-hq-cancelled"] {
+        let handle = app.clone();
+        app.listen(finished, move |_| {
+            update_progress(&handle, true, |progress| progress.hq = None);
+        });
+    }
+}
+
+fn handle_menu_event(app: &AppHandle, event: MenuEvent) {
+    match event.id().as_ref() {
+        "show" => show_main_window(app),
+        "toggle_indexing" => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Some(queue) = app.try_state::<Arc<tokio::sync::Mutex<ThumbnailQueueManager>>>() {
+                    let queue = queue.lock().await;
+                    if queue.is_paused().await {
+                        queue.resume().await;
+                    } else {
+                        queue.pause().await;
+                    }
+                }
+                let state = refresh(&app).await;
+                let _ = app.emit("background-work-paused", &state);
+            });
+        }
+        "toggle_hq" => {
+            thumbnail_queue::set_hq_paused(!thumbnail_queue::is_hq_paused());
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let state = refresh(&app).await;
+                let _ = app.emit("background-work-paused", &state);
+            });
+        }
+        "quit" => app.exit(0),
+        _ => {}
+    }
+}
+
+/// 트레이 아이콘 생성 (썸네일 큐 관리 상태를 등록한 뒤 호출)
+pub fn init(app: &AppHandle) -> Result<(), String> {
+    let to_error = |e: tauri::Error| format!("Failed to create tray menu: {}", e);
+
+    let show_item = MenuItem::with_id(app, "show", "PixEngine 열기", true, None::<&str>).map_err(to_error)?;
+    let indexing_item = MenuItem::with_id(app, "toggle_indexing", indexing_label(false), true, None::<&str>)
+        .map_err(to_error)?;
+    let hq_item = MenuItem::with_id(app, "toggle_hq", hq_label(false), true, None::<&str>).map_err(to_error)?;
+    let quit_item = MenuItem::with_id(app, "quit", "종료", true, None::<&str>).map_err(to_error)?;
+    let separator = PredefinedMenuItem::separator(app).map_err(to_error)?;
+    let quit_separator = PredefinedMenuItem::separator(app).map_err(to_error)?;
+
+    let menu = Menu::with_items(
+        app,
+        &[&show_item, &separator, &indexing_item, &hq_item, &quit_separator, &quit_item],
+    )
+    .map_err(to_error)?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("PixEngine")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(handle_menu_event)
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder
+        .build(app)
+        .map_err(|e| format!("Failed to create tray icon: {}", e))?;
+
+    app.manage(TrayState {
+        indexing_item,
+        hq_item,
+        progress: Mutex::new(TrayProgress::default()),
+    });
+    listen_progress(app);
+    Ok(())
+}
+
+/// 메인 윈도우 닫기: 트레이로 최소화 설정이면 숨기기만 함 (백그라운드 작업 계속)
+pub fn handle_window_event(window: &Window, event: &WindowEvent) {
+    if let WindowEvent::CloseRequested { api, .. } = event {
+        if window.label() == "main" && settings::current().minimize_to_tray {
+            api.prevent_close();
+            let _ = window.hide();
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use rusqlite::{params, OptionalExtension};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::formats::{is_image_file, is_raw_file};
+use crate::metadata_store::MetadataStore;
+
+/// 파생 파일 → 원본 연결 테이블 스키마
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS image_versions (
+        path       TEXT PRIMARY KEY,
+        original   TEXT NOT NULL,
+        manual     INTEGER NOT NULL DEFAULT 0,
+        created_at INTEGER NOT NULL
+    );
+
+    CREATE INDEX IF NOT EXISTS idx_image_versions_original ON image_versions(original);
+";
+
+/// 편집/내보내기 프로그램이 파일명에 붙이는 접미사 (대소문자 무시, 뒤에 번호가 붙을 수 있음: "-Edit-2")
+const DERIVATIVE_SUFFIXES: &[&str] = &[
+    "-edit", "_edit", " edit", "-edited", "_edited", "-export", "_export", "-hdr", "_hdr", "-pano", "_pano",
+    " copy", "-copy", "_copy",
+];
+
+/// 버전 하나 (원본 또는 파생 파일)
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionInfo {
+    pub path: String,
+    pub is_original: bool,
+    /// 수정 시간 (Unix 초)
+    pub modified: Option<u64>,
+}
+
+/// 원본과 파생 파일 목록 (원본 먼저, 파생 파일은 수정 시간 순)
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionSet {
+    pub original: String,
+    pub versions: Vec<VersionInfo>,
+}
+
+/// version-detected 이벤트
+#[derive(Debug, Clone, Serialize)]
+struct VersionDetected {
+    original: String,
+    path: String,
+}
+
+/// 버전 테이블 초기화
+pub fn init_schema(store: &MetadataStore) -> Result<(), String> {
+    store.with_conn(|conn| conn.execute_batch(SCHEMA))
+}
+
+// 접미사 하나 제거 (대소문자 무시)
+fn strip_suffix_ignore_case<'a>(stem: &'a str, suffix: &str) -> Option<&'a str> {
+    let start = stem.len().checked_sub(suffix.len())?;
+    let tail = stem.get(start..)?;
+    (start > 0 && tail.eq_ignore_ascii_case(suffix)).then_some(&stem[..start])
+}
+
+/// 파생 파일명이면 원본 파일명(확장자 제외) 반환 ("IMG_0001-Edit-2" → "IMG_0001")
+fn derivative_base(stem: &str) -> Option<&str> {
+    // 뒤에 붙은 번호 ("-2", "_3", " 2") 제거한 이름도 확인
+    let without_number = stem
+        .trim_end_matches(|c: char| c.is_ascii_digit())
+        .strip_suffix(['-', '_', ' '])
+        .filter(|rest| rest.len() + 1 < stem.len());
+
+    [Some(stem), without_number].into_iter().flatten().find_map(|candidate| {
+        DERIVATIVE_SUFFIXES
+            .iter()
+            .find_map(|suffix| strip_suffix_ignore_case(candidate, suffix))
+    })
+}
+
+fn file_stem(path: &Path) -> Option<String> {
+    path.file_stem().map(|stem| stem.to_string_lossy().to_string())
+}
+
+/// 같은 폴더에서 파생 파일의 원본 찾기 (이름이 같은 이미지, RAW 우선)
+fn find_original(path: &Path) -> Option<PathBuf> {
+    let stem = file_stem(path)?;
+    let base = derivative_base(&stem)?;
+    let mut candidates: Vec<PathBuf> = fs::read_dir(path.parent()?)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|candidate| candidate != path && is_image_file(candidate))
+        .filter(|candidate| file_stem(candidate).as_deref() == Some(base))
+        .collect();
+    candidates.sort_by_key(|candidate| (!is_raw_file(&candidate.to_string_lossy()), candidate.clone()));
+    candidates.into_iter().next()
+}
+
+fn lookup_original(store: &MetadataStore, path: &str) -> Result<Option<String>, String> {
+    store.with_conn(|conn| {
+        conn.query_row(
+            "SELECT original FROM image_versions WHERE path = ?1",
+            params![path],
+            |row| row.get(0),
+        )
+        .optional()
+    })
+}
+
+fn record(store: &MetadataStore, original: &str, path: &str, manual: bool) -> Result<(), String> {
+    // 자동 감지는 직접 연결한 기록을 덮어쓰지 않음
+    let sql = if manual {
+        "INSERT OR REPLACE INTO image_versions (path, original, manual, created_at) VALUES (?1, ?2, 1, ?3)"
+    } else {
+        "INSERT OR IGNORE INTO image_versions (path, original, manual, created_at) VALUES (?1, ?2, 0, ?3)"
+    };
+    store.with_conn(|conn| conn.execute(sql, params![path, original, chrono::Utc::now().timestamp()]))?;
+    Ok(())
+}
+
+/// 파일명으로 원본을 찾아 연결 (파생 파일이 아니거나 원본이 없으면 None)
+pub fn detect(store: &MetadataStore, path: &str) -> Result<Option<String>, String> {
+    let Some(original) = find_original(Path::new(path)) else {
+        return Ok(None);
+    };
+    let original = original.to_string_lossy().to_string();
+    record(store, &original, path, false)?;
+    Ok(Some(original))
+}
+
+/// 새 파일이 파생 파일이면 원본과 연결하고 version-detected 이벤트 전송 (폴더 감시에서 호출)
+pub fn on_file_added(app: &AppHandle, path: &str) {
+    if file_stem(Path::new(path)).as_deref().and_then(derivative_base).is_none() {
+        return;
+    }
+    let Some(store) = app.try_state::<Arc<MetadataStore>>() else {
+        return;
+    };
+    match detect(&store, path) {
+        Ok(Some(original)) => {
+            let _ = app.emit("version-detected", VersionDetected {
+                original,
+                path: path.to_string(),
+            });
+        }
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to record version for {}: {}", path, e),
+    }
+}
+
+/// 파생 파일을 원본에 직접 연결 (파일명 규칙과 다른 내보내기 결과 등)
+pub fn link(store: &MetadataStore, original: &str, path: &str) -> Result<(), String> {
+    if original == path {
+        return Err("Invalid version link: a file cannot be a version of itself".to_string());
+    }
+    // 원본이 다른 파일의 파생 파일이면 그 원본에 연결
+    let root = lookup_original(store, original)?.unwrap_or_else(|| original.to_string());
+    record(store, &root, path, true)
+}
+
+fn modified(path: &str) -> Option<u64> {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+}
+
+/// 이미지의 모든 버전 (원본이든 파생 파일이든 같은 목록, 없어진 파일은 제외)
+/// 아직 연결되지 않은 같은 폴더의 파생 파일도 파일명으로 찾아 연결
+pub fn get_versions(store: &MetadataStore, path: &str) -> Result<VersionSet, String> {
+    let original = match lookup_original(store, path)? {
+        Some(original) => original,
+        None => detect(store, path)?.unwrap_or_else(|| path.to_string()),
+    };
+
+    let original_path = Path::new(&original);
+    if let (Some(stem), Some(parent)) = (file_stem(original_path), original_path.parent()) {
+        let siblings: Vec<PathBuf> = fs::read_dir(parent)
+            .map(|entries| entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect())
+            .unwrap_or_default();
+        for sibling in siblings {
+            let is_derivative = file_stem(&sibling).as_deref().and_then(derivative_base) == Some(stem.as_str());
+            if is_derivative && is_image_file(&sibling) {
+                record(store, &original, &sibling.to_string_lossy(), false)?;
+            }
+        }
+    }
+
+    let derivatives: Vec<String> = store.with_conn(|conn| {
+        let mut stmt = conn.prepare_cached("SELECT path FROM image_versions WHERE original = ?1")?;
+        let rows = stmt.query_map(params![original], |row| row.get(0))?;
+        rows.collect()
+    })?;
+
+    let mut versions: Vec<VersionInfo> = derivatives
+        .into_iter()
+        .filter(|derivative| Path::new(derivative).is_file())
+        .map(|derivative| VersionInfo {
+            modified: modified(&derivative),
+            path: derivative,
+            is_original: false,
+        })
+        .collect();
+    versions.sort_by_key(|version| version.modified);
+    versions.insert(0, VersionInfo {
+        path: original.clone(),
+        is_original: true,
+        modified: modified(&original),
+    });
+
+    Ok(VersionSet { original, versions })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derivative_base() {
+        assert_eq!(derivative_base("IMG_0001-Edit"), Some("IMG_0001"));
+        assert_eq!(derivative_base("IMG_0001-Edit-2"), Some("IMG_0001"));
+        assert_eq!(derivative_base("DSC_1234_edit"), Some("DSC_1234"));
+        assert_eq!(derivative_base("photo copy 2"), Some("photo"));
+        assert_eq!(derivative_base("pano-HDR"), Some("pano"));
+        assert_eq!(derivative_base("IMG_0001"), None);
+        assert_eq!(derivative_base("IMG_0001-2"), None);
+        assert_eq!(derivative_base("-edit"), None);
+    }
+}
@@ -0,0 +1,239 @@
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+use crate::export::{self, ExportFormat, ExportOptions};
+
+/// 바탕화면 배치 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WallpaperMode {
+    /// 화면을 채우도록 확대 (넘치는 부분은 잘림)
+    #[default]
+    Fill,
+    /// 화면 안에 전체가 보이도록 맞춤
+    Fit,
+    /// 원래 크기로 가운데
+    Center,
+}
+
+// 바탕화면 이미지 폴더 (OS가 설정 후에도 파일을 계속 참조하므로 캐시 디렉토리에 유지)
+fn get_wallpaper_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_cache_dir()
+        .map(|p| p.join("wallpaper"))
+        .map_err(|e| format!("Failed to get app cache dir: {}", e))
+}
+
+// OS가 그대로 읽을 수 있는 파일인지 (JPEG/PNG, EXIF 회전 없음)
+fn is_native_format(source: &str) -> bool {
+    let extension = Path::new(source)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    matches!(extension.as_str(), "jpg" | "jpeg" | "png")
+        && crate::thumbnail::extract_exif_metadata(source).map_or(1, |metadata| metadata.orientation) == 1
+}
+
+// 바탕화면으로 쓸 파일 (지원하지 않는 형식은 PNG로 변환, 이전 파일은 삭제)
+// 스풀 파일(spooled)은 나중에 지워지므로 그대로 쓸 수 있는 형식도 복사
+// 파일 이름은 원본 경로 해시 (macOS는 같은 경로면 바뀐 내용을 다시 읽지 않음)
+fn prepare(app: &AppHandle, source: &str, spooled: bool) -> Result<PathBuf, String> {
+    let native = is_native_format(source);
+    if native && !spooled {
+        return Ok(PathBuf::from(source));
+    }
+
+    let dir = get_wallpaper_dir(app)?;
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create wallpaper directory: {}", e))?;
+    let key = blake3::hash(source.as_bytes()).to_hex();
+
+    if native {
+        let extension = Path::new(source).extension().unwrap_or_default().to_string_lossy();
+        let target = dir.join(format!("wallpaper_{}.{}", &key[..16], extension));
+        fs::copy(source, &target).map_err(|e| format!("Failed to copy wallpaper: {}", e))?;
+        return Ok(target);
+    }
+
+    let options = ExportOptions {
+        format: ExportFormat::Png,
+        ..ExportOptions::default()
+    };
+    let png = export::render(Path::new(source), &options)?;
+    let target = dir.join(format!("wallpaper_{}.png", &key[..16]));
+    fs::write(&target, png).map_err(|e| format!("Failed to write wallpaper: {}", e))?;
+    Ok(target)
+}
+
+/// Windows: 배치 방식은 레지스트리(HKCU\Control Panel\Desktop), 이미지는 SystemParametersInfo
+#[cfg(target_os = "windows")]
+fn apply(_app: &AppHandle, image: &Path, mode: WallpaperMode) -> Result<(), String> {
+    use windows::core::{HSTRING, PCWSTR};
+    use windows::Win32::System::Registry::{RegSetKeyValueW, HKEY_CURRENT_USER, REG_SZ};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        SystemParametersInfoW, SPIF_SENDCHANGE, SPIF_UPDATEINIFILE, SPI_SETDESKWALLPAPER,
+    };
+
+    // WallpaperStyle: 10 = 채우기, 6 = 맞춤, 0 = 가운데 (TileWallpaper 0)
+    let style = match mode {
+        WallpaperMode::Fill => "10",
+        WallpaperMode::Fit => "6",
+        WallpaperMode::Center => "0",
+    };
+    let key = HSTRING::from("Control Panel\\Desktop");
+    for (name, value) in [("WallpaperStyle", style), ("TileWallpaper", "0")] {
+        let name = HSTRING::from(name);
+        let data: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+        let result = unsafe {
+            RegSetKeyValueW(
+                HKEY_CURRENT_USER,
+                PCWSTR(key.as_ptr()),
+                PCWSTR(name.as_ptr()),
+                REG_SZ.0,
+                Some(data.as_ptr() as *const _),
+                (data.len() * 2) as u32,
+            )
+        };
+        if result.is_err() {
+            return Err(format!("Failed to set wallpaper style: {:?}", result));
+        }
+    }
+
+    let mut path: Vec<u16> = image.as_os_str().to_string_lossy().encode_utf16().chain(std::iter::once(0)).collect();
+    unsafe {
+        SystemParametersInfoW(
+            SPI_SETDESKWALLPAPER,
+            0,
+            Some(path.as_mut_ptr() as *mut _),
+            SPIF_UPDATEINIFILE | SPIF_SENDCHANGE,
+        )
+    }
+    .map_err(|e| format!("Failed to set wallpaper: {}", e))
+}
+
+/// macOS: NSWorkspace setDesktopImageURL (모든 화면, NSScreen은 메인 스레드에서만 접근)
+#[cfg(target_os = "macos")]
+fn apply(app: &AppHandle, image: &Path, mode: WallpaperMode) -> Result<(), String> {
+    use objc2::rc::Retained;
+    use objc2::runtime::AnyObject;
+    use objc2_app_kit::{
+        NSScreen, NSWorkspace, NSWorkspaceDesktopImageAllowClippingKey, NSWorkspaceDesktopImageScalingKey,
+    };
+    use objc2_foundation::{MainThreadMarker, NSDictionary, NSNumber, NSString, NSURL};
+
+    // NSImageScaling: 3 = 비율 유지 확대/축소, 2 = 원래 크기 / 채우기만 잘림 허용
+    let (scaling, clipping) = match mode {
+        WallpaperMode::Fill => (3, true),
+        WallpaperMode::Fit => (3, false),
+        WallpaperMode::Center => (2, false),
+    };
+    let path = image.to_string_lossy().to_string();
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    app.run_on_main_thread(move || {
+        let result = (|| -> Result<(), String> {
+            let mtm = MainThreadMarker::new().ok_or("Not on main thread")?;
+            unsafe {
+                let url = NSURL::fileURLWithPath(&NSString::from_str(&path));
+                let options = NSDictionary::from_vec(
+                    &[NSWorkspaceDesktopImageScalingKey, NSWorkspaceDesktopImageAllowClippingKey],
+                    vec![NSNumber::new_usize(scaling), NSNumber::new_bool(clipping)],
+                );
+                let options = Retained::cast::<NSDictionary<NSString, AnyObject>>(options);
+                let workspace = NSWorkspace::sharedWorkspace();
+                for screen in NSScreen::screens(mtm).iter() {
+                    workspace
+                        .setDesktopImageURL_forScreen_options_error(&url, screen, &options)
+                        .map_err(|e| format!("Failed to set wallpaper: {}", e.localizedDescription()))?;
+                }
+            }
+            Ok(())
+        })();
+        let _ = sender.send(result);
+    })
+    .map_err(|e| format!("Failed to run on main thread: {}", e))?;
+    receiver
+        .recv()
+        .map_err(|_| "Wallpaper was not set".to_string())?
+}
+
+/// Linux: KDE Plasma는 plasmashell DBus 스크립트, 그 외(GNOME 계열)는 gsettings
+#[cfg(target_os = "linux")]
+fn apply(_app: &AppHandle, image: &Path, mode: WallpaperMode) -> Result<(), String> {
+    use std::process::Command;
+
+    let run = |program: &str, args: &[&str]| -> Result<(), String> {
+        let output = Command::new(program)
+            .args(args)
+            .output()
+            .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+        if !output.status.success() {
+            return Err(format!("Failed to set wallpaper: {}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+        Ok(())
+    };
+
+    // 공백, 한글 등은 퍼센트 인코딩
+    let uri = url::Url::from_file_path(image)
+        .map_err(|_| format!("Invalid wallpaper path: {}", image.display()))?
+        .to_string();
+    let desktop = std::env::var("XDG_CURRENT_DESKTOP").unwrap_or_default().to_lowercase();
+    if desktop.contains("kde") {
+        // FillMode: 2 = 비율 유지 잘라 채우기, 1 = 비율 유지 맞춤, 6 = 가운데
+        let fill_mode = match mode {
+            WallpaperMode::Fill => 2,
+            WallpaperMode::Fit => 1,
+            WallpaperMode::Center => 6,
+        };
+        let script = format!(
+            "desktops().forEach(d => {{ d.wallpaperPlugin = 'org.kde.image'; \
+             d.currentConfigGroup = ['Wallpaper', 'org.kde.image', 'General']; \
+             d.writeConfig('Image', {url}); d.writeConfig('FillMode', {fill_mode}); }});",
+            url = serde_json::to_string(&uri).map_err(|e| e.to_string())?,
+            fill_mode = fill_mode,
+        );
+        return run(
+            "dbus-send",
+            &[
+                "--session",
+                "--type=method_call",
+                "--dest=org.kde.plasmashell",
+                "/PlasmaShell",
+                "org.kde.PlasmaShell.evaluateScript",
+                &format!("string:{}", script),
+            ],
+        );
+    }
+
+    let options = match mode {
+        WallpaperMode::Fill => "zoom",
+        WallpaperMode::Fit => "scaled",
+        WallpaperMode::Center => "centered",
+    };
+    run("gsettings", &["set", "org.gnome.desktop.background", "picture-options", options])?;
+    run("gsettings", &["set", "org.gnome.desktop.background", "picture-uri", &uri])?;
+    // 다크 모드용 키는 GNOME 42 이상에만 있음
+    let _ = run("gsettings", &["set", "org.gnome.desktop.background", "picture-uri-dark", &uri]);
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+fn apply(_app: &AppHandle, _image: &Path, _mode: WallpaperMode) -> Result<(), String> {
+    Err("Setting the wallpaper is not supported on this platform".to_string())
+}
+
+/// 이미지를 바탕화면으로 설정 (원격/압축 파일 항목은 스풀로 받은 뒤 복사해서 사용)
+pub fn set_as_wallpaper(app: &AppHandle, file_path: &str, mode: WallpaperMode) -> Result<(), String> {
+    let image = if crate::remote_source::is_remote_path(file_path) {
+        prepare(app, &crate::remote_source::spool(app, file_path)?.to_string_lossy(), true)?
+    } else if crate::archive_source::is_archive_path(file_path) {
+        prepare(app, &crate::archive_source::spool(app, file_path)?.to_string_lossy(), true)?
+    } else {
+        prepare(app, &crate::fs_path::to_fs_string(file_path), false)?
+    };
+    apply(app, &image, mode)?;
+    tracing::info!("Wallpaper set: {} ({:?})", image.display(), mode);
+    Ok(())
+}
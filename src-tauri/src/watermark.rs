@@ -0,0 +1,347 @@
+use lazy_static::lazy_static;
+use resvg::usvg::{self, fontdb};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::thumbnail;
+
+/// 워터마크 미리보기 기본 크기 (긴 변 px)
+const PREVIEW_MAX_SIZE: u32 = 480;
+
+lazy_static! {
+    /// 시스템 글꼴 목록 (처음 텍스트 워터마크를 그릴 때 1회 로드)
+    static ref SYSTEM_FONTS: Arc<fontdb::Database> = {
+        let mut db = fontdb::Database::new();
+        db.load_system_fonts();
+        Arc::new(db)
+    };
+}
+
+/// 워터마크 위치 (이미지 가장자리 기준)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatermarkAnchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    #[default]
+    BottomRight,
+}
+
+impl WatermarkAnchor {
+    // 가로/세로 정렬 비율 (0 왼쪽·위, 0.5 가운데, 1 오른쪽·아래)
+    fn alignment(self) -> (f32, f32) {
+        match self {
+            WatermarkAnchor::TopLeft => (0.0, 0.0),
+            WatermarkAnchor::Top => (0.5, 0.0),
+            WatermarkAnchor::TopRight => (1.0, 0.0),
+            WatermarkAnchor::Left => (0.0, 0.5),
+            WatermarkAnchor::Center => (0.5, 0.5),
+            WatermarkAnchor::Right => (1.0, 0.5),
+            WatermarkAnchor::BottomLeft => (0.0, 1.0),
+            WatermarkAnchor::Bottom => (0.5, 1.0),
+            WatermarkAnchor::BottomRight => (1.0, 1.0),
+        }
+    }
+}
+
+/// 워터마크 내용 (텍스트 또는 PNG 로고)
+/// 크기는 이미지 비율로 지정해 내보내기 크기와 관계없이 같은 모양으로 보임
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum WatermarkContent {
+    Text {
+        text: String,
+        /// 글꼴 패밀리 이름 또는 글꼴 파일 경로 (.ttf/.otf), None이면 시스템 기본 산세리프
+        #[serde(default)]
+        font: Option<String>,
+        /// 글자 크기 (이미지 짧은 변의 %, 1~50)
+        size: f32,
+        /// 글자 색 (#RRGGBB)
+        #[serde(default = "default_text_color")]
+        color: String,
+    },
+    Logo {
+        /// PNG 파일 경로 (알파 유지)
+        path: String,
+        /// 로고 너비 (이미지 너비의 %, 1~100)
+        scale: f32,
+    },
+}
+
+fn default_text_color() -> String {
+    "#ffffff".to_string()
+}
+
+/// 내보내기 워터마크 설정
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatermarkOptions {
+    pub content: WatermarkContent,
+    /// 불투명도 (0~1)
+    #[serde(default = "default_opacity")]
+    pub opacity: f32,
+    #[serde(default)]
+    pub anchor: WatermarkAnchor,
+    /// 가장자리 여백 (이미지 짧은 변의 %, 0~25)
+    #[serde(default = "default_margin")]
+    pub margin: f32,
+}
+
+fn default_opacity() -> f32 {
+    0.5
+}
+
+fn default_margin() -> f32 {
+    3.0
+}
+
+impl WatermarkOptions {
+    /// 값 범위 검증 (로고 파일은 합성할 때 확인, 설정 파일을 읽을 때 없어도 나머지 설정은 유지)
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.opacity) {
+            return Err(format!("Invalid watermark opacity: {} (0-1)", self.opacity));
+        }
+        if !(0.0..=25.0).contains(&self.margin) {
+            return Err(format!("Invalid watermark margin: {} (0-25)", self.margin));
+        }
+        match &self.content {
+            WatermarkContent::Text { text, size, color, .. } => {
+                if text.trim().is_empty() {
+                    return Err("Watermark text is empty".to_string());
+                }
+                if !(1.0..=50.0).contains(size) {
+                    return Err(format!("Invalid watermark text size: {} (1-50)", size));
+                }
+                if crate::settings::parse_hex_color(color).is_none() {
+                    return Err(format!("Invalid watermark color: {} (#RRGGBB)", color));
+                }
+            }
+            WatermarkContent::Logo { path, scale } => {
+                if path.is_empty() {
+                    return Err("Watermark logo path is empty".to_string());
+                }
+                if !(1.0..=100.0).contains(scale) {
+                    return Err(format!("Invalid watermark logo scale: {} (1-100)", scale));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// 글꼴 목록과 패밀리 이름 (파일 경로면 그 파일의 첫 패밀리)
+fn resolve_font(font: Option<&str>) -> Result<(Arc<fontdb::Database>, String), String> {
+    let fonts = SYSTEM_FONTS.clone();
+    let Some(font) = font.filter(|font| !font.trim().is_empty()) else {
+        return Ok((fonts, "sans-serif".to_string()));
+    };
+    if !Path::new(font).is_file() {
+        return Ok((fonts, font.to_string()));
+    }
+
+    let mut db = (*fonts).clone();
+    let data = std::fs::read(font).map_err(|e| format!("Failed to read font {}: {}", font, e))?;
+    let ids = db.load_font_source(fontdb::Source::Binary(Arc::new(data)));
+    let family = ids
+        .first()
+        .and_then(|id| db.face(*id))
+        .and_then(|face| face.families.first())
+        .map(|(family, _)| family.clone())
+        .ok_or_else(|| format!("Unsupported font file: {}", font))?;
+    Ok((Arc::new(db), family))
+}
+
+// 텍스트를 직선 알파 RGBA로 렌더링 (글자 영역만큼 잘라서 반환)
+fn render_text(text: &str, font: Option<&str>, font_size: f32, color: &str) -> Result<(Vec<u8>, u32, u32), String> {
+    let (fontdb, family) = resolve_font(font)?;
+    let svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="1" height="1"><text x="0" y="0" font-family="{}" font-size="{}" fill="{}">{}</text></svg>"#,
+        escape_xml(&family),
+        font_size,
+        escape_xml(color),
+        escape_xml(text),
+    );
+
+    let options = usvg::Options {
+        fontdb,
+        ..usvg::Options::default()
+    };
+    let tree = usvg::Tree::from_str(&svg, &options).map_err(|e| format!("Failed to render watermark text: {}", e))?;
+
+    // 글꼴이 없으면 글자 경로가 비어 있음
+    let bounds = tree.root().abs_bounding_box();
+    let width = bounds.width().ceil() as u32;
+    let height = bounds.height().ceil() as u32;
+    if width == 0 || height == 0 {
+        return Err("Watermark text has no glyphs (font not found)".to_string());
+    }
+    let mut pixmap = resvg::tiny_skia::Pixmap::new(width, height).ok_or("Failed to create pixmap for watermark")?;
+
+    let transform = resvg::tiny_skia::Transform::from_translate(-bounds.x(), -bounds.y());
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+
+    // tiny_skia는 premultiplied RGBA → 직선 알파로 변환
+    let rgba = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|p| {
+            let c = p.demultiply();
+            [c.red(), c.green(), c.blue(), c.alpha()]
+        })
+        .collect();
+    Ok((rgba, width, height))
+}
+
+// 로고 PNG를 목표 너비로 축소/확대 (직선 알파 RGBA)
+fn render_logo(path: &str, target_width: u32) -> Result<(Vec<u8>, u32, u32), String> {
+    let logo = image::open(crate::fs_path::to_fs_string(path))
+        .map_err(|e| format!("Failed to open watermark logo: {}", e))?
+        .into_rgba8();
+    let (src_width, src_height) = logo.dimensions();
+    let width = target_width.max(1);
+    let height = ((src_height as f64 * width as f64 / src_width.max(1) as f64).round() as u32).max(1);
+    if (width, height) == (src_width, src_height) {
+        return Ok((logo.into_raw(), width, height));
+    }
+
+    let pixels = thumbnail::resize_fast(
+        logo.into_raw(),
+        src_width,
+        src_height,
+        width,
+        height,
+        fast_image_resize::PixelType::U8x4,
+    )?;
+    Ok((pixels, width, height))
+}
+
+/// 워터마크 합성 (pixels는 RGB 또는 RGBA, channels로 구분)
+pub fn apply(pixels: &mut [u8], width: u32, height: u32, channels: usize, options: &WatermarkOptions) -> Result<(), String> {
+    let short_edge = width.min(height) as f32;
+    let (overlay, overlay_width, overlay_height) = match &options.content {
+        WatermarkContent::Text { text, font, size, color } => {
+            render_text(text, font.as_deref(), (short_edge * size / 100.0).max(1.0), color)?
+        }
+        WatermarkContent::Logo { path, scale } => render_logo(path, (width as f32 * scale / 100.0).round() as u32)?,
+    };
+
+    // 여백을 뺀 영역 안에서 기준 위치에 배치 (넘치는 부분은 잘림)
+    let margin = (short_edge * options.margin / 100.0).round() as i64;
+    let (align_x, align_y) = options.anchor.alignment();
+    let free_width = width as i64 - 2 * margin - overlay_width as i64;
+    let free_height = height as i64 - 2 * margin - overlay_height as i64;
+    let left = margin + (free_width as f32 * align_x).round() as i64;
+    let top = margin + (free_height as f32 * align_y).round() as i64;
+
+    let opacity = options.opacity.clamp(0.0, 1.0);
+    for oy in 0..overlay_height as i64 {
+        let y = top + oy;
+        if y < 0 || y >= height as i64 {
+            continue;
+        }
+        for ox in 0..overlay_width as i64 {
+            let x = left + ox;
+            if x < 0 || x >= width as i64 {
+                continue;
+            }
+            let src = &overlay[(oy as usize * overlay_width as usize + ox as usize) * 4..][..4];
+            let alpha = src[3] as f32 / 255.0 * opacity;
+            if alpha <= 0.0 {
+                continue;
+            }
+
+            let dst = &mut pixels[(y as usize * width as usize + x as usize) * channels..][..channels];
+            if channels == 4 {
+                // 투명 배경 위에서는 알파까지 합성
+                let dst_alpha = dst[3] as f32 / 255.0;
+                let out_alpha = alpha + dst_alpha * (1.0 - alpha);
+                for (d, s) in dst.iter_mut().zip(src).take(3) {
+                    let blended = (*s as f32 * alpha + *d as f32 * dst_alpha * (1.0 - alpha)) / out_alpha;
+                    *d = blended.round().clamp(0.0, 255.0) as u8;
+                }
+                dst[3] = (out_alpha * 255.0).round() as u8;
+            } else {
+                for (d, s) in dst.iter_mut().zip(src) {
+                    *d = (*s as f32 * alpha + *d as f32 * (1.0 - alpha)).round() as u8;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 워터마크를 적용한 샘플 썸네일 (WebP, 설정 화면 미리보기용)
+pub fn render_preview(
+    path: &Path,
+    options: &WatermarkOptions,
+    max_size: Option<u32>,
+) -> Result<crate::preview_filter::PreviewImage, String> {
+    let (img, icc_profile) = crate::export::decode(path)?;
+    let (src_width, src_height) = (img.width(), img.height());
+    let (width, height) = thumbnail::fit_within(src_width, src_height, max_size.unwrap_or(PREVIEW_MAX_SIZE));
+
+    let mut rgb = thumbnail::resize_fast(
+        img.into_rgb8().into_raw(),
+        src_width,
+        src_height,
+        width,
+        height,
+        fast_image_resize::PixelType::U8x3,
+    )?;
+    crate::color_profile::convert_to_srgb(icc_profile.as_deref(), &mut rgb, crate::color_profile::PixelLayout::Rgb8);
+    apply(&mut rgb, width, height, 3, options)?;
+
+    let webp = thumbnail::encode_thumbnail_to_webp(&rgb, width, height, false, 85.0)?;
+    Ok(crate::preview_filter::PreviewImage {
+        data_base64: thumbnail::encode_to_base64(&webp),
+        width,
+        height,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(escape_xml("A & B <C> \"D\""), "A &amp; B &lt;C&gt; &quot;D&quot;");
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut options = WatermarkOptions {
+            content: WatermarkContent::Text {
+                text: "© PixEngine".to_string(),
+                font: None,
+                size: 5.0,
+                color: "#ffffff".to_string(),
+            },
+            opacity: 0.5,
+            anchor: WatermarkAnchor::BottomRight,
+            margin: 3.0,
+        };
+        assert!(options.validate().is_ok());
+        options.opacity = 1.5;
+        assert!(options.validate().is_err());
+        options.opacity = 0.5;
+        options.content = WatermarkContent::Logo {
+            path: "logo.png".to_string(),
+            scale: 0.0,
+        };
+        assert!(options.validate().is_err());
+    }
+}
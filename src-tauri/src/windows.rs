@@ -0,0 +1,95 @@
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+
+/// 보조 윈도우 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WindowKind {
+    Viewer,
+    Compare,
+}
+
+impl WindowKind {
+    fn label_prefix(self) -> &'static str {
+        match self {
+            WindowKind::Viewer => "viewer",
+            WindowKind::Compare => "compare",
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            WindowKind::Viewer => "PixEngine Viewer",
+            WindowKind::Compare => "PixEngine Compare",
+        }
+    }
+}
+
+/// 보조 윈도우가 표시할 내용 (프론트엔드가 시작할 때 조회)
+#[derive(Debug, Clone, Serialize)]
+pub struct WindowPayload {
+    pub kind: WindowKind,
+    pub paths: Vec<String>,
+}
+
+lazy_static! {
+    /// 윈도우 라벨 → 표시 내용
+    static ref PAYLOADS: Mutex<HashMap<String, WindowPayload>> = Mutex::new(HashMap::new());
+}
+
+/// 비어 있는 가장 작은 번호로 라벨 생성
+/// (번호를 재사용해야 라벨별로 저장된 윈도우 상태가 다음 실행에도 적용됨)
+fn next_label(app: &AppHandle, kind: WindowKind) -> String {
+    (1..)
+        .map(|n| format!("{}-{}", kind.label_prefix(), n))
+        .find(|label| app.get_webview_window(label).is_none())
+        .expect("unbounded label range")
+}
+
+pub fn payload(label: &str) -> Option<WindowPayload> {
+    PAYLOADS.lock().ok()?.get(label).cloned()
+}
+
+/// 보조 윈도우 생성
+/// 썸네일/별점 이벤트는 AppHandle::emit으로 모든 윈도우에 전달되므로 별도 라우팅 불필요
+pub fn open_window(app: &AppHandle, kind: WindowKind, paths: Vec<String>) -> Result<String, String> {
+    let label = next_label(app, kind);
+
+    if let Ok(mut payloads) = PAYLOADS.lock() {
+        payloads.insert(label.clone(), WindowPayload { kind, paths });
+    }
+
+    let window = WebviewWindowBuilder::new(app, &label, WebviewUrl::App("index.html".into()))
+        .title(kind.title())
+        .inner_size(1200.0, 800.0)
+        .min_inner_size(800.0, 600.0)
+        .decorations(false)
+        .disable_drag_drop_handler()
+        .visible(false)
+        .background_color(tauri::window::Color(0x17, 0x17, 0x17, 0xff))
+        .build()
+        .map_err(|e| {
+            if let Ok(mut payloads) = PAYLOADS.lock() {
+                payloads.remove(&label);
+            }
+            format!("Failed to create window: {}", e)
+        })?;
+
+    crate::restore_window_state(&window);
+
+    // 윈도우가 닫히면 표시 내용 정리
+    let closed_label = label.clone();
+    window.on_window_event(move |event| {
+        if let tauri::WindowEvent::Destroyed = event {
+            if let Ok(mut payloads) = PAYLOADS.lock() {
+                payloads.remove(&closed_label);
+            }
+        }
+    });
+
+    tracing::info!("Opened {} window", label);
+    Ok(label)
+}
@@ -1,12 +1,14 @@
 import { useState, useCallback, useEffect } from "react";
 import { getCurrentWindow } from "@tauri-apps/api/window";
+import { invoke } from "@tauri-apps/api/core";
 import { TitleBar } from "./components/layout/TitleBar";
 import { MainLayout } from "./components/layout/MainLayout";
 import { StatusBar } from "./components/layout/StatusBar";
 import { useWindowState } from "./hooks/useWindowState";
+import { useOpenTarget } from "./hooks/useOpenTarget";
 import { theme } from "./lib/theme";
 import { FolderProvider, useFolderContext } from "./contexts/FolderContext";
-import { ImageProvider } from "./contexts/ImageContext";
+import { ImageProvider, useImageContext } from "./contexts/ImageContext";
 import { WindowFocusProvider } from "./contexts/WindowFocusContext";
 import { DialogProvider } from "./contexts/DialogContext";
 import { ToastProvider } from "./contexts/ToastContext";
@@ -15,6 +17,12 @@ import { useViewerStore } from "./store/viewerStore";
 
 const appWindow = getCurrentWindow();
 
+// 보조 뷰어/비교 윈도우가 표시할 내용 (메인 윈도우는 null)
+interface WindowPayload {
+  kind: 'viewer' | 'compare';
+  paths: string[];
+}
+
 function AppContent() {
   const { refreshCurrentFolder, currentFolder } = useFolderContext();
 
@@ -22,6 +30,28 @@ function AppContent() {
   const setToggleFullscreen = useViewerStore((state) => state.setToggleFullscreen);
   const isFullscreenViewer = useViewerStore((state) => state.isFullscreenViewer);
   const setIsFullscreenViewer = useViewerStore((state) => state.setIsFullscreenViewer);
+  const { loadImage } = useImageContext();
+
+  // 실행 인자/두 번째 실행으로 전달된 폴더·이미지 열기
+  useOpenTarget();
+
+  // 보조 윈도우: 열 때 지정된 이미지를 전체화면 뷰어로 표시
+  useEffect(() => {
+    if (appWindow.label === 'main') {
+      return;
+    }
+
+    invoke<WindowPayload | null>('get_window_payload')
+      .then((payload) => {
+        if (payload && payload.paths.length > 0) {
+          setIsFullscreenViewer(true);
+          loadImage(payload.paths[0]);
+        }
+      })
+      .catch((error) => {
+        console.error('[App] Failed to load window payload:', error);
+      });
+  }, [loadImage, setIsFullscreenViewer]);
 
   // 브라우저 기본 컨텍스트 메뉴 비활성화
   useEffect(() => {
@@ -2,12 +2,14 @@ import { Folder, FolderOpen, ChevronRight, ChevronDown, HardDrive, Monitor, Star
 import { useState, useEffect, useRef } from "react";
 import { createPortal } from "react-dom";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { load } from "@tauri-apps/plugin-store";
 import { useFolderContext } from "../../contexts/FolderContext";
 import { useImageContext } from "../../contexts/ImageContext";
 import { useDialog } from "../../contexts/DialogContext";
 import { useToast } from "../../contexts/ToastContext";
 import { normalizePath } from "../../lib/pathUtils";
+import { getErrorMessage } from "../../lib/errorHandler";
 
 interface FolderNode {
   name: string;
@@ -20,6 +22,20 @@ interface FolderNode {
   isFavorite?: boolean;
   icon?: 'computer' | 'star';
   treeId?: string; // 트리 구분용 ID (main, favorites 등)
+  isCloudPlaceholder?: boolean; // 클라우드 전용 폴더 (OneDrive 등)
+}
+
+interface DirectoryEntry {
+  name: string;
+  path: string;
+  isDir: boolean;
+  isCloudPlaceholder: boolean;
+}
+
+// 읽지 못한 항목은 errors에 이유와 함께 포함됨
+interface DirectoryListing {
+  entries: DirectoryEntry[];
+  errors: Array<{ name: string; path: string; error: { kind: string; message: string } }>;
 }
 
 const IMAGE_EXTENSIONS = [
@@ -61,6 +77,7 @@ export function FolderTreePanel() {
   const [favorites, setFavorites] = useState<Favorite[]>([]);
   const [contextMenu, setContextMenu] = useState<{ x: number; y: number; node: FolderNode } | null>(null);
   const folderRefreshCallbacks = useRef<Map<string, () => void>>(new Map());
+  const treeWatchTimerRef = useRef<ReturnType<typeof setTimeout> | null>(null);
   const [renamingNode, setRenamingNode] = useState<FolderNode | null>(null);
   const [selectedNode, setSelectedNode] = useState<FolderNode | null>(null);
   const panelRef = useRef<HTMLDivElement>(null);
@@ -74,6 +91,21 @@ export function FolderTreePanel() {
     initialize();
   }, []);
 
+  // 탐색기 등에서 하위 폴더가 생성/삭제/이름 변경되면 해당 폴더 다시 읽기
+  useEffect(() => {
+    const unlisten = listen<{ parent: string }>('folder-tree-changed', (event) => {
+      folderRefreshCallbacks.current.get(event.payload.parent)?.();
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+      if (treeWatchTimerRef.current) {
+        clearTimeout(treeWatchTimerRef.current);
+      }
+      invoke('stop_folder_tree_watch').catch(() => {});
+    };
+  }, []);
+
   // 컨텍스트 메뉴 외부 클릭 감지
   useEffect(() => {
     const handleClickOutside = () => {
@@ -300,6 +332,17 @@ export function FolderTreePanel() {
 
   const registerFolderRefresh = (path: string, callback: () => void) => {
     folderRefreshCallbacks.current.set(path, callback);
+
+    // 표시된 폴더들의 하위 폴더 변경 감시 (노드가 연달아 추가되므로 모아서 한 번에)
+    if (treeWatchTimerRef.current) {
+      clearTimeout(treeWatchTimerRef.current);
+    }
+    treeWatchTimerRef.current = setTimeout(() => {
+      treeWatchTimerRef.current = null;
+      invoke('watch_folder_tree', { roots: Array.from(folderRefreshCallbacks.current.keys()) }).catch((err) => {
+        console.warn('Failed to watch folder tree:', err);
+      });
+    }, 500);
   };
 
   const handleRenameComplete = async (oldPath: string, _newName?: string, wasRenamed?: boolean) => {
@@ -325,7 +368,7 @@ export function FolderTreePanel() {
 
     try {
       // 현재 폴더의 내용 읽기
-      const entries = await invoke<Array<{ name: string; path: string; isDir: boolean }>>(
+      const { entries } = await invoke<DirectoryListing>(
         "read_directory_contents",
         { path: targetNode.path }
       );
@@ -366,7 +409,7 @@ export function FolderTreePanel() {
         isOpen: false
       });
     } catch (error) {
-      toast.error(`폴더 생성 실패: ${error}`);
+      toast.error(`폴더 생성 실패: ${getErrorMessage(error)}`);
     }
   };
 
@@ -384,13 +427,13 @@ export function FolderTreePanel() {
 
     try {
       // 폴더 내용 확인
-      const entries = await invoke<Array<{ name: string; path: string; isDir: boolean }>>(
+      const { entries, errors } = await invoke<DirectoryListing>(
         "read_directory_contents",
         { path: targetNode.path }
       );
 
-      // 폴더가 비어있지 않으면 삭제 불가
-      if (entries.length > 0) {
+      // 폴더가 비어있지 않으면 삭제 불가 (읽지 못한 항목도 포함)
+      if (entries.length + errors.length > 0) {
         await dialog.showAlert(
           `"${targetNode.name}" 폴더를 삭제할 수 없습니다.\n\n폴더 내에 파일이나 하위 폴더가 있습니다.\n먼저 폴더를 비운 후 삭제해주세요.`,
           { icon: 'error' }
@@ -422,7 +465,7 @@ export function FolderTreePanel() {
         window.location.reload();
       }
     } catch (error) {
-      toast.error(`폴더 삭제 실패: ${error}`);
+      toast.error(`폴더 삭제 실패: ${getErrorMessage(error)}`);
     }
   };
 
@@ -728,13 +771,13 @@ function FolderTreeItem({
       if (node.isDrive) {
         setIsLoading(true);
         try {
-          const entries = await invoke<Array<{ name: string; path: string; isDir: boolean }>>(
+          const { entries } = await invoke<DirectoryListing>(
             "read_directory_contents",
             { path: node.path }
           );
           const folderNodes: FolderNode[] = entries
             .filter(entry => entry.isDir)
-            .map(entry => ({ name: entry.name, path: entry.path, isOpen: false, children: undefined, treeId: node.treeId }))
+            .map(entry => ({ name: entry.name, path: entry.path, isOpen: false, children: undefined, treeId: node.treeId, isCloudPlaceholder: entry.isCloudPlaceholder }))
             .sort((a, b) => a.name.localeCompare(b.name));
           setChildren(folderNodes);
           setIsOpen(true);
@@ -759,11 +802,15 @@ function FolderTreeItem({
 
     setIsLoading(true);
     try {
-      const entries = await invoke<Array<{ name: string; path: string; isDir: boolean }>>(
+      const { entries, errors } = await invoke<DirectoryListing>(
         "read_directory_contents",
         { path: node.path }
       );
 
+      if (errors.length > 0) {
+        console.warn(`Failed to read ${errors.length} entries in:`, node.path, errors);
+      }
+
       // 폴더와 이미지 파일 분리
       const folderNodes: FolderNode[] = [];
       const imageFiles: string[] = [];
@@ -776,6 +823,7 @@ function FolderTreeItem({
             isOpen: false,
             children: undefined,
             treeId: node.treeId, // 부모의 treeId 상속
+            isCloudPlaceholder: entry.isCloudPlaceholder,
           });
         } else {
           // 이미지 파일인지 확인
@@ -947,7 +995,7 @@ function FolderTreeItem({
         onRenameComplete(node.path, newName.trim(), true);
       }
     } catch (error) {
-      toast.error(`이름 변경 실패: ${error}`);
+      toast.error(`이름 변경 실패: ${getErrorMessage(error)}`);
       if (onRenameComplete) {
         onRenameComplete(node.path, undefined, false);
       }
@@ -1021,7 +1069,7 @@ function FolderTreeItem({
             />
           ) : (
             <span
-              className={`text-xs flex-1 truncate ${isCurrentFolder ? 'text-blue-300 font-semibold' : 'text-gray-200'}`}
+              className={`text-xs flex-1 truncate ${isCurrentFolder ? 'text-blue-300 font-semibold' : 'text-gray-200'} ${node.isCloudPlaceholder ? 'opacity-50' : ''}`}
               title={node.name}
             >
               {node.name}
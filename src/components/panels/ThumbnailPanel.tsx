@@ -9,7 +9,7 @@ import { useDebounce } from '../../hooks/useDebounce'
 import { useToast } from '../../contexts/ToastContext'
 import { useDialog } from '../../contexts/DialogContext'
 import { Store } from '@tauri-apps/plugin-store'
-import { logError } from '../../lib/errorHandler'
+import { logError, getErrorMessage } from '../../lib/errorHandler'
 import { useViewerStore } from '../../store/viewerStore'
 import { writeImageRating } from '../../lib/rating'
 import { ContextMenu, ContextMenuItem, ContextMenuDivider, ContextMenuSubmenu } from '../common/ContextMenu'
@@ -20,6 +20,7 @@ import {
   THUMBNAIL_SIZE_MIN,
   THUMBNAIL_SIZE_MAX,
   THUMBNAIL_GAP,
+  THUMBNAIL_TIER_BASE,
   DEBOUNCE_FOCUS_INDEX,
   VIRTUAL_SCROLL_OVERSCAN,
   FOLDER_WATCH_RESUME_DELAY
@@ -37,6 +38,7 @@ interface ThumbnailResult {
   height: number
   source: 'cache' | 'exif' | 'dct'
   exif_metadata?: ExifMetadata
+  has_alpha?: boolean
 }
 
 interface ExifMetadata {
@@ -54,10 +56,41 @@ interface ExifMetadata {
   height?: number
 }
 
+// get_fast_placeholder 결과 (큐가 파일에 닿기 전 먼저 그릴 것)
+type FastPlaceholder =
+  | { kind: 'cached' | 'exif'; thumbnail_base64: string; width: number; height: number; orientation: number }
+  | { kind: 'color'; width: number; height: number; orientation: number; color?: string | null }
+
+// 4x4 색 격자 문자열("rrggbb" 16개)을 CSS 색 배열로
+function parseColorGrid(grid?: string | null): string[] | null {
+  if (!grid || grid.length !== 96) return null
+  return Array.from({ length: 16 }, (_, i) => `#${grid.slice(i * 6, i * 6 + 6)}`)
+}
+
 interface ThumbnailProgress {
   completed: number
   total: number
   current_path: string
+  eta_seconds?: number | null // 남은 예상 시간 (초, 샘플이 모이기 전에는 null)
+  items_per_second?: number | null
+}
+
+// 남은 시간 표시 ("약 3분 남음")
+function formatEta(seconds: number): string {
+  if (seconds < 60) return `약 ${Math.max(1, Math.round(seconds))}초 남음`
+  if (seconds < 3600) return `약 ${Math.round(seconds / 60)}분 남음`
+  return `약 ${Math.floor(seconds / 3600)}시간 ${Math.round((seconds % 3600) / 60)}분 남음`
+}
+
+interface HqProgress extends ThumbnailProgress {
+  cursor: number
+  delivered: boolean
+}
+
+interface HqResultsPage {
+  results: ThumbnailResult[]
+  cursor: number
+  has_more: boolean
 }
 
 export const ThumbnailPanel = memo(function ThumbnailPanel() {
@@ -68,6 +101,10 @@ export const ThumbnailPanel = memo(function ThumbnailPanel() {
   const { success, error } = useToast()
   const { showConfirm } = useDialog()
   const [thumbnails, setThumbnails] = useState<Map<string, ThumbnailResult>>(new Map())
+  const [tierThumbnails, setTierThumbnails] = useState<Map<string, ThumbnailResult>>(new Map()) // HiDPI용 큰 썸네일 (보이는 셀만)
+  const tierRequestedRef = useRef<Set<string>>(new Set()) // 큰 썸네일을 요청한 경로 (중복 요청 방지)
+  const [placeholderColors, setPlaceholderColors] = useState<Map<string, string>>(new Map()) // 썸네일 전 평균 색 (경로 -> #rrggbb)
+  const placeholderRequestedRef = useRef<Set<string>>(new Set()) // 자리 표시를 요청한 경로
   const [progress, setProgress] = useState<ThumbnailProgress | null>(null)
   const [isGenerating, setIsGenerating] = useState(false)
   const [hqProgress, setHqProgress] = useState<ThumbnailProgress | null>(null)
@@ -110,6 +147,7 @@ export const ThumbnailPanel = memo(function ThumbnailPanel() {
   const [ratingMatchMode, setRatingMatchMode] = useState<RatingMatchMode>('exact')
   const [isFilterDropdownOpen, setIsFilterDropdownOpen] = useState(false)
   const filterDropdownRef = useRef<HTMLDivElement>(null)
+  const hqCatchUpCursorRef = useRef<number | null>(null) // 창이 숨겨진 동안 처음 놓친 HQ 결과 커서
 
   // 연속 재생 모드 상태
   const [continuousPlayState, setContinuousPlayState] = useState<{
@@ -532,6 +570,126 @@ export const ThumbnailPanel = memo(function ThumbnailPanel() {
     }
   }, [isGeneratingHq, isVertical, columnCount, imageFiles.length])
 
+  // 가상 스크롤로 렌더링 중인 이미지 경로
+  const getVisiblePaths = () => {
+    const visiblePaths: string[] = []
+    if (isVertical) {
+      rowVirtualizer.getVirtualItems().forEach((virtualRow) => {
+        const startIndex = virtualRow.index * columnCount
+        const endIndex = Math.min(startIndex + columnCount, sortedImages.length)
+        visiblePaths.push(...sortedImages.slice(startIndex, endIndex))
+      })
+    } else {
+      horizontalVirtualizer.getVirtualItems().forEach((item) => {
+        visiblePaths.push(sortedImages[item.index])
+      })
+    }
+    return visiblePaths
+  }
+
+  // 썸네일이 아직 없는 보이는 셀은 캐시/EXIF 썸네일이나 평균 색을 먼저 표시
+  useEffect(() => {
+    const scrollArea = scrollAreaRef.current
+    if (!scrollArea) return
+
+    let timeoutId: number
+
+    const requestPlaceholders = () => {
+      clearTimeout(timeoutId)
+      timeoutId = setTimeout(() => {
+        for (const path of getVisiblePaths()) {
+          if (!path || thumbnails.has(path) || placeholderRequestedRef.current.has(path)) continue
+          placeholderRequestedRef.current.add(path)
+
+          invoke<FastPlaceholder | null>('get_fast_placeholder', { path })
+            .then((placeholder) => {
+              if (!placeholder) return
+              if (placeholder.kind === 'color') {
+                if (!placeholder.color) return
+                const color = placeholder.color
+                setPlaceholderColors((prev) => new Map(prev).set(path, color))
+                return
+              }
+              // 생성 결과가 먼저 왔으면 유지
+              setThumbnails((prev) => {
+                if (prev.has(path)) return prev
+                const next = new Map(prev)
+                next.set(path, {
+                  path,
+                  thumbnail_base64: placeholder.thumbnail_base64,
+                  width: placeholder.width,
+                  height: placeholder.height,
+                  source: placeholder.kind === 'cached' ? 'cache' : 'exif',
+                  exif_metadata: { orientation: placeholder.orientation },
+                })
+                return next
+              })
+            })
+            .catch((error) => logError(error, 'Get fast placeholder'))
+        }
+      }, 50)
+    }
+
+    scrollArea.addEventListener('scroll', requestPlaceholders, { passive: true })
+    requestPlaceholders()
+
+    return () => {
+      clearTimeout(timeoutId)
+      scrollArea.removeEventListener('scroll', requestPlaceholders)
+    }
+  }, [isVertical, columnCount, thumbnails, sortedImages])
+
+  // HiDPI 화면에서 셀이 기본 썸네일보다 크면 보이는 셀만 큰 썸네일로 교체 (기본 썸네일을 먼저 표시)
+  useEffect(() => {
+    const devicePixelRatio = window.devicePixelRatio || 1
+    const cellSize = isVertical ? rowHeight - THUMBNAIL_GAP : thumbnailSize
+    if (cellSize * devicePixelRatio <= THUMBNAIL_TIER_BASE) return
+
+    const scrollArea = scrollAreaRef.current
+    if (!scrollArea) return
+
+    let timeoutId: number
+
+    const promoteVisible = () => {
+      clearTimeout(timeoutId)
+      timeoutId = setTimeout(() => {
+        for (const path of getVisiblePaths()) {
+          const base = thumbnails.get(path)
+          // 기본 썸네일이 나온 뒤에만 교체, 실패한 파일은 다시 요청하지 않음
+          if (!base || tierRequestedRef.current.has(path)) continue
+          tierRequestedRef.current.add(path)
+
+          invoke<ThumbnailResult>('generate_tier_thumbnail', { filePath: path, cellSize, devicePixelRatio })
+            .then((result) => {
+              setTierThumbnails((prev) => {
+                const next = new Map(prev)
+                // 방향 정보는 기본 썸네일의 EXIF 사용
+                next.set(path, { ...result, exif_metadata: result.exif_metadata ?? base.exif_metadata })
+                return next
+              })
+            })
+            .catch((error) => logError(error, 'Generate tier thumbnail'))
+        }
+      }, 300)
+    }
+
+    scrollArea.addEventListener('scroll', promoteVisible, { passive: true })
+    promoteVisible()
+
+    return () => {
+      clearTimeout(timeoutId)
+      scrollArea.removeEventListener('scroll', promoteVisible)
+    }
+  }, [isVertical, columnCount, rowHeight, thumbnailSize, thumbnails, sortedImages])
+
+  // 폴더가 바뀌면 큰 썸네일과 자리 표시 초기화
+  useEffect(() => {
+    setTierThumbnails(new Map())
+    tierRequestedRef.current.clear()
+    setPlaceholderColors(new Map())
+    placeholderRequestedRef.current.clear()
+  }, [currentFolder])
+
   // focusedIndex 변경 시 자동 스크롤
   useEffect(() => {
     if (focusedIndex < 0 || focusedIndex >= sortedImages.length) return
@@ -690,7 +848,7 @@ export const ThumbnailPanel = memo(function ThumbnailPanel() {
         setCutImages(new Set())
       }
     } catch (err) {
-      error(err as string)
+      error(getErrorMessage(err))
     }
   }, [currentFolder, overwriteFiles, skipFiles, success, error])
 
@@ -734,7 +892,7 @@ export const ThumbnailPanel = memo(function ThumbnailPanel() {
               setCutImages(new Set())
             }
           } catch (err) {
-            error(err as string)
+            error(getErrorMessage(err))
           }
         } else {
           const newSkipFiles = [...skipFiles, ...allConflictFiles]
@@ -756,7 +914,7 @@ export const ThumbnailPanel = memo(function ThumbnailPanel() {
               setCutImages(new Set())
             }
           } catch (err) {
-            error(err as string)
+            error(getErrorMessage(err))
           }
         }
       } else {
@@ -902,7 +1060,7 @@ export const ThumbnailPanel = memo(function ThumbnailPanel() {
         scrollAreaRef.current?.focus()
       })
     } catch (err) {
-      error(err as string)
+      error(getErrorMessage(err))
       setRenamingImage(null)
       setNewFileName('')
       // 에러 발생 시에도 폴더 감시 재개
@@ -972,7 +1130,7 @@ export const ThumbnailPanel = memo(function ThumbnailPanel() {
       setSelectedImages(new Set())
       setCutImages(new Set())
     } catch (err) {
-      error(err as string)
+      error(getErrorMessage(err))
     }
   }, [selectedImages, showConfirm, success, error])
 
@@ -1421,6 +1579,72 @@ export const ThumbnailPanel = memo(function ThumbnailPanel() {
     }
   }, [])
 
+  // 창이 다시 보이면 숨겨진 동안 놓친 HQ 썸네일 가져오기
+  useEffect(() => {
+    const catchUp = async () => {
+      let cursor = hqCatchUpCursorRef.current
+      if (cursor === null || document.hidden) return
+      hqCatchUpCursorRef.current = null
+
+      try {
+        for (;;) {
+          const page = await invoke<HqResultsPage>('get_hq_results_since', { cursor })
+          if (page.results.length > 0) {
+            setThumbnails((prev) => {
+              const next = new Map(prev)
+              for (const result of page.results) {
+                next.set(result.path, result)
+              }
+              return next
+            })
+          }
+          cursor = page.cursor
+          if (!page.has_more) break
+        }
+      } catch (error) {
+        logError(error, 'Load missed HQ thumbnails')
+      }
+    }
+
+    document.addEventListener('visibilitychange', catchUp)
+    window.addEventListener('focus', catchUp)
+    return () => {
+      document.removeEventListener('visibilitychange', catchUp)
+      window.removeEventListener('focus', catchUp)
+    }
+  }, [])
+
+  // 별점 쓰기 실패 이벤트 리스너 (쓰기는 백엔드 큐에서 나중에 처리됨)
+  useEffect(() => {
+    const unlisten = listen<{
+      path: string
+      rating: number
+      conflict: boolean
+      read_only: boolean
+      pending: boolean
+      error: string
+    }>(
+      'rating-write-failed',
+      (event) => {
+        const { path, conflict, read_only, pending, error: message } = event.payload
+        const fileName = path.split(/[/\\]/).pop() || path
+        if (conflict) {
+          error(`다른 프로그램이 "${fileName}"을(를) 수정해서 별점을 저장하지 않았습니다`)
+        } else if (read_only && pending) {
+          error(`"${fileName}"이(가) 읽기 전용이라 별점을 앱에 보관했습니다. 쓸 수 있게 되면 파일에 저장합니다`)
+        } else if (read_only) {
+          error(`"${fileName}"이(가) 읽기 전용이라 별점을 저장하지 못했습니다`)
+        } else {
+          error(`별점 저장 실패 (${fileName}): ${message}`)
+        }
+      }
+    )
+
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [error])
+
   // 진행률 이벤트 리스너
   useEffect(() => {
     const unlistenProgress = listen<ThumbnailProgress>('thumbnail-progress', (event) => {
@@ -1433,6 +1657,41 @@ export const ThumbnailPanel = memo(function ThumbnailPanel() {
         next.set(event.payload.path, event.payload)
         return next
       })
+      // 파일이 바뀌어 다시 생성된 썸네일이면 큰 썸네일도 다시 요청
+      tierRequestedRef.current.delete(event.payload.path)
+      setTierThumbnails((prev) => {
+        if (!prev.has(event.payload.path)) return prev
+        const next = new Map(prev)
+        next.delete(event.payload.path)
+        return next
+      })
+    })
+
+    // 이미지가 많은 폴더는 완료 이벤트가 묶어서 옴
+    const unlistenCompletedBatch = listen<{ results: ThumbnailResult[]; progress: ThumbnailProgress }>(
+      'thumbnail-completed-batch',
+      (event) => {
+        setThumbnails((prev) => {
+          const next = new Map(prev)
+          for (const result of event.payload.results) {
+            next.set(result.path, result)
+          }
+          return next
+        })
+      }
+    )
+
+    // 이전에 열었던 폴더의 완료 썸네일 (생성 결과가 이미 있으면 유지)
+    const unlistenWarmCache = listen<ThumbnailResult[]>('thumbnail-warm-cache', (event) => {
+      setThumbnails((prev) => {
+        const next = new Map(prev)
+        for (const result of event.payload) {
+          if (!next.has(result.path)) {
+            next.set(result.path, result)
+          }
+        }
+        return next
+      })
     })
 
     const unlistenAllCompleted = listen('thumbnail-all-completed', async () => {
@@ -1474,8 +1733,13 @@ export const ThumbnailPanel = memo(function ThumbnailPanel() {
     })
 
     // 고화질 썸네일 이벤트 리스너
-    const unlistenHqProgress = listen<ThumbnailProgress>('thumbnail-hq-progress', (event) => {
-      setHqProgress(event.payload)
+    // 창이 숨겨진 동안은 결과 없이 진행 수만 오므로, 처음 놓친 결과의 커서를 기억했다가 다시 보일 때 가져옴
+    const unlistenHqProgress = listen<HqProgress>('thumbnail-hq-progress', (event) => {
+      const { cursor, delivered, ...progress } = event.payload
+      if (!delivered && hqCatchUpCursorRef.current === null) {
+        hqCatchUpCursorRef.current = cursor - 1
+      }
+      setHqProgress(progress)
     })
 
     const unlistenHqCompleted = listen<ThumbnailResult>('thumbnail-hq-completed', (event) => {
@@ -1513,6 +1777,8 @@ export const ThumbnailPanel = memo(function ThumbnailPanel() {
     return () => {
       unlistenProgress.then((fn) => fn())
       unlistenCompleted.then((fn) => fn())
+      unlistenCompletedBatch.then((fn) => fn())
+      unlistenWarmCache.then((fn) => fn())
       unlistenAllCompleted.then((fn) => fn())
       unlistenHqProgress.then((fn) => fn())
       unlistenHqCompleted.then((fn) => fn())
@@ -1855,7 +2121,8 @@ export const ThumbnailPanel = memo(function ThumbnailPanel() {
                   >
                     {rowImages.map((imagePath, colIndex) => {
                       const index = virtualRow.index * columnCount + colIndex
-                      const thumbnail = thumbnails.get(imagePath)
+                      const thumbnail = tierThumbnails.get(imagePath) ?? thumbnails.get(imagePath)
+                      const colorGrid = thumbnail ? null : parseColorGrid(lightMetadataMap.get(imagePath)?.color_grid)
                       const transform = thumbnail?.exif_metadata
                         ? getOrientationTransform(thumbnail.exif_metadata.orientation)
                         : ''
@@ -1933,8 +2200,19 @@ export const ThumbnailPanel = memo(function ThumbnailPanel() {
                                 loading="lazy"
                               />
                             ) : (
-                              <div className="flex h-full items-center justify-center">
-                                <Loader2 className="h-6 w-6 animate-spin text-gray-600" />
+                              <div
+                                className="relative flex h-full items-center justify-center overflow-hidden"
+                                style={{ backgroundColor: placeholderColors.get(imagePath) }}
+                              >
+                                {/* 이전에 썸네일을 만든 파일은 색 격자를 흐리게 표시 */}
+                                {colorGrid && (
+                                  <div className="absolute inset-0 grid scale-110 grid-cols-4 grid-rows-4 blur-md">
+                                    {colorGrid.map((color, i) => (
+                                      <div key={i} style={{ backgroundColor: color }} />
+                                    ))}
+                                  </div>
+                                )}
+                                <Loader2 className="relative h-6 w-6 animate-spin text-gray-600" />
                               </div>
                             )}
                             {/* 별점 배지 (우측 상단) */}
@@ -1994,7 +2272,8 @@ export const ThumbnailPanel = memo(function ThumbnailPanel() {
           >
             {horizontalVirtualizer.getVirtualItems().map((virtualItem) => {
               const imagePath = sortedImages[virtualItem.index]
-              const thumbnail = thumbnails.get(imagePath)
+              const thumbnail = tierThumbnails.get(imagePath) ?? thumbnails.get(imagePath)
+              const colorGrid = thumbnail ? null : parseColorGrid(lightMetadataMap.get(imagePath)?.color_grid)
               const transform = thumbnail?.exif_metadata
                 ? getOrientationTransform(thumbnail.exif_metadata.orientation)
                 : ''
@@ -2076,8 +2355,19 @@ export const ThumbnailPanel = memo(function ThumbnailPanel() {
                         loading="lazy"
                       />
                     ) : (
-                      <div className="flex h-full items-center justify-center">
-                        <Loader2 className="h-6 w-6 animate-spin text-gray-600" />
+                      <div
+                        className="relative flex h-full items-center justify-center overflow-hidden"
+                        style={{ backgroundColor: placeholderColors.get(imagePath) }}
+                      >
+                        {/* 이전에 썸네일을 만든 파일은 색 격자를 흐리게 표시 */}
+                        {colorGrid && (
+                          <div className="absolute inset-0 grid scale-110 grid-cols-4 grid-rows-4 blur-md">
+                            {colorGrid.map((color, i) => (
+                              <div key={i} style={{ backgroundColor: color }} />
+                            ))}
+                          </div>
+                        )}
+                        <Loader2 className="relative h-6 w-6 animate-spin text-gray-600" />
                       </div>
                     )}
                     {/* 별점 배지 (우측 상단) */}
@@ -2139,6 +2429,18 @@ export const ThumbnailPanel = memo(function ThumbnailPanel() {
                       : `${Math.round((progress?.completed ?? 0) / (progress?.total ?? 1) * 100)}%`
                     }
                   </span>
+                  {(() => {
+                    const current = isGeneratingHq ? hqProgress : progress
+                    if (!(isGenerating || isGeneratingHq) || current?.eta_seconds == null) return null
+                    return (
+                      <span
+                        className="text-xs text-gray-500 whitespace-nowrap"
+                        title={current.items_per_second ? `${current.items_per_second.toFixed(1)}장/초` : undefined}
+                      >
+                        {formatEta(current.eta_seconds)}
+                      </span>
+                    )
+                  })()}
                 </>
               )}
 
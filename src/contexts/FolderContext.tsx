@@ -10,6 +10,7 @@ export interface LightMetadata {
   modified_time?: string;
   date_taken?: string;
   rating?: number; // XMP 별점 (0-5)
+  color_grid?: string | null; // 자리 표시용 4x4 평균 색 ("rrggbb" 16개, 썸네일을 만든 적 있는 파일만)
 }
 
 interface FolderContextType {
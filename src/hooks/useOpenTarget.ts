@@ -0,0 +1,74 @@
+import { useEffect } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+import { useFolderContext } from "../contexts/FolderContext";
+import { useImageContext } from "../contexts/ImageContext";
+
+interface OpenTarget {
+  folder: string;
+  image: string | null;
+}
+
+interface FolderIndexInfo {
+  folder: string;
+  total: number;
+}
+
+interface ImagePage {
+  offset: number;
+  total: number;
+  paths: string[];
+}
+
+/**
+ * 탐색기/Finder에서 파일·폴더로 실행했거나 두 번째 실행으로 전달된 대상 열기
+ * 리스너 등록 후 frontend_ready를 호출해야 실행 인자로 받은 대상이 전송됨
+ */
+export function useOpenTarget() {
+  const { setFolderImages, loadLightMetadata } = useFolderContext();
+  const { loadImage } = useImageContext();
+
+  useEffect(() => {
+    // 보조 뷰어 윈도우는 열 대상을 처리하지 않음
+    if (getCurrentWindow().label !== "main") {
+      return;
+    }
+
+    let unlisten: (() => void) | undefined;
+    let isMounted = true;
+
+    const openTarget = async ({ folder, image }: OpenTarget) => {
+      try {
+        const info = await invoke<FolderIndexInfo>("open_image_folder", { path: folder });
+        const page = await invoke<ImagePage>("get_image_page", { offset: 0, count: info.total });
+        const totalSize = await invoke<number>("calculate_images_total_size", { paths: page.paths });
+
+        setFolderImages(info.folder, page.paths, totalSize);
+        loadLightMetadata(page.paths, true).catch(err => console.error('Failed to load light metadata:', err));
+
+        if (image) {
+          await loadImage(image, page.paths.indexOf(image));
+        }
+      } catch (error) {
+        console.error("[OpenTarget] Failed to open:", error);
+      }
+    };
+
+    listen<OpenTarget>("open-target", (event) => openTarget(event.payload)).then((fn) => {
+      if (!isMounted) {
+        fn();
+        return;
+      }
+      unlisten = fn;
+      invoke("frontend_ready").catch((error) => {
+        console.error("[OpenTarget] frontend_ready failed:", error);
+      });
+    });
+
+    return () => {
+      isMounted = false;
+      unlisten?.();
+    };
+  }, [setFolderImages, loadLightMetadata, loadImage]);
+}
@@ -1,5 +1,5 @@
 import { describe, it, expect, vi, beforeEach } from 'vitest';
-import { AppError, handleError, logError } from '../errorHandler';
+import { AppError, handleError, logError, getErrorMessage, isBackendError } from '../errorHandler';
 
 describe('errorHandler', () => {
   beforeEach(() => {
@@ -37,6 +37,13 @@ describe('errorHandler', () => {
       expect(result.message).toBe('Test error');
     });
 
+    it('should handle backend error object', () => {
+      const result = handleError({ kind: 'not_found', message: 'Path does not exist: /a' });
+      expect(result).toBeInstanceOf(AppError);
+      expect(result.message).toBe('Path does not exist: /a');
+      expect(result.code).toBe('not_found');
+    });
+
     it('should add context to error message', () => {
       const error = new Error('Test error');
       handleError(error, 'TestContext');
@@ -44,6 +51,15 @@ describe('errorHandler', () => {
     });
   });
 
+  describe('getErrorMessage', () => {
+    it('should extract message from backend error, Error and string', () => {
+      expect(getErrorMessage({ kind: 'io', code: 5, message: 'Access denied' })).toBe('Access denied');
+      expect(getErrorMessage(new Error('Test error'))).toBe('Test error');
+      expect(getErrorMessage('Test error')).toBe('Test error');
+      expect(isBackendError('Test error')).toBe(false);
+    });
+  });
+
   describe('logError', () => {
     it('should log error without throwing', () => {
       const error = new Error('Test error');
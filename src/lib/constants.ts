@@ -10,6 +10,7 @@ export const THUMBNAIL_SIZE_MIN = 75; // 최소 썸네일 크기 (px)
 export const THUMBNAIL_SIZE_MAX = 320; // 최대 썸네일 크기 (px)
 export const THUMBNAIL_SIZE_DEFAULT = 150; // 기본 썸네일 크기 (px)
 export const THUMBNAIL_SIZE_STEP = 25; // 썸네일 크기 조정 단계 (px)
+export const THUMBNAIL_TIER_BASE = 320; // 기본 썸네일 해상도 (px), 셀 크기 × 화면 배율이 더 크면 큰 썸네일로 교체
 
 // 디바운스/쓰로틀 시간 (ms)
 export const DEBOUNCE_FOCUS_INDEX = 150; // 포커스 인덱스 변경 디바운스
@@ -11,6 +11,47 @@ export class AppError extends Error {
   }
 }
 
+// 백엔드 커맨드 에러 종류 (src-tauri/src/error.rs AppError)
+export type BackendErrorKind =
+  | 'not_found'
+  | 'permission_denied'
+  | 'read_only'
+  | 'already_exists'
+  | 'unsupported_format'
+  | 'invalid_input'
+  | 'network_unreachable'
+  | 'auth_failed'
+  | 'unknown_host_key'
+  | 'host_key_changed'
+  | 'cancelled'
+  | 'io'
+  | 'other';
+
+export interface BackendError {
+  kind: BackendErrorKind;
+  message: string;
+  code?: number | null;
+  // unknown_host_key, host_key_changed: 서버 호스트 키 지문 (SHA256:...)
+  fingerprint?: string;
+}
+
+export function isBackendError(error: unknown): error is BackendError {
+  return (
+    typeof error === 'object' &&
+    error !== null &&
+    typeof (error as BackendError).kind === 'string' &&
+    typeof (error as BackendError).message === 'string'
+  );
+}
+
+// 에러 메시지 추출 (백엔드 에러 객체, Error, 문자열 모두 지원)
+export function getErrorMessage(error: unknown): string {
+  if (isBackendError(error) || error instanceof Error) {
+    return error.message;
+  }
+  return String(error);
+}
+
 export function handleError(error: unknown, context?: string): AppError {
   const contextPrefix = context ? `[${context}] ` : '';
 
@@ -19,6 +60,11 @@ export function handleError(error: unknown, context?: string): AppError {
     return error;
   }
 
+  if (isBackendError(error)) {
+    console.error(`${contextPrefix}[${error.kind}] ${error.message}`);
+    return new AppError(error.message, error.kind, error);
+  }
+
   if (error instanceof Error) {
     console.error(`${contextPrefix}${error.message}`, error);
     return new AppError(error.message, 'UNKNOWN_ERROR', error);
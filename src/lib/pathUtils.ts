@@ -56,7 +56,7 @@ export function isImageFile(path: string): boolean {
   const ext = getFileExtension(path);
   const imageExtensions = [
     'jpg', 'jpeg', 'png', 'gif', 'bmp', 'webp', 'tiff', 'tif',
-    'exr', 'avif', 'ico', 'svg',
+    'exr', 'avif', 'ico', 'svg', 'psd', 'psb',
     // RAW formats
     'nef', 'nrw', 'cr2', 'crw', 'arw', 'srf', 'sr2', 'dng', 'raf', 'orf', 'rw2', 'pef'
   ];
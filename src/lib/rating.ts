@@ -18,8 +18,18 @@ export async function readImageRatingsBatch(filePaths: string[]): Promise<Array<
   return await invoke<Array<[string, number | null]>>('read_image_ratings_batch', { filePaths })
 }
 
+/**
+ * 여러 이미지의 별점을 한 번에 조회 (메타데이터 DB 캐시, 바뀐 파일만 다시 읽음)
+ * @param paths 이미지 파일 경로 배열
+ * @returns [경로, 별점] 튜플 배열 (읽을 수 없는 파일은 null)
+ */
+export async function getRatingsBatch(paths: string[]): Promise<Array<[string, number | null]>> {
+  return await invoke<Array<[string, number | null]>>('get_ratings_batch', { paths })
+}
+
 /**
  * XMP Rating 쓰기 (0-5)
+ * 백엔드 쓰기 큐에 예약되고 바로 반환 (완료: rating-changed, 실패/충돌: rating-write-failed 이벤트)
  * @param filePath 이미지 파일 경로
  * @param rating 별점 (0 = unrate, 1-5 = rating)
  */
@@ -29,3 +39,15 @@ export async function writeImageRating(filePath: string, rating: number): Promis
   }
   await invoke<void>('write_image_rating', { filePath, rating })
 }
+
+/** 별점 저장 위치 (xmp: 파일, database: XMP를 쓸 수 없는 형식이라 앱 DB, pending: 읽기 전용이라 쓰기 대기) */
+export type RatingSource = 'xmp' | 'database' | 'pending'
+
+/**
+ * 파일별 별점 저장 위치 조회 (그리드 표시용)
+ * @param paths 이미지 파일 경로 배열
+ * @returns [경로, 저장 위치] 튜플 배열
+ */
+export async function getRatingSources(paths: string[]): Promise<Array<[string, RatingSource]>> {
+  return await invoke<Array<[string, RatingSource]>>('get_rating_sources', { paths })
+}